@@ -0,0 +1,434 @@
+//! Sham macros.
+//! 
+//! This library provides the procedural macros for the Sham library, which are
+//! re-exported by the `sham` crate when its `macros` feature is enabled.
+//! 
+
+
+
+//		Global configuration
+
+//	Customisations of the standard linting configuration
+#![allow(clippy::multiple_crate_versions, reason = "Cannot resolve all these")]
+
+
+
+//		Packages
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+	Error,
+	FnArg,
+	Ident,
+	ItemFn,
+	ItemUse,
+	Path,
+	ReturnType,
+	Token,
+	Type,
+	UseTree,
+	parse::Parser,
+	parse_macro_input,
+	parse_str,
+	punctuated::Punctuated,
+};
+
+//	Used by the documentation examples only
+#[cfg(test)]
+use sham as _;
+
+
+
+//		Constants
+
+/// The genuine types that have mocks, along with the paths of the mocks
+/// beneath the `sham` crate.
+const MOCKS: &[(&str, &str)] = &[
+	("bollard::Docker",                             "docker::MockDocker"),
+	("bollard::container::Config",                  "docker::MockContainerConfig"),
+	("bollard::container::CreateContainerOptions",  "docker::MockCreateContainerOptions"),
+	("bollard::container::LogOutput",               "docker::MockLogOutput"),
+	("bollard::container::RemoveContainerOptions",  "docker::MockRemoveContainerOptions"),
+	("bollard::errors::Error",                      "docker::MockDockerError"),
+	("bollard::image::CreateImageOptions",          "docker::MockCreateImageOptions"),
+	("bollard::models::ContainerCreateResponse",    "docker::MockContainerCreateResponse"),
+	("bollard::models::ContainerWaitResponse",      "docker::MockContainerWaitResponse"),
+	("bollard::models::CreateImageInfo",            "docker::MockCreateImageInfo"),
+	("bollard::models::ProgressDetail",             "docker::MockProgressDetail"),
+	("kube::Api",                                   "kube::MockApi"),
+	("kube::Client",                                "kube::MockKubeClient"),
+	("kube::Error",                                 "kube::MockKubeError"),
+	("kube::api::DeleteParams",                     "kube::MockDeleteParams"),
+	("kube::api::ListParams",                       "kube::MockListParams"),
+	("kube::api::Patch",                            "kube::MockPatch"),
+	("kube::api::PatchParams",                      "kube::MockPatchParams"),
+	("kube::api::PostParams",                       "kube::MockPostParams"),
+	("kube::api::WatchEvent",                       "kube::MockWatchEvent"),
+	("kube::api::WatchParams",                      "kube::MockWatchParams"),
+	("kube::core::ErrorResponse",                   "kube::MockErrorResponse"),
+	("kube::core::ObjectList",                      "kube::MockObjectList"),
+	("native_tls::TlsConnector",                    "tls::MockTls"),
+	("native_tls::TlsStream",                       "tls::MockTlsStream"),
+	("reqwest::Client",                             "reqwest::MockClient"),
+	("reqwest::Error",                              "reqwest::MockError"),
+	("reqwest::RequestBuilder",                     "reqwest::MockRequestBuilder"),
+	("reqwest::Response",                           "reqwest::MockResponse"),
+	("sentry::Breadcrumb",                          "sentry::MockBreadcrumb"),
+	("sentry::Level",                               "sentry::MockLevel"),
+	("sentry::Scope",                               "sentry::MockScope"),
+	("sentry::protocol::Event",                     "sentry::MockSentryEvent"),
+	("sentry::protocol::Exception",                 "sentry::MockSentryException"),
+	("std::os::unix::net::UnixListener",            "unix::MockUnixListener"),
+	("std::os::unix::net::UnixStream",              "unix::MockUnixStream"),
+	("std::process::Command",                       "std_process::FakeCommand"),
+	("std::process::Stdio",                         "std_process::MockStdio"),
+	("tokio::net::UnixListener",                    "unix::MockUnixListener"),
+	("tokio::net::UnixStream",                      "unix::MockUnixStream"),
+	("tokio::net::unix::UCred",                     "unix::MockPeerCred"),
+	("tokio_rustls::TlsConnector",                  "tls::MockTls"),
+	("tokio_rustls::client::TlsStream",             "tls::MockTlsStream"),
+];
+
+
+
+//		Structs
+
+//		Import																	
+/// A single item imported by a `use` declaration.
+struct Import {
+	//		Private properties													
+	/// The name that the item is imported as.
+	alias: Ident,
+	
+	/// The full path of the item, with segments separated by `::`.
+	path:  String,
+	
+	/// The span of the item, for reporting errors.
+	span:  Span,
+}
+
+//		TestOptions																
+/// The arguments given to the `test` attribute.
+#[derive(Default)]
+struct TestOptions {
+	//		Private properties													
+	/// Whether to write a report of the interactions with the mocks.
+	report: bool,
+	
+	/// The variant of `MockUnusedPolicy` to apply, if any.
+	unused: Option<Ident>,
+}
+
+
+
+//		Macros
+
+//		swap_uses																
+/// Swaps genuine imports for mocks when testing.
+/// 
+/// This attribute is applied to a `use` declaration of genuine types, and
+/// expands it into two declarations: the original one, which applies when not
+/// testing, and one that imports the corresponding mocks from the `sham` crate
+/// under the same names, which applies when testing. Renamed imports keep
+/// their names, and nested groups are supported.
+/// 
+/// If Sham is only a development dependency, the attribute can be applied
+/// using `#[cfg_attr(test, sham::swap_uses)]`, which leaves the declaration
+/// untouched when not testing.
+/// 
+/// # Errors
+/// 
+/// A compile error will be produced for a glob import, or for an item that
+/// has no mock.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// #[sham::swap_uses]
+/// use std::process::{Command, Stdio as ChildIo};
+/// 
+/// // Equivalent to:
+/// //
+/// // #[cfg(not(test))]
+/// // use std::process::{Command, Stdio as ChildIo};
+/// // #[cfg(test)]
+/// // use sham::std_process::FakeCommand as Command;
+/// // #[cfg(test)]
+/// // use sham::std_process::MockStdio as ChildIo;
+/// ```
+/// 
+#[proc_macro_attribute]
+pub fn swap_uses(args: TokenStream, item: TokenStream) -> TokenStream {
+	let arguments = TokenStream2::from(args);
+	if !arguments.is_empty() {
+		return Error::new_spanned(arguments, "swap_uses does not take any arguments").to_compile_error().into();
+	}
+	let declaration = parse_macro_input!(item as ItemUse);
+	expand_swap_uses(&declaration).unwrap_or_else(Error::into_compile_error).into()
+}
+
+//		test																	
+/// Sets up and verifies the mocks for a test.
+/// 
+/// This attribute is applied to a test function in place of `#[test]`. It
+/// creates a new `sham::Context` for the test, enters it on the current thread,
+/// passes it to the function if the function takes an argument, runs the test,
+/// and then verifies the context, panicking with a list of every failure if any
+/// of the registered checks failed. The checks include the expectations of the
+/// mocks created during the test, such as the clients from
+/// `sham::reqwest::create_mock_client()` and the commands from
+/// `sham::std_process::FakeCommand::new()`, which register themselves with the
+/// entered context. The argument can be either a `Context` or a `&Context`, and
+/// `sham::verify_all()` can be used to verify the context early without being
+/// given it.
+/// 
+/// Asynchronous test functions are run to completion on a current-thread Tokio
+/// runtime, in the same way as `#[tokio::test]`, when the `tokio` feature of
+/// the `sham` crate is enabled, and otherwise on a simple executor that does
+/// not depend upon any runtime, so that `async-std` and `smol` can be used.
+/// Other attributes, such as `#[should_panic]` and `#[ignore]`, can be used
+/// alongside this one, and test functions that return a `Result` are supported.
+/// 
+/// If the `report` argument is given, as `#[sham::test(report)]`, a report of
+/// the interactions with the mocks is written to `target/sham-reports/` once
+/// the test has finished, whether it passed or failed, named after the path of
+/// the test. This requires the `report` feature of the `sham` crate.
+/// 
+/// If the `deny_unused` or `warn_unused` argument is given, mocks and fixtures
+/// that are set up but never used, such as routes added to the context, are
+/// reported as a failure or as a warning when the test finishes. See
+/// `sham::MockUnusedPolicy`. Arguments can be combined, as in
+/// `#[sham::test(report, deny_unused)]`.
+/// 
+/// # Errors
+/// 
+/// A compile error will be produced for a function that takes more than one
+/// argument, takes `self`, or is generic, or for any argument to the attribute
+/// other than `report`, `deny_unused`, and `warn_unused`, or for both of the
+/// last two.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use sham::Context;
+/// 
+/// #[sham::test]
+/// fn checks_run_at_the_end(context: &Context) {
+///     context.register("cache warmed", || Ok(()));
+/// }
+/// 
+/// #[sham::test]
+/// async fn async_tests_run_to_completion(context: Context) {
+///     context.register("token refreshed", || Ok(()));
+/// }
+/// ```
+/// 
+#[proc_macro_attribute]
+pub fn test(args: TokenStream, item: TokenStream) -> TokenStream {
+	let options  = match parse_test_options(TokenStream2::from(args)) {
+		Ok(options) => options,
+		Err(error)  => return error.into_compile_error().into(),
+	};
+	let function = parse_macro_input!(item as ItemFn);
+	expand_test(&function, &options).unwrap_or_else(Error::into_compile_error).into()
+}
+
+
+
+//		Functions
+
+//		collect_imports															
+/// Collects the items imported by a `use` tree.
+/// 
+/// # Errors
+/// 
+/// An error will be returned for glob imports, and for imports of `self`.
+/// 
+fn collect_imports(tree: &UseTree, prefix: &mut Vec<String>, imports: &mut Vec<Import>) -> syn::Result<()> {
+	match *tree {
+		UseTree::Path(ref path)     => {
+			prefix.push(path.ident.to_string());
+			collect_imports(&path.tree, prefix, imports)?;
+			drop(prefix.pop());
+		},
+		UseTree::Name(ref name)     => {
+			if name.ident == "self" {
+				return Err(Error::new_spanned(name, "swap_uses cannot swap modules, only the items in them"));
+			}
+			imports.push(Import {
+				alias: name.ident.clone(),
+				path:  full_path(prefix, &name.ident),
+				span:  name.ident.span(),
+			});
+		},
+		UseTree::Rename(ref rename) => imports.push(Import {
+			alias: rename.rename.clone(),
+			path:  full_path(prefix, &rename.ident),
+			span:  rename.ident.span(),
+		}),
+		UseTree::Glob(ref glob)     => {
+			return Err(Error::new_spanned(glob, "swap_uses cannot swap glob imports, as the mocks have different names"));
+		},
+		UseTree::Group(ref group)   => {
+			for item in &group.items {
+				collect_imports(item, prefix, imports)?;
+			}
+		},
+	}
+	Ok(())
+}
+
+//		expand_swap_uses														
+/// Expands a `use` declaration into the genuine and mocked declarations.
+/// 
+/// # Errors
+/// 
+/// An error will be returned if any of the imported items cannot be swapped.
+/// 
+fn expand_swap_uses(declaration: &ItemUse) -> syn::Result<TokenStream2> {
+	let mut imports = Vec::new();
+	collect_imports(&declaration.tree, &mut Vec::new(), &mut imports)?;
+	let attrs       = &declaration.attrs;
+	let vis         = &declaration.vis;
+	let mocks       = imports.iter().map(|import| {
+		let mock = MOCKS.iter()
+			.find(|&&(genuine, _)| genuine == import.path)
+			.map(|&(_, mock)| mock)
+			.ok_or_else(|| Error::new(import.span, format!("There is no Sham mock for `{}`", import.path)))?
+		;
+		let path: Path = parse_str(&format!("::sham::{mock}"))?;
+		let alias      = &import.alias;
+		Ok(quote! {
+			#[cfg(test)]
+			#(#attrs)*
+			#vis use #path as #alias;
+		})
+	}).collect::<syn::Result<Vec<_>>>()?;
+	Ok(quote! {
+		#[cfg(not(test))]
+		#declaration
+		#(#mocks)*
+	})
+}
+
+//		expand_test																
+/// Expands a test function into one that sets up and verifies a context.
+/// 
+/// The original function is kept inside the generated one, under the same
+/// name, so that it is called with the context and its return value is passed
+/// back once the context has been verified.
+/// 
+/// # Parameters
+/// 
+/// * `function` - The test function.
+/// * `options`  - The arguments given to the attribute.
+/// 
+/// # Errors
+/// 
+/// An error will be returned if the function has an unsupported signature.
+/// 
+fn expand_test(function: &ItemFn, options: &TestOptions) -> syn::Result<TokenStream2> {
+	let sig = &function.sig;
+	if !sig.generics.params.is_empty() {
+		return Err(Error::new_spanned(&sig.generics, "sham::test functions cannot be generic"));
+	}
+	if sig.inputs.len() > 1 {
+		return Err(Error::new_spanned(&sig.inputs, "sham::test functions take at most one argument, which is the Context"));
+	}
+	let argument = match sig.inputs.first().map(|input| match *input {
+		FnArg::Receiver(ref receiver) => Err(receiver),
+		FnArg::Typed(ref typed)       => Ok(matches!(*typed.ty, Type::Reference(_))),
+	}) {
+		None              => quote! {},
+		Some(Err(receiver)) => return Err(Error::new_spanned(receiver, "sham::test functions cannot take self")),
+		Some(Ok(true))    => quote! { &context },
+		Some(Ok(false))   => quote! { ::core::clone::Clone::clone(&context) },
+	};
+	let name   = &sig.ident;
+	let call   = if sig.asyncness.is_some() {
+		quote! { ::sham::context::block_on(#name(#argument)) }
+	} else {
+		quote! { #name(#argument) }
+	};
+	let attrs  = &function.attrs;
+	let vis    = &function.vis;
+	let output = &sig.output;
+	let block  = &function.block;
+	let enable = if options.report {
+		quote! { context.enable_report(::core::concat!(::core::module_path!(), "::", ::core::stringify!(#name))); }
+	} else {
+		quote! {}
+	};
+	let unused = options.unused.as_ref().map_or_else(
+		|| quote! {},
+		|policy| quote! { context.set_unused_policy(::sham::MockUnusedPolicy::#policy); },
+	);
+	let run    = if matches!(*output, ReturnType::Default) {
+		quote! {
+			#call;
+			drop(guard);
+		}
+	} else {
+		quote! {
+			let result = #call;
+			drop(guard);
+			result
+		}
+	};
+	Ok(quote! {
+		#(#attrs)*
+		#[::core::prelude::v1::test]
+		#vis fn #name() #output {
+			#sig #block
+			let context = ::sham::Context::new();
+			#enable
+			#unused
+			let guard   = context.enter();
+			#run
+		}
+	})
+}
+
+//		full_path																
+/// Joins a path prefix and a name into a full path.
+fn full_path(prefix: &[String], name: &Ident) -> String {
+	let mut segments = prefix.to_vec();
+	segments.push(name.to_string());
+	segments.join("::")
+}
+
+//		parse_test_options														
+/// Parses the arguments given to the `test` attribute.
+/// 
+/// # Parameters
+/// 
+/// * `args` - The arguments, separated by commas.
+/// 
+/// # Errors
+/// 
+/// An error will be returned for an unknown argument, or if both
+/// `deny_unused` and `warn_unused` are given.
+/// 
+fn parse_test_options(args: TokenStream2) -> syn::Result<TestOptions> {
+	let mut options = TestOptions::default();
+	for ident in Punctuated::<Ident, Token![,]>::parse_terminated.parse2(args)? {
+		let policy = match ident.to_string().as_str() {
+			"report"      => {
+				options.report = true;
+				continue;
+			},
+			"deny_unused" => Ident::new("Fail", ident.span()),
+			"warn_unused" => Ident::new("Warn", ident.span()),
+			_             => return Err(Error::new_spanned(
+				ident,
+				"sham::test only takes the report, deny_unused, and warn_unused arguments",
+			)),
+		};
+		if options.unused.replace(policy).is_some() {
+			return Err(Error::new_spanned(ident, "sham::test takes only one of deny_unused and warn_unused"));
+		}
+	}
+	Ok(options)
+}
@@ -0,0 +1,34 @@
+//! Tests for the `test` attribute macro.
+
+
+
+//		Global configuration
+
+//	Lints specifically disabled for tests
+#![allow(unused_crate_dependencies, reason = "The dependencies of the macros are not used by the tests")]
+
+
+
+//		Tests
+
+#[cfg(test)]
+mod mock_client {
+	use sham::reqwest::{MockError, create_mock_client};
+	
+	//		expectations_met													
+	/// A test in which every request expected by a mock client is made passes.
+	#[sham::test]
+	async fn expectations_met() {
+		let client = create_mock_client(vec![("https://api.example.com/users", Err(MockError::default()))]);
+		assert!(client.get("https://api.example.com/users").send().await.is_err(), "The mocked error should be returned");
+	}
+	
+	//		expectations_unmet													
+	/// A test in which a request expected by a mock client is never made fails
+	/// when the context is verified, rather than when the client is dropped.
+	#[sham::test]
+	#[should_panic(expected = "1. create_mock_client(): create_mock_client() expected 1 GET request(s), but 1 of them were never made")]
+	fn expectations_unmet() {
+		let _client = create_mock_client(vec![("https://api.example.com/users", Err(MockError::default()))]);
+	}
+}
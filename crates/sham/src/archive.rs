@@ -0,0 +1,504 @@
+//! This module provides archive fixtures in order to test extraction code.
+//! 
+//! This module provides [`MockArchive`], which builds tar, gzipped tar, and zip
+//! archives in memory from a list of entries, along with [`gzip()`] for single
+//! files, and can break them in the ways listed by [`MockArchiveDefect`], such
+//! as by truncating them or corrupting their checksums. This is important
+//! because code that extracts and validates archives, such as installers and
+//! backup tools, has to cope with downloads that were cut off, with data that
+//! was corrupted in transit, and with malicious entries, such as the "zip slip"
+//! paths that try to escape the directory being extracted to, and committing
+//! binary fixtures for each of these cases is awkward to review and maintain.
+//! 
+//! The approach taken is that the archives are written byte by byte, without
+//! any compression, so that no further dependencies are needed, and the output
+//! is the same on every run. The tar archives use the `ustar` format, the zip
+//! archives use the stored method, and the gzip streams use stored deflate
+//! blocks, all of which are valid, and are read by any standard tool or crate.
+//! Entry paths are written exactly as given, without normalisation, so that
+//! paths such as `../../etc/passwd` and `/etc/passwd` can be used to test the
+//! handling of traversal. The bytes can then be returned by the mocks in the
+//! same way as any other body, such as with
+//! [`create_mock_response()`](crate::reqwest::create_mock_response()).
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use sham::archive::{MockArchive, MockArchiveDefect, gzip};
+//! 
+//! let archive = MockArchive::new()
+//!     .with_directory("app/")
+//!     .with_file("app/config.toml", b"port = 8080\n")
+//!     .with_symlink("app/current", "config.toml")
+//!     .with_file("../../etc/cron.d/evil", b"* * * * * root rm -rf /\n")
+//! ;
+//! 
+//! let zip = archive.zip();
+//! assert!(zip.starts_with(b"PK\x03\x04"));
+//! assert!(zip.windows(6).any(|bytes| bytes == b"../../"));
+//! 
+//! let tar = archive.tar();
+//! assert_eq!(&tar[257..263], b"ustar\0");
+//! assert_eq!(tar.len() % 512, 0);
+//! 
+//! let truncated = archive.clone().with_defect(MockArchiveDefect::Truncated).tar_gz();
+//! assert!(truncated.len() < archive.tar_gz().len());
+//! 
+//! assert!(gzip(b"hello", None).starts_with(&[0x1F, 0x8B]));
+//! ```
+//! 
+
+
+
+//		Packages
+
+use core::fmt::{Display, Formatter, self};
+
+
+
+//		Constants
+
+/// The size of a block of a tar archive.
+const TAR_BLOCK: usize = 512;
+
+/// The largest number of bytes in a stored deflate block.
+const STORED_BLOCK: usize = 0xFFFF;
+
+/// The date of every zip entry, which is the 1st of January 1980, the earliest
+/// date that can be stored.
+const ZIP_DATE: u16 = 0x0021;
+
+/// The version of the zip format needed to extract a stored entry.
+const ZIP_VERSION: u16 = 10;
+
+/// The version of the zip format that made the archive, with Unix in the high
+/// byte, so that the permissions of the entries are read.
+const ZIP_MADE_BY: u16 = 0x031E;
+
+
+
+//		Enums
+
+//		MockArchiveDefect														
+/// A way in which an archive can be broken.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum MockArchiveDefect {
+	/// The checksums are wrong: the header checksums of a tar archive, the
+	/// CRC-32 of each zip entry, or the CRC-32 of a gzip stream.
+	CorruptChecksum,
+	
+	/// The archive is cut off half-way through, as a failed download would be.
+	Truncated,
+}
+
+//󰭅		MockArchiveDefect														
+impl MockArchiveDefect {
+	//		all																	
+	/// Returns all of the kinds of defect.
+	#[must_use]
+	pub const fn all() -> [Self; 2] {
+		[
+			Self::CorruptChecksum,
+			Self::Truncated,
+		]
+	}
+}
+
+//󰭅		Display																	
+impl Display for MockArchiveDefect {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let description = match *self {
+			Self::CorruptChecksum => "corrupt checksum",
+			Self::Truncated       => "truncated",
+		};
+		write!(f, "{description}")
+	}
+}
+
+//		MockArchiveEntryKind													
+/// The kind of an entry in an archive.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum MockArchiveEntryKind {
+	/// A directory.
+	Directory,
+	
+	/// A regular file.
+	File,
+	
+	/// A symbolic link to the given target.
+	Symlink(String),
+}
+
+
+
+//		Structs
+
+//		MockArchive																
+/// A list of entries that can be written as an archive.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[must_use]
+pub struct MockArchive {
+	//		Private properties													
+	/// The defect to apply to the archives, if any.
+	defect:  Option<MockArchiveDefect>,
+	
+	/// The entries, in order.
+	entries: Vec<MockArchiveEntry>,
+}
+
+//󰭅		MockArchive																
+impl MockArchive {
+	//		new																	
+	/// Creates a new, empty archive.
+	pub fn new() -> Self {
+		Self::default()
+	}
+	
+	//		with_defect															
+	/// Breaks the archives in the given way.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `defect` - The defect to apply.
+	/// 
+	pub const fn with_defect(mut self, defect: MockArchiveDefect) -> Self {
+		self.defect = Some(defect);
+		self
+	}
+	
+	//		with_directory														
+	/// Adds a directory, with a mode of `0755`.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `path` - The path of the directory. A trailing `/` is added if it is
+	///   missing.
+	/// 
+	pub fn with_directory<P: Into<String>>(self, path: P) -> Self {
+		let mut name = path.into();
+		if !name.ends_with('/') {
+			name.push('/');
+		}
+		self.with_entry(MockArchiveEntry {
+			path:     name,
+			kind:     MockArchiveEntryKind::Directory,
+			contents: Vec::new(),
+			mode:     0o755,
+		})
+	}
+	
+	//		with_entry															
+	/// Adds an entry.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `entry` - The entry to add.
+	/// 
+	pub fn with_entry(mut self, entry: MockArchiveEntry) -> Self {
+		self.entries.push(entry);
+		self
+	}
+	
+	//		with_file															
+	/// Adds a regular file, with a mode of `0644`.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `path`     - The path of the file, which is written exactly as given.
+	/// * `contents` - The contents of the file.
+	/// 
+	pub fn with_file<P: Into<String>>(self, path: P, contents: &[u8]) -> Self {
+		self.with_entry(MockArchiveEntry {
+			path:     path.into(),
+			kind:     MockArchiveEntryKind::File,
+			contents: contents.to_vec(),
+			mode:     0o644,
+		})
+	}
+	
+	//		with_symlink														
+	/// Adds a symbolic link, with a mode of `0777`.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `path`   - The path of the link.
+	/// * `target` - The target of the link, which can point anywhere,
+	///   including outside of the archive.
+	/// 
+	pub fn with_symlink<P: Into<String>, T: Into<String>>(self, path: P, target: T) -> Self {
+		self.with_entry(MockArchiveEntry {
+			path:     path.into(),
+			kind:     MockArchiveEntryKind::Symlink(target.into()),
+			contents: Vec::new(),
+			mode:     0o777,
+		})
+	}
+	
+	//		entries																
+	/// Returns the entries, in order.
+	#[must_use]
+	pub fn entries(&self) -> &[MockArchiveEntry] {
+		&self.entries
+	}
+	
+	//		tar																	
+	/// Writes the entries as a `ustar` archive.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if a path or link target is too long for the
+	/// `ustar` format, which allows paths of up to 255 bytes when they can be
+	/// split at a `/`, and link targets of up to 100 bytes.
+	/// 
+	#[must_use]
+	pub fn tar(&self) -> Vec<u8> {
+		let mut output = Vec::new();
+		for entry in &self.entries {
+			let mut header = tar_header(entry);
+			if self.defect == Some(MockArchiveDefect::CorruptChecksum) {
+				header[148] ^= 0x01;
+			}
+			output.extend_from_slice(&header);
+			output.extend_from_slice(&entry.contents);
+			output.resize(output.len().next_multiple_of(TAR_BLOCK), 0);
+		}
+		output.resize(output.len() + TAR_BLOCK * 2, 0);
+		self.truncate(output)
+	}
+	
+	//		tar_gz																
+	/// Writes the entries as a gzipped `ustar` archive.
+	/// 
+	/// A corrupt checksum is applied to the gzip stream, rather than to the
+	/// archive within it.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic in the same cases as [`tar()`](MockArchive::tar()).
+	/// 
+	#[must_use]
+	pub fn tar_gz(&self) -> Vec<u8> {
+		let tar = Self { defect: None, entries: self.entries.clone() }.tar();
+		gzip(&tar, self.defect)
+	}
+	
+	//		zip																	
+	/// Writes the entries as a zip archive, using the stored method.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the archive is larger than 4 GiB, or has
+	/// more than 65,535 entries, which would need the zip64 format.
+	/// 
+	#[must_use]
+	pub fn zip(&self) -> Vec<u8> {
+		let mut output  = Vec::new();
+		let mut central = Vec::new();
+		for entry in &self.entries {
+			let data         = entry.data();
+			let mut checksum = crc32(data);
+			if self.defect == Some(MockArchiveDefect::CorruptChecksum) {
+				checksum ^= 0xFFFF_FFFF;
+			}
+			let offset = u32::try_from(output.len()).expect("Archive is too large for zip");
+			let size   = u32::try_from(data.len()).expect("Entry is too large for zip");
+			let name   = u16::try_from(entry.path.len()).expect("Path is too long for zip");
+			let mode   = entry.kind.type_bits() | entry.mode;
+			let dos    = if entry.kind == MockArchiveEntryKind::Directory { 0x10 } else { 0 };
+			push_u32s(&mut output, &[0x0403_4B50]);
+			push_u16s(&mut output, &[ZIP_VERSION, 0, 0, 0, ZIP_DATE]);
+			push_u32s(&mut output, &[checksum, size, size]);
+			push_u16s(&mut output, &[name, 0]);
+			output.extend_from_slice(entry.path.as_bytes());
+			output.extend_from_slice(data);
+			push_u32s(&mut central, &[0x0201_4B50]);
+			push_u16s(&mut central, &[ZIP_MADE_BY, ZIP_VERSION, 0, 0, 0, ZIP_DATE]);
+			push_u32s(&mut central, &[checksum, size, size]);
+			push_u16s(&mut central, &[name, 0, 0, 0, 0]);
+			push_u32s(&mut central, &[(mode << 16_u8) | dos, offset]);
+			central.extend_from_slice(entry.path.as_bytes());
+		}
+		let count  = u16::try_from(self.entries.len()).expect("Too many entries for zip");
+		let start  = u32::try_from(output.len()).expect("Archive is too large for zip");
+		let length = u32::try_from(central.len()).expect("Archive is too large for zip");
+		output.extend_from_slice(&central);
+		push_u32s(&mut output, &[0x0605_4B50]);
+		push_u16s(&mut output, &[0, 0, count, count]);
+		push_u32s(&mut output, &[length, start]);
+		push_u16s(&mut output, &[0]);
+		self.truncate(output)
+	}
+	
+	//		truncate															
+	/// Cuts the given archive in half, if it should be truncated.
+	fn truncate(&self, mut output: Vec<u8>) -> Vec<u8> {
+		if self.defect == Some(MockArchiveDefect::Truncated) {
+			output.truncate(output.len().div_euclid(2));
+		}
+		output
+	}
+}
+
+//		MockArchiveEntry														
+/// An entry in a [`MockArchive`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct MockArchiveEntry {
+	//		Public properties													
+	/// The path of the entry, which is written exactly as given.
+	pub path:     String,
+	
+	/// The kind of the entry.
+	pub kind:     MockArchiveEntryKind,
+	
+	/// The contents of the entry, which are empty for directories and links.
+	pub contents: Vec<u8>,
+	
+	/// The Unix permissions of the entry, such as `0o644`.
+	pub mode:     u32,
+}
+
+//󰭅		MockArchiveEntry														
+impl MockArchiveEntry {
+	//		data																
+	/// Returns the data stored for the entry, which for a link is its target.
+	fn data(&self) -> &[u8] {
+		match self.kind {
+			MockArchiveEntryKind::Symlink(ref target) => target.as_bytes(),
+			MockArchiveEntryKind::Directory
+			| MockArchiveEntryKind::File              => &self.contents,
+		}
+	}
+}
+
+//󰭅		MockArchiveEntryKind													
+impl MockArchiveEntryKind {
+	//		type_bits															
+	/// Returns the bits of a Unix mode that give the type of the entry.
+	const fn type_bits(&self) -> u32 {
+		match *self {
+			Self::Directory  => 0o040_000,
+			Self::File       => 0o100_000,
+			Self::Symlink(_) => 0o120_000,
+		}
+	}
+}
+
+
+
+//		Functions
+
+//		crc32																	
+/// Calculates the CRC-32 of the given data, as used by zip and gzip.
+fn crc32(data: &[u8]) -> u32 {
+	!data.iter().fold(0xFFFF_FFFF_u32, |crc, &byte| {
+		(0..8).fold(crc ^ u32::from(byte), |value, _| {
+			if value & 1 == 1 { (value >> 1_u8) ^ 0xEDB8_8320 } else { value >> 1_u8 }
+		})
+	})
+}
+
+//		gzip																	
+/// Compresses the given data as a gzip stream, using stored deflate blocks.
+/// 
+/// # Parameters
+/// 
+/// * `data`   - The data to compress.
+/// * `defect` - The defect to apply to the stream, if any.
+/// 
+#[must_use]
+pub fn gzip(data: &[u8], defect: Option<MockArchiveDefect>) -> Vec<u8> {
+	let mut output = vec![0x1F, 0x8B, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF];
+	let mut blocks = data.chunks(STORED_BLOCK).peekable();
+	if blocks.peek().is_none() {
+		output.extend_from_slice(&[0x01, 0x00, 0x00, 0xFF, 0xFF]);
+	}
+	while let Some(block) = blocks.next() {
+		let length = u16::try_from(block.len()).expect("Block is at most 65,535 bytes");
+		output.push(u8::from(blocks.peek().is_none()));
+		output.extend_from_slice(&length.to_le_bytes());
+		output.extend_from_slice(&(!length).to_le_bytes());
+		output.extend_from_slice(block);
+	}
+	let mut checksum = crc32(data);
+	if defect == Some(MockArchiveDefect::CorruptChecksum) {
+		checksum ^= 0xFFFF_FFFF;
+	}
+	push_u32s(&mut output, &[checksum, u32::try_from(data.len() & 0xFFFF_FFFF).unwrap_or(u32::MAX)]);
+	if defect == Some(MockArchiveDefect::Truncated) {
+		output.truncate(output.len().div_euclid(2));
+	}
+	output
+}
+
+//		tar_header																
+/// Creates the `ustar` header block for an entry.
+fn tar_header(entry: &MockArchiveEntry) -> [u8; TAR_BLOCK] {
+	let mut header         = [0_u8; TAR_BLOCK];
+	let (prefix, name)     = split_tar_path(&entry.path);
+	let (flag, link, size) = match entry.kind {
+		MockArchiveEntryKind::Directory          => (b'5', "", 0),
+		MockArchiveEntryKind::File               => (b'0', "", entry.contents.len()),
+		MockArchiveEntryKind::Symlink(ref target) => (b'2', target.as_str(), 0),
+	};
+	assert!(link.len() <= 100, "Link target is too long for ustar: {link}");
+	header[..name.len()].copy_from_slice(name.as_bytes());
+	write_octal(&mut header[100..108], u64::from(entry.mode));
+	write_octal(&mut header[108..116], 0);
+	write_octal(&mut header[116..124], 0);
+	write_octal(&mut header[124..136], u64::try_from(size).unwrap_or(u64::MAX));
+	write_octal(&mut header[136..148], 0);
+	header[148..156].fill(b' ');
+	header[156] = flag;
+	header[157..157 + link.len()].copy_from_slice(link.as_bytes());
+	header[257..263].copy_from_slice(b"ustar\0");
+	header[263..265].copy_from_slice(b"00");
+	header[345..345 + prefix.len()].copy_from_slice(prefix.as_bytes());
+	let checksum = header.iter().map(|&byte| u64::from(byte)).sum::<u64>();
+	write_octal(&mut header[148..155], checksum);
+	header
+}
+
+//		push_u16s																
+/// Appends the given numbers as little-endian 16-bit values.
+fn push_u16s(output: &mut Vec<u8>, values: &[u16]) {
+	for value in values {
+		output.extend_from_slice(&value.to_le_bytes());
+	}
+}
+
+//		push_u32s																
+/// Appends the given numbers as little-endian 32-bit values.
+fn push_u32s(output: &mut Vec<u8>, values: &[u32]) {
+	for value in values {
+		output.extend_from_slice(&value.to_le_bytes());
+	}
+}
+
+//		split_tar_path															
+/// Splits a path into the prefix and name fields of a `ustar` header.
+fn split_tar_path(path: &str) -> (&str, &str) {
+	if path.len() <= 100 {
+		return ("", path);
+	}
+	path.char_indices()
+		.filter(|&(index, c)| c == '/' && index <= 155 && path.len() - index - 1 <= 100)
+		.map(|(index, _)| {
+			let (prefix, rest) = path.split_at(index);
+			(prefix, rest.strip_prefix('/').unwrap_or(rest))
+		})
+		.next()
+		.unwrap_or_else(|| panic!("Path is too long for ustar: {path}"))
+}
+
+//		write_octal																
+/// Writes a number into a field of a `ustar` header, as zero-padded octal
+/// followed by a null byte.
+fn write_octal(field: &mut [u8], value: u64) {
+	let digits = format!("{value:0width$o}", width = field.len() - 1);
+	field[..digits.len()].copy_from_slice(digits.as_bytes());
+	field[digits.len()] = 0;
+}
@@ -0,0 +1,257 @@
+//! This module provides a token refresh flow in order to test authentication
+//! middleware.
+//! 
+//! This module provides [`MockAuthFlow`], which serves a protected route that
+//! rejects requests with `401 Unauthorized` until a token has been obtained
+//! from its refresh route, after which it accepts the token issued by the most
+//! recent refresh. This is important because the middleware that notices a
+//! `401`, refreshes the token, and retries the request is easy to get subtly
+//! wrong, such as by retrying with the old token, refreshing on every request,
+//! or retrying forever, and testing it otherwise means writing the same
+//! stateful routes by hand for every project.
+//! 
+//! The approach taken is that the flow holds the token that is currently
+//! valid, which its [`refresh()`](MockAuthFlow::refresh()) route replaces each
+//! time it is called, and which its [`protected()`](MockAuthFlow::protected())
+//! routes require as a bearer token before passing the request on to their
+//! own responders. A test therefore only needs to declare the two routes. The
+//! current token can be [expired](MockAuthFlow::expire()) to make the code
+//! under test go round the flow again, and the number of refreshes and
+//! rejections can be checked afterwards.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use futures_executor::block_on;
+//! use reqwest::{Method, StatusCode, header::AUTHORIZATION};
+//! use serde_json::Value as Json;
+//! use sham::{auth::MockAuthFlow, reqwest::{create_mock_response, create_routed_mock_client}};
+//! use std::collections::HashMap;
+//! 
+//! let flow   = MockAuthFlow::new();
+//! let client = create_routed_mock_client(vec![
+//!     flow.protected(Method::GET, "https://api.example.com/me", |request| {
+//!         Ok(create_mock_response(request.url.clone(), StatusCode::OK, None::<&str>, None, HashMap::<String, String>::new(), Ok(b"alice")))
+//!     }),
+//!     flow.refresh(Method::POST, "https://auth.example.com/token"),
+//! ]);
+//! 
+//! block_on(async {
+//!     let rejected = client.get("https://api.example.com/me").send().await.unwrap();
+//!     assert_eq!(rejected.status(), StatusCode::UNAUTHORIZED);
+//! 
+//!     let tokens: Json = client.post("https://auth.example.com/token").send().await.unwrap().json().await.unwrap();
+//!     let token        = tokens["access_token"].as_str().unwrap();
+//! 
+//!     let accepted = client.get("https://api.example.com/me")
+//!         .header(AUTHORIZATION, format!("Bearer {token}"))
+//!         .send().await.unwrap()
+//!     ;
+//!     assert_eq!(accepted.status(), StatusCode::OK);
+//! });
+//! 
+//! assert_eq!(flow.refreshes(), 1);
+//! assert_eq!(flow.rejections(), 1);
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::reqwest::{MockError, MockRequest, MockResponse, MockRoute, create_mock_response};
+use reqwest::{
+	IntoUrl,
+	Method,
+	StatusCode,
+	header::{AUTHORIZATION, WWW_AUTHENTICATE},
+};
+use serde_json::json;
+use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+
+
+//		Structs
+
+//		AuthState																
+/// The state of a [`MockAuthFlow`], which is shared by its clones and routes.
+#[derive(Debug, Default)]
+struct AuthState {
+	//		Private properties													
+	/// The token that the protected routes currently accept, if any.
+	current:  Option<String>,
+	
+	/// The number of tokens issued so far.
+	issued:   usize,
+	
+	/// The number of requests rejected by the protected routes.
+	rejected: usize,
+}
+
+//		MockAuthFlow															
+/// A token refresh flow, which rejects requests to its protected routes until
+/// a token has been obtained from its refresh route.
+/// 
+/// Clones share the same state, so a clone can be used to serve routes while
+/// the original is used to make assertions.
+/// 
+#[derive(Clone, Debug, Default)]
+pub struct MockAuthFlow {
+	//		Private properties													
+	/// The state of the flow, shared between clones.
+	state: Arc<Mutex<AuthState>>,
+}
+
+//󰭅		MockAuthFlow															
+impl MockAuthFlow {
+	//		new																	
+	/// Creates a new flow, with no valid token.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+	
+	//		expire																
+	/// Expires the current token, so that the protected routes reject it, and
+	/// the code under test has to refresh it again.
+	pub fn expire(&self) {
+		self.lock().current = None;
+	}
+	
+	//		lock																
+	/// Locks the state, recovering from poisoning.
+	fn lock(&self) -> MutexGuard<'_, AuthState> {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+	
+	//		protected															
+	/// Creates a route that requires the current token.
+	/// 
+	/// Requests that carry the current token as a bearer token in their
+	/// `Authorization` header are passed on to the given responder. All others
+	/// are rejected with `401 Unauthorized`, along with a `WWW-Authenticate`
+	/// header saying that the token is invalid.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `method`    - The HTTP method to match.
+	/// * `url`       - The URL to match. Any query string is ignored.
+	/// * `responder` - The function that produces the response for an
+	///   authorised request.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the URL is not valid.
+	/// 
+	pub fn protected<U, F>(&self, method: Method, url: U, responder: F) -> MockRoute
+	where
+		U: IntoUrl,
+		F: Fn(&MockRequest) -> Result<MockResponse, MockError> + Send + Sync + 'static,
+	{
+		let flow = self.clone();
+		MockRoute::new(method, url, move |request| {
+			let presented  = request.headers.get(AUTHORIZATION)
+				.and_then(|value| value.to_str().ok())
+				.and_then(|value| value.strip_prefix("Bearer "))
+			;
+			let authorised = {
+				let mut state = flow.lock();
+				let valid     = presented.is_some() && presented == state.current.as_deref();
+				if !valid {
+					state.rejected += 1;
+				}
+				valid
+			};
+			if authorised {
+				responder(request)
+			} else {
+				Ok(unauthorised(request))
+			}
+		})
+	}
+	
+	//		refresh																
+	/// Creates a route that issues a new token each time it is called.
+	/// 
+	/// The new token replaces the previous one, which the protected routes no
+	/// longer accept. The response is a JSON object in the form of an `OAuth2`
+	/// token response, with the token in its `access_token` property.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `method` - The HTTP method to match.
+	/// * `url`    - The URL to match. Any query string is ignored.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the URL is not valid.
+	/// 
+	#[must_use]
+	pub fn refresh<U: IntoUrl>(&self, method: Method, url: U) -> MockRoute {
+		let flow = self.clone();
+		MockRoute::new(method, url, move |request| {
+			let token = {
+				let mut state = flow.lock();
+				state.issued += 1;
+				let issued    = format!("sham-token-{}", state.issued);
+				state.current = Some(issued.clone());
+				issued
+			};
+			let body  = json!({
+				"access_token": token,
+				"token_type":   "Bearer",
+				"expires_in":   3_600,
+			}).to_string();
+			Ok(create_mock_response(
+				request.url.clone(),
+				StatusCode::OK,
+				Some("application/json"),
+				Some(body.len()),
+				HashMap::<String, String>::new(),
+				Ok(body.as_bytes()),
+			))
+		})
+	}
+	
+	//		refreshes															
+	/// The number of times that a token has been issued by the refresh route.
+	#[must_use]
+	pub fn refreshes(&self) -> usize {
+		self.lock().issued
+	}
+	
+	//		rejections															
+	/// The number of requests that the protected routes have rejected.
+	#[must_use]
+	pub fn rejections(&self) -> usize {
+		self.lock().rejected
+	}
+	
+	//		token																
+	/// The token that the protected routes currently accept, if any.
+	#[must_use]
+	pub fn token(&self) -> Option<String> {
+		self.lock().current.clone()
+	}
+}
+
+
+
+//		Functions
+
+//		unauthorised															
+/// Creates a `401 Unauthorized` response to the given request.
+fn unauthorised(request: &MockRequest) -> MockResponse {
+	let body = json!({ "error": "invalid_token" }).to_string();
+	create_mock_response(
+		request.url.clone(),
+		StatusCode::UNAUTHORIZED,
+		Some("application/json"),
+		Some(body.len()),
+		HashMap::from([(WWW_AUTHENTICATE.as_str(), r#"Bearer error="invalid_token""#)]),
+		Ok(body.as_bytes()),
+	)
+}
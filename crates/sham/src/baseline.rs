@@ -0,0 +1,448 @@
+//! This module provides baselines of recorded interactions in order to detect
+//! changes in the external calls made by the code under test.
+//! 
+//! This module provides [`MockBaseline`], which saves the interactions recorded
+//! by a [`Context`] to a file, and compares the interactions of later runs
+//! against it. This is important because a change to the code under test can
+//! quietly alter the calls that it makes to other systems, such as calling an
+//! endpoint twice, or no longer sending an audit event, while every assertion
+//! in the test still passes.
+//! 
+//! The approach taken is that a baseline is a plain text file, with one
+//! interaction per line, so that it can be committed alongside the tests and
+//! reviewed in a diff. The first run of a test writes the baseline, and later
+//! runs compare against it, either in order or ignoring order, and fail with a
+//! report of the interactions that are missing and those that are unexpected.
+//! Setting the `SHAM_UPDATE_BASELINES` environment variable rewrites the
+//! baselines instead of comparing against them, to accept a change.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use sham::{baseline::{MockBaseline, MockBaselineOrder}, context::Context};
+//! 
+//! let path = std::env::temp_dir().join("sham-baseline-example.txt");
+//! drop(std::fs::remove_file(&path));
+//! 
+//! let first = Context::new();
+//! first.record("reqwest", "GET https://api.example.com/users", "200 OK");
+//! first.record("log",     "INFO Fetched users",                "captured");
+//! MockBaseline::register(&first, &path, MockBaselineOrder::Unordered);
+//! first.assert_verified();
+//! 
+//! let second = Context::new();
+//! second.record("log",     "INFO Fetched users",                "captured");
+//! second.record("reqwest", "GET https://api.example.com/users", "200 OK");
+//! second.record("reqwest", "GET https://api.example.com/users", "200 OK");
+//! let error = MockBaseline::check(&second, &path, MockBaselineOrder::Unordered).unwrap_err();
+//! assert!(error.to_string().contains("+ reqwest: GET https://api.example.com/users -> 200 OK"));
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::context::{Context, MockInteraction};
+use core::{
+	error::Error,
+	fmt::{Display, Formatter, Write, self},
+};
+use std::{
+	env,
+	fs,
+	io::Error as IoError,
+	path::{Path, PathBuf},
+};
+
+
+
+//		Constants
+
+/// The first line of every baseline file.
+const HEADER: &str = "# sham interaction baseline: module, action, outcome";
+
+/// The environment variable that causes baselines to be rewritten.
+const UPDATE_VAR: &str = "SHAM_UPDATE_BASELINES";
+
+
+
+//		Enums
+
+//		MockBaselineError														
+/// An error when saving, loading, or comparing against a baseline.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockBaselineError {
+	/// The baseline file could not be read or written.
+	Io(PathBuf, String),
+	
+	/// A line of the baseline file could not be parsed, with its line number.
+	LineParse(String, usize),
+	
+	/// The interactions differed from the baseline.
+	Mismatch(PathBuf, MockBaselineDiff),
+}
+
+//󰭅		Display																	
+impl Display for MockBaselineError {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match *self {
+			Self::Io(ref path, ref message)     => write!(f, "Could not access baseline {}: {message}", path.display()),
+			Self::LineParse(ref line, number)   => write!(f, "Error parsing baseline line {number}: '{line}'"),
+			Self::Mismatch(ref path, ref diff)  => write!(
+				f,
+				"Interactions differ from the baseline at {}:\n{diff}Set {UPDATE_VAR}=1 to accept the new interactions.",
+				path.display(),
+			),
+		}
+	}
+}
+
+//󰭅		Error																	
+impl Error for MockBaselineError {}
+
+//		MockBaselineOrder														
+/// How interactions are compared against a baseline.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockBaselineOrder {
+	/// The interactions must occur in the same order as in the baseline.
+	#[default]
+	Ordered,
+	
+	/// The interactions must occur the same number of times as in the
+	/// baseline, in any order. This suits code that makes calls concurrently.
+	Unordered,
+}
+
+
+
+//		Structs
+
+//		MockBaseline															
+/// A saved set of interactions that later runs are compared against.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockBaseline {
+	//		Public properties													
+	/// The interactions of the baseline, in the order they were recorded.
+	pub interactions: Vec<MockInteraction>,
+}
+
+//󰭅		MockBaseline															
+impl MockBaseline {
+	//		new																	
+	/// Creates a new baseline from the given interactions.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `interactions` - The interactions, in the order they were recorded.
+	/// 
+	#[must_use]
+	pub const fn new(interactions: Vec<MockInteraction>) -> Self {
+		Self { interactions }
+	}
+	
+	//		from_context														
+	/// Creates a new baseline from the interactions recorded by a context so
+	/// far.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `context` - The context to take the interactions from.
+	/// 
+	#[must_use]
+	pub fn from_context(context: &Context) -> Self {
+		Self::new(context.interactions())
+	}
+	
+	//		check																
+	/// Compares the interactions recorded by a context so far against the
+	/// baseline saved at the given path.
+	/// 
+	/// If there is no baseline at the path, or the `SHAM_UPDATE_BASELINES`
+	/// environment variable is set to anything other than an empty string or
+	/// `0`, the baseline is written instead, and the check passes.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `context` - The context to take the interactions from.
+	/// * `file`    - The path of the baseline file.
+	/// * `order`   - How to compare the interactions.
+	/// 
+	/// # Errors
+	/// 
+	/// A [`MockBaselineError::Mismatch`] will be returned if the interactions
+	/// differ from the baseline, and other errors if the baseline cannot be
+	/// read, parsed, or written.
+	/// 
+	pub fn check<P: AsRef<Path>>(context: &Context, file: P, order: MockBaselineOrder) -> Result<(), MockBaselineError> {
+		let path   = file.as_ref();
+		let actual = Self::from_context(context);
+		let update = env::var(UPDATE_VAR).is_ok_and(|value| !value.is_empty() && value != "0");
+		if update || !path.exists() {
+			return actual.save(path);
+		}
+		let diff = Self::load(path)?.diff(&actual.interactions, order);
+		if diff.is_empty() {
+			Ok(())
+		} else {
+			Err(MockBaselineError::Mismatch(path.to_owned(), diff))
+		}
+	}
+	
+	//		diff																
+	/// Compares the given interactions against the baseline.
+	/// 
+	/// When comparing in order, the smallest set of changes that turns the
+	/// baseline into the given interactions is reported, so an interaction that
+	/// has moved is reported as both missing and unexpected.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `actual` - The interactions to compare.
+	/// * `order`  - How to compare the interactions.
+	/// 
+	#[must_use]
+	pub fn diff(&self, actual: &[MockInteraction], order: MockBaselineOrder) -> MockBaselineDiff {
+		match order {
+			MockBaselineOrder::Ordered   => diff_ordered(&self.interactions, actual),
+			MockBaselineOrder::Unordered => diff_unordered(&self.interactions, actual),
+		}
+	}
+	
+	//		load																
+	/// Loads a baseline from a file.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `file` - The path of the baseline file.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the file cannot be read or parsed.
+	/// 
+	pub fn load<P: AsRef<Path>>(file: P) -> Result<Self, MockBaselineError> {
+		let path = file.as_ref();
+		let text = fs::read_to_string(path).map_err(|err| MockBaselineError::Io(path.to_owned(), err.to_string()))?;
+		Self::parse(&text)
+	}
+	
+	//		parse																
+	/// Parses a baseline from the contents of a baseline file.
+	/// 
+	/// Blank lines, and lines starting with `#`, are ignored. Every other line
+	/// holds the module, action, and outcome of an interaction, separated by
+	/// tabs, with tabs, newlines, and backslashes within them escaped.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `text` - The contents of the file.
+	/// 
+	/// # Errors
+	/// 
+	/// A [`MockBaselineError::LineParse`] will be returned if a line does not
+	/// have exactly three fields.
+	/// 
+	pub fn parse(text: &str) -> Result<Self, MockBaselineError> {
+		let mut interactions = Vec::new();
+		for (index, line) in text.lines().enumerate() {
+			if line.trim().is_empty() || line.starts_with('#') {
+				continue;
+			}
+			let fields = line.split('\t').map(unescape).collect::<Vec<_>>();
+			let [ref module, ref action, ref outcome] = *fields.as_slice() else {
+				return Err(MockBaselineError::LineParse(line.to_owned(), index + 1));
+			};
+			interactions.push(MockInteraction {
+				module:  module.clone(),
+				action:  action.clone(),
+				outcome: outcome.clone(),
+			});
+		}
+		Ok(Self::new(interactions))
+	}
+	
+	//		register															
+	/// Registers a check of the interactions against the baseline saved at the
+	/// given path, to be run when the context is verified.
+	/// 
+	/// This compares all of the interactions recorded during the test, as the
+	/// check is run when the test finishes. See [`MockBaseline::check()`] for
+	/// when the baseline is written instead.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `context` - The context to check.
+	/// * `file`    - The path of the baseline file, which is usually relative to
+	///   the package root, such as `tests/baselines/<test>.txt`.
+	/// * `order`   - How to compare the interactions.
+	/// 
+	pub fn register<P: Into<PathBuf>>(context: &Context, file: P, order: MockBaselineOrder) {
+		let path  = file.into();
+		let owned = context.clone();
+		context.register(format!("baseline {}", path.display()), move || {
+			Self::check(&owned, &path, order).map_err(|err| err.to_string())
+		});
+	}
+	
+	//		save																
+	/// Saves the baseline to a file, creating its directory if necessary.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `file` - The path of the baseline file.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the file cannot be written.
+	/// 
+	pub fn save<P: AsRef<Path>>(&self, file: P) -> Result<(), MockBaselineError> {
+		let path    = file.as_ref();
+		let failure = |err: IoError| MockBaselineError::Io(path.to_owned(), err.to_string());
+		if let Some(directory) = path.parent().filter(|directory| !directory.as_os_str().is_empty()) {
+			fs::create_dir_all(directory).map_err(failure)?;
+		}
+		fs::write(path, self.to_string()).map_err(failure)
+	}
+}
+
+//󰭅		Display																	
+impl Display for MockBaseline {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		writeln!(f, "{HEADER}")?;
+		for interaction in &self.interactions {
+			writeln!(
+				f,
+				"{}\t{}\t{}",
+				escape(&interaction.module),
+				escape(&interaction.action),
+				escape(&interaction.outcome),
+			)?;
+		}
+		Ok(())
+	}
+}
+
+//		MockBaselineDiff														
+/// The differences between a baseline and the interactions of a run.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockBaselineDiff {
+	//		Public properties													
+	/// The interactions in the baseline that did not occur.
+	pub missing:    Vec<MockInteraction>,
+	
+	/// The interactions that occurred but are not in the baseline.
+	pub unexpected: Vec<MockInteraction>,
+}
+
+//󰭅		MockBaselineDiff														
+impl MockBaselineDiff {
+	//		is_empty															
+	/// Whether the interactions matched the baseline.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.missing.is_empty() && self.unexpected.is_empty()
+	}
+}
+
+//󰭅		Display																	
+impl Display for MockBaselineDiff {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let mut output = String::new();
+		for (sign, interactions) in [('-', &self.missing), ('+', &self.unexpected)] {
+			for interaction in interactions {
+				_ = writeln!(output, "  {sign} {}: {} -> {}", interaction.module, interaction.action, interaction.outcome);
+			}
+		}
+		if output.is_empty() {
+			output.push_str("  (no differences)\n");
+		}
+		f.write_str(&output)
+	}
+}
+
+
+
+//		Functions
+
+//		diff_ordered															
+/// Finds the interactions to remove from and add to the expected ones to
+/// produce the actual ones, using their longest common subsequence.
+fn diff_ordered(expected: &[MockInteraction], actual: &[MockInteraction]) -> MockBaselineDiff {
+	let mut lengths = vec![vec![0_usize; actual.len() + 1]; expected.len() + 1];
+	for (i, left) in expected.iter().enumerate().rev() {
+		for (j, right) in actual.iter().enumerate().rev() {
+			lengths[i][j] = if left == right {
+				lengths[i + 1][j + 1] + 1
+			} else {
+				lengths[i + 1][j].max(lengths[i][j + 1])
+			};
+		}
+	}
+	let mut diff   = MockBaselineDiff::default();
+	let (mut i, mut j) = (0, 0);
+	while i < expected.len() && j < actual.len() {
+		if expected[i] == actual[j] {
+			i += 1;
+			j += 1;
+		} else if lengths[i + 1][j] >= lengths[i][j + 1] {
+			diff.missing.push(expected[i].clone());
+			i += 1;
+		} else {
+			diff.unexpected.push(actual[j].clone());
+			j += 1;
+		}
+	}
+	diff.missing.extend_from_slice(&expected[i..]);
+	diff.unexpected.extend_from_slice(&actual[j..]);
+	diff
+}
+
+//		diff_unordered															
+/// Finds the interactions that occur more often in the expected ones than in
+/// the actual ones, and the reverse, ignoring order.
+fn diff_unordered(expected: &[MockInteraction], actual: &[MockInteraction]) -> MockBaselineDiff {
+	let mut remaining = actual.iter().map(Some).collect::<Vec<_>>();
+	let mut diff      = MockBaselineDiff::default();
+	for interaction in expected {
+		match remaining.iter_mut().find(|slot| slot.is_some_and(|other| other == interaction)) {
+			Some(slot) => *slot = None,
+			None       => diff.missing.push(interaction.clone()),
+		}
+	}
+	diff.unexpected = remaining.into_iter().flatten().cloned().collect();
+	diff
+}
+
+//		escape																	
+/// Escapes the backslashes, tabs, and line breaks in a field.
+fn escape(field: &str) -> String {
+	field.replace('\\', r"\\").replace('\t', r"\t").replace('\n', r"\n").replace('\r', r"\r")
+}
+
+//		unescape																
+/// Reverses [`escape()`].
+fn unescape(field: &str) -> String {
+	let mut output = String::with_capacity(field.len());
+	let mut chars  = field.chars();
+	while let Some(c) = chars.next() {
+		if c != '\\' {
+			output.push(c);
+			continue;
+		}
+		match chars.next() {
+			Some('t')   => output.push('\t'),
+			Some('n')   => output.push('\n'),
+			Some('r')   => output.push('\r'),
+			Some(other) => output.push(other),
+			None        => output.push('\\'),
+		}
+	}
+	output
+}
@@ -0,0 +1,402 @@
+//! This module provides reusable BDD steps in order to drive the mocks from
+//! Gherkin.
+//! 
+//! This module provides [`MockSteps`], which understands a set of common step
+//! phrases, such as "Given the API returns 503 for POST /orders", "Then GET
+//! /health was requested twice", and "Then the command `git push` was run", and
+//! applies them to a [`Context`]. This is important because behaviour-driven
+//! test suites, such as those written with
+//! [`cucumber`](https://crates.io/crates/cucumber), otherwise need their own
+//! glue code in every project to turn each phrase into calls to the mocks.
+//! 
+//! The approach taken is that the steps are plain text, so that they can be
+//! passed on from the step definitions of any BDD framework, or run directly
+//! from a list. The `Given` steps add routes to the context, so they need to
+//! run before the client is created with [`Context::client()`], and the `Then`
+//! steps check the interactions that the context has recorded, so the context
+//! needs to be entered on the thread that sends the requests or runs the
+//! commands. Commands are recorded when they are run using the `FakeCommand` of
+//! the `std_process` feature, or by any other mock that records them with the
+//! module name `std_process`. The `Given`, `When`, `Then`, `And`, and `But`
+//! keywords are optional, and paths are resolved against a base URL. The steps
+//! understood are:
+//! 
+//!   - `the API returns <status> for <METHOD> <path>`
+//!   - ``the API returns <status> with body `<body>` for <METHOD> <path>``
+//!   - `the API is unreachable for <METHOD> <path>`
+//!   - `the API times out for <METHOD> <path>`
+//!   - `<n> seconds|minutes|hours pass`, which advances the virtual clock when
+//!     the `clock` feature is enabled
+//!   - `<METHOD> <path> was requested`, optionally followed by `once`, `twice`,
+//!     or `<n> times`
+//!   - `<METHOD> <path> was not requested`
+//!   - `no requests were sent`
+//!   - ``the command `<command>` was run``, optionally followed by `once`,
+//!     `twice`, or `<n> times`
+//!   - ``the command `<command>` was not run``
+//! 
+//! The steps do not depend upon any particular BDD framework. With
+//! [`cucumber`](https://crates.io/crates/cucumber), a `MockSteps` can be kept
+//! in the world, and a single catch-all step definition can pass each step on
+//! to it, after any steps specific to the project have had the chance to match:
+//! 
+//! ```rust,ignore
+//! use cucumber::{World, given, then, when};
+//! use sham::{Context, bdd::MockSteps};
+//! 
+//! #[derive(Debug, World)]
+//! #[world(init = Self::new)]
+//! struct ApiWorld {
+//!     context: Context,
+//!     steps:   MockSteps,
+//! }
+//! 
+//! impl ApiWorld {
+//!     fn new() -> Self {
+//!         let context = Context::new();
+//!         let steps   = MockSteps::new(&context, "https://api.example.com");
+//!         Self { context, steps }
+//!     }
+//! }
+//! 
+//! #[given(regex = r"^(.+)$")]
+//! #[when(regex = r"^(.+)$")]
+//! #[then(regex = r"^(.+)$")]
+//! fn mock_step(world: &mut ApiWorld, step: String) {
+//!     world.steps.run(&step).unwrap();
+//! }
+//! ```
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use futures_executor::block_on;
+//! use sham::{Context, bdd::MockSteps};
+//! 
+//! let context = Context::new();
+//! let steps   = MockSteps::new(&context, "https://api.example.com");
+//! steps.run("Given the API returns 503 for POST /orders").unwrap();
+//! steps.run("And the API returns 200 with body `{\"status\":\"ok\"}` for GET /health").unwrap();
+//! 
+//! let guard   = context.enter();
+//! let client  = context.client();
+//! assert_eq!(block_on(client.post("https://api.example.com/orders").send()).unwrap().status, 503);
+//! assert_eq!(block_on(block_on(client.get("https://api.example.com/health").send()).unwrap().text()).unwrap(), r#"{"status":"ok"}"#);
+//! 
+//! steps.run("Then POST /orders was requested once").unwrap();
+//! steps.run("But DELETE /orders was not requested").unwrap();
+//! assert!(steps.run("Then GET /health was requested twice").is_err());
+//! drop(guard);
+//! ```
+//! 
+//! Checking the commands that were run:
+//! 
+//! ```rust
+//! use sham::{Context, bdd::MockSteps};
+//! 
+//! let context = Context::new();
+//! let steps   = MockSteps::new(&context, "https://api.example.com");
+//! context.record("std_process", "git push", "Success (os error 0)");
+//! 
+//! steps.run("Then the command `git push` was run once").unwrap();
+//! steps.run("But the command `git pull` was not run").unwrap();
+//! assert!(steps.run("Then the command `git push` was run twice").is_err());
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::{
+	context::Context,
+	reqwest::{MockError, MockResponse, MockRoute, create_mock_response},
+};
+use core::{
+	error::Error,
+	fmt::{Display, Formatter, self},
+};
+use reqwest::{IntoUrl, Method, StatusCode, Url};
+use std::collections::HashMap;
+
+#[cfg(feature = "clock")]
+use core::time::Duration;
+
+
+
+//		Constants
+
+/// The keywords that can begin a step.
+const KEYWORDS: [&str; 5] = ["Given ", "When ", "Then ", "And ", "But "];
+
+
+
+//		Enums
+
+//		MockStepError															
+/// An error from running a step.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockStepError {
+	/// The step was understood, but its expectation was not met. The step and
+	/// the reason are given.
+	Failed(String, String),
+	
+	/// The step was understood, but is not valid, such as having an unknown
+	/// status code. The step and the reason are given.
+	Invalid(String, String),
+	
+	/// The step is not one that is understood.
+	Unrecognised(String),
+}
+
+//󰭅		Display																	
+impl Display for MockStepError {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match *self {
+			Self::Failed(ref step, ref reason)  => write!(f, "Step failed: \"{step}\": {reason}"),
+			Self::Invalid(ref step, ref reason) => write!(f, "Invalid step: \"{step}\": {reason}"),
+			Self::Unrecognised(ref step)        => write!(f, "Unrecognised step: \"{step}\""),
+		}
+	}
+}
+
+//󰭅		Error																	
+impl Error for MockStepError {}
+
+
+
+//		Structs
+
+//		MockSteps																
+/// A runner of common BDD steps against a [`Context`].
+#[derive(Clone, Debug)]
+pub struct MockSteps {
+	//		Private properties													
+	/// The URL that paths are resolved against.
+	base:    Url,
+	
+	/// The context that the steps are applied to.
+	context: Context,
+}
+
+//󰭅		MockSteps																
+impl MockSteps {
+	//		new																	
+	/// Creates a new runner of steps.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `context` - The context to apply the steps to.
+	/// * `base`    - The URL that the paths in the steps are resolved against.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the base URL is not valid.
+	/// 
+	pub fn new<U: IntoUrl>(context: &Context, base: U) -> Self {
+		Self {
+			base:    base.into_url().expect("Invalid base URL"),
+			context: context.clone(),
+		}
+	}
+	
+	//		run																	
+	/// Runs a single step.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `step` - The text of the step, with or without its keyword.
+	/// 
+	/// # Errors
+	/// 
+	/// A [`MockStepError`] will be returned if the step is not understood, is
+	/// not valid, or if its expectation was not met.
+	/// 
+	pub fn run(&self, step: &str) -> Result<(), MockStepError> {
+		let text = KEYWORDS.iter().find_map(|keyword| step.trim().strip_prefix(keyword)).unwrap_or(step).trim();
+		if let Some(rest) = text.strip_prefix("the API ") {
+			return self.given_api(text, rest);
+		}
+		if let Some(rest) = text.strip_prefix("the command ") {
+			return self.then_command(text, rest);
+		}
+		if text == "no requests were sent" {
+			return self.then_requested(text, None, 0);
+		}
+		if let Some(target) = text.strip_suffix(" was not requested") {
+			return self.then_requested(text, Some(target), 0);
+		}
+		if let Some((target, count)) = text.split_once(" was requested") {
+			return parse_times(text, count)?.map_or_else(
+				|| self.then_requested_at_least_once(text, target),
+				|expected| self.then_requested(text, Some(target), expected),
+			);
+		}
+		#[cfg(feature = "clock")]
+		if let Some(duration) = parse_duration(text) {
+			self.context.clock().advance(duration);
+			return Ok(());
+		}
+		Err(MockStepError::Unrecognised(text.to_owned()))
+	}
+	
+	//		given_api															
+	/// Adds a route for a step that begins with "the API".
+	fn given_api(&self, text: &str, rest: &str) -> Result<(), MockStepError> {
+		let invalid           = |reason: &str| MockStepError::Invalid(text.to_owned(), reason.to_owned());
+		let (outcome, target) = rest.rsplit_once(" for ").ok_or_else(|| invalid("missing \"for <METHOD> <path>\""))?;
+		let (method, url)     = self.parse_target(text, target)?;
+		let result            = match outcome {
+			"is unreachable" => Err(MockError::builder().connect().url(url.clone()).build()),
+			"times out"      => Err(MockError::builder().timeout().url(url.clone()).build()),
+			_                => {
+				let returns      = outcome.strip_prefix("returns ").ok_or_else(|| invalid("unknown outcome"))?;
+				let (code, body)   = match returns.split_once(" with body ") {
+					Some((code, quoted)) => (code, quoted.strip_prefix('`').and_then(|body| body.strip_suffix('`'))
+						.ok_or_else(|| invalid("the body must be quoted with backticks"))?
+					),
+					None                 => (returns, ""),
+				};
+				let status       = code.parse::<u16>().ok().and_then(|number| StatusCode::from_u16(number).ok())
+					.ok_or_else(|| invalid("invalid status code"))?
+				;
+				Ok(create_mock_response(url.clone(), status, None::<&str>, None, HashMap::<String, String>::new(), Ok(body.as_bytes())))
+			},
+		};
+		self.context.add_route(MockRoute::new(method, url, move |request| respond(&result, &request.url)));
+		Ok(())
+	}
+	
+	//		commands															
+	/// Counts the commands recorded by the context that match the given
+	/// command line.
+	fn commands(&self, command: &str) -> usize {
+		self.context.interactions().iter()
+			.filter(|interaction| interaction.module == "std_process" && interaction.action == command)
+			.count()
+	}
+	
+	//		parse_target														
+	/// Parses a method and a path or URL, resolving the path against the base
+	/// URL.
+	fn parse_target(&self, text: &str, target: &str) -> Result<(Method, Url), MockStepError> {
+		let invalid        = |reason: &str| MockStepError::Invalid(text.to_owned(), reason.to_owned());
+		let (method, path) = target.trim().split_once(' ').ok_or_else(|| invalid("expected \"<METHOD> <path>\""))?;
+		let parsed_method  = Method::from_bytes(method.as_bytes()).map_err(|_err| invalid("invalid method"))?;
+		let url            = self.base.join(path.trim()).map_err(|_err| invalid("invalid path"))?;
+		Ok((parsed_method, url))
+	}
+	
+	//		requests															
+	/// Counts the requests recorded by the context that match the given
+	/// target, or all requests if there is no target.
+	fn requests(&self, text: &str, target: Option<&str>) -> Result<usize, MockStepError> {
+		let wanted = target.map(|value| self.parse_target(text, value)).transpose()?;
+		Ok(self.context.interactions().iter()
+			.filter(|interaction| interaction.module == "reqwest")
+			.filter(|interaction| wanted.as_ref().map_or(true, |pair| is_request_to(&interaction.action, &pair.0, &pair.1)))
+			.count()
+		)
+	}
+	
+	//		then_command														
+	/// Checks the commands run, for a step that begins with "the command".
+	fn then_command(&self, text: &str, rest: &str) -> Result<(), MockStepError> {
+		let invalid            = |reason: &str| MockStepError::Invalid(text.to_owned(), reason.to_owned());
+		let (command, outcome) = rest.strip_prefix('`').and_then(|quoted| quoted.split_once("` was "))
+			.ok_or_else(|| invalid("the command must be quoted with backticks"))?
+		;
+		let expected           = match outcome.strip_prefix("run") {
+			_ if outcome == "not run" => Some(0),
+			Some(count)               => parse_times(text, count)?,
+			None                      => return Err(invalid("expected \"was run\" or \"was not run\"")),
+		};
+		let count              = self.commands(command);
+		match expected {
+			Some(times) if count != times => {
+				Err(MockStepError::Failed(text.to_owned(), format!("expected {times} run(s), but it was run {count} time(s)")))
+			},
+			None if count == 0            => Err(MockStepError::Failed(text.to_owned(), "the command was not run".to_owned())),
+			_                             => Ok(()),
+		}
+	}
+	
+	//		then_requested														
+	/// Checks that the given number of requests matching the target were sent.
+	fn then_requested(&self, text: &str, target: Option<&str>, expected: usize) -> Result<(), MockStepError> {
+		let count = self.requests(text, target)?;
+		if count == expected {
+			Ok(())
+		} else {
+			Err(MockStepError::Failed(text.to_owned(), format!("expected {expected} request(s), but {count} were sent")))
+		}
+	}
+	
+	//		then_requested_at_least_once										
+	/// Checks that at least one request matching the target was sent.
+	fn then_requested_at_least_once(&self, text: &str, target: &str) -> Result<(), MockStepError> {
+		if self.requests(text, Some(target))? > 0 {
+			Ok(())
+		} else {
+			Err(MockStepError::Failed(text.to_owned(), "no matching request was sent".to_owned()))
+		}
+	}
+}
+
+
+
+//		Functions
+
+//		is_request_to															
+/// Whether the action of a recorded interaction is a request with the given
+/// method to the given URL, ignoring any query string.
+fn is_request_to(action: &str, method: &Method, url: &Url) -> bool {
+	action.split_once(' ').is_some_and(|(sent_method, sent_url)| {
+		sent_method == method.as_str() && Url::parse(sent_url).is_ok_and(|mut parsed| {
+			parsed.set_query(None);
+			parsed == *url
+		})
+	})
+}
+
+//		parse_duration															
+/// Parses a step such as "5 minutes pass" into a duration.
+#[cfg(feature = "clock")]
+fn parse_duration(text: &str) -> Option<Duration> {
+	let amount         = ["have passed", "has passed", "passes", "pass"].iter().find_map(|suffix| text.strip_suffix(suffix))?;
+	let (number, unit) = amount.trim().split_once(' ')?;
+	let value          = number.parse::<u64>().ok()?;
+	let scale          = match unit.trim_end_matches('s') {
+		"second" => 1,
+		"minute" => 60,
+		"hour"   => 3_600,
+		_        => return None,
+	};
+	Some(Duration::from_secs(value.saturating_mul(scale)))
+}
+
+//		parse_times																
+/// Parses the number of times that something is expected to have happened,
+/// such as "twice", which is `None` if no number is given.
+fn parse_times(text: &str, count: &str) -> Result<Option<usize>, MockStepError> {
+	Ok(match count.trim() {
+		""      => None,
+		"once"  => Some(1),
+		"twice" => Some(2),
+		other   => Some(other.strip_suffix(" times").and_then(|number| number.parse().ok())
+			.ok_or_else(|| MockStepError::Invalid(text.to_owned(), format!("invalid count \"{other}\"")))?
+		),
+	})
+}
+
+//		respond																	
+/// Produces the response for a route added by a step, with the URL of the
+/// request.
+fn respond(result: &Result<MockResponse, MockError>, url: &Url) -> Result<MockResponse, MockError> {
+	match *result {
+		Ok(ref response) => Ok(MockResponse { url: url.clone(), ..response.clone() }),
+		Err(ref error)   => Err(error.clone().with_url(url.clone())),
+	}
+}
@@ -0,0 +1,311 @@
+//! This module provides scripted outages in order to test circuit breakers.
+//! 
+//! This module provides [`MockOutage`], which makes a route fail for a while
+//! and then recover, and records when each call to it was made, so that a test
+//! can check when the code under test stopped sending requests, i.e. when its
+//! circuit breaker opened, and when it tried again, i.e. when the breaker went
+//! half-open. This is important because a circuit breaker is only useful if it
+//! opens after the right number of failures and stays open for long enough,
+//! and neither of these can be seen from the responses alone.
+//! 
+//! The approach taken is that the outage starts with the first call to its
+//! route, and lasts for a set time, during which every call fails with a
+//! status of `503 Service Unavailable`, after which calls are passed on to the
+//! responder of the route. The time of each call comes from the virtual clock
+//! of the [`Context`](crate::Context) entered on the current thread, so the
+//! waits of the code under test, such as those made with
+//! [`sleep()`](crate::reqwest::sleep()), take no real time. A gap between calls
+//! of at least the [quiet period](MockOutage::with_quiet_period()) is taken to
+//! mean that the breaker was open, and the first call after it to be the probe
+//! made when the breaker went half-open.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use core::time::Duration;
+//! use futures_executor::block_on;
+//! use reqwest::{Method, StatusCode};
+//! use sham::{Context, breaker::MockOutage, reqwest::{create_mock_response, create_routed_mock_client}};
+//! use std::collections::HashMap;
+//! 
+//! let context = Context::new();
+//! let _guard  = context.enter();
+//! let outage  = MockOutage::new(Duration::from_secs(20));
+//! let client  = create_routed_mock_client(vec![
+//!     outage.route(Method::GET, "https://api.example.com/health", |request| {
+//!         Ok(create_mock_response(request.url.clone(), StatusCode::OK, None::<&str>, None, HashMap::<String, String>::new(), Ok(b"")))
+//!     }),
+//! ]);
+//! 
+//! block_on(async {
+//!     for _ in 0..3 {
+//!         let response = client.get("https://api.example.com/health").send().await.unwrap();
+//!         assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+//!     }
+//!     context.clock().advance(Duration::from_secs(30));
+//!     let probe = client.get("https://api.example.com/health").send().await.unwrap();
+//!     assert_eq!(probe.status(), StatusCode::OK);
+//! });
+//! 
+//! outage.assert_opened_after(3);
+//! outage.assert_probed_after(Duration::from_secs(30));
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::reqwest::{MockError, MockRequest, MockResponse, MockRoute, create_mock_response};
+use core::{
+	fmt::Write,
+	time::Duration,
+};
+use reqwest::{IntoUrl, Method, StatusCode};
+use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex, MutexGuard, PoisonError},
+	time::SystemTime,
+};
+
+
+
+//		Structs
+
+//		MockOutage																
+/// A scripted outage, which makes a route fail for a while and then recover,
+/// and records the calls made to it.
+/// 
+/// Clones share the same state, so a clone can be used to serve the route
+/// while the original is used to make assertions.
+/// 
+#[derive(Clone, Debug)]
+pub struct MockOutage {
+	//		Private properties													
+	/// How long the outage lasts from the first call.
+	duration: Duration,
+	
+	/// The shortest gap between calls that is taken to mean that the breaker
+	/// was open.
+	quiet:    Duration,
+	
+	/// The calls made so far, shared between clones.
+	state:    Arc<Mutex<Vec<(SystemTime, bool)>>>,
+	
+	/// The status of the responses during the outage.
+	status:   StatusCode,
+}
+
+//󰭅		MockOutage																
+impl MockOutage {
+	//		new																	
+	/// Creates a new outage.
+	/// 
+	/// By default, calls fail with `503 Service Unavailable`, and a gap of a
+	/// second or more between calls is taken to mean that the breaker was open.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `duration` - How long the outage lasts, from the first call.
+	/// 
+	#[must_use]
+	pub fn new(duration: Duration) -> Self {
+		Self {
+			duration,
+			quiet:  Duration::from_secs(1),
+			state:  Arc::new(Mutex::new(Vec::new())),
+			status: StatusCode::SERVICE_UNAVAILABLE,
+		}
+	}
+	
+	//		with_quiet_period													
+	/// Sets the shortest gap between calls that is taken to mean that the
+	/// breaker was open. This should be longer than any backoff between the
+	/// retries made before the breaker opens.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `quiet` - The shortest gap.
+	/// 
+	#[must_use]
+	pub const fn with_quiet_period(mut self, quiet: Duration) -> Self {
+		self.quiet = quiet;
+		self
+	}
+	
+	//		with_status															
+	/// Sets the status of the responses during the outage.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `status` - The status to fail with.
+	/// 
+	#[must_use]
+	pub const fn with_status(mut self, status: StatusCode) -> Self {
+		self.status = status;
+		self
+	}
+	
+	//		assert_opened_after													
+	/// Checks that the breaker opened after the given number of failed calls,
+	/// i.e. that the code under test made exactly that many calls before it
+	/// first stopped for the quiet period, and that they all failed.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `failures` - The number of failed calls after which the breaker
+	///   should have opened.
+	/// 
+	/// # Panics
+	/// 
+	/// Panics with the timeline of the calls if the breaker did not open, or
+	/// opened after a different number of calls, or if any of those calls
+	/// succeeded.
+	/// 
+	pub fn assert_opened_after(&self, failures: usize) {
+		let calls  = self.lock().clone();
+		let opened = self.opened_after();
+		let failed = calls.iter().take(failures).all(|call| call.1);
+		assert!(
+			opened == Some(failures) && failed,
+			"Expected the breaker to open after {failures} failed call(s), but {}:{}",
+			opened.map_or_else(|| "it never stopped sending requests".to_owned(), |count| format!("it opened after {count}")),
+			timeline(&calls),
+		);
+	}
+	
+	//		assert_probed_after													
+	/// Checks that the code under test probed the route again once the breaker
+	/// had been open for at least the given time.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `cooldown` - The shortest time that the breaker should have stayed
+	///   open before going half-open.
+	/// 
+	/// # Panics
+	/// 
+	/// Panics with the timeline of the calls if the breaker never opened, if
+	/// no probe was made, or if the probe was made too soon.
+	/// 
+	pub fn assert_probed_after(&self, cooldown: Duration) {
+		let calls = self.lock().clone();
+		let open  = self.open_for();
+		assert!(
+			open.is_some_and(|waited| waited >= cooldown),
+			"Expected a probe after the breaker had been open for at least {}ms, but {}:{}",
+			cooldown.as_millis(),
+			open.map_or_else(|| "none was made".to_owned(), |waited| format!("it was made after {}ms", waited.as_millis())),
+			timeline(&calls),
+		);
+	}
+	
+	//		calls																
+	/// The calls made to the route so far, in order, as the time since the
+	/// first call and whether the call failed because of the outage.
+	#[must_use]
+	pub fn calls(&self) -> Vec<(Duration, bool)> {
+		let calls = self.lock().clone();
+		let start = calls.first().map(|call| call.0);
+		calls.into_iter()
+			.map(|call| (start.and_then(|first| call.0.duration_since(first).ok()).unwrap_or_default(), call.1))
+			.collect()
+	}
+	
+	//		lock																
+	/// Locks the calls, recovering from poisoning.
+	fn lock(&self) -> MutexGuard<'_, Vec<(SystemTime, bool)>> {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+	
+	//		open_for															
+	/// How long the breaker stayed open before the code under test probed the
+	/// route again, i.e. the length of the first quiet period, if there was a
+	/// call after it.
+	#[must_use]
+	pub fn open_for(&self) -> Option<Duration> {
+		self.calls().windows(2)
+			.map(|pair| pair[1].0.saturating_sub(pair[0].0))
+			.find(|&gap| gap >= self.quiet)
+	}
+	
+	//		opened_after														
+	/// The number of calls made before the breaker first opened, i.e. before
+	/// the first quiet period, if there was one.
+	/// 
+	/// A breaker that opened after the last call, so that no further calls were
+	/// made, cannot be told apart from one that never opened, so a probe is
+	/// needed for this to be known.
+	/// 
+	#[must_use]
+	pub fn opened_after(&self) -> Option<usize> {
+		self.calls().windows(2)
+			.position(|pair| pair[1].0.saturating_sub(pair[0].0) >= self.quiet)
+			.map(|index| index + 1)
+	}
+	
+	//		route																
+	/// Creates a route that fails during the outage, and is then served by the
+	/// given responder.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `method`    - The HTTP method to match.
+	/// * `url`       - The URL to match. Any query string is ignored.
+	/// * `responder` - The function that produces the response once the
+	///   outage is over.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the URL is not valid.
+	/// 
+	pub fn route<U, F>(&self, method: Method, url: U, responder: F) -> MockRoute
+	where
+		U: IntoUrl,
+		F: Fn(&MockRequest) -> Result<MockResponse, MockError> + Send + Sync + 'static,
+	{
+		let outage = self.clone();
+		MockRoute::contextual(method, url, move |request, invocation| {
+			let failing = {
+				let mut calls = outage.lock();
+				let start     = calls.first().map_or(invocation.now, |call| call.0);
+				let elapsed   = invocation.now.duration_since(start).unwrap_or_default();
+				let failing   = elapsed < outage.duration;
+				calls.push((invocation.now, failing));
+				failing
+			};
+			if failing {
+				Ok(create_mock_response(
+					request.url.clone(),
+					outage.status,
+					None::<&str>,
+					Some(0),
+					HashMap::<String, String>::new(),
+					Ok(b""),
+				))
+			} else {
+				responder(request)
+			}
+		})
+	}
+}
+
+
+
+//		Functions
+
+//		timeline																
+/// Describes the given calls, one per line, with the time since the first
+/// call and whether each one failed.
+fn timeline(calls: &[(SystemTime, bool)]) -> String {
+	let start    = calls.first().map(|call| call.0);
+	let mut text = String::new();
+	for (number, call) in calls.iter().enumerate() {
+		let elapsed = start.and_then(|first| call.0.duration_since(first).ok()).unwrap_or_default();
+		_ = write!(text, "\n  {}: +{}ms {}", number + 1, elapsed.as_millis(), if call.1 { "failed" } else { "served" });
+	}
+	if calls.is_empty() {
+		text.push_str("\n  (no calls)");
+	}
+	text
+}
@@ -0,0 +1,360 @@
+//! This module mocks a cache client in order to test caching logic.
+//! 
+//! This module provides an in-memory cache client with the operations common to
+//! Memcached and similar caches, i.e. getting, setting, deleting, and
+//! compare-and-swap, with expiry driven by a [`MockClock`]. This is important
+//! because unit tests should not depend upon a cache server being available,
+//! and because the behaviour that cache-aside and stampede-protection logic
+//! exists to handle, such as expiry, a burst of misses, or the cache being
+//! unreachable, needs to be produced on demand.
+//! 
+//! The approach taken is that the "real" code should access the cache through
+//! a client that it is given, and the test code then supplies a [`MockCache`].
+//! Clones of a [`MockCache`] share the same entries, so the test can keep one
+//! in order to advance time, inject faults, and inspect the statistics, whilst
+//! the code under test uses another.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use core::time::Duration;
+//! use sham::{
+//!     cache::{MockCache, MockCacheError},
+//!     clock::MockClock,
+//! };
+//! 
+//! let clock = MockClock::new();
+//! let cache = MockCache::new(&clock);
+//! 
+//! cache.set("user:1", b"Alice", Some(Duration::from_secs(60))).unwrap();
+//! assert_eq!(cache.get("user:1").unwrap(), Some(b"Alice".to_vec()));
+//! 
+//! clock.advance(Duration::from_secs(61));
+//! assert_eq!(cache.get("user:1").unwrap(), None);
+//! 
+//! cache.inject_connection_failures(1);
+//! assert_eq!(cache.get("user:1"), Err(MockCacheError::ConnectionFailed));
+//! assert_eq!(cache.stats().misses, 1);
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::clock::MockClock;
+use core::{
+	error::Error,
+	fmt::{Display, Formatter, self},
+	time::Duration,
+};
+use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex, MutexGuard, PoisonError},
+	time::SystemTime,
+};
+
+
+
+//		Enums
+
+//		MockCacheError															
+/// A mocked cache error type.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockCacheError {
+	/// The cache could not be reached.
+	ConnectionFailed,
+}
+
+//󰭅		Display																	
+impl Display for MockCacheError {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match *self {
+			Self::ConnectionFailed => write!(f, "Connection to the cache failed"),
+		}
+	}
+}
+
+//󰭅		Error																	
+impl Error for MockCacheError {}
+
+
+
+//		Structs
+
+//		CacheEntry																
+/// An entry held by the mocked cache.
+#[derive(Clone, Debug)]
+struct CacheEntry {
+	/// The compare-and-swap token of the entry.
+	cas:     u64,
+	
+	/// The time at which the entry expires, if any.
+	expires: Option<SystemTime>,
+	
+	/// The value of the entry.
+	value:   Vec<u8>,
+}
+
+//		CacheState																
+/// The state of a [`MockCache`] instance.
+#[derive(Debug, Default)]
+struct CacheState {
+	/// The number of operations left to fail with a connection error.
+	connection_failures: usize,
+	
+	/// The entries held by the cache.
+	entries:             HashMap<String, CacheEntry>,
+	
+	/// The number of gets left to miss regardless of the entries.
+	forced_misses:       usize,
+	
+	/// The latest compare-and-swap token issued.
+	last_cas:            u64,
+	
+	/// The statistics of the operations performed.
+	stats:               MockCacheStats,
+}
+
+//		MockCache																
+/// A mocked cache client, backed by an in-memory store.
+#[derive(Clone, Debug)]
+pub struct MockCache {
+	//		Private properties													
+	/// The clock that drives expiry.
+	clock: MockClock,
+	
+	/// The state of the cache, shared between clones.
+	state: Arc<Mutex<CacheState>>,
+}
+
+//󰭅		MockCache																
+impl MockCache {
+	//		new																	
+	/// Creates a new, empty cache.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `clock` - The clock that drives expiry.
+	/// 
+	#[must_use]
+	pub fn new(clock: &MockClock) -> Self {
+		Self {
+			clock: clock.clone(),
+			state: Arc::new(Mutex::new(CacheState::default())),
+		}
+	}
+	
+	//		cas																	
+	/// Sets a value only if the entry has not changed since the given token was
+	/// obtained, returning whether the value was set.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `key`   - The key of the entry.
+	/// * `value` - The value to set.
+	/// * `ttl`   - How long the entry should live for, or [`None`] for no
+	///   expiry.
+	/// * `cas`   - The token obtained from [`gets()`](MockCache::gets()).
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if a connection failure has been injected.
+	/// 
+	pub fn cas(&self, key: &str, value: &[u8], ttl: Option<Duration>, cas: u64) -> Result<bool, MockCacheError> {
+		let mut state = self.connect()?;
+		let now       = self.clock.now();
+		let current   = state.entries.get(key).filter(|entry| is_live(entry, now)).map(|entry| entry.cas);
+		if current != Some(cas) {
+			state.stats.cas_conflicts = state.stats.cas_conflicts.saturating_add(1);
+			return Ok(false);
+		}
+		self.store(&mut state, key, value, ttl);
+		drop(state);
+		Ok(true)
+	}
+	
+	//		delete																
+	/// Deletes an entry, returning whether it existed.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `key` - The key of the entry.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if a connection failure has been injected.
+	/// 
+	pub fn delete(&self, key: &str) -> Result<bool, MockCacheError> {
+		let mut state = self.connect()?;
+		let now       = self.clock.now();
+		state.stats.deletes = state.stats.deletes.saturating_add(1);
+		let existed   = state.entries.remove(key).is_some_and(|entry| is_live(&entry, now));
+		drop(state);
+		Ok(existed)
+	}
+	
+	//		get																	
+	/// Gets the value of an entry, if it exists and has not expired.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `key` - The key of the entry.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if a connection failure has been injected.
+	/// 
+	pub fn get(&self, key: &str) -> Result<Option<Vec<u8>>, MockCacheError> {
+		Ok(self.gets(key)?.map(|(value, _)| value))
+	}
+	
+	//		gets																
+	/// Gets the value of an entry along with its compare-and-swap token, if it
+	/// exists and has not expired.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `key` - The key of the entry.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if a connection failure has been injected.
+	/// 
+	pub fn gets(&self, key: &str) -> Result<Option<(Vec<u8>, u64)>, MockCacheError> {
+		let mut state = self.connect()?;
+		let now       = self.clock.now();
+		let found     = if state.forced_misses > 0 {
+			state.forced_misses = state.forced_misses.saturating_sub(1);
+			None
+		} else {
+			state.entries.get(key).filter(|entry| is_live(entry, now)).map(|entry| (entry.value.clone(), entry.cas))
+		};
+		if found.is_some() {
+			state.stats.hits   = state.stats.hits.saturating_add(1);
+		} else {
+			state.stats.misses = state.stats.misses.saturating_add(1);
+		}
+		drop(state);
+		Ok(found)
+	}
+	
+	//		inject_connection_failures											
+	/// Makes the next operations fail as if the cache could not be reached.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `count` - The number of operations to fail.
+	/// 
+	pub fn inject_connection_failures(&self, count: usize) {
+		self.lock().connection_failures = count;
+	}
+	
+	//		inject_miss_storm													
+	/// Makes the next gets miss, regardless of what the cache holds, as happens
+	/// when a cache node is restarted or a popular entry expires.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `count` - The number of gets to miss.
+	/// 
+	pub fn inject_miss_storm(&self, count: usize) {
+		self.lock().forced_misses = count;
+	}
+	
+	//		set																	
+	/// Sets the value of an entry.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `key`   - The key of the entry.
+	/// * `value` - The value to set.
+	/// * `ttl`   - How long the entry should live for, or [`None`] for no
+	///   expiry.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if a connection failure has been injected.
+	/// 
+	pub fn set(&self, key: &str, value: &[u8], ttl: Option<Duration>) -> Result<(), MockCacheError> {
+		let mut state = self.connect()?;
+		self.store(&mut state, key, value, ttl);
+		drop(state);
+		Ok(())
+	}
+	
+	//		stats																
+	/// Returns the statistics of the operations performed so far.
+	#[must_use]
+	pub fn stats(&self) -> MockCacheStats {
+		self.lock().stats
+	}
+	
+	//		connect																
+	/// Locks the state of the cache, failing if a connection failure has been
+	/// injected.
+	fn connect(&self) -> Result<MutexGuard<'_, CacheState>, MockCacheError> {
+		let mut state = self.lock();
+		if state.connection_failures > 0 {
+			state.connection_failures = state.connection_failures.saturating_sub(1);
+			return Err(MockCacheError::ConnectionFailed);
+		}
+		Ok(state)
+	}
+	
+	//		lock																
+	/// Locks the state of the cache.
+	fn lock(&self) -> MutexGuard<'_, CacheState> {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+	
+	//		store																
+	/// Stores a value, issuing a new compare-and-swap token.
+	fn store(&self, state: &mut CacheState, key: &str, value: &[u8], ttl: Option<Duration>) {
+		state.last_cas  = state.last_cas.saturating_add(1);
+		state.stats.sets = state.stats.sets.saturating_add(1);
+		drop(state.entries.insert(key.to_owned(), CacheEntry {
+			cas:     state.last_cas,
+			expires: ttl.map(|duration| self.clock.now() + duration),
+			value:   value.to_vec(),
+		}));
+	}
+}
+
+//		MockCacheStats															
+/// The statistics of the operations performed on a [`MockCache`].
+/// 
+/// These allow stampede-protection logic to be verified, for instance by
+/// checking that a burst of misses resulted in only one set.
+/// 
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockCacheStats {
+	//		Public properties													
+	/// The number of compare-and-swaps that failed due to a changed entry.
+	pub cas_conflicts: usize,
+	
+	/// The number of deletes.
+	pub deletes:       usize,
+	
+	/// The number of gets that found a value.
+	pub hits:          usize,
+	
+	/// The number of gets that did not find a value.
+	pub misses:        usize,
+	
+	/// The number of values stored, by sets and successful compare-and-swaps.
+	pub sets:          usize,
+}
+
+
+
+//		Functions
+
+//		is_live																	
+/// Checks whether an entry has not expired.
+fn is_live(entry: &CacheEntry, now: SystemTime) -> bool {
+	entry.expires.map_or(true, |expires| now < expires)
+}
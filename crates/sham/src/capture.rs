@@ -0,0 +1,490 @@
+//! This module provides captured requests and commands, with redaction, in
+//! order to share what a test sent without sharing its secrets.
+//! 
+//! This module provides [`CapturedRequest`] and [`CapturedCommand`], which are
+//! plain, serialisable records of an HTTP request and of a command line, and a
+//! [`Redactor`], which masks the secrets in them: authentication headers,
+//! passwords and tokens given as arguments, values in JSON bodies picked out by
+//! [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901), query parameters,
+//! and known secret values wherever they appear. This is important because
+//! snapshots, baselines, and reports are most useful when they are committed
+//! alongside the tests or attached to a bug report, which is not safe if they
+//! contain real credentials, and which is easy to get wrong by hand.
+//! 
+//! The approach taken is that redaction is applied to the captured records
+//! rather than to the mocks, so that the code under test and the assertions in
+//! the test still see the real values, and only what is written out is masked.
+//! Each secret is replaced with [`MASK`], leaving the rest of the record as it
+//! was, so that the shape of what was sent can still be reviewed. A redactor
+//! can also be given to a [`Context`](crate::Context), with
+//! [`Context::set_redactor()`](crate::Context::set_redactor()), so that the
+//! interactions it records, and therefore its reports and baselines, are
+//! redacted as they are recorded. [`Redactor::standard()`] masks the usual
+//! credentials, and further rules can be added to it.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use sham::capture::{CapturedCommand, CapturedRequest, Redactor};
+//! 
+//! let redactor = Redactor::standard()
+//!     .with_json_pointer("/account/pin")
+//!     .with_value("hunter2")
+//! ;
+//! 
+//! let request  = CapturedRequest::new("POST", "https://api.example.com/login?token=abc&page=1")
+//!     .with_header("Authorization", "Bearer abc")
+//!     .with_body(r#"{"user":"jo","account":{"pin":"1234"},"note":"hunter2"}"#)
+//! ;
+//! let redacted = redactor.redact_request(&request);
+//! assert_eq!(redacted.url, "https://api.example.com/login?token=[REDACTED]&page=1");
+//! assert_eq!(redacted.headers, vec![("authorization".to_owned(), "[REDACTED]".to_owned())]);
+//! assert_eq!(redacted.body.unwrap(), r#"{"user":"jo","account":{"pin":"[REDACTED]"},"note":"[REDACTED]"}"#);
+//! 
+//! let command  = CapturedCommand::new("deploy", ["--user", "jo", "--password", "hunter2", "--token=abc"]);
+//! assert_eq!(redactor.redact_command(&command).to_string(), "deploy --user jo --password [REDACTED] --token=[REDACTED]");
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::context::MockInteraction;
+#[cfg(feature = "reqwest")]
+use crate::reqwest::MockRequest;
+use core::fmt::{Display, Formatter, self};
+use serde::Serialize;
+use serde_json::{Value as Json, from_str as from_json_str, to_string as to_json_string};
+
+
+
+//		Constants
+
+/// The text that replaces each redacted secret.
+pub const MASK: &str = "[REDACTED]";
+
+
+
+//		Structs
+
+//		CapturedCommand															
+/// A command line that was run, or would have been run, by the code under
+/// test.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct CapturedCommand {
+	//		Public properties													
+	/// The program that was run.
+	pub program: String,
+	
+	/// The arguments given to the program, in order.
+	pub args:    Vec<String>,
+}
+
+//󰭅		CapturedCommand															
+impl CapturedCommand {
+	//		new																	
+	/// Creates a new captured command.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `program` - The program that was run.
+	/// * `args`    - The arguments given to the program.
+	/// 
+	pub fn new<I, S>(program: &str, args: I) -> Self
+	where
+		I: IntoIterator<Item = S>,
+		S: Into<String>,
+	{
+		Self {
+			program: program.to_owned(),
+			args:    args.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+//󰭅		Display																	
+impl Display for CapturedCommand {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.program)?;
+		for arg in &self.args {
+			write!(f, " {arg}")?;
+		}
+		Ok(())
+	}
+}
+
+//		CapturedRequest															
+/// An HTTP request that was sent by the code under test.
+/// 
+/// Header names are held in lowercase, and the body, if any, is held as text,
+/// with any bytes that are not valid UTF-8 replaced.
+/// 
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct CapturedRequest {
+	//		Public properties													
+	/// The HTTP method of the request.
+	pub method:  String,
+	
+	/// The URL of the request.
+	pub url:     String,
+	
+	/// The headers of the request, as names and values, in order.
+	pub headers: Vec<(String, String)>,
+	
+	/// The body of the request, if there was one.
+	pub body:    Option<String>,
+}
+
+//󰭅		CapturedRequest															
+impl CapturedRequest {
+	//		new																	
+	/// Creates a new captured request, with no headers and no body.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `method` - The HTTP method of the request.
+	/// * `url`    - The URL of the request.
+	/// 
+	#[must_use]
+	pub fn new(method: &str, url: &str) -> Self {
+		Self {
+			method:  method.to_owned(),
+			url:     url.to_owned(),
+			headers: Vec::new(),
+			body:    None,
+		}
+	}
+	
+	//		with_body															
+	/// Sets the body of the request.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `body` - The body of the request.
+	/// 
+	#[must_use]
+	pub fn with_body(mut self, body: &str) -> Self {
+		self.body = Some(body.to_owned());
+		self
+	}
+	
+	//		with_header															
+	/// Adds a header to the request.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name`  - The name of the header, in any case.
+	/// * `value` - The value of the header.
+	/// 
+	#[must_use]
+	pub fn with_header(mut self, name: &str, value: &str) -> Self {
+		self.headers.push((name.to_ascii_lowercase(), value.to_owned()));
+		self
+	}
+}
+
+//󰭅		Display																	
+impl Display for CapturedRequest {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		write!(f, "{} {}", self.method, self.url)
+	}
+}
+
+//󰭅		From<&MockRequest>														
+#[cfg(feature = "reqwest")]
+impl From<&MockRequest> for CapturedRequest {
+	//		from																
+	fn from(request: &MockRequest) -> Self {
+		Self {
+			method:  request.method.to_string(),
+			url:     request.url.to_string(),
+			headers: request.headers.iter()
+				.map(|(name, value)| (name.as_str().to_owned(), String::from_utf8_lossy(value.as_bytes()).into_owned()))
+				.collect()
+			,
+			body:    request.body.as_deref().map(|body| String::from_utf8_lossy(body).into_owned()),
+		}
+	}
+}
+
+//		Redactor																
+/// A set of rules for masking the secrets in captured records.
+/// 
+/// A new redactor masks nothing until rules are added to it.
+/// 
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[must_use]
+pub struct Redactor {
+	//		Private properties													
+	/// The options whose values are masked, such as `--password`.
+	arguments:  Vec<String>,
+	
+	/// The names of the headers whose values are masked, in lowercase.
+	headers:    Vec<String>,
+	
+	/// The names of the query or form parameters whose values are masked.
+	parameters: Vec<String>,
+	
+	/// The JSON pointers to the values in JSON bodies that are masked.
+	pointers:   Vec<String>,
+	
+	/// The secret values that are masked wherever they appear.
+	values:     Vec<String>,
+}
+
+//󰭅		Redactor																
+impl Redactor {
+	//		new																	
+	/// Creates a new redactor, which masks nothing until rules are added.
+	pub fn new() -> Self {
+		Self::default()
+	}
+	
+	//		standard															
+	/// Creates a new redactor that masks the usual credentials.
+	/// 
+	/// These are the `authorization`, `cookie`, `proxy-authorization`,
+	/// `set-cookie`, and `x-api-key` headers, the `--password`, `--secret`, and
+	/// `--token` options, and the `access_token`, `api_key`, `client_secret`,
+	/// `password`, and `token` parameters.
+	/// 
+	pub fn standard() -> Self {
+		let owned = |names: &[&str]| names.iter().map(|&name| name.to_owned()).collect();
+		Self {
+			arguments:  owned(&["--password", "--secret", "--token"]),
+			headers:    owned(&["authorization", "cookie", "proxy-authorization", "set-cookie", "x-api-key"]),
+			parameters: owned(&["access_token", "api_key", "client_secret", "password", "token"]),
+			pointers:   Vec::new(),
+			values:     Vec::new(),
+		}
+	}
+	
+	//		with_argument														
+	/// Masks the value of an option, whether it is given as the next argument,
+	/// as in `--password secret`, or in the same argument, as in
+	/// `--password=secret`.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `option` - The option, such as `--password`.
+	/// 
+	pub fn with_argument(mut self, option: &str) -> Self {
+		self.arguments.push(option.to_owned());
+		self
+	}
+	
+	//		with_header															
+	/// Masks the values of a header.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the header, in any case.
+	/// 
+	pub fn with_header(mut self, name: &str) -> Self {
+		self.headers.push(name.to_ascii_lowercase());
+		self
+	}
+	
+	//		with_json_pointer													
+	/// Masks a value in JSON bodies, wherever the body is valid JSON and has a
+	/// value at the pointer.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `pointer` - The JSON pointer to the value, such as `/user/password`.
+	/// 
+	pub fn with_json_pointer(mut self, pointer: &str) -> Self {
+		self.pointers.push(pointer.to_owned());
+		self
+	}
+	
+	//		with_parameter														
+	/// Masks the value of a query parameter in URLs, or of a parameter in a
+	/// form body.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the parameter, which is case-sensitive.
+	/// 
+	pub fn with_parameter(mut self, name: &str) -> Self {
+		self.parameters.push(name.to_owned());
+		self
+	}
+	
+	//		with_value															
+	/// Masks a secret value wherever it appears, such as a password that the
+	/// test has configured.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `secret` - The secret value. An empty value is ignored.
+	/// 
+	pub fn with_value(mut self, secret: &str) -> Self {
+		if !secret.is_empty() {
+			self.values.push(secret.to_owned());
+		}
+		self
+	}
+	
+	//		redact_arguments													
+	/// Masks the secrets in a list of arguments.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `args` - The arguments to redact.
+	/// 
+	#[must_use]
+	pub fn redact_arguments(&self, args: &[String]) -> Vec<String> {
+		let mut redacted = Vec::with_capacity(args.len());
+		let mut masking  = false;
+		for arg in args {
+			if masking {
+				masking = false;
+				redacted.push(MASK.to_owned());
+			} else if self.arguments.contains(arg) {
+				masking = true;
+				redacted.push(arg.clone());
+			} else if let Some(option) = self.arguments.iter().find(|&option| {
+				arg.strip_prefix(option.as_str()).is_some_and(|rest| rest.starts_with('='))
+			}) {
+				redacted.push(format!("{option}={MASK}"));
+			} else {
+				redacted.push(self.redact_text(arg));
+			}
+		}
+		redacted
+	}
+	
+	//		redact_body															
+	/// Masks the secrets in a body.
+	/// 
+	/// If any JSON pointers have been added and the body is valid JSON, the
+	/// values at those pointers are masked, and the body is re-encoded without
+	/// whitespace. Parameters and secret values are then masked as for
+	/// [`redact_text()`](Redactor::redact_text()).
+	/// 
+	/// # Parameters
+	/// 
+	/// * `body` - The body to redact.
+	/// 
+	#[must_use]
+	pub fn redact_body(&self, body: &str) -> String {
+		let json = if self.pointers.is_empty() { None } else { from_json_str::<Json>(body).ok() };
+		let Some(mut value) = json else {
+			return self.redact_text(body);
+		};
+		for pointer in &self.pointers {
+			if let Some(target) = value.pointer_mut(pointer) {
+				*target = Json::String(MASK.to_owned());
+			}
+		}
+		self.redact_text(&to_json_string(&value).unwrap_or_else(|_| body.to_owned()))
+	}
+	
+	//		redact_command														
+	/// Masks the secrets in a captured command.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `command` - The command to redact.
+	/// 
+	#[must_use]
+	pub fn redact_command(&self, command: &CapturedCommand) -> CapturedCommand {
+		CapturedCommand {
+			program: self.redact_text(&command.program),
+			args:    self.redact_arguments(&command.args),
+		}
+	}
+	
+	//		redact_interaction													
+	/// Masks the secrets in a recorded interaction, treating the action and
+	/// the outcome as text.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `interaction` - The interaction to redact.
+	/// 
+	#[must_use]
+	pub fn redact_interaction(&self, interaction: &MockInteraction) -> MockInteraction {
+		MockInteraction {
+			module:  interaction.module.clone(),
+			action:  self.redact_text(&interaction.action),
+			outcome: self.redact_text(&interaction.outcome),
+		}
+	}
+	
+	//		redact_request														
+	/// Masks the secrets in a captured request.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `request` - The request to redact.
+	/// 
+	#[must_use]
+	pub fn redact_request(&self, request: &CapturedRequest) -> CapturedRequest {
+		CapturedRequest {
+			method:  request.method.clone(),
+			url:     self.redact_text(&request.url),
+			headers: request.headers.iter().map(|header| {
+				let masked = self.headers.iter().any(|name| name.eq_ignore_ascii_case(&header.0));
+				(header.0.clone(), if masked { MASK.to_owned() } else { self.redact_text(&header.1) })
+			}).collect(),
+			body:    request.body.as_deref().map(|body| self.redact_body(body)),
+		}
+	}
+	
+	//		redact_text															
+	/// Masks the secrets in free text, such as a URL or a log message.
+	/// 
+	/// The value of each parameter is masked where it follows `?`, `&`, a
+	/// space, or the start of the text, up to the next `&`, `#`, quote, or
+	/// space, and then each secret value is masked wherever it appears.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `text` - The text to redact.
+	/// 
+	#[must_use]
+	pub fn redact_text(&self, text: &str) -> String {
+		let mut redacted = mask_parameters(text, &self.parameters);
+		for secret in &self.values {
+			redacted = redacted.replace(secret.as_str(), MASK);
+		}
+		redacted
+	}
+}
+
+
+
+//		Functions
+
+//		mask_parameters															
+/// Masks the values of the named parameters in text.
+fn mask_parameters(text: &str, names: &[String]) -> String {
+	let mut masked   = String::with_capacity(text.len());
+	let mut rest     = text;
+	let mut boundary = true;
+	while let Some(next) = rest.chars().next() {
+		let parameter = names.iter().filter(|_| boundary).find_map(|name| {
+			rest.strip_prefix(name.as_str()).and_then(|after| after.strip_prefix('=')).map(|value| (name, value))
+		});
+		if let Some((name, value)) = parameter {
+			let end  = value.find(|c: char| matches!(c, '&' | '#' | '"' | '\'') || c.is_whitespace()).unwrap_or(value.len());
+			masked.push_str(name);
+			masked.push('=');
+			masked.push_str(MASK);
+			rest     = value.get(end..).unwrap_or_default();
+			boundary = false;
+		} else {
+			masked.push(next);
+			rest     = rest.get(next.len_utf8()..).unwrap_or_default();
+			boundary = matches!(next, '?' | '&') || next.is_whitespace();
+		}
+	}
+	masked
+}
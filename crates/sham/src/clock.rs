@@ -0,0 +1,378 @@
+//! This module provides a virtual clock in order to test time-dependent code.
+//! 
+//! This module provides a clock that only moves when told to, so that code that
+//! depends on the passage of time, such as token expiry, caching, and retry
+//! back-off, can be tested instantly and deterministically. This is important
+//! because unit tests should not wait for real time to pass, and should not
+//! give different results depending on when they are run.
+//! 
+//! The approach taken is that the "real" code should obtain the current time
+//! from a clock that it is given, rather than calling [`SystemTime::now()`]
+//! directly, and the test code then supplies a [`MockClock`] and advances it
+//! as required. Clones of a [`MockClock`] share the same time, so the test can
+//! keep one and hand another to the code under test, or to other mocks in this
+//! crate that are driven by virtual time.
+//! 
+//! Asynchronous code can wait on the clock using [`MockClock::sleep()`]. Each
+//! wait registers its deadline with the clock, and once no other task has
+//! registered a wait for a full round of polling, the clock is advanced to the
+//! earliest deadline, in the same way that the paused time of an async runtime
+//! moves forward when every task is waiting. Concurrent waits therefore
+//! overlap, rather than adding up.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use core::time::Duration;
+//! use sham::clock::MockClock;
+//! use std::time::UNIX_EPOCH;
+//! 
+//! let clock = MockClock::at(UNIX_EPOCH + Duration::from_secs(1_000));
+//! let other = clock.clone();
+//! 
+//! clock.advance(Duration::from_secs(30));
+//! assert_eq!(other.unix_timestamp(), 1_030);
+//! assert_eq!(other.elapsed(), Duration::from_secs(30));
+//! ```
+//! 
+//! Waiting concurrently:
+//! 
+//! ```rust
+//! use core::time::Duration;
+//! use futures_executor::block_on;
+//! use sham::clock::MockClock;
+//! use std::thread;
+//! 
+//! let clock = MockClock::new();
+//! let waits = [50, 80, 30].map(|millis| clock.sleep(Duration::from_millis(millis)));
+//! thread::scope(|scope| {
+//!     for wait in waits {
+//!         scope.spawn(move || block_on(wait));
+//!     }
+//! });
+//! assert_eq!(clock.elapsed(), Duration::from_millis(80));
+//! ```
+//! 
+
+
+
+//		Packages
+
+use core::{
+	future::Future,
+	pin::Pin,
+	task::{Context, Poll, Waker},
+	time::Duration,
+};
+use std::{
+	sync::{Arc, Mutex, MutexGuard, PoisonError},
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+
+
+//		Structs
+
+//		ClockSleep																
+/// A wait on a [`MockClock`], returned by [`MockClock::sleep()`].
+struct ClockSleep {
+	//		Private properties													
+	/// The time at which the wait completes.
+	deadline: SystemTime,
+	
+	/// The identifier of the wait, once it has been registered with the
+	/// clock.
+	id:       Option<u64>,
+	
+	/// The generation of the clock when the wait last found itself to be the
+	/// earliest, if it has.
+	seen:     Option<u64>,
+	
+	/// The state of the clock.
+	state:    Arc<Mutex<ClockState>>,
+}
+
+//󰭅		Drop																	
+impl Drop for ClockSleep {
+	//		drop																
+	fn drop(&mut self) {
+		if let Some(id) = self.id.take() {
+			let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+			state.remove(id);
+			state.wake_earliest();
+		}
+	}
+}
+
+//󰭅		Future																	
+impl Future for ClockSleep {
+	type Output = ();
+	
+	//		poll																
+	fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		let this = &mut *self;
+		this.state.lock().unwrap_or_else(PoisonError::into_inner).poll_wait(
+			this.deadline,
+			&mut this.id,
+			&mut this.seen,
+			cx.waker(),
+		)
+	}
+}
+
+//		ClockState																
+/// The state of a [`MockClock`], shared between clones.
+#[derive(Debug)]
+struct ClockState {
+	//		Private properties													
+	/// A count that changes whenever a wait is registered or the clock is
+	/// moved by a wait, so that a wait can tell whether anything happened
+	/// during a round of polling.
+	generation: u64,
+	
+	/// The identifier to give to the next wait.
+	next_id:    u64,
+	
+	/// The current time.
+	now:        SystemTime,
+	
+	/// The waits that are pending.
+	timers:     Vec<ClockTimer>,
+}
+
+//󰭅		ClockState																
+impl ClockState {
+	//		earliest															
+	/// Returns the pending wait with the earliest deadline, with ties going to
+	/// the one registered first.
+	fn earliest(&self) -> Option<&ClockTimer> {
+		self.timers.iter().min_by_key(|timer| (timer.deadline, timer.id))
+	}
+	
+	//		poll_wait															
+	/// Polls a wait, registering it with the clock the first time, and moving
+	/// the clock on to its deadline once it is the earliest wait and nothing
+	/// has changed since it was last polled.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `deadline` - The time at which the wait completes.
+	/// * `id`       - The identifier of the wait, if it has been registered.
+	/// * `seen`     - The generation last seen by the wait as the earliest.
+	/// * `waker`    - The waker of the task that is waiting.
+	/// 
+	fn poll_wait(
+		&mut self,
+		deadline: SystemTime,
+		id:       &mut Option<u64>,
+		seen:     &mut Option<u64>,
+		waker:    &Waker,
+	) -> Poll<()> {
+		if self.now >= deadline {
+			if let Some(current) = id.take() {
+				self.remove(current);
+				self.wake_earliest();
+			}
+			return Poll::Ready(());
+		}
+		let Some(current) = *id else {
+			let next         = self.next_id;
+			self.next_id    += 1;
+			self.generation += 1;
+			self.timers.push(ClockTimer { deadline, id: next, waker: waker.clone() });
+			*id              = Some(next);
+			waker.wake_by_ref();
+			return Poll::Pending;
+		};
+		if let Some(timer) = self.timers.iter_mut().find(|timer| timer.id == current) {
+			timer.waker.clone_from(waker);
+		}
+		if self.earliest().is_some_and(|timer| timer.id == current) {
+			if *seen == Some(self.generation) {
+				self.now         = deadline;
+				self.generation += 1;
+				self.remove(current);
+				*id              = None;
+				self.wake_due();
+				self.wake_earliest();
+				return Poll::Ready(());
+			}
+			*seen = Some(self.generation);
+			waker.wake_by_ref();
+		}
+		Poll::Pending
+	}
+	
+	//		remove																
+	/// Removes a pending wait.
+	fn remove(&mut self, id: u64) {
+		self.timers.retain(|timer| timer.id != id);
+	}
+	
+	//		wake_due															
+	/// Wakes the tasks of the waits whose deadlines have been reached.
+	fn wake_due(&self) {
+		for timer in self.timers.iter().filter(|timer| timer.deadline <= self.now) {
+			timer.waker.wake_by_ref();
+		}
+	}
+	
+	//		wake_earliest														
+	/// Wakes the task of the earliest pending wait, so that it can move the
+	/// clock on once nothing else is happening.
+	fn wake_earliest(&self) {
+		if let Some(timer) = self.earliest() {
+			timer.waker.wake_by_ref();
+		}
+	}
+}
+
+//		ClockTimer																
+/// A wait that is pending on a [`MockClock`].
+#[derive(Debug)]
+struct ClockTimer {
+	//		Private properties													
+	/// The time at which the wait completes.
+	deadline: SystemTime,
+	
+	/// The identifier of the wait.
+	id:       u64,
+	
+	/// The waker of the task that is waiting.
+	waker:    Waker,
+}
+
+//		MockClock																
+/// A virtual clock, which only moves when advanced.
+#[derive(Clone, Debug)]
+pub struct MockClock {
+	//		Private properties													
+	/// The time at which the clock was created.
+	start: SystemTime,
+	
+	/// The current time and the pending waits, shared between clones.
+	state: Arc<Mutex<ClockState>>,
+}
+
+//󰭅		MockClock																
+impl MockClock {
+	//		new																	
+	/// Creates a new clock, starting at the current real time.
+	/// 
+	/// Starting at the real time means that values derived from the clock,
+	/// such as token expiry times, remain plausible to libraries that also
+	/// consult the real time. Use [`at()`](MockClock::at()) when a fixed
+	/// starting point is needed.
+	/// 
+	#[must_use]
+	pub fn new() -> Self {
+		Self::at(SystemTime::now())
+	}
+	
+	//		at																	
+	/// Creates a new clock, starting at the given time.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `time` - The time to start at.
+	/// 
+	#[must_use]
+	pub fn at(time: SystemTime) -> Self {
+		Self {
+			start: time,
+			state: Arc::new(Mutex::new(ClockState {
+				generation: 0,
+				next_id:    0,
+				now:        time,
+				timers:     Vec::new(),
+			})),
+		}
+	}
+	
+	//		advance																
+	/// Moves the clock forward by the given duration.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `duration` - The amount of time to move forward by.
+	/// 
+	pub fn advance(&self, duration: Duration) {
+		let mut state = self.lock();
+		state.now    += duration;
+		state.wake_due();
+	}
+	
+	//		elapsed																
+	/// Returns the amount of time that the clock has moved since it was
+	/// created, or zero if it has been set to an earlier time.
+	#[must_use]
+	pub fn elapsed(&self) -> Duration {
+		self.now().duration_since(self.start).unwrap_or_default()
+	}
+	
+	//		now																	
+	/// Returns the current time.
+	#[must_use]
+	pub fn now(&self) -> SystemTime {
+		self.lock().now
+	}
+	
+	//		set																	
+	/// Sets the clock to the given time, which may be in the past.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `time` - The time to set.
+	/// 
+	pub fn set(&self, time: SystemTime) {
+		let mut state = self.lock();
+		state.now     = time;
+		state.wake_due();
+	}
+	
+	//		sleep																
+	/// Waits until the clock has moved forward by the given duration.
+	/// 
+	/// The wait completes as soon as the clock reaches its deadline, whether
+	/// it is advanced by the test or by another wait. Once the wait is the
+	/// earliest that is pending, and no wait has been registered with the
+	/// clock for a full round of polling, it advances the clock to its own
+	/// deadline, so that waits that run concurrently overlap, and the clock
+	/// only moves as far as the longest of them. A wait that is dropped before
+	/// it completes is removed from the clock.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `duration` - The time to wait.
+	/// 
+	pub fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send + 'static {
+		ClockSleep {
+			deadline: self.now() + duration,
+			id:       None,
+			seen:     None,
+			state:    Arc::clone(&self.state),
+		}
+	}
+	
+	//		unix_timestamp														
+	/// Returns the current time as a number of whole seconds since the Unix
+	/// epoch, or zero if the clock is set before it.
+	#[must_use]
+	pub fn unix_timestamp(&self) -> u64 {
+		self.now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+	}
+	
+	//		lock																
+	/// Locks the state of the clock.
+	fn lock(&self) -> MutexGuard<'_, ClockState> {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+}
+
+//󰭅		Default																	
+impl Default for MockClock {
+	//		default																
+	fn default() -> Self {
+		Self::new()
+	}
+}
@@ -0,0 +1,592 @@
+//! This module mocks configuration loading in order to test settings handling.
+//! 
+//! This module provides an in-memory stand-in for loading configuration from
+//! `.env` files in the style of [Dotenvy](https://crates.io/crates/dotenvy),
+//! from other configuration files, and from the process environment, with
+//! defaults beneath them all. This is important because unit tests should not
+//! write temporary files or mutate the environment of the test process, which
+//! is shared between tests running in parallel, and because the precedence
+//! between the sources is a common source of bugs.
+//! 
+//! The approach taken is that the "real" code should obtain its configuration
+//! through a loader that it is given, rather than by calling Dotenvy and
+//! [`std::env`](mod@std::env) directly, and the test code then supplies a [`MockConfig`]
+//! holding the environment, file contents, and defaults. The methods of
+//! [`MockConfig`] mirror the functions of Dotenvy, so loading a `.env` file
+//! sets only the variables that are not already set, unless an override is
+//! requested. Each variable remembers where its value came from, so that
+//! precedence rules can be asserted directly. Paths are matched using a
+//! [`MockPathMatcher`], so that fixtures written with forward slashes are found
+//! by code that uses backslashes on Windows, and vice versa.
+//! 
+//! Presets are provided for the environments of common container runtimes,
+//! such as [`MockConfig::docker()`] and [`MockConfig::kubernetes()`], with the
+//! environment variables, marker files, and cgroup contents that detection code
+//! looks for, along with [`MockConfig::host()`] for a machine that is not in a
+//! container at all. These allow the branches that only run inside containers
+//! to be tested anywhere. The file contents can be read with
+//! [`MockConfig::read_to_string()`], and marker files checked for with
+//! [`MockConfig::exists()`].
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use sham::config::{MockConfig, MockConfigSource};
+//! 
+//! let config = MockConfig::new()
+//!     .with_default("PORT", "80")
+//!     .with_default("LOG_LEVEL", "info")
+//!     .with_file(".env", "PORT=8080\nHOST=localhost # the local host\n")
+//!     .with_env("PORT", "9090")
+//! ;
+//! 
+//! config.dotenv().unwrap();
+//! assert_eq!(config.var("PORT").unwrap(), "9090");
+//! assert_eq!(config.source("PORT"), Some(MockConfigSource::Env));
+//! assert_eq!(config.var("HOST").unwrap(), "localhost");
+//! assert_eq!(config.source("HOST"), Some(MockConfigSource::File(".env".into())));
+//! assert_eq!(config.var("LOG_LEVEL").unwrap(), "info");
+//! assert_eq!(config.source("LOG_LEVEL"), Some(MockConfigSource::Default));
+//! assert!(config.var("MISSING").is_err());
+//! 
+//! let pod = MockConfig::kubernetes();
+//! assert!(pod.var("KUBERNETES_SERVICE_HOST").is_ok());
+//! assert!(pod.read_to_string("/proc/self/cgroup").unwrap().contains("kubepods"));
+//! assert!(!pod.exists("/.dockerenv"));
+//! assert!(MockConfig::docker().exists("/.dockerenv"));
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::path::MockPathMatcher;
+use core::{
+	error::Error,
+	fmt::{Display, Formatter, self},
+};
+use std::{
+	collections::BTreeMap,
+	path::{Path, PathBuf},
+	sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+
+
+//		Constants
+
+/// The hostname used by the container presets, which is the short form of
+/// [`CONTAINER_ID`], as Docker and Podman set it.
+const CONTAINER_HOSTNAME: &str = "3f4e8a1c9b2d";
+
+/// The ID of the container used by the container presets.
+const CONTAINER_ID: &str = "3f4e8a1c9b2d7e6f5a4b3c2d1e0f9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f";
+
+
+
+//		Enums
+
+//		MockConfigError															
+/// A mocked configuration error type.
+/// 
+/// This mirrors the variants of `dotenvy::Error`.
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockConfigError {
+	/// A variable was not present in any source.
+	EnvVar(String),
+	
+	/// A file could not be found.
+	Io(PathBuf),
+	
+	/// A line of a `.env` file could not be parsed.
+	LineParse(String, usize),
+}
+
+//󰭅		Display																	
+impl Display for MockConfigError {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match *self {
+			Self::EnvVar(ref key)            => write!(f, "environment variable not found: {key}"),
+			Self::Io(ref path)               => write!(f, "No such file or directory: {}", path.display()),
+			Self::LineParse(ref line, index) => write!(f, "Error parsing line: '{line}', error at line index: {index}"),
+		}
+	}
+}
+
+//󰭅		Error																	
+impl Error for MockConfigError {}
+
+//		MockConfigSource														
+/// Where the value of a variable came from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockConfigSource {
+	/// The value is a default.
+	Default,
+	
+	/// The value was set in the environment.
+	Env,
+	
+	/// The value was loaded from the given file.
+	File(PathBuf),
+}
+
+
+
+//		Structs
+
+//		ConfigState																
+/// The state of a [`MockConfig`] instance.
+#[derive(Debug, Default)]
+struct ConfigState {
+	/// The default values of variables.
+	defaults: BTreeMap<String, String>,
+	
+	/// The environment, with the source of each value.
+	env:      BTreeMap<String, (String, MockConfigSource)>,
+	
+	/// The contents of the files that can be loaded, by path.
+	files:    BTreeMap<PathBuf, String>,
+	
+	/// The files loaded so far, in order.
+	loaded:   Vec<PathBuf>,
+	
+	/// The matcher used to find files by path.
+	paths:    MockPathMatcher,
+}
+
+//󰭅		ConfigState																
+impl ConfigState {
+	//		file																
+	/// Returns the contents of the file that matches the given path, if any.
+	fn file(&self, path: &Path) -> Option<String> {
+		self.files.iter().find(|&(key, _)| self.paths.matches(key, path)).map(|(_, contents)| contents.clone())
+	}
+}
+
+//		MockConfig																
+/// A mocked configuration loader, backed by an in-memory environment and
+/// files.
+/// 
+/// Clones share the same state.
+/// 
+#[derive(Clone, Debug, Default)]
+pub struct MockConfig {
+	//		Private properties													
+	/// The state of the loader, shared between clones.
+	state: Arc<Mutex<ConfigState>>,
+}
+
+//󰭅		MockConfig																
+impl MockConfig {
+	//		new																	
+	/// Creates a new loader with an empty environment and no files.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+	
+	//		docker																
+	/// Creates a new loader for a Docker container, with the `/.dockerenv`
+	/// marker file, a `HOSTNAME` of the short container ID, and cgroups under
+	/// `/docker/` in `/proc/self/cgroup` and `/proc/1/cgroup`, in the cgroup v1
+	/// format.
+	#[must_use]
+	pub fn docker() -> Self {
+		let cgroup = format!("12:memory:/docker/{CONTAINER_ID}\n11:cpu,cpuacct:/docker/{CONTAINER_ID}\n1:name=systemd:/docker/{CONTAINER_ID}\n");
+		Self::new()
+			.with_env("HOSTNAME", CONTAINER_HOSTNAME)
+			.with_file("/.dockerenv", "")
+			.with_file("/proc/1/cgroup", &cgroup)
+			.with_file("/proc/self/cgroup", &cgroup)
+	}
+	
+	//		host																
+	/// Creates a new loader for a machine that is not in a container, with no
+	/// marker files, and the cgroups of a systemd session in `/proc/self/cgroup`
+	/// and `/proc/1/cgroup`, in the cgroup v2 format.
+	#[must_use]
+	pub fn host() -> Self {
+		Self::new()
+			.with_env("HOSTNAME", "workstation")
+			.with_file("/proc/1/cgroup", "0::/init.scope\n")
+			.with_file("/proc/self/cgroup", "0::/user.slice/user-1000.slice/session-1.scope\n")
+	}
+	
+	//		kubernetes															
+	/// Creates a new loader for a Kubernetes pod, with the service environment
+	/// variables, a `HOSTNAME` of the pod name, the service account files, and
+	/// cgroups under `/kubepods/` in `/proc/self/cgroup` and `/proc/1/cgroup`,
+	/// in the cgroup v1 format. There is no `/.dockerenv`, as with runtimes
+	/// such as containerd.
+	#[must_use]
+	pub fn kubernetes() -> Self {
+		let cgroup = format!("12:memory:/kubepods/burstable/pod5f2d1c3e-8a4b-4c6d-9e7f-0a1b2c3d4e5f/{CONTAINER_ID}\n1:name=systemd:/kubepods/burstable/pod5f2d1c3e-8a4b-4c6d-9e7f-0a1b2c3d4e5f/{CONTAINER_ID}\n");
+		Self::new()
+			.with_env("HOSTNAME", "web-7d4b9c8f6-x2k8p")
+			.with_env("KUBERNETES_PORT", "tcp://10.96.0.1:443")
+			.with_env("KUBERNETES_SERVICE_HOST", "10.96.0.1")
+			.with_env("KUBERNETES_SERVICE_PORT", "443")
+			.with_file("/proc/1/cgroup", &cgroup)
+			.with_file("/proc/self/cgroup", &cgroup)
+			.with_file("/var/run/secrets/kubernetes.io/serviceaccount/namespace", "default")
+			.with_file("/var/run/secrets/kubernetes.io/serviceaccount/token", "eyJhbGciOiJSUzI1NiJ9.e30.c2lnbmF0dXJl")
+	}
+	
+	//		podman																
+	/// Creates a new loader for a Podman container, with the `container`
+	/// environment variable, the `/run/.containerenv` marker file, and the
+	/// cgroup v2 format, in which `/proc/self/cgroup` shows only the root.
+	#[must_use]
+	pub fn podman() -> Self {
+		Self::new()
+			.with_env("HOSTNAME", CONTAINER_HOSTNAME)
+			.with_env("container", "podman")
+			.with_file("/proc/1/cgroup", "0::/\n")
+			.with_file("/proc/self/cgroup", "0::/\n")
+			.with_file("/run/.containerenv", &format!("engine=\"podman-4.9.3\"\nname=\"web\"\nid=\"{CONTAINER_ID}\"\nrootless=1\n"))
+	}
+	
+	//		with_default														
+	/// Sets the default value of a variable, used when no other source sets it.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `key`   - The name of the variable.
+	/// * `value` - The default value.
+	/// 
+	#[must_use]
+	pub fn with_default(self, key: &str, value: &str) -> Self {
+		drop(self.lock().defaults.insert(key.to_owned(), value.to_owned()));
+		self
+	}
+	
+	//		with_env															
+	/// Sets a variable in the environment, as if it had been set before the
+	/// process started.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `key`   - The name of the variable.
+	/// * `value` - The value.
+	/// 
+	#[must_use]
+	pub fn with_env(self, key: &str, value: &str) -> Self {
+		drop(self.lock().env.insert(key.to_owned(), (value.to_owned(), MockConfigSource::Env)));
+		self
+	}
+	
+	//		with_file															
+	/// Provides the contents of a file that can be loaded.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `path`     - The path of the file.
+	/// * `contents` - The contents of the file.
+	/// 
+	#[must_use]
+	pub fn with_file<P: AsRef<Path>>(self, path: P, contents: &str) -> Self {
+		let mut state = self.lock();
+		let paths     = state.paths;
+		state.files.retain(|key, _| !paths.matches(key, path.as_ref()));
+		drop(state.files.insert(path.as_ref().to_path_buf(), contents.to_owned()));
+		drop(state);
+		self
+	}
+	
+	//		with_path_matcher													
+	/// Sets the matcher used to find files by path. By default, paths are
+	/// normalised as described by [`MockPathMatcher::new()`].
+	/// 
+	/// # Parameters
+	/// 
+	/// * `matcher` - The matcher to use, such as [`MockPathMatcher::exact()`]
+	///   to turn normalisation off.
+	/// 
+	#[must_use]
+	pub fn with_path_matcher(self, matcher: MockPathMatcher) -> Self {
+		self.lock().paths = matcher;
+		self
+	}
+	
+	//		dotenv																
+	/// Loads the `.env` file, setting only the variables that are not already
+	/// set, and returns its path.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the file does not exist or cannot be
+	/// parsed.
+	/// 
+	/// # See also
+	/// 
+	/// * `dotenvy::dotenv()`
+	/// 
+	pub fn dotenv(&self) -> Result<PathBuf, MockConfigError> {
+		self.load(Path::new(".env"), false)
+	}
+	
+	//		dotenv_override														
+	/// Loads the `.env` file, replacing any variables that are already set, and
+	/// returns its path.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the file does not exist or cannot be
+	/// parsed.
+	/// 
+	/// # See also
+	/// 
+	/// * `dotenvy::dotenv_override()`
+	/// 
+	pub fn dotenv_override(&self) -> Result<PathBuf, MockConfigError> {
+		self.load(Path::new(".env"), true)
+	}
+	
+	//		exists																
+	/// Whether a file exists, for marker files whose presence matters rather
+	/// than their contents. Unlike reading a file, this does not record it as
+	/// loaded.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `path` - The path of the file.
+	/// 
+	/// # See also
+	/// 
+	/// * [`std::path::Path::exists()`]
+	/// 
+	#[must_use]
+	pub fn exists<P: AsRef<Path>>(&self, path: P) -> bool {
+		self.lock().file(path.as_ref()).is_some()
+	}
+	
+	//		from_filename														
+	/// Loads the given `.env`-style file, setting only the variables that are
+	/// not already set, and returns its path.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `path` - The path of the file.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the file does not exist or cannot be
+	/// parsed.
+	/// 
+	/// # See also
+	/// 
+	/// * `dotenvy::from_filename()`
+	/// 
+	pub fn from_filename<P: AsRef<Path>>(&self, path: P) -> Result<PathBuf, MockConfigError> {
+		self.load(path.as_ref(), false)
+	}
+	
+	//		from_filename_override												
+	/// Loads the given `.env`-style file, replacing any variables that are
+	/// already set, and returns its path.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `path` - The path of the file.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the file does not exist or cannot be
+	/// parsed.
+	/// 
+	/// # See also
+	/// 
+	/// * `dotenvy::from_filename_override()`
+	/// 
+	pub fn from_filename_override<P: AsRef<Path>>(&self, path: P) -> Result<PathBuf, MockConfigError> {
+		self.load(path.as_ref(), true)
+	}
+	
+	//		loaded_files														
+	/// Returns the files loaded so far, in order.
+	#[must_use]
+	pub fn loaded_files(&self) -> Vec<PathBuf> {
+		self.lock().loaded.clone()
+	}
+	
+	//		read_to_string														
+	/// Returns the contents of a file, for configuration formats that are
+	/// parsed by the code under test.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `path` - The path of the file.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the file does not exist.
+	/// 
+	/// # See also
+	/// 
+	/// * [`std::fs::read_to_string()`]
+	/// 
+	pub fn read_to_string<P: AsRef<Path>>(&self, path: P) -> Result<String, MockConfigError> {
+		let mut state = self.lock();
+		let contents  = state.file(path.as_ref()).ok_or_else(|| MockConfigError::Io(path.as_ref().to_path_buf()))?;
+		state.loaded.push(path.as_ref().to_path_buf());
+		drop(state);
+		Ok(contents)
+	}
+	
+	//		set_var																
+	/// Sets a variable in the environment, as the code under test would with
+	/// [`std::env::set_var()`].
+	/// 
+	/// # Parameters
+	/// 
+	/// * `key`   - The name of the variable.
+	/// * `value` - The value.
+	/// 
+	pub fn set_var(&self, key: &str, value: &str) {
+		drop(self.lock().env.insert(key.to_owned(), (value.to_owned(), MockConfigSource::Env)));
+	}
+	
+	//		source																
+	/// Returns where the value of a variable came from, if it is set.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `key` - The name of the variable.
+	/// 
+	#[must_use]
+	pub fn source(&self, key: &str) -> Option<MockConfigSource> {
+		let state = self.lock();
+		state.env.get(key).map(|entry| entry.1.clone())
+			.or_else(|| state.defaults.contains_key(key).then_some(MockConfigSource::Default))
+	}
+	
+	//		var																	
+	/// Returns the value of a variable, from the environment, any loaded files,
+	/// or the defaults, in that order of precedence.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `key` - The name of the variable.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the variable is not set by any source.
+	/// 
+	/// # See also
+	/// 
+	/// * `dotenvy::var()`
+	/// 
+	pub fn var(&self, key: &str) -> Result<String, MockConfigError> {
+		let state = self.lock();
+		state.env.get(key).map(|entry| entry.0.clone())
+			.or_else(|| state.defaults.get(key).cloned())
+			.ok_or_else(|| MockConfigError::EnvVar(key.to_owned()))
+	}
+	
+	//		vars																
+	/// Returns all variables and their values, including defaults, ordered by
+	/// name.
+	/// 
+	/// # See also
+	/// 
+	/// * `dotenvy::vars()`
+	/// 
+	#[must_use]
+	pub fn vars(&self) -> Vec<(String, String)> {
+		let state    = self.lock();
+		let mut vars = state.defaults.clone();
+		vars.extend(state.env.iter().map(|(key, entry)| (key.clone(), entry.0.clone())));
+		drop(state);
+		vars.into_iter().collect()
+	}
+	
+	//		load																
+	/// Loads a `.env`-style file into the environment.
+	fn load(&self, path: &Path, override_existing: bool) -> Result<PathBuf, MockConfigError> {
+		let mut state = self.lock();
+		let contents  = state.file(path).ok_or_else(|| MockConfigError::Io(path.to_path_buf()))?;
+		let mut parsed: BTreeMap<String, String> = BTreeMap::new();
+		for line in contents.lines() {
+			let Some((key, value)) = parse_line(line, |name| {
+				parsed.get(name).cloned().or_else(|| state.env.get(name).map(|entry| entry.0.clone()))
+			})? else {
+				continue;
+			};
+			drop(parsed.insert(key, value));
+		}
+		for (key, value) in parsed {
+			if override_existing || !state.env.contains_key(&key) {
+				drop(state.env.insert(key, (value, MockConfigSource::File(path.to_path_buf()))));
+			}
+		}
+		state.loaded.push(path.to_path_buf());
+		drop(state);
+		Ok(path.to_path_buf())
+	}
+	
+	//		lock																
+	/// Locks the state of the loader.
+	fn lock(&self) -> MutexGuard<'_, ConfigState> {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+}
+
+
+
+//		Functions
+
+//		expand																	
+/// Expands `$NAME` and `${NAME}` references in a value, using the given lookup,
+/// and replacing unknown names with nothing.
+fn expand<F: Fn(&str) -> Option<String>>(value: &str, lookup: &F) -> String {
+	let mut output = String::with_capacity(value.len());
+	let mut rest   = value;
+	while let Some((before, after)) = rest.split_once('$') {
+		output.push_str(before);
+		let (name, remainder) = after.strip_prefix('{').map_or_else(
+			|| after.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).map_or((after, ""), |end| after.split_at(end)),
+			|braced| braced.split_once('}').unwrap_or((braced, "")),
+		);
+		if name.is_empty() {
+			output.push('$');
+		} else {
+			output.push_str(&lookup(name).unwrap_or_default());
+		}
+		rest = remainder;
+	}
+	output.push_str(rest);
+	output
+}
+
+//		parse_line																
+/// Parses a line of a `.env` file, returning [`None`] for blank lines and
+/// comments.
+fn parse_line<F: Fn(&str) -> Option<String>>(line: &str, lookup: F) -> Result<Option<(String, String)>, MockConfigError> {
+	let trimmed = line.trim();
+	if trimmed.is_empty() || trimmed.starts_with('#') {
+		return Ok(None);
+	}
+	let statement = trimmed.strip_prefix("export ").unwrap_or(trimmed).trim_start();
+	let Some((name, assigned)) = statement.split_once('=') else {
+		return Err(MockConfigError::LineParse(line.to_owned(), statement.len()));
+	};
+	let key       = name.trim();
+	if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.')) {
+		return Err(MockConfigError::LineParse(line.to_owned(), 0));
+	}
+	let raw       = assigned.trim();
+	let unclosed  = || MockConfigError::LineParse(line.to_owned(), line.len());
+	let value     = if let Some(quoted) = raw.strip_prefix('\'') {
+		quoted.split_once('\'').ok_or_else(unclosed)?.0.to_owned()
+	} else if let Some(quoted) = raw.strip_prefix('"') {
+		expand(&quoted.split_once('"').ok_or_else(unclosed)?.0.replace("\\n", "\n"), &lookup)
+	} else {
+		expand(raw.split_once(" #").map_or(raw, |(unquoted, _)| unquoted).trim_end(), &lookup)
+	};
+	Ok(Some((key.to_owned(), value)))
+}
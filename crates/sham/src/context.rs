@@ -0,0 +1,1231 @@
+//! This module provides a per-test context in order to verify mocks together.
+//! 
+//! This module provides a [`Context`] with which the checks for the mocks used
+//! by a test can be registered, so that they are all run at the end of the test
+//! and any failures are reported together. This is important because each mock
+//! otherwise verifies itself when it is dropped, and the first one to fail
+//! panics and hides the others, which makes it hard to see everything that
+//! went wrong.
+//! 
+//! The approach taken is that a check is a function that returns an error
+//! message if the expectations of a mock were not met. Checks that panic, such
+//! as calls to the `checkpoint()` function of a [`mockall`](https://crates.io/crates/mockall)
+//! mock, are caught, and their panic messages are reported as failures. The
+//! `test` attribute, when the `macros` feature is enabled, creates a context
+//! for each test, passes it to the test function, and verifies it once the
+//! test has finished, which removes the need for setup and teardown code in
+//! every test.
+//! 
+//! Mocks and fixtures that are set up but never used can also be detected, by
+//! setting a [`MockUnusedPolicy`] with [`Context::set_unused_policy()`]. The
+//! routes added to the context are tracked automatically, and other fixtures
+//! can be tracked with [`Context::register_fixture()`]. When the context is
+//! verified, any that were not used are reported as a warning or as a failure,
+//! which helps to keep large test suites free of dead fixtures.
+//! 
+//! A context can also be entered on the current thread, using
+//! [`Context::enter()`], so that [`verify_all()`] can verify it without being
+//! given it, and so that it is verified automatically when the returned
+//! [`ContextGuard`] is dropped. Mocks that are created while a context is
+//! entered, such as the clients from `reqwest::create_mock_client()` and the
+//! commands from `std_process::FakeCommand::new()`, register the checks for
+//! their expectations with it, so that any that were not met are reported when
+//! it is verified, rather than by a panic when the mock is dropped. Futures
+//! that may run on other threads can carry the context with them by being
+//! wrapped with [`Context::scope()`]. Entered contexts are kept apart per
+//! thread, so that tests running in parallel do not see each other's mocks.
+//! 
+//! The context also keeps a record of the interactions with the mocks, such as
+//! the HTTP requests sent, the commands run, and the sockets connected to,
+//! which the mocks add to the context that is entered on the current thread.
+//! When the `report` feature is enabled, a report of these can be written to a
+//! JSON file under `target/sham-reports/` when the test finishes, whether it
+//! passes or fails, which helps with debugging flaky tests in CI.
+//! 
+//! The context also gives access to the mocks of whichever modules are enabled,
+//! such as the virtual clock, the configuration and environment, the routes of
+//! the HTTP client, and the recorders for logs, metrics, and traces. This means
+//! that helper functions can be given the one context rather than each of the
+//! mocks separately, and that scenarios spanning several modules, such as HTTP
+//! retries driven by virtual time, can be configured in one place. The mocks
+//! are shared by all clones of the context, and live as long as it does.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use sham::Context;
+//! 
+//! let context = Context::new();
+//! context.register("cache warmed", || Ok(()));
+//! context.register("token refreshed", || Err("refresh was never called".to_owned()));
+//! context.register("mock client", || panic!("expectation not satisfied"));
+//! 
+//! let error = context.verify().unwrap_err();
+//! assert_eq!(error.failures.len(), 2);
+//! assert_eq!(error.failures[0].name, "token refreshed");
+//! ```
+//! 
+
+
+
+//		Packages
+
+#[cfg(feature = "capture")]
+use crate::capture::Redactor;
+#[cfg(feature = "clock")]
+use crate::clock::MockClock;
+#[cfg(feature = "config")]
+use crate::config::MockConfig;
+#[cfg(feature = "log")]
+use crate::log::MockLogs;
+#[cfg(feature = "metrics")]
+use crate::metrics::MockMetrics;
+#[cfg(feature = "reqwest")]
+use crate::reqwest::{MockClient, MockRoute, create_routed_mock_client};
+#[cfg(feature = "sentry")]
+use crate::sentry::MockSentry;
+#[cfg(feature = "throttle")]
+use crate::throttle::MockThrottleObserver;
+#[cfg(feature = "tracing")]
+use crate::tracing::MockTracing;
+#[cfg(feature = "reqwest")]
+use reqwest::{Url, header::HeaderValue};
+#[cfg(feature = "report")]
+use serde::Serialize;
+#[cfg(feature = "report")]
+use serde_json::to_vec_pretty;
+use crate::{
+	scope::{CONTEXT, ScopeGuard},
+	seed::{SEED_VARIABLE, initial_seed},
+};
+use core::{
+	error::Error,
+	future::Future,
+	fmt::{Debug, Display, Formatter, self},
+	mem::take,
+	panic::AssertUnwindSafe,
+};
+#[cfg(all(feature = "macros", not(feature = "tokio")))]
+use core::{
+	pin::pin,
+	task::{Context as TaskContext, Poll, Waker},
+};
+#[cfg(any(feature = "log", feature = "tracing"))]
+use std::sync::OnceLock;
+#[cfg(any(feature = "report", feature = "reqwest", feature = "std_process"))]
+use std::env;
+#[cfg(any(feature = "reqwest", feature = "std_process"))]
+use std::io::{IsTerminal, stderr};
+#[cfg(feature = "report")]
+use std::{
+	fs,
+	io::{Error as IoError, Result as IoResult},
+	path::PathBuf,
+};
+use std::{
+	panic::catch_unwind,
+	sync::{Arc, Mutex, MutexGuard, PoisonError},
+	thread,
+};
+#[cfg(all(feature = "macros", not(feature = "tokio")))]
+use std::{task::Wake, thread::Thread};
+#[cfg(all(feature = "macros", feature = "tokio"))]
+use tokio::runtime::Builder;
+
+
+
+//		Types
+
+/// A function that checks whether the expectations of a mock were met.
+type Check = Box<dyn FnOnce() -> Result<(), String> + Send>;
+
+/// A function that reports whether a fixture has been used.
+type Fixture = Box<dyn FnOnce() -> bool + Send>;
+
+
+
+//		Enums
+
+//		MockUnusedPolicy														
+/// What to do about mocks and fixtures that were set up but never used.
+/// 
+/// # See also
+/// 
+/// * [`Context::set_unused_policy()`]
+/// 
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockUnusedPolicy {
+	/// Unused mocks and fixtures are not checked for. This is the default.
+	#[default]
+	Ignore,
+	
+	/// Unused mocks and fixtures are listed in a warning printed to standard
+	/// error, but verification still succeeds.
+	Warn,
+	
+	/// Unused mocks and fixtures are reported as a verification failure.
+	Fail,
+}
+
+
+
+//		Structs
+
+//		Context																	
+/// A per-test context, with which the checks for mocks are registered.
+/// 
+/// Clones of a [`Context`] share the same checks and mocks, so that a clone can
+/// be given to helper functions or to the code under test.
+/// 
+#[derive(Clone, Default)]
+pub struct Context {
+	//		Private properties													
+	/// The state shared by all clones of the context.
+	state: Arc<ContextState>,
+}
+
+//󰭅		Context																	
+impl Context {
+	//		new																	
+	/// Creates a new, empty context.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+	
+	//		add_route															
+	/// Adds a route to be served by the clients created from the context.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `route` - The route to add. Routes are matched in the order they were
+	///   added.
+	/// 
+	#[cfg(feature = "reqwest")]
+	pub fn add_route(&self, route: MockRoute) {
+		let tracked = route.clone();
+		self.register_fixture(format!("{route:?}"), move || tracked.hits() > 0);
+		self.state.routes.lock().unwrap_or_else(PoisonError::into_inner).push(route);
+	}
+	
+	//		assert_user_agent													
+	/// Checks that every request sent by the mocked Reqwest clients while the
+	/// context was entered identified itself with the given `User-Agent`.
+	/// 
+	/// The `User-Agent` of each request is the one set on the request, or
+	/// otherwise the [default](Context::set_default_user_agent()) of the
+	/// context.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `expected` - The `User-Agent` that every request should have sent.
+	/// 
+	/// # Panics
+	/// 
+	/// Panics with a list of the requests that sent a different `User-Agent`,
+	/// or none at all, if there are any.
+	/// 
+	/// # Examples
+	/// 
+	/// ```rust,should_panic
+	/// use reqwest::{StatusCode, header::USER_AGENT};
+	/// use sham::{Context, reqwest::{MockResponse, MockRoute, create_mock_response}};
+	/// use std::collections::HashMap;
+	/// 
+	/// # futures_executor::block_on(async {
+	/// let context = Context::new();
+	/// context.set_default_user_agent("my-library/1.0");
+	/// context.add_route(MockRoute::new(reqwest::Method::GET, "https://api.example.com/", |request| {
+	///     Ok(create_mock_response(request.url.clone(), StatusCode::OK, None::<&str>, None, HashMap::<String, String>::new(), Ok(b"")))
+	/// }));
+	/// let _guard  = context.enter();
+	/// let client  = context.client();
+	/// 
+	/// drop(client.get("https://api.example.com/").send().await);
+	/// drop(client.get("https://api.example.com/").header(USER_AGENT, "curl/8.0").send().await);
+	/// 
+	/// assert_eq!(context.user_agents(), vec![Some("my-library/1.0".to_owned()), Some("curl/8.0".to_owned())]);
+	/// context.assert_user_agent("my-library/1.0");
+	/// # });
+	/// ```
+	/// 
+	#[cfg(feature = "reqwest")]
+	pub fn assert_user_agent(&self, expected: &str) {
+		let sent       = self.state.user_agents.lock().unwrap_or_else(PoisonError::into_inner).clone();
+		let mismatched = sent.iter()
+			.filter(|entry| entry.1.as_deref() != Some(expected))
+			.map(|entry| entry.1.as_ref().map_or_else(
+				|| format!("\n  {}: no User-Agent", entry.0),
+				|agent| format!("\n  {}: `{agent}`", entry.0),
+			))
+			.collect::<String>()
+		;
+		assert!(
+			mismatched.is_empty(),
+			"Expected every request to send the User-Agent `{expected}`, but these did not:{mismatched}",
+		);
+	}
+	
+	//		assert_verified														
+	/// Verifies the context, panicking if any of the checks failed.
+	/// 
+	/// # Panics
+	/// 
+	/// Panics with a message listing every failure, if any of the checks
+	/// failed.
+	/// 
+	/// # See also
+	/// 
+	/// * [`Context::verify()`]
+	/// 
+	pub fn assert_verified(&self) {
+		if let Err(error) = self.verify() {
+			panic!("{error}");
+		}
+	}
+	
+	//		base_url															
+	/// The URL against which relative URLs in fixtures are resolved, if one has
+	/// been set.
+	/// 
+	/// # See also
+	/// 
+	/// * [`Context::set_base_url()`]
+	/// 
+	#[cfg(feature = "reqwest")]
+	#[must_use]
+	pub fn base_url(&self) -> Option<Url> {
+		self.state.base_url.lock().unwrap_or_else(PoisonError::into_inner).clone()
+	}
+	
+	//		check_unused														
+	/// Checks the registered fixtures for use, according to the policy that has
+	/// been set, returning a failure if required.
+	#[expect(clippy::print_stderr, reason = "Warnings are shown alongside the test output")]
+	fn check_unused(&self) -> Option<MockVerificationFailure> {
+		let fixtures = take(&mut *self.state.fixtures.lock().unwrap_or_else(PoisonError::into_inner));
+		let policy   = *self.state.unused.lock().unwrap_or_else(PoisonError::into_inner);
+		if policy == MockUnusedPolicy::Ignore {
+			return None;
+		}
+		let unused = fixtures.into_iter()
+			.filter_map(|(name, used)| (!used()).then_some(name))
+			.collect::<Vec<_>>()
+		;
+		if unused.is_empty() {
+			return None;
+		}
+		let message = format!("set up but never used: {}", unused.join(", "));
+		if policy == MockUnusedPolicy::Warn {
+			eprintln!("warning: sham: mocks {message}");
+			return None;
+		}
+		Some(MockVerificationFailure { name: "unused mocks".to_owned(), message })
+	}
+	
+	//		client																
+	/// Creates a mock HTTP client that serves the routes added so far.
+	/// 
+	/// Routes added after the client has been created are not served by it.
+	/// 
+	/// # Examples
+	/// 
+	/// ```rust
+	/// use futures_executor::block_on;
+	/// use reqwest::{Method, StatusCode};
+	/// use sham::{Context, reqwest::{MockRoute, create_mock_response}};
+	/// use std::collections::HashMap;
+	/// 
+	/// let context = Context::new();
+	/// context.add_route(MockRoute::new(Method::GET, "https://example.com/health", |request| {
+	///     Ok(create_mock_response(
+	///         request.url.as_str(),
+	///         StatusCode::OK,
+	///         Some("text/plain"),
+	///         None,
+	///         HashMap::<String, String>::new(),
+	///         Ok(b"ok"),
+	///     ))
+	/// }));
+	/// 
+	/// let client   = context.client();
+	/// let response = block_on(client.get("https://example.com/health").send()).unwrap();
+	/// assert_eq!(response.status(), StatusCode::OK);
+	/// ```
+	/// 
+	/// # See also
+	/// 
+	/// * [`create_routed_mock_client()`]
+	/// 
+	#[cfg(feature = "reqwest")]
+	#[must_use]
+	pub fn client(&self) -> MockClient {
+		let routes = self.state.routes.lock().unwrap_or_else(PoisonError::into_inner).clone();
+		create_routed_mock_client(routes)
+	}
+	
+	//		clock																
+	/// The virtual clock, which is shared by everything set up from the
+	/// context.
+	/// 
+	/// Mocks that are driven by virtual time should be given this clock, so
+	/// that advancing it affects all of them together.
+	/// 
+	#[cfg(feature = "clock")]
+	#[must_use]
+	pub fn clock(&self) -> &MockClock {
+		&self.state.clock
+	}
+	
+	//		config																
+	/// The mocked configuration and environment variables.
+	#[cfg(feature = "config")]
+	#[must_use]
+	pub fn config(&self) -> &MockConfig {
+		&self.state.config
+	}
+	
+	//		current																
+	/// The context that has been entered on the current thread, if any.
+	/// 
+	/// # See also
+	/// 
+	/// * [`Context::enter()`]
+	/// 
+	#[must_use]
+	pub fn current() -> Option<Self> {
+		CONTEXT.current()
+	}
+	
+	//		default_user_agent													
+	/// The `User-Agent` sent by the mocked Reqwest clients for requests that do
+	/// not set one, if a default has been set.
+	/// 
+	/// # See also
+	/// 
+	/// * [`Context::set_default_user_agent()`]
+	/// 
+	#[cfg(feature = "reqwest")]
+	#[must_use]
+	pub fn default_user_agent(&self) -> Option<HeaderValue> {
+		self.state.default_user_agent.lock().unwrap_or_else(PoisonError::into_inner).clone()
+	}
+	
+	//		enable_report														
+	/// Enables writing a report of the interactions with the mocks when the
+	/// guard returned by [`enter()`](Context::enter()) is dropped.
+	/// 
+	/// The report is written to `sham-reports/<name>.json` beneath the target
+	/// directory, which is `$CARGO_TARGET_DIR` if set, or otherwise `target`
+	/// beneath the current directory. It is written whether the test passes or
+	/// fails, and lists the interactions in the order they happened, along with
+	/// any failed checks. Characters that are not safe in a filename are
+	/// replaced in the name.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the report, which is usually the path of the
+	///   test.
+	/// 
+	#[cfg(feature = "report")]
+	pub fn enable_report(&self, name: &str) {
+		*self.state.report.lock().unwrap_or_else(PoisonError::into_inner) = Some(name.to_owned());
+	}
+	
+	//		enter																
+	/// Enters the context on the current thread, until the returned guard is
+	/// dropped.
+	/// 
+	/// While the context is entered, it is returned by [`Context::current()`]
+	/// and verified by [`verify_all()`]. When the guard is dropped, the context
+	/// that was previously entered, if any, is restored, and this context is
+	/// verified, panicking with a list of every failure if any of its checks
+	/// failed. Verification on drop is skipped if the thread is already
+	/// panicking, so that the original panic is not hidden.
+	/// 
+	/// # Examples
+	/// 
+	/// ```rust,should_panic
+	/// use sham::Context;
+	/// 
+	/// let context = Context::new();
+	/// let guard   = context.enter();
+	/// Context::current().unwrap().register("token refreshed", || Err("never called".to_owned()));
+	/// drop(guard);
+	/// ```
+	/// 
+	pub fn enter(&self) -> ContextGuard {
+		ContextGuard { context: self.clone(), scope: Some(CONTEXT.enter(self.clone())) }
+	}
+	
+	//		interactions														
+	/// Returns the interactions recorded so far, in order.
+	#[must_use]
+	pub fn interactions(&self) -> Vec<MockInteraction> {
+		self.state.interactions.lock().unwrap_or_else(PoisonError::into_inner).clone()
+	}
+	
+	//		lock																
+	/// Locks the registered checks, recovering from poisoning.
+	fn lock(&self) -> MutexGuard<'_, Vec<(String, Check)>> {
+		self.state.checks.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+	
+	//		logs																
+	/// The log records captured while the context is alive.
+	/// 
+	/// Capturing starts on the current thread the first time this is called.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if a different global logger has already been
+	/// installed.
+	/// 
+	/// # See also
+	/// 
+	/// * [`MockLogs::capture()`]
+	/// 
+	#[cfg(feature = "log")]
+	#[must_use]
+	pub fn logs(&self) -> &MockLogs {
+		self.state.logs.get_or_init(MockLogs::capture)
+	}
+	
+	//		metrics																
+	/// The recorder for metrics, which needs to be installed on the threads
+	/// that emit them.
+	#[cfg(feature = "metrics")]
+	#[must_use]
+	pub fn metrics(&self) -> &MockMetrics {
+		&self.state.metrics
+	}
+	
+	//		pending																
+	/// The names of the checks that have been registered and not yet run.
+	#[must_use]
+	pub fn pending(&self) -> Vec<String> {
+		self.lock().iter().map(|check| check.0.clone()).collect()
+	}
+	
+	//		print_seed															
+	/// Prints the seed of the context to standard error, if it was chosen
+	/// rather than set, and has been used, so that a failed test can be
+	/// replayed.
+	#[expect(clippy::print_stderr, reason = "The seed is shown alongside the test output")]
+	fn print_seed(&self) {
+		let chosen = *self.state.seed.lock().unwrap_or_else(PoisonError::into_inner);
+		if let Some((seed, false)) = chosen {
+			eprintln!("The seed of this test was {seed}. Set {SEED_VARIABLE}={seed} to replay it.");
+		}
+	}
+	
+	//		record																
+	/// Records an interaction with a mock.
+	/// 
+	/// The mocks in this crate record their interactions with the context that
+	/// is entered on the current thread, and other mocks can do the same. When
+	/// the `capture` feature is enabled, the action and the outcome are
+	/// redacted by the redactor of the context before they are recorded.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `module`  - The module of the mock, such as `reqwest`.
+	/// * `action`  - What was done, such as the method and URL of a request.
+	/// * `outcome` - What happened, such as the status of the response.
+	/// 
+	pub fn record(&self, module: &str, action: &str, outcome: &str) {
+		let interaction = MockInteraction {
+			module:  module.to_owned(),
+			action:  action.to_owned(),
+			outcome: outcome.to_owned(),
+		};
+		#[cfg(feature = "capture")]
+		let recorded    = self.redactor().redact_interaction(&interaction);
+		#[cfg(not(feature = "capture"))]
+		let recorded    = interaction;
+		self.state.interactions.lock().unwrap_or_else(PoisonError::into_inner).push(recorded);
+	}
+	
+	//		record_user_agent													
+	/// Records the `User-Agent` sent with a request by a mocked Reqwest client.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `request`    - The method and URL of the request.
+	/// * `user_agent` - The `User-Agent` that was sent, if any.
+	/// 
+	#[cfg(feature = "reqwest")]
+	pub(crate) fn record_user_agent(&self, request: &str, user_agent: Option<String>) {
+		self.state.user_agents.lock().unwrap_or_else(PoisonError::into_inner).push((request.to_owned(), user_agent));
+	}
+	
+	//		redactor															
+	/// The redactor that is applied to the interactions as they are recorded,
+	/// which masks nothing unless it has been set.
+	/// 
+	/// # See also
+	/// 
+	/// * [`Context::set_redactor()`]
+	/// 
+	#[cfg(feature = "capture")]
+	pub fn redactor(&self) -> Redactor {
+		self.state.redactor.lock().unwrap_or_else(PoisonError::into_inner).clone()
+	}
+	
+	//		register															
+	/// Registers a check to be run when the context is verified.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name`  - A name for the check, used when reporting failures.
+	/// * `check` - A function that returns an error message if the expectations
+	///   of the mock were not met. A panic is treated in the same way as an
+	///   error, using the panic message.
+	/// 
+	pub fn register<N, F>(&self, name: N, check: F)
+	where
+		N: Into<String>,
+		F: FnOnce() -> Result<(), String> + Send + 'static,
+	{
+		self.lock().push((name.into(), Box::new(check)));
+	}
+	
+	//		register_fixture													
+	/// Registers a mock or fixture to be checked for use when the context is
+	/// verified.
+	/// 
+	/// This has no effect unless a [`MockUnusedPolicy`] other than `Ignore` has
+	/// been set, in which case fixtures that were never used are reported when
+	/// the context is verified. The routes added with `Context::add_route()`,
+	/// when the `reqwest` feature is enabled, are registered automatically.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - A name for the fixture, used when reporting it as unused.
+	/// * `used` - A function that returns whether the fixture has been used.
+	/// 
+	/// # Examples
+	/// 
+	/// ```rust
+	/// use sham::{Context, MockUnusedPolicy};
+	/// use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
+	/// 
+	/// let context = Context::new();
+	/// context.set_unused_policy(MockUnusedPolicy::Fail);
+	/// let seeded  = Arc::new(AtomicBool::new(false));
+	/// let flag    = Arc::clone(&seeded);
+	/// context.register_fixture("seeded users", move || flag.load(Ordering::SeqCst));
+	/// context.register_fixture("seeded orders", || false);
+	/// seeded.store(true, Ordering::SeqCst);
+	/// 
+	/// let error = context.verify().unwrap_err();
+	/// assert_eq!(error.failures[0].name, "unused mocks");
+	/// assert!(error.failures[0].message.contains("seeded orders"));
+	/// assert!(!error.failures[0].message.contains("seeded users"));
+	/// ```
+	/// 
+	pub fn register_fixture<N, F>(&self, name: N, used: F)
+	where
+		N: Into<String>,
+		F: FnOnce() -> bool + Send + 'static,
+	{
+		self.state.fixtures.lock().unwrap_or_else(PoisonError::into_inner).push((name.into(), Box::new(used)));
+	}
+	
+	//		scope																
+	/// Wraps a future so that the context is entered whenever it is polled.
+	/// 
+	/// This makes the context task-local rather than thread-local, so that it
+	/// follows the future onto whichever thread polls it, such as when the
+	/// future is spawned onto a multi-threaded runtime. The context is exited
+	/// again after each poll, and is not verified when the future completes, as
+	/// it is still owned by the test.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `future` - The future to run within the context.
+	/// 
+	/// # Examples
+	/// 
+	/// ```rust
+	/// use futures_executor::block_on;
+	/// use sham::Context;
+	/// use std::thread;
+	/// 
+	/// let context = Context::new();
+	/// let task    = context.scope(async { Context::current().is_some() });
+	/// assert!(thread::spawn(move || block_on(task)).join().unwrap());
+	/// assert!(Context::current().is_none());
+	/// ```
+	/// 
+	pub fn scope<F: Future>(&self, future: F) -> impl Future<Output = F::Output> {
+		CONTEXT.scope(self.clone(), future)
+	}
+	
+	//		seed																
+	/// The seed of the context, from which the randomised behaviour of the
+	/// mocks is derived.
+	/// 
+	/// Unless it has been set, the seed is taken from the `SHAM_SEED`
+	/// environment variable the first time it is needed, or chosen at random
+	/// if that is not set, and stays the same for the life of the context.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if `SHAM_SEED` is set, but is not an unsigned
+	/// 64-bit integer.
+	/// 
+	/// # See also
+	/// 
+	/// * [`seed_for()`](crate::seed::seed_for())
+	/// 
+	#[must_use]
+	pub fn seed(&self) -> u64 {
+		self.state.seed.lock().unwrap_or_else(PoisonError::into_inner).get_or_insert_with(|| (initial_seed(), false)).0
+	}
+	
+	//		sentry																
+	/// The mocked error-reporting client.
+	#[cfg(feature = "sentry")]
+	#[must_use]
+	pub fn sentry(&self) -> &MockSentry {
+		&self.state.sentry
+	}
+	
+	//		set_base_url														
+	/// Sets the URL against which relative URLs in fixtures are resolved, such
+	/// as those given to [`try_create_mock_response()`](crate::reqwest::try_create_mock_response()),
+	/// while the context is entered.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `url` - The base URL. As usual, a base URL that does not end with `/`
+	///   has its last segment replaced, rather than added to.
+	/// 
+	#[cfg(feature = "reqwest")]
+	pub fn set_base_url(&self, url: Url) {
+		*self.state.base_url.lock().unwrap_or_else(PoisonError::into_inner) = Some(url);
+	}
+	
+	//		set_default_user_agent												
+	/// Sets the `User-Agent` sent by the mocked Reqwest clients for requests
+	/// that do not set one, while the context is entered.
+	/// 
+	/// This stands in for [`reqwest::ClientBuilder::user_agent()`], which the
+	/// genuine client applies to every request that does not set its own, so
+	/// that the value the code under test gives to the builder can be set here.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `user_agent` - The `User-Agent` to send by default.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the `User-Agent` is not a valid header
+	/// value.
+	/// 
+	#[cfg(feature = "reqwest")]
+	pub fn set_default_user_agent(&self, user_agent: &str) {
+		let value = HeaderValue::from_str(user_agent).unwrap_or_else(|_| panic!("Invalid User-Agent: {user_agent}"));
+		*self.state.default_user_agent.lock().unwrap_or_else(PoisonError::into_inner) = Some(value);
+	}
+	
+	//		set_redactor														
+	/// Sets the redactor that is applied to the interactions as they are
+	/// recorded, so that reports and baselines do not contain secrets.
+	/// 
+	/// Interactions that have already been recorded are not redacted.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `redactor` - The redactor to apply.
+	/// 
+	#[cfg(feature = "capture")]
+	pub fn set_redactor(&self, redactor: Redactor) {
+		*self.state.redactor.lock().unwrap_or_else(PoisonError::into_inner) = redactor;
+	}
+	
+	//		set_seed															
+	/// Sets the seed of the context, overriding the `SHAM_SEED` environment
+	/// variable.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `seed` - The seed to use.
+	/// 
+	pub fn set_seed(&self, seed: u64) {
+		*self.state.seed.lock().unwrap_or_else(PoisonError::into_inner) = Some((seed, true));
+	}
+	
+	//		set_unused_policy													
+	/// Sets what to do about mocks and fixtures that were set up but never
+	/// used, when the context is verified.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `policy` - The policy to apply.
+	/// 
+	/// # See also
+	/// 
+	/// * [`Context::register_fixture()`]
+	/// 
+	pub fn set_unused_policy(&self, policy: MockUnusedPolicy) {
+		*self.state.unused.lock().unwrap_or_else(PoisonError::into_inner) = policy;
+	}
+	
+	//		throttle															
+	/// The observer of the rate at which the mocked Reqwest clients send
+	/// requests to each host while the context is entered.
+	#[cfg(feature = "throttle")]
+	#[must_use]
+	pub fn throttle(&self) -> &MockThrottleObserver {
+		&self.state.throttle
+	}
+	
+	//		tracing																
+	/// The spans and events captured while the context is alive.
+	/// 
+	/// Capturing starts on the current thread the first time this is called.
+	/// 
+	/// # See also
+	/// 
+	/// * [`MockTracing::capture()`]
+	/// 
+	#[cfg(feature = "tracing")]
+	#[must_use]
+	pub fn tracing(&self) -> &MockTracing {
+		self.state.tracing.get_or_init(MockTracing::capture)
+	}
+	
+	//		user_agents															
+	/// Returns the distinct `User-Agent`s sent by the mocked Reqwest clients
+	/// while the context was entered, in the order in which they were first
+	/// sent. Requests that sent no `User-Agent` are represented by `None`.
+	/// 
+	/// # See also
+	/// 
+	/// * [`Context::assert_user_agent()`]
+	/// 
+	#[cfg(feature = "reqwest")]
+	#[must_use]
+	pub fn user_agents(&self) -> Vec<Option<String>> {
+		let sent         = self.state.user_agents.lock().unwrap_or_else(PoisonError::into_inner);
+		let mut distinct = Vec::new();
+		for entry in sent.iter() {
+			if !distinct.contains(&entry.1) {
+				distinct.push(entry.1.clone());
+			}
+		}
+		drop(sent);
+		distinct
+	}
+	
+	//		verify																
+	/// Runs all of the registered checks, in the order they were registered.
+	/// 
+	/// Every check is run, even if earlier ones fail, and each check is only
+	/// run once, after which it is removed from the context. The registered
+	/// fixtures are then checked for use, according to the [`MockUnusedPolicy`]
+	/// that has been set, and are likewise removed.
+	/// 
+	/// # Errors
+	/// 
+	/// A [`MockVerificationError`] listing every failure will be returned if
+	/// any of the checks failed.
+	/// 
+	pub fn verify(&self) -> Result<(), MockVerificationError> {
+		let checks       = take(&mut *self.lock());
+		let mut failures = checks.into_iter().filter_map(|(name, check)| {
+			let message = match catch_unwind(AssertUnwindSafe(check)) {
+				Ok(Ok(()))       => return None,
+				Ok(Err(message)) => message,
+				Err(payload)     => payload.downcast_ref::<&str>().map(|&text| text.to_owned())
+					.or_else(|| payload.downcast_ref::<String>().cloned())
+					.unwrap_or_else(|| "check panicked".to_owned())
+				,
+			};
+			Some(MockVerificationFailure { name, message })
+		}).collect::<Vec<_>>();
+		failures.extend(self.check_unused());
+		if failures.is_empty() {
+			Ok(())
+		} else {
+			Err(MockVerificationError { failures })
+		}
+	}
+	
+	//		write_report														
+	/// Writes a report of the interactions with the mocks, returning its path.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name`     - The name of the report.
+	/// * `passed`   - Whether the test passed.
+	/// * `failures` - The checks that failed.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the report cannot be written.
+	/// 
+	#[cfg(feature = "report")]
+	fn write_report(&self, name: &str, passed: bool, failures: &[MockVerificationFailure]) -> IoResult<PathBuf> {
+		let directory = env::var_os("CARGO_TARGET_DIR").map_or_else(|| PathBuf::from("target"), PathBuf::from).join("sham-reports");
+		let filename  = name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' }).collect::<String>();
+		let path      = directory.join(format!("{filename}.json"));
+		let report    = Report { test: name, passed, interactions: self.interactions(), failures };
+		fs::create_dir_all(&directory)?;
+		fs::write(&path, to_vec_pretty(&report).map_err(IoError::other)?)?;
+		Ok(path)
+	}
+}
+
+//󰭅		Debug																	
+impl Debug for Context {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Context")
+			.field("pending", &self.pending())
+			.finish()
+	}
+}
+
+//		ContextGuard															
+/// A guard that keeps a [`Context`] entered on the current thread.
+/// 
+/// When dropped, the previously entered context is restored, and the context
+/// is verified.
+/// 
+/// # See also
+/// 
+/// * [`Context::enter()`]
+/// 
+#[derive(Debug)]
+#[must_use = "The context is exited as soon as the guard is dropped"]
+pub struct ContextGuard {
+	//		Private properties													
+	/// The context that was entered.
+	context:  Context,
+	
+	/// The entry of the context on the current thread, until it is exited.
+	scope:    Option<ScopeGuard<Context>>,
+}
+
+//󰭅		ContextGuard															
+impl ContextGuard {
+	//		context																
+	/// The context that was entered.
+	#[must_use]
+	pub const fn context(&self) -> &Context {
+		&self.context
+	}
+}
+
+//󰭅		Drop																	
+impl Drop for ContextGuard {
+	//		drop																
+	fn drop(&mut self) {
+		drop(self.scope.take());
+		let panicking = thread::panicking();
+		let result    = if panicking { Ok(()) } else { self.context.verify() };
+		#[cfg(feature = "report")]
+		{
+			let report = self.context.state.report.lock().unwrap_or_else(PoisonError::into_inner).clone();
+			if let Some(name) = report {
+				let failures = result.as_ref().err().map_or(&[][..], |error| &error.failures);
+				drop(self.context.write_report(&name, !panicking && failures.is_empty(), failures));
+			}
+		}
+		if panicking || result.is_err() {
+			self.context.print_seed();
+		}
+		if let Err(error) = result {
+			panic!("{error}");
+		}
+	}
+}
+
+//		ContextState															
+/// The state of a [`Context`], shared by all of its clones.
+#[derive(Default)]
+struct ContextState {
+	//		Private properties													
+	/// The URL against which relative URLs in fixtures are resolved.
+	#[cfg(feature = "reqwest")]
+	base_url: Mutex<Option<Url>>,
+	
+	/// The registered checks, along with their names.
+	checks:  Mutex<Vec<(String, Check)>>,
+	
+	/// The virtual clock.
+	#[cfg(feature = "clock")]
+	clock:   MockClock,
+	
+	/// The `User-Agent` sent by the mocked Reqwest clients for requests that do
+	/// not set one.
+	#[cfg(feature = "reqwest")]
+	default_user_agent: Mutex<Option<HeaderValue>>,
+	
+	/// The registered fixtures, along with their names.
+	fixtures: Mutex<Vec<(String, Fixture)>>,
+	
+	/// The interactions with the mocks, in order.
+	interactions: Mutex<Vec<MockInteraction>>,
+	
+	/// The mocked configuration and environment variables.
+	#[cfg(feature = "config")]
+	config:  MockConfig,
+	
+	/// The log capture, once started.
+	#[cfg(feature = "log")]
+	logs:    OnceLock<MockLogs>,
+	
+	/// The metrics recorder.
+	#[cfg(feature = "metrics")]
+	metrics: MockMetrics,
+	
+	/// The redactor applied to the interactions as they are recorded.
+	#[cfg(feature = "capture")]
+	redactor: Mutex<Redactor>,
+	
+	/// The routes served by the clients created from the context.
+	#[cfg(feature = "reqwest")]
+	routes:  Mutex<Vec<MockRoute>>,
+	
+	/// The name of the report to write, if enabled.
+	#[cfg(feature = "report")]
+	report:  Mutex<Option<String>>,
+	
+	/// The seed, once chosen or set, and whether it was set.
+	seed:    Mutex<Option<(u64, bool)>>,
+	
+	/// The mocked error-reporting client.
+	#[cfg(feature = "sentry")]
+	sentry:  MockSentry,
+	
+	/// The observer of the rate of requests.
+	#[cfg(feature = "throttle")]
+	throttle: MockThrottleObserver,
+	
+	/// The tracing capture, once started.
+	#[cfg(feature = "tracing")]
+	tracing: OnceLock<MockTracing>,
+	
+	/// The requests sent by the mocked Reqwest clients, along with the
+	/// `User-Agent` that each of them sent.
+	#[cfg(feature = "reqwest")]
+	user_agents: Mutex<Vec<(String, Option<String>)>>,
+	
+	/// What to do about unused mocks and fixtures.
+	unused:  Mutex<MockUnusedPolicy>,
+}
+
+//		MockInteraction															
+/// An interaction with a mock, recorded by a [`Context`].
+#[cfg_attr(feature = "report", derive(Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockInteraction {
+	//		Public properties													
+	/// The module of the mock, such as `reqwest`.
+	pub module:  String,
+	
+	/// What was done, such as the method and URL of a request.
+	pub action:  String,
+	
+	/// What happened, such as the status of the response.
+	pub outcome: String,
+}
+
+//		MockVerificationError													
+/// The failures found when verifying a [`Context`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockVerificationError {
+	//		Public properties													
+	/// The checks that failed, in the order they were registered.
+	pub failures: Vec<MockVerificationFailure>,
+}
+
+//󰭅		Display																	
+impl Display for MockVerificationError {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let count = self.failures.len();
+		write!(f, "{count} mock verification {}:", if count == 1 { "failure" } else { "failures" })?;
+		for (index, failure) in self.failures.iter().enumerate() {
+			write!(f, "\n  {}. {failure}", index + 1)?;
+		}
+		Ok(())
+	}
+}
+
+//󰭅		Error																	
+impl Error for MockVerificationError {}
+
+//		MockVerificationFailure													
+/// A check that failed when verifying a [`Context`].
+#[cfg_attr(feature = "report", derive(Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockVerificationFailure {
+	//		Public properties													
+	/// The name the check was registered with.
+	pub name:    String,
+	
+	/// The error message or panic message produced by the check.
+	pub message: String,
+}
+
+//󰭅		Display																	
+impl Display for MockVerificationFailure {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		write!(f, "{}: {}", self.name, self.message)
+	}
+}
+
+//		Report																	
+/// A report of the interactions with the mocks during a test.
+#[cfg(feature = "report")]
+#[derive(Serialize)]
+struct Report<'r> {
+	//		Private properties													
+	/// The name of the test.
+	test:         &'r str,
+	
+	/// Whether the test passed.
+	passed:       bool,
+	
+	/// The interactions with the mocks, in order.
+	interactions: Vec<MockInteraction>,
+	
+	/// The checks that failed.
+	failures:     &'r [MockVerificationFailure],
+}
+
+//		ThreadWaker																
+/// A waker that unparks the thread running [`block_on()`].
+#[cfg(all(feature = "macros", not(feature = "tokio")))]
+struct ThreadWaker(Thread);
+
+//󰭅		Wake																	
+#[cfg(all(feature = "macros", not(feature = "tokio")))]
+impl Wake for ThreadWaker {
+	//		wake																
+	fn wake(self: Arc<Self>) {
+		self.0.unpark();
+	}
+	
+	//		wake_by_ref															
+	fn wake_by_ref(self: &Arc<Self>) {
+		self.0.unpark();
+	}
+}
+
+
+
+//		Functions
+
+//		block_on																
+/// Runs a future to completion on a current-thread Tokio runtime.
+/// 
+/// This is used by the [`test`](crate::test) attribute to run asynchronous
+/// tests, and is not intended to be called directly.
+/// 
+/// # Panics
+/// 
+/// Panics if the runtime cannot be created.
+/// 
+#[cfg(all(feature = "macros", feature = "tokio"))]
+#[doc(hidden)]
+pub fn block_on<F: Future>(future: F) -> F::Output {
+	Builder::new_current_thread()
+		.enable_all()
+		.build()
+		.expect("Failed to create the Tokio runtime")
+		.block_on(future)
+}
+
+//		block_on																
+/// Runs a future to completion on the current thread, without depending upon
+/// any particular runtime.
+/// 
+/// This is used by the [`test`](crate::test) attribute to run asynchronous
+/// tests when the `tokio` feature is not enabled, and is not intended to be
+/// called directly.
+/// 
+#[cfg(all(feature = "macros", not(feature = "tokio")))]
+#[doc(hidden)]
+pub fn block_on<F: Future>(future: F) -> F::Output {
+	let waker       = Waker::from(Arc::new(ThreadWaker(thread::current())));
+	let mut context = TaskContext::from_waker(&waker);
+	let mut pinned  = pin!(future);
+	loop {
+		if let Poll::Ready(output) = pinned.as_mut().poll(&mut context) {
+			return output;
+		}
+		thread::park();
+	}
+}
+
+//		verify_all																
+/// Verifies the context that has been entered on the current thread.
+/// 
+/// Every check registered with the context so far is run, across all of the
+/// modules, and all of the failures are reported together. If no context has
+/// been entered, there is nothing to verify.
+/// 
+/// # Errors
+/// 
+/// A [`MockVerificationError`] listing every failure will be returned if any
+/// of the checks failed.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use sham::{Context, verify_all};
+/// 
+/// let context = Context::new();
+/// let _guard  = context.enter();
+/// context.register("cache warmed", || Ok(()));
+/// context.register("token refreshed", || Err("never called".to_owned()));
+/// 
+/// let error = verify_all().unwrap_err();
+/// assert_eq!(error.failures[0].name, "token refreshed");
+/// ```
+/// 
+/// # See also
+/// 
+/// * [`Context::enter()`]
+/// * [`Context::verify()`]
+/// 
+pub fn verify_all() -> Result<(), MockVerificationError> {
+	Context::current().map_or(Ok(()), |context| context.verify())
+}
+
+//		mismatch_message														
+/// Describes a call received by a mock that differs from the one a helper set
+/// it up to expect.
+/// 
+/// The message states what was expected, and then shows the expected and the
+/// received calls one above the other, so that the difference stands out. The
+/// two lines are coloured when standard error is a terminal, unless the
+/// `NO_COLOR` environment variable is set.
+/// 
+/// # Parameters
+/// 
+/// * `summary`  - What the helper expected, such as "`create_mock_client()`
+///   expected request 1 of 2 to be `GET https://example.com/`".
+/// * `expected` - The call that was expected.
+/// * `received` - The call that was received.
+/// 
+#[cfg(any(feature = "reqwest", feature = "std_process"))]
+pub(crate) fn mismatch_message(summary: &str, expected: &str, received: &str) -> String {
+	let (red, green, reset) = if stderr().is_terminal() && env::var_os("NO_COLOR").is_none() {
+		("\x1b[31m", "\x1b[32m", "\x1b[0m")
+	} else {
+		("", "", "")
+	};
+	format!("{summary}, but received a different call:\n{red}  - expected: {expected}{reset}\n{green}  + received: {received}{reset}")
+}
@@ -0,0 +1,631 @@
+//! This module mocks a job scheduler's trigger source in order to test jobs.
+//! 
+//! This module provides a [`MockScheduler`] that decides when cron-scheduled
+//! jobs are due according to a [`MockClock`], rather than the real time, along
+//! with a [`MockCronSchedule`] that parses standard five-field cron expressions.
+//! This is important because background jobs that run hourly, nightly, or on
+//! the first of the month cannot be tested by waiting for them, and because the
+//! schedules themselves are easy to get wrong.
+//! 
+//! The approach taken is that the "real" code should register its jobs with a
+//! scheduler that it is given, and the test code then supplies a
+//! [`MockScheduler`] and moves time forward with
+//! [`advance()`](MockScheduler::advance()). Each time that a schedule would
+//! have fired during that period is recorded, in order, and the job registered
+//! for it, if any, is run synchronously on the calling thread before
+//! [`advance()`](MockScheduler::advance()) returns. Times are evaluated in UTC,
+//! with a resolution of one minute.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use core::time::Duration;
+//! use sham::{
+//!     clock::MockClock,
+//!     cron::{MockCronSchedule, MockScheduler},
+//! };
+//! use std::{
+//!     sync::{Arc, atomic::{AtomicUsize, Ordering}},
+//!     time::UNIX_EPOCH,
+//! };
+//! 
+//! let weekly: MockCronSchedule = "30 9 * * MON".parse().unwrap();
+//! let leap:   MockCronSchedule = "0 0 29 FEB *".parse().unwrap();
+//! assert_eq!(weekly.next_after(UNIX_EPOCH), Some(UNIX_EPOCH + Duration::from_secs(4 * 86_400 + 9 * 3_600 + 30 * 60)));
+//! assert_eq!(leap.next_after(UNIX_EPOCH),   Some(UNIX_EPOCH + Duration::from_secs(789 * 86_400)));
+//! 
+//! let clock     = MockClock::at(UNIX_EPOCH);
+//! let scheduler = MockScheduler::new(&clock);
+//! let runs      = Arc::new(AtomicUsize::new(0));
+//! let counter   = Arc::clone(&runs);
+//! 
+//! scheduler.schedule_fn("cleanup", "*/15 * * * *", move || {
+//!     _ = counter.fetch_add(1, Ordering::SeqCst);
+//! }).unwrap();
+//! scheduler.schedule("report", "@daily").unwrap();
+//! 
+//! scheduler.advance(Duration::from_secs(60 * 60));
+//! assert_eq!(runs.load(Ordering::SeqCst), 4);
+//! assert_eq!(scheduler.fire_count("report"), 0);
+//! 
+//! scheduler.advance(Duration::from_secs(23 * 60 * 60));
+//! assert_eq!(scheduler.fire_count("report"), 1);
+//! assert_eq!(scheduler.fire_count("cleanup"), 96);
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::clock::MockClock;
+use core::{
+	error::Error,
+	fmt::{Debug, Display, Formatter, self},
+	str::FromStr,
+	time::Duration,
+};
+use std::{
+	sync::{Arc, Mutex, MutexGuard, PoisonError},
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+
+
+//		Constants
+
+/// The number of days to search ahead for the next firing of a schedule,
+/// which covers the longest gap between leap days.
+const SEARCH_DAYS: u64 = 366 * 8;
+
+/// The names of the months, in order, starting from January as 1.
+const MONTH_NAMES: [&str; 12] = ["JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC"];
+
+/// The names of the days of the week, in order, starting from Sunday as 0.
+const WEEKDAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+
+
+//		Types
+
+/// A job run when a schedule fires.
+type Job = Arc<Mutex<Box<dyn FnMut() + Send>>>;
+
+
+
+//		Enums
+
+//		MockCronError															
+/// A mocked scheduler error type.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockCronError {
+	/// A job with the given name is already scheduled.
+	Duplicate(String),
+	
+	/// The cron expression is invalid. The reason is given.
+	InvalidExpression(String, String),
+	
+	/// No job with the given name is scheduled.
+	NotFound(String),
+}
+
+//󰭅		Display																	
+impl Display for MockCronError {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match *self {
+			Self::Duplicate(ref name)                     => write!(f, "Job already scheduled: {name}"),
+			Self::InvalidExpression(ref expr, ref reason) => write!(f, "Invalid cron expression \"{expr}\": {reason}"),
+			Self::NotFound(ref name)                      => write!(f, "Job not found: {name}"),
+		}
+	}
+}
+
+//󰭅		Error																	
+impl Error for MockCronError {}
+
+
+
+//		Structs
+
+//		MockCronFiring															
+/// A record of a schedule firing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockCronFiring {
+	//		Public properties													
+	/// The name of the job.
+	pub name: String,
+	
+	/// The time at which the schedule fired.
+	pub time: SystemTime,
+}
+
+//		MockCronSchedule														
+/// A parsed cron expression.
+/// 
+/// The standard five fields are supported, i.e. minute, hour, day of the
+/// month, month, and day of the week, each of which may be `*`, a value, a
+/// range, a list, or a step such as `*/5` or `10-30/10`. Months and days of the
+/// week may also be given by their three-letter English names, and Sunday may
+/// be given as either `0` or `7`. As with cron, if both the day of the month
+/// and the day of the week are restricted, the schedule fires when either
+/// matches. The `@yearly`, `@annually`, `@monthly`, `@weekly`, `@daily`,
+/// `@midnight`, and `@hourly` shorthands are also accepted.
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MockCronSchedule {
+	//		Private properties													
+	/// Whether a day matches when either the day of the month or the day of
+	/// the week matches, rather than both.
+	day_or:     bool,
+	
+	/// The days of the month, as a bitmask.
+	days:       u64,
+	
+	/// The original expression.
+	expression: String,
+	
+	/// The hours, as a bitmask.
+	hours:      u64,
+	
+	/// The minutes, as a bitmask.
+	minutes:    u64,
+	
+	/// The months, as a bitmask.
+	months:     u64,
+	
+	/// The days of the week, as a bitmask.
+	weekdays:   u64,
+}
+
+//󰭅		MockCronSchedule														
+impl MockCronSchedule {
+	//		fires_between														
+	/// Returns the times at which the schedule fires after `from`, up to and
+	/// including `to`.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `from` - The time to search after.
+	/// * `to`   - The time to search up to.
+	/// 
+	#[must_use]
+	pub fn fires_between(&self, from: SystemTime, to: SystemTime) -> Vec<SystemTime> {
+		let mut times = Vec::new();
+		let mut after = from;
+		while let Some(next) = self.next_after(after).filter(|next| *next <= to) {
+			times.push(next);
+			after = next;
+		}
+		times
+	}
+	
+	//		matches																
+	/// Returns whether the schedule fires during the minute containing the
+	/// given time.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `time` - The time to check.
+	/// 
+	#[must_use]
+	pub fn matches(&self, time: SystemTime) -> bool {
+		let secs = unix_secs(time);
+		self.matches_day(secs.div_euclid(86_400))
+			&& bit(self.hours,   secs.div_euclid(3_600).rem_euclid(24))
+			&& bit(self.minutes, secs.div_euclid(60).rem_euclid(60))
+	}
+	
+	//		next_after															
+	/// Returns the next time, on a minute boundary, strictly after the given
+	/// time at which the schedule fires. This is [`None`] if the schedule can
+	/// never fire, e.g. for the 30th of February.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `time` - The time to search after.
+	/// 
+	#[must_use]
+	pub fn next_after(&self, time: SystemTime) -> Option<SystemTime> {
+		let start     = unix_secs(time).div_euclid(60).saturating_add(1);
+		let first_day = start.div_euclid(1_440);
+		for day in first_day..first_day.saturating_add(SEARCH_DAYS) {
+			if !self.matches_day(day) {
+				continue;
+			}
+			let day_start = day.saturating_mul(1_440);
+			for minute in start.max(day_start)..day_start.saturating_add(1_440) {
+				if bit(self.hours, minute.div_euclid(60).rem_euclid(24)) && bit(self.minutes, minute.rem_euclid(60)) {
+					return Some(UNIX_EPOCH + Duration::from_secs(minute.saturating_mul(60)));
+				}
+			}
+		}
+		None
+	}
+	
+	//		matches_day															
+	/// Returns whether the schedule fires on the given day since the epoch.
+	const fn matches_day(&self, day: u64) -> bool {
+		let (_, month, day_of_month) = civil_from_days(day);
+		let weekday                  = day.saturating_add(4).rem_euclid(7);
+		let by_date                  = bit(self.days,     day_of_month);
+		let by_weekday               = bit(self.weekdays, weekday);
+		bit(self.months, month) && if self.day_or { by_date || by_weekday } else { by_date && by_weekday }
+	}
+}
+
+//󰭅		Display																	
+impl Display for MockCronSchedule {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.expression)
+	}
+}
+
+//󰭅		FromStr																	
+impl FromStr for MockCronSchedule {
+	type Err = MockCronError;
+	
+	//		from_str															
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let expanded = match s.trim() {
+			"@yearly" | "@annually" => "0 0 1 1 *",
+			"@monthly"              => "0 0 1 * *",
+			"@weekly"               => "0 0 * * 0",
+			"@daily" | "@midnight"  => "0 0 * * *",
+			"@hourly"               => "0 * * * *",
+			other                   => other,
+		};
+		let invalid = |reason: &str| MockCronError::InvalidExpression(s.to_owned(), reason.to_owned());
+		let fields  = expanded.split_whitespace().collect::<Vec<_>>();
+		let &[minutes, hours, days, months, weekdays] = fields.as_slice() else {
+			return Err(invalid("expected five fields"));
+		};
+		let (weekdays_mask, weekdays_any) = parse_field(weekdays, 0, 7, 0, &WEEKDAY_NAMES).map_err(|reason| invalid(&reason))?;
+		let (days_mask,     days_any)     = parse_field(days,     1, 31, 1, &[]).map_err(|reason| invalid(&reason))?;
+		Ok(Self {
+			day_or:     !days_any && !weekdays_any,
+			days:       days_mask,
+			expression: s.trim().to_owned(),
+			hours:      parse_field(hours,   0, 23, 0, &[]).map_err(|reason| invalid(&reason))?.0,
+			minutes:    parse_field(minutes, 0, 59, 0, &[]).map_err(|reason| invalid(&reason))?.0,
+			months:     parse_field(months,  1, 12, 1, &MONTH_NAMES).map_err(|reason| invalid(&reason))?.0,
+			//	Sunday can be given as either 0 or 7
+			weekdays:   if bit(weekdays_mask, 7) { weekdays_mask | 1 } else { weekdays_mask },
+		})
+	}
+}
+
+//		MockScheduler															
+/// A mocked job scheduler, driven by a [`MockClock`].
+/// 
+/// Clones share the same jobs and records.
+/// 
+#[derive(Clone)]
+pub struct MockScheduler {
+	//		Private properties													
+	/// The clock that determines when jobs are due.
+	clock: MockClock,
+	
+	/// The state of the scheduler, shared between clones.
+	state: Arc<Mutex<SchedulerState>>,
+}
+
+//󰭅		MockScheduler															
+impl MockScheduler {
+	//		new																	
+	/// Creates a new scheduler with no jobs, which considers jobs due from the
+	/// current time of the given clock onwards.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `clock` - The clock that determines when jobs are due.
+	/// 
+	#[must_use]
+	pub fn new(clock: &MockClock) -> Self {
+		Self {
+			clock: clock.clone(),
+			state: Arc::new(Mutex::new(SchedulerState {
+				checked: clock.now(),
+				entries: Vec::new(),
+				fired:   Vec::new(),
+			})),
+		}
+	}
+	
+	//		advance																
+	/// Moves the clock forward by the given duration, and then runs any jobs
+	/// that became due, as per [`tick()`](MockScheduler::tick()).
+	/// 
+	/// # Parameters
+	/// 
+	/// * `duration` - The amount of time to move forward by.
+	/// 
+	pub fn advance(&self, duration: Duration) {
+		self.clock.advance(duration);
+		self.tick();
+	}
+	
+	//		fire_count															
+	/// Returns the number of times that the named job has fired.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the job.
+	/// 
+	#[must_use]
+	pub fn fire_count(&self, name: &str) -> usize {
+		self.lock().fired.iter().filter(|firing| firing.name == name).count()
+	}
+	
+	//		fired																
+	/// Returns a record of every time that a job has fired, in order.
+	#[must_use]
+	pub fn fired(&self) -> Vec<MockCronFiring> {
+		self.lock().fired.clone()
+	}
+	
+	//		next_fire															
+	/// Returns the next time at which the named job will fire, if ever.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the job.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if no job with the given name is scheduled.
+	/// 
+	pub fn next_fire(&self, name: &str) -> Result<Option<SystemTime>, MockCronError> {
+		let state = self.lock();
+		let entry = state.entries.iter().find(|entry| entry.name == name).ok_or_else(|| MockCronError::NotFound(name.to_owned()))?;
+		let next  = entry.schedule.next_after(state.checked);
+		drop(state);
+		Ok(next)
+	}
+	
+	//		remove																
+	/// Removes the named job, returning whether it was scheduled.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the job.
+	/// 
+	pub fn remove(&self, name: &str) -> bool {
+		let mut state = self.lock();
+		let count     = state.entries.len();
+		state.entries.retain(|entry| entry.name != name);
+		let removed   = state.entries.len() != count;
+		drop(state);
+		removed
+	}
+	
+	//		run_now																
+	/// Runs the named job immediately, regardless of its schedule. This is not
+	/// recorded as a firing.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the job.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if no job with the given name is scheduled.
+	/// 
+	pub fn run_now(&self, name: &str) -> Result<(), MockCronError> {
+		let state = self.lock();
+		let job   = state.entries.iter().find(|entry| entry.name == name).ok_or_else(|| MockCronError::NotFound(name.to_owned()))?.job.clone();
+		drop(state);
+		if let Some(action) = job {
+			(action.lock().unwrap_or_else(PoisonError::into_inner))();
+		}
+		Ok(())
+	}
+	
+	//		schedule															
+	/// Schedules a named job with no action, so that only its firings are
+	/// recorded.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name`       - The name of the job.
+	/// * `expression` - The cron expression. See [`MockCronSchedule`] for the
+	///   supported syntax.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the expression is invalid, or if a job with
+	/// the same name is already scheduled.
+	/// 
+	pub fn schedule(&self, name: &str, expression: &str) -> Result<(), MockCronError> {
+		self.add(name, expression, None)
+	}
+	
+	//		schedule_fn															
+	/// Schedules a named job that runs the given closure each time it fires.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name`       - The name of the job.
+	/// * `expression` - The cron expression. See [`MockCronSchedule`] for the
+	///   supported syntax.
+	/// * `job`        - The closure to run.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the expression is invalid, or if a job with
+	/// the same name is already scheduled.
+	/// 
+	pub fn schedule_fn<F>(&self, name: &str, expression: &str, job: F) -> Result<(), MockCronError>
+	where
+		F: FnMut() + Send + 'static,
+	{
+		let boxed: Box<dyn FnMut() + Send> = Box::new(job);
+		self.add(name, expression, Some(Arc::new(Mutex::new(boxed))))
+	}
+	
+	//		tick																
+	/// Records every firing that became due between the last check and the
+	/// current time of the clock, and runs the corresponding jobs in order.
+	/// 
+	/// The jobs are run after the internal state has been released, so they
+	/// may safely use the scheduler themselves. If the clock has been set
+	/// backwards, nothing fires, and jobs become due from the new time.
+	/// 
+	pub fn tick(&self) {
+		let now       = self.clock.now();
+		let mut state = self.lock();
+		let checked   = state.checked;
+		state.checked = now;
+		let mut due   = state.entries.iter().enumerate().flat_map(|(index, entry)| {
+			entry.schedule.fires_between(checked, now).into_iter().map(move |time| (time, index))
+		}).collect::<Vec<_>>();
+		due.sort_unstable();
+		let mut jobs  = Vec::new();
+		for (time, index) in due {
+			if let Some(entry) = state.entries.get(index) {
+				jobs.extend(entry.job.clone());
+				let name = entry.name.clone();
+				state.fired.push(MockCronFiring { name, time });
+			}
+		}
+		drop(state);
+		for job in jobs {
+			(job.lock().unwrap_or_else(PoisonError::into_inner))();
+		}
+	}
+	
+	//		add																	
+	/// Adds a job.
+	fn add(&self, name: &str, expression: &str, job: Option<Job>) -> Result<(), MockCronError> {
+		let schedule  = expression.parse()?;
+		let mut state = self.lock();
+		if state.entries.iter().any(|entry| entry.name == name) {
+			return Err(MockCronError::Duplicate(name.to_owned()));
+		}
+		state.entries.push(Entry { job, name: name.to_owned(), schedule });
+		drop(state);
+		Ok(())
+	}
+	
+	//		lock																
+	/// Locks the shared state.
+	fn lock(&self) -> MutexGuard<'_, SchedulerState> {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+}
+
+//󰭅		Debug																	
+impl Debug for MockScheduler {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let names = self.lock().entries.iter().map(|entry| entry.name.clone()).collect::<Vec<_>>();
+		f.debug_struct("MockScheduler")
+			.field("clock", &self.clock)
+			.field("jobs",  &names)
+			.finish_non_exhaustive()
+	}
+}
+
+//		Entry																	
+/// A scheduled job.
+struct Entry {
+	//		Private properties													
+	/// The action to run when the schedule fires, if any.
+	job:      Option<Job>,
+	
+	/// The name of the job.
+	name:     String,
+	
+	/// The schedule of the job.
+	schedule: MockCronSchedule,
+}
+
+//		SchedulerState															
+/// The state of a [`MockScheduler`].
+struct SchedulerState {
+	//		Private properties													
+	/// The time up to which firings have been processed.
+	checked: SystemTime,
+	
+	/// The scheduled jobs, in the order they were added.
+	entries: Vec<Entry>,
+	
+	/// The firings so far, in order.
+	fired:   Vec<MockCronFiring>,
+}
+
+
+
+//		Functions
+
+//		bit																		
+/// Returns whether the given bit is set in a mask.
+const fn bit(mask: u64, index: u64) -> bool {
+	index < 64 && mask & (1 << index) != 0
+}
+
+//		civil_from_days															
+/// Converts a number of days since the Unix epoch into a year, month, and day
+/// of the month, using the proleptic Gregorian calendar.
+const fn civil_from_days(days: u64) -> (u64, u64, u64) {
+	let shifted = days.saturating_add(719_468);
+	let era     = shifted.div_euclid(146_097);
+	let of_era  = shifted.rem_euclid(146_097);
+	let year    = of_era
+		.saturating_sub(of_era.div_euclid(1_460))
+		.saturating_add(of_era.div_euclid(36_524))
+		.saturating_sub(of_era.div_euclid(146_096))
+		.div_euclid(365)
+	;
+	let of_year = of_era.saturating_sub(year.saturating_mul(365).saturating_add(year.div_euclid(4)).saturating_sub(year.div_euclid(100)));
+	let shifted_month = of_year.saturating_mul(5).saturating_add(2).div_euclid(153);
+	let day     = of_year.saturating_sub(shifted_month.saturating_mul(153).saturating_add(2).div_euclid(5)).saturating_add(1);
+	let month   = if shifted_month < 10 { shifted_month.saturating_add(3) } else { shifted_month.saturating_sub(9) };
+	(era.saturating_mul(400).saturating_add(year).saturating_add(if month <= 2 { 1 } else { 0 }), month, day)
+}
+
+//		parse_field																
+/// Parses a field of a cron expression into a bitmask, along with whether the
+/// field is unrestricted, i.e. starts with `*`.
+fn parse_field(field: &str, min: u64, max: u64, offset: u64, names: &[&str]) -> Result<(u64, bool), String> {
+	let value = |text: &str| -> Result<u64, String> {
+		names.iter()
+			.zip(offset..)
+			.find_map(|(name, number)| name.eq_ignore_ascii_case(text).then_some(number))
+			.map_or_else(|| text.parse().map_err(|_err| format!("invalid value \"{text}\"")), Ok)
+	};
+	let mut mask = 0_u64;
+	for part in field.split(',') {
+		let (range, step) = match part.split_once('/') {
+			Some((range, text)) => (range, text.parse::<u64>().ok().filter(|step| *step > 0).ok_or_else(|| format!("invalid step \"{text}\""))?),
+			None                => (part, 0),
+		};
+		let (start, end)  = if range == "*" {
+			(min, max)
+		} else if let Some((from, to)) = range.split_once('-') {
+			(value(from)?, value(to)?)
+		} else {
+			let single = value(range)?;
+			(single, if step > 0 { max } else { single })
+		};
+		if start < min || end > max || start > end {
+			return Err(format!("\"{part}\" is out of range {min}-{max}"));
+		}
+		let mut current = start;
+		while current <= end {
+			mask   |= 1 << current;
+			current = current.saturating_add(step.max(1));
+		}
+	}
+	Ok((mask, field.starts_with('*')))
+}
+
+//		unix_secs																
+/// Returns the number of whole seconds since the Unix epoch, or zero if the
+/// time is before it.
+fn unix_secs(time: SystemTime) -> u64 {
+	time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
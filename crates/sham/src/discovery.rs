@@ -0,0 +1,710 @@
+//! This module mocks etcd and Consul in order to test service discovery.
+//! 
+//! This module provides an in-memory key-value store in the style of
+//! [etcd](https://etcd.io/), with revisions, leases, and watches, and an
+//! in-memory service registry in the style of [Consul](https://www.consul.io/),
+//! with health checks and change indexes, both with time driven by a
+//! [`MockClock`]. This is important because unit tests should not depend upon
+//! a cluster being available, and because the changes that configuration
+//! watchers and service discovery code must react to, such as a lease expiring
+//! or an instance failing its health check, need to be produced on demand.
+//! 
+//! The approach taken is that the "real" code should access the cluster
+//! through a client that it is given, and the test code then supplies a
+//! [`MockKvStore`] or [`MockServiceRegistry`]. Clones share the same state, so
+//! the test can keep one in order to script changes to the cluster whilst the
+//! code under test uses another. Leases and check TTLs are evaluated against
+//! the clock whenever the state is accessed, so advancing the clock is enough
+//! to make them expire.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use core::time::Duration;
+//! use sham::{
+//!     clock::MockClock,
+//!     discovery::{MockHealth, MockKvEvent, MockKvStore, MockService, MockServiceRegistry},
+//! };
+//! 
+//! let clock = MockClock::new();
+//! let kv    = MockKvStore::new(&clock);
+//! let lease = kv.grant_lease(Duration::from_secs(10));
+//! 
+//! kv.put("/config/mode", b"fast", Some(lease)).unwrap();
+//! clock.advance(Duration::from_secs(11));
+//! assert_eq!(kv.get("/config/mode"), None);
+//! 
+//! let events = kv.watch("/config/", 0);
+//! assert!(matches!(events[..], [MockKvEvent::Put(_), MockKvEvent::Delete(_)]));
+//! 
+//! let registry = MockServiceRegistry::new(&clock);
+//! registry.register(MockService::new("web-1", "web", "10.0.0.1", 8080), Some(Duration::from_secs(30)));
+//! assert_eq!(registry.healthy("web").len(), 1);
+//! 
+//! clock.advance(Duration::from_secs(31));
+//! assert_eq!(registry.health("web-1"), Some(MockHealth::Critical));
+//! assert!(registry.healthy("web").is_empty());
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::clock::MockClock;
+use core::{
+	error::Error,
+	fmt::{Display, Formatter, self},
+	time::Duration,
+};
+use std::{
+	collections::BTreeMap,
+	sync::{Arc, Mutex, MutexGuard, PoisonError},
+	time::SystemTime,
+};
+
+
+
+//		Enums
+
+//		MockHealth																
+/// The health of a registered service instance.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockHealth {
+	/// The instance is failing its health check.
+	Critical,
+	
+	/// The instance is passing its health check.
+	Passing,
+	
+	/// The instance is passing its health check, but with a warning.
+	Warning,
+}
+
+//		MockKvError																
+/// A mocked key-value store error type.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockKvError {
+	/// The lease does not exist, or has expired.
+	LeaseNotFound(i64),
+}
+
+//󰭅		Display																	
+impl Display for MockKvError {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match *self {
+			Self::LeaseNotFound(lease) => write!(f, "Requested lease not found: {lease}"),
+		}
+	}
+}
+
+//󰭅		Error																	
+impl Error for MockKvError {}
+
+//		MockKvEvent																
+/// A change to the key-value store, as yielded by a watch.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockKvEvent {
+	/// A key was deleted, either explicitly or by its lease expiring. The
+	/// value is that of the key before deletion, with the revision of the
+	/// deletion.
+	Delete(MockKeyValue),
+	
+	/// A key was created or updated.
+	Put(MockKeyValue),
+}
+
+//󰭅		MockKvEvent																
+impl MockKvEvent {
+	//		kv																	
+	/// Returns the key-value pair that the event is about.
+	#[must_use]
+	pub const fn kv(&self) -> &MockKeyValue {
+		match *self {
+			Self::Delete(ref kv) | Self::Put(ref kv) => kv,
+		}
+	}
+}
+
+
+
+//		Structs
+
+//		Instance																
+/// A service instance held by a [`MockServiceRegistry`].
+#[derive(Clone, Debug)]
+struct Instance {
+	/// The time by which the TTL check must be passed, if it has one.
+	deadline: Option<SystemTime>,
+	
+	/// The health of the instance.
+	health:   MockHealth,
+	
+	/// The details of the instance.
+	service:  MockService,
+	
+	/// The TTL of the health check, if it has one.
+	ttl:      Option<Duration>,
+}
+
+//		KvState																	
+/// The state of a [`MockKvStore`] instance.
+#[derive(Debug, Default)]
+struct KvState {
+	/// The changes made so far, in order.
+	events:     Vec<MockKvEvent>,
+	
+	/// The current key-value pairs.
+	keys:       BTreeMap<String, MockKeyValue>,
+	
+	/// The leases, with their TTLs and expiry times.
+	leases:     BTreeMap<i64, (Duration, SystemTime)>,
+	
+	/// The latest lease ID issued.
+	last_lease: i64,
+	
+	/// The current revision of the store.
+	revision:   i64,
+}
+
+//		MockKeyValue															
+/// A key-value pair held by a [`MockKvStore`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockKeyValue {
+	//		Public properties													
+	/// The key.
+	pub key:             String,
+	
+	/// The value.
+	pub value:           Vec<u8>,
+	
+	/// The revision at which the key was created.
+	pub create_revision: i64,
+	
+	/// The revision at which the key was last modified.
+	pub mod_revision:    i64,
+	
+	/// The number of times the key has been written since it was created.
+	pub version:         i64,
+	
+	/// The lease that the key is attached to, if any.
+	pub lease:           Option<i64>,
+}
+
+//		MockKvStore																
+/// A mocked key-value store in the style of etcd.
+#[derive(Clone, Debug)]
+pub struct MockKvStore {
+	//		Private properties													
+	/// The clock that drives lease expiry.
+	clock: MockClock,
+	
+	/// The state of the store, shared between clones.
+	state: Arc<Mutex<KvState>>,
+}
+
+//󰭅		MockKvStore																
+impl MockKvStore {
+	//		new																	
+	/// Creates a new, empty store.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `clock` - The clock that drives lease expiry.
+	/// 
+	#[must_use]
+	pub fn new(clock: &MockClock) -> Self {
+		Self {
+			clock: clock.clone(),
+			state: Arc::new(Mutex::new(KvState::default())),
+		}
+	}
+	
+	//		delete																
+	/// Deletes a key, returning whether it existed.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `key` - The key to delete.
+	/// 
+	pub fn delete(&self, key: &str) -> bool {
+		let mut state = self.lock();
+		let existed   = remove_key(&mut state, key);
+		drop(state);
+		existed
+	}
+	
+	//		get																	
+	/// Gets a key-value pair.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `key` - The key to get.
+	/// 
+	#[must_use]
+	pub fn get(&self, key: &str) -> Option<MockKeyValue> {
+		self.lock().keys.get(key).cloned()
+	}
+	
+	//		get_prefix															
+	/// Gets the key-value pairs whose keys start with the given prefix, in key
+	/// order.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `prefix` - The prefix to match.
+	/// 
+	#[must_use]
+	pub fn get_prefix(&self, prefix: &str) -> Vec<MockKeyValue> {
+		self.lock().keys.values().filter(|kv| kv.key.starts_with(prefix)).cloned().collect()
+	}
+	
+	//		grant_lease															
+	/// Grants a lease, returning its ID. Keys attached to the lease are deleted
+	/// when it expires.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `ttl` - How long the lease lives for unless kept alive.
+	/// 
+	pub fn grant_lease(&self, ttl: Duration) -> i64 {
+		let mut state = self.lock();
+		state.last_lease = state.last_lease.saturating_add(1);
+		let lease     = state.last_lease;
+		_ = state.leases.insert(lease, (ttl, self.clock.now() + ttl));
+		drop(state);
+		lease
+	}
+	
+	//		keep_alive															
+	/// Renews a lease for another period of its TTL.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `lease` - The ID of the lease.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the lease does not exist or has expired.
+	/// 
+	pub fn keep_alive(&self, lease: i64) -> Result<(), MockKvError> {
+		let mut state = self.lock();
+		let now       = self.clock.now();
+		let entry     = state.leases.get_mut(&lease).ok_or(MockKvError::LeaseNotFound(lease))?;
+		entry.1       = now + entry.0;
+		drop(state);
+		Ok(())
+	}
+	
+	//		put																	
+	/// Sets the value of a key, returning the new revision of the store.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `key`   - The key to set.
+	/// * `value` - The value to set.
+	/// * `lease` - The lease to attach the key to, if any.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the lease does not exist or has expired.
+	/// 
+	pub fn put(&self, key: &str, value: &[u8], lease: Option<i64>) -> Result<i64, MockKvError> {
+		let mut state = self.lock();
+		if let Some(id) = lease {
+			if !state.leases.contains_key(&id) {
+				return Err(MockKvError::LeaseNotFound(id));
+			}
+		}
+		state.revision = state.revision.saturating_add(1);
+		let revision  = state.revision;
+		let previous  = state.keys.get(key);
+		let kv        = MockKeyValue {
+			key:             key.to_owned(),
+			value:           value.to_vec(),
+			create_revision: previous.map_or(revision, |kv| kv.create_revision),
+			mod_revision:    revision,
+			version:         previous.map_or(1, |kv| kv.version.saturating_add(1)),
+			lease,
+		};
+		state.events.push(MockKvEvent::Put(kv.clone()));
+		drop(state.keys.insert(key.to_owned(), kv));
+		drop(state);
+		Ok(revision)
+	}
+	
+	//		revision															
+	/// Returns the current revision of the store.
+	#[must_use]
+	pub fn revision(&self) -> i64 {
+		self.lock().revision
+	}
+	
+	//		revoke_lease														
+	/// Revokes a lease, deleting the keys attached to it.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `lease` - The ID of the lease.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the lease does not exist or has expired.
+	/// 
+	pub fn revoke_lease(&self, lease: i64) -> Result<(), MockKvError> {
+		let mut state = self.lock();
+		if state.leases.remove(&lease).is_none() {
+			return Err(MockKvError::LeaseNotFound(lease));
+		}
+		remove_leased(&mut state, lease);
+		drop(state);
+		Ok(())
+	}
+	
+	//		watch																
+	/// Returns the changes to keys starting with the given prefix that were
+	/// made after the given revision, in order.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `prefix` - The prefix to match.
+	/// * `after`  - The revision to return changes after.
+	/// 
+	#[must_use]
+	pub fn watch(&self, prefix: &str, after: i64) -> Vec<MockKvEvent> {
+		self.lock().events.iter()
+			.filter(|event| event.kv().mod_revision > after && event.kv().key.starts_with(prefix))
+			.cloned()
+			.collect()
+	}
+	
+	//		lock																
+	/// Locks the state of the store, first expiring any leases that have run
+	/// out.
+	fn lock(&self) -> MutexGuard<'_, KvState> {
+		let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+		let now       = self.clock.now();
+		let expired   = state.leases.iter()
+			.filter(|&(_, &(_, expires))| expires <= now)
+			.map(|(&lease, _)| lease)
+			.collect::<Vec<_>>()
+		;
+		for lease in expired {
+			_ = state.leases.remove(&lease);
+			remove_leased(&mut state, lease);
+		}
+		state
+	}
+}
+
+//		MockService																
+/// A service instance registered with a [`MockServiceRegistry`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockService {
+	//		Public properties													
+	/// The unique ID of the instance.
+	pub id:      String,
+	
+	/// The name of the service that the instance belongs to.
+	pub name:    String,
+	
+	/// The address of the instance.
+	pub address: String,
+	
+	/// The port of the instance.
+	pub port:    u16,
+	
+	/// The tags of the instance.
+	pub tags:    Vec<String>,
+}
+
+//󰭅		MockService																
+impl MockService {
+	//		new																	
+	/// Creates a new service instance with no tags.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `id`      - The unique ID of the instance.
+	/// * `name`    - The name of the service.
+	/// * `address` - The address of the instance.
+	/// * `port`    - The port of the instance.
+	/// 
+	#[must_use]
+	pub fn new(id: &str, name: &str, address: &str, port: u16) -> Self {
+		Self {
+			id:      id.to_owned(),
+			name:    name.to_owned(),
+			address: address.to_owned(),
+			port,
+			tags:    Vec::new(),
+		}
+	}
+	
+	//		with_tags															
+	/// Sets the tags of the instance.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `tags` - The tags.
+	/// 
+	#[must_use]
+	pub fn with_tags(mut self, tags: &[&str]) -> Self {
+		self.tags = tags.iter().map(ToString::to_string).collect();
+		self
+	}
+}
+
+//		MockServiceRegistry														
+/// A mocked service registry in the style of Consul.
+/// 
+/// Every change to the registry bumps its index, which allows code that uses
+/// blocking queries to be tested by checking whether anything has changed
+/// since the index it last saw.
+/// 
+#[derive(Clone, Debug)]
+pub struct MockServiceRegistry {
+	//		Private properties													
+	/// The clock that drives check TTLs.
+	clock: MockClock,
+	
+	/// The state of the registry, shared between clones.
+	state: Arc<Mutex<RegistryState>>,
+}
+
+//󰭅		MockServiceRegistry														
+impl MockServiceRegistry {
+	//		new																	
+	/// Creates a new, empty registry.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `clock` - The clock that drives check TTLs.
+	/// 
+	#[must_use]
+	pub fn new(clock: &MockClock) -> Self {
+		Self {
+			clock: clock.clone(),
+			state: Arc::new(Mutex::new(RegistryState::default())),
+		}
+	}
+	
+	//		deregister															
+	/// Removes an instance, returning whether it was registered.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `id` - The ID of the instance.
+	/// 
+	pub fn deregister(&self, id: &str) -> bool {
+		let mut state = self.lock();
+		let existed   = state.instances.remove(id).is_some();
+		if existed {
+			state.index = state.index.saturating_add(1);
+		}
+		drop(state);
+		existed
+	}
+	
+	//		health																
+	/// Returns the health of an instance, if it is registered.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `id` - The ID of the instance.
+	/// 
+	#[must_use]
+	pub fn health(&self, id: &str) -> Option<MockHealth> {
+		self.lock().instances.get(id).map(|instance| instance.health)
+	}
+	
+	//		healthy																
+	/// Returns the instances of a service that are passing their health checks,
+	/// including those with warnings, ordered by ID.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the service.
+	/// 
+	#[must_use]
+	pub fn healthy(&self, name: &str) -> Vec<MockService> {
+		self.services(name).into_iter()
+			.filter(|&(_, health)| health != MockHealth::Critical)
+			.map(|(service, _)| service)
+			.collect()
+	}
+	
+	//		index																
+	/// Returns the current index of the registry.
+	#[must_use]
+	pub fn index(&self) -> u64 {
+		self.lock().index
+	}
+	
+	//		pass_ttl															
+	/// Marks the TTL check of an instance as passing, resetting its deadline,
+	/// and returns whether the instance is registered.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `id` - The ID of the instance.
+	/// 
+	pub fn pass_ttl(&self, id: &str) -> bool {
+		let now       = self.clock.now();
+		let mut state = self.lock();
+		let Some(instance) = state.instances.get_mut(id) else {
+			return false;
+		};
+		instance.deadline = instance.ttl.map(|ttl| now + ttl);
+		let changed   = instance.health != MockHealth::Passing;
+		instance.health = MockHealth::Passing;
+		if changed {
+			state.index = state.index.saturating_add(1);
+		}
+		drop(state);
+		true
+	}
+	
+	//		register															
+	/// Registers an instance, replacing any with the same ID. The instance
+	/// starts off passing.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `service` - The instance to register.
+	/// * `ttl`     - The TTL of the instance's health check, if it has one.
+	///   If the check is not passed again within the TTL, the instance becomes
+	///   critical.
+	/// 
+	pub fn register(&self, service: MockService, ttl: Option<Duration>) {
+		let now       = self.clock.now();
+		let mut state = self.lock();
+		state.index   = state.index.saturating_add(1);
+		drop(state.instances.insert(service.id.clone(), Instance {
+			deadline: ttl.map(|duration| now + duration),
+			health:   MockHealth::Passing,
+			service,
+			ttl,
+		}));
+	}
+	
+	//		services															
+	/// Returns the instances of a service along with their health, ordered by
+	/// ID.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the service.
+	/// 
+	#[must_use]
+	pub fn services(&self, name: &str) -> Vec<(MockService, MockHealth)> {
+		self.lock().instances.values()
+			.filter(|instance| instance.service.name == name)
+			.map(|instance| (instance.service.clone(), instance.health))
+			.collect()
+	}
+	
+	//		services_since														
+	/// Returns the instances of a service along with the current index, if the
+	/// registry has changed since the given index. This mirrors a blocking
+	/// query, where [`None`] represents the query timing out without a change.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name`  - The name of the service.
+	/// * `index` - The index last seen.
+	/// 
+	#[must_use]
+	pub fn services_since(&self, name: &str, index: u64) -> Option<(u64, Vec<(MockService, MockHealth)>)> {
+		let current = self.index();
+		(current > index).then(|| (current, self.services(name)))
+	}
+	
+	//		set_health															
+	/// Sets the health of an instance, and returns whether it is registered.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `id`     - The ID of the instance.
+	/// * `health` - The health to set.
+	/// 
+	pub fn set_health(&self, id: &str, health: MockHealth) -> bool {
+		let mut state = self.lock();
+		let Some(instance) = state.instances.get_mut(id) else {
+			return false;
+		};
+		let changed   = instance.health != health;
+		instance.health = health;
+		if changed {
+			state.index = state.index.saturating_add(1);
+		}
+		drop(state);
+		true
+	}
+	
+	//		lock																
+	/// Locks the state of the registry, first failing any TTL checks that have
+	/// run out.
+	fn lock(&self) -> MutexGuard<'_, RegistryState> {
+		let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+		let now       = self.clock.now();
+		let mut changed = false;
+		for instance in state.instances.values_mut() {
+			if instance.health != MockHealth::Critical && instance.deadline.is_some_and(|deadline| deadline <= now) {
+				instance.health = MockHealth::Critical;
+				changed         = true;
+			}
+		}
+		if changed {
+			state.index = state.index.saturating_add(1);
+		}
+		state
+	}
+}
+
+//		RegistryState															
+/// The state of a [`MockServiceRegistry`] instance.
+#[derive(Debug, Default)]
+struct RegistryState {
+	/// The current index of the registry.
+	index:     u64,
+	
+	/// The registered instances, keyed by ID.
+	instances: BTreeMap<String, Instance>,
+}
+
+
+
+//		Functions
+
+//		remove_key																
+/// Removes a key from the store, recording the deletion, and returns whether
+/// it existed.
+fn remove_key(state: &mut KvState, key: &str) -> bool {
+	let Some(mut kv) = state.keys.remove(key) else {
+		return false;
+	};
+	state.revision  = state.revision.saturating_add(1);
+	kv.mod_revision = state.revision;
+	state.events.push(MockKvEvent::Delete(kv));
+	true
+}
+
+//		remove_leased															
+/// Removes the keys attached to a lease.
+fn remove_leased(state: &mut KvState, lease: i64) {
+	let keys = state.keys.values()
+		.filter(|kv| kv.lease == Some(lease))
+		.map(|kv| kv.key.clone())
+		.collect::<Vec<_>>()
+	;
+	for key in keys {
+		_ = remove_key(state, &key);
+	}
+}
@@ -0,0 +1,852 @@
+//! This module mocks the Docker Engine API in order to test orchestration.
+//! 
+//! This module provides a mock of the container lifecycle operations of the
+//! [Bollard](https://crates.io/crates/bollard) Docker client, i.e. pulling
+//! images, and creating, starting, waiting for, reading the logs of, and
+//! removing containers, backed by an in-memory model of a Docker daemon. This
+//! is important because unit tests should not depend upon a Docker daemon being
+//! available, and because the failure paths, such as a missing image or a
+//! container exiting with an error, are hard to reproduce against a real one.
+//! 
+//! The approach taken is that the "real" code should import the `Docker`
+//! client from [Bollard](https://crates.io/crates/bollard) when running in
+//! non-test mode, but import [`MockDocker`] from this module when running in
+//! test mode, along with the supporting types, which mirror the names and
+//! shapes of Bollard's. The test code then declares which images exist, and
+//! scripts what containers created from them will output and how they will
+//! exit.
+//! 
+//! Notably, the mock is more restrictive than Bollard. Where Bollard accepts
+//! generic option types, the mock accepts only the concrete ones defined here,
+//! which are simplified to the fields most commonly used. This is still
+//! compatible with the "real" code in most cases, as the field names match.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use futures_executor::block_on;
+//! use futures_util::StreamExt;
+//! use sham::docker::{
+//!     MockContainerConfig,
+//!     MockContainerScript,
+//!     MockCreateImageOptions,
+//!     MockDocker,
+//!     MockDockerError,
+//! };
+//! 
+//! let docker = MockDocker::new()
+//!     .with_image("alpine:3", &["layer1", "layer2"])
+//!     .with_script("alpine:3", MockContainerScript::new(1).stderr("boom\n"))
+//! ;
+//! 
+//! block_on(async {
+//!     let options  = MockCreateImageOptions { from_image: "alpine".to_owned(), tag: "3".to_owned() };
+//!     let progress = docker.create_image(Some(options), None, None).collect::<Vec<_>>().await;
+//!     assert!(progress.iter().all(Result::is_ok));
+//! 
+//!     let config    = MockContainerConfig { image: Some("alpine:3".to_owned()), ..Default::default() };
+//!     let container = docker.create_container(None, config).await.unwrap();
+//!     docker.start_container(&container.id, None).await.unwrap();
+//! 
+//!     let exit = docker.wait_container(&container.id, None).next().await.unwrap();
+//!     assert!(matches!(exit, Err(MockDockerError::DockerContainerWaitError { code: 1, .. })));
+//! 
+//!     let logs = docker.logs(&container.id, None).collect::<Vec<_>>().await;
+//!     assert_eq!(logs[0].as_ref().unwrap().to_string(), "boom\n");
+//! });
+//! ```
+//! 
+
+
+
+//		Packages
+
+use bytes::Bytes;
+use core::{
+	error::Error,
+	fmt::{Display, Formatter, self},
+	pin::Pin,
+};
+use futures_util::stream::{Stream, self};
+use std::{
+	collections::{BTreeMap, HashMap},
+	sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+
+
+//		Modules
+
+//		prelude																	
+/// The mocks, named after the genuine types.
+/// 
+/// This allows the mocks for the [Bollard](https://crates.io/crates/bollard)
+/// crate to be imported with a single glob import when testing, without
+/// renaming them. The names are the same as those used by the `swap_uses`
+/// attribute, and by the combined [`prelude`](crate::prelude).
+/// 
+pub mod prelude {
+	pub use super::{
+		MockContainerConfig         as Config,
+		MockContainerCreateResponse as ContainerCreateResponse,
+		MockContainerWaitResponse   as ContainerWaitResponse,
+		MockCreateContainerOptions  as CreateContainerOptions,
+		MockCreateImageInfo         as CreateImageInfo,
+		MockCreateImageOptions      as CreateImageOptions,
+		MockDocker                  as Docker,
+		MockDockerError             as Error,
+		MockLogOutput               as LogOutput,
+		MockProgressDetail          as ProgressDetail,
+		MockRemoveContainerOptions  as RemoveContainerOptions,
+	};
+}
+
+
+
+//		Enums
+
+//		MockContainerStatus														
+/// The status of a mocked container.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockContainerStatus {
+	/// The container has been created, but not started.
+	Created,
+	
+	/// The container has been started, and has not yet been waited for.
+	Running,
+	
+	/// The container has exited with the given code.
+	Exited(i64),
+	
+	/// The container has been removed.
+	Removed,
+}
+
+//		MockDockerError															
+/// A mocked error type for Bollard.
+/// 
+/// This mirrors the variants of `bollard::errors::Error` that the Docker
+/// daemon's responses produce.
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockDockerError {
+	/// The container exited with a non-zero code whilst being waited for.
+	DockerContainerWaitError {
+		/// The error message, if any.
+		error: String,
+		
+		/// The exit code.
+		code:  i64,
+	},
+	
+	/// The daemon responded with an error.
+	DockerResponseServerError {
+		/// The HTTP status code of the response.
+		status_code: u16,
+		
+		/// The error message.
+		message:     String,
+	},
+}
+
+//󰭅		Display																	
+impl Display for MockDockerError {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match *self {
+			Self::DockerContainerWaitError { ref error, code }             => {
+				write!(f, "Docker container wait error: {error}, code: {code}")
+			},
+			Self::DockerResponseServerError { status_code, ref message } => {
+				write!(f, "Docker responded with status code {status_code}: {message}")
+			},
+		}
+	}
+}
+
+//󰭅		Error																	
+impl Error for MockDockerError {}
+
+//		MockLogOutput															
+/// A chunk of output from a mocked container.
+/// 
+/// # See also
+/// 
+/// * `bollard::container::LogOutput`
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockLogOutput {
+	/// Output written to standard error.
+	StdErr {
+		/// The output.
+		message: Bytes,
+	},
+	
+	/// Output written to standard output.
+	StdOut {
+		/// The output.
+		message: Bytes,
+	},
+}
+
+//󰭅		MockLogOutput															
+impl MockLogOutput {
+	//		into_bytes															
+	/// Returns the output as bytes.
+	#[must_use]
+	pub fn into_bytes(self) -> Bytes {
+		match self {
+			Self::StdErr { message } | Self::StdOut { message } => message,
+		}
+	}
+}
+
+//󰭅		Display																	
+impl Display for MockLogOutput {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match *self {
+			Self::StdErr { ref message } | Self::StdOut { ref message } => {
+				write!(f, "{}", String::from_utf8_lossy(message))
+			},
+		}
+	}
+}
+
+
+
+//		Structs
+
+//		ContainerRecord															
+/// A container held by the mocked daemon.
+#[derive(Clone, Debug)]
+struct ContainerRecord {
+	/// The configuration that the container was created with.
+	config: MockContainerConfig,
+	
+	/// The ID of the container.
+	id:     String,
+	
+	/// The name of the container.
+	name:   String,
+	
+	/// The script that the container follows.
+	script: MockContainerScript,
+	
+	/// The status of the container.
+	status: MockContainerStatus,
+}
+
+//		DockerState																
+/// The state of a [`MockDocker`] instance.
+#[derive(Debug, Default)]
+struct DockerState {
+	/// The containers created so far, in order of creation.
+	containers: Vec<ContainerRecord>,
+	
+	/// The images that have been pulled or preloaded.
+	local:      Vec<String>,
+	
+	/// The images available to pull, with their layer IDs.
+	registry:   BTreeMap<String, Vec<String>>,
+	
+	/// The scripts for containers created from each image.
+	scripts:    HashMap<String, MockContainerScript>,
+}
+
+//󰭅		DockerState																
+impl DockerState {
+	//		find																
+	/// Finds a container that has not been removed, by ID, ID prefix, or name.
+	fn find(&mut self, name: &str) -> Result<&mut ContainerRecord, MockDockerError> {
+		let bare = name.trim_start_matches('/');
+		self.containers.iter_mut()
+			.filter(|container| container.status != MockContainerStatus::Removed)
+			.find(|container| container.name == bare || (bare.len() >= 12 && container.id.starts_with(bare)))
+			.ok_or_else(|| server_error(404, &format!("No such container: {name}")))
+	}
+}
+
+//		MockContainer															
+/// A snapshot of a container held by the mocked daemon.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockContainer {
+	//		Public properties													
+	/// The ID of the container.
+	pub id:     String,
+	
+	/// The name of the container.
+	pub name:   String,
+	
+	/// The configuration that the container was created with.
+	pub config: MockContainerConfig,
+	
+	/// The status of the container.
+	pub status: MockContainerStatus,
+}
+
+//		MockContainerConfig														
+/// The configuration of a container to create.
+/// 
+/// # See also
+/// 
+/// * `bollard::container::Config`
+/// 
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MockContainerConfig {
+	//		Public properties													
+	/// The command to run.
+	pub cmd:         Option<Vec<String>>,
+	
+	/// The environment variables, in `NAME=value` form.
+	pub env:         Option<Vec<String>>,
+	
+	/// The image to create the container from.
+	pub image:       Option<String>,
+	
+	/// The labels to attach to the container.
+	pub labels:      Option<HashMap<String, String>>,
+	
+	/// The working directory for the command.
+	pub working_dir: Option<String>,
+}
+
+//		MockContainerCreateResponse												
+/// The response to creating a container.
+/// 
+/// # See also
+/// 
+/// * `bollard::models::ContainerCreateResponse`
+/// 
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockContainerCreateResponse {
+	//		Public properties													
+	/// The ID of the created container.
+	pub id:       String,
+	
+	/// Any warnings encountered whilst creating the container.
+	pub warnings: Vec<String>,
+}
+
+//		MockContainerScript														
+/// What a container will output and how it will exit.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MockContainerScript {
+	//		Private properties													
+	/// The exit code.
+	exit_code:   i64,
+	
+	/// The output, in the order it is written.
+	logs:        Vec<MockLogOutput>,
+	
+	/// The error to return when the container is started, if any.
+	start_error: Option<MockDockerError>,
+}
+
+//󰭅		MockContainerScript														
+impl MockContainerScript {
+	//		new																	
+	/// Creates a new script for a container that exits with the given code.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `exit_code` - The exit code.
+	/// 
+	#[must_use]
+	pub fn new(exit_code: i64) -> Self {
+		Self { exit_code, ..Default::default() }
+	}
+	
+	//		fail_to_start														
+	/// Makes starting the container fail with the given error.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `error` - The error to return.
+	/// 
+	#[must_use]
+	pub fn fail_to_start(mut self, error: MockDockerError) -> Self {
+		self.start_error = Some(error);
+		self
+	}
+	
+	//		stderr																
+	/// Adds output written to standard error.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `output` - The output.
+	/// 
+	#[must_use]
+	pub fn stderr(mut self, output: &str) -> Self {
+		self.logs.push(MockLogOutput::StdErr { message: Bytes::copy_from_slice(output.as_bytes()) });
+		self
+	}
+	
+	//		stdout																
+	/// Adds output written to standard output.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `output` - The output.
+	/// 
+	#[must_use]
+	pub fn stdout(mut self, output: &str) -> Self {
+		self.logs.push(MockLogOutput::StdOut { message: Bytes::copy_from_slice(output.as_bytes()) });
+		self
+	}
+}
+
+//		MockContainerWaitResponse												
+/// The response to waiting for a container that exited successfully.
+/// 
+/// # See also
+/// 
+/// * `bollard::models::ContainerWaitResponse`
+/// 
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockContainerWaitResponse {
+	//		Public properties													
+	/// The exit code of the container.
+	pub status_code: i64,
+}
+
+//		MockCreateContainerOptions												
+/// The options for creating a container.
+/// 
+/// # See also
+/// 
+/// * `bollard::container::CreateContainerOptions`
+/// 
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MockCreateContainerOptions {
+	//		Public properties													
+	/// The name to give the container.
+	pub name: String,
+}
+
+//		MockCreateImageInfo														
+/// A progress update from pulling an image.
+/// 
+/// # See also
+/// 
+/// * `bollard::models::CreateImageInfo`
+/// 
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockCreateImageInfo {
+	//		Public properties													
+	/// The ID of the layer that the update is about, if any.
+	pub id:              Option<String>,
+	
+	/// The status message.
+	pub status:          Option<String>,
+	
+	/// The progress of the layer, if it is being downloaded.
+	pub progress_detail: Option<MockProgressDetail>,
+}
+
+//		MockCreateImageOptions													
+/// The options for pulling an image.
+/// 
+/// # See also
+/// 
+/// * `bollard::image::CreateImageOptions`
+/// 
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MockCreateImageOptions {
+	//		Public properties													
+	/// The name of the image to pull.
+	pub from_image: String,
+	
+	/// The tag to pull, which defaults to `latest` when empty.
+	pub tag:        String,
+}
+
+//		MockDocker																
+/// A mocked Docker client, backed by an in-memory daemon.
+/// 
+/// Clones share the same daemon, so a clone can be handed to the code under
+/// test while the original is used to make assertions.
+/// 
+/// # See also
+/// 
+/// * `bollard::Docker`
+/// 
+#[derive(Clone, Debug, Default)]
+pub struct MockDocker {
+	//		Private properties													
+	/// The state of the daemon, shared between clones.
+	state: Arc<Mutex<DockerState>>,
+}
+
+//󰭅		MockDocker																
+#[expect(clippy::unused_async, reason = "Needed for compatibility with Bollard")]
+impl MockDocker {
+	//		new																	
+	/// Creates a new client for a daemon with no images or containers.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+	
+	//		with_image															
+	/// Makes an image available to pull, made up of the given layers.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `image`  - The image, including its tag, e.g. `alpine:3`.
+	/// * `layers` - The IDs of the layers of the image.
+	/// 
+	#[must_use]
+	pub fn with_image(self, image: &str, layers: &[&str]) -> Self {
+		drop(self.lock().registry.insert(
+			normalise_image(image),
+			layers.iter().map(ToString::to_string).collect(),
+		));
+		self
+	}
+	
+	//		with_local_image													
+	/// Makes an image available locally, as if it had already been pulled.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `image` - The image, including its tag, e.g. `alpine:3`.
+	/// 
+	#[must_use]
+	pub fn with_local_image(self, image: &str) -> Self {
+		self.lock().local.push(normalise_image(image));
+		self
+	}
+	
+	//		with_script															
+	/// Sets what containers created from an image will output and how they
+	/// will exit. Containers from images without a script exit with code zero
+	/// and no output.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `image`  - The image, including its tag, e.g. `alpine:3`.
+	/// * `script` - The script for the containers.
+	/// 
+	#[must_use]
+	pub fn with_script(self, image: &str, script: MockContainerScript) -> Self {
+		drop(self.lock().scripts.insert(normalise_image(image), script));
+		self
+	}
+	
+	//		containers															
+	/// Returns the containers created so far, in order of creation, including
+	/// those that have been removed.
+	#[must_use]
+	pub fn containers(&self) -> Vec<MockContainer> {
+		self.lock().containers.iter().map(|container| MockContainer {
+			id:     container.id.clone(),
+			name:   container.name.clone(),
+			config: container.config.clone(),
+			status: container.status,
+		}).collect()
+	}
+	
+	//		create_container													
+	/// Creates a container from a local image.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if no image was given, if the image is not
+	/// available locally, or if the name is already in use.
+	/// 
+	/// # See also
+	/// 
+	/// * `bollard::Docker::create_container()`
+	/// 
+	pub async fn create_container(
+		&self,
+		options: Option<MockCreateContainerOptions>,
+		config:  MockContainerConfig,
+	) -> Result<MockContainerCreateResponse, MockDockerError> {
+		let mut state = self.lock();
+		let image     = normalise_image(config.image.as_deref().unwrap_or_default());
+		if config.image.is_none() {
+			return Err(server_error(400, "No command specified"));
+		}
+		if !state.local.contains(&image) {
+			return Err(server_error(404, &format!("No such image: {image}")));
+		}
+		let number    = state.containers.len().saturating_add(1);
+		let id        = format!("{number:064x}");
+		let name      = options.map_or_else(|| format!("container_{number}"), |given| given.name);
+		if state.containers.iter().any(|container| container.name == name && container.status != MockContainerStatus::Removed) {
+			return Err(server_error(409, &format!(r#"Conflict. The container name "/{name}" is already in use"#)));
+		}
+		let script    = state.scripts.get(&image).cloned().unwrap_or_default();
+		state.containers.push(ContainerRecord {
+			config,
+			id:     id.clone(),
+			name,
+			script,
+			status: MockContainerStatus::Created,
+		});
+		drop(state);
+		Ok(MockContainerCreateResponse { id, warnings: Vec::new() })
+	}
+	
+	//		create_image														
+	/// Pulls an image, returning a stream of progress updates.
+	/// 
+	/// # Errors
+	/// 
+	/// The stream will yield an error if the image is not available to pull.
+	/// 
+	/// # See also
+	/// 
+	/// * `bollard::Docker::create_image()`
+	/// 
+	pub fn create_image(
+		&self,
+		options:      Option<MockCreateImageOptions>,
+		_root_fs:     Option<Bytes>,
+		_credentials: Option<String>,
+	) -> Pin<Box<dyn Stream<Item = Result<MockCreateImageInfo, MockDockerError>> + Send>> {
+		let wanted    = options.unwrap_or_default();
+		let tag       = if wanted.tag.is_empty() { "latest" } else { wanted.tag.as_str() };
+		let image     = normalise_image(&format!("{}:{tag}", wanted.from_image));
+		let mut state = self.lock();
+		let Some(layers) = state.registry.get(&image).cloned() else {
+			let message = format!(
+				"pull access denied for {}, repository does not exist or may require 'docker login'",
+				wanted.from_image,
+			);
+			return Box::pin(stream::iter(vec![Err(server_error(404, &message))]));
+		};
+		let mut updates = vec![MockCreateImageInfo {
+			status: Some(format!("Pulling from {}", wanted.from_image)),
+			id:     Some(tag.to_owned()),
+			..Default::default()
+		}];
+		for layer in &layers {
+			let update = |status: &str, progress_detail: Option<MockProgressDetail>| MockCreateImageInfo {
+				id:     Some(layer.clone()),
+				status: Some(status.to_owned()),
+				progress_detail,
+			};
+			updates.push(update("Pulling fs layer", None));
+			updates.push(update("Downloading", Some(MockProgressDetail { current: Some(512), total: Some(1_024) })));
+			updates.push(update("Downloading", Some(MockProgressDetail { current: Some(1_024), total: Some(1_024) })));
+			updates.push(update("Download complete", None));
+			updates.push(update("Pull complete", None));
+		}
+		updates.push(MockCreateImageInfo {
+			status: Some(format!("Status: Downloaded newer image for {image}")),
+			..Default::default()
+		});
+		if !state.local.contains(&image) {
+			state.local.push(image);
+		}
+		drop(state);
+		Box::pin(stream::iter(updates.into_iter().map(Ok)))
+	}
+	
+	//		logs																
+	/// Returns a stream of the output of a container that has been started.
+	/// 
+	/// # Errors
+	/// 
+	/// The stream will yield an error if the container does not exist.
+	/// 
+	/// # See also
+	/// 
+	/// * `bollard::Docker::logs()`
+	/// 
+	pub fn logs(
+		&self,
+		container_name: &str,
+		_options:       Option<HashMap<String, String>>,
+	) -> Pin<Box<dyn Stream<Item = Result<MockLogOutput, MockDockerError>> + Send>> {
+		let result = self.lock().find(container_name).map(|container| {
+			if container.status == MockContainerStatus::Created {
+				Vec::new()
+			} else {
+				container.script.logs.clone()
+			}
+		});
+		match result {
+			Ok(logs)   => Box::pin(stream::iter(logs.into_iter().map(Ok))),
+			Err(error) => Box::pin(stream::iter(vec![Err(error)])),
+		}
+	}
+	
+	//		pulled_images														
+	/// Returns the images that are available locally, in the order they were
+	/// pulled or preloaded.
+	#[must_use]
+	pub fn pulled_images(&self) -> Vec<String> {
+		self.lock().local.clone()
+	}
+	
+	//		remove_container													
+	/// Removes a container.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the container does not exist, or if it is
+	/// running and removal is not forced.
+	/// 
+	/// # See also
+	/// 
+	/// * `bollard::Docker::remove_container()`
+	/// 
+	pub async fn remove_container(
+		&self,
+		container_name: &str,
+		options:        Option<MockRemoveContainerOptions>,
+	) -> Result<(), MockDockerError> {
+		let mut state = self.lock();
+		let container = state.find(container_name)?;
+		if container.status == MockContainerStatus::Running && !options.is_some_and(|given| given.force) {
+			return Err(server_error(409, &format!(
+				"You cannot remove a running container {}. Stop the container before attempting removal or force remove",
+				container.id,
+			)));
+		}
+		container.status = MockContainerStatus::Removed;
+		drop(state);
+		Ok(())
+	}
+	
+	//		start_container														
+	/// Starts a container.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the container does not exist, or if its
+	/// script says that it fails to start.
+	/// 
+	/// # See also
+	/// 
+	/// * `bollard::Docker::start_container()`
+	/// 
+	pub async fn start_container(
+		&self,
+		container_name: &str,
+		_options:       Option<HashMap<String, String>>,
+	) -> Result<(), MockDockerError> {
+		let mut state = self.lock();
+		let container = state.find(container_name)?;
+		if let Some(ref error) = container.script.start_error {
+			return Err(error.clone());
+		}
+		if container.status == MockContainerStatus::Created {
+			container.status = MockContainerStatus::Running;
+		}
+		drop(state);
+		Ok(())
+	}
+	
+	//		wait_container														
+	/// Waits for a container to exit, returning a stream that yields its exit
+	/// status.
+	/// 
+	/// # Errors
+	/// 
+	/// The stream will yield an error if the container does not exist, or if
+	/// it exits with a non-zero code.
+	/// 
+	/// # See also
+	/// 
+	/// * `bollard::Docker::wait_container()`
+	/// 
+	pub fn wait_container(
+		&self,
+		container_name: &str,
+		_options:       Option<HashMap<String, String>>,
+	) -> Pin<Box<dyn Stream<Item = Result<MockContainerWaitResponse, MockDockerError>> + Send>> {
+		let mut state = self.lock();
+		let result    = state.find(container_name).map(|container| {
+			let code = match container.status {
+				MockContainerStatus::Exited(code) => code,
+				MockContainerStatus::Created
+				| MockContainerStatus::Running
+				| MockContainerStatus::Removed    => container.script.exit_code,
+			};
+			container.status = MockContainerStatus::Exited(code);
+			code
+		});
+		drop(state);
+		let item = match result {
+			Ok(0)      => Ok(MockContainerWaitResponse { status_code: 0 }),
+			Ok(code)   => Err(MockDockerError::DockerContainerWaitError { error: String::new(), code }),
+			Err(error) => Err(error),
+		};
+		Box::pin(stream::iter(vec![item]))
+	}
+	
+	//		lock																
+	/// Locks the state of the daemon.
+	fn lock(&self) -> MutexGuard<'_, DockerState> {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+}
+
+//		MockProgressDetail														
+/// The progress of downloading a layer.
+/// 
+/// # See also
+/// 
+/// * `bollard::models::ProgressDetail`
+/// 
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockProgressDetail {
+	//		Public properties													
+	/// The number of bytes downloaded so far.
+	pub current: Option<i64>,
+	
+	/// The total number of bytes to download.
+	pub total:   Option<i64>,
+}
+
+//		MockRemoveContainerOptions												
+/// The options for removing a container.
+/// 
+/// # See also
+/// 
+/// * `bollard::container::RemoveContainerOptions`
+/// 
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MockRemoveContainerOptions {
+	//		Public properties													
+	/// Whether to remove the container even if it is running.
+	pub force: bool,
+	
+	/// Whether to remove the anonymous volumes of the container.
+	pub v:     bool,
+}
+
+
+
+//		Functions
+
+//		normalise_image															
+/// Normalises an image name so that it always carries a tag.
+fn normalise_image(image: &str) -> String {
+	let name = image.rsplit('/').next().unwrap_or(image);
+	if name.contains(':') || image.is_empty() {
+		image.to_owned()
+	} else {
+		format!("{image}:latest")
+	}
+}
+
+//		server_error															
+/// Creates an error as returned by the daemon.
+fn server_error(status_code: u16, message: &str) -> MockDockerError {
+	MockDockerError::DockerResponseServerError { status_code, message: message.to_owned() }
+}
@@ -0,0 +1,608 @@
+//! This module mocks a DNS-over-HTTPS upstream in order to test resolvers.
+//! 
+//! This module provides routes for the [`reqwest`](crate::reqwest) mock that
+//! act as a [DNS-over-HTTPS](https://www.rfc-editor.org/rfc/rfc8484) resolver,
+//! answering both DNS wire-format queries, by `GET` with a `dns` parameter or
+//! by `POST`, and queries to the JSON API popularised by Google and Cloudflare,
+//! by `GET` with `name` and `type` parameters. Answers come from a configured
+//! set of records, following CNAME chains, and unknown names are answered with
+//! `NXDOMAIN`. This is important because resolvers that use DNS-over-HTTPS, such as
+//! those built with [Hickory DNS](https://crates.io/crates/hickory-resolver),
+//! need to handle upstream failures and fall back, and public resolvers cannot
+//! be made to fail on demand.
+//! 
+//! The approach taken is that a [`MockDoh`] holds the records for one
+//! upstream, along with any failure to produce, and its
+//! [`routes()`](MockDoh::routes()) serve the queries. Several upstreams can be
+//! combined into one client, to test fallback from one to another.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use futures_executor::block_on;
+//! use serde_json::Value as Json;
+//! use sham::{
+//!     doh::{MockDnsRecord, MockDoh, MockDohFailure},
+//!     reqwest::create_routed_mock_client,
+//! };
+//! 
+//! let primary   = MockDoh::new("https://dns.example/dns-query").with_failure(MockDohFailure::Truncated);
+//! let secondary = MockDoh::new("https://dns.example.net/dns-query")
+//!     .with_record("www.example.com", MockDnsRecord::Cname("example.com".to_owned()))
+//!     .with_record("example.com",     MockDnsRecord::A("192.0.2.1".parse().unwrap()))
+//! ;
+//! let client    = create_routed_mock_client(primary.routes().into_iter().chain(secondary.routes()).collect());
+//! 
+//! block_on(async {
+//!     let answer: Json = client.get("https://dns.example.net/dns-query?name=www.example.com&type=A")
+//!         .send().await.unwrap()
+//!         .json().await.unwrap()
+//!     ;
+//!     assert_eq!(answer["Status"], 0);
+//!     assert_eq!(answer["Answer"][1]["data"], "192.0.2.1");
+//! });
+//! assert_eq!(secondary.queries()[0].name, "www.example.com");
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::reqwest::{
+	MockClient,
+	MockError,
+	MockRequest,
+	MockResponse,
+	MockRoute,
+	create_mock_response,
+	create_routed_mock_client,
+};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD as BASE64_URL};
+use core::{
+	net::{Ipv4Addr, Ipv6Addr},
+	str::from_utf8,
+};
+use reqwest::{IntoUrl, Method, StatusCode, Url};
+use serde_json::{Value as Json, json};
+use std::{
+	collections::{BTreeMap, HashMap},
+	sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+
+
+//		Constants
+
+/// The maximum number of CNAME records followed when answering a query.
+const MAX_CNAME_CHAIN: usize = 8;
+
+/// The DNS response code for a successful query.
+const RCODE_NOERROR: u16 = 0;
+
+/// The DNS response code for a name that does not exist.
+const RCODE_NXDOMAIN: u16 = 3;
+
+/// The DNS response code for a server failure.
+const RCODE_SERVFAIL: u16 = 2;
+
+/// The DNS response code for a refused query.
+const RCODE_REFUSED: u16 = 5;
+
+
+
+//		Enums
+
+//		MockDnsRecord															
+/// A DNS record served by [`MockDoh`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockDnsRecord {
+	/// An IPv4 address record.
+	A(Ipv4Addr),
+	
+	/// An IPv6 address record.
+	Aaaa(Ipv6Addr),
+	
+	/// A canonical name record, pointing to another name.
+	Cname(String),
+	
+	/// A text record.
+	Txt(String),
+}
+
+//󰭅		MockDnsRecord															
+impl MockDnsRecord {
+	//		record_type															
+	/// Returns the type of the record.
+	#[must_use]
+	pub const fn record_type(&self) -> MockDnsRecordType {
+		match *self {
+			Self::A(_)     => MockDnsRecordType::A,
+			Self::Aaaa(_)  => MockDnsRecordType::Aaaa,
+			Self::Cname(_) => MockDnsRecordType::Cname,
+			Self::Txt(_)   => MockDnsRecordType::Txt,
+		}
+	}
+	
+	//		json_data															
+	/// Returns the data of the record in the form used by the JSON API.
+	fn json_data(&self) -> String {
+		match *self {
+			Self::A(ip)             => ip.to_string(),
+			Self::Aaaa(ip)          => ip.to_string(),
+			Self::Cname(ref target) => format!("{}.", normalise(target)),
+			Self::Txt(ref text)     => format!("\"{text}\""),
+		}
+	}
+	
+	//		wire_data															
+	/// Returns the data of the record in DNS wire format.
+	fn wire_data(&self) -> Vec<u8> {
+		match *self {
+			Self::A(ip)             => ip.octets().to_vec(),
+			Self::Aaaa(ip)          => ip.octets().to_vec(),
+			Self::Cname(ref target) => encode_name(target),
+			Self::Txt(ref text)     => text.as_bytes().chunks(255).flat_map(|chunk| {
+				u8::try_from(chunk.len()).into_iter().chain(chunk.iter().copied())
+			}).collect(),
+		}
+	}
+}
+
+//		MockDnsRecordType														
+/// The type of a DNS record or query.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum MockDnsRecordType {
+	/// An IPv4 address record.
+	A,
+	
+	/// An IPv6 address record.
+	Aaaa,
+	
+	/// A canonical name record.
+	Cname,
+	
+	/// A text record.
+	Txt,
+	
+	/// Any other type, by its numeric code. Queries for these are answered
+	/// with no records.
+	Other(u16),
+}
+
+//󰭅		MockDnsRecordType														
+impl MockDnsRecordType {
+	//		code																
+	/// Returns the numeric code of the type.
+	#[must_use]
+	pub const fn code(&self) -> u16 {
+		match *self {
+			Self::A           => 1,
+			Self::Cname       => 5,
+			Self::Txt         => 16,
+			Self::Aaaa        => 28,
+			Self::Other(code) => code,
+		}
+	}
+	
+	//		from_code															
+	/// Returns the type with the given numeric code.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `code` - The numeric code.
+	/// 
+	#[must_use]
+	pub const fn from_code(code: u16) -> Self {
+		match code {
+			1     => Self::A,
+			5     => Self::Cname,
+			16    => Self::Txt,
+			28    => Self::Aaaa,
+			other => Self::Other(other),
+		}
+	}
+	
+	//		from_name															
+	/// Returns the type with the given mnemonic, e.g. `AAAA`, or numeric code,
+	/// as accepted by the `type` parameter of the JSON API.
+	fn from_name(name: &str) -> Option<Self> {
+		match name.to_ascii_uppercase().as_str() {
+			"A"     => Some(Self::A),
+			"AAAA"  => Some(Self::Aaaa),
+			"CNAME" => Some(Self::Cname),
+			"TXT"   => Some(Self::Txt),
+			other   => other.parse().ok().map(Self::from_code),
+		}
+	}
+}
+
+//		MockDohFailure															
+/// The ways in which a mocked DNS-over-HTTPS upstream can fail.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockDohFailure {
+	/// The upstream responds successfully, but with a body that cannot be
+	/// parsed as a DNS message or as JSON.
+	Malformed,
+	
+	/// The upstream answers every query with the `REFUSED` response code.
+	Refused,
+	
+	/// The upstream responds with `500 Internal Server Error`.
+	ServerError,
+	
+	/// The upstream answers every query with the `SERVFAIL` response code.
+	ServFail,
+	
+	/// The request times out.
+	Timeout,
+	
+	/// The upstream responds successfully, but the body is cut off halfway.
+	Truncated,
+	
+	/// The upstream responds successfully, but with an HTML page, as a captive
+	/// portal or misconfigured proxy would.
+	WrongContentType,
+}
+
+//		MockDohFormat															
+/// The format in which a DNS-over-HTTPS query was made.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockDohFormat {
+	/// The JSON API, i.e. `GET` with `name` and `type` parameters.
+	Json,
+	
+	/// DNS wire format, i.e. `GET` with a `dns` parameter, or `POST`.
+	Wire,
+}
+
+
+
+//		Structs
+
+//		MockDnsQuery															
+/// A record of a query received by [`MockDoh`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockDnsQuery {
+	//		Public properties													
+	/// The format of the query.
+	pub format:      MockDohFormat,
+	
+	/// The name queried, in lowercase and without a trailing dot.
+	pub name:        String,
+	
+	/// The type of record queried.
+	pub record_type: MockDnsRecordType,
+}
+
+//		MockDoh																	
+/// A mocked DNS-over-HTTPS upstream.
+/// 
+/// Clones share the same record of queries.
+/// 
+#[derive(Clone, Debug)]
+pub struct MockDoh {
+	//		Private properties													
+	/// The failure to produce, if any.
+	failure: Option<MockDohFailure>,
+	
+	/// The queries received, shared between clones.
+	queries: Arc<Mutex<Vec<MockDnsQuery>>>,
+	
+	/// The records, by normalised name.
+	records: BTreeMap<String, Vec<MockDnsRecord>>,
+	
+	/// The time to live of answers, in seconds.
+	ttl:     u32,
+	
+	/// The URL of the upstream.
+	url:     Url,
+}
+
+//󰭅		MockDoh																	
+impl MockDoh {
+	//		new																	
+	/// Creates a new upstream with no records, and a time to live of 300
+	/// seconds.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `url` - The URL of the upstream, e.g. `https://dns.example/dns-query`.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the URL is not valid.
+	/// 
+	pub fn new<U: IntoUrl>(url: U) -> Self {
+		Self {
+			failure: None,
+			queries: Arc::new(Mutex::new(Vec::new())),
+			records: BTreeMap::new(),
+			ttl:     300,
+			url:     url.into_url().unwrap(),
+		}
+	}
+	
+	//		with_failure														
+	/// Makes the upstream fail in the given way.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `failure` - The way in which to fail.
+	/// 
+	#[must_use]
+	pub const fn with_failure(mut self, failure: MockDohFailure) -> Self {
+		self.failure = Some(failure);
+		self
+	}
+	
+	//		with_record															
+	/// Adds a record. Several records of the same type can be added for a name.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name`   - The name that the record belongs to.
+	/// * `record` - The record.
+	/// 
+	#[must_use]
+	pub fn with_record(mut self, name: &str, record: MockDnsRecord) -> Self {
+		self.records.entry(normalise(name)).or_default().push(record);
+		self
+	}
+	
+	//		with_ttl															
+	/// Sets the time to live of answers.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `ttl` - The time to live, in seconds.
+	/// 
+	#[must_use]
+	pub const fn with_ttl(mut self, ttl: u32) -> Self {
+		self.ttl = ttl;
+		self
+	}
+	
+	//		client																
+	/// Creates a mock client that serves only this upstream.
+	#[must_use]
+	pub fn client(&self) -> MockClient {
+		create_routed_mock_client(self.routes())
+	}
+	
+	//		queries																
+	/// Returns the queries received, in order. Requests that could not be
+	/// parsed as queries are not included.
+	#[must_use]
+	pub fn queries(&self) -> Vec<MockDnsQuery> {
+		self.lock().clone()
+	}
+	
+	//		routes																
+	/// Returns the routes that serve the upstream, for use with
+	/// [`create_routed_mock_client()`].
+	#[must_use]
+	pub fn routes(&self) -> Vec<MockRoute> {
+		[Method::GET, Method::POST].into_iter().map(|method| {
+			let doh = self.clone();
+			MockRoute::new(method, self.url.clone(), move |request| doh.serve(request))
+		}).collect()
+	}
+	
+	//		url																	
+	/// Returns the URL of the upstream.
+	#[must_use]
+	pub fn url(&self) -> Url {
+		self.url.clone()
+	}
+	
+	//		lock																
+	/// Locks the record of queries.
+	fn lock(&self) -> MutexGuard<'_, Vec<MockDnsQuery>> {
+		self.queries.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+	
+	//		resolve																
+	/// Resolves a query, returning the response code and the answers along
+	/// with the names that they belong to.
+	fn resolve(&self, name: &str, record_type: MockDnsRecordType) -> (u16, Vec<(String, MockDnsRecord)>) {
+		let mut answers = Vec::new();
+		let mut current = normalise(name);
+		for _ in 0..MAX_CNAME_CHAIN {
+			let Some(records) = self.records.get(&current) else {
+				return (if answers.is_empty() { RCODE_NXDOMAIN } else { RCODE_NOERROR }, answers);
+			};
+			let matching = records.iter().filter(|record| record.record_type() == record_type).collect::<Vec<_>>();
+			if !matching.is_empty() {
+				answers.extend(matching.into_iter().map(|record| (current.clone(), record.clone())));
+				break;
+			}
+			let Some(cname) = records.iter().find(|record| record.record_type() == MockDnsRecordType::Cname) else {
+				break;
+			};
+			answers.push((current.clone(), cname.clone()));
+			if let MockDnsRecord::Cname(ref target) = *cname {
+				current = normalise(target);
+			}
+		}
+		(RCODE_NOERROR, answers)
+	}
+	
+	//		serve																
+	/// Serves a request.
+	fn serve(&self, request: &MockRequest) -> Result<MockResponse, MockError> {
+		let params   = request.url.query_pairs().into_owned().collect::<HashMap<String, String>>();
+		let received = if request.method == Method::POST {
+			request.body.as_deref().and_then(parse_query).map(|parsed| (MockDohFormat::Wire, parsed))
+		} else if let Some(encoded) = params.get("dns") {
+			BASE64_URL.decode(encoded).ok().as_deref().and_then(parse_query).map(|parsed| (MockDohFormat::Wire, parsed))
+		} else {
+			params.get("name").map(|name| (MockDohFormat::Json, WireQuery {
+				id:          0,
+				name:        normalise(name),
+				question:    Vec::new(),
+				record_type: params.get("type").map_or(Some(MockDnsRecordType::A), |kind| MockDnsRecordType::from_name(kind))
+					.unwrap_or(MockDnsRecordType::Other(0)),
+			}))
+		};
+		let Some((format, query)) = received else {
+			return Ok(respond(request, StatusCode::BAD_REQUEST, "text/plain", b"Bad Request"));
+		};
+		self.lock().push(MockDnsQuery { format, name: query.name.clone(), record_type: query.record_type });
+		let content_type = match format {
+			MockDohFormat::Json => "application/dns-json",
+			MockDohFormat::Wire => "application/dns-message",
+		};
+		let (rcode, answers) = match self.failure {
+			Some(MockDohFailure::Malformed)        => return Ok(respond(request, StatusCode::OK, content_type, b"\x00\x01garbage")),
+			Some(MockDohFailure::Refused)          => (RCODE_REFUSED, Vec::new()),
+			Some(MockDohFailure::ServerError)      => return Ok(respond(request, StatusCode::INTERNAL_SERVER_ERROR, "text/plain", b"Internal Server Error")),
+			Some(MockDohFailure::ServFail)         => (RCODE_SERVFAIL, Vec::new()),
+			Some(MockDohFailure::Timeout)          => return Err(MockError::builder().timeout().url(request.url.clone()).build()),
+			Some(MockDohFailure::WrongContentType) => return Ok(respond(request, StatusCode::OK, "text/html", b"<html><body>Sign in to continue</body></html>")),
+			Some(MockDohFailure::Truncated) | None => self.resolve(&query.name, query.record_type),
+		};
+		let mut body = match format {
+			MockDohFormat::Json => self.json_answer(&query, rcode, &answers),
+			MockDohFormat::Wire => self.wire_answer(&query, rcode, &answers),
+		};
+		if self.failure == Some(MockDohFailure::Truncated) {
+			body.truncate(body.len().div_euclid(2));
+		}
+		Ok(respond(request, StatusCode::OK, content_type, &body))
+	}
+	
+	//		json_answer															
+	/// Builds a response in the form used by the JSON API.
+	fn json_answer(&self, query: &WireQuery, rcode: u16, answers: &[(String, MockDnsRecord)]) -> Vec<u8> {
+		json!({
+			"Status":   rcode,
+			"TC":       false,
+			"RD":       true,
+			"RA":       true,
+			"AD":       false,
+			"CD":       false,
+			"Question": [{ "name": format!("{}.", query.name), "type": query.record_type.code() }],
+			"Answer":   answers.iter().map(|answer| json!({
+				"name": format!("{}.", answer.0),
+				"type": answer.1.record_type().code(),
+				"TTL":  self.ttl,
+				"data": answer.1.json_data(),
+			})).collect::<Vec<Json>>(),
+		}).to_string().into_bytes()
+	}
+	
+	//		wire_answer															
+	/// Builds a response in DNS wire format.
+	fn wire_answer(&self, query: &WireQuery, rcode: u16, answers: &[(String, MockDnsRecord)]) -> Vec<u8> {
+		let mut message = Vec::new();
+		message.extend(query.id.to_be_bytes());
+		message.extend((0x8180_u16 | rcode).to_be_bytes());
+		message.extend(1_u16.to_be_bytes());
+		message.extend(u16::try_from(answers.len()).unwrap_or(u16::MAX).to_be_bytes());
+		message.extend([0, 0, 0, 0]);
+		message.extend(&query.question);
+		for answer in answers {
+			let data = answer.1.wire_data();
+			message.extend(encode_name(&answer.0));
+			message.extend(answer.1.record_type().code().to_be_bytes());
+			message.extend(1_u16.to_be_bytes());
+			message.extend(self.ttl.to_be_bytes());
+			message.extend(u16::try_from(data.len()).unwrap_or(u16::MAX).to_be_bytes());
+			message.extend(data);
+		}
+		message
+	}
+}
+
+//		WireQuery																
+/// A parsed DNS query.
+struct WireQuery {
+	//		Private properties													
+	/// The ID of the query, to be echoed in the response.
+	id:          u16,
+	
+	/// The name queried, normalised.
+	name:        String,
+	
+	/// The question section of the query, to be echoed in the response.
+	question:    Vec<u8>,
+	
+	/// The type of record queried.
+	record_type: MockDnsRecordType,
+}
+
+
+
+//		Functions
+
+//		encode_name																
+/// Encodes a name in DNS wire format, without compression.
+fn encode_name(name: &str) -> Vec<u8> {
+	let normalised = normalise(name);
+	let mut encoded = Vec::new();
+	for label in normalised.split('.').filter(|label| !label.is_empty()) {
+		encoded.extend(u8::try_from(label.len()));
+		encoded.extend(label.as_bytes());
+	}
+	encoded.push(0);
+	encoded
+}
+
+//		normalise																
+/// Normalises a name to lowercase, without a trailing dot.
+fn normalise(name: &str) -> String {
+	name.trim_end_matches('.').to_ascii_lowercase()
+}
+
+//		parse_query																
+/// Parses a DNS wire-format query with a single question.
+fn parse_query(message: &[u8]) -> Option<WireQuery> {
+	let read_u16 = |offset: usize| -> Option<u16> {
+		Some(u16::from_be_bytes([*message.get(offset)?, *message.get(offset.checked_add(1)?)?]))
+	};
+	if read_u16(4)? != 1 {
+		return None;
+	}
+	let mut labels = Vec::new();
+	let mut offset = 12_usize;
+	loop {
+		let length = usize::from(*message.get(offset)?);
+		offset     = offset.checked_add(1)?;
+		if length == 0 {
+			break;
+		}
+		if length & 0xC0 != 0 {
+			return None;
+		}
+		let end    = offset.checked_add(length)?;
+		labels.push(from_utf8(message.get(offset..end)?).ok()?);
+		offset     = end;
+	}
+	let record_type = MockDnsRecordType::from_code(read_u16(offset)?);
+	let end         = offset.checked_add(4)?;
+	Some(WireQuery {
+		id:       read_u16(0)?,
+		name:     normalise(&labels.join(".")),
+		question: message.get(12..end)?.to_vec(),
+		record_type,
+	})
+}
+
+//		respond																	
+/// Creates a response to the given request.
+fn respond(request: &MockRequest, status: StatusCode, content_type: &str, body: &[u8]) -> MockResponse {
+	create_mock_response(
+		request.url.clone(),
+		status,
+		Some(content_type),
+		Some(body.len()),
+		HashMap::<String, String>::new(),
+		Ok(body),
+	)
+}
@@ -0,0 +1,254 @@
+//! This module provides object-safe traits in order to inject mocks at runtime.
+//! 
+//! This module provides the [`DynHttpClient`] and [`DynCommandRunner`] traits,
+//! which can be used as `Box<dyn DynHttpClient>` and `Box<dyn DynCommandRunner>`
+//! and are implemented for both the genuine types and the mocks. This is
+//! important because some applications prefer to inject their dependencies at
+//! runtime, or hold them in structs that cannot be generic, and so cannot swap
+//! the genuine types for the mocks using conditional compilation.
+//! 
+//! The approach taken is that the traits have no generic methods, and return
+//! boxed futures, so that they can be used as trait objects. The HTTP client
+//! trait is implemented for the Reqwest [`Client`], and for the
+//! [`MockClient`] of the [`reqwest`](crate::reqwest) module, whether it was
+//! created with expectations or with routes. Both produce a
+//! [`MockResponse`], with the body already read, or a [`MockError`], so that
+//! the application code sees the same types whichever is in use. The command
+//! trait is implemented by [`SystemCommandRunner`], which runs real commands,
+//! and is mocked by [`mockall`](https://crates.io/crates/mockall) as
+//! [`MockDynCommandRunner`].
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use futures_executor::block_on;
+//! use reqwest::{Method, StatusCode};
+//! use sham::{
+//!     dynamic::{DynCommandRunner, DynHttpClient, MockDynCommandRunner},
+//!     reqwest::{MockRoute, create_mock_response, create_routed_mock_client},
+//! };
+//! use std::{collections::HashMap, os::unix::process::ExitStatusExt, process::{ExitStatus, Output}};
+//! 
+//! struct App {
+//!     http:     Box<dyn DynHttpClient>,
+//!     commands: Box<dyn DynCommandRunner>,
+//! }
+//! 
+//! impl App {
+//!     fn healthy(&self) -> bool {
+//!         let url = "https://app.local/health".parse().unwrap();
+//!         block_on(self.http.fetch(url)).is_ok_and(|response| response.status.is_success())
+//!             && self.commands.run("systemctl", &["is-active".to_owned(), "app".to_owned()]).is_ok_and(|output| output.status.success())
+//!     }
+//! }
+//! 
+//! let mut commands = MockDynCommandRunner::new();
+//! commands.expect_run()
+//!     .withf(|program, args| program == "systemctl" && args == ["is-active", "app"])
+//!     .returning(|_, _| Ok(Output { status: ExitStatus::from_raw(0), stdout: b"active\n".to_vec(), stderr: vec![] }))
+//! ;
+//! let app = App {
+//!     http:     Box::new(create_routed_mock_client(vec![
+//!         MockRoute::new(Method::GET, "https://app.local/health", |request| {
+//!             Ok(create_mock_response(request.url.as_str(), StatusCode::OK, None::<&str>, None, HashMap::<String, String>::new(), Ok(b"")))
+//!         }),
+//!     ])),
+//!     commands: Box::new(commands),
+//! };
+//! assert!(app.healthy());
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::reqwest::{MockClient, MockError, MockResponse, ReplayCount};
+use bytes::Bytes;
+use core::{future::Future, pin::Pin};
+use mockall::automock;
+use reqwest::{Client, Error as ReqwestError, Method, Response, Url, header::HeaderMap};
+use std::{
+	io::Result as IoResult,
+	process::{Command, Output},
+	sync::Arc,
+};
+
+
+
+//		Types
+
+/// The future returned by [`DynHttpClient::send()`].
+pub type HttpFuture<'f> = Pin<Box<dyn Future<Output = Result<MockResponse, MockError>> + Send + 'f>>;
+
+
+
+//		Traits
+
+//§		DynCommandRunner														
+/// An object-safe runner of commands.
+/// 
+/// This is implemented by [`SystemCommandRunner`], and is mocked by
+/// [`mockall`](https://crates.io/crates/mockall) as [`MockDynCommandRunner`].
+/// 
+#[automock]
+pub trait DynCommandRunner: Send + Sync {
+	//		run																	
+	/// Runs the given program with the given arguments, waits for it to
+	/// finish, and collects its output.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `program` - The program to run.
+	/// * `args`    - The arguments to pass to the program.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the program could not be run.
+	/// 
+	/// # See also
+	/// 
+	/// * [`std::process::Command::output()`]
+	/// 
+	fn run(&self, program: &str, args: &[String]) -> IoResult<Output>;
+}
+
+//§		DynHttpClient															
+/// An object-safe HTTP client.
+/// 
+/// This is implemented for the Reqwest [`Client`], and for
+/// the [`MockClient`].
+/// 
+pub trait DynHttpClient: Send + Sync {
+	//		fetch																
+	/// Sends a `GET` request with no headers, and returns the response with its
+	/// body read.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `url` - The URL of the request.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the request could not be sent, or if the
+	/// body of the response could not be read.
+	/// 
+	fn fetch(&self, url: Url) -> HttpFuture<'_> {
+		self.send(Method::GET, url, HeaderMap::new(), None)
+	}
+	
+	//		send																
+	/// Sends a request, and returns the response with its body read.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `method`  - The HTTP method of the request.
+	/// * `url`     - The URL of the request.
+	/// * `headers` - The headers of the request.
+	/// * `body`    - The body of the request, if any.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the request could not be sent, or if the
+	/// body of the response could not be read. The [`MockClient`] only
+	/// supports the methods that it mocks, and returns a builder error for any
+	/// others.
+	/// 
+	fn send(&self, method: Method, url: Url, headers: HeaderMap, body: Option<Bytes>) -> HttpFuture<'_>;
+}
+
+//󰭅		DynHttpClient for Client												
+impl DynHttpClient for Client {
+	//		send																
+	fn send(&self, method: Method, url: Url, headers: HeaderMap, body: Option<Bytes>) -> HttpFuture<'_> {
+		let mut builder = self.request(method, url).headers(headers);
+		if let Some(bytes) = body {
+			builder = builder.body(bytes);
+		}
+		Box::pin(async move {
+			let response = builder.send().await.map_err(convert_error)?;
+			Ok(convert_response(response).await)
+		})
+	}
+}
+
+//󰭅		DynHttpClient for MockClient											
+impl DynHttpClient for MockClient {
+	//		send																
+	fn send(&self, method: Method, url: Url, headers: HeaderMap, body: Option<Bytes>) -> HttpFuture<'_> {
+		let mut builder = match method {
+			Method::DELETE => self.delete(url),
+			Method::GET    => self.get(url),
+			Method::PATCH  => self.patch(url),
+			Method::POST   => self.post(url),
+			Method::PUT    => self.put(url),
+			_              => return Box::pin(async move { Err(MockError::builder().builder().url(url).build()) }),
+		}.headers(headers);
+		if let Some(bytes) = body {
+			builder = builder.body(bytes);
+		}
+		Box::pin(async move { builder.send().await })
+	}
+}
+
+
+
+//		Structs
+
+//		SystemCommandRunner														
+/// A [`DynCommandRunner`] that runs real commands.
+#[derive(Clone, Copy, Debug, Default)]
+#[non_exhaustive]
+pub struct SystemCommandRunner;
+
+//󰭅		SystemCommandRunner														
+impl SystemCommandRunner {
+	//		new																	
+	/// Creates a new runner of real commands.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self
+	}
+}
+
+//󰭅		DynCommandRunner for SystemCommandRunner								
+impl DynCommandRunner for SystemCommandRunner {
+	//		run																	
+	fn run(&self, program: &str, args: &[String]) -> IoResult<Output> {
+		Command::new(program).args(args).output()
+	}
+}
+
+
+
+//		Functions
+
+//		convert_error															
+/// Converts a Reqwest error into a mocked one, keeping the original as its
+/// source.
+fn convert_error(error: ReqwestError) -> MockError {
+	MockError {
+		is_body:     error.is_body(),
+		is_builder:  error.is_builder(),
+		is_connect:  error.is_connect(),
+		is_decode:   error.is_decode(),
+		is_redirect: error.is_redirect(),
+		is_request:  error.is_request(),
+		is_status:   error.is_status(),
+		is_timeout:  error.is_timeout(),
+		status:      error.status(),
+		url:         error.url().cloned(),
+		source:      Some(Arc::new(error)),
+	}
+}
+
+//		convert_response														
+/// Converts a Reqwest response into a mocked one, reading its body.
+async fn convert_response(response: Response) -> MockResponse {
+	let url     = response.url().clone();
+	let status  = response.status();
+	let headers = response.headers().clone();
+	let version = response.version();
+	let body    = response.bytes().await.map(Arc::new).map_err(convert_error);
+	MockResponse { url, status, headers, body, chunks: None, stalled: false, trailers: None, version, replays: ReplayCount::default() }
+}
@@ -0,0 +1,342 @@
+//! This module provides host failover scenarios in order to test clients that
+//! fall back from one host to another.
+//! 
+//! This module provides [`MockFailover`], which combines a mocked
+//! [DNS-over-HTTPS](crate::doh) upstream with mocked HTTP hosts, some of which
+//! resolve but cannot be connected to, and others of which resolve and serve
+//! requests, and records the lookups and connection attempts made against them
+//! in order. This is important because failover is the standard way of coping
+//! with a host that has gone down, but testing it means setting up the same
+//! records, failing routes, and bookkeeping by hand in every project, and the
+//! order in which the hosts were tried cannot be seen from the final response.
+//! 
+//! The approach taken is that each host is added with its URL and address,
+//! which become an address record of the upstream, and a route of the
+//! [`reqwest`](crate::reqwest) mock for every common method under its URL.
+//! The routes of an unreachable host fail with a connection error, as though
+//! the address did not answer, and those of a reachable host are passed on to
+//! its responder. The [`routes()`](MockFailover::routes()) of the scenario
+//! include those of the upstream, so that a single routed client serves both
+//! the lookups and the requests, and every lookup and attempt is added to one
+//! shared list of [`MockFailoverAttempt`]s, which the assertions check.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use futures_executor::block_on;
+//! use reqwest::StatusCode;
+//! use sham::{failover::MockFailover, reqwest::create_mock_response};
+//! use std::collections::HashMap;
+//! 
+//! let failover = MockFailover::new("https://dns.example/dns-query")
+//!     .with_unreachable_host("https://a.example.com/", "192.0.2.1".parse().unwrap())
+//!     .with_reachable_host("https://b.example.com/", "192.0.2.2".parse().unwrap(), |request| {
+//!         Ok(create_mock_response(request.url.clone(), StatusCode::OK, None::<&str>, None, HashMap::<String, String>::new(), Ok(b"ok")))
+//!     })
+//! ;
+//! let client   = failover.client();
+//! 
+//! block_on(async {
+//!     for host in ["a.example.com", "b.example.com"] {
+//!         let lookup = client.get(format!("https://dns.example/dns-query?name={host}&type=A")).send().await.unwrap();
+//!         assert_eq!(lookup.status(), StatusCode::OK);
+//!         if let Ok(response) = client.get(format!("https://{host}/status")).send().await {
+//!             assert_eq!(response.status(), StatusCode::OK);
+//!             break;
+//!         }
+//!     }
+//! });
+//! 
+//! failover.assert_tried(&["a.example.com", "b.example.com"]);
+//! failover.assert_resolved_before_connecting();
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::{
+	doh::{MockDnsRecord, MockDoh},
+	reqwest::{MockClient, MockError, MockRequest, MockResponse, MockRoute, create_routed_mock_client},
+};
+use core::{
+	fmt::{Display, Formatter, Write as _, self},
+	net::IpAddr,
+};
+use reqwest::{IntoUrl, Method, Url};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+
+
+//		Enums
+
+//		MockFailoverAttempt														
+/// A lookup or connection attempt made during a [`MockFailover`] scenario.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockFailoverAttempt {
+	/// A connection to the given host, and whether it succeeded.
+	Connect(String, bool),
+	
+	/// A query to the upstream for the given name.
+	Lookup(String),
+}
+
+//󰭅		Display																	
+impl Display for MockFailoverAttempt {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match *self {
+			Self::Connect(ref host, true)  => write!(f, "connected to {host}"),
+			Self::Connect(ref host, false) => write!(f, "failed to connect to {host}"),
+			Self::Lookup(ref name)         => write!(f, "looked up {name}"),
+		}
+	}
+}
+
+
+
+//		Structs
+
+//		MockFailover															
+/// A failover scenario, with a DNS-over-HTTPS upstream and hosts that resolve
+/// but may not be reachable.
+/// 
+/// Clones share the same record of attempts, so a clone can be used to serve
+/// the routes while the original is used to make assertions.
+/// 
+#[derive(Clone, Debug)]
+pub struct MockFailover {
+	//		Private properties													
+	/// The lookups and connection attempts made so far, in order.
+	attempts: Arc<Mutex<Vec<MockFailoverAttempt>>>,
+	
+	/// The upstream that resolves the hosts.
+	doh:      MockDoh,
+	
+	/// The routes that serve the hosts.
+	hosts:    Vec<MockRoute>,
+}
+
+//󰭅		MockFailover															
+impl MockFailover {
+	//		new																	
+	/// Creates a new scenario with no hosts.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `url` - The URL of the DNS-over-HTTPS upstream, e.g.
+	///   `https://dns.example/dns-query`.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the URL is not valid.
+	/// 
+	pub fn new<U: IntoUrl>(url: U) -> Self {
+		Self {
+			attempts: Arc::new(Mutex::new(Vec::new())),
+			doh:      MockDoh::new(url),
+			hosts:    Vec::new(),
+		}
+	}
+	
+	//		with_host															
+	/// Adds a host that resolves to the given address, along with its routes,
+	/// which are served by the given function. An error of [`None`] from the
+	/// function means that the connection failed.
+	fn with_host<U, F>(mut self, url: U, address: IpAddr, responder: F) -> Self
+	where
+		U: IntoUrl,
+		F: Fn(&MockRequest) -> Result<MockResponse, Option<MockError>> + Send + Sync + 'static,
+	{
+		let base   = url.into_url().expect("Invalid host URL");
+		let name   = base.host_str().expect("Host URL has no host").to_owned();
+		let record = match address {
+			IpAddr::V4(v4) => MockDnsRecord::A(v4),
+			IpAddr::V6(v6) => MockDnsRecord::Aaaa(v6),
+		};
+		self.doh   = self.doh.with_record(&name, record);
+		let shared = Arc::new(responder);
+		for method in [Method::DELETE, Method::GET, Method::PATCH, Method::POST, Method::PUT] {
+			let attempts = Arc::clone(&self.attempts);
+			let host     = name.clone();
+			let serve    = Arc::clone(&shared);
+			self.hosts.push(MockRoute::prefixed(method, base.clone(), move |request| {
+				let result = serve(request);
+				lock(&attempts).push(MockFailoverAttempt::Connect(host.clone(), !matches!(result, Err(None))));
+				result.map_err(|error| error.unwrap_or_else(|| refused(&request.url)))
+			}));
+		}
+		self
+	}
+	
+	//		with_reachable_host													
+	/// Adds a host that resolves to the given address and serves requests.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `url`       - The URL of the host, e.g. `https://b.example.com/`.
+	///   Requests to any path under it are served.
+	/// * `address`   - The address that the host resolves to.
+	/// * `responder` - The function that produces the response to a request.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the URL is not valid, or has no host.
+	/// 
+	#[must_use]
+	pub fn with_reachable_host<U, F>(self, url: U, address: IpAddr, responder: F) -> Self
+	where
+		U: IntoUrl,
+		F: Fn(&MockRequest) -> Result<MockResponse, MockError> + Send + Sync + 'static,
+	{
+		self.with_host(url, address, move |request| responder(request).map_err(Some))
+	}
+	
+	//		with_unreachable_host												
+	/// Adds a host that resolves to the given address, but cannot be connected
+	/// to, so that every request to it fails with a connection error.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `url`     - The URL of the host, e.g. `https://a.example.com/`.
+	///   Requests to any path under it fail.
+	/// * `address` - The address that the host resolves to.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the URL is not valid, or has no host.
+	/// 
+	#[must_use]
+	pub fn with_unreachable_host<U: IntoUrl>(self, url: U, address: IpAddr) -> Self {
+		self.with_host(url, address, |_| Err(None))
+	}
+	
+	//		assert_resolved_before_connecting									
+	/// Checks that every host was looked up before the first attempt to
+	/// connect to it.
+	/// 
+	/// # Panics
+	/// 
+	/// Panics with the list of attempts if a connection to a host was attempted
+	/// before the host had been looked up.
+	/// 
+	pub fn assert_resolved_before_connecting(&self) {
+		let attempts = self.attempts();
+		for (index, attempt) in attempts.iter().enumerate() {
+			if let MockFailoverAttempt::Connect(ref host, _) = *attempt {
+				let resolved = attempts.iter().take(index).any(|earlier| *earlier == MockFailoverAttempt::Lookup(host.clone()));
+				assert!(resolved, "Expected {host} to be looked up before connecting to it:{}", timeline(&attempts));
+			}
+		}
+	}
+	
+	//		assert_tried														
+	/// Checks that connections were attempted to the given hosts, in the given
+	/// order, and to no others.
+	/// 
+	/// Repeated attempts to the same host, such as retries before failing
+	/// over, are only counted the first time, so that this checks the order of
+	/// failover regardless of any retry policy.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `hosts` - The hosts, such as `a.example.com`, in the order in which
+	///   they should have been tried.
+	/// 
+	/// # Panics
+	/// 
+	/// Panics with the list of attempts if the hosts were tried in a different
+	/// order, or if a different set of hosts was tried.
+	/// 
+	pub fn assert_tried(&self, hosts: &[&str]) {
+		let attempts = self.attempts();
+		let tried    = self.tried();
+		assert!(tried == hosts, "Expected connections to {hosts:?}, in that order, but got {tried:?}:{}", timeline(&attempts));
+	}
+	
+	//		attempts															
+	/// The lookups and connection attempts made so far, in order.
+	#[must_use]
+	pub fn attempts(&self) -> Vec<MockFailoverAttempt> {
+		lock(&self.attempts).clone()
+	}
+	
+	//		client																
+	/// Creates a mock client that serves the upstream and the hosts.
+	#[must_use]
+	pub fn client(&self) -> MockClient {
+		create_routed_mock_client(self.routes())
+	}
+	
+	//		doh																	
+	/// The DNS-over-HTTPS upstream that resolves the hosts, for checking the
+	/// queries that it received.
+	#[must_use]
+	pub const fn doh(&self) -> &MockDoh {
+		&self.doh
+	}
+	
+	//		routes																
+	/// Returns the routes that serve the upstream and the hosts, for use with
+	/// [`create_routed_mock_client()`].
+	#[must_use]
+	pub fn routes(&self) -> Vec<MockRoute> {
+		let lookups = self.doh.routes().into_iter().map(|route| {
+			let (attempts, doh) = (Arc::clone(&self.attempts), self.doh.clone());
+			MockRoute::new(route.method().clone(), route.url().clone(), move |request| {
+				let before = doh.queries().len();
+				let result = route.respond(request);
+				lock(&attempts).extend(doh.queries().into_iter().skip(before).map(|query| MockFailoverAttempt::Lookup(query.name)));
+				result
+			})
+		}).collect::<Vec<_>>();
+		lookups.into_iter().chain(self.hosts.iter().cloned()).collect()
+	}
+	
+	//		tried																
+	/// The hosts to which connections were attempted, in the order in which
+	/// each was first tried.
+	#[must_use]
+	pub fn tried(&self) -> Vec<String> {
+		let mut hosts = Vec::<String>::new();
+		for attempt in self.attempts() {
+			if let MockFailoverAttempt::Connect(host, _) = attempt {
+				if !hosts.contains(&host) {
+					hosts.push(host);
+				}
+			}
+		}
+		hosts
+	}
+}
+
+
+
+//		Functions
+
+//		lock																	
+/// Locks the given attempts, recovering from poisoning.
+fn lock(attempts: &Mutex<Vec<MockFailoverAttempt>>) -> MutexGuard<'_, Vec<MockFailoverAttempt>> {
+	attempts.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+//		refused																	
+/// Creates the error for a connection to the given URL that was refused.
+fn refused(url: &Url) -> MockError {
+	MockError::builder().connect().message("connection refused").url(url.clone()).build()
+}
+
+//		timeline																
+/// Describes the given attempts, one per line.
+fn timeline(attempts: &[MockFailoverAttempt]) -> String {
+	let mut text = String::new();
+	for (number, attempt) in attempts.iter().enumerate() {
+		_ = write!(text, "\n  {}: {attempt}", number + 1);
+	}
+	if attempts.is_empty() {
+		text.push_str("\n  (no attempts)");
+	}
+	text
+}
@@ -0,0 +1,321 @@
+//! This module provides fault injection in order to test resilience.
+//! 
+//! This module provides a [`FaultPlan`], which describes the latency and the
+//! errors to inject, and which can be attached to several of the mocks in this
+//! crate, such as the routes of the [`reqwest`](crate::reqwest) mock and the
+//! namespace of the [`unix`](crate::unix) mock. This is important because
+//! resilience tests need the same chaos to be applied consistently across all
+//! of the external dependencies of the code under test, and should not have to
+//! configure each mock separately in its own way.
+//! 
+//! The approach taken is that each interaction with a mock that has a plan
+//! attached draws a [`Fault`] from the plan, which gives the latency to apply
+//! and the kind of error to produce, if any. The draws come from a pseudo-random
+//! generator seeded by the plan, so that a failing test can be reproduced by
+//! running it with the same seed. Clones of a plan share the same generator, so
+//! that a single sequence of faults is spread across all of the mocks that it
+//! is attached to, and the faults injected so far can be inspected afterwards.
+//! Each mock translates the kinds of fault into its own errors, and
+//! [`FaultKind::io_error()`] does the same for I/O errors, for use with other
+//! mocks.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use core::time::Duration;
+//! use sham::fault::{FaultKind, FaultPlan};
+//! 
+//! let plan = FaultPlan::new(42)
+//!     .with_latency(Duration::from_millis(5), Duration::from_millis(20))
+//!     .with_error_rate(0.5)
+//!     .with_error_kinds(&[FaultKind::ConnectionReset, FaultKind::Timeout])
+//! ;
+//! let faults = (0..100).map(|_| plan.draw()).collect::<Vec<_>>();
+//! 
+//! assert!(faults.iter().all(|fault| fault.latency >= Duration::from_millis(5)));
+//! assert!(faults.iter().any(|fault| fault.kind == Some(FaultKind::Timeout)));
+//! assert_eq!(plan.injected().len(), faults.iter().filter(|fault| fault.kind.is_some()).count());
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::seed::{seed_for, split_mix64};
+use core::{
+	fmt::{Display, Formatter, self},
+	time::Duration,
+};
+use std::{
+	io::{Error as IoError, ErrorKind},
+	sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+
+
+//		Enums
+
+//		FaultKind																
+/// A kind of fault that can be injected.
+/// 
+/// Each mock translates these into the errors that it would produce when the
+/// equivalent failure happens for real.
+/// 
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum FaultKind {
+	/// The connection was refused.
+	ConnectionRefused,
+	
+	/// The connection was reset by the other side.
+	ConnectionReset,
+	
+	/// The other side is up, but reports that it is unavailable, such as with
+	/// an HTTP `503 Service Unavailable` response.
+	ServiceUnavailable,
+	
+	/// The operation timed out.
+	Timeout,
+}
+
+//󰭅		FaultKind																
+impl FaultKind {
+	//		all																	
+	/// Returns all of the kinds of fault.
+	#[must_use]
+	pub const fn all() -> [Self; 4] {
+		[Self::ConnectionRefused, Self::ConnectionReset, Self::ServiceUnavailable, Self::Timeout]
+	}
+	
+	//		io_error															
+	/// Returns the I/O error that corresponds to the fault.
+	#[must_use]
+	pub fn io_error(self) -> IoError {
+		let kind = match self {
+			Self::ConnectionRefused  => ErrorKind::ConnectionRefused,
+			Self::ConnectionReset    => ErrorKind::ConnectionReset,
+			Self::ServiceUnavailable => ErrorKind::ConnectionAborted,
+			Self::Timeout            => ErrorKind::TimedOut,
+		};
+		IoError::new(kind, self.to_string())
+	}
+}
+
+//󰭅		Display																	
+impl Display for FaultKind {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let description = match *self {
+			Self::ConnectionRefused  => "injected fault: connection refused",
+			Self::ConnectionReset    => "injected fault: connection reset",
+			Self::ServiceUnavailable => "injected fault: service unavailable",
+			Self::Timeout            => "injected fault: timed out",
+		};
+		write!(f, "{description}")
+	}
+}
+
+
+
+//		Structs
+
+//		Fault																	
+/// A fault drawn from a [`FaultPlan`], to be applied to a single interaction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Fault {
+	//		Public properties													
+	/// The latency to apply before the interaction completes.
+	pub latency: Duration,
+	
+	/// The kind of error to produce, if any.
+	pub kind:    Option<FaultKind>,
+}
+
+//		FaultPlan																
+/// A plan describing the latency and errors to inject into the mocks that it
+/// is attached to.
+/// 
+/// By default, a plan injects no latency and no errors. Clones share the same
+/// generator and record of injected faults.
+/// 
+#[derive(Clone, Debug)]
+pub struct FaultPlan {
+	//		Private properties													
+	/// The state shared by all clones of the plan.
+	state: Arc<Mutex<FaultState>>,
+}
+
+//󰭅		FaultPlan																
+impl FaultPlan {
+	//		new																	
+	/// Creates a new plan, which injects nothing until configured.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `seed` - The seed for the generator that decides which faults to
+	///   inject.
+	/// 
+	#[must_use]
+	pub fn new(seed: u64) -> Self {
+		Self { state: Arc::new(Mutex::new(FaultState {
+			error_kinds: FaultKind::all().to_vec(),
+			error_rate:  0.0,
+			generator:   seed,
+			injected:    Vec::new(),
+			latency:     (Duration::ZERO, Duration::ZERO),
+			seed,
+		})) }
+	}
+	
+	//		seeded																
+	/// Creates a new plan, which injects nothing until configured, seeded from
+	/// the seed of the current test.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the `SHAM_SEED` environment variable is
+	/// set, but is not an unsigned 64-bit integer.
+	/// 
+	/// # See also
+	/// 
+	/// * [`seed_for()`](crate::seed::seed_for())
+	/// 
+	#[must_use]
+	pub fn seeded() -> Self {
+		Self::new(seed_for("fault"))
+	}
+	
+	//		with_error_kinds													
+	/// Sets the kinds of error to inject, which are chosen between evenly. By
+	/// default, all kinds are injected.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `kinds` - The kinds of error to inject. If this is empty, no errors
+	///   will be injected.
+	/// 
+	#[must_use]
+	pub fn with_error_kinds(self, kinds: &[FaultKind]) -> Self {
+		self.lock().error_kinds = kinds.to_vec();
+		self
+	}
+	
+	//		with_error_rate														
+	/// Sets the proportion of interactions that fail.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `rate` - The proportion of interactions that fail, from `0.0` for
+	///   none to `1.0` for all. Values outside this range are clamped.
+	/// 
+	#[must_use]
+	pub fn with_error_rate(self, rate: f64) -> Self {
+		self.lock().error_rate = rate.clamp(0.0, 1.0);
+		self
+	}
+	
+	//		with_latency														
+	/// Sets the range of latency to apply to every interaction, which is chosen
+	/// evenly from between the given bounds.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `min` - The minimum latency.
+	/// * `max` - The maximum latency. If this is less than the minimum, the
+	///   minimum is always used.
+	/// 
+	#[must_use]
+	pub fn with_latency(self, min: Duration, max: Duration) -> Self {
+		self.lock().latency = (min, max.max(min));
+		self
+	}
+	
+	//		draw																
+	/// Draws the fault to apply to the next interaction, recording it if an
+	/// error is to be injected.
+	/// 
+	/// This is called by the mocks that the plan is attached to, but can also
+	/// be called directly in order to apply the plan to other mocks.
+	/// 
+	pub fn draw(&self) -> Fault {
+		let mut state  = self.lock();
+		let (min, max) = state.latency;
+		let latency    = min.saturating_add(max.saturating_sub(min).mul_f64(state.fraction()));
+		let fails      = state.fraction() < state.error_rate;
+		let choice     = state.next();
+		let kind       = fails.then(|| {
+			let count = u64::try_from(state.error_kinds.len()).unwrap_or(u64::MAX);
+			choice.checked_rem(count)
+				.and_then(|index| usize::try_from(index).ok())
+				.and_then(|index| state.error_kinds.get(index).copied())
+		}).flatten();
+		if let Some(injected) = kind {
+			state.injected.push(injected);
+		}
+		drop(state);
+		Fault { latency, kind }
+	}
+	
+	//		injected															
+	/// Returns the kinds of error injected so far, in order.
+	#[must_use]
+	pub fn injected(&self) -> Vec<FaultKind> {
+		self.lock().injected.clone()
+	}
+	
+	//		seed																
+	/// Returns the seed that the plan was created with.
+	#[must_use]
+	pub fn seed(&self) -> u64 {
+		self.lock().seed
+	}
+	
+	//		lock																
+	/// Locks the state of the plan, recovering from poisoning.
+	fn lock(&self) -> MutexGuard<'_, FaultState> {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+}
+
+//		FaultState																
+/// The state of a [`FaultPlan`].
+#[derive(Debug)]
+struct FaultState {
+	//		Private properties													
+	/// The kinds of error to inject.
+	error_kinds: Vec<FaultKind>,
+	
+	/// The proportion of interactions that fail.
+	error_rate:  f64,
+	
+	/// The state of the pseudo-random generator.
+	generator:   u64,
+	
+	/// The kinds of error injected so far.
+	injected:    Vec<FaultKind>,
+	
+	/// The minimum and maximum latency.
+	latency:     (Duration, Duration),
+	
+	/// The seed that the plan was created with.
+	seed:        u64,
+}
+
+//󰭅		FaultState																
+impl FaultState {
+	//		fraction															
+	/// Returns the next pseudo-random number, as a fraction from `0.0` up to
+	/// but not including `1.0`.
+	fn fraction(&mut self) -> f64 {
+		let high = u32::try_from(self.next() >> 32_u8).unwrap_or(u32::MAX);
+		f64::from(high) / 4_294_967_296.0
+	}
+	
+	//		next																
+	/// Returns the next pseudo-random number.
+	const fn next(&mut self) -> u64 {
+		split_mix64(&mut self.generator)
+	}
+}
@@ -0,0 +1,376 @@
+//! This module mocks external IP and geo-IP services in order to test lookups.
+//! 
+//! This module provides prebuilt routes for the [`reqwest`](crate::reqwest)
+//! mock that imitate common "what is my IP" and geo-IP services, i.e.
+//! [ipify](https://www.ipify.org/), [icanhazip](https://icanhazip.com/),
+//! [ifconfig.me](https://ifconfig.me/), [ip-api](https://ip-api.com/), and
+//! [ipinfo](https://ipinfo.io/), returning a configured address and location
+//! in the format of each service. This is important because network diagnostic
+//! features should not make external calls from tests, and because the ways in
+//! which these free services fail, such as rate limiting, need to be handled.
+//! 
+//! The approach taken is that a [`MockGeoIp`] holds the address and location
+//! to report, along with any failures to produce, and its
+//! [`routes()`](MockGeoIp::routes()) serve the endpoints of every service. The
+//! routes can be served on their own using [`client()`](MockGeoIp::client()),
+//! or combined with other routes. Failures can be set for all services or for
+//! individual ones, so that fallback from one service to another can be tested.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use futures_executor::block_on;
+//! use serde_json::Value as Json;
+//! use sham::geoip::{MockGeoIp, MockGeoIpFailure, MockGeoIpService};
+//! 
+//! let geoip  = MockGeoIp::new("203.0.113.7".parse().unwrap())
+//!     .with_service_failure(MockGeoIpService::Ipify, MockGeoIpFailure::RateLimited)
+//! ;
+//! let client = geoip.client();
+//! 
+//! block_on(async {
+//!     let limited = client.get("https://api.ipify.org/").send().await.unwrap();
+//!     assert_eq!(limited.status(), 429);
+//! 
+//!     let ip = client.get("https://icanhazip.com/").send().await.unwrap().text().await.unwrap();
+//!     assert_eq!(ip.trim(), "203.0.113.7");
+//! 
+//!     let info: Json = client.get("https://ipinfo.io/json").send().await.unwrap().json().await.unwrap();
+//!     assert_eq!(info["country"], "GB");
+//! });
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::reqwest::{
+	MockClient,
+	MockError,
+	MockRequest,
+	MockResponse,
+	MockRoute,
+	create_mock_response,
+	create_routed_mock_client,
+};
+use core::net::IpAddr;
+use reqwest::{Method, StatusCode};
+use serde_json::json;
+use std::collections::HashMap;
+
+
+
+//		Enums
+
+//		MockGeoIpFailure														
+/// The ways in which a mocked geo-IP service can fail.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockGeoIpFailure {
+	/// The connection is refused.
+	ConnectionRefused,
+	
+	/// The service responds successfully, but with a body that is not in the
+	/// expected format.
+	Malformed,
+	
+	/// The service responds with `429 Too Many Requests`.
+	RateLimited,
+	
+	/// The service responds with `503 Service Unavailable`.
+	ServerError,
+	
+	/// The request times out.
+	Timeout,
+}
+
+//		MockGeoIpService														
+/// The services imitated by [`MockGeoIp`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockGeoIpService {
+	/// `https://icanhazip.com/`, which returns the address as text.
+	Icanhazip,
+	
+	/// `https://ifconfig.me/ip`, which returns the address as text.
+	IfconfigMe,
+	
+	/// `http://ip-api.com/json`, which returns the address and location as
+	/// JSON, optionally for a given address.
+	IpApi,
+	
+	/// `https://api.ipify.org/`, which returns the address as text, or as JSON
+	/// when given `?format=json`.
+	Ipify,
+	
+	/// `https://ipinfo.io/json`, which returns the address and location as
+	/// JSON, optionally for a given address.
+	IpInfo,
+}
+
+//󰭅		MockGeoIpService														
+impl MockGeoIpService {
+	//		all																	
+	/// Returns all of the services.
+	#[must_use]
+	pub const fn all() -> [Self; 5] {
+		[Self::Icanhazip, Self::IfconfigMe, Self::IpApi, Self::Ipify, Self::IpInfo]
+	}
+	
+	//		base_url															
+	/// Returns the URL that the routes of the service are based on.
+	#[must_use]
+	pub const fn base_url(&self) -> &'static str {
+		match *self {
+			Self::Icanhazip  => "https://icanhazip.com/",
+			Self::IfconfigMe => "https://ifconfig.me/ip",
+			Self::IpApi      => "http://ip-api.com/json",
+			Self::Ipify      => "https://api.ipify.org/",
+			Self::IpInfo     => "https://ipinfo.io/",
+		}
+	}
+}
+
+
+
+//		Structs
+
+//		MockGeoIp																
+/// A set of mocked external IP and geo-IP services.
+#[derive(Clone, Debug)]
+pub struct MockGeoIp {
+	//		Private properties													
+	/// The failures to produce, by service.
+	failures: HashMap<&'static str, MockGeoIpFailure>,
+	
+	/// The address to report.
+	ip:       IpAddr,
+	
+	/// The location to report.
+	location: MockGeoLocation,
+}
+
+//󰭅		MockGeoIp																
+impl MockGeoIp {
+	//		new																	
+	/// Creates a new set of services that report the given address, located
+	/// with the default [`MockGeoLocation`].
+	/// 
+	/// # Parameters
+	/// 
+	/// * `ip` - The address to report.
+	/// 
+	#[must_use]
+	pub fn new(ip: IpAddr) -> Self {
+		Self { failures: HashMap::new(), ip, location: MockGeoLocation::default() }
+	}
+	
+	//		with_failure														
+	/// Makes every service fail in the given way.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `failure` - The way in which to fail.
+	/// 
+	#[must_use]
+	pub fn with_failure(mut self, failure: MockGeoIpFailure) -> Self {
+		for service in MockGeoIpService::all() {
+			_ = self.failures.insert(service.base_url(), failure);
+		}
+		self
+	}
+	
+	//		with_location														
+	/// Sets the location to report.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `location` - The location.
+	/// 
+	#[must_use]
+	pub fn with_location(mut self, location: MockGeoLocation) -> Self {
+		self.location = location;
+		self
+	}
+	
+	//		with_service_failure												
+	/// Makes a single service fail in the given way.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `service` - The service to fail.
+	/// * `failure` - The way in which to fail.
+	/// 
+	#[must_use]
+	pub fn with_service_failure(mut self, service: MockGeoIpService, failure: MockGeoIpFailure) -> Self {
+		_ = self.failures.insert(service.base_url(), failure);
+		self
+	}
+	
+	//		client																
+	/// Creates a mock client that serves only these services.
+	#[must_use]
+	pub fn client(&self) -> MockClient {
+		create_routed_mock_client(self.routes())
+	}
+	
+	//		routes																
+	/// Returns the routes that serve every service, for use with
+	/// [`create_routed_mock_client()`].
+	#[must_use]
+	pub fn routes(&self) -> Vec<MockRoute> {
+		MockGeoIpService::all().into_iter().map(|service| self.service_route(service)).collect()
+	}
+	
+	//		service_route														
+	/// Returns the route that serves a single service.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `service` - The service to serve.
+	/// 
+	#[must_use]
+	pub fn service_route(&self, service: MockGeoIpService) -> MockRoute {
+		let geoip = self.clone();
+		MockRoute::prefixed(Method::GET, service.base_url(), move |request| geoip.serve(service, request))
+	}
+	
+	//		serve																
+	/// Serves a request to a service.
+	fn serve(&self, service: MockGeoIpService, request: &MockRequest) -> Result<MockResponse, MockError> {
+		if let Some(&failure) = self.failures.get(service.base_url()) {
+			return fail(failure, request);
+		}
+		let queried  = request.url.path_segments()
+			.and_then(|mut segments| match service {
+				MockGeoIpService::IpApi  => segments.nth(1),
+				MockGeoIpService::IpInfo => segments.next().filter(|segment| *segment != "json"),
+				MockGeoIpService::Icanhazip
+				| MockGeoIpService::IfconfigMe
+				| MockGeoIpService::Ipify => None,
+			})
+			.and_then(|segment| segment.parse::<IpAddr>().ok())
+			.unwrap_or(self.ip)
+		;
+		let location = &self.location;
+		Ok(match service {
+			MockGeoIpService::Icanhazip | MockGeoIpService::IfconfigMe => {
+				respond(request, StatusCode::OK, "text/plain", &format!("{queried}\n"))
+			},
+			MockGeoIpService::Ipify if request.url.query_pairs().any(|(key, value)| key == "format" && value == "json") => {
+				respond(request, StatusCode::OK, "application/json", &json!({ "ip": queried }).to_string())
+			},
+			MockGeoIpService::Ipify => {
+				respond(request, StatusCode::OK, "text/plain", &queried.to_string())
+			},
+			MockGeoIpService::IpApi => respond(request, StatusCode::OK, "application/json", &json!({
+				"status":      "success",
+				"country":     location.country,
+				"countryCode": location.country_code,
+				"region":      location.region_code,
+				"regionName":  location.region,
+				"city":        location.city,
+				"lat":         location.latitude,
+				"lon":         location.longitude,
+				"timezone":    location.timezone,
+				"org":         location.org,
+				"query":       queried,
+			}).to_string()),
+			MockGeoIpService::IpInfo => respond(request, StatusCode::OK, "application/json", &json!({
+				"ip":       queried,
+				"city":     location.city,
+				"region":   location.region,
+				"country":  location.country_code,
+				"loc":      format!("{:.4},{:.4}", location.latitude, location.longitude),
+				"org":      location.org,
+				"timezone": location.timezone,
+			}).to_string()),
+		})
+	}
+}
+
+//		MockGeoLocation															
+/// A location reported by [`MockGeoIp`].
+/// 
+/// The default location is London, in the United Kingdom.
+/// 
+#[derive(Clone, Debug, PartialEq)]
+pub struct MockGeoLocation {
+	//		Public properties													
+	/// The name of the city.
+	pub city:         String,
+	
+	/// The name of the country.
+	pub country:      String,
+	
+	/// The ISO 3166-1 alpha-2 code of the country.
+	pub country_code: String,
+	
+	/// The latitude, in degrees.
+	pub latitude:     f64,
+	
+	/// The longitude, in degrees.
+	pub longitude:    f64,
+	
+	/// The organisation that owns the address, usually prefixed by its AS
+	/// number.
+	pub org:          String,
+	
+	/// The name of the region.
+	pub region:       String,
+	
+	/// The code of the region.
+	pub region_code:  String,
+	
+	/// The IANA name of the time zone.
+	pub timezone:     String,
+}
+
+//󰭅		Default																	
+impl Default for MockGeoLocation {
+	//		default																
+	fn default() -> Self {
+		Self {
+			city:         "London".to_owned(),
+			country:      "United Kingdom".to_owned(),
+			country_code: "GB".to_owned(),
+			latitude:     51.5074,
+			longitude:    -0.1278,
+			org:          "AS64496 Example Networks".to_owned(),
+			region:       "England".to_owned(),
+			region_code:  "ENG".to_owned(),
+			timezone:     "Europe/London".to_owned(),
+		}
+	}
+}
+
+
+
+//		Functions
+
+//		fail																	
+/// Produces the given failure in response to a request.
+fn fail(failure: MockGeoIpFailure, request: &MockRequest) -> Result<MockResponse, MockError> {
+	match failure {
+		MockGeoIpFailure::ConnectionRefused => Err(MockError::builder().connect().url(request.url.clone()).build()),
+		MockGeoIpFailure::Malformed         => Ok(respond(request, StatusCode::OK, "text/html", "<html><body>Under maintenance</body></html>")),
+		MockGeoIpFailure::RateLimited       => Ok(respond(request, StatusCode::TOO_MANY_REQUESTS, "text/plain", "Too Many Requests")),
+		MockGeoIpFailure::ServerError       => Ok(respond(request, StatusCode::SERVICE_UNAVAILABLE, "text/plain", "Service Unavailable")),
+		MockGeoIpFailure::Timeout           => Err(MockError::builder().timeout().url(request.url.clone()).build()),
+	}
+}
+
+//		respond																	
+/// Creates a response to the given request.
+fn respond(request: &MockRequest, status: StatusCode, content_type: &str, body: &str) -> MockResponse {
+	create_mock_response(
+		request.url.clone(),
+		status,
+		Some(content_type),
+		Some(body.len()),
+		HashMap::<String, String>::new(),
+		Ok(body.as_bytes()),
+	)
+}
+
@@ -0,0 +1,475 @@
+//! This module mocks a GraphQL API in order to test GraphQL clients.
+//! 
+//! This module provides a GraphQL-aware layer on top of the [`reqwest`](crate::reqwest)
+//! mock, which matches requests on their parsed operation name and variables,
+//! rather than on the raw bytes of the body, and builds the `data` and `errors`
+//! envelopes of the responses. This is important because clients such as
+//! [`graphql-client`](https://crates.io/crates/graphql_client) and [Cynic](https://crates.io/crates/cynic)
+//! generate the query text, and its formatting is not something that tests
+//! should depend upon, nor should each test have to parse it.
+//! 
+//! The approach taken is that a [`MockGraphQl`] holds a list of operations,
+//! each with a name, optional variables to match, and a response, and its
+//! [`routes()`](MockGraphQl::routes()) serve `POST` requests to the endpoint
+//! from that list. The operation name is taken from the `operationName` field
+//! of the request if present, or otherwise parsed from the query. Variables
+//! match if the request contains at least the given ones. The requests received
+//! are recorded so that assertions can be made about them.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use futures_executor::block_on;
+//! use serde_json::{Value as Json, json};
+//! use sham::graphql::{MockGraphQl, MockGraphQlError, MockGraphQlResponse};
+//! 
+//! let graphql = MockGraphQl::new("https://api.example.com/graphql")
+//!     .operation("GetUser", Some(json!({ "id": 1 })), MockGraphQlResponse::data(json!({ "user": { "name": "Alice" } })))
+//!     .operation("GetUser", None, MockGraphQlResponse::errors(vec![MockGraphQlError::new("User not found")]))
+//! ;
+//! let client  = graphql.client();
+//! 
+//! block_on(async {
+//!     let response: Json = client.post(graphql.url())
+//!         .json(&json!({ "query": "query GetUser($id: Int!) { user(id: $id) { name } }", "variables": { "id": 1 } }))
+//!         .send().await.unwrap()
+//!         .json().await.unwrap()
+//!     ;
+//!     assert_eq!(response["data"]["user"]["name"], "Alice");
+//! });
+//! 
+//! assert_eq!(graphql.requests()[0].operation_name.as_deref(), Some("GetUser"));
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::reqwest::{
+	MockClient,
+	MockError,
+	MockRequest,
+	MockResponse,
+	MockRoute,
+	create_mock_response,
+	create_routed_mock_client,
+};
+use core::fmt::{Debug, Formatter, self};
+use reqwest::{IntoUrl, Method, StatusCode, Url};
+use serde_json::{Map, Value as Json};
+use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex, PoisonError},
+};
+
+
+
+//		Types
+
+/// A function that produces the response to a matched operation.
+type Handler = Arc<dyn Fn(&MockGraphQlRequest) -> MockGraphQlResponse + Send + Sync>;
+
+
+
+//		Structs
+
+//		MockGraphQl																
+/// A mocked GraphQL endpoint.
+/// 
+/// Clones share the same operations and recorded requests.
+/// 
+#[derive(Clone)]
+pub struct MockGraphQl {
+	//		Private properties													
+	/// The operations served, in the order they are matched.
+	operations: Vec<Operation>,
+	
+	/// The requests received, in order.
+	requests:   Arc<Mutex<Vec<MockGraphQlRequest>>>,
+	
+	/// The URL of the endpoint.
+	url:        Url,
+}
+
+//󰭅		MockGraphQl																
+impl MockGraphQl {
+	//		new																	
+	/// Creates a new endpoint with no operations.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `url` - The URL of the endpoint.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the URL is invalid.
+	/// 
+	#[must_use]
+	pub fn new<U: IntoUrl>(url: U) -> Self {
+		Self {
+			operations: Vec::new(),
+			requests:   Arc::new(Mutex::new(Vec::new())),
+			url:        url.into_url().unwrap(),
+		}
+	}
+	
+	//		operation															
+	/// Adds an operation that responds with a fixed response.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name`      - The name of the operation.
+	/// * `variables` - The variables that the request must contain, if any.
+	/// * `response`  - The response to give.
+	/// 
+	#[must_use]
+	pub fn operation(self, name: &str, variables: Option<Json>, response: MockGraphQlResponse) -> Self {
+		self.operation_fn(name, variables, move |_| response.clone())
+	}
+	
+	//		operation_fn														
+	/// Adds an operation whose response is produced from the request.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name`      - The name of the operation.
+	/// * `variables` - The variables that the request must contain, if any.
+	/// * `handler`   - The function that produces the response.
+	/// 
+	#[must_use]
+	pub fn operation_fn<F>(mut self, name: &str, variables: Option<Json>, handler: F) -> Self
+	where
+		F: Fn(&MockGraphQlRequest) -> MockGraphQlResponse + Send + Sync + 'static,
+	{
+		self.operations.push(Operation {
+			handler: Arc::new(handler),
+			name:    name.to_owned(),
+			variables,
+		});
+		self
+	}
+	
+	//		client																
+	/// Creates a mock client that serves only this endpoint.
+	#[must_use]
+	pub fn client(&self) -> MockClient {
+		create_routed_mock_client(self.routes())
+	}
+	
+	//		requests															
+	/// Returns the requests received so far, in order.
+	#[must_use]
+	pub fn requests(&self) -> Vec<MockGraphQlRequest> {
+		self.requests.lock().unwrap_or_else(PoisonError::into_inner).clone()
+	}
+	
+	//		routes																
+	/// Returns the routes that serve this endpoint, for use with
+	/// [`create_routed_mock_client()`].
+	#[must_use]
+	pub fn routes(&self) -> Vec<MockRoute> {
+		let graphql = self.clone();
+		vec![MockRoute::new(Method::POST, self.url.clone(), move |request| graphql.serve(request))]
+	}
+	
+	//		url																	
+	/// Returns the URL of the endpoint.
+	#[must_use]
+	pub fn url(&self) -> Url {
+		self.url.clone()
+	}
+	
+	//		find_operation														
+	/// Finds the first operation that matches a request.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if no operation matches the request, listing
+	/// the operations that are available.
+	/// 
+	fn find_operation(&self, request: &MockGraphQlRequest) -> &Operation {
+		self.operations.iter().find(|operation| operation.matches(request)).unwrap_or_else(|| panic!(
+			"No mock operation matches {} with variables {}, available operations: {:?}",
+			request.operation_name.as_deref().unwrap_or("<anonymous>"),
+			request.variables,
+			self.operations,
+		))
+	}
+	
+	//		serve																
+	/// Serves a request to the endpoint.
+	#[expect(clippy::unnecessary_wraps, reason = "Needed for the route signature")]
+	fn serve(&self, request: &MockRequest) -> Result<MockResponse, MockError> {
+		let Some(parsed) = MockGraphQlRequest::parse(request) else {
+			return Ok(json_response(request, StatusCode::BAD_REQUEST, &MockGraphQlResponse::errors(vec![
+				MockGraphQlError::new("Must provide query string."),
+			]).to_json()));
+		};
+		self.requests.lock().unwrap_or_else(PoisonError::into_inner).push(parsed.clone());
+		let response = (self.find_operation(&parsed).handler)(&parsed);
+		Ok(json_response(request, StatusCode::OK, &response.to_json()))
+	}
+}
+
+//󰭅		Debug																	
+impl Debug for MockGraphQl {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("MockGraphQl")
+			.field("operations", &self.operations)
+			.field("url",        &self.url.as_str())
+			.finish_non_exhaustive()
+	}
+}
+
+//		MockGraphQlError														
+/// An error in a GraphQL response.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct MockGraphQlError {
+	//		Public properties													
+	/// A description of the error.
+	pub message:    String,
+	
+	/// The path to the field that caused the error, if any.
+	pub path:       Option<Vec<Json>>,
+	
+	/// Additional information about the error, if any.
+	pub extensions: Option<Map<String, Json>>,
+}
+
+//󰭅		MockGraphQlError														
+impl MockGraphQlError {
+	//		new																	
+	/// Creates a new error with the given message.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `message` - A description of the error.
+	/// 
+	#[must_use]
+	pub fn new(message: &str) -> Self {
+		Self { message: message.to_owned(), ..Default::default() }
+	}
+	
+	//		with_extension														
+	/// Adds an extension to the error, such as a `code`.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `key`   - The name of the extension.
+	/// * `value` - The value of the extension.
+	/// 
+	#[must_use]
+	pub fn with_extension(mut self, key: &str, value: Json) -> Self {
+		drop(self.extensions.get_or_insert_with(Map::new).insert(key.to_owned(), value));
+		self
+	}
+	
+	//		with_path															
+	/// Sets the path to the field that caused the error.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `path` - The path, made up of field names and list indexes.
+	/// 
+	#[must_use]
+	pub fn with_path(mut self, path: Vec<Json>) -> Self {
+		self.path = Some(path);
+		self
+	}
+	
+	//		to_json																
+	/// Converts the error to JSON.
+	fn to_json(&self) -> Json {
+		let mut error = Map::new();
+		drop(error.insert("message".to_owned(), Json::String(self.message.clone())));
+		if let Some(ref path) = self.path {
+			drop(error.insert("path".to_owned(), Json::Array(path.clone())));
+		}
+		if let Some(ref extensions) = self.extensions {
+			drop(error.insert("extensions".to_owned(), Json::Object(extensions.clone())));
+		}
+		Json::Object(error)
+	}
+}
+
+//		MockGraphQlRequest														
+/// A GraphQL request received by a [`MockGraphQl`] endpoint.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct MockGraphQlRequest {
+	//		Public properties													
+	/// The name of the operation, from the request or parsed from the query.
+	pub operation_name: Option<String>,
+	
+	/// The query text.
+	pub query:          String,
+	
+	/// The variables, which is an empty object if none were sent.
+	pub variables:      Json,
+}
+
+//󰭅		MockGraphQlRequest														
+impl MockGraphQlRequest {
+	//		parse																
+	/// Parses a GraphQL request from the JSON body of an HTTP request.
+	fn parse(request: &MockRequest) -> Option<Self> {
+		let body  = serde_json::from_slice::<Json>(request.body.as_deref()?).ok()?;
+		let query = body.get("query")?.as_str()?.to_owned();
+		Some(Self {
+			operation_name: body.get("operationName")
+				.and_then(Json::as_str)
+				.map(ToOwned::to_owned)
+				.or_else(|| operation_name(&query)),
+			variables:      body.get("variables").filter(|variables| !variables.is_null()).cloned()
+				.unwrap_or_else(|| Json::Object(Map::new())),
+			query,
+		})
+	}
+}
+
+//		MockGraphQlResponse														
+/// A GraphQL response, made up of data and errors.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct MockGraphQlResponse {
+	//		Public properties													
+	/// The data, if any.
+	pub data:   Option<Json>,
+	
+	/// The errors, if any.
+	pub errors: Vec<MockGraphQlError>,
+}
+
+//󰭅		MockGraphQlResponse														
+impl MockGraphQlResponse {
+	//		data																
+	/// Creates a successful response with the given data.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `data` - The data.
+	/// 
+	#[must_use]
+	pub const fn data(data: Json) -> Self {
+		Self { data: Some(data), errors: Vec::new() }
+	}
+	
+	//		errors																
+	/// Creates a failed response with the given errors, and `null` data.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `errors` - The errors.
+	/// 
+	#[must_use]
+	pub const fn errors(errors: Vec<MockGraphQlError>) -> Self {
+		Self { data: None, errors }
+	}
+	
+	//		partial																
+	/// Creates a partially successful response with both data and errors.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `data`   - The data.
+	/// * `errors` - The errors.
+	/// 
+	#[must_use]
+	pub const fn partial(data: Json, errors: Vec<MockGraphQlError>) -> Self {
+		Self { data: Some(data), errors }
+	}
+	
+	//		to_json																
+	/// Converts the response to its JSON envelope.
+	fn to_json(&self) -> Json {
+		let mut envelope = Map::new();
+		drop(envelope.insert("data".to_owned(), self.data.clone().unwrap_or(Json::Null)));
+		if !self.errors.is_empty() {
+			drop(envelope.insert("errors".to_owned(), self.errors.iter().map(MockGraphQlError::to_json).collect()));
+		}
+		Json::Object(envelope)
+	}
+}
+
+//		Operation																
+/// An operation served by a [`MockGraphQl`] endpoint.
+#[derive(Clone)]
+struct Operation {
+	/// The function that produces the response.
+	handler:   Handler,
+	
+	/// The name of the operation.
+	name:      String,
+	
+	/// The variables that the request must contain, if any.
+	variables: Option<Json>,
+}
+
+//󰭅		Operation																
+impl Operation {
+	//		matches																
+	/// Checks whether the operation matches a request.
+	fn matches(&self, request: &MockGraphQlRequest) -> bool {
+		request.operation_name.as_deref() == Some(self.name.as_str())
+			&& self.variables.as_ref().map_or(true, |expected| contains_json(&request.variables, expected))
+	}
+}
+
+//󰭅		Debug																	
+impl Debug for Operation {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self.variables {
+			Some(ref variables) => write!(f, "{}({variables})", self.name),
+			None                => write!(f, "{}(..)", self.name),
+		}
+	}
+}
+
+
+
+//		Functions
+
+//		contains_json															
+/// Checks whether a JSON value contains another, i.e. whether every field of
+/// the expected object is present in the actual one with a matching value.
+fn contains_json(actual: &Json, expected: &Json) -> bool {
+	match (actual.as_object(), expected.as_object()) {
+		(Some(actual_fields), Some(expected_fields)) => expected_fields.iter().all(|(key, value)| {
+			actual_fields.get(key).is_some_and(|found| contains_json(found, value))
+		}),
+		(_, _)                                       => actual == expected,
+	}
+}
+
+//		json_response															
+/// Creates a JSON response to the given request.
+fn json_response(request: &MockRequest, status: StatusCode, body: &Json) -> MockResponse {
+	let bytes = body.to_string().into_bytes();
+	create_mock_response(
+		request.url.clone(),
+		status,
+		Some("application/json"),
+		Some(bytes.len()),
+		HashMap::<String, String>::new(),
+		Ok(&bytes),
+	)
+}
+
+//		operation_name															
+/// Parses the name of the first named operation from a query.
+fn operation_name(query: &str) -> Option<String> {
+	let mut tokens = query.split(|c: char| c.is_whitespace() || matches!(c, '(' | '{' | '@')).filter(|token| !token.is_empty());
+	while let Some(token) = tokens.next() {
+		if matches!(token, "query" | "mutation" | "subscription") {
+			return tokens.next()
+				.filter(|name| name.chars().all(|c| c.is_alphanumeric() || c == '_'))
+				.map(ToOwned::to_owned);
+		}
+	}
+	None
+}
+
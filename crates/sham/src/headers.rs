@@ -0,0 +1,319 @@
+//! This module provides assertions about HTTP headers in order to check what
+//! was sent and received.
+//! 
+//! This module provides [`HeaderAssertions`], which checks a set of headers,
+//! such as those of a captured [`MockRequest`] or of a [`MockResponse`], for
+//! the presence, absence, and values of individual headers, or compares them
+//! with an exact set of expected headers. This is important because checking
+//! headers by hand is repetitive and easy to get subtly wrong: names are not
+//! case-sensitive, a header can appear more than once, a list can be sent as
+//! one line or as several, and a failed `assert_eq!()` between two header maps
+//! gives little clue as to what actually differs.
+//! 
+//! The approach taken is that names are always compared without regard to
+//! case, and values are compared after trimming surrounding whitespace. A
+//! header that appears more than once is treated as the set of its values, in
+//! any order, and a value is also found if it is one of the elements of a
+//! comma-separated list. Exact comparisons can allow extra headers that are not
+//! of interest, such as `date`, and when headers do not match, the differences
+//! are listed, one header per line. The assertions work on any [`HeaderMap`],
+//! which is the type used by the `http` crate, and so by both Reqwest and
+//! Hyper.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use reqwest::header::HeaderMap;
+//! use sham::headers::HeaderAssertions;
+//! 
+//! let mut headers = HeaderMap::new();
+//! headers.insert("Content-Type", "application/json".parse().unwrap());
+//! headers.append("Accept-Encoding", "gzip, br".parse().unwrap());
+//! headers.append("X-Request-Id", "abc123".parse().unwrap());
+//! headers.insert("Date", "Tue, 15 Nov 1994 08:12:31 GMT".parse().unwrap());
+//! 
+//! let assertions = HeaderAssertions::new(&headers).with_allowed_extra("date");
+//! assert!(assertions.contains("content-type", "application/json"));
+//! assert!(assertions.contains("accept-encoding", "br"));
+//! assertions.assert_absent("authorization");
+//! assertions.assert_exactly([
+//!     ("content-type",    "application/json"),
+//!     ("accept-encoding", "gzip, br"),
+//!     ("x-request-id",    "abc123"),
+//! ]);
+//! 
+//! assert_eq!(
+//!     assertions.diff([("content-type", "text/plain"), ("accept-encoding", "gzip, br")]).unwrap(),
+//!     "- content-type: text/plain\n+ content-type: application/json\n+ x-request-id: abc123",
+//! );
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::reqwest::{MockRequest, MockResponse};
+use reqwest::header::{HeaderMap, SET_COOKIE};
+use std::collections::BTreeMap;
+
+
+
+//		Structs
+
+//		HeaderAssertions														
+/// A set of assertions about a set of HTTP headers.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct HeaderAssertions<'a> {
+	//		Private properties													
+	/// The names of the headers that may be present without being expected,
+	/// in lowercase.
+	allowed: Vec<String>,
+	
+	/// The headers to check.
+	headers: &'a HeaderMap,
+}
+
+//󰭅		HeaderAssertions														
+impl<'a> HeaderAssertions<'a> {
+	//		new																	
+	/// Creates a new set of assertions about the given headers.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `headers` - The headers to check.
+	/// 
+	pub const fn new(headers: &'a HeaderMap) -> Self {
+		Self {
+			allowed: Vec::new(),
+			headers,
+		}
+	}
+	
+	//		for_request															
+	/// Creates a new set of assertions about the headers of a captured
+	/// request.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `request` - The request whose headers to check.
+	/// 
+	pub const fn for_request(request: &'a MockRequest) -> Self {
+		Self::new(&request.headers)
+	}
+	
+	//		for_response														
+	/// Creates a new set of assertions about the headers of a response.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `response` - The response whose headers to check.
+	/// 
+	pub const fn for_response(response: &'a MockResponse) -> Self {
+		Self::new(&response.headers)
+	}
+	
+	//		with_allowed_extra													
+	/// Allows a header to be present without being expected, when comparing
+	/// with an exact set. If it is expected, its values are still checked.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the header, in any case.
+	/// 
+	pub fn with_allowed_extra(mut self, name: &str) -> Self {
+		self.allowed.push(name.to_ascii_lowercase());
+		self
+	}
+	
+	//		assert_absent														
+	/// Asserts that a header is not present.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the header, in any case.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the header is present, showing its values.
+	/// 
+	pub fn assert_absent(&self, name: &str) {
+		let values = self.values(name);
+		assert!(values.is_empty(), "Expected header `{}` to be absent, found: {}", name.to_ascii_lowercase(), values.join(" | "));
+	}
+	
+	//		assert_contains														
+	/// Asserts that a header has the given value.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name`  - The name of the header, in any case.
+	/// * `value` - The value to look for.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the header does not have the value, showing
+	/// the values that it does have.
+	/// 
+	/// # See also
+	/// 
+	/// * [`contains()`](HeaderAssertions::contains())
+	/// 
+	pub fn assert_contains(&self, name: &str, value: &str) {
+		if !self.contains(name, value) {
+			let values = self.values(name);
+			let found  = if values.is_empty() { "nothing".to_owned() } else { values.join(" | ") };
+			panic!("Expected header `{}` to contain `{}`, found: {found}", name.to_ascii_lowercase(), value.trim());
+		}
+	}
+	
+	//		assert_exactly														
+	/// Asserts that the headers are exactly the given set, apart from any
+	/// allowed extras.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `expected` - The expected headers, as names and values. A name can be
+	///   given more than once, for a header with several values.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the headers differ, listing the differences.
+	/// 
+	/// # See also
+	/// 
+	/// * [`diff()`](HeaderAssertions::diff())
+	/// 
+	pub fn assert_exactly<I, K, V>(&self, expected: I)
+	where
+		I: IntoIterator<Item = (K, V)>,
+		K: AsRef<str>,
+		V: AsRef<str>,
+	{
+		if let Some(difference) = self.diff(expected) {
+			panic!("Headers do not match:\n{difference}");
+		}
+	}
+	
+	//		assert_present														
+	/// Asserts that a header is present, with any value.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the header, in any case.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the header is not present.
+	/// 
+	pub fn assert_present(&self, name: &str) {
+		assert!(!self.values(name).is_empty(), "Expected header `{}` to be present", name.to_ascii_lowercase());
+	}
+	
+	//		contains															
+	/// Whether a header has the given value.
+	/// 
+	/// The value is found if it matches one of the values of the header, or
+	/// one of the elements of a value that is a comma-separated list, apart
+	/// from for `set-cookie`, whose values are never lists. Surrounding
+	/// whitespace is ignored.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name`  - The name of the header, in any case.
+	/// * `value` - The value to look for.
+	/// 
+	#[must_use]
+	pub fn contains(&self, name: &str, value: &str) -> bool {
+		let wanted = value.trim();
+		let listed = !name.eq_ignore_ascii_case(SET_COOKIE.as_str());
+		self.values(name).iter().any(|found| {
+			found == wanted || (listed && found.split(',').any(|element| element.trim() == wanted))
+		})
+	}
+	
+	//		diff																
+	/// Describes the differences between the headers and the given set, apart
+	/// from any allowed extras, if there are any.
+	/// 
+	/// Each difference is given on a line of its own, in order of name, with
+	/// `-` for an expected value that was not found, and `+` for a value that
+	/// was found but not expected.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `expected` - The expected headers, as names and values. A name can be
+	///   given more than once, for a header with several values.
+	/// 
+	#[must_use]
+	pub fn diff<I, K, V>(&self, expected: I) -> Option<String>
+	where
+		I: IntoIterator<Item = (K, V)>,
+		K: AsRef<str>,
+		V: AsRef<str>,
+	{
+		let mut wanted = BTreeMap::<String, Vec<String>>::new();
+		for (name, value) in expected {
+			wanted.entry(name.as_ref().to_ascii_lowercase()).or_default().push(value.as_ref().trim().to_owned());
+		}
+		let mut found  = BTreeMap::<String, Vec<String>>::new();
+		for name in self.headers.keys() {
+			if wanted.contains_key(name.as_str()) || !self.allowed.iter().any(|allowed| allowed == name.as_str()) {
+				drop(found.insert(name.as_str().to_owned(), self.values(name.as_str())));
+			}
+		}
+		let mut names  = wanted.keys().chain(found.keys()).cloned().collect::<Vec<_>>();
+		names.sort_unstable();
+		names.dedup();
+		let mut lines  = Vec::new();
+		for name in names {
+			let mut missing = wanted.get(&name).cloned().unwrap_or_default();
+			let mut extra   = Vec::new();
+			for value in found.get(&name).cloned().unwrap_or_default() {
+				match missing.iter().position(|other| *other == value) {
+					Some(index) => drop(missing.remove(index)),
+					None        => extra.push(value),
+				}
+			}
+			lines.extend(missing.iter().map(|value| format!("- {name}: {value}")));
+			lines.extend(extra.iter().map(|value| format!("+ {name}: {value}")));
+		}
+		(!lines.is_empty()).then(|| lines.join("\n"))
+	}
+	
+	//		matches_exactly														
+	/// Whether the headers are exactly the given set, apart from any allowed
+	/// extras.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `expected` - The expected headers, as names and values. A name can be
+	///   given more than once, for a header with several values.
+	/// 
+	#[must_use]
+	pub fn matches_exactly<I, K, V>(&self, expected: I) -> bool
+	where
+		I: IntoIterator<Item = (K, V)>,
+		K: AsRef<str>,
+		V: AsRef<str>,
+	{
+		self.diff(expected).is_none()
+	}
+	
+	//		values																
+	/// Returns the values of a header, in the order received, with surrounding
+	/// whitespace removed. Values that are not valid UTF-8 are converted
+	/// lossily.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the header, in any case.
+	/// 
+	#[must_use]
+	pub fn values(&self, name: &str) -> Vec<String> {
+		self.headers.get_all(name.to_ascii_lowercase().as_str()).iter()
+			.map(|value| String::from_utf8_lossy(value.as_bytes()).trim().to_owned())
+			.collect()
+	}
+}
@@ -0,0 +1,263 @@
+//! This module mocks machine identity lookups in order to test their consumers.
+//! 
+//! This module provides scripted values for the hostname, the current username,
+//! and the machine ID, in the style of the [Whoami](https://crates.io/crates/whoami)
+//! and [Hostname](https://crates.io/crates/hostname) crates and of reading
+//! `/etc/machine-id`. This is important because code that embeds the identity
+//! of the machine in reports, telemetry, or licence checks behaves differently
+//! depending on where it runs, and the awkward cases, such as an empty hostname
+//! inside a container or a missing machine ID, are rarely present on the
+//! machines that run the tests.
+//! 
+//! The approach taken is that the "real" code should look up the identity of
+//! the machine through a provider that it is given, and the test code then
+//! supplies a [`MockIdentity`] with the values, or failures, for the persona
+//! being tested. Some common personas are provided as constructors.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use sham::identity::{MockIdentity, MockIdentityError, MockIdentityField};
+//! 
+//! let identity = MockIdentity::new()
+//!     .with_hostname("build-01")
+//!     .with_username("ci")
+//!     .with_failure(MockIdentityField::MachineId, MockIdentityError::NotFound)
+//! ;
+//! assert_eq!(identity.hostname().unwrap(), "build-01");
+//! assert_eq!(identity.username().unwrap(), "ci");
+//! assert_eq!(identity.machine_id(), Err(MockIdentityError::NotFound));
+//! 
+//! let container = MockIdentity::container();
+//! assert_eq!(container.hostname().unwrap(), "");
+//! ```
+//! 
+
+
+
+//		Packages
+
+use core::{
+	error::Error,
+	fmt::{Display, Formatter, self},
+};
+
+
+
+//		Enums
+
+//		MockIdentityError														
+/// A mocked identity lookup error type.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockIdentityError {
+	/// The value could not be found, e.g. a missing `/etc/machine-id`.
+	NotFound,
+	
+	/// The value could not be read due to a lack of permission.
+	PermissionDenied,
+	
+	/// The value could not be read for some other reason.
+	Other(String),
+}
+
+//󰭅		Display																	
+impl Display for MockIdentityError {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match *self {
+			Self::NotFound           => write!(f, "Not found"),
+			Self::PermissionDenied   => write!(f, "Permission denied"),
+			Self::Other(ref message) => write!(f, "{message}"),
+		}
+	}
+}
+
+//󰭅		Error																	
+impl Error for MockIdentityError {}
+
+//		MockIdentityField														
+/// The identity values that can be looked up.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockIdentityField {
+	/// The hostname of the machine.
+	Hostname,
+	
+	/// The ID of the machine.
+	MachineId,
+	
+	/// The name of the current user.
+	Username,
+}
+
+
+
+//		Structs
+
+//		MockIdentity															
+/// A mocked provider of machine identity.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MockIdentity {
+	//		Private properties													
+	/// The hostname, or the error to return when it is looked up.
+	hostname:   Result<String, MockIdentityError>,
+	
+	/// The machine ID, or the error to return when it is looked up.
+	machine_id: Result<String, MockIdentityError>,
+	
+	/// The username, or the error to return when it is looked up.
+	username:   Result<String, MockIdentityError>,
+}
+
+//󰭅		MockIdentity															
+impl MockIdentity {
+	//		new																	
+	/// Creates a new provider for an ordinary workstation, with the hostname
+	/// `localhost`, the username `user`, and a fixed machine ID.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			hostname:   Ok("localhost".to_owned()),
+			machine_id: Ok("0123456789abcdef0123456789abcdef".to_owned()),
+			username:   Ok("user".to_owned()),
+		}
+	}
+	
+	//		container															
+	/// Creates a new provider for a minimal container, which has an empty
+	/// hostname, runs as `root`, and has no machine ID.
+	#[must_use]
+	pub fn container() -> Self {
+		Self {
+			hostname:   Ok(String::new()),
+			machine_id: Err(MockIdentityError::NotFound),
+			username:   Ok("root".to_owned()),
+		}
+	}
+	
+	//		sandboxed															
+	/// Creates a new provider for a sandbox that denies access to all identity
+	/// information.
+	#[must_use]
+	pub const fn sandboxed() -> Self {
+		Self {
+			hostname:   Err(MockIdentityError::PermissionDenied),
+			machine_id: Err(MockIdentityError::PermissionDenied),
+			username:   Err(MockIdentityError::PermissionDenied),
+		}
+	}
+	
+	//		with_failure														
+	/// Makes looking up a value fail with the given error.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `field` - The value to fail.
+	/// * `error` - The error to return.
+	/// 
+	#[must_use]
+	pub fn with_failure(mut self, field: MockIdentityField, error: MockIdentityError) -> Self {
+		*self.field_mut(field) = Err(error);
+		self
+	}
+	
+	//		with_hostname														
+	/// Sets the hostname.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `hostname` - The hostname.
+	/// 
+	#[must_use]
+	pub fn with_hostname(mut self, hostname: &str) -> Self {
+		self.hostname = Ok(hostname.to_owned());
+		self
+	}
+	
+	//		with_machine_id														
+	/// Sets the machine ID.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `machine_id` - The machine ID.
+	/// 
+	#[must_use]
+	pub fn with_machine_id(mut self, machine_id: &str) -> Self {
+		self.machine_id = Ok(machine_id.to_owned());
+		self
+	}
+	
+	//		with_username														
+	/// Sets the username.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `username` - The username.
+	/// 
+	#[must_use]
+	pub fn with_username(mut self, username: &str) -> Self {
+		self.username = Ok(username.to_owned());
+		self
+	}
+	
+	//		hostname															
+	/// Returns the hostname.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if a failure has been set for the hostname.
+	/// 
+	/// # See also
+	/// 
+	/// * `hostname::get()`
+	/// * `whoami::fallible::hostname()`
+	/// 
+	pub fn hostname(&self) -> Result<String, MockIdentityError> {
+		self.hostname.clone()
+	}
+	
+	//		machine_id															
+	/// Returns the machine ID.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if a failure has been set for the machine ID.
+	/// 
+	pub fn machine_id(&self) -> Result<String, MockIdentityError> {
+		self.machine_id.clone()
+	}
+	
+	//		username															
+	/// Returns the name of the current user.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if a failure has been set for the username.
+	/// 
+	/// # See also
+	/// 
+	/// * `whoami::fallible::username()`
+	/// 
+	pub fn username(&self) -> Result<String, MockIdentityError> {
+		self.username.clone()
+	}
+	
+	//		field_mut															
+	/// Returns the given value for modification.
+	const fn field_mut(&mut self, field: MockIdentityField) -> &mut Result<String, MockIdentityError> {
+		match field {
+			MockIdentityField::Hostname  => &mut self.hostname,
+			MockIdentityField::MachineId => &mut self.machine_id,
+			MockIdentityField::Username  => &mut self.username,
+		}
+	}
+}
+
+//󰭅		Default																	
+impl Default for MockIdentity {
+	//		default																
+	fn default() -> Self {
+		Self::new()
+	}
+}
@@ -0,0 +1,427 @@
+//! This module mocks a JSON-RPC 2.0 server in order to test JSON-RPC clients.
+//! 
+//! This module provides a [JSON-RPC 2.0](https://www.jsonrpc.org/specification)
+//! layer on top of the [`reqwest`](crate::reqwest) mock, which matches calls on
+//! their method and parameters, correlates the `id` of each response with its
+//! request, and handles batches and notifications. This is important because
+//! clients of blockchain nodes, language servers, and similar services speak
+//! JSON-RPC, and hand-building the envelopes in every test obscures what is
+//! actually being tested.
+//! 
+//! The approach taken is that a [`MockJsonRpc`] holds a list of methods, each
+//! with a name, optional parameters to match, and a result or error, and its
+//! [`routes()`](MockJsonRpc::routes()) serve `POST` requests to the endpoint
+//! from that list. Parameters match if the call contains at least the given
+//! ones. Calls to methods that are not listed receive the standard "Method not
+//! found" error. For other transports, such as web sockets, payloads can be
+//! passed to [`handle()`](MockJsonRpc::handle()) directly. The calls received
+//! are recorded so that assertions can be made about them.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use futures_executor::block_on;
+//! use serde_json::{Value as Json, json};
+//! use sham::jsonrpc::{MockJsonRpc, MockJsonRpcError};
+//! 
+//! let rpc    = MockJsonRpc::new("https://node.example.com/")
+//!     .method("eth_blockNumber", None, Ok(json!("0x10")))
+//!     .method("eth_getBalance", Some(json!(["0xdead"])), Err(MockJsonRpcError::new(-32000, "Account locked")))
+//! ;
+//! let client = rpc.client();
+//! 
+//! block_on(async {
+//!     let responses: Json = client.post(rpc.url())
+//!         .json(&json!([
+//!             { "jsonrpc": "2.0", "id": 7, "method": "eth_blockNumber" },
+//!             { "jsonrpc": "2.0", "id": 8, "method": "eth_getBalance", "params": ["0xdead", "latest"] },
+//!         ]))
+//!         .send().await.unwrap()
+//!         .json().await.unwrap()
+//!     ;
+//!     assert_eq!(responses[0], json!({ "jsonrpc": "2.0", "id": 7, "result": "0x10" }));
+//!     assert_eq!(responses[1]["error"]["code"], -32000);
+//! });
+//! 
+//! assert_eq!(rpc.requests().len(), 2);
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::reqwest::{
+	MockClient,
+	MockError,
+	MockRequest,
+	MockResponse,
+	MockRoute,
+	create_mock_response,
+	create_routed_mock_client,
+};
+use core::fmt::{Debug, Formatter, self};
+use reqwest::{IntoUrl, Method, StatusCode, Url};
+use serde_json::{Map, Value as Json, json};
+use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex, PoisonError},
+};
+
+
+
+//		Types
+
+/// A function that produces the result of a matched call.
+type Handler = Arc<dyn Fn(&MockJsonRpcRequest) -> Result<Json, MockJsonRpcError> + Send + Sync>;
+
+
+
+//		Structs
+
+//		MockJsonRpc																
+/// A mocked JSON-RPC 2.0 endpoint.
+/// 
+/// Clones share the same methods and recorded calls.
+/// 
+#[derive(Clone)]
+pub struct MockJsonRpc {
+	//		Private properties													
+	/// The methods served, in the order they are matched.
+	methods:  Vec<RpcMethod>,
+	
+	/// The calls received, in order.
+	requests: Arc<Mutex<Vec<MockJsonRpcRequest>>>,
+	
+	/// The URL of the endpoint.
+	url:      Url,
+}
+
+//󰭅		MockJsonRpc																
+impl MockJsonRpc {
+	//		new																	
+	/// Creates a new endpoint with no methods.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `url` - The URL of the endpoint.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the URL is invalid.
+	/// 
+	#[must_use]
+	pub fn new<U: IntoUrl>(url: U) -> Self {
+		Self {
+			methods:  Vec::new(),
+			requests: Arc::new(Mutex::new(Vec::new())),
+			url:      url.into_url().unwrap(),
+		}
+	}
+	
+	//		method																
+	/// Adds a method that responds with a fixed result or error.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name`   - The name of the method.
+	/// * `params` - The parameters that the call must contain, if any.
+	/// * `result` - The result or error to give.
+	/// 
+	#[must_use]
+	pub fn method(self, name: &str, params: Option<Json>, result: Result<Json, MockJsonRpcError>) -> Self {
+		self.method_fn(name, params, move |_| result.clone())
+	}
+	
+	//		method_fn															
+	/// Adds a method whose result is produced from the call.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name`    - The name of the method.
+	/// * `params`  - The parameters that the call must contain, if any.
+	/// * `handler` - The function that produces the result or error.
+	/// 
+	#[must_use]
+	pub fn method_fn<F>(mut self, name: &str, params: Option<Json>, handler: F) -> Self
+	where
+		F: Fn(&MockJsonRpcRequest) -> Result<Json, MockJsonRpcError> + Send + Sync + 'static,
+	{
+		self.methods.push(RpcMethod {
+			handler: Arc::new(handler),
+			name:    name.to_owned(),
+			params,
+		});
+		self
+	}
+	
+	//		client																
+	/// Creates a mock client that serves only this endpoint.
+	#[must_use]
+	pub fn client(&self) -> MockClient {
+		create_routed_mock_client(self.routes())
+	}
+	
+	//		handle																
+	/// Handles a JSON-RPC payload, which may be a single call or a batch,
+	/// returning the response payload, or [`None`] if there is nothing to
+	/// respond with because the payload only contained notifications.
+	/// 
+	/// This allows the endpoint to be used with transports other than HTTP.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `payload` - The JSON-RPC payload.
+	/// 
+	#[must_use]
+	pub fn handle(&self, payload: &[u8]) -> Option<Json> {
+		let Ok(parsed) = serde_json::from_slice::<Json>(payload) else {
+			return Some(error_response(&Json::Null, &MockJsonRpcError::new(-32700, "Parse error")));
+		};
+		match parsed {
+			Json::Array(calls) if calls.is_empty() => {
+				Some(error_response(&Json::Null, &MockJsonRpcError::new(-32600, "Invalid Request")))
+			},
+			Json::Array(calls)                     => {
+				let responses = calls.iter().filter_map(|call| self.call(call)).collect::<Vec<_>>();
+				(!responses.is_empty()).then_some(Json::Array(responses))
+			},
+			Json::Null
+			| Json::Bool(_)
+			| Json::Number(_)
+			| Json::String(_)
+			| Json::Object(_)                      => self.call(&parsed),
+		}
+	}
+	
+	//		requests															
+	/// Returns the calls received so far, in order, including notifications.
+	#[must_use]
+	pub fn requests(&self) -> Vec<MockJsonRpcRequest> {
+		self.requests.lock().unwrap_or_else(PoisonError::into_inner).clone()
+	}
+	
+	//		routes																
+	/// Returns the routes that serve this endpoint, for use with
+	/// [`create_routed_mock_client()`].
+	#[must_use]
+	pub fn routes(&self) -> Vec<MockRoute> {
+		let rpc = self.clone();
+		vec![MockRoute::new(Method::POST, self.url.clone(), move |request| rpc.serve(request))]
+	}
+	
+	//		url																	
+	/// Returns the URL of the endpoint.
+	#[must_use]
+	pub fn url(&self) -> Url {
+		self.url.clone()
+	}
+	
+	//		call																
+	/// Handles a single call, returning the response unless it is a
+	/// notification.
+	fn call(&self, call: &Json) -> Option<Json> {
+		let Some(request) = MockJsonRpcRequest::parse(call) else {
+			let id = call.get("id").cloned().unwrap_or(Json::Null);
+			return Some(error_response(&id, &MockJsonRpcError::new(-32600, "Invalid Request")));
+		};
+		self.requests.lock().unwrap_or_else(PoisonError::into_inner).push(request.clone());
+		let result = self.methods.iter()
+			.find(|method| method.matches(&request))
+			.map_or_else(
+				|| Err(MockJsonRpcError::new(-32601, "Method not found")),
+				|method| (method.handler)(&request),
+			)
+		;
+		let id     = request.id?;
+		Some(match result {
+			Ok(value)  => json!({ "jsonrpc": "2.0", "id": id, "result": value }),
+			Err(error) => error_response(&id, &error),
+		})
+	}
+	
+	//		serve																
+	/// Serves a request to the endpoint.
+	#[expect(clippy::unnecessary_wraps, reason = "Needed for the route signature")]
+	fn serve(&self, request: &MockRequest) -> Result<MockResponse, MockError> {
+		let response = self.handle(request.body.as_deref().unwrap_or_default());
+		Ok(response.map_or_else(
+			|| create_mock_response(
+				request.url.clone(),
+				StatusCode::NO_CONTENT,
+				None::<String>,
+				Some(0),
+				HashMap::<String, String>::new(),
+				Ok(b""),
+			),
+			|body| {
+				let bytes = body.to_string().into_bytes();
+				create_mock_response(
+					request.url.clone(),
+					StatusCode::OK,
+					Some("application/json"),
+					Some(bytes.len()),
+					HashMap::<String, String>::new(),
+					Ok(&bytes),
+				)
+			},
+		))
+	}
+}
+
+//󰭅		Debug																	
+impl Debug for MockJsonRpc {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("MockJsonRpc")
+			.field("methods", &self.methods)
+			.field("url",     &self.url.as_str())
+			.finish_non_exhaustive()
+	}
+}
+
+//		MockJsonRpcError														
+/// A JSON-RPC error object.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct MockJsonRpcError {
+	//		Public properties													
+	/// The error code.
+	pub code:    i64,
+	
+	/// A short description of the error.
+	pub message: String,
+	
+	/// Additional information about the error, if any.
+	pub data:    Option<Json>,
+}
+
+//󰭅		MockJsonRpcError														
+impl MockJsonRpcError {
+	//		new																	
+	/// Creates a new error.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `code`    - The error code.
+	/// * `message` - A short description of the error.
+	/// 
+	#[must_use]
+	pub fn new(code: i64, message: &str) -> Self {
+		Self { code, message: message.to_owned(), data: None }
+	}
+	
+	//		with_data															
+	/// Adds additional information to the error.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `data` - The additional information.
+	/// 
+	#[must_use]
+	pub fn with_data(mut self, data: Json) -> Self {
+		self.data = Some(data);
+		self
+	}
+}
+
+//		MockJsonRpcRequest														
+/// A JSON-RPC call received by a [`MockJsonRpc`] endpoint.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct MockJsonRpcRequest {
+	//		Public properties													
+	/// The ID of the call, or [`None`] if it is a notification.
+	pub id:     Option<Json>,
+	
+	/// The name of the method.
+	pub method: String,
+	
+	/// The parameters, which is [`Json::Null`] if none were sent.
+	pub params: Json,
+}
+
+//󰭅		MockJsonRpcRequest														
+impl MockJsonRpcRequest {
+	//		parse																
+	/// Parses a call, returning [`None`] if it is not a valid JSON-RPC 2.0
+	/// request object.
+	fn parse(call: &Json) -> Option<Self> {
+		if call.get("jsonrpc").and_then(Json::as_str) != Some("2.0") {
+			return None;
+		}
+		Some(Self {
+			id:     call.get("id").cloned(),
+			method: call.get("method")?.as_str()?.to_owned(),
+			params: call.get("params").cloned().unwrap_or(Json::Null),
+		})
+	}
+}
+
+//		RpcMethod																
+/// A method served by a [`MockJsonRpc`] endpoint.
+#[derive(Clone)]
+struct RpcMethod {
+	/// The function that produces the result.
+	handler: Handler,
+	
+	/// The name of the method.
+	name:    String,
+	
+	/// The parameters that the call must contain, if any.
+	params:  Option<Json>,
+}
+
+//󰭅		RpcMethod																
+impl RpcMethod {
+	//		matches																
+	/// Checks whether the method matches a call.
+	fn matches(&self, request: &MockJsonRpcRequest) -> bool {
+		request.method == self.name
+			&& self.params.as_ref().map_or(true, |expected| contains_json(&request.params, expected))
+	}
+}
+
+//󰭅		Debug																	
+impl Debug for RpcMethod {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self.params {
+			Some(ref params) => write!(f, "{}({params})", self.name),
+			None             => write!(f, "{}(..)", self.name),
+		}
+	}
+}
+
+
+
+//		Functions
+
+//		contains_json															
+/// Checks whether JSON parameters contain the expected ones. Objects match if
+/// every expected field is present with a matching value, and arrays match if
+/// they start with the expected elements.
+fn contains_json(actual: &Json, expected: &Json) -> bool {
+	if let (Some(actual_fields), Some(expected_fields)) = (actual.as_object(), expected.as_object()) {
+		return expected_fields.iter().all(|(key, value)| {
+			actual_fields.get(key).is_some_and(|found| contains_json(found, value))
+		});
+	}
+	if let (Some(actual_items), Some(expected_items)) = (actual.as_array(), expected.as_array()) {
+		return actual_items.len() >= expected_items.len()
+			&& actual_items.iter().zip(expected_items).all(|(found, value)| contains_json(found, value));
+	}
+	actual == expected
+}
+
+//		error_response															
+/// Creates an error response with the given ID.
+fn error_response(id: &Json, error: &MockJsonRpcError) -> Json {
+	let mut object = Map::new();
+	drop(object.insert("code".to_owned(),    json!(error.code)));
+	drop(object.insert("message".to_owned(), json!(error.message)));
+	if let Some(ref data) = error.data {
+		drop(object.insert("data".to_owned(), data.clone()));
+	}
+	json!({ "jsonrpc": "2.0", "id": id, "error": object })
+}
@@ -0,0 +1,924 @@
+//! This module mocks the Kube client in order to test Kubernetes controllers.
+//! 
+//! This module provides a mock of the [`Api`](https://docs.rs/kube/latest/kube/struct.Api.html)
+//! surface of the [Kube](https://crates.io/crates/kube) crate, i.e. listing,
+//! getting, creating, replacing, patching, deleting, and watching resources,
+//! backed by an in-memory object store. This is important because unit tests
+//! should not depend upon a Kubernetes cluster being available, and because the
+//! failure paths that controllers and operators must handle, such as update
+//! conflicts, are hard to reproduce against a real one.
+//! 
+//! The approach taken is that the "real" code should import `Api` and `Client`
+//! from [Kube](https://crates.io/crates/kube) when running in non-test mode,
+//! but import [`MockApi`] and [`MockKubeClient`] from this module when running
+//! in test mode, along with the supporting types, which mirror the names and
+//! shapes of Kube's. Resources can be of any type that can be serialised and
+//! deserialised, including the types from [`k8s-openapi`](https://crates.io/crates/k8s-openapi),
+//! as they are held in the store as JSON. Each write bumps the resource
+//! version, and is recorded so that it can be replayed by a watch. Errors, such
+//! as `409 Conflict`, can be injected for the next call of a given verb.
+//! 
+//! Notably, the mock is more restrictive than Kube. Only equality-based label
+//! selectors are supported, only merge and apply patches are supported, and a
+//! watch yields the events recorded so far and then ends, rather than waiting
+//! for more.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use futures_executor::block_on;
+//! use futures_util::StreamExt;
+//! use serde_json::{Value as Json, json};
+//! use sham::kube::{
+//!     MockApi,
+//!     MockKubeClient,
+//!     MockKubeError,
+//!     MockPatch,
+//!     MockPatchParams,
+//!     MockPostParams,
+//!     MockVerb,
+//!     MockWatchEvent,
+//!     MockWatchParams,
+//! };
+//! 
+//! let client = MockKubeClient::new();
+//! let api    = MockApi::<Json>::namespaced(client.clone(), "default");
+//! 
+//! block_on(async {
+//!     let pod = json!({ "metadata": { "name": "web", "labels": { "app": "web" } } });
+//!     api.create(&MockPostParams::default(), &pod).await.unwrap();
+//! 
+//!     client.inject_conflict(MockVerb::Patch);
+//!     let patch  = MockPatch::Merge(json!({ "spec": { "replicas": 2 } }));
+//!     let result = api.patch("web", &MockPatchParams::default(), &patch).await;
+//!     assert!(matches!(result, Err(MockKubeError::Api(ref error)) if error.code == 409));
+//! 
+//!     let patched = api.patch("web", &MockPatchParams::default(), &patch).await.unwrap();
+//!     assert_eq!(patched["spec"]["replicas"], 2);
+//! 
+//!     let events = api.watch(&MockWatchParams::default(), "0").await.unwrap().collect::<Vec<_>>().await;
+//!     assert!(matches!(events[0], Ok(MockWatchEvent::Added(_))));
+//!     assert!(matches!(events[1], Ok(MockWatchEvent::Modified(_))));
+//! });
+//! ```
+//! 
+
+
+
+//		Packages
+
+use core::{
+	any::type_name,
+	error::Error,
+	fmt::{Display, Formatter, self},
+	marker::PhantomData,
+	pin::Pin,
+};
+use futures_util::stream::{Stream, self};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Map, Value as Json};
+use std::{
+	collections::BTreeMap,
+	sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+
+
+//		Modules
+
+//		prelude																	
+/// The mocks, named after the genuine types.
+/// 
+/// This allows the mocks for the [Kube](https://crates.io/crates/kube) crate to
+/// be imported with a single glob import when testing, without renaming them.
+/// The names are the same as those used by the `swap_uses` attribute, and by
+/// the combined [`prelude`](crate::prelude).
+/// 
+pub mod prelude {
+	pub use super::{
+		MockApi           as Api,
+		MockDeleteParams  as DeleteParams,
+		MockErrorResponse as ErrorResponse,
+		MockKubeClient    as Client,
+		MockKubeError     as Error,
+		MockListParams    as ListParams,
+		MockObjectList    as ObjectList,
+		MockPatch         as Patch,
+		MockPatchParams   as PatchParams,
+		MockPostParams    as PostParams,
+		MockWatchEvent    as WatchEvent,
+		MockWatchParams   as WatchParams,
+	};
+}
+
+
+
+//		Types
+
+/// The key of a stored object, i.e. its resource type, namespace, and name.
+type ObjectKey = (&'static str, Option<String>, String);
+
+
+
+//		Enums
+
+//		MockKubeError															
+/// A mocked error type for Kube.
+/// 
+/// This mirrors the variants of `kube::Error` that are relevant to the mock.
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockKubeError {
+	/// The API server responded with an error.
+	Api(MockErrorResponse),
+	
+	/// An object could not be converted to or from JSON.
+	SerdeError(String),
+}
+
+//󰭅		Display																	
+impl Display for MockKubeError {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match *self {
+			Self::Api(ref response)     => write!(f, "ApiError: {}: {} ({})", response.message, response.reason, response.code),
+			Self::SerdeError(ref error) => write!(f, "Error deserializing response: {error}"),
+		}
+	}
+}
+
+//󰭅		Error																	
+impl Error for MockKubeError {}
+
+//		MockPatch																
+/// A patch to apply to an object.
+/// 
+/// # See also
+/// 
+/// * `kube::api::Patch`
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockPatch<T: Serialize> {
+	/// A server-side apply, which creates the object if it does not exist.
+	Apply(T),
+	
+	/// A JSON merge patch, as per RFC 7386.
+	Merge(T),
+}
+
+//		MockVerb																
+/// The operations of the mocked API, used to target injected errors.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockVerb {
+	/// Creating an object.
+	Create,
+	
+	/// Deleting an object.
+	Delete,
+	
+	/// Getting an object.
+	Get,
+	
+	/// Listing objects.
+	List,
+	
+	/// Patching an object.
+	Patch,
+	
+	/// Replacing an object.
+	Replace,
+	
+	/// Watching objects.
+	Watch,
+}
+
+//		MockWatchEvent															
+/// An event yielded by a watch.
+/// 
+/// # See also
+/// 
+/// * `kube::api::WatchEvent`
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockWatchEvent<K> {
+	/// An object was created.
+	Added(K),
+	
+	/// An object was deleted.
+	Deleted(K),
+	
+	/// The watch produced an error, such as `410 Gone`.
+	Error(MockErrorResponse),
+	
+	/// An object was changed.
+	Modified(K),
+}
+
+//		StoredEvent																
+/// An event recorded by the mocked API server.
+#[derive(Clone, Debug)]
+enum StoredEvent {
+	/// An object was created.
+	Added(Json),
+	
+	/// An object was deleted.
+	Deleted(Json),
+	
+	/// An error was scripted.
+	Error(MockErrorResponse),
+	
+	/// An object was changed.
+	Modified(Json),
+}
+
+
+
+//		Structs
+
+//		KubeState																
+/// The state of a [`MockKubeClient`] instance.
+#[derive(Debug, Default)]
+struct KubeState {
+	/// The recorded events, with their resource types, namespaces, and resource
+	/// versions.
+	events:   Vec<(&'static str, Option<String>, u64, StoredEvent)>,
+	
+	/// The errors to return for the next calls of each verb.
+	injected: Vec<(MockVerb, MockKubeError)>,
+	
+	/// The stored objects.
+	objects:  BTreeMap<ObjectKey, Json>,
+	
+	/// The latest resource version issued.
+	version:  u64,
+}
+
+//󰭅		KubeState																
+impl KubeState {
+	//		next_version														
+	/// Issues the next resource version.
+	const fn next_version(&mut self) -> u64 {
+		self.version = self.version.saturating_add(1);
+		self.version
+	}
+	
+	//		record																
+	/// Stores an object, stamping it with a new resource version, and records
+	/// the event.
+	fn record(&mut self, key: ObjectKey, mut object: Json, added: bool) -> Json {
+		let version = self.next_version();
+		set_metadata(&mut object, "resourceVersion", Json::String(version.to_string()));
+		if added {
+			set_metadata(&mut object, "uid", Json::String(format!("00000000-0000-0000-0000-{version:012}")));
+		}
+		let event   = if added { StoredEvent::Added(object.clone()) } else { StoredEvent::Modified(object.clone()) };
+		self.events.push((key.0, key.1.clone(), version, event));
+		drop(self.objects.insert(key, object.clone()));
+		object
+	}
+	
+	//		take_injected														
+	/// Takes the next error injected for the given verb, if any.
+	fn take_injected(&mut self, verb: MockVerb) -> Result<(), MockKubeError> {
+		match self.injected.iter().position(|injected| injected.0 == verb) {
+			Some(index) => Err(self.injected.remove(index).1),
+			None        => Ok(()),
+		}
+	}
+}
+
+//		MockApi																	
+/// A mocked Kube API for a resource type.
+/// 
+/// # See also
+/// 
+/// * `kube::Api`
+/// 
+#[derive(Clone, Debug)]
+pub struct MockApi<K> {
+	//		Private properties													
+	/// The client that holds the store.
+	client:    MockKubeClient,
+	
+	/// The namespace that the API is scoped to, if any.
+	namespace: Option<String>,
+	
+	/// The resource type.
+	resource:  PhantomData<fn() -> K>,
+}
+
+//󰭅		MockApi																	
+#[expect(clippy::unused_async, reason = "Needed for compatibility with Kube")]
+impl<K: Clone + DeserializeOwned + Serialize + Send + Sync + 'static> MockApi<K> {
+	//		all																	
+	/// Creates an API that spans all namespaces, or that is for
+	/// cluster-scoped resources.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `client` - The client that holds the store.
+	/// 
+	#[must_use]
+	pub const fn all(client: MockKubeClient) -> Self {
+		Self { client, namespace: None, resource: PhantomData }
+	}
+	
+	//		namespaced															
+	/// Creates an API that is scoped to a namespace.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `client`    - The client that holds the store.
+	/// * `namespace` - The namespace.
+	/// 
+	#[must_use]
+	pub fn namespaced(client: MockKubeClient, namespace: &str) -> Self {
+		Self { client, namespace: Some(namespace.to_owned()), resource: PhantomData }
+	}
+	
+	//		create																
+	/// Creates an object.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the object has no name, if an object with
+	/// the same name already exists, or if an error has been injected.
+	/// 
+	/// # See also
+	/// 
+	/// * `kube::Api::create()`
+	/// 
+	pub async fn create(&self, _pp: &MockPostParams, data: &K) -> Result<K, MockKubeError> {
+		let mut object = to_json(data)?;
+		let name       = object_name(&object)?;
+		let mut state  = self.client.lock();
+		state.take_injected(MockVerb::Create)?;
+		let key        = self.key(&name);
+		if state.objects.contains_key(&key) {
+			return Err(api_error(409, "AlreadyExists", &format!(r#"{} "{name}" already exists"#, kind_of::<K>())));
+		}
+		if let Some(ref namespace) = self.namespace {
+			set_metadata(&mut object, "namespace", Json::String(namespace.clone()));
+		}
+		let stored     = state.record(key, object, true);
+		drop(state);
+		from_json(stored)
+	}
+	
+	//		delete																
+	/// Deletes an object, returning it as it was before deletion.
+	/// 
+	/// Unlike Kube, which returns either the object or a status, the object is
+	/// always returned.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the object does not exist, or if an error
+	/// has been injected.
+	/// 
+	/// # See also
+	/// 
+	/// * `kube::Api::delete()`
+	/// 
+	pub async fn delete(&self, name: &str, _dp: &MockDeleteParams) -> Result<K, MockKubeError> {
+		let mut state = self.client.lock();
+		state.take_injected(MockVerb::Delete)?;
+		let key       = self.key(name);
+		let object    = state.objects.remove(&key).ok_or_else(|| not_found::<K>(name))?;
+		let version   = state.next_version();
+		state.events.push((key.0, key.1, version, StoredEvent::Deleted(object.clone())));
+		drop(state);
+		from_json(object)
+	}
+	
+	//		get																	
+	/// Gets an object.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the object does not exist, or if an error
+	/// has been injected.
+	/// 
+	/// # See also
+	/// 
+	/// * `kube::Api::get()`
+	/// 
+	pub async fn get(&self, name: &str) -> Result<K, MockKubeError> {
+		self.get_opt(name).await?.ok_or_else(|| not_found::<K>(name))
+	}
+	
+	//		get_opt																
+	/// Gets an object, returning [`None`] if it does not exist.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if an error has been injected.
+	/// 
+	/// # See also
+	/// 
+	/// * `kube::Api::get_opt()`
+	/// 
+	pub async fn get_opt(&self, name: &str) -> Result<Option<K>, MockKubeError> {
+		let mut state = self.client.lock();
+		state.take_injected(MockVerb::Get)?;
+		let object    = state.objects.get(&self.key(name)).cloned();
+		drop(state);
+		object.map(from_json).transpose()
+	}
+	
+	//		list																
+	/// Lists the objects that match the label selector, ordered by namespace
+	/// and name.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if an error has been injected.
+	/// 
+	/// # See also
+	/// 
+	/// * `kube::Api::list()`
+	/// 
+	pub async fn list(&self, lp: &MockListParams) -> Result<MockObjectList<K>, MockKubeError> {
+		let mut state = self.client.lock();
+		state.take_injected(MockVerb::List)?;
+		let objects   = state.objects.iter()
+			.filter(|&(key, object)| self.contains(key) && matches_labels(object, lp.label_selector.as_deref()))
+			.map(|(_, object)| from_json(object.clone()))
+			.collect::<Result<Vec<_>, _>>()
+		;
+		let version   = state.version.to_string();
+		drop(state);
+		Ok(MockObjectList {
+			items:            objects?,
+			resource_version: version,
+		})
+	}
+	
+	//		patch																
+	/// Patches an object.
+	/// 
+	/// If the patch carries a resource version that does not match the stored
+	/// one, a `409 Conflict` error is returned, as with optimistic concurrency
+	/// on a real cluster.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the object does not exist and the patch is
+	/// not an apply, if the resource version conflicts, or if an error has been
+	/// injected.
+	/// 
+	/// # See also
+	/// 
+	/// * `kube::Api::patch()`
+	/// 
+	pub async fn patch<P: Serialize + Sync>(
+		&self,
+		name:   &str,
+		_pp:    &MockPatchParams,
+		patch:  &MockPatch<P>,
+	) -> Result<K, MockKubeError> {
+		let (changes, apply) = match *patch {
+			MockPatch::Apply(ref changes) => (to_json(changes)?, true),
+			MockPatch::Merge(ref changes) => (to_json(changes)?, false),
+		};
+		let mut state = self.client.lock();
+		state.take_injected(MockVerb::Patch)?;
+		let key       = self.key(name);
+		let existing  = state.objects.get(&key).cloned();
+		let added     = existing.is_none();
+		let mut object = match existing {
+			Some(object)   => {
+				check_version::<K>(name, &object, &changes)?;
+				object
+			},
+			None if apply  => {
+				let mut object = Json::Object(Map::new());
+				set_metadata(&mut object, "name", Json::String(name.to_owned()));
+				if let Some(ref namespace) = self.namespace {
+					set_metadata(&mut object, "namespace", Json::String(namespace.clone()));
+				}
+				object
+			},
+			None           => return Err(not_found::<K>(name)),
+		};
+		merge_patch(&mut object, &changes);
+		let stored    = state.record(key, object, added);
+		drop(state);
+		from_json(stored)
+	}
+	
+	//		replace																
+	/// Replaces an object.
+	/// 
+	/// If the new object carries a resource version that does not match the
+	/// stored one, a `409 Conflict` error is returned, as with optimistic
+	/// concurrency on a real cluster.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the object does not exist, if the resource
+	/// version conflicts, or if an error has been injected.
+	/// 
+	/// # See also
+	/// 
+	/// * `kube::Api::replace()`
+	/// 
+	pub async fn replace(&self, name: &str, _pp: &MockPostParams, data: &K) -> Result<K, MockKubeError> {
+		let mut object = to_json(data)?;
+		let mut state  = self.client.lock();
+		state.take_injected(MockVerb::Replace)?;
+		let key        = self.key(name);
+		let existing   = state.objects.get(&key).ok_or_else(|| not_found::<K>(name))?;
+		check_version::<K>(name, existing, &object)?;
+		if let Some(uid) = existing.pointer("/metadata/uid").cloned() {
+			set_metadata(&mut object, "uid", uid);
+		}
+		if let Some(ref namespace) = self.namespace {
+			set_metadata(&mut object, "namespace", Json::String(namespace.clone()));
+		}
+		let stored     = state.record(key, object, false);
+		drop(state);
+		from_json(stored)
+	}
+	
+	//		watch																
+	/// Watches the objects that match the label selector, yielding the events
+	/// recorded after the given resource version, and then ending.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if an error has been injected. The stream will
+	/// yield an error if an event cannot be converted to the resource type.
+	/// 
+	/// # See also
+	/// 
+	/// * `kube::Api::watch()`
+	/// 
+	pub async fn watch(
+		&self,
+		wp:      &MockWatchParams,
+		version: &str,
+	) -> Result<Pin<Box<dyn Stream<Item = Result<MockWatchEvent<K>, MockKubeError>> + Send>>, MockKubeError> {
+		let since     = version.parse::<u64>().unwrap_or_default();
+		let mut state = self.client.lock();
+		state.take_injected(MockVerb::Watch)?;
+		let events    = state.events.iter()
+			.filter(|event| event.0 == type_name::<K>() && event.2 > since)
+			.filter(|event| self.namespace.is_none() || event.1 == self.namespace)
+			.filter(|event| match event.3 {
+				StoredEvent::Added(ref object)
+				| StoredEvent::Deleted(ref object)
+				| StoredEvent::Modified(ref object) => matches_labels(object, wp.label_selector.as_deref()),
+				StoredEvent::Error(_)               => true,
+			})
+			.map(|event| match event.3.clone() {
+				StoredEvent::Added(object)    => from_json(object).map(MockWatchEvent::Added),
+				StoredEvent::Deleted(object)  => from_json(object).map(MockWatchEvent::Deleted),
+				StoredEvent::Error(response)  => Ok(MockWatchEvent::Error(response)),
+				StoredEvent::Modified(object) => from_json(object).map(MockWatchEvent::Modified),
+			})
+			.collect::<Vec<_>>()
+		;
+		drop(state);
+		Ok(Box::pin(stream::iter(events)))
+	}
+	
+	//		contains															
+	/// Checks whether a stored object falls within the scope of the API.
+	fn contains(&self, key: &ObjectKey) -> bool {
+		key.0 == type_name::<K>() && (self.namespace.is_none() || key.1 == self.namespace)
+	}
+	
+	//		key																	
+	/// Returns the key of the named object within the scope of the API.
+	fn key(&self, name: &str) -> ObjectKey {
+		(type_name::<K>(), self.namespace.clone(), name.to_owned())
+	}
+}
+
+//		MockDeleteParams														
+/// The parameters for deleting an object.
+/// 
+/// # See also
+/// 
+/// * `kube::api::DeleteParams`
+/// 
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MockDeleteParams {
+	//		Public properties													
+	/// The grace period before the object is deleted, in seconds.
+	pub grace_period_seconds: Option<u32>,
+}
+
+//		MockErrorResponse														
+/// An error response from the mocked API server.
+/// 
+/// # See also
+/// 
+/// * `kube::core::ErrorResponse`
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockErrorResponse {
+	//		Public properties													
+	/// The status, which is always `Failure`.
+	pub status:  String,
+	
+	/// A description of the error.
+	pub message: String,
+	
+	/// The reason for the error, such as `Conflict` or `NotFound`.
+	pub reason:  String,
+	
+	/// The HTTP status code.
+	pub code:    u16,
+}
+
+//󰭅		MockErrorResponse														
+impl MockErrorResponse {
+	//		new																	
+	/// Creates a new error response.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `code`    - The HTTP status code.
+	/// * `reason`  - The reason for the error, such as `Conflict`.
+	/// * `message` - A description of the error.
+	/// 
+	#[must_use]
+	pub fn new(code: u16, reason: &str, message: &str) -> Self {
+		Self {
+			status:  "Failure".to_owned(),
+			message: message.to_owned(),
+			reason:  reason.to_owned(),
+			code,
+		}
+	}
+}
+
+//		MockKubeClient															
+/// A mocked Kube client, backed by an in-memory object store.
+/// 
+/// Clones share the same store, so a clone can be handed to the code under
+/// test while the original is used to set up state and inject errors.
+/// 
+/// # See also
+/// 
+/// * `kube::Client`
+/// 
+#[derive(Clone, Debug, Default)]
+pub struct MockKubeClient {
+	//		Private properties													
+	/// The state of the store, shared between clones.
+	state: Arc<Mutex<KubeState>>,
+}
+
+//󰭅		MockKubeClient															
+impl MockKubeClient {
+	//		new																	
+	/// Creates a new client for an empty store.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+	
+	//		inject_conflict														
+	/// Makes the next call of the given verb fail with `409 Conflict`.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `verb` - The verb to fail.
+	/// 
+	pub fn inject_conflict(&self, verb: MockVerb) {
+		self.inject_error(verb, api_error(
+			409,
+			"Conflict",
+			"Operation cannot be fulfilled: the object has been modified; please apply your changes to the latest version and try again",
+		));
+	}
+	
+	//		inject_error														
+	/// Makes the next call of the given verb fail with the given error. Errors
+	/// injected for the same verb are returned in the order injected.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `verb`  - The verb to fail.
+	/// * `error` - The error to return.
+	/// 
+	pub fn inject_error(&self, verb: MockVerb, error: MockKubeError) {
+		self.lock().injected.push((verb, error));
+	}
+	
+	//		inject_watch_error													
+	/// Records an error event, such as `410 Gone`, to be yielded by watches of
+	/// the given resource type.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `namespace` - The namespace of the watches, if any.
+	/// * `response`  - The error to yield.
+	/// 
+	pub fn inject_watch_error<K>(&self, namespace: Option<&str>, response: MockErrorResponse) {
+		let mut state = self.lock();
+		let version   = state.next_version();
+		state.events.push((type_name::<K>(), namespace.map(ToOwned::to_owned), version, StoredEvent::Error(response)));
+	}
+	
+	//		lock																
+	/// Locks the state of the store.
+	fn lock(&self) -> MutexGuard<'_, KubeState> {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+}
+
+//		MockListParams															
+/// The parameters for listing objects.
+/// 
+/// # See also
+/// 
+/// * `kube::api::ListParams`
+/// 
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MockListParams {
+	//		Public properties													
+	/// An equality-based label selector, such as `app=web,tier!=db`.
+	pub label_selector: Option<String>,
+}
+
+//		MockObjectList															
+/// A list of objects.
+/// 
+/// # See also
+/// 
+/// * `kube::core::ObjectList`
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockObjectList<K> {
+	//		Public properties													
+	/// The objects.
+	pub items:            Vec<K>,
+	
+	/// The resource version of the store when the list was taken, from which a
+	/// watch can be started.
+	pub resource_version: String,
+}
+
+//		MockPatchParams															
+/// The parameters for patching an object.
+/// 
+/// # See also
+/// 
+/// * `kube::api::PatchParams`
+/// 
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MockPatchParams {
+	//		Public properties													
+	/// The name of the field manager, used for server-side apply.
+	pub field_manager: Option<String>,
+	
+	/// Whether to take ownership of conflicting fields when applying.
+	pub force:         bool,
+}
+
+//		MockPostParams															
+/// The parameters for creating or replacing an object.
+/// 
+/// # See also
+/// 
+/// * `kube::api::PostParams`
+/// 
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MockPostParams {
+	//		Public properties													
+	/// Whether to validate the request without persisting it.
+	pub dry_run:       bool,
+	
+	/// The name of the field manager.
+	pub field_manager: Option<String>,
+}
+
+//		MockWatchParams															
+/// The parameters for watching objects.
+/// 
+/// # See also
+/// 
+/// * `kube::api::WatchParams`
+/// 
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MockWatchParams {
+	//		Public properties													
+	/// An equality-based label selector, such as `app=web,tier!=db`.
+	pub label_selector: Option<String>,
+}
+
+
+
+//		Functions
+
+//		api_error																
+/// Creates an error as returned by the API server.
+fn api_error(code: u16, reason: &str, message: &str) -> MockKubeError {
+	MockKubeError::Api(MockErrorResponse::new(code, reason, message))
+}
+
+//		check_version															
+/// Checks that the resource version given in a change, if any, matches that of
+/// the stored object.
+fn check_version<K>(name: &str, stored: &Json, changes: &Json) -> Result<(), MockKubeError> {
+	match changes.pointer("/metadata/resourceVersion") {
+		Some(version) if Some(version) != stored.pointer("/metadata/resourceVersion") => Err(api_error(
+			409,
+			"Conflict",
+			&format!(
+				r#"Operation cannot be fulfilled on {} "{name}": the object has been modified; please apply your changes to the latest version and try again"#,
+				kind_of::<K>(),
+			),
+		)),
+		Some(_) | None => Ok(()),
+	}
+}
+
+//		from_json																
+/// Converts a stored object to the resource type.
+fn from_json<K: DeserializeOwned>(object: Json) -> Result<K, MockKubeError> {
+	serde_json::from_value(object).map_err(|err| MockKubeError::SerdeError(err.to_string()))
+}
+
+//		kind_of																	
+/// Returns a readable name for the resource type.
+fn kind_of<K>() -> &'static str {
+	let name = type_name::<K>();
+	name.rsplit("::").next().unwrap_or(name)
+}
+
+//		matches_labels															
+/// Checks whether an object matches an equality-based label selector.
+fn matches_labels(object: &Json, selector: Option<&str>) -> bool {
+	selector.unwrap_or_default().split(',').map(str::trim).filter(|term| !term.is_empty()).all(|term| {
+		let (label, expected, equal) = match term.split_once("!=") {
+			Some((label, expected)) => (label, expected, false),
+			None                    => match term.split_once('=') {
+				Some((label, expected)) => (label, expected.trim_start_matches('='), true),
+				None                    => return object.pointer("/metadata/labels").and_then(|labels| labels.get(term)).is_some(),
+			},
+		};
+		let actual = object.pointer("/metadata/labels").and_then(|labels| labels.get(label.trim())).and_then(Json::as_str);
+		(actual == Some(expected.trim())) == equal
+	})
+}
+
+//		merge_patch																
+/// Applies a JSON merge patch, as per RFC 7386.
+fn merge_patch(target: &mut Json, patch: &Json) {
+	let Some(changes) = patch.as_object() else {
+		target.clone_from(patch);
+		return;
+	};
+	if !target.is_object() {
+		*target = Json::Object(Map::new());
+	}
+	if let Some(fields) = target.as_object_mut() {
+		for (field, value) in changes {
+			if value.is_null() {
+				drop(fields.remove(field));
+			} else {
+				merge_patch(fields.entry(field.clone()).or_insert(Json::Null), value);
+			}
+		}
+	}
+}
+
+//		not_found																
+/// Creates a `404 Not Found` error for the named object.
+fn not_found<K>(name: &str) -> MockKubeError {
+	api_error(404, "NotFound", &format!(r#"{} "{name}" not found"#, kind_of::<K>()))
+}
+
+//		object_name																
+/// Returns the name of an object.
+fn object_name(object: &Json) -> Result<String, MockKubeError> {
+	object.pointer("/metadata/name")
+		.and_then(Json::as_str)
+		.map(ToOwned::to_owned)
+		.ok_or_else(|| api_error(422, "Invalid", "metadata.name: Required value: name is required"))
+}
+
+//		set_metadata															
+/// Sets a field of the metadata of an object.
+fn set_metadata(object: &mut Json, field: &str, value: Json) {
+	merge_patch(object, &Json::Object(Map::from_iter([
+		("metadata".to_owned(), Json::Object(Map::from_iter([(field.to_owned(), value)]))),
+	])));
+}
+
+//		to_json																	
+/// Converts an object to JSON.
+fn to_json<T: Serialize>(object: &T) -> Result<Json, MockKubeError> {
+	serde_json::to_value(object).map_err(|err| MockKubeError::SerdeError(err.to_string()))
+}
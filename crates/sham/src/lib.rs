@@ -41,12 +41,311 @@
 
 
 
+//		Packages
+
+//	Used by the documentation examples only
+#[cfg(test)]
+use futures_executor as _;
+//	Only used by the modules that support them, which may not be enabled
+#[cfg(feature = "futures-io")]
+use futures_io as _;
+#[cfg(feature = "tokio")]
+use tokio as _;
+
+pub use crate::context::{
+	Context,
+	ContextGuard,
+	MockInteraction,
+	MockUnusedPolicy,
+	MockVerificationError,
+	MockVerificationFailure,
+	verify_all,
+};
+pub use crate::seed::seed;
+
+
+
+//		Macros
+
+#[cfg(feature = "macros")]
+pub use sham_macros::{swap_uses, test};
+
+
+
 //		Modules
 
+pub mod context;
+pub mod prelude;
+mod scope;
+pub mod seed;
+
+#[cfg(feature = "archive")]
+pub mod archive;
+
+#[cfg(feature = "auth")]
+pub mod auth;
+
+#[cfg(feature = "baseline")]
+pub mod baseline;
+
+#[cfg(feature = "bdd")]
+pub mod bdd;
+
+#[cfg(feature = "breaker")]
+pub mod breaker;
+
+#[cfg(feature = "cache")]
+pub mod cache;
+
+#[cfg(feature = "capture")]
+pub mod capture;
+
+#[cfg(feature = "clock")]
+pub mod clock;
+
+#[cfg(feature = "config")]
+pub mod config;
+
+#[cfg(feature = "cron")]
+pub mod cron;
+
+#[cfg(feature = "discovery")]
+pub mod discovery;
+
+#[cfg(feature = "docker")]
+pub mod docker;
+
+#[cfg(feature = "doh")]
+pub mod doh;
+
+#[cfg(feature = "dynamic")]
+pub mod dynamic;
+
+#[cfg(feature = "failover")]
+pub mod failover;
+
+#[cfg(feature = "fault")]
+pub mod fault;
+
+#[cfg(feature = "geoip")]
+pub mod geoip;
+
+#[cfg(feature = "graphql")]
+pub mod graphql;
+
+#[cfg(feature = "headers")]
+pub mod headers;
+
+#[cfg(feature = "identity")]
+pub mod identity;
+
+#[cfg(feature = "jsonrpc")]
+pub mod jsonrpc;
+
+#[cfg(feature = "kube")]
+pub mod kube;
+
+#[cfg(feature = "locale")]
+pub mod locale;
+
+#[cfg(feature = "log")]
+pub mod log;
+
+#[cfg(feature = "malformed")]
+pub mod malformed;
+
+#[cfg(feature = "metrics")]
+pub mod metrics;
+
+#[cfg(feature = "network")]
+pub mod network;
+
+#[cfg(feature = "oauth")]
+pub mod oauth;
+
+#[cfg(feature = "output")]
+pub mod output;
+
+#[cfg(feature = "path")]
+pub mod path;
+
+#[cfg(feature = "presets")]
+pub mod presets;
+
+#[cfg(feature = "privilege")]
+pub mod privilege;
+
+#[cfg(feature = "prompt")]
+pub mod prompt;
+
+#[cfg(feature = "proptest")]
+pub mod proptest;
+
+#[cfg(feature = "prost")]
+pub mod prost;
+
+#[cfg(feature = "records")]
+pub mod records;
+
 #[cfg(feature = "reqwest")]
 pub mod reqwest;
 
+#[cfg(feature = "resource")]
+pub mod resource;
+
+#[cfg(feature = "scenario")]
+pub mod scenario;
+
+#[cfg(feature = "sentry")]
+pub mod sentry;
+
+#[cfg(feature = "shell")]
+pub mod shell;
+
+#[cfg(feature = "sleep")]
+pub mod sleep;
+
+#[cfg(feature = "soap")]
+pub mod soap;
+
 #[cfg(feature = "std_process")]
 pub mod std_process;
 
+#[cfg(feature = "stripe")]
+pub mod stripe;
+
+#[cfg(feature = "systemd")]
+pub mod systemd;
+
+#[cfg(feature = "terminal")]
+pub mod terminal;
+
+#[cfg(feature = "throttle")]
+pub mod throttle;
+
+#[cfg(feature = "tls")]
+pub mod tls;
+
+#[cfg(feature = "tracing")]
+pub mod tracing;
+
+#[cfg(feature = "unix")]
+pub mod unix;
+
+#[cfg(feature = "url")]
+pub mod url;
+
+#[cfg(feature = "webhook")]
+pub mod webhook;
+
+#[cfg(feature = "which")]
+pub mod which;
+
+#[cfg(feature = "xml")]
+pub mod xml;
+
+
+
+//		Constants
+
+/// The features of the crate, and whether each one is enabled.
+const FEATURES: [(&str, bool); 59] = [
+	("archive",     cfg!(feature = "archive")),
+	("auth",        cfg!(feature = "auth")),
+	("baseline",    cfg!(feature = "baseline")),
+	("bdd",         cfg!(feature = "bdd")),
+	("breaker",     cfg!(feature = "breaker")),
+	("cache",       cfg!(feature = "cache")),
+	("capture",     cfg!(feature = "capture")),
+	("clock",       cfg!(feature = "clock")),
+	("config",      cfg!(feature = "config")),
+	("cron",        cfg!(feature = "cron")),
+	("discovery",   cfg!(feature = "discovery")),
+	("docker",      cfg!(feature = "docker")),
+	("doh",         cfg!(feature = "doh")),
+	("dynamic",     cfg!(feature = "dynamic")),
+	("failover",    cfg!(feature = "failover")),
+	("fault",       cfg!(feature = "fault")),
+	("futures-io",  cfg!(feature = "futures-io")),
+	("geoip",       cfg!(feature = "geoip")),
+	("graphql",     cfg!(feature = "graphql")),
+	("headers",     cfg!(feature = "headers")),
+	("identity",    cfg!(feature = "identity")),
+	("jsonrpc",     cfg!(feature = "jsonrpc")),
+	("kube",        cfg!(feature = "kube")),
+	("locale",      cfg!(feature = "locale")),
+	("log",         cfg!(feature = "log")),
+	("macros",      cfg!(feature = "macros")),
+	("malformed",   cfg!(feature = "malformed")),
+	("metrics",     cfg!(feature = "metrics")),
+	("network",     cfg!(feature = "network")),
+	("oauth",       cfg!(feature = "oauth")),
+	("output",      cfg!(feature = "output")),
+	("path",        cfg!(feature = "path")),
+	("presets",     cfg!(feature = "presets")),
+	("privilege",   cfg!(feature = "privilege")),
+	("prompt",      cfg!(feature = "prompt")),
+	("proptest",    cfg!(feature = "proptest")),
+	("prost",       cfg!(feature = "prost")),
+	("records",     cfg!(feature = "records")),
+	("report",      cfg!(feature = "report")),
+	("reqwest",     cfg!(feature = "reqwest")),
+	("resource",    cfg!(feature = "resource")),
+	("scenario",    cfg!(feature = "scenario")),
+	("sentry",      cfg!(feature = "sentry")),
+	("shell",       cfg!(feature = "shell")),
+	("sleep",       cfg!(feature = "sleep")),
+	("soap",        cfg!(feature = "soap")),
+	("std_process", cfg!(feature = "std_process")),
+	("stripe",      cfg!(feature = "stripe")),
+	("systemd",     cfg!(feature = "systemd")),
+	("terminal",    cfg!(feature = "terminal")),
+	("throttle",    cfg!(feature = "throttle")),
+	("tls",         cfg!(feature = "tls")),
+	("tokio",       cfg!(feature = "tokio")),
+	("tracing",     cfg!(feature = "tracing")),
+	("unix",        cfg!(feature = "unix")),
+	("url",         cfg!(feature = "url")),
+	("webhook",     cfg!(feature = "webhook")),
+	("which",       cfg!(feature = "which")),
+	("xml",         cfg!(feature = "xml")),
+];
+
+
+
+//		Functions
 
+//		capabilities															
+/// Returns the names of the features of the crate that are enabled.
+/// 
+/// Each mock module, and each optional capability such as the `test` attribute
+/// or the reports, is behind its own feature, so that only what is needed has
+/// to be built. This reports which of them were enabled when the crate was
+/// built, in alphabetical order, so that shared test helpers can adapt, and
+/// so that a test can explain clearly that it needs a feature which is missing.
+/// The `full` and `all` convenience features are not listed themselves.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// let enabled = sham::capabilities();
+/// assert_eq!(enabled.contains(&"reqwest"), cfg!(feature = "reqwest"));
+/// assert!(enabled.windows(2).all(|pair| pair[0] < pair[1]));
+/// 
+/// // With every feature enabled, every feature in the manifest is reported.
+/// if cfg!(feature = "full") {
+///     let manifest = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"));
+///     let section  = manifest.split("[features]").nth(1).unwrap().split("\n\n").next().unwrap();
+///     for line in section.lines().filter(|line| line.contains('=')) {
+///         let name = line.split('=').next().unwrap().trim();
+///         if !["default", "all", "full"].contains(&name) {
+///             assert!(enabled.contains(&name), "Feature `{name}` is not reported by capabilities()");
+///         }
+///     }
+/// }
+/// ```
+/// 
+#[must_use]
+pub fn capabilities() -> Vec<&'static str> {
+	FEATURES.iter().filter(|&&(_, enabled)| enabled).map(|&(name, _)| name).collect()
+}
@@ -0,0 +1,739 @@
+//! This module mocks the local time zone and locale in order to test date and
+//! number handling.
+//! 
+//! This module provides a [`MockTimeZone`], which implements the
+//! [`chrono::TimeZone`] trait with configurable daylight saving transitions,
+//! and a [`Locale`] trait, with a [`MockLocale`] that supplies the decimal and
+//! grouping separators and the week numbering rules of a chosen locale. This is
+//! important because date-boundary bugs, such as times that do not exist or
+//! occur twice when the clocks change, or dates that fall in week 53 or week 1
+//! depending on where the week starts, only show up in certain zones and
+//! locales, and the zone and locale of the machine running the tests cannot be
+//! relied on.
+//! 
+//! The approach taken is that the "real" code should be generic over the
+//! [`chrono::TimeZone`] that it uses, rather than reaching for
+//! [`chrono::Local`](https://docs.rs/chrono/latest/chrono/struct.Local.html),
+//! and should format numbers and dates through a [`Locale`] that it is given.
+//! The test code then supplies a [`MockTimeZone`] and a [`MockLocale`]
+//! instead. A time zone has a standard offset, and a list of
+//! transitions to other offsets, which can be given explicitly or generated
+//! from a [`MockDstRule`]. Local times that fall in the gap when the clocks go
+//! forward do not exist, and local times that fall in the overlap when the
+//! clocks go back are ambiguous, earliest first, just as with real zones. When
+//! the `clock` feature is enabled, [`MockTimeZone::now()`] gives the time of a
+//! [`MockClock`] in the zone.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use chrono::{NaiveDate, TimeZone, Weekday};
+//! use sham::locale::{Locale, MockLocale, MockTimeZone};
+//! 
+//! let london = MockTimeZone::europe_london();
+//! let date   = NaiveDate::from_ymd_opt(2024, 3, 31).unwrap();
+//! assert!(london.from_local_datetime(&date.and_hms_opt(1, 30, 0).unwrap()).single().is_none());
+//! assert_eq!(london.from_local_datetime(&date.and_hms_opt(2, 30, 0).unwrap()).unwrap().to_rfc3339(), "2024-03-31T02:30:00+01:00");
+//! assert_eq!(london.with_ymd_and_hms(2024, 10, 27, 1, 30, 0).earliest().unwrap().to_string(), "2024-10-27 01:30:00 BST");
+//! assert_eq!(london.with_ymd_and_hms(2024, 10, 27, 1, 30, 0).latest().unwrap().to_string(), "2024-10-27 01:30:00 GMT");
+//! 
+//! let us = MockLocale::en_us();
+//! let de = MockLocale::de_de();
+//! assert_eq!(us.format_number(1_234_567.891, 2), "1,234,567.89");
+//! assert_eq!(de.format_number(1_234_567.891, 2), "1.234.567,89");
+//! assert_eq!(de.parse_number("1.234,5"), Some(1_234.5));
+//! 
+//! let new_year = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+//! assert_eq!(us.first_day_of_week(), Weekday::Sun);
+//! assert_eq!(us.week_of_year(new_year), (2022, 1));
+//! assert_eq!(de.week_of_year(new_year), (2021, 52));
+//! ```
+//! 
+
+
+
+//		Packages
+
+use chrono::{
+	DateTime,
+	Datelike,
+	Days,
+	FixedOffset,
+	MappedLocalTime,
+	NaiveDate,
+	NaiveDateTime,
+	NaiveTime,
+	Offset,
+	TimeDelta,
+	TimeZone,
+	Utc,
+	Weekday,
+};
+use core::{
+	fmt::{Display, Formatter, self},
+	iter,
+	ops::RangeInclusive,
+};
+use std::sync::Arc;
+
+#[cfg(feature = "clock")]
+use crate::clock::MockClock;
+
+
+
+//		Traits
+
+//§		Locale																	
+/// A provider of the conventions of a locale.
+/// 
+/// The separators and week rules are required, and the formatting, parsing,
+/// and week numbering built on them are provided.
+/// 
+pub trait Locale {
+	//		decimal_separator													
+	/// Returns the character that separates the whole and fractional parts of
+	/// a number.
+	fn decimal_separator(&self) -> char;
+	
+	//		first_day_of_week													
+	/// Returns the day on which each week starts.
+	fn first_day_of_week(&self) -> Weekday;
+	
+	//		grouping_separator													
+	/// Returns the character that separates groups of thousands, if any.
+	fn grouping_separator(&self) -> Option<char>;
+	
+	//		minimal_days_in_first_week											
+	/// Returns the number of days of a new year that the first week of that
+	/// year must contain, which is `4` for ISO 8601 week numbering and `1` for
+	/// the numbering used in the United States.
+	fn minimal_days_in_first_week(&self) -> u32;
+	
+	//		format_number														
+	/// Formats a number with the given number of decimal places, using the
+	/// separators of the locale.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `value`    - The number to format.
+	/// * `decimals` - The number of decimal places to show.
+	/// 
+	fn format_number(&self, value: f64, decimals: usize) -> String {
+		if !value.is_finite() {
+			return value.to_string();
+		}
+		let formatted          = format!("{:.*}", decimals, value.abs());
+		let (whole, fraction)  = formatted.split_once('.').unwrap_or((&formatted, ""));
+		let mut output         = String::new();
+		if value.is_sign_negative() && formatted.chars().any(|digit| digit != '0' && digit != '.') {
+			output.push('-');
+		}
+		let count              = whole.chars().count();
+		for (index, digit) in whole.chars().enumerate() {
+			if index > 0 && (count - index) % 3 == 0 {
+				if let Some(separator) = self.grouping_separator() {
+					output.push(separator);
+				}
+			}
+			output.push(digit);
+		}
+		if !fraction.is_empty() {
+			output.push(self.decimal_separator());
+			output.push_str(fraction);
+		}
+		output
+	}
+	
+	//		parse_number														
+	/// Parses a number written with the separators of the locale.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `text` - The text to parse.
+	/// 
+	fn parse_number(&self, text: &str) -> Option<f64> {
+		let grouping = self.grouping_separator();
+		let decimal  = self.decimal_separator();
+		text.trim().chars()
+			.filter(|&character| Some(character) != grouping)
+			.map(|character| if character == decimal { '.' } else { character })
+			.collect::<String>()
+			.parse()
+			.ok()
+	}
+	
+	//		start_of_week														
+	/// Returns the first day of the week that contains the given date.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `date` - The date to find the week of.
+	/// 
+	fn start_of_week(&self, date: NaiveDate) -> NaiveDate {
+		date - Days::new(u64::from(days_since(date.weekday(), self.first_day_of_week())))
+	}
+	
+	//		week_of_year														
+	/// Returns the week-numbering year and the week number of the given date.
+	/// 
+	/// The week-numbering year can differ from the calendar year for dates at
+	/// the start and end of the year, such as the 1st of January 2022, which is
+	/// in week 52 of 2021 by ISO 8601, but in week 1 of 2022 in the United
+	/// States.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `date` - The date to find the week of.
+	/// 
+	fn week_of_year(&self, date: NaiveDate) -> (i32, u32) {
+		let first_day    = self.first_day_of_week();
+		let minimal_days = self.minimal_days_in_first_week();
+		let year         = [date.year() + 1, date.year(), date.year() - 1].into_iter()
+			.find(|&candidate| first_week_start(candidate, first_day, minimal_days) <= date)
+			.unwrap_or_else(|| date.year())
+		;
+		let days         = (date - first_week_start(year, first_day, minimal_days)).num_days();
+		(year, u32::try_from(days.div_euclid(7) + 1).unwrap_or_default())
+	}
+}
+
+
+
+//		Enums
+
+//		MockDstRule																
+/// A rule for the dates on which daylight saving time starts and ends.
+/// 
+/// Each rule uses the current definition for every year that it is applied to,
+/// and daylight saving time is always one hour ahead of standard time.
+/// 
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum MockDstRule {
+	/// The rule used in the European Union and the United Kingdom, where the
+	/// clocks go forward at 01:00 UTC on the last Sunday in March, and back at
+	/// 01:00 UTC on the last Sunday in October.
+	European,
+	
+	/// The rule used in most of the United States and Canada, where the clocks
+	/// go forward at 02:00 local time on the second Sunday in March, and back
+	/// at 02:00 local time on the first Sunday in November.
+	NorthAmerican,
+}
+
+//󰭅		MockDstRule																
+impl MockDstRule {
+	//		transitions															
+	/// Returns the UTC times at which daylight saving time starts and ends in
+	/// the given year, for a zone with the given standard offset.
+	fn transitions(self, year: i32, standard: FixedOffset) -> Option<(NaiveDateTime, NaiveDateTime)> {
+		let one_am       = NaiveTime::from_hms_opt(1, 0, 0)?;
+		let two_am       = NaiveTime::from_hms_opt(2, 0, 0)?;
+		let standard_gap = TimeDelta::seconds(i64::from(standard.local_minus_utc()));
+		let summer_gap   = standard_gap + TimeDelta::hours(1);
+		match self {
+			Self::European      => Some((
+				last_weekday_of_month(year, 3, Weekday::Sun)?.and_time(one_am),
+				last_weekday_of_month(year, 10, Weekday::Sun)?.and_time(one_am),
+			)),
+			Self::NorthAmerican => Some((
+				NaiveDate::from_weekday_of_month_opt(year, 3, Weekday::Sun, 2)?.and_time(two_am) - standard_gap,
+				NaiveDate::from_weekday_of_month_opt(year, 11, Weekday::Sun, 1)?.and_time(two_am) - summer_gap,
+			)),
+		}
+	}
+}
+
+
+
+//		Structs
+
+//		MockLocale																
+/// A mocked locale, with the conventions of a chosen locale.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct MockLocale {
+	//		Public properties													
+	/// The character that separates the whole and fractional parts of a
+	/// number.
+	pub decimal_separator:          char,
+	
+	/// The day on which each week starts.
+	pub first_day_of_week:          Weekday,
+	
+	/// The character that separates groups of thousands, if any.
+	pub grouping_separator:         Option<char>,
+	
+	/// The number of days of a new year that the first week of that year must
+	/// contain.
+	pub minimal_days_in_first_week: u32,
+}
+
+//󰭅		MockLocale																
+impl MockLocale {
+	//		de_de																
+	/// Creates a new locale with the conventions of Germany, which uses a comma
+	/// as the decimal separator, a full stop to group thousands, and ISO 8601
+	/// week numbering.
+	#[must_use]
+	pub const fn de_de() -> Self {
+		Self {
+			decimal_separator:          ',',
+			first_day_of_week:          Weekday::Mon,
+			grouping_separator:         Some('.'),
+			minimal_days_in_first_week: 4,
+		}
+	}
+	
+	//		en_gb																
+	/// Creates a new locale with the conventions of the United Kingdom, which
+	/// uses a full stop as the decimal separator, a comma to group thousands,
+	/// and ISO 8601 week numbering.
+	#[must_use]
+	pub const fn en_gb() -> Self {
+		Self {
+			decimal_separator:          '.',
+			first_day_of_week:          Weekday::Mon,
+			grouping_separator:         Some(','),
+			minimal_days_in_first_week: 4,
+		}
+	}
+	
+	//		en_us																
+	/// Creates a new locale with the conventions of the United States, which
+	/// uses a full stop as the decimal separator, a comma to group thousands,
+	/// and weeks that start on Sunday, with week 1 being the week that
+	/// contains the 1st of January.
+	#[must_use]
+	pub const fn en_us() -> Self {
+		Self {
+			decimal_separator:          '.',
+			first_day_of_week:          Weekday::Sun,
+			grouping_separator:         Some(','),
+			minimal_days_in_first_week: 1,
+		}
+	}
+	
+	//		fr_fr																
+	/// Creates a new locale with the conventions of France, which uses a comma
+	/// as the decimal separator, a narrow no-break space to group thousands,
+	/// and ISO 8601 week numbering.
+	#[must_use]
+	pub const fn fr_fr() -> Self {
+		Self {
+			decimal_separator:          ',',
+			first_day_of_week:          Weekday::Mon,
+			grouping_separator:         Some('\u{202F}'),
+			minimal_days_in_first_week: 4,
+		}
+	}
+	
+	//		with_decimal_separator												
+	/// Sets the character that separates the whole and fractional parts of a
+	/// number.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `separator` - The decimal separator.
+	/// 
+	#[must_use]
+	pub const fn with_decimal_separator(mut self, separator: char) -> Self {
+		self.decimal_separator = separator;
+		self
+	}
+	
+	//		with_first_day_of_week												
+	/// Sets the day on which each week starts.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `day` - The first day of the week.
+	/// 
+	#[must_use]
+	pub const fn with_first_day_of_week(mut self, day: Weekday) -> Self {
+		self.first_day_of_week = day;
+		self
+	}
+	
+	//		with_grouping_separator												
+	/// Sets the character that separates groups of thousands, or `None` for
+	/// no grouping.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `separator` - The grouping separator, if any.
+	/// 
+	#[must_use]
+	pub const fn with_grouping_separator(mut self, separator: Option<char>) -> Self {
+		self.grouping_separator = separator;
+		self
+	}
+	
+	//		with_minimal_days_in_first_week										
+	/// Sets the number of days of a new year that the first week of that year
+	/// must contain, from `1` to `7`.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `days` - The minimal number of days in the first week.
+	/// 
+	#[must_use]
+	pub const fn with_minimal_days_in_first_week(mut self, days: u32) -> Self {
+		self.minimal_days_in_first_week = days;
+		self
+	}
+}
+
+//󰭅		Default																	
+impl Default for MockLocale {
+	//		default																
+	fn default() -> Self {
+		Self::en_us()
+	}
+}
+
+//󰭅		Locale																	
+impl Locale for MockLocale {
+	//		decimal_separator													
+	fn decimal_separator(&self) -> char {
+		self.decimal_separator
+	}
+	
+	//		first_day_of_week													
+	fn first_day_of_week(&self) -> Weekday {
+		self.first_day_of_week
+	}
+	
+	//		grouping_separator													
+	fn grouping_separator(&self) -> Option<char> {
+		self.grouping_separator
+	}
+	
+	//		minimal_days_in_first_week											
+	fn minimal_days_in_first_week(&self) -> u32 {
+		self.minimal_days_in_first_week
+	}
+}
+
+//		MockOffset																
+/// The offset from UTC of a [`MockTimeZone`] at a particular time.
+/// 
+/// This is displayed as the abbreviation of the zone at that time, such as
+/// `BST`, so that it appears in formatted times in place of the numeric offset.
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MockOffset {
+	//		Private properties													
+	/// The period of the zone that the offset belongs to.
+	period: Period,
+	
+	/// The zone that the offset belongs to.
+	zone:   MockTimeZone,
+}
+
+//󰭅		MockOffset																
+impl MockOffset {
+	//		abbreviation														
+	/// Returns the abbreviation of the zone at this offset, such as `BST`.
+	#[must_use]
+	pub fn abbreviation(&self) -> &str {
+		&self.period.abbreviation
+	}
+	
+	//		is_dst																
+	/// Whether this offset differs from the standard offset of the zone, which
+	/// is the case during daylight saving time.
+	#[must_use]
+	pub fn is_dst(&self) -> bool {
+		self.period.offset != self.zone.rules.standard.offset
+	}
+}
+
+//󰭅		Display																	
+impl Display for MockOffset {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.period.abbreviation)
+	}
+}
+
+//󰭅		Offset																	
+impl Offset for MockOffset {
+	//		fix																	
+	fn fix(&self) -> FixedOffset {
+		self.period.offset
+	}
+}
+
+//		MockTimeZone															
+/// A mocked time zone, with a standard offset and a list of transitions.
+/// 
+/// Clones share the same definition, which cannot be changed once the zone is
+/// in use.
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MockTimeZone {
+	//		Private properties													
+	/// The definition of the zone.
+	rules: Arc<Rules>,
+}
+
+//󰭅		MockTimeZone															
+impl MockTimeZone {
+	//		new																	
+	/// Creates a new time zone with a fixed offset and no transitions.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name`         - The name of the zone, such as `Europe/London`.
+	/// * `abbreviation` - The abbreviation used for the standard offset, such
+	///   as `GMT`.
+	/// * `offset`       - The standard offset from UTC, in seconds east.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the offset is not less than a day.
+	/// 
+	#[must_use]
+	pub fn new(name: &str, abbreviation: &str, offset: i32) -> Self {
+		Self {
+			rules: Arc::new(Rules {
+				name:        name.to_owned(),
+				standard:    Period::new(abbreviation, offset),
+				transitions: Vec::new(),
+			}),
+		}
+	}
+	
+	//		america_new_york													
+	/// Creates a new time zone similar to `America/New_York`, using the
+	/// [`NorthAmerican`](MockDstRule::NorthAmerican) rule from 2000 to 2099.
+	#[must_use]
+	pub fn america_new_york() -> Self {
+		Self::new("America/New_York", "EST", -5 * 3_600).with_dst(MockDstRule::NorthAmerican, "EDT", 2_000..=2_099)
+	}
+	
+	//		europe_berlin														
+	/// Creates a new time zone similar to `Europe/Berlin`, using the
+	/// [`European`](MockDstRule::European) rule from 2000 to 2099.
+	#[must_use]
+	pub fn europe_berlin() -> Self {
+		Self::new("Europe/Berlin", "CET", 3_600).with_dst(MockDstRule::European, "CEST", 2_000..=2_099)
+	}
+	
+	//		europe_london														
+	/// Creates a new time zone similar to `Europe/London`, using the
+	/// [`European`](MockDstRule::European) rule from 2000 to 2099.
+	#[must_use]
+	pub fn europe_london() -> Self {
+		Self::new("Europe/London", "GMT", 0).with_dst(MockDstRule::European, "BST", 2_000..=2_099)
+	}
+	
+	//		utc																	
+	/// Creates a new time zone for UTC.
+	#[must_use]
+	pub fn utc() -> Self {
+		Self::new("UTC", "UTC", 0)
+	}
+	
+	//		with_dst															
+	/// Adds the transitions into and out of daylight saving time for each of
+	/// the given years, according to a rule.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `rule`         - The rule that decides when daylight saving time
+	///   starts and ends.
+	/// * `abbreviation` - The abbreviation used during daylight saving time,
+	///   such as `BST`.
+	/// * `years`        - The years to add the transitions for.
+	/// 
+	#[must_use]
+	pub fn with_dst(mut self, rule: MockDstRule, abbreviation: &str, years: RangeInclusive<i32>) -> Self {
+		let rules    = Arc::make_mut(&mut self.rules);
+		let standard = rules.standard.clone();
+		let summer   = Period::new(abbreviation, standard.offset.local_minus_utc() + 3_600);
+		for year in years {
+			if let Some((start, end)) = rule.transitions(year, standard.offset) {
+				rules.transitions.push((start, summer.clone()));
+				rules.transitions.push((end,   standard.clone()));
+			}
+		}
+		rules.transitions.sort_by_key(|transition| transition.0);
+		self
+	}
+	
+	//		with_transition														
+	/// Adds a transition, after which the zone uses a different offset until
+	/// the next transition.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `at`           - The time at which the transition happens.
+	/// * `abbreviation` - The abbreviation used after the transition.
+	/// * `offset`       - The offset from UTC after the transition, in seconds
+	///   east.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the offset is not less than a day.
+	/// 
+	#[must_use]
+	pub fn with_transition(mut self, at: DateTime<Utc>, abbreviation: &str, offset: i32) -> Self {
+		let rules = Arc::make_mut(&mut self.rules);
+		rules.transitions.push((at.naive_utc(), Period::new(abbreviation, offset)));
+		rules.transitions.sort_by_key(|transition| transition.0);
+		self
+	}
+	
+	//		name																
+	/// Returns the name of the zone.
+	#[must_use]
+	pub fn name(&self) -> &str {
+		&self.rules.name
+	}
+	
+	//		now																	
+	/// Returns the current time of the given clock in this zone.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `clock` - The clock to read the time from.
+	/// 
+	#[cfg(feature = "clock")]
+	#[must_use]
+	pub fn now(&self, clock: &MockClock) -> DateTime<Self> {
+		DateTime::<Utc>::from(clock.now()).with_timezone(self)
+	}
+	
+	//		offset																
+	/// Creates the offset for a period of this zone.
+	fn offset(&self, period: &Period) -> MockOffset {
+		MockOffset {
+			period: period.clone(),
+			zone:   self.clone(),
+		}
+	}
+	
+	//		period_at															
+	/// Returns the period of the zone that is in effect at the given UTC time.
+	fn period_at(&self, utc: &NaiveDateTime) -> &Period {
+		let index = self.rules.transitions.partition_point(|transition| transition.0 <= *utc);
+		index.checked_sub(1)
+			.and_then(|previous| self.rules.transitions.get(previous))
+			.map_or(&self.rules.standard, |transition| &transition.1)
+	}
+}
+
+//󰭅		TimeZone																
+impl TimeZone for MockTimeZone {
+	type Offset = MockOffset;
+	
+	//		from_offset															
+	fn from_offset(offset: &MockOffset) -> Self {
+		offset.zone.clone()
+	}
+	
+	//		offset_from_local_date												
+	fn offset_from_local_date(&self, local: &NaiveDate) -> MappedLocalTime<MockOffset> {
+		self.offset_from_local_datetime(&local.and_time(NaiveTime::MIN))
+	}
+	
+	//		offset_from_local_datetime											
+	fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> MappedLocalTime<MockOffset> {
+		let mut found = Vec::<(NaiveDateTime, &Period)>::new();
+		let periods   = iter::once(&self.rules.standard).chain(self.rules.transitions.iter().map(|transition| &transition.1));
+		for period in periods {
+			let Some(utc) = local.checked_sub_signed(TimeDelta::seconds(i64::from(period.offset.local_minus_utc()))) else {
+				continue;
+			};
+			if self.period_at(&utc) == period && !found.iter().any(|existing| existing.0 == utc) {
+				found.push((utc, period));
+			}
+		}
+		found.sort_by_key(|candidate| candidate.0);
+		match *found.as_slice() {
+			[]                                    => MappedLocalTime::None,
+			[(_, only)]                           => MappedLocalTime::Single(self.offset(only)),
+			[(_, earliest), .., (_, latest)]      => MappedLocalTime::Ambiguous(self.offset(earliest), self.offset(latest)),
+		}
+	}
+	
+	//		offset_from_utc_date												
+	fn offset_from_utc_date(&self, utc: &NaiveDate) -> MockOffset {
+		self.offset_from_utc_datetime(&utc.and_time(NaiveTime::MIN))
+	}
+	
+	//		offset_from_utc_datetime											
+	fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> MockOffset {
+		self.offset(self.period_at(utc))
+	}
+}
+
+//		Period																	
+/// A period during which a time zone uses a particular offset.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Period {
+	//		Private properties													
+	/// The abbreviation used during the period.
+	abbreviation: String,
+	
+	/// The offset from UTC during the period.
+	offset:       FixedOffset,
+}
+
+//󰭅		Period																	
+impl Period {
+	//		new																	
+	/// Creates a new period.
+	fn new(abbreviation: &str, offset: i32) -> Self {
+		Self {
+			abbreviation: abbreviation.to_owned(),
+			offset:       FixedOffset::east_opt(offset).expect("Invalid offset"),
+		}
+	}
+}
+
+//		Rules																	
+/// The definition of a time zone.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Rules {
+	//		Private properties													
+	/// The name of the zone.
+	name:        String,
+	
+	/// The period used before the first transition.
+	standard:    Period,
+	
+	/// The transitions of the zone, in order, with the UTC time at which each
+	/// one happens.
+	transitions: Vec<(NaiveDateTime, Period)>,
+}
+
+
+
+//		Functions
+
+//		days_since																
+/// Returns the number of days from the given start of the week to the given
+/// day.
+const fn days_since(day: Weekday, start: Weekday) -> u32 {
+	(day.num_days_from_monday() + 7 - start.num_days_from_monday()) % 7
+}
+
+//		first_week_start														
+/// Returns the date on which the first week of the given year starts.
+fn first_week_start(year: i32, first_day: Weekday, minimal_days: u32) -> NaiveDate {
+	let new_year = NaiveDate::from_ymd_opt(year, 1, 1).unwrap_or_default();
+	let offset   = days_since(new_year.weekday(), first_day);
+	let start    = new_year - Days::new(u64::from(offset));
+	if 7 - offset >= minimal_days { start } else { start + Days::new(7) }
+}
+
+//		last_weekday_of_month													
+/// Returns the date of the last given day of the week in a month.
+fn last_weekday_of_month(year: i32, month: u32, day: Weekday) -> Option<NaiveDate> {
+	let next_month = if month == 12 { NaiveDate::from_ymd_opt(year + 1, 1, 1) } else { NaiveDate::from_ymd_opt(year, month + 1, 1) };
+	let last       = next_month?.pred_opt()?;
+	last.checked_sub_days(Days::new(u64::from(days_since(last.weekday(), day))))
+}
@@ -0,0 +1,270 @@
+//! This module mocks the [`log`] crate's logger in order to test logging.
+//! 
+//! This module provides a test logger that captures the log records emitted
+//! through the [`log`] crate's macros, including their structured key-value
+//! pairs, so that tests can assert that the expected messages were logged, and
+//! that unexpected ones were not. This is important because logging is often
+//! the only observable outcome of certain code paths, such as retries and
+//! fallbacks, and those still need to be tested.
+//! 
+//! The approach taken is that a single global logger is installed the first
+//! time that [`MockLogs::capture()`] is called, and that logger forwards each
+//! record to the capture that is active on the current thread. As `cargo test`
+//! runs each test on its own thread, this keeps the records of tests that run
+//! in parallel isolated from each other. Records emitted on other threads, or
+//! when no capture is active, are discarded.
+//! 
+//! Notably, as only one global logger can ever be installed, this cannot be
+//! combined with another logger, such as [`env_logger`](https://crates.io/crates/env_logger),
+//! in the same test binary.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use log::{Level, info, warn};
+//! use sham::log::MockLogs;
+//! 
+//! let logs = MockLogs::capture();
+//! warn!(attempt = 2; "Request failed, retrying");
+//! info!(target: "app::db", "Connected");
+//! 
+//! logs.assert_contains(Level::Warn, "retrying");
+//! logs.assert_not_contains(Level::Error, "retrying");
+//! assert_eq!(logs.records()[0].kv, vec![("attempt".to_owned(), "2".to_owned())]);
+//! assert_eq!(logs.records()[1].target, "app::db");
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::scope::{LOG_CAPTURE, ScopeGuard};
+use core::fmt::Write;
+use log::{
+	Level,
+	LevelFilter,
+	Log,
+	Metadata,
+	Record,
+	kv::{Error as KvError, Key, Value, VisitSource},
+};
+use std::sync::{Arc, Mutex, Once, PoisonError};
+
+
+
+//		Statics
+
+/// The global logger, which forwards records to the current thread's capture.
+static LOGGER: CaptureLogger = CaptureLogger;
+
+/// Ensures that the global logger is only installed once.
+static INSTALL: Once = Once::new();
+
+
+
+//		Structs
+
+//		CaptureLogger															
+/// The global logger, which forwards records to the current thread's capture.
+#[derive(Debug)]
+struct CaptureLogger;
+
+//󰭅		Log																		
+impl Log for CaptureLogger {
+	//		enabled																
+	fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+		LOG_CAPTURE.current().is_some()
+	}
+	
+	//		log																	
+	fn log(&self, record: &Record<'_>) {
+		if let Some(records) = LOG_CAPTURE.current() {
+			let mut visitor = KvCollector::default();
+			_ = record.key_values().visit(&mut visitor);
+			records.lock().unwrap_or_else(PoisonError::into_inner).push(MockLogRecord {
+				level:   record.level(),
+				target:  record.target().to_owned(),
+				message: record.args().to_string(),
+				kv:      visitor.pairs,
+			});
+		}
+	}
+	
+	//		flush																
+	fn flush(&self) {}
+}
+
+//		KvCollector																
+/// Collects the key-value pairs of a log record as strings.
+#[derive(Debug, Default)]
+struct KvCollector {
+	/// The collected key-value pairs, in the order they were visited.
+	pairs: Vec<(String, String)>,
+}
+
+//󰭅		VisitSource																
+impl<'kvs> VisitSource<'kvs> for KvCollector {
+	//		visit_pair															
+	fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), KvError> {
+		let mut formatted = String::new();
+		_ = write!(formatted, "{value}");
+		self.pairs.push((key.as_str().to_owned(), formatted));
+		Ok(())
+	}
+}
+
+//		MockLogRecord															
+/// A captured log record.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockLogRecord {
+	//		Public properties													
+	/// The level of the record.
+	pub level:   Level,
+	
+	/// The target of the record, which defaults to the module path.
+	pub target:  String,
+	
+	/// The formatted message of the record.
+	pub message: String,
+	
+	/// The structured key-value pairs of the record, with the values formatted
+	/// as strings, in the order they were given.
+	pub kv:      Vec<(String, String)>,
+}
+
+//		MockLogs																
+/// A capture of the log records emitted on the current thread.
+/// 
+/// Records are captured from when [`capture()`](MockLogs::capture()) is called
+/// until the capture is dropped. Captures can be nested, in which case the
+/// innermost one receives the records, and the outer one resumes when the inner
+/// one is dropped.
+/// 
+#[derive(Debug)]
+pub struct MockLogs {
+	//		Private properties													
+	/// The captured records.
+	records: Arc<Mutex<Vec<MockLogRecord>>>,
+	
+	/// The entry of the capture on the current thread, which is removed when
+	/// the capture is dropped, so that any capture that it is nested in
+	/// resumes.
+	_scope:  ScopeGuard<Arc<Mutex<Vec<MockLogRecord>>>>,
+}
+
+//󰭅		MockLogs																
+impl MockLogs {
+	//		capture																
+	/// Starts capturing the log records emitted on the current thread.
+	/// 
+	/// The global logger is installed the first time this is called, and the
+	/// maximum log level is set to [`Trace`](LevelFilter::Trace).
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if a different global logger has already been
+	/// installed.
+	/// 
+	#[must_use]
+	pub fn capture() -> Self {
+		INSTALL.call_once(|| {
+			log::set_logger(&LOGGER).expect("Another global logger has already been installed");
+			log::set_max_level(LevelFilter::Trace);
+		});
+		let records  = Arc::new(Mutex::new(Vec::new()));
+		let scope    = LOG_CAPTURE.enter(Arc::clone(&records));
+		Self { records, _scope: scope }
+	}
+	
+	//		assert_contains														
+	/// Asserts that a record was captured at the given level, with a message
+	/// containing the given text.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `level` - The level of the expected record.
+	/// * `text`  - The text that the message of the record should contain.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if no matching record was captured, listing
+	/// the records that were.
+	/// 
+	pub fn assert_contains(&self, level: Level, text: &str) {
+		assert!(
+			self.count(level, text) > 0,
+			"Expected a {level} log record containing {text:?}, but found:\n{}",
+			self.describe(),
+		);
+	}
+	
+	//		assert_not_contains													
+	/// Asserts that no record was captured at the given level with a message
+	/// containing the given text.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `level` - The level of the unexpected record.
+	/// * `text`  - The text that the message of the record should not contain.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if a matching record was captured, listing the
+	/// records that were.
+	/// 
+	pub fn assert_not_contains(&self, level: Level, text: &str) {
+		assert!(
+			self.count(level, text) == 0,
+			"Expected no {level} log record containing {text:?}, but found:\n{}",
+			self.describe(),
+		);
+	}
+	
+	//		clear																
+	/// Discards all of the records captured so far.
+	pub fn clear(&self) {
+		self.records.lock().unwrap_or_else(PoisonError::into_inner).clear();
+	}
+	
+	//		count																
+	/// Returns the number of records captured at the given level with a
+	/// message containing the given text.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `level` - The level of the records to count.
+	/// * `text`  - The text that the messages of the records should contain.
+	/// 
+	#[must_use]
+	pub fn count(&self, level: Level, text: &str) -> usize {
+		self.records
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner)
+			.iter()
+			.filter(|record| record.level == level && record.message.contains(text))
+			.count()
+	}
+	
+	//		records																
+	/// Returns a copy of the records captured so far, in order.
+	#[must_use]
+	pub fn records(&self) -> Vec<MockLogRecord> {
+		self.records.lock().unwrap_or_else(PoisonError::into_inner).clone()
+	}
+	
+	//		describe															
+	/// Describes the captured records, one per line, for use in assertion
+	/// messages.
+	fn describe(&self) -> String {
+		let records = self.records();
+		if records.is_empty() {
+			return "  (no records)".to_owned();
+		}
+		records.iter().fold(String::new(), |mut output, record| {
+			_ = writeln!(output, "  [{}] {}: {}", record.level, record.target, record.message);
+			output
+		})
+	}
+}
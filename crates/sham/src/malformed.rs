@@ -0,0 +1,428 @@
+//! This module provides malformed HTTP responses in order to test parsers.
+//! 
+//! This module provides functions that create deliberately broken responses for
+//! the [`reqwest`](crate::reqwest) mock, such as truncated JSON, a wrong
+//! `Content-Length`, invalid UTF-8, enormous headers, and chunked bodies that
+//! never finish, along with a [`Malformation`] enum that lists them all. There
+//! are also functions for tricky headers, such as duplicate `Set-Cookie`
+//! headers, values folded together with commas or by obsolete line folding,
+//! and values that are not valid UTF-8. This
+//! is important because real servers, proxies, and networks do produce broken
+//! responses, and the code that parses them should fail cleanly rather than
+//! panic or hang, which is hard to check without a separate fuzzing harness.
+//! 
+//! The approach taken is that each function returns an ordinary
+//! [`MockResponse`], with a `200 OK` status, which can be returned from the
+//! responders of [`MockRoute`](crate::reqwest::MockRoute)s or from the mocked
+//! senders in the same way as any other response, and whose public fields can
+//! be changed afterwards. The mocked response behaves as the real one would:
+//! decoding a broken body as JSON returns a decode error, invalid UTF-8 is
+//! replaced when reading the body as text, and reading a body that has
+//! [`stalled`](MockResponse::stalled) never completes, so that timeouts can be
+//! tested. [`Malformation::all()`] allows every kind of breakage to be applied
+//! to the same payload in a single test. The tricky headers are limited to
+//! what a [`HeaderMap`](reqwest::header::HeaderMap) can represent, so a line
+//! fold is given as a lenient parser would unfold it, as a space followed by
+//! the indentation of the continuation line, rather than as a raw line break.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use futures_executor::block_on;
+//! use futures_util::{FutureExt, StreamExt};
+//! use serde_json::{Value, json};
+//! use reqwest::header::SET_COOKIE;
+//! use sham::malformed::{Malformation, duplicate_headers, invalid_utf8, invalid_utf8_header, truncated_json, unterminated_chunked};
+//! 
+//! let url      = "https://api.example.com/config";
+//! let response = truncated_json(url, &json!({ "name": "app", "replicas": 3 }));
+//! assert!(block_on(response.json::<Value>()).unwrap_err().is_decode());
+//! 
+//! let response = invalid_utf8(url, b"caf\xC3\xA9");
+//! assert!(block_on(response.text()).unwrap().contains('\u{FFFD}'));
+//! 
+//! let response   = unterminated_chunked(url, b"partial");
+//! let mut chunks = response.bytes_stream();
+//! assert_eq!(block_on(chunks.next()).unwrap().unwrap(), "partial");
+//! assert!(chunks.next().now_or_never().is_none());
+//! 
+//! let response = duplicate_headers(url, b"", "set-cookie", &["a=1", "b=2"]);
+//! assert_eq!(response.headers.get_all(SET_COOKIE).iter().count(), 2);
+//! 
+//! let response = invalid_utf8_header(url, b"", "x-name", "café");
+//! assert!(response.headers["x-name"].to_str().is_err());
+//! 
+//! for malformation in Malformation::all() {
+//!     let response = malformation.response(url, &json!({ "name": "app" }));
+//!     if !response.stalled {
+//!         let _ = block_on(response.json::<Value>());
+//!     }
+//! }
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::reqwest::{MockResponse, create_mock_response};
+use core::fmt::{Display, Formatter, self};
+use reqwest::{
+	IntoUrl,
+	StatusCode,
+	header::{HeaderName, HeaderValue, CONTENT_LENGTH, CONTENT_TYPE, TRANSFER_ENCODING},
+};
+use serde::Serialize;
+use serde_json::to_vec as to_json_vec;
+use std::collections::HashMap;
+
+
+
+//		Constants
+
+/// The total size of the headers added by [`Malformation::EnormousHeaders`],
+/// which is far more than servers and proxies usually accept.
+pub const ENORMOUS_HEADERS_SIZE: usize = 1024 * 1024;
+
+/// The size of the value of each header added by [`enormous_headers()`].
+const HEADER_CHUNK_SIZE: usize = 8 * 1024;
+
+/// The bytes inserted by [`invalid_utf8()`] and [`invalid_utf8_header()`],
+/// which are a lead byte without its continuation byte, followed by a byte that
+/// never appears in UTF-8.
+const INVALID_UTF8: [u8; 3] = [0xC3, 0x28, 0xFF];
+
+/// What an obsolete line fold becomes in [`line_folded_header()`], which is the
+/// space that a lenient parser puts in place of the line break, followed by
+/// the tab that indents the continuation line.
+const LINE_FOLD: &str = " \t";
+
+
+
+//		Enums
+
+//		Malformation															
+/// A way in which a response can be malformed.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Malformation {
+	/// The headers are far larger than servers and proxies usually accept. See
+	/// [`enormous_headers()`].
+	EnormousHeaders,
+	
+	/// The body contains invalid UTF-8. See [`invalid_utf8()`].
+	InvalidUtf8,
+	
+	/// The JSON body is cut off part-way through. See [`truncated_json()`].
+	TruncatedJson,
+	
+	/// The chunked body never finishes. See [`unterminated_chunked()`].
+	UnterminatedChunked,
+	
+	/// The `Content-Length` states more than the body contains. See
+	/// [`wrong_content_length()`].
+	WrongContentLength,
+}
+
+//󰭅		Malformation															
+impl Malformation {
+	//		all																	
+	/// Returns all of the kinds of malformation.
+	#[must_use]
+	pub const fn all() -> [Self; 5] {
+		[
+			Self::EnormousHeaders,
+			Self::InvalidUtf8,
+			Self::TruncatedJson,
+			Self::UnterminatedChunked,
+			Self::WrongContentLength,
+		]
+	}
+	
+	//		response															
+	/// Creates a response that carries the given value as JSON, malformed in
+	/// this way.
+	/// 
+	/// The response has a `Content-Type` of `application/json`, so that the
+	/// malformation is the only thing wrong with it.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `url`   - The URL of the response.
+	/// * `value` - The value to encode as the JSON body.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the URL is not valid, or if the value cannot
+	/// be encoded as JSON.
+	/// 
+	pub fn response<U: IntoUrl, T: Serialize>(self, url: U, value: &T) -> MockResponse {
+		let body         = to_json_vec(value).expect("Value cannot be encoded as JSON");
+		let mut response = match self {
+			Self::EnormousHeaders     => enormous_headers(url, &body, ENORMOUS_HEADERS_SIZE),
+			Self::InvalidUtf8         => invalid_utf8(url, &body),
+			Self::TruncatedJson       => truncated_json(url, value),
+			Self::UnterminatedChunked => unterminated_chunked(url, &body),
+			Self::WrongContentLength  => wrong_content_length(url, &body, body.len().saturating_mul(2).saturating_add(1)),
+		};
+		drop(response.headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json")));
+		response
+	}
+}
+
+//󰭅		Display																	
+impl Display for Malformation {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let description = match *self {
+			Self::EnormousHeaders     => "enormous headers",
+			Self::InvalidUtf8         => "invalid UTF-8",
+			Self::TruncatedJson       => "truncated JSON",
+			Self::UnterminatedChunked => "unterminated chunked body",
+			Self::WrongContentLength  => "wrong content length",
+		};
+		write!(f, "{description}")
+	}
+}
+
+
+
+//		Functions
+
+//		duplicate_headers														
+/// Creates a response with the given header repeated, once for each value.
+/// 
+/// This is how a server sends several cookies, as `Set-Cookie` headers cannot
+/// be combined, but it can also be used for headers that should only appear
+/// once, such as two conflicting `Content-Type` headers, to check which one
+/// the code under test believes.
+/// 
+/// # Parameters
+/// 
+/// * `url`    - The URL of the response.
+/// * `body`   - The body of the response.
+/// * `name`   - The name of the header, such as `set-cookie`.
+/// * `values` - The values of the header, in the order that they are sent.
+/// 
+/// # Panics
+/// 
+/// This function will panic if the URL is not valid, or if the name or any of
+/// the values is not valid in a header.
+/// 
+pub fn duplicate_headers<U: IntoUrl>(url: U, body: &[u8], name: &str, values: &[&str]) -> MockResponse {
+	let mut response = plain_response(url, body);
+	let header_name  = HeaderName::try_from(name).expect("Invalid header name");
+	for value in values {
+		_ = response.headers.append(&header_name, HeaderValue::from_str(value).expect("Invalid header value"));
+	}
+	response
+}
+
+//		enormous_headers														
+/// Creates a response with headers of the given total size.
+/// 
+/// The headers are named `x-padding-1`, `x-padding-2`, and so on, each with a
+/// value of up to 8 KiB, which is the limit of many servers for a single
+/// header.
+/// 
+/// # Parameters
+/// 
+/// * `url`  - The URL of the response.
+/// * `body` - The body of the response.
+/// * `size` - The total size of the values of the headers to add, such as
+///   [`ENORMOUS_HEADERS_SIZE`].
+/// 
+/// # Panics
+/// 
+/// This function will panic if the URL is not valid.
+/// 
+pub fn enormous_headers<U: IntoUrl>(url: U, body: &[u8], size: usize) -> MockResponse {
+	let mut response  = plain_response(url, body);
+	let mut remaining = size;
+	let mut index     = 0_usize;
+	while remaining > 0 {
+		let length = remaining.min(HEADER_CHUNK_SIZE);
+		index      = index.saturating_add(1);
+		remaining  = remaining.saturating_sub(length);
+		let name   = HeaderName::try_from(format!("x-padding-{index}")).expect("Invalid header name");
+		drop(response.headers.insert(name, HeaderValue::from_str(&"a".repeat(length)).expect("Invalid header value")));
+	}
+	response
+}
+
+//		folded_header															
+/// Creates a response with the given values folded into a single header,
+/// separated by commas.
+/// 
+/// This is what proxies are allowed to do with most repeated headers, but it
+/// is ambiguous for values that contain commas themselves, such as `Set-Cookie`
+/// headers with an `Expires` date, which code that splits on commas will get
+/// wrong.
+/// 
+/// # Parameters
+/// 
+/// * `url`    - The URL of the response.
+/// * `body`   - The body of the response.
+/// * `name`   - The name of the header, such as `set-cookie`.
+/// * `values` - The values to fold together.
+/// 
+/// # Panics
+/// 
+/// This function will panic if the URL is not valid, or if the name or the
+/// folded value is not valid in a header.
+/// 
+pub fn folded_header<U: IntoUrl>(url: U, body: &[u8], name: &str, values: &[&str]) -> MockResponse {
+	let mut response = plain_response(url, body);
+	let header_name  = HeaderName::try_from(name).expect("Invalid header name");
+	drop(response.headers.insert(header_name, HeaderValue::from_str(&values.join(", ")).expect("Invalid header value")));
+	response
+}
+
+//		invalid_utf8															
+/// Creates a response whose body has invalid UTF-8 inserted into the middle.
+/// 
+/// # Parameters
+/// 
+/// * `url`  - The URL of the response.
+/// * `body` - The body of the response, before the invalid bytes are inserted.
+/// 
+/// # Panics
+/// 
+/// This function will panic if the URL is not valid.
+/// 
+pub fn invalid_utf8<U: IntoUrl>(url: U, body: &[u8]) -> MockResponse {
+	let (head, tail) = body.split_at(body.len().div_euclid(2));
+	plain_response(url, &[head, &INVALID_UTF8, tail].concat())
+}
+
+//		invalid_utf8_header														
+/// Creates a response with a header whose value has invalid UTF-8 inserted
+/// into the middle.
+/// 
+/// Header values are allowed to contain bytes outside of ASCII, so the header
+/// is delivered, but converting its value to a string fails.
+/// 
+/// # Parameters
+/// 
+/// * `url`   - The URL of the response.
+/// * `body`  - The body of the response.
+/// * `name`  - The name of the header.
+/// * `value` - The value of the header, before the invalid bytes are inserted.
+/// 
+/// # Panics
+/// 
+/// This function will panic if the URL is not valid, or if the name or the
+/// value is not valid in a header.
+/// 
+pub fn invalid_utf8_header<U: IntoUrl>(url: U, body: &[u8], name: &str, value: &str) -> MockResponse {
+	let mut response = plain_response(url, body);
+	let header_name  = HeaderName::try_from(name).expect("Invalid header name");
+	let (head, tail) = value.as_bytes().split_at(value.len().div_euclid(2));
+	let bytes        = [head, &INVALID_UTF8, tail].concat();
+	drop(response.headers.insert(header_name, HeaderValue::from_bytes(&bytes).expect("Invalid header value")));
+	response
+}
+
+//		line_folded_header														
+/// Creates a response with a header whose value was spread over several lines
+/// using obsolete line folding.
+/// 
+/// Line folding was removed from HTTP/1.1 by RFC 7230, and a
+/// [`HeaderMap`](reqwest::header::HeaderMap) cannot hold line breaks, so the
+/// value is given as a lenient parser would unfold it, with each line break
+/// replaced by a space and the tab that indented the next line kept. Code that
+/// trims only the ends of values, or that splits on whitespace, will see the
+/// folds.
+/// 
+/// # Parameters
+/// 
+/// * `url`   - The URL of the response.
+/// * `body`  - The body of the response.
+/// * `name`  - The name of the header.
+/// * `lines` - The lines of the value, before they are folded together.
+/// 
+/// # Panics
+/// 
+/// This function will panic if the URL is not valid, or if the name or any of
+/// the lines is not valid in a header.
+/// 
+pub fn line_folded_header<U: IntoUrl>(url: U, body: &[u8], name: &str, lines: &[&str]) -> MockResponse {
+	let mut response = plain_response(url, body);
+	let header_name  = HeaderName::try_from(name).expect("Invalid header name");
+	drop(response.headers.insert(header_name, HeaderValue::from_str(&lines.join(LINE_FOLD)).expect("Invalid header value")));
+	response
+}
+
+//		truncated_json															
+/// Creates a response whose body is the given value as JSON, cut off half-way
+/// through.
+/// 
+/// The `Content-Length` matches the truncated body, so that only the JSON is
+/// broken, as happens when a server fails part-way through writing it.
+/// 
+/// # Parameters
+/// 
+/// * `url`   - The URL of the response.
+/// * `value` - The value to encode as JSON.
+/// 
+/// # Panics
+/// 
+/// This function will panic if the URL is not valid, or if the value cannot be
+/// encoded as JSON.
+/// 
+pub fn truncated_json<U: IntoUrl, T: Serialize>(url: U, value: &T) -> MockResponse {
+	let encoded = to_json_vec(value).expect("Value cannot be encoded as JSON");
+	let body    = encoded.get(..encoded.len().div_euclid(2)).unwrap_or_default();
+	create_mock_response(url, StatusCode::OK, Some("application/json"), Some(body.len()), HashMap::<String, String>::new(), Ok(body))
+}
+
+//		unterminated_chunked													
+/// Creates a response with a chunked body that never finishes.
+/// 
+/// The body is sent, but the final chunk never arrives, so reading the whole
+/// body never completes, and the stream of the body yields the body and then
+/// waits forever.
+/// 
+/// # Parameters
+/// 
+/// * `url`  - The URL of the response.
+/// * `body` - The part of the body that is sent.
+/// 
+/// # Panics
+/// 
+/// This function will panic if the URL is not valid.
+/// 
+pub fn unterminated_chunked<U: IntoUrl>(url: U, body: &[u8]) -> MockResponse {
+	let mut response = plain_response(url, body);
+	drop(response.headers.insert(TRANSFER_ENCODING, HeaderValue::from_static("chunked")));
+	response.stalled = true;
+	response
+}
+
+//		wrong_content_length													
+/// Creates a response whose `Content-Length` does not match its body.
+/// 
+/// The body is delivered as given, so that code which checks the length of the
+/// body against the header can detect the mismatch.
+/// 
+/// # Parameters
+/// 
+/// * `url`    - The URL of the response.
+/// * `body`   - The body of the response.
+/// * `stated` - The length to state in the `Content-Length` header.
+/// 
+/// # Panics
+/// 
+/// This function will panic if the URL is not valid.
+/// 
+pub fn wrong_content_length<U: IntoUrl>(url: U, body: &[u8], stated: usize) -> MockResponse {
+	let mut response = plain_response(url, body);
+	drop(response.headers.insert(CONTENT_LENGTH, HeaderValue::from(stated)));
+	response
+}
+
+//		plain_response															
+/// Creates a `200 OK` response with the given body and no headers.
+fn plain_response<U: IntoUrl>(url: U, body: &[u8]) -> MockResponse {
+	create_mock_response(url, StatusCode::OK, None::<&str>, None, HashMap::<String, String>::new(), Ok(body))
+}
@@ -0,0 +1,301 @@
+//! This module mocks a [`metrics`] recorder in order to test instrumentation.
+//! 
+//! This module provides a test recorder that accumulates the counters, gauges,
+//! and histograms emitted through the [`metrics`] crate's macros, keyed by name
+//! and labels, so that tests can assert that the expected measurements were
+//! made. This is important because metrics are frequently relied upon for
+//! alerting and capacity planning, and yet are rarely tested, meaning that a
+//! renamed metric or a missing label can go unnoticed until it is needed.
+//! 
+//! The approach taken is that a [`MockMetrics`] instance is installed as the
+//! thread-local recorder for as long as the guard returned by
+//! [`install()`](MockMetrics::install()) is held. As `cargo test` runs each
+//! test on its own thread, this keeps the metrics of tests that run in parallel
+//! isolated from each other, and does not interfere with any global recorder.
+//! Metrics emitted on other threads are not captured.
+//! 
+//! Labels are matched regardless of their order, so a metric registered with
+//! `("method", "GET"), ("code", "500")` can be queried with the labels in
+//! either order. Metrics that have never been registered read as zero, or as
+//! empty, as appropriate.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use metrics::{counter, gauge, histogram};
+//! use sham::metrics::MockMetrics;
+//! 
+//! let metrics = MockMetrics::new();
+//! let _guard  = metrics.install();
+//! 
+//! counter!("http_requests_total", "code" => "500").increment(1);
+//! counter!("http_requests_total", "code" => "500").increment(1);
+//! counter!("http_requests_total", "code" => "200").increment(1);
+//! gauge!("connections").set(4.0);
+//! histogram!("latency_seconds").record(0.25);
+//! 
+//! assert_eq!(metrics.counter("http_requests_total", &[("code", "500")]), 2);
+//! assert_eq!(metrics.counter("http_requests_total", &[("code", "404")]), 0);
+//! assert_eq!(metrics.gauge("connections", &[]), Some(4.0));
+//! assert_eq!(metrics.histogram("latency_seconds", &[]), vec![0.25]);
+//! ```
+//! 
+
+
+
+//		Packages
+
+use core::sync::atomic::{AtomicU64, Ordering};
+use metrics::{
+	Counter,
+	Gauge,
+	Histogram,
+	HistogramFn,
+	Key,
+	KeyName,
+	LocalRecorderGuard,
+	Metadata,
+	Recorder,
+	SharedString,
+	Unit,
+};
+use std::{
+	collections::BTreeMap,
+	sync::{Arc, Mutex, PoisonError},
+};
+
+
+
+//		Types
+
+/// The identity of a metric, being its name and its labels sorted by key.
+type MetricKey = (String, Vec<(String, String)>);
+
+
+
+//		Structs
+
+//		MetricsState															
+/// The metrics accumulated by a [`MockMetrics`] instance.
+#[derive(Debug, Default)]
+struct MetricsState {
+	/// The registered counters.
+	counters:     BTreeMap<MetricKey, Arc<AtomicU64>>,
+	
+	/// The descriptions given for metrics, by name.
+	descriptions: BTreeMap<String, MockMetricDescription>,
+	
+	/// The registered gauges, with their values stored as [`f64`] bits.
+	gauges:       BTreeMap<MetricKey, Arc<AtomicU64>>,
+	
+	/// The registered histograms.
+	histograms:   BTreeMap<MetricKey, Arc<MockHistogram>>,
+}
+
+//		MockHistogram															
+/// A histogram that keeps every recorded value.
+#[derive(Debug, Default)]
+struct MockHistogram {
+	/// The recorded values, in the order they were recorded.
+	values: Mutex<Vec<f64>>,
+}
+
+//󰭅		HistogramFn																
+impl HistogramFn for MockHistogram {
+	//		record																
+	fn record(&self, value: f64) {
+		self.values.lock().unwrap_or_else(PoisonError::into_inner).push(value);
+	}
+}
+
+//		MockMetricDescription													
+/// The description given for a metric.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockMetricDescription {
+	//		Public properties													
+	/// The unit of the metric, if one was given.
+	pub unit:        Option<Unit>,
+	
+	/// The description of the metric.
+	pub description: String,
+}
+
+//		MockMetrics																
+/// A recorder that accumulates the metrics emitted on the current thread.
+/// 
+/// Clones share the same accumulated metrics, so a clone can be handed to code
+/// under test while the original is used to make assertions.
+/// 
+#[derive(Clone, Debug, Default)]
+pub struct MockMetrics {
+	//		Private properties													
+	/// The accumulated metrics.
+	state: Arc<Mutex<MetricsState>>,
+}
+
+//󰭅		MockMetrics																
+impl MockMetrics {
+	//		new																	
+	/// Creates a new recorder with no metrics.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+	
+	//		install																
+	/// Installs this recorder as the recorder for the current thread.
+	/// 
+	/// Metrics emitted on the current thread are accumulated by this recorder
+	/// until the returned guard is dropped, at which point the previously
+	/// installed recorder, if any, is restored.
+	/// 
+	#[must_use]
+	pub fn install(&self) -> LocalRecorderGuard<'_> {
+		metrics::set_default_local_recorder(self)
+	}
+	
+	//		clear																
+	/// Discards all of the metrics accumulated so far.
+	/// 
+	/// Handles that have already been registered by the code under test are
+	/// detached, and so anything recorded through them afterwards is ignored.
+	/// 
+	pub fn clear(&self) {
+		*self.state.lock().unwrap_or_else(PoisonError::into_inner) = MetricsState::default();
+	}
+	
+	//		counter																
+	/// Returns the value of a counter, or zero if it has not been registered.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name`   - The name of the counter.
+	/// * `labels` - The labels of the counter, in any order.
+	/// 
+	#[must_use]
+	pub fn counter(&self, name: &str, labels: &[(&str, &str)]) -> u64 {
+		self.state
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner)
+			.counters
+			.get(&metric_key(name, labels))
+			.map_or(0, |counter| counter.load(Ordering::Acquire))
+	}
+	
+	//		description															
+	/// Returns the description given for a metric, if any.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the metric.
+	/// 
+	#[must_use]
+	pub fn description(&self, name: &str) -> Option<MockMetricDescription> {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner).descriptions.get(name).cloned()
+	}
+	
+	//		gauge																
+	/// Returns the value of a gauge, or [`None`] if it has not been registered.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name`   - The name of the gauge.
+	/// * `labels` - The labels of the gauge, in any order.
+	/// 
+	#[must_use]
+	pub fn gauge(&self, name: &str, labels: &[(&str, &str)]) -> Option<f64> {
+		self.state
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner)
+			.gauges
+			.get(&metric_key(name, labels))
+			.map(|gauge| f64::from_bits(gauge.load(Ordering::Acquire)))
+	}
+	
+	//		histogram															
+	/// Returns the values recorded by a histogram, in order, or an empty list
+	/// if it has not been registered.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name`   - The name of the histogram.
+	/// * `labels` - The labels of the histogram, in any order.
+	/// 
+	#[must_use]
+	pub fn histogram(&self, name: &str, labels: &[(&str, &str)]) -> Vec<f64> {
+		self.state
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner)
+			.histograms
+			.get(&metric_key(name, labels))
+			.map(|histogram| histogram.values.lock().unwrap_or_else(PoisonError::into_inner).clone())
+			.unwrap_or_default()
+	}
+	
+	//		describe															
+	/// Records the description given for a metric.
+	fn describe(&self, key: &KeyName, unit: Option<Unit>, description: &SharedString) {
+		let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+		drop(state.descriptions.insert(
+			key.as_str().to_owned(),
+			MockMetricDescription { unit, description: description.to_string() },
+		));
+	}
+}
+
+//󰭅		Recorder																
+impl Recorder for MockMetrics {
+	//		describe_counter													
+	fn describe_counter(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+		self.describe(&key, unit, &description);
+	}
+	
+	//		describe_gauge														
+	fn describe_gauge(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+		self.describe(&key, unit, &description);
+	}
+	
+	//		describe_histogram													
+	fn describe_histogram(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+		self.describe(&key, unit, &description);
+	}
+	
+	//		register_counter													
+	fn register_counter(&self, key: &Key, _metadata: &Metadata<'_>) -> Counter {
+		let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+		Counter::from_arc(Arc::clone(state.counters.entry(key_of(key)).or_default()))
+	}
+	
+	//		register_gauge														
+	fn register_gauge(&self, key: &Key, _metadata: &Metadata<'_>) -> Gauge {
+		let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+		Gauge::from_arc(Arc::clone(state.gauges.entry(key_of(key)).or_default()))
+	}
+	
+	//		register_histogram													
+	fn register_histogram(&self, key: &Key, _metadata: &Metadata<'_>) -> Histogram {
+		let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+		Histogram::from_arc(Arc::clone(state.histograms.entry(key_of(key)).or_default()))
+	}
+}
+
+
+
+//		Functions
+
+//		key_of																	
+/// Converts a [`metrics`] key into the identity of a metric.
+fn key_of(key: &Key) -> MetricKey {
+	let mut labels = key.labels().map(|label| (label.key().to_owned(), label.value().to_owned())).collect::<Vec<_>>();
+	labels.sort();
+	(key.name().to_owned(), labels)
+}
+
+//		metric_key																
+/// Builds the identity of a metric from a name and labels given in any order.
+fn metric_key(name: &str, labels: &[(&str, &str)]) -> MetricKey {
+	let mut sorted = labels.iter().map(|label| (label.0.to_owned(), label.1.to_owned())).collect::<Vec<_>>();
+	sorted.sort();
+	(name.to_owned(), sorted)
+}
@@ -0,0 +1,352 @@
+//! This module mocks network interface enumeration in order to test its users.
+//! 
+//! This module provides scripted network interfaces, with their addresses,
+//! flags, and MTUs, in the style of `getifaddrs()` and of the [`if-addrs`](https://crates.io/crates/if-addrs)
+//! and [`network-interface`](https://crates.io/crates/network-interface)
+//! crates. This is important because code that picks an address to bind to, or
+//! that detects whether the machine is connected to a VPN, otherwise depends
+//! upon the interfaces of whichever machine happens to run the tests, which
+//! differ between developer workstations and CI runners.
+//! 
+//! The approach taken is that the "real" code should enumerate the interfaces
+//! through a provider that it is given, and the test code then supplies a
+//! [`MockNetwork`] holding the interfaces for the scenario being tested. Some
+//! common scenarios are provided as constructors.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use sham::network::{MockInterface, MockNetwork};
+//! 
+//! let network = MockNetwork::workstation()
+//!     .with_interface(MockInterface::new("tun0").with_address("10.8.0.2/32").with_mtu(1420).point_to_point())
+//! ;
+//! let interfaces = network.interfaces().unwrap();
+//! 
+//! assert_eq!(interfaces.len(), 3);
+//! assert!(interfaces.iter().any(|interface| interface.flags.point_to_point && interface.flags.up));
+//! assert_eq!(network.addresses().unwrap()[0].0, "lo");
+//! ```
+//! 
+
+
+
+//		Packages
+
+use core::{
+	error::Error,
+	fmt::{Display, Formatter, self},
+	net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
+
+
+
+//		Enums
+
+//		MockNetworkError														
+/// A mocked network enumeration error type.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockNetworkError {
+	/// The interfaces could not be enumerated due to a lack of permission.
+	PermissionDenied,
+	
+	/// The interfaces could not be enumerated for some other reason.
+	Other(String),
+}
+
+//󰭅		Display																	
+impl Display for MockNetworkError {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match *self {
+			Self::PermissionDenied   => write!(f, "Permission denied"),
+			Self::Other(ref message) => write!(f, "{message}"),
+		}
+	}
+}
+
+//󰭅		Error																	
+impl Error for MockNetworkError {}
+
+
+
+//		Structs
+
+//		MockInterface															
+/// A mocked network interface.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockInterface {
+	//		Public properties													
+	/// The name of the interface, e.g. `eth0`.
+	pub name:      String,
+	
+	/// The addresses assigned to the interface.
+	pub addresses: Vec<MockInterfaceAddress>,
+	
+	/// The flags of the interface.
+	pub flags:     MockInterfaceFlags,
+	
+	/// The hardware address of the interface, if it has one.
+	pub mac:       Option<[u8; 6]>,
+	
+	/// The maximum transmission unit of the interface.
+	pub mtu:       u32,
+}
+
+//󰭅		MockInterface															
+impl MockInterface {
+	//		new																	
+	/// Creates a new interface that is up and running, with an MTU of 1500, and
+	/// no addresses.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the interface.
+	/// 
+	#[must_use]
+	pub fn new(name: &str) -> Self {
+		Self {
+			name:      name.to_owned(),
+			addresses: Vec::new(),
+			flags:     MockInterfaceFlags { up: true, running: true, multicast: true, ..Default::default() },
+			mac:       None,
+			mtu:       1_500,
+		}
+	}
+	
+	//		down																
+	/// Marks the interface as down.
+	#[must_use]
+	pub const fn down(mut self) -> Self {
+		self.flags.up      = false;
+		self.flags.running = false;
+		self
+	}
+	
+	//		loopback															
+	/// Marks the interface as a loopback interface.
+	#[must_use]
+	pub const fn loopback(mut self) -> Self {
+		self.flags.loopback  = true;
+		self.flags.multicast = false;
+		self
+	}
+	
+	//		point_to_point														
+	/// Marks the interface as a point-to-point link, as used by VPN tunnels.
+	#[must_use]
+	pub const fn point_to_point(mut self) -> Self {
+		self.flags.point_to_point = true;
+		self.flags.multicast      = false;
+		self
+	}
+	
+	//		with_address														
+	/// Adds an address to the interface.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `cidr` - The address and prefix length, e.g. `192.168.1.10/24`. If
+	///   the prefix length is omitted, the address is treated as a host
+	///   address.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the address is invalid.
+	/// 
+	#[must_use]
+	pub fn with_address(mut self, cidr: &str) -> Self {
+		let (address, prefix) = cidr.split_once('/').unwrap_or((cidr, ""));
+		let ip                = address.parse::<IpAddr>().unwrap();
+		let prefix_len        = if prefix.is_empty() {
+			if ip.is_ipv4() { 32 } else { 128 }
+		} else {
+			prefix.parse().unwrap()
+		};
+		self.addresses.push(MockInterfaceAddress { ip, prefix_len });
+		self
+	}
+	
+	//		with_mac															
+	/// Sets the hardware address of the interface.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `mac` - The hardware address.
+	/// 
+	#[must_use]
+	pub const fn with_mac(mut self, mac: [u8; 6]) -> Self {
+		self.mac = Some(mac);
+		self
+	}
+	
+	//		with_mtu															
+	/// Sets the maximum transmission unit of the interface.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `mtu` - The maximum transmission unit.
+	/// 
+	#[must_use]
+	pub const fn with_mtu(mut self, mtu: u32) -> Self {
+		self.mtu = mtu;
+		self
+	}
+}
+
+//		MockInterfaceAddress													
+/// An address assigned to a [`MockInterface`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockInterfaceAddress {
+	//		Public properties													
+	/// The IP address.
+	pub ip:         IpAddr,
+	
+	/// The length of the network prefix, in bits.
+	pub prefix_len: u8,
+}
+
+//󰭅		MockInterfaceAddress													
+impl MockInterfaceAddress {
+	//		netmask																
+	/// Returns the netmask corresponding to the prefix length.
+	#[must_use]
+	pub fn netmask(&self) -> IpAddr {
+		match self.ip {
+			IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::from(u32::MAX.checked_shl(32_u32.saturating_sub(u32::from(self.prefix_len))).unwrap_or(0))),
+			IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::from(u128::MAX.checked_shl(128_u32.saturating_sub(u32::from(self.prefix_len))).unwrap_or(0))),
+		}
+	}
+}
+
+//		MockInterfaceFlags														
+/// The flags of a [`MockInterface`].
+#[expect(clippy::struct_excessive_bools, reason = "Mirrors the interface flags")]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockInterfaceFlags {
+	//		Public properties													
+	/// Whether the interface is a loopback interface.
+	pub loopback:       bool,
+	
+	/// Whether the interface supports multicast.
+	pub multicast:      bool,
+	
+	/// Whether the interface is a point-to-point link.
+	pub point_to_point: bool,
+	
+	/// Whether the interface has resources allocated and is operational.
+	pub running:        bool,
+	
+	/// Whether the interface has been brought up.
+	pub up:             bool,
+}
+
+//		MockNetwork																
+/// A mocked provider of network interfaces.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MockNetwork {
+	//		Private properties													
+	/// The error to return when the interfaces are enumerated, if any.
+	failure:    Option<MockNetworkError>,
+	
+	/// The interfaces, in the order they are enumerated.
+	interfaces: Vec<MockInterface>,
+}
+
+//󰭅		MockNetwork																
+impl MockNetwork {
+	//		new																	
+	/// Creates a new provider with no interfaces.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+	
+	//		loopback_only														
+	/// Creates a new provider with only the loopback interface, as found in an
+	/// isolated container.
+	#[must_use]
+	pub fn loopback_only() -> Self {
+		Self::new().with_interface(
+			MockInterface::new("lo").loopback().with_address("127.0.0.1/8").with_address("::1/128").with_mtu(1 << 16),
+		)
+	}
+	
+	//		workstation															
+	/// Creates a new provider with the loopback interface and an Ethernet
+	/// interface on a private IPv4 network with a link-local IPv6 address.
+	#[must_use]
+	pub fn workstation() -> Self {
+		Self::loopback_only().with_interface(
+			MockInterface::new("eth0")
+				.with_address("192.168.1.10/24")
+				.with_address("fe80::1/64")
+				.with_mac([0x02, 0x00, 0x00, 0x00, 0x00, 0x01])
+			,
+		)
+	}
+	
+	//		with_failure														
+	/// Makes enumerating the interfaces fail with the given error.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `error` - The error to return.
+	/// 
+	#[must_use]
+	pub fn with_failure(mut self, error: MockNetworkError) -> Self {
+		self.failure = Some(error);
+		self
+	}
+	
+	//		with_interface														
+	/// Adds an interface.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `interface` - The interface to add.
+	/// 
+	#[must_use]
+	pub fn with_interface(mut self, interface: MockInterface) -> Self {
+		self.interfaces.push(interface);
+		self
+	}
+	
+	//		addresses															
+	/// Returns each address along with the name of its interface, in the style
+	/// of `getifaddrs()`, in the order the interfaces were added.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if a failure has been set.
+	/// 
+	/// # See also
+	/// 
+	/// * `if_addrs::get_if_addrs()`
+	/// 
+	pub fn addresses(&self) -> Result<Vec<(String, MockInterfaceAddress)>, MockNetworkError> {
+		Ok(self.interfaces()?.into_iter().flat_map(|interface| {
+			let name = interface.name;
+			interface.addresses.into_iter().map(move |address| (name.clone(), address))
+		}).collect())
+	}
+	
+	//		interfaces															
+	/// Returns the interfaces, in the order they were added.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if a failure has been set.
+	/// 
+	/// # See also
+	/// 
+	/// * `network_interface::NetworkInterface::show()`
+	/// 
+	pub fn interfaces(&self) -> Result<Vec<MockInterface>, MockNetworkError> {
+		self.failure.as_ref().map_or_else(|| Ok(self.interfaces.clone()), |error| Err(error.clone()))
+	}
+}
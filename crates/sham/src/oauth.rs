@@ -0,0 +1,690 @@
+//! This module mocks an OAuth2/OIDC provider in order to test auth clients.
+//! 
+//! This module provides a set of routes for the [`reqwest`](crate::reqwest)
+//! mock that implement the token, introspection, JWKS, and discovery endpoints
+//! of an OAuth2/OIDC provider, so that clients can be tested through their
+//! full token lifecycle, including expiry and refresh, without standing up a
+//! real identity provider such as Keycloak. This is important because token
+//! refresh is notoriously hard to get right, and is otherwise only exercised
+//! in production, after the first token expires.
+//! 
+//! The approach taken is that a [`MockOAuthProvider`] holds the state of the
+//! provider, i.e. the tokens that it has issued and its signing keys, and its
+//! [`routes()`](MockOAuthProvider::routes()) serve requests against that
+//! state. Expiry is driven by a [`MockClock`], so tests can advance time past
+//! the lifetime of a token rather than waiting for it. The routes can be served
+//! on their own using [`client()`](MockOAuthProvider::client()), or combined
+//! with the routes for the API being called.
+//! 
+//! Access tokens are JWTs signed with HMAC-SHA256, and the signing keys are
+//! published by the JWKS endpoint as symmetric (`oct`) keys. Keys can be
+//! rotated and retired to test how clients handle key changes. Refresh tokens
+//! are opaque, and are rotated on use by default.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use core::time::Duration;
+//! use futures_executor::block_on;
+//! use serde_json::Value as Json;
+//! use sham::{clock::MockClock, oauth::MockOAuthProvider};
+//! 
+//! let clock    = MockClock::new();
+//! let provider = MockOAuthProvider::new("https://auth.example.com/", &clock)
+//!     .with_access_token_ttl(Duration::from_secs(300))
+//! ;
+//! let client   = provider.client();
+//! 
+//! block_on(async {
+//!     let tokens: Json = client.post(provider.token_url())
+//!         .form(&[("grant_type", "password"), ("client_id", "app"), ("username", "alice")])
+//!         .send().await.unwrap()
+//!         .json().await.unwrap()
+//!     ;
+//!     let access_token  = tokens["access_token"].as_str().unwrap();
+//!     let refresh_token = tokens["refresh_token"].as_str().unwrap();
+//! 
+//!     clock.advance(Duration::from_secs(301));
+//!     let introspection: Json = client.post(provider.introspection_url())
+//!         .form(&[("token", access_token)])
+//!         .send().await.unwrap()
+//!         .json().await.unwrap()
+//!     ;
+//!     assert_eq!(introspection["active"], false);
+//! 
+//!     let refreshed = client.post(provider.token_url())
+//!         .form(&[("grant_type", "refresh_token"), ("refresh_token", refresh_token)])
+//!         .send().await.unwrap()
+//!     ;
+//!     assert_eq!(refreshed.status(), 200);
+//! });
+//! 
+//! assert_eq!(provider.grants(), vec!["password", "refresh_token"]);
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::{
+	clock::MockClock,
+	reqwest::{
+		MockClient,
+		MockError,
+		MockRequest,
+		MockResponse,
+		MockRoute,
+		create_mock_response,
+		create_routed_mock_client,
+	},
+};
+use base64::{
+	Engine,
+	engine::general_purpose::{STANDARD as BASE64, URL_SAFE_NO_PAD as BASE64URL},
+};
+use core::time::Duration;
+use hmac::{Hmac, Mac};
+use reqwest::{
+	IntoUrl,
+	Method,
+	StatusCode,
+	Url,
+	header::AUTHORIZATION,
+};
+use serde_json::{Value as Json, json};
+use sha2::{Digest, Sha256};
+use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex, MutexGuard, PoisonError},
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+
+
+//		Enums
+
+//		TokenKind																
+/// The kind of a token issued by the provider.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum TokenKind {
+	/// An access token.
+	Access,
+	
+	/// A refresh token.
+	Refresh,
+}
+
+
+
+//		Structs
+
+//		IssuedToken																
+/// A token issued by the provider.
+#[derive(Clone, Debug)]
+struct IssuedToken {
+	/// The client that the token was issued to.
+	client_id:  String,
+	
+	/// When the token expires, if it does.
+	expires_at: Option<SystemTime>,
+	
+	/// When the token was issued.
+	issued_at:  SystemTime,
+	
+	/// The kind of token.
+	kind:       TokenKind,
+	
+	/// Whether the token has been revoked, or used up in the case of a rotated
+	/// refresh token.
+	revoked:    bool,
+	
+	/// The scope granted to the token, if any.
+	scope:      Option<String>,
+	
+	/// The subject that the token represents.
+	subject:    String,
+}
+
+//		ProviderState															
+/// The state of a [`MockOAuthProvider`].
+#[derive(Debug)]
+struct ProviderState {
+	/// The lifetime of access tokens.
+	access_ttl:     Duration,
+	
+	/// The grant types requested from the token endpoint, in order.
+	grants:         Vec<String>,
+	
+	/// The signing keys published by the JWKS endpoint. The last one is used
+	/// for signing.
+	keys:           Vec<SigningKey>,
+	
+	/// The number used to make the next token or key unique.
+	next_id:        u64,
+	
+	/// Whether refresh tokens are replaced each time they are used.
+	rotate_refresh: bool,
+	
+	/// The lifetime of refresh tokens, if they expire.
+	refresh_ttl:    Option<Duration>,
+	
+	/// The tokens issued so far, by token string.
+	tokens:         HashMap<String, IssuedToken>,
+}
+
+//󰭅		ProviderState															
+impl ProviderState {
+	//		add_signing_key														
+	/// Generates a new signing key, which becomes the current one, and returns
+	/// its key ID.
+	fn add_signing_key(&mut self) -> String {
+		let kid    = format!("key-{}", self.take_id());
+		let secret = Sha256::digest(format!("sham-oauth-{kid}")).to_vec();
+		self.keys.push(SigningKey { kid: kid.clone(), secret });
+		kid
+	}
+	
+	//		is_active															
+	/// Whether the given token is known, unrevoked, and unexpired.
+	fn is_active(&self, token: &str, now: SystemTime) -> bool {
+		self.tokens.get(token).is_some_and(|issued| {
+			!issued.revoked && issued.expires_at.map_or(true, |expires_at| now < expires_at)
+		})
+	}
+	
+	//		take_id																
+	/// Returns the next unique number.
+	const fn take_id(&mut self) -> u64 {
+		self.next_id += 1;
+		self.next_id
+	}
+}
+
+//		SigningKey																
+/// A key used to sign access tokens.
+#[derive(Clone, Debug)]
+struct SigningKey {
+	/// The key ID.
+	kid:    String,
+	
+	/// The secret.
+	secret: Vec<u8>,
+}
+
+//		MockOAuthProvider														
+/// A mocked OAuth2/OIDC provider.
+/// 
+/// Clones share the same state, so a clone can be used to serve routes while
+/// the original is used to make assertions and change the configuration.
+/// 
+#[derive(Clone, Debug)]
+pub struct MockOAuthProvider {
+	//		Private properties													
+	/// The clock that drives token expiry.
+	clock:  MockClock,
+	
+	/// The issuer URL, under which the endpoints are served.
+	issuer: Url,
+	
+	/// The state of the provider, shared between clones.
+	state:  Arc<Mutex<ProviderState>>,
+}
+
+//󰭅		MockOAuthProvider														
+impl MockOAuthProvider {
+	//		new																	
+	/// Creates a new provider.
+	/// 
+	/// By default, access tokens last for an hour, refresh tokens do not expire
+	/// and are rotated on use, and there is a single signing key.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `issuer` - The issuer URL, under which the endpoints are served.
+	/// * `clock`  - The clock that drives token expiry.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the issuer URL is not valid.
+	/// 
+	#[must_use]
+	pub fn new<U: IntoUrl>(issuer: U, clock: &MockClock) -> Self {
+		let mut state = ProviderState {
+			access_ttl:     Duration::from_secs(3_600),
+			grants:         Vec::new(),
+			keys:           Vec::new(),
+			next_id:        0,
+			rotate_refresh: true,
+			refresh_ttl:    None,
+			tokens:         HashMap::new(),
+		};
+		drop(state.add_signing_key());
+		Self {
+			clock:  clock.clone(),
+			issuer: issuer.into_url().unwrap(),
+			state:  Arc::new(Mutex::new(state)),
+		}
+	}
+	
+	//		with_access_token_ttl												
+	/// Sets the lifetime of access tokens issued from now on.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `ttl` - The lifetime of access tokens.
+	/// 
+	#[must_use]
+	pub fn with_access_token_ttl(self, ttl: Duration) -> Self {
+		self.lock().access_ttl = ttl;
+		self
+	}
+	
+	//		with_refresh_token_rotation											
+	/// Sets whether refresh tokens are replaced each time they are used, in
+	/// which case using one a second time fails with `invalid_grant`.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `rotate` - Whether to rotate refresh tokens.
+	/// 
+	#[must_use]
+	pub fn with_refresh_token_rotation(self, rotate: bool) -> Self {
+		self.lock().rotate_refresh = rotate;
+		self
+	}
+	
+	//		with_refresh_token_ttl												
+	/// Sets the lifetime of refresh tokens issued from now on.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `ttl` - The lifetime of refresh tokens.
+	/// 
+	#[must_use]
+	pub fn with_refresh_token_ttl(self, ttl: Duration) -> Self {
+		self.lock().refresh_ttl = Some(ttl);
+		self
+	}
+	
+	//		client																
+	/// Creates a mock client that serves the provider's routes.
+	#[must_use]
+	pub fn client(&self) -> MockClient {
+		create_routed_mock_client(self.routes())
+	}
+	
+	//		current_key_id														
+	/// Returns the ID of the key that access tokens are currently signed with.
+	#[must_use]
+	pub fn current_key_id(&self) -> String {
+		self.lock().keys.last().map(|key| key.kid.clone()).unwrap_or_default()
+	}
+	
+	//		discovery_url														
+	/// Returns the URL of the OIDC discovery document.
+	#[must_use]
+	pub fn discovery_url(&self) -> Url {
+		self.endpoint(".well-known/openid-configuration")
+	}
+	
+	//		grants																
+	/// Returns the grant types requested from the token endpoint, in order,
+	/// including any that were rejected.
+	#[must_use]
+	pub fn grants(&self) -> Vec<String> {
+		self.lock().grants.clone()
+	}
+	
+	//		introspection_url													
+	/// Returns the URL of the token introspection endpoint.
+	#[must_use]
+	pub fn introspection_url(&self) -> Url {
+		self.endpoint("introspect")
+	}
+	
+	//		is_active															
+	/// Whether the given token was issued by the provider, and has neither been
+	/// revoked nor expired.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `token` - The access or refresh token to check.
+	/// 
+	#[must_use]
+	pub fn is_active(&self, token: &str) -> bool {
+		self.lock().is_active(token, self.clock.now())
+	}
+	
+	//		issue_access_token													
+	/// Issues an access token directly, without going through the token
+	/// endpoint, for tests that only need a valid token.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `client_id` - The client to issue the token to.
+	/// * `subject`   - The subject that the token represents.
+	/// 
+	#[must_use]
+	pub fn issue_access_token(&self, client_id: &str, subject: &str) -> String {
+		self.issue_access(&mut self.lock(), client_id, subject, None)
+	}
+	
+	//		jwks_url															
+	/// Returns the URL of the JWKS endpoint.
+	#[must_use]
+	pub fn jwks_url(&self) -> Url {
+		self.endpoint("jwks")
+	}
+	
+	//		retire_signing_keys													
+	/// Removes all but the current signing key from the JWKS endpoint, so that
+	/// tokens signed with earlier keys can no longer be verified.
+	pub fn retire_signing_keys(&self) {
+		let mut state = self.lock();
+		let count     = state.keys.len();
+		drop(state.keys.drain(..count.saturating_sub(1)));
+	}
+	
+	//		revoke																
+	/// Revokes the given token, so that it is no longer active.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `token` - The access or refresh token to revoke.
+	/// 
+	pub fn revoke(&self, token: &str) {
+		if let Some(issued) = self.lock().tokens.get_mut(token) {
+			issued.revoked = true;
+		}
+	}
+	
+	//		rotate_signing_key													
+	/// Generates a new signing key, which is used to sign access tokens from
+	/// now on, and returns its key ID. Earlier keys remain published until
+	/// they are [retired](MockOAuthProvider::retire_signing_keys()).
+	pub fn rotate_signing_key(&self) -> String {
+		self.lock().add_signing_key()
+	}
+	
+	//		routes																
+	/// Returns the routes that serve the provider's endpoints.
+	/// 
+	/// The routes are:
+	/// 
+	///   - `GET  .well-known/openid-configuration` - The discovery document.
+	///   - `GET  jwks`                             - The signing keys.
+	///   - `POST introspect`                       - Token introspection.
+	///   - `POST token`                            - The token endpoint.
+	/// 
+	#[must_use]
+	pub fn routes(&self) -> Vec<MockRoute> {
+		let discovery     = self.clone();
+		let jwks          = self.clone();
+		let introspection = self.clone();
+		let token         = self.clone();
+		vec![
+			MockRoute::new(Method::GET,  self.discovery_url(),     move |request| discovery.discovery(request)),
+			MockRoute::new(Method::GET,  self.jwks_url(),          move |request| jwks.jwks(request)),
+			MockRoute::new(Method::POST, self.introspection_url(), move |request| introspection.introspect(request)),
+			MockRoute::new(Method::POST, self.token_url(),         move |request| token.token(request)),
+		]
+	}
+	
+	//		token_url															
+	/// Returns the URL of the token endpoint.
+	#[must_use]
+	pub fn token_url(&self) -> Url {
+		self.endpoint("token")
+	}
+	
+	//		discovery															
+	/// Serves the OIDC discovery document.
+	#[expect(clippy::unnecessary_wraps, reason = "Needed for the route signature")]
+	fn discovery(&self, request: &MockRequest) -> Result<MockResponse, MockError> {
+		Ok(json_response(request, StatusCode::OK, &json!({
+			"issuer":                                self.issuer.as_str(),
+			"token_endpoint":                        self.token_url().as_str(),
+			"introspection_endpoint":                self.introspection_url().as_str(),
+			"jwks_uri":                              self.jwks_url().as_str(),
+			"grant_types_supported":                 ["authorization_code", "client_credentials", "password", "refresh_token"],
+			"id_token_signing_alg_values_supported": ["HS256"],
+		})))
+	}
+	
+	//		endpoint															
+	/// Returns the URL of the given endpoint under the issuer URL.
+	fn endpoint(&self, path: &str) -> Url {
+		let mut url = self.issuer.clone();
+		url.set_path(&format!("{}/{path}", self.issuer.path().trim_end_matches('/')));
+		url
+	}
+	
+	//		introspect															
+	/// Serves the token introspection endpoint.
+	#[expect(clippy::unnecessary_wraps, reason = "Needed for the route signature")]
+	fn introspect(&self, request: &MockRequest) -> Result<MockResponse, MockError> {
+		let form  = parse_form(request);
+		let state = self.lock();
+		let body  = form.get("token")
+			.filter(|token| state.is_active(token, self.clock.now()))
+			.and_then(|token| state.tokens.get(token))
+			.map_or_else(|| json!({ "active": false }), |issued| json!({
+				"active":     true,
+				"client_id":  issued.client_id,
+				"exp":        issued.expires_at.map(unix_timestamp),
+				"iat":        unix_timestamp(issued.issued_at),
+				"iss":        self.issuer.as_str(),
+				"scope":      issued.scope,
+				"sub":        issued.subject,
+				"token_type": match issued.kind {
+					TokenKind::Access  => "Bearer",
+					TokenKind::Refresh => "refresh_token",
+				},
+			}))
+		;
+		drop(state);
+		Ok(json_response(request, StatusCode::OK, &body))
+	}
+	
+	//		issue_access														
+	/// Issues and records a signed access token.
+	fn issue_access(&self, state: &mut ProviderState, client_id: &str, subject: &str, scope: Option<&str>) -> String {
+		let now     = self.clock.now();
+		let jti     = state.take_id();
+		let token   = sign(state, &json!({
+			"iss":       self.issuer.as_str(),
+			"sub":       subject,
+			"client_id": client_id,
+			"iat":       unix_timestamp(now),
+			"exp":       unix_timestamp(now + state.access_ttl),
+			"jti":       jti.to_string(),
+			"scope":     scope,
+		}));
+		drop(state.tokens.insert(token.clone(), IssuedToken {
+			client_id:  client_id.to_owned(),
+			expires_at: Some(now + state.access_ttl),
+			issued_at:  now,
+			kind:       TokenKind::Access,
+			revoked:    false,
+			scope:      scope.map(ToOwned::to_owned),
+			subject:    subject.to_owned(),
+		}));
+		token
+	}
+	
+	//		issue_tokens														
+	/// Issues a set of tokens, and returns the token endpoint response body.
+	fn issue_tokens(
+		&self,
+		state:         &mut ProviderState,
+		client_id:     &str,
+		subject:       &str,
+		scope:         Option<&str>,
+		with_refresh:  bool,
+	) -> Json {
+		let now          = self.clock.now();
+		let access_token = self.issue_access(state, client_id, subject, scope);
+		let mut body     = json!({
+			"access_token": access_token,
+			"token_type":   "Bearer",
+			"expires_in":   state.access_ttl.as_secs(),
+		});
+		if let Some(granted) = scope {
+			body["scope"] = json!(granted);
+			if granted.split(' ').any(|part| part == "openid") {
+				body["id_token"] = json!(sign(state, &json!({
+					"iss": self.issuer.as_str(),
+					"sub": subject,
+					"aud": client_id,
+					"iat": unix_timestamp(now),
+					"exp": unix_timestamp(now + state.access_ttl),
+				})));
+			}
+		}
+		if with_refresh {
+			let refresh_token = format!("refresh-{}", state.take_id());
+			let expires_at    = state.refresh_ttl.map(|ttl| now + ttl);
+			drop(state.tokens.insert(refresh_token.clone(), IssuedToken {
+				client_id:  client_id.to_owned(),
+				expires_at,
+				issued_at:  now,
+				kind:       TokenKind::Refresh,
+				revoked:    false,
+				scope:      scope.map(ToOwned::to_owned),
+				subject:    subject.to_owned(),
+			}));
+			body["refresh_token"] = json!(refresh_token);
+		}
+		body
+	}
+	
+	//		jwks																
+	/// Serves the JWKS endpoint.
+	#[expect(clippy::unnecessary_wraps, reason = "Needed for the route signature")]
+	fn jwks(&self, request: &MockRequest) -> Result<MockResponse, MockError> {
+		let keys = self.lock().keys.iter().map(|key| json!({
+			"kty": "oct",
+			"use": "sig",
+			"alg": "HS256",
+			"kid": key.kid,
+			"k":   BASE64URL.encode(&key.secret),
+		})).collect::<Vec<_>>();
+		Ok(json_response(request, StatusCode::OK, &json!({ "keys": keys })))
+	}
+	
+	//		lock																
+	/// Locks the state of the provider.
+	fn lock(&self) -> MutexGuard<'_, ProviderState> {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+	
+	//		token																
+	/// Serves the token endpoint.
+	#[expect(clippy::unnecessary_wraps, reason = "Needed for the route signature")]
+	fn token(&self, request: &MockRequest) -> Result<MockResponse, MockError> {
+		let form      = parse_form(request);
+		let client_id = form.get("client_id").cloned().or_else(|| basic_auth_user(request)).unwrap_or_else(|| "client".to_owned());
+		let scope     = form.get("scope").map(String::as_str);
+		let mut state = self.lock();
+		let Some(grant) = form.get("grant_type") else {
+			return Ok(oauth_error(request, "invalid_request", "Missing grant_type"));
+		};
+		state.grants.push(grant.clone());
+		let body = match grant.as_str() {
+			"authorization_code" => match form.get("code") {
+				Some(_) => self.issue_tokens(&mut state, &client_id, "user", scope, true),
+				None    => return Ok(oauth_error(request, "invalid_request", "Missing code")),
+			},
+			"client_credentials" => self.issue_tokens(&mut state, &client_id, &client_id, scope, false),
+			"password"           => match form.get("username") {
+				Some(username) => self.issue_tokens(&mut state, &client_id, username, scope, true),
+				None           => return Ok(oauth_error(request, "invalid_request", "Missing username")),
+			},
+			"refresh_token"      => {
+				let Some(issued) = form.get("refresh_token")
+					.filter(|token| state.is_active(token, self.clock.now()))
+					.and_then(|token| state.tokens.get(token))
+					.filter(|issued| issued.kind == TokenKind::Refresh)
+					.cloned()
+				else {
+					return Ok(oauth_error(request, "invalid_grant", "Refresh token is invalid, expired, or revoked"));
+				};
+				if state.rotate_refresh {
+					if let Some(used) = form.get("refresh_token").and_then(|token| state.tokens.get_mut(token)) {
+						used.revoked = true;
+					}
+				}
+				let granted = scope.or(issued.scope.as_deref());
+				self.issue_tokens(&mut state, &issued.client_id, &issued.subject, granted, true)
+			},
+			_                    => return Ok(oauth_error(request, "unsupported_grant_type", "Grant type is not supported")),
+		};
+		drop(state);
+		Ok(json_response(request, StatusCode::OK, &body))
+	}
+}
+
+
+
+//		Functions
+
+//		basic_auth_user															
+/// Returns the username from the request's basic authorisation header, if any.
+fn basic_auth_user(request: &MockRequest) -> Option<String> {
+	let encoded = request.headers.get(AUTHORIZATION)?.to_str().ok()?.strip_prefix("Basic ")?;
+	let decoded = String::from_utf8(BASE64.decode(encoded).ok()?).ok()?;
+	decoded.split(':').next().map(ToOwned::to_owned)
+}
+
+//		json_response															
+/// Creates a JSON response to the given request.
+fn json_response(request: &MockRequest, status: StatusCode, body: &Json) -> MockResponse {
+	let bytes = body.to_string().into_bytes();
+	create_mock_response(
+		request.url.clone(),
+		status,
+		Some("application/json"),
+		Some(bytes.len()),
+		HashMap::<String, String>::new(),
+		Ok(&bytes),
+	)
+}
+
+//		oauth_error																
+/// Creates an `OAuth2` error response to the given request.
+fn oauth_error(request: &MockRequest, error: &str, description: &str) -> MockResponse {
+	json_response(request, StatusCode::BAD_REQUEST, &json!({
+		"error":             error,
+		"error_description": description,
+	}))
+}
+
+//		parse_form																
+/// Parses the URL-encoded form in the body of the given request.
+fn parse_form(request: &MockRequest) -> HashMap<String, String> {
+	request.body.as_ref()
+		.and_then(|body| serde_urlencoded::from_bytes(body).ok())
+		.unwrap_or_default()
+}
+
+//		sign																	
+/// Creates a JWT with the given claims, signed with the current signing key.
+fn sign(state: &ProviderState, claims: &Json) -> String {
+	let key     = state.keys.last().expect("Provider has no signing key");
+	let header  = json!({ "alg": "HS256", "typ": "JWT", "kid": key.kid });
+	let payload = format!(
+		"{}.{}",
+		BASE64URL.encode(header.to_string()),
+		BASE64URL.encode(claims.to_string()),
+	);
+	let mut mac = Hmac::<Sha256>::new_from_slice(&key.secret).expect("HMAC accepts keys of any length");
+	mac.update(payload.as_bytes());
+	format!("{payload}.{}", BASE64URL.encode(mac.finalize().into_bytes()))
+}
+
+//		unix_timestamp															
+/// Converts a time to a number of whole seconds since the Unix epoch.
+fn unix_timestamp(time: SystemTime) -> u64 {
+	time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
@@ -0,0 +1,432 @@
+//! This module provides command output fixtures in order to test output
+//! parsers.
+//! 
+//! This module provides [`MockOutput`], which builds the bytes that a command
+//! writes to its standard output or standard error, in the
+//! [encoding](MockEncoding) and with the [line endings](MockLineEnding) that
+//! real tools use, including byte order marks, and with ANSI escape sequences
+//! for colours and cursor movement interleaved with the text. This is important
+//! because code that parses the output of other programs is usually written and
+//! tested against clean UTF-8 with Unix line endings, and then meets Windows
+//! tools that emit UTF-16 or a legacy code page with CRLF line endings, and
+//! tools that colour their output even when it is not going to a terminal.
+//! 
+//! The approach taken is that the output is described once, as text, and
+//! [`MockOutput::bytes()`] then encodes it for the chosen platform, so that the
+//! same fixture can be checked in every form in which a parser might receive
+//! it. [`MockOutput::plain()`] gives the text that a correct parser should
+//! recover, without the escape sequences and with Unix line endings, to compare
+//! against. The bytes can then be returned by the mocks in the same way as any
+//! other output.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use sham::output::{MockEncoding, MockOutput, MockPlatform};
+//! 
+//! let output = MockOutput::for_platform(MockPlatform::Windows)
+//!     .with_styled("error", "31")
+//!     .with_line(": café not found")
+//!     .with_ansi("\x1b[2K")
+//!     .with_line("Done")
+//! ;
+//! 
+//! assert_eq!(output.plain(), "error: café not found\nDone\n");
+//! assert_eq!(output.bytes(), b"\x1b[31merror\x1b[0m: caf\xE9 not found\r\n\x1b[2KDone\r\n");
+//! 
+//! let utf16 = output.with_encoding(MockEncoding::Utf16Le).bytes();
+//! assert_eq!(&utf16[..4], &[0xFF, 0xFE, 0x1B, 0x00]);
+//! ```
+//! 
+
+
+
+//		Packages
+
+use core::fmt::{Display, Formatter, self};
+
+
+
+//		Constants
+
+/// The characters of Windows-1252 from `0x80` to `0x9F`, where it differs from
+/// ISO-8859-1, with the bytes that it leaves undefined as the replacement
+/// character.
+const WINDOWS_1252: [char; 32] = [
+	'€', '\u{FFFD}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{FFFD}', 'Ž', '\u{FFFD}',
+	'\u{FFFD}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{FFFD}', 'ž', 'Ÿ',
+];
+
+
+
+//		Enums
+
+//		MockEncoding															
+/// The encoding of the output of a command.
+/// 
+/// Characters that cannot be represented in a single-byte encoding are written
+/// as `?`, as Windows does.
+/// 
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum MockEncoding {
+	/// ISO-8859-1, in which each character up to `U+00FF` is a single byte.
+	Latin1,
+	
+	/// UTF-16, big-endian, with a byte order mark.
+	Utf16Be,
+	
+	/// UTF-16, little-endian, with a byte order mark, as written by PowerShell
+	/// and by many Windows tools when their output is redirected.
+	Utf16Le,
+	
+	/// UTF-8, without a byte order mark.
+	Utf8,
+	
+	/// UTF-8, with a byte order mark, as written by some Windows tools.
+	Utf8Bom,
+	
+	/// Windows-1252, the code page used by Windows in Western Europe and the
+	/// Americas, which is ISO-8859-1 with extra characters, such as `€`, in
+	/// place of some control characters.
+	Windows1252,
+}
+
+//󰭅		MockEncoding															
+impl MockEncoding {
+	//		all																	
+	/// Returns all of the encodings.
+	#[must_use]
+	pub const fn all() -> [Self; 6] {
+		[
+			Self::Latin1,
+			Self::Utf16Be,
+			Self::Utf16Le,
+			Self::Utf8,
+			Self::Utf8Bom,
+			Self::Windows1252,
+		]
+	}
+	
+	//		bom																	
+	/// Returns the byte order mark that starts output in this encoding, which
+	/// is empty if there is none.
+	#[must_use]
+	pub const fn bom(self) -> &'static [u8] {
+		match self {
+			Self::Latin1 | Self::Utf8 | Self::Windows1252 => &[],
+			Self::Utf16Be                                 => &[0xFE, 0xFF],
+			Self::Utf16Le                                 => &[0xFF, 0xFE],
+			Self::Utf8Bom                                 => &[0xEF, 0xBB, 0xBF],
+		}
+	}
+	
+	//		encode																
+	/// Encodes the given text, starting with the byte order mark, if any.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `text` - The text to encode.
+	/// 
+	#[must_use]
+	pub fn encode(self, text: &str) -> Vec<u8> {
+		let mut bytes = self.bom().to_vec();
+		match self {
+			Self::Latin1      => bytes.extend(text.chars().map(|c| u8::try_from(c).unwrap_or(b'?'))),
+			Self::Utf16Be     => bytes.extend(text.encode_utf16().flat_map(u16::to_be_bytes)),
+			Self::Utf16Le     => bytes.extend(text.encode_utf16().flat_map(u16::to_le_bytes)),
+			Self::Utf8
+			| Self::Utf8Bom   => bytes.extend_from_slice(text.as_bytes()),
+			Self::Windows1252 => bytes.extend(text.chars().map(windows_1252)),
+		}
+		bytes
+	}
+}
+
+//󰭅		Display																	
+impl Display for MockEncoding {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let description = match *self {
+			Self::Latin1      => "ISO-8859-1",
+			Self::Utf16Be     => "UTF-16BE",
+			Self::Utf16Le     => "UTF-16LE",
+			Self::Utf8        => "UTF-8",
+			Self::Utf8Bom     => "UTF-8 with BOM",
+			Self::Windows1252 => "Windows-1252",
+		};
+		write!(f, "{description}")
+	}
+}
+
+//		MockLineEnding															
+/// The line ending used in the output of a command.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum MockLineEnding {
+	/// A carriage return followed by a line feed, as used on Windows.
+	CrLf,
+	
+	/// A line feed, as used on Linux and macOS.
+	Lf,
+}
+
+//󰭅		MockLineEnding															
+impl MockLineEnding {
+	//		as_str																
+	/// Returns the characters of the line ending.
+	#[must_use]
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::CrLf => "\r\n",
+			Self::Lf   => "\n",
+		}
+	}
+}
+
+//		MockPlatform															
+/// A platform whose tools write their output in a particular way.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum MockPlatform {
+	/// Linux, whose tools write UTF-8 with line feeds.
+	Linux,
+	
+	/// Windows, whose console tools write in the ANSI code page, which is
+	/// taken to be [`Windows1252`](MockEncoding::Windows1252), with carriage
+	/// returns and line feeds.
+	Windows,
+}
+
+//󰭅		MockPlatform															
+impl MockPlatform {
+	//		current																
+	/// Returns the platform that the tests are running on, treating everything
+	/// other than Windows as Linux.
+	#[must_use]
+	pub const fn current() -> Self {
+		if cfg!(windows) { Self::Windows } else { Self::Linux }
+	}
+	
+	//		encoding															
+	/// Returns the encoding that the tools of the platform usually write.
+	#[must_use]
+	pub const fn encoding(self) -> MockEncoding {
+		match self {
+			Self::Linux   => MockEncoding::Utf8,
+			Self::Windows => MockEncoding::Windows1252,
+		}
+	}
+	
+	//		line_ending															
+	/// Returns the line ending that the tools of the platform usually write.
+	#[must_use]
+	pub const fn line_ending(self) -> MockLineEnding {
+		match self {
+			Self::Linux   => MockLineEnding::Lf,
+			Self::Windows => MockLineEnding::CrLf,
+		}
+	}
+}
+
+
+
+//		Structs
+
+//		MockOutput																
+/// The output of a command, described as text, and encoded as it would be
+/// written by a real tool.
+/// 
+/// Line feeds in the text, including those added by
+/// [`with_line()`](MockOutput::with_line()), are written using the line
+/// ending of the output. Carriage returns on their own, as used by progress
+/// bars to redraw a line, are left as they are.
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[must_use]
+pub struct MockOutput {
+	//		Private properties													
+	/// The encoding to write the output in.
+	encoding:    MockEncoding,
+	
+	/// The line ending to write the output with.
+	line_ending: MockLineEnding,
+	
+	/// The text without any escape sequences.
+	plain:       String,
+	
+	/// The text with its escape sequences, and with line feeds for line
+	/// endings.
+	text:        String,
+}
+
+//󰭅		MockOutput																
+impl MockOutput {
+	//		new																	
+	/// Creates a new, empty output, in UTF-8 with line feeds.
+	pub const fn new() -> Self {
+		Self {
+			encoding:    MockEncoding::Utf8,
+			line_ending: MockLineEnding::Lf,
+			plain:       String::new(),
+			text:        String::new(),
+		}
+	}
+	
+	//		for_platform														
+	/// Creates a new, empty output, in the encoding and with the line ending
+	/// that the tools of the given platform usually write.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `platform` - The platform to write the output for.
+	/// 
+	pub const fn for_platform(platform: MockPlatform) -> Self {
+		Self::new().with_encoding(platform.encoding()).with_line_ending(platform.line_ending())
+	}
+	
+	//		with_ansi															
+	/// Adds an ANSI escape sequence, such as `\x1b[2K` to clear the line, which
+	/// appears in the bytes but not in the plain text.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `sequence` - The escape sequence, including the escape character.
+	/// 
+	pub fn with_ansi(mut self, sequence: &str) -> Self {
+		self.text.push_str(sequence);
+		self
+	}
+	
+	//		with_encoding														
+	/// Sets the encoding to write the output in.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `encoding` - The encoding.
+	/// 
+	pub const fn with_encoding(mut self, encoding: MockEncoding) -> Self {
+		self.encoding = encoding;
+		self
+	}
+	
+	//		with_line															
+	/// Adds text followed by a line ending.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `text` - The text of the line, without the line ending.
+	/// 
+	pub fn with_line(self, text: &str) -> Self {
+		self.with_text(text).with_text("\n")
+	}
+	
+	//		with_line_ending													
+	/// Sets the line ending to write the output with.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `line_ending` - The line ending.
+	/// 
+	pub const fn with_line_ending(mut self, line_ending: MockLineEnding) -> Self {
+		self.line_ending = line_ending;
+		self
+	}
+	
+	//		with_styled															
+	/// Adds text styled with the given Select Graphic Rendition parameters,
+	/// followed by a reset, as coloured output is written.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `text`  - The text to style.
+	/// * `style` - The parameters, such as `31` for red or `1;33` for bold
+	///   yellow.
+	/// 
+	pub fn with_styled(self, text: &str, style: &str) -> Self {
+		self.with_ansi(&format!("\x1b[{style}m")).with_text(text).with_ansi("\x1b[0m")
+	}
+	
+	//		with_text															
+	/// Adds text, without a line ending.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `text` - The text to add.
+	/// 
+	pub fn with_text(mut self, text: &str) -> Self {
+		self.plain.push_str(text);
+		self.text.push_str(text);
+		self
+	}
+	
+	//		bytes																
+	/// Returns the output as it would be written by the command, encoded and
+	/// with its line endings, escape sequences, and byte order mark.
+	#[must_use]
+	pub fn bytes(&self) -> Vec<u8> {
+		self.encoding.encode(&self.text())
+	}
+	
+	//		encoding															
+	/// Returns the encoding that the output is written in.
+	#[must_use]
+	pub const fn encoding(&self) -> MockEncoding {
+		self.encoding
+	}
+	
+	//		line_ending															
+	/// Returns the line ending that the output is written with.
+	#[must_use]
+	pub const fn line_ending(&self) -> MockLineEnding {
+		self.line_ending
+	}
+	
+	//		lines																
+	/// Returns the lines of the plain text, without their line endings, which
+	/// is what a parser that reads the output line by line should recover.
+	#[must_use]
+	pub fn lines(&self) -> Vec<String> {
+		self.plain.lines().map(ToOwned::to_owned).collect()
+	}
+	
+	//		plain																
+	/// Returns the text that a correct parser should recover from the output,
+	/// without the escape sequences, and with line feeds for line endings.
+	#[must_use]
+	pub fn plain(&self) -> String {
+		self.plain.clone()
+	}
+	
+	//		text																
+	/// Returns the output as text, with its line endings and escape sequences,
+	/// before it is encoded.
+	#[must_use]
+	pub fn text(&self) -> String {
+		self.text.replace('\n', self.line_ending.as_str())
+	}
+}
+
+//󰭅		Default																	
+impl Default for MockOutput {
+	//		default																
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+
+
+//		Functions
+
+//		windows_1252															
+/// Encodes a character as Windows-1252, or as `?` if it cannot be.
+fn windows_1252(c: char) -> u8 {
+	match u8::try_from(c) {
+		Ok(byte) if !(0x80..0xA0).contains(&byte) => byte,
+		_                                          => WINDOWS_1252.iter()
+			.position(|&mapped| mapped == c && mapped != '\u{FFFD}')
+			.and_then(|index| u8::try_from(index).ok())
+			.map_or(b'?', |index| index.wrapping_add(0x80)),
+	}
+}
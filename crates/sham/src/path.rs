@@ -0,0 +1,190 @@
+//! This module provides path normalisation in order to match fixtures across
+//! platforms.
+//! 
+//! This module provides [`MockPathMatcher`], which is used by the mocks that
+//! look things up by path, such as the files of the [`config`](crate::config)
+//! mock and the sockets of the [`unix`](crate::unix) mock, to decide whether
+//! two paths refer to the same thing. This is important because fixtures are
+//! often written on one platform and run on another, so that a test which sets
+//! up `config/app.env` on Linux may look for `config\app.env` on Windows CI,
+//! and would fail for reasons that have nothing to do with the code under test.
+//! 
+//! The approach taken is that paths are normalised before they are compared:
+//! backslashes become forward slashes, the verbatim (`\\?\`) and device
+//! (`\\.\`) prefixes are removed, UNC prefixes are kept as a leading `//`,
+//! drive letters are upper-cased, repeated separators and `.` segments are
+//! removed, and `..` segments are resolved lexically. Paths are compared
+//! case-insensitively on Windows and case-sensitively elsewhere, as the
+//! filesystems of each target usually do, which can be changed. The filesystem
+//! is never consulted. For tests that need to check the exact paths used,
+//! [`MockPathMatcher::exact()`] turns normalisation off.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use sham::path::MockPathMatcher;
+//! 
+//! let matcher = MockPathMatcher::new().with_case_sensitivity(false);
+//! assert!(matcher.matches("config/app.env", r"config\App.env"));
+//! assert!(matcher.matches("./config//app.env", "config/defaults/../app.env"));
+//! assert!(matcher.matches(r"\\?\C:\data\app.env", "c:/data/app.env"));
+//! assert!(matcher.matches(r"\\?\UNC\server\share\app.env", "//server/share/app.env"));
+//! assert_eq!(matcher.normalise(r"C:\Data\.\app.env"), "c:/data/app.env");
+//! 
+//! assert!(!MockPathMatcher::exact().matches("config/app.env", r"config\app.env"));
+//! ```
+//! 
+
+
+
+//		Packages
+
+use std::path::Path;
+
+
+
+//		Structs
+
+//		MockPathMatcher															
+/// A matcher that decides whether two paths refer to the same thing.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockPathMatcher {
+	//		Public properties													
+	/// Whether paths that differ only in case are different. By default, this
+	/// is `false` on Windows and `true` elsewhere.
+	pub case_sensitive: bool,
+	
+	/// Whether paths are compared exactly as given, without normalisation.
+	pub exact:          bool,
+}
+
+//󰭅		MockPathMatcher															
+impl MockPathMatcher {
+	//		new																	
+	/// Creates a new matcher that normalises paths, and compares them with the
+	/// case sensitivity that is usual for the target platform.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			case_sensitive: !cfg!(windows),
+			exact:          false,
+		}
+	}
+	
+	//		exact																
+	/// Creates a new matcher that compares paths exactly as given.
+	#[must_use]
+	pub const fn exact() -> Self {
+		Self {
+			case_sensitive: true,
+			exact:          true,
+		}
+	}
+	
+	//		with_case_sensitivity												
+	/// Sets whether paths that differ only in case are different.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `case_sensitive` - Whether to compare paths case-sensitively.
+	/// 
+	#[must_use]
+	pub const fn with_case_sensitivity(mut self, case_sensitive: bool) -> Self {
+		self.case_sensitive = case_sensitive;
+		self
+	}
+	
+	//		matches																
+	/// Whether the given paths refer to the same thing.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `left`  - The first path.
+	/// * `right` - The second path.
+	/// 
+	pub fn matches<L: AsRef<Path>, R: AsRef<Path>>(&self, left: L, right: R) -> bool {
+		if self.exact {
+			return left.as_ref() == right.as_ref();
+		}
+		self.normalise(left) == self.normalise(right)
+	}
+	
+	//		normalise															
+	/// Normalises a path for comparison.
+	/// 
+	/// The result uses forward slashes, and is lower-cased if the matcher is
+	/// not case-sensitive. An empty relative path becomes `.`.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `path` - The path to normalise.
+	/// 
+	pub fn normalise<P: AsRef<Path>>(&self, path: P) -> String {
+		let text = path.as_ref().to_string_lossy();
+		if self.exact {
+			return text.into_owned();
+		}
+		let slashed        = text.replace('\\', "/");
+		let (prefix, rest) = split_prefix(&slashed);
+		let mut segments   = Vec::<&str>::new();
+		for segment in rest.split('/') {
+			match segment {
+				"" | "."                                                  => {},
+				".." if segments.last().is_some_and(|&last| last != "..") => drop(segments.pop()),
+				".." if !prefix.is_empty()                                => {},
+				_                                                         => segments.push(segment),
+			}
+		}
+		let joined     = segments.join("/");
+		let normalised = match (prefix.is_empty(), joined.is_empty()) {
+			(true, true)  => ".".to_owned(),
+			(false, true) => prefix,
+			(_, false)    => format!("{prefix}{joined}"),
+		};
+		if self.case_sensitive { normalised } else { normalised.to_lowercase() }
+	}
+}
+
+//󰭅		Default																	
+impl Default for MockPathMatcher {
+	//		default																
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+
+
+//		Functions
+
+//		split_prefix															
+/// Splits a path that uses forward slashes into its normalised root, such as
+/// `/`, `//server/share/`, or `C:/`, and the rest of the path.
+fn split_prefix(path: &str) -> (String, &str) {
+	let unprefixed = path.strip_prefix("//?/UNC/").map(|rest| ("//", rest))
+		.or_else(|| path.strip_prefix("//?/").map(|rest| ("", rest)))
+		.or_else(|| path.strip_prefix("//./").map(|rest| ("", rest)))
+	;
+	let (unc, rest) = match unprefixed {
+		Some(("//", rest)) => (true, rest),
+		Some((_, rest))    => (false, rest),
+		None               => path.strip_prefix("//").map_or((false, path), |rest| (true, rest)),
+	};
+	if unc {
+		let mut parts = rest.splitn(3, '/');
+		let server    = parts.next().unwrap_or_default();
+		let share     = parts.next().unwrap_or_default();
+		return (format!("//{server}/{share}/"), parts.next().unwrap_or_default());
+	}
+	let mut chars = rest.chars();
+	if let (Some(letter @ ('a'..='z' | 'A'..='Z')), Some(':')) = (chars.next(), chars.next()) {
+		let drive = letter.to_ascii_uppercase();
+		let tail  = chars.as_str();
+		return tail.strip_prefix('/').map_or_else(
+			|| (format!("{drive}:"), tail),
+			|after| (format!("{drive}:/"), after),
+		);
+	}
+	rest.strip_prefix('/').map_or_else(|| (String::new(), rest), |after| ("/".to_owned(), after))
+}
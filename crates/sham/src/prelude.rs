@@ -0,0 +1,65 @@
+//! This module provides the mocks under the names of the genuine types.
+//! 
+//! Each module that mocks a third-party crate or part of the standard library
+//! has its own `prelude`, which exports the mocks under the names of the types
+//! that they stand in for, such as `Client`, `Response`, and `Command`. This
+//! module combines the preludes of all of the enabled modules, so that a test
+//! module can obtain all of the mocks that it needs with a single glob import.
+//! The names are stable, and are the same as those used by the `swap_uses`
+//! attribute, when the `macros` feature is enabled.
+//! 
+//! Notably, `Client` and `Error` are not included here, because they are used
+//! by more than one of the mocked crates, and so would be ambiguous. They can
+//! be imported from the prelude of the relevant module instead, such as
+//! `reqwest::prelude`, which exports everything for that module.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! #[cfg(not(test))]
+//! use std::process::{Command, Stdio};
+//! #[cfg(test)]
+//! use sham::prelude::*;
+//! ```
+//! 
+
+
+
+//		Packages
+
+#[cfg(feature = "docker")]
+pub use crate::docker::prelude::{
+	Config,
+	ContainerCreateResponse,
+	ContainerWaitResponse,
+	CreateContainerOptions,
+	CreateImageInfo,
+	CreateImageOptions,
+	Docker,
+	LogOutput,
+	ProgressDetail,
+	RemoveContainerOptions,
+};
+#[cfg(feature = "kube")]
+pub use crate::kube::prelude::{
+	Api,
+	DeleteParams,
+	ErrorResponse,
+	ListParams,
+	ObjectList,
+	Patch,
+	PatchParams,
+	PostParams,
+	WatchEvent,
+	WatchParams,
+};
+#[cfg(feature = "reqwest")]
+pub use crate::reqwest::prelude::{RequestBuilder, Response};
+#[cfg(feature = "sentry")]
+pub use crate::sentry::prelude::*;
+#[cfg(feature = "std_process")]
+pub use crate::std_process::prelude::*;
+#[cfg(feature = "tls")]
+pub use crate::tls::prelude::*;
+#[cfg(feature = "unix")]
+pub use crate::unix::prelude::*;
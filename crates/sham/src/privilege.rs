@@ -0,0 +1,411 @@
+//! This module mocks privilege checks in order to test both of their branches.
+//! 
+//! This module provides a [`Privileges`] trait, for the questions that code
+//! asks about the privileges it is running with, such as "am I root?", what the
+//! effective user and group IDs are, and which Linux capabilities are held, and
+//! a [`MockPrivileges`] that answers them with scripted values. This is
+//! important because code that refuses to run as root, or that requires it, or
+//! that needs a capability such as binding to a port below 1024, can otherwise
+//! only have one of its branches tested, as the user that runs the tests is
+//! fixed by the CI system, and is rarely root.
+//! 
+//! The approach taken is that the "real" code should check its privileges
+//! through a [`Privileges`] implementation that it is given, rather than by
+//! calling `geteuid()` or reading `/proc/self/status` directly, and the test
+//! code then supplies a [`MockPrivileges`]. Some common personas are provided
+//! as constructors, including root inside a container, which has a user ID of
+//! `0` but only the restricted set of capabilities that Docker grants by
+//! default.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use sham::privilege::{MockCapability, MockPrivileges, Privileges};
+//! 
+//! let user = MockPrivileges::user(1_000, 1_000);
+//! assert!(!user.is_root());
+//! assert!(!user.has_capability(MockCapability::NetBindService));
+//! 
+//! let user = user.with_capability(MockCapability::NetBindService);
+//! assert!(user.has_capability(MockCapability::NetBindService));
+//! 
+//! let container = MockPrivileges::container_root();
+//! assert!(container.is_root());
+//! assert!(container.has_capability(MockCapability::Chown));
+//! assert!(!container.has_capability(MockCapability::SysAdmin));
+//! ```
+//! 
+
+
+
+//		Packages
+
+use core::fmt::{Display, Formatter, self};
+use std::collections::BTreeSet;
+
+
+
+//		Traits
+
+//§		Privileges																
+/// A provider of the privileges that the current process is running with.
+/// 
+/// The user and group IDs and the capability check are required, and the root
+/// check built on them is provided.
+/// 
+pub trait Privileges {
+	//		effective_gid														
+	/// Returns the effective group ID of the process.
+	fn effective_gid(&self) -> u32;
+	
+	//		effective_uid														
+	/// Returns the effective user ID of the process.
+	fn effective_uid(&self) -> u32;
+	
+	//		has_capability														
+	/// Whether the process holds the given capability in its effective set.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `capability` - The capability to check for.
+	/// 
+	fn has_capability(&self, capability: MockCapability) -> bool;
+	
+	//		is_root																
+	/// Whether the process is running as root, which is when its effective
+	/// user ID is `0`.
+	/// 
+	/// Note that this says nothing about the capabilities held, which can be
+	/// restricted even for root, such as inside a container.
+	/// 
+	fn is_root(&self) -> bool {
+		self.effective_uid() == 0
+	}
+}
+
+
+
+//		Enums
+
+//		MockCapability															
+/// A Linux capability, as described in `capabilities(7)`.
+/// 
+/// This is displayed as the name of the constant for the capability, such as
+/// `CAP_NET_BIND_SERVICE`.
+/// 
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub enum MockCapability {
+	/// Writing records to the kernel audit log.
+	AuditWrite,
+	
+	/// Changing the owners of files.
+	Chown,
+	
+	/// Bypassing the read, write, and execute permission checks of files.
+	DacOverride,
+	
+	/// Bypassing the checks that require the owner of a file to match.
+	Fowner,
+	
+	/// Keeping the set-user-ID and set-group-ID bits when a file is modified.
+	Fsetid,
+	
+	/// Locking memory, such as with `mlock()`.
+	IpcLock,
+	
+	/// Sending signals to processes that belong to other users.
+	Kill,
+	
+	/// Creating special files with `mknod()`.
+	Mknod,
+	
+	/// Configuring network interfaces, routing tables, and firewalls.
+	NetAdmin,
+	
+	/// Binding to ports below 1024.
+	NetBindService,
+	
+	/// Using raw and packet sockets.
+	NetRaw,
+	
+	/// Changing the group IDs of the process.
+	Setgid,
+	
+	/// Setting the capabilities of files.
+	Setfcap,
+	
+	/// Changing the capabilities of the process.
+	Setpcap,
+	
+	/// Changing the user IDs of the process.
+	Setuid,
+	
+	/// Performing a wide range of administrative operations, such as mounting
+	/// filesystems.
+	SysAdmin,
+	
+	/// Changing the root directory with `chroot()`.
+	SysChroot,
+	
+	/// Tracing other processes, such as with `ptrace()`.
+	SysPtrace,
+	
+	/// Overriding resource limits.
+	SysResource,
+	
+	/// Setting the system clock.
+	SysTime,
+}
+
+//󰭅		MockCapability															
+impl MockCapability {
+	//		all																	
+	/// Returns every capability, in order.
+	#[must_use]
+	pub const fn all() -> [Self; 20] {
+		[
+			Self::AuditWrite,
+			Self::Chown,
+			Self::DacOverride,
+			Self::Fowner,
+			Self::Fsetid,
+			Self::IpcLock,
+			Self::Kill,
+			Self::Mknod,
+			Self::NetAdmin,
+			Self::NetBindService,
+			Self::NetRaw,
+			Self::Setgid,
+			Self::Setfcap,
+			Self::Setpcap,
+			Self::Setuid,
+			Self::SysAdmin,
+			Self::SysChroot,
+			Self::SysPtrace,
+			Self::SysResource,
+			Self::SysTime,
+		]
+	}
+	
+	//		docker_default														
+	/// Returns the capabilities that Docker grants to a container by default.
+	#[must_use]
+	pub const fn docker_default() -> [Self; 14] {
+		[
+			Self::AuditWrite,
+			Self::Chown,
+			Self::DacOverride,
+			Self::Fowner,
+			Self::Fsetid,
+			Self::Kill,
+			Self::Mknod,
+			Self::NetBindService,
+			Self::NetRaw,
+			Self::Setgid,
+			Self::Setfcap,
+			Self::Setpcap,
+			Self::Setuid,
+			Self::SysChroot,
+		]
+	}
+	
+	//		as_str																
+	/// Returns the name of the constant for the capability, such as
+	/// `CAP_NET_BIND_SERVICE`.
+	#[must_use]
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::AuditWrite     => "CAP_AUDIT_WRITE",
+			Self::Chown          => "CAP_CHOWN",
+			Self::DacOverride    => "CAP_DAC_OVERRIDE",
+			Self::Fowner         => "CAP_FOWNER",
+			Self::Fsetid         => "CAP_FSETID",
+			Self::IpcLock        => "CAP_IPC_LOCK",
+			Self::Kill           => "CAP_KILL",
+			Self::Mknod          => "CAP_MKNOD",
+			Self::NetAdmin       => "CAP_NET_ADMIN",
+			Self::NetBindService => "CAP_NET_BIND_SERVICE",
+			Self::NetRaw         => "CAP_NET_RAW",
+			Self::Setgid         => "CAP_SETGID",
+			Self::Setfcap        => "CAP_SETFCAP",
+			Self::Setpcap        => "CAP_SETPCAP",
+			Self::Setuid         => "CAP_SETUID",
+			Self::SysAdmin       => "CAP_SYS_ADMIN",
+			Self::SysChroot      => "CAP_SYS_CHROOT",
+			Self::SysPtrace      => "CAP_SYS_PTRACE",
+			Self::SysResource    => "CAP_SYS_RESOURCE",
+			Self::SysTime        => "CAP_SYS_TIME",
+		}
+	}
+}
+
+//󰭅		Display																	
+impl Display for MockCapability {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.as_str())
+	}
+}
+
+
+
+//		Structs
+
+//		MockPrivileges															
+/// A mocked provider of process privileges, with scripted values.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MockPrivileges {
+	//		Private properties													
+	/// The capabilities held.
+	capabilities:  BTreeSet<MockCapability>,
+	
+	/// The effective group ID.
+	effective_gid: u32,
+	
+	/// The effective user ID.
+	effective_uid: u32,
+}
+
+//󰭅		MockPrivileges															
+impl MockPrivileges {
+	//		new																	
+	/// Creates a new provider for an ordinary user, with the user and group
+	/// IDs `1000`, and no capabilities.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self::user(1_000, 1_000)
+	}
+	
+	//		container_root														
+	/// Creates a new provider for root inside a container, with the user and
+	/// group IDs `0`, and only the capabilities that Docker grants by default.
+	#[must_use]
+	pub fn container_root() -> Self {
+		Self::user(0, 0).with_capabilities(MockCapability::docker_default())
+	}
+	
+	//		root																
+	/// Creates a new provider for root on the host, with the user and group IDs
+	/// `0`, and every capability.
+	#[must_use]
+	pub fn root() -> Self {
+		Self::user(0, 0).with_capabilities(MockCapability::all())
+	}
+	
+	//		user																
+	/// Creates a new provider for a user, with no capabilities.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `uid` - The effective user ID.
+	/// * `gid` - The effective group ID.
+	/// 
+	#[must_use]
+	pub const fn user(uid: u32, gid: u32) -> Self {
+		Self {
+			capabilities:  BTreeSet::new(),
+			effective_gid: gid,
+			effective_uid: uid,
+		}
+	}
+	
+	//		with_capabilities													
+	/// Adds capabilities to those held.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `capabilities` - The capabilities to add.
+	/// 
+	#[must_use]
+	pub fn with_capabilities<I: IntoIterator<Item = MockCapability>>(mut self, capabilities: I) -> Self {
+		self.capabilities.extend(capabilities);
+		self
+	}
+	
+	//		with_capability														
+	/// Adds a capability to those held.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `capability` - The capability to add.
+	/// 
+	#[must_use]
+	pub fn with_capability(mut self, capability: MockCapability) -> Self {
+		_ = self.capabilities.insert(capability);
+		self
+	}
+	
+	//		with_gid															
+	/// Sets the effective group ID.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `gid` - The effective group ID.
+	/// 
+	#[must_use]
+	pub const fn with_gid(mut self, gid: u32) -> Self {
+		self.effective_gid = gid;
+		self
+	}
+	
+	//		with_uid															
+	/// Sets the effective user ID, without changing the capabilities held.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `uid` - The effective user ID.
+	/// 
+	#[must_use]
+	pub const fn with_uid(mut self, uid: u32) -> Self {
+		self.effective_uid = uid;
+		self
+	}
+	
+	//		without_capability													
+	/// Removes a capability from those held, such as to simulate a process
+	/// that has dropped it.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `capability` - The capability to remove.
+	/// 
+	#[must_use]
+	pub fn without_capability(mut self, capability: MockCapability) -> Self {
+		_ = self.capabilities.remove(&capability);
+		self
+	}
+	
+	//		capabilities														
+	/// Returns the capabilities held, in order.
+	#[must_use]
+	pub fn capabilities(&self) -> Vec<MockCapability> {
+		self.capabilities.iter().copied().collect()
+	}
+}
+
+//󰭅		Default																	
+impl Default for MockPrivileges {
+	//		default																
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+//󰭅		Privileges																
+impl Privileges for MockPrivileges {
+	//		effective_gid														
+	fn effective_gid(&self) -> u32 {
+		self.effective_gid
+	}
+	
+	//		effective_uid														
+	fn effective_uid(&self) -> u32 {
+		self.effective_uid
+	}
+	
+	//		has_capability														
+	fn has_capability(&self, capability: MockCapability) -> bool {
+		self.capabilities.contains(&capability)
+	}
+}
@@ -0,0 +1,450 @@
+//! This module mocks interactive prompts in order to test CLI wizards.
+//! 
+//! This module provides a [`MockPrompter`] that stands in for interactive
+//! prompt libraries such as [`dialoguer`](https://crates.io/crates/dialoguer)
+//! and [`inquire`](https://crates.io/crates/inquire), covering confirmation,
+//! text input, single selection, and multiple selection prompts. Each prompt is
+//! answered from a queue of scripted answers, and the prompt text, items, and
+//! default value are recorded, so that the questions asked can be asserted upon
+//! afterwards. This is important because unit tests cannot rely upon having a
+//! TTY available, nor upon anyone being there to answer.
+//! 
+//! The approach taken is that the "real" code asks its questions through a
+//! thin prompter type that wraps the prompt library, and uses the
+//! [`MockPrompter`] in its place when running in test mode. The test code then
+//! scripts the answers, runs the code, and checks the recorded prompts.
+//! 
+//! If a prompt is asked when the next scripted answer is of a different kind,
+//! or when there are no answers left, the mock will panic, as this indicates
+//! that the code under test did not ask the questions that were expected.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use sham::prompt::{MockAnswer, MockPrompter};
+//! 
+//! let mut prompter = MockPrompter::with_answers([
+//!     MockAnswer::Input("my-project".to_owned()),
+//!     MockAnswer::Select(1),
+//!     MockAnswer::Default,
+//! ]);
+//! 
+//! assert_eq!(prompter.input("Project name", None).unwrap(), "my-project");
+//! assert_eq!(prompter.select("Licence", &["MIT", "Apache-2.0"], Some(0)).unwrap(), 1);
+//! assert!(prompter.confirm("Initialise a Git repository?", Some(true)).unwrap());
+//! 
+//! assert_eq!(prompter.records()[1].prompt, "Licence");
+//! assert_eq!(prompter.records()[2].default, Some(MockAnswer::Confirm(true)));
+//! ```
+//! 
+
+
+
+//		Packages
+
+use std::{
+	collections::VecDeque,
+	io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult},
+};
+
+
+
+//		Enums
+
+//		MockAnswer																
+/// A scripted answer to a prompt.
+/// 
+/// This is also used to describe the default value of a prompt, in the
+/// [`MockPromptRecord`].
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockAnswer {
+	/// The answer to a confirmation prompt.
+	Confirm(bool),
+	
+	/// Accept the default value of the prompt, whatever kind it is. This is
+	/// the equivalent of the user just pressing the `Enter` key.
+	Default,
+	
+	/// Fail the prompt with an error of the given kind, for instance
+	/// [`Interrupted`](IoErrorKind::Interrupted) to simulate the user
+	/// cancelling with `Ctrl+C`.
+	Error(IoErrorKind),
+	
+	/// The answer to a text input prompt.
+	Input(String),
+	
+	/// The answer to a multiple selection prompt, as the indexes of the chosen
+	/// items.
+	MultiSelect(Vec<usize>),
+	
+	/// The answer to a single selection prompt, as the index of the chosen
+	/// item.
+	Select(usize),
+}
+
+//		MockPromptKind															
+/// The kind of prompt that was asked.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockPromptKind {
+	/// A yes/no confirmation prompt.
+	Confirm,
+	
+	/// A text input prompt.
+	Input,
+	
+	/// A multiple selection prompt.
+	MultiSelect,
+	
+	/// A single selection prompt.
+	Select,
+}
+
+
+
+//		Structs
+
+//		MockPromptRecord														
+/// A record of a prompt that was asked.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockPromptRecord {
+	//		Public properties													
+	/// The kind of prompt.
+	pub kind:    MockPromptKind,
+	
+	/// The text of the prompt.
+	pub prompt:  String,
+	
+	/// The default value of the prompt, if one was given, expressed as the
+	/// answer that it would produce.
+	pub default: Option<MockAnswer>,
+	
+	/// The items offered for selection. This is empty for confirmation and
+	/// text input prompts.
+	pub items:   Vec<String>,
+}
+
+//		MockPrompter															
+/// A mocked interactive prompter.
+/// 
+/// Prompts are answered from a queue of scripted [`MockAnswer`]s, and each
+/// prompt asked is recorded as a [`MockPromptRecord`].
+/// 
+#[derive(Clone, Debug, Default)]
+pub struct MockPrompter {
+	//		Private properties													
+	/// The scripted answers that have not yet been used.
+	answers: VecDeque<MockAnswer>,
+	
+	/// The prompts that have been asked.
+	records: Vec<MockPromptRecord>,
+}
+
+//󰭅		MockPrompter															
+impl MockPrompter {
+	//		new																	
+	/// Creates a new prompter with no scripted answers.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+	
+	//		with_answers														
+	/// Creates a new prompter with the given scripted answers.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `answers` - The answers to give, in the order that the prompts are
+	///   expected to be asked.
+	/// 
+	#[must_use]
+	pub fn with_answers<I: IntoIterator<Item = MockAnswer>>(answers: I) -> Self {
+		Self {
+			answers: answers.into_iter().collect(),
+			records: Vec::new(),
+		}
+	}
+	
+	//		confirm																
+	/// Asks a yes/no confirmation question.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `prompt`  - The text of the prompt.
+	/// * `default` - The default answer, if any.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the scripted answer is
+	/// [`MockAnswer::Error`].
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if there are no scripted answers left, if the
+	/// next answer is for a different kind of prompt, or if the answer is
+	/// [`MockAnswer::Default`] and no default was given.
+	/// 
+	/// # See also
+	/// 
+	/// * [`dialoguer::Confirm`](https://docs.rs/dialoguer/latest/dialoguer/struct.Confirm.html)
+	/// * [`inquire::Confirm`](https://docs.rs/inquire/latest/inquire/struct.Confirm.html)
+	/// 
+	pub fn confirm(&mut self, prompt: &str, default: Option<bool>) -> IoResult<bool> {
+		let answer = self.answer(MockPromptKind::Confirm, prompt, default.map(MockAnswer::Confirm), Vec::new())?;
+		if let MockAnswer::Confirm(confirmed) = answer {
+			Ok(confirmed)
+		} else {
+			Self::mismatch(MockPromptKind::Confirm, prompt, &answer)
+		}
+	}
+	
+	//		input																
+	/// Asks for a line of text input.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `prompt`  - The text of the prompt.
+	/// * `default` - The default value, if any.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the scripted answer is
+	/// [`MockAnswer::Error`].
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if there are no scripted answers left, if the
+	/// next answer is for a different kind of prompt, or if the answer is
+	/// [`MockAnswer::Default`] and no default was given.
+	/// 
+	/// # See also
+	/// 
+	/// * [`dialoguer::Input`](https://docs.rs/dialoguer/latest/dialoguer/struct.Input.html)
+	/// * [`inquire::Text`](https://docs.rs/inquire/latest/inquire/struct.Text.html)
+	/// 
+	pub fn input(&mut self, prompt: &str, default: Option<&str>) -> IoResult<String> {
+		let default_answer = default.map(|value| MockAnswer::Input(value.to_owned()));
+		let answer         = self.answer(MockPromptKind::Input, prompt, default_answer, Vec::new())?;
+		if let MockAnswer::Input(text) = answer {
+			Ok(text)
+		} else {
+			Self::mismatch(MockPromptKind::Input, prompt, &answer)
+		}
+	}
+	
+	//		multiselect															
+	/// Asks for any number of items to be chosen from a list.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `prompt`   - The text of the prompt.
+	/// * `items`    - The items to choose from.
+	/// * `defaults` - Whether each item is chosen by default, if specified.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the scripted answer is
+	/// [`MockAnswer::Error`].
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if there are no scripted answers left, if the
+	/// next answer is for a different kind of prompt, if the answer is
+	/// [`MockAnswer::Default`] and no defaults were given, or if any of the
+	/// chosen indexes is outside the list of items.
+	/// 
+	/// # See also
+	/// 
+	/// * [`dialoguer::MultiSelect`](https://docs.rs/dialoguer/latest/dialoguer/struct.MultiSelect.html)
+	/// * [`inquire::MultiSelect`](https://docs.rs/inquire/latest/inquire/struct.MultiSelect.html)
+	/// 
+	pub fn multiselect<T: ToString>(
+		&mut self,
+		prompt:   &str,
+		items:    &[T],
+		defaults: Option<&[bool]>,
+	) -> IoResult<Vec<usize>> {
+		let default_answer = defaults.map(|chosen| MockAnswer::MultiSelect(
+			chosen.iter().enumerate().filter(|&(_, &is_chosen)| is_chosen).map(|(index, _)| index).collect()
+		));
+		let labels         = items.iter().map(ToString::to_string).collect::<Vec<_>>();
+		let answer         = self.answer(MockPromptKind::MultiSelect, prompt, default_answer, labels)?;
+		if let MockAnswer::MultiSelect(indexes) = answer {
+			for &index in &indexes {
+				Self::check_index(index, items.len(), prompt);
+			}
+			Ok(indexes)
+		} else {
+			Self::mismatch(MockPromptKind::MultiSelect, prompt, &answer)
+		}
+	}
+	
+	//		push_answer															
+	/// Adds an answer to the end of the scripted answer queue.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `answer` - The answer to add.
+	/// 
+	pub fn push_answer(&mut self, answer: MockAnswer) {
+		self.answers.push_back(answer);
+	}
+	
+	//		records																
+	/// Returns the prompts that have been asked, in order.
+	#[must_use]
+	pub fn records(&self) -> &[MockPromptRecord] {
+		&self.records
+	}
+	
+	//		remaining_answers													
+	/// Returns the number of scripted answers that have not yet been used.
+	#[must_use]
+	pub fn remaining_answers(&self) -> usize {
+		self.answers.len()
+	}
+	
+	//		select																
+	/// Asks for a single item to be chosen from a list.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `prompt`  - The text of the prompt.
+	/// * `items`   - The items to choose from.
+	/// * `default` - The index of the item chosen by default, if any.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the scripted answer is
+	/// [`MockAnswer::Error`].
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if there are no scripted answers left, if the
+	/// next answer is for a different kind of prompt, if the answer is
+	/// [`MockAnswer::Default`] and no default was given, or if the chosen index
+	/// is outside the list of items.
+	/// 
+	/// # See also
+	/// 
+	/// * [`dialoguer::Select`](https://docs.rs/dialoguer/latest/dialoguer/struct.Select.html)
+	/// * [`inquire::Select`](https://docs.rs/inquire/latest/inquire/struct.Select.html)
+	/// 
+	pub fn select<T: ToString>(&mut self, prompt: &str, items: &[T], default: Option<usize>) -> IoResult<usize> {
+		let labels = items.iter().map(ToString::to_string).collect::<Vec<_>>();
+		let answer = self.answer(MockPromptKind::Select, prompt, default.map(MockAnswer::Select), labels)?;
+		if let MockAnswer::Select(index) = answer {
+			Self::check_index(index, items.len(), prompt);
+			Ok(index)
+		} else {
+			Self::mismatch(MockPromptKind::Select, prompt, &answer)
+		}
+	}
+	
+	//		answer																
+	/// Records a prompt and takes the next scripted answer for it.
+	/// 
+	/// [`MockAnswer::Default`] is resolved to the default value, and
+	/// [`MockAnswer::Error`] is converted into an error.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `kind`    - The kind of prompt.
+	/// * `prompt`  - The text of the prompt.
+	/// * `default` - The default value of the prompt, if any.
+	/// * `items`   - The items offered for selection, if any.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the scripted answer is
+	/// [`MockAnswer::Error`].
+	/// 
+	fn answer(
+		&mut self,
+		kind:    MockPromptKind,
+		prompt:  &str,
+		default: Option<MockAnswer>,
+		items:   Vec<String>,
+	) -> IoResult<MockAnswer> {
+		self.records.push(MockPromptRecord {
+			kind,
+			prompt:  prompt.to_owned(),
+			default: default.clone(),
+			items,
+		});
+		let answer = self.next_answer(kind, prompt);
+		if let MockAnswer::Error(error) = answer {
+			Err(IoError::new(error, format!("Scripted error for prompt {prompt:?}")))
+		} else if answer == MockAnswer::Default {
+			Ok(Self::resolve_default(kind, prompt, default))
+		} else {
+			Ok(answer)
+		}
+	}
+	
+	//		check_index															
+	/// Checks that a chosen index is within the list of items.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `index`  - The chosen index.
+	/// * `count`  - The number of items.
+	/// * `prompt` - The text of the prompt.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the index is outside the list of items.
+	/// 
+	fn check_index(index: usize, count: usize, prompt: &str) {
+		assert!(index < count, "Scripted answer {index} is out of range for the {count} items of prompt {prompt:?}");
+	}
+	
+	//		mismatch															
+	/// Panics because the scripted answer does not match the kind of prompt.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `kind`   - The kind of prompt.
+	/// * `prompt` - The text of the prompt.
+	/// * `answer` - The scripted answer.
+	/// 
+	fn mismatch<T>(kind: MockPromptKind, prompt: &str, answer: &MockAnswer) -> T {
+		panic!("Scripted answer {answer:?} does not match {kind:?} prompt {prompt:?}");
+	}
+	
+	//		next_answer															
+	/// Takes the next scripted answer from the queue.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `kind`   - The kind of prompt.
+	/// * `prompt` - The text of the prompt.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if there are no scripted answers left.
+	/// 
+	fn next_answer(&mut self, kind: MockPromptKind, prompt: &str) -> MockAnswer {
+		self.answers.pop_front().unwrap_or_else(|| panic!("No scripted answer left for {kind:?} prompt {prompt:?}"))
+	}
+	
+	//		resolve_default														
+	/// Resolves a scripted [`MockAnswer::Default`] to the default value.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `kind`    - The kind of prompt.
+	/// * `prompt`  - The text of the prompt.
+	/// * `default` - The default value of the prompt, if any.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the prompt has no default.
+	/// 
+	fn resolve_default(kind: MockPromptKind, prompt: &str, default: Option<MockAnswer>) -> MockAnswer {
+		default.unwrap_or_else(|| panic!("Scripted default answer for {kind:?} prompt {prompt:?}, which has no default"))
+	}
+}
@@ -0,0 +1,299 @@
+//! This module provides property-testing strategies in order to test error
+//! handling.
+//! 
+//! This module implements the [`Arbitrary`] trait of [`proptest`](https://crates.io/crates/proptest)
+//! for the [`MockResponse`] and [`MockError`] types of the [`reqwest`](crate::reqwest)
+//! mock, and provides strategies for the status codes, headers, and command
+//! outputs that make them up, along with strategies for common behaviours of
+//! real servers, such as a client error with a JSON error body. This is
+//! important because code that talks to external systems has to cope with a
+//! wide range of responses, and hand-picking a few examples for each test
+//! leaves most of that range untested.
+//! 
+//! The approach taken is that each strategy generates values that a real server
+//! or command could plausibly produce, such as only valid status codes and
+//! header names, so that failures found point to genuine problems in the code
+//! under test rather than to impossible inputs. The generated responses can be
+//! returned from the responders of [`MockRoute`](crate::reqwest::MockRoute)s,
+//! or from the mocked senders set up by [`create_mock_client()`](crate::reqwest::create_mock_client()),
+//! in the same way as hand-written ones.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use proptest::{prelude::*, test_runner::TestRunner};
+//! use sham::{proptest::{client_error_status, json_error_response}, reqwest::MockResponse};
+//! 
+//! fn describe(response: &MockResponse) -> String {
+//!     if response.status.is_success() {
+//!         "ok".to_owned()
+//!     } else {
+//!         format!("failed with {}", response.status.as_u16())
+//!     }
+//! }
+//! 
+//! let mut runner = TestRunner::default();
+//! runner.run(&json_error_response("https://api.example.com/users", client_error_status()), |response| {
+//!     prop_assert!(describe(&response).starts_with("failed with 4"));
+//!     prop_assert_eq!(response.headers["content-type"].to_str().unwrap(), "application/json");
+//!     Ok(())
+//! }).unwrap();
+//! 
+//! runner.run(&any::<MockResponse>(), |response| {
+//!     prop_assert!(!describe(&response).is_empty());
+//!     Ok(())
+//! }).unwrap();
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::reqwest::{MockError, MockResponse, ReplayCount};
+use bytes::Bytes;
+use proptest::{
+	arbitrary::{Arbitrary, any},
+	collection::{btree_map, vec},
+	option,
+	prop_oneof,
+	sample::select,
+	strategy::{BoxedStrategy, Just, Strategy},
+};
+use reqwest::{
+	IntoUrl,
+	StatusCode,
+	Url,
+	Version,
+	header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE},
+};
+use serde_json::json;
+use std::sync::Arc;
+
+#[cfg(unix)]
+use std::{
+	os::unix::process::ExitStatusExt,
+	process::{ExitStatus, Output},
+};
+
+
+
+//		Constants
+
+/// The URLs that generated responses and errors are given by default.
+const URLS: [&str; 3] = [
+	"https://api.example.com/",
+	"https://api.example.com/v1/items",
+	"http://localhost:8080/health",
+];
+
+
+
+//󰭅		Arbitrary for MockError													
+impl Arbitrary for MockError {
+	type Parameters = ();
+	type Strategy   = BoxedStrategy<Self>;
+	
+	//		arbitrary_with														
+	fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+		error(url()).boxed()
+	}
+}
+
+//󰭅		Arbitrary for MockResponse												
+impl Arbitrary for MockResponse {
+	type Parameters = ();
+	type Strategy   = BoxedStrategy<Self>;
+	
+	//		arbitrary_with														
+	fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+		url().prop_flat_map(|url| response(url, status())).boxed()
+	}
+}
+
+
+
+//		Functions
+
+//		client_error_status														
+/// Generates client error status codes, from `400` to `499`.
+pub fn client_error_status() -> impl Strategy<Value = StatusCode> {
+	status_in(400, 499)
+}
+
+//		error																	
+/// Generates errors of the kinds that sending a request can produce.
+/// 
+/// Each error is a connection failure, a timeout, a failure to send the
+/// request, a failure to read or decode the body, a redirect failure, or an
+/// error status, with the URL set.
+/// 
+/// # Parameters
+/// 
+/// * `urls` - The strategy for the URL of the request that failed.
+/// 
+pub fn error<S: Strategy<Value = Url>>(urls: S) -> impl Strategy<Value = MockError> {
+	let kind = prop_oneof![
+		Just(MockError { is_connect:  true, ..Default::default() }),
+		Just(MockError { is_timeout:  true, ..Default::default() }),
+		Just(MockError { is_request:  true, ..Default::default() }),
+		Just(MockError { is_request:  true, is_timeout: true, ..Default::default() }),
+		Just(MockError { is_body:     true, ..Default::default() }),
+		Just(MockError { is_decode:   true, ..Default::default() }),
+		Just(MockError { is_redirect: true, ..Default::default() }),
+		error_status().prop_map(|status| MockError { is_status: true, status: Some(status), ..Default::default() }),
+	];
+	(kind, urls).prop_map(|(error, url)| MockError { url: Some(url), ..error })
+}
+
+//		error_status															
+/// Generates client and server error status codes, from `400` to `599`.
+pub fn error_status() -> impl Strategy<Value = StatusCode> {
+	status_in(400, 599)
+}
+
+//		headers																	
+/// Generates sets of up to eight extension headers, named `x-…`, with visible
+/// ASCII values.
+pub fn headers() -> impl Strategy<Value = HeaderMap> {
+	btree_map("x-[a-z]{1,12}(-[a-z]{1,8})?", "[!-~]([ -~]{0,30}[!-~])?", 0..=8).prop_map(|pairs| {
+		pairs.into_iter()
+			.filter_map(|(name, value)| Some((
+				HeaderName::from_bytes(name.as_bytes()).ok()?,
+				HeaderValue::from_str(&value).ok()?,
+			)))
+			.collect()
+	})
+}
+
+//		json_error_response														
+/// Generates responses with the given status codes and a JSON error body.
+/// 
+/// The body is an object with an `error` message, and sometimes a numeric
+/// `code`, which is how most JSON APIs describe their errors.
+/// 
+/// # Parameters
+/// 
+/// * `url`      - The URL of the responses.
+/// * `statuses` - The strategy for the status codes, such as
+///   [`client_error_status()`].
+/// 
+/// # Panics
+/// 
+/// This function will panic if the URL is not valid.
+/// 
+pub fn json_error_response<U, S>(url: U, statuses: S) -> impl Strategy<Value = MockResponse>
+where
+	U: IntoUrl,
+	S: Strategy<Value = StatusCode>,
+{
+	let target = url.into_url().expect("Invalid URL");
+	(statuses, headers(), "[ -~]{0,40}", option::of(any::<u32>())).prop_map(move |(status, mut headers, message, code)| {
+		let body = code.map_or_else(
+			|| json!({ "error": message }),
+			|number| json!({ "error": message, "code": number }),
+		);
+		drop(headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json")));
+		MockResponse {
+			url:     target.clone(),
+			status,
+			headers,
+			body:     Ok(Arc::new(Bytes::from(body.to_string()))),
+			chunks:   None,
+			stalled:  false,
+			trailers: None,
+			version:  Version::HTTP_11,
+			replays:  ReplayCount::default(),
+		}
+	})
+}
+
+//		output																	
+/// Generates the outputs of commands that have exited.
+/// 
+/// The exit code is usually `0` or `1`, but can be anything up to `255`, and
+/// the standard output and standard error are each a few lines of visible ASCII
+/// text, or empty.
+/// 
+#[cfg(unix)]
+pub fn output() -> impl Strategy<Value = Output> {
+	let codes = prop_oneof![
+		3 => Just(0_i32),
+		2 => Just(1_i32),
+		1 => 2_i32..=255_i32,
+	];
+	let text = || vec("[ -~]{0,60}", 0..=4).prop_map(|lines| lines.into_iter().map(|mut line| { line.push('\n'); line }).collect::<String>());
+	(codes, text(), text()).prop_map(|(code, stdout, stderr)| Output {
+		status: ExitStatus::from_raw(code.wrapping_shl(8)),
+		stdout: stdout.into_bytes(),
+		stderr: stderr.into_bytes(),
+	})
+}
+
+//		response																
+/// Generates responses with the given status codes, any extension headers, and
+/// a short body of any bytes.
+/// 
+/// # Parameters
+/// 
+/// * `url`      - The URL of the responses.
+/// * `statuses` - The strategy for the status codes, such as [`status()`].
+/// 
+/// # Panics
+/// 
+/// This function will panic if the URL is not valid.
+/// 
+pub fn response<U, S>(url: U, statuses: S) -> impl Strategy<Value = MockResponse>
+where
+	U: IntoUrl,
+	S: Strategy<Value = StatusCode>,
+{
+	let target = url.into_url().expect("Invalid URL");
+	(statuses, headers(), vec(any::<u8>(), 0..=256)).prop_map(move |(status, headers, body)| MockResponse {
+		url:     target.clone(),
+		status,
+		headers,
+		body:     Ok(Arc::new(Bytes::from(body))),
+		chunks:   None,
+		stalled:  false,
+		trailers: None,
+		version:  Version::HTTP_11,
+		replays:  ReplayCount::default(),
+	})
+}
+
+//		server_error_status														
+/// Generates server error status codes, from `500` to `599`.
+pub fn server_error_status() -> impl Strategy<Value = StatusCode> {
+	status_in(500, 599)
+}
+
+//		status																	
+/// Generates status codes from `100` to `599`, favouring success codes.
+pub fn status() -> impl Strategy<Value = StatusCode> {
+	prop_oneof![
+		4 => status_in(200, 299),
+		1 => status_in(100, 199),
+		2 => status_in(300, 399),
+		2 => status_in(400, 499),
+		2 => status_in(500, 599),
+	]
+}
+
+//		success_status															
+/// Generates success status codes, from `200` to `299`.
+pub fn success_status() -> impl Strategy<Value = StatusCode> {
+	status_in(200, 299)
+}
+
+//		status_in																
+/// Generates status codes within the given range.
+fn status_in(min: u16, max: u16) -> impl Strategy<Value = StatusCode> {
+	(min..=max).prop_map(|code| StatusCode::from_u16(code).expect("Invalid status code"))
+}
+
+//		url																		
+/// Picks one of the default URLs.
+fn url() -> impl Strategy<Value = Url> {
+	select(URLS.to_vec()).prop_map(|url| Url::parse(url).expect("Invalid URL"))
+}
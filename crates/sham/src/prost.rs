@@ -0,0 +1,268 @@
+//! This module provides Protobuf helpers for the `reqwest` mock in order to test
+//! clients that send and receive [`prost`] messages.
+//! 
+//! This module provides functions that build responses from [`prost`]
+//! messages, either as plain Protobuf bodies, as used by REST APIs that speak
+//! Protobuf, or framed as [gRPC-Web](https://github.com/grpc/grpc/blob/master/doc/PROTOCOL-WEB.md),
+//! and functions that decode the bodies of captured requests into a given
+//! message type, so that they can be compared with the messages expected. This
+//! is important because Protobuf bodies are binary, so comparing them byte by
+//! byte gives failures that cannot be read, and is fragile, as equal messages
+//! do not always encode to the same bytes.
+//! 
+//! The approach taken is that request bodies are decoded into the message type
+//! that the test expects, and compared as messages, with any difference shown
+//! using the [`Debug`] output of the messages. A request whose content type is
+//! gRPC-Web has the framing of its first message removed before it is decoded.
+//! The text form of gRPC-Web, which is Base64-encoded, is not supported.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use prost::Message;
+//! use reqwest::{Method, StatusCode};
+//! use sham::{
+//!     prost::{assert_request_body, create_mock_protobuf_response, decode_request},
+//!     reqwest::{MockRoute, create_routed_mock_client},
+//! };
+//! use std::sync::{Arc, Mutex};
+//! 
+//! #[derive(Clone, PartialEq, Message)]
+//! struct GetUser {
+//!     #[prost(uint64, tag = "1")]
+//!     id: u64,
+//! }
+//! 
+//! #[derive(Clone, PartialEq, Message)]
+//! struct User {
+//!     #[prost(string, tag = "1")]
+//!     name: String,
+//! }
+//! 
+//! let captured = Arc::new(Mutex::new(Vec::new()));
+//! let recorder = Arc::clone(&captured);
+//! let client   = create_routed_mock_client(vec![
+//!     MockRoute::new(Method::POST, "https://api.example.com/users.get", move |request| {
+//!         recorder.lock().unwrap().push(request.clone());
+//!         let query: GetUser = decode_request(request).unwrap();
+//!         Ok(create_mock_protobuf_response(request.url.as_str(), StatusCode::OK, &User { name: format!("User {}", query.id) }))
+//!     }),
+//! ]);
+//! 
+//! let response = futures_executor::block_on(async {
+//!     client.post("https://api.example.com/users.get")
+//!         .body(GetUser { id: 42 }.encode_to_vec())
+//!         .send().await.unwrap()
+//!         .bytes().await.unwrap()
+//! });
+//! assert_eq!(User::decode(response).unwrap().name, "User 42");
+//! assert_request_body(&captured.lock().unwrap()[0], &GetUser { id: 42 });
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::reqwest::{MockRequest, MockResponse, create_mock_response};
+use core::{
+	error::Error,
+	fmt::{Display, Formatter, self},
+};
+use prost::{DecodeError, Message};
+use reqwest::{
+	IntoUrl,
+	StatusCode,
+	header::CONTENT_TYPE,
+};
+use std::collections::HashMap;
+
+
+
+//		Constants
+
+/// The flag of a gRPC-Web frame that carries trailers rather than a message.
+const GRPC_WEB_TRAILERS: u8 = 0x80;
+
+
+
+//		Enums
+
+//		MockProtobufError														
+/// An error when decoding the body of a request as a Protobuf message.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockProtobufError {
+	/// The body could not be decoded as the message type.
+	Decode(DecodeError),
+	
+	/// The body is gRPC-Web, but its first frame is incomplete, or carries
+	/// trailers rather than a message.
+	InvalidFrame,
+	
+	/// The request has no body.
+	MissingBody,
+}
+
+//󰭅		Display																	
+impl Display for MockProtobufError {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match *self {
+			Self::Decode(ref err) => write!(f, "Cannot decode the Protobuf body: {err}"),
+			Self::InvalidFrame    => write!(f, "The gRPC-Web body does not start with a complete message frame"),
+			Self::MissingBody     => write!(f, "The request has no body"),
+		}
+	}
+}
+
+//󰭅		Error																	
+impl Error for MockProtobufError {
+	//		source																
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match *self {
+			Self::Decode(ref err)                  => Some(err),
+			Self::InvalidFrame | Self::MissingBody => None,
+		}
+	}
+}
+
+
+
+//		Functions
+
+//		assert_request_body														
+/// Asserts that the body of a request decodes to the expected message.
+/// 
+/// # Parameters
+/// 
+/// * `request`  - The request whose body to check.
+/// * `expected` - The expected message.
+/// 
+/// # Panics
+/// 
+/// This function will panic if the body cannot be decoded, or if it decodes to
+/// a different message, showing both messages.
+/// 
+pub fn assert_request_body<M: Message + Default + PartialEq>(request: &MockRequest, expected: &M) {
+	match decode_request::<M>(request) {
+		Ok(actual) if actual == *expected => {},
+		Ok(actual)                        => panic!("Protobuf body does not match.\nExpected: {expected:#?}\nFound:    {actual:#?}"),
+		Err(err)                          => panic!("{err}"),
+	}
+}
+
+//		create_mock_grpc_web_response											
+/// Creates a mock Reqwest response that carries a message using gRPC-Web.
+/// 
+/// The body is a frame that carries the message, followed by a frame that
+/// carries the trailers, with a `grpc-status` of `0`.
+/// 
+/// # Parameters
+/// 
+/// * `url`     - The URL of the response.
+/// * `message` - The message to respond with.
+/// 
+/// # Panics
+/// 
+/// This function will panic if the URL is not valid.
+/// 
+pub fn create_mock_grpc_web_response<U: IntoUrl, M: Message>(url: U, message: &M) -> MockResponse {
+	let mut body = frame(0, &message.encode_to_vec());
+	body.extend(frame(GRPC_WEB_TRAILERS, b"grpc-status:0\r\n"));
+	create_mock_response(
+		url,
+		StatusCode::OK,
+		Some("application/grpc-web+proto"),
+		Some(body.len()),
+		HashMap::<String, String>::new(),
+		Ok(&body),
+	)
+}
+
+//		create_mock_protobuf_response											
+/// Creates a mock Reqwest response with a Protobuf body.
+/// 
+/// The content type is `application/x-protobuf`, and the content length is set
+/// to match the body.
+/// 
+/// # Parameters
+/// 
+/// * `url`     - The URL of the response.
+/// * `status`  - The status code of the response.
+/// * `message` - The message to respond with.
+/// 
+/// # Panics
+/// 
+/// This function will panic if the URL is not valid.
+/// 
+pub fn create_mock_protobuf_response<U: IntoUrl, M: Message>(url: U, status: StatusCode, message: &M) -> MockResponse {
+	let body = message.encode_to_vec();
+	create_mock_response(
+		url,
+		status,
+		Some("application/x-protobuf"),
+		Some(body.len()),
+		HashMap::<String, String>::new(),
+		Ok(&body),
+	)
+}
+
+//		decode_request															
+/// Decodes the body of a request as a message of the given type.
+/// 
+/// If the content type of the request is gRPC-Web, the message is taken from
+/// the first frame of the body.
+/// 
+/// # Parameters
+/// 
+/// * `request` - The request whose body to decode.
+/// 
+/// # Errors
+/// 
+/// An error will be returned if the request has no body, or if the body cannot
+/// be decoded as the message type.
+/// 
+pub fn decode_request<M: Message + Default>(request: &MockRequest) -> Result<M, MockProtobufError> {
+	let body     = request.body.as_deref().ok_or(MockProtobufError::MissingBody)?;
+	let grpc_web = request.headers.get(CONTENT_TYPE)
+		.and_then(|value| value.to_str().ok())
+		.is_some_and(|value| value.trim().to_ascii_lowercase().starts_with("application/grpc-web"))
+	;
+	let payload  = if grpc_web { unframe(body).ok_or(MockProtobufError::InvalidFrame)? } else { body };
+	M::decode(payload).map_err(MockProtobufError::Decode)
+}
+
+//		matches_request															
+/// Whether the body of a request decodes to the expected message.
+/// 
+/// # Parameters
+/// 
+/// * `request`  - The request whose body to check.
+/// * `expected` - The expected message.
+/// 
+#[must_use]
+pub fn matches_request<M: Message + Default + PartialEq>(request: &MockRequest, expected: &M) -> bool {
+	decode_request::<M>(request).is_ok_and(|actual| actual == *expected)
+}
+
+//		frame																	
+/// Wraps data in a gRPC-Web frame with the given flag.
+fn frame(flag: u8, data: &[u8]) -> Vec<u8> {
+	let length    = u32::try_from(data.len()).unwrap_or(u32::MAX);
+	let mut frame = Vec::with_capacity(data.len().saturating_add(5));
+	frame.push(flag);
+	frame.extend_from_slice(&length.to_be_bytes());
+	frame.extend_from_slice(data);
+	frame
+}
+
+//		unframe																	
+/// Returns the message carried by the first gRPC-Web frame of a body, if it is
+/// complete and carries a message.
+fn unframe(body: &[u8]) -> Option<&[u8]> {
+	let (&flag, rest)     = body.split_first()?;
+	let (prefix, payload) = rest.split_first_chunk::<4>()?;
+	let length            = usize::try_from(u32::from_be_bytes(*prefix)).ok()?;
+	(flag & GRPC_WEB_TRAILERS == 0).then(|| payload.get(..length)).flatten()
+}
@@ -0,0 +1,377 @@
+//! This module provides streamed CSV and NDJSON responses for the `reqwest`
+//! mock in order to test data pipelines.
+//! 
+//! This module provides a [`MockRecordStream`], which turns a sequence of
+//! records into a response whose body is delivered in chunks, as a
+//! [CSV](https://www.rfc-editor.org/rfc/rfc4180) file or as
+//! [newline-delimited JSON](https://github.com/ndjson/ndjson-spec). This is
+//! important because code that exports or imports data usually processes the
+//! body of such a response as a stream of rows, rather than reading it all at
+//! once, and so has to cope with rows that are split across chunks, and with
+//! connections that drop part of the way through, neither of which a body that
+//! arrives in one piece ever exercises.
+//! 
+//! The approach taken is that the records are serialised when the stream is
+//! created, using [`serde`], and the body is then divided into chunks at
+//! configurable flush boundaries, either after a number of records, or after a
+//! number of bytes, which can fall in the middle of a record. The stream can
+//! also be made to fail after a number of records, in which case those records
+//! are delivered, followed by a body error, as happens when a connection is
+//! lost. The chunks are yielded by [`MockResponse::bytes_stream()`], whereas
+//! reading the whole body returns the whole body, or the error.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use futures_executor::block_on;
+//! use futures_util::StreamExt;
+//! use serde::Serialize;
+//! use sham::records::MockRecordStream;
+//! 
+//! #[derive(Serialize)]
+//! struct Sale {
+//!     id:    u32,
+//!     total: f64,
+//! }
+//! 
+//! let sales    = (1..=5).map(|id| Sale { id, total: f64::from(id) * 2.5 });
+//! let response = MockRecordStream::csv(sales)
+//!     .with_flush_every(2)
+//!     .with_error_after(3)
+//!     .response("https://api.example.com/sales.csv")
+//! ;
+//! 
+//! block_on(async {
+//!     let mut chunks = response.bytes_stream();
+//!     assert_eq!(chunks.next().await.unwrap().unwrap(), "id,total\n1,2.5\n2,5.0\n");
+//!     assert_eq!(chunks.next().await.unwrap().unwrap(), "3,7.5\n");
+//!     assert!(chunks.next().await.unwrap().unwrap_err().is_body());
+//!     assert!(chunks.next().await.is_none());
+//!     assert!(response.text().await.is_err());
+//! });
+//! 
+//! let response = MockRecordStream::ndjson([Sale { id: 1, total: 2.5 }]).response("https://api.example.com/sales");
+//! assert_eq!(response.headers()["content-type"], "application/x-ndjson");
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::reqwest::{MockError, MockRequest, MockResponse, create_mock_response};
+use bytes::Bytes;
+use csv::WriterBuilder;
+use reqwest::{
+	IntoUrl,
+	StatusCode,
+	header::{HeaderValue, TRANSFER_ENCODING},
+};
+use serde::Serialize;
+use serde_json::to_writer as to_json_writer;
+use std::{
+	collections::HashMap,
+	sync::Arc,
+};
+
+
+
+//		Enums
+
+//		Flush																	
+/// Where the body of a stream is divided into chunks.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Flush {
+	/// After the given number of bytes.
+	Bytes(usize),
+	
+	/// After the given number of records.
+	Records(usize),
+}
+
+//		MockRecordFormat														
+/// The format of the records in a [`MockRecordStream`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum MockRecordFormat {
+	/// Comma-separated values, with a header row naming the fields.
+	Csv,
+	
+	/// Newline-delimited JSON, with one JSON object per line.
+	Ndjson,
+}
+
+//󰭅		MockRecordFormat														
+impl MockRecordFormat {
+	//		content_type														
+	/// Returns the content type of a body in this format.
+	#[must_use]
+	pub const fn content_type(self) -> &'static str {
+		match self {
+			Self::Csv    => "text/csv; charset=utf-8",
+			Self::Ndjson => "application/x-ndjson",
+		}
+	}
+}
+
+
+
+//		Structs
+
+//		MockRecordStream														
+/// A sequence of records, which is delivered as a streamed response.
+/// 
+/// By default, each record is flushed in a chunk of its own, and the stream
+/// does not fail.
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[must_use]
+pub struct MockRecordStream {
+	//		Private properties													
+	/// The number of records after which the stream fails, if it does.
+	error_after: Option<usize>,
+	
+	/// Where the body is divided into chunks.
+	flush:       Flush,
+	
+	/// The format of the records.
+	format:      MockRecordFormat,
+	
+	/// The header row, if there is one.
+	header:      Option<Bytes>,
+	
+	/// The serialised records, each with its line ending.
+	records:     Vec<Bytes>,
+}
+
+//󰭅		MockRecordStream														
+impl MockRecordStream {
+	//		csv																	
+	/// Creates a new stream of records in CSV format, with a header row.
+	/// 
+	/// The header row is taken from the field names of the first record, so
+	/// the records should be structs or maps with the same fields. Nested
+	/// values are not supported by CSV.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `records` - The records to stream.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if a record cannot be encoded as CSV.
+	/// 
+	pub fn csv<I, T>(records: I) -> Self
+	where
+		I: IntoIterator<Item = T>,
+		T: Serialize,
+	{
+		let mut header = None;
+		let encoded    = records.into_iter().enumerate().map(|(index, record)| {
+			let row = encode_csv(&record, false);
+			if index == 0 {
+				let full = encode_csv(&record, true);
+				header   = Some(full.slice(..full.len().saturating_sub(row.len())));
+			}
+			row
+		}).collect();
+		Self {
+			error_after: None,
+			flush:       Flush::Records(1),
+			format:      MockRecordFormat::Csv,
+			header:      header.filter(|bytes| !bytes.is_empty()),
+			records:     encoded,
+		}
+	}
+	
+	//		ndjson																
+	/// Creates a new stream of records in NDJSON format.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `records` - The records to stream.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if a record cannot be encoded as JSON.
+	/// 
+	pub fn ndjson<I, T>(records: I) -> Self
+	where
+		I: IntoIterator<Item = T>,
+		T: Serialize,
+	{
+		let encoded = records.into_iter().map(|record| {
+			let mut line = Vec::new();
+			to_json_writer(&mut line, &record).expect("Record cannot be encoded as JSON");
+			line.push(b'\n');
+			Bytes::from(line)
+		}).collect();
+		Self {
+			error_after: None,
+			flush:       Flush::Records(1),
+			format:      MockRecordFormat::Ndjson,
+			header:      None,
+			records:     encoded,
+		}
+	}
+	
+	//		with_chunk_size														
+	/// Flushes the body after every given number of bytes, regardless of where
+	/// the records end, so that records are split across chunks.
+	/// 
+	/// This replaces any previous flush boundary.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `bytes` - The size of each chunk, in bytes. The last chunk may be
+	///   smaller. A size of `0` is treated as `1`.
+	/// 
+	pub fn with_chunk_size(mut self, bytes: usize) -> Self {
+		self.flush = Flush::Bytes(bytes.max(1));
+		self
+	}
+	
+	//		with_error_after													
+	/// Makes the stream fail after the given number of records, as though the
+	/// connection had dropped.
+	/// 
+	/// The records before the failure are delivered, followed by a body error.
+	/// If there are no more records than this, the stream does not fail.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `records` - The number of records to deliver before failing.
+	/// 
+	pub const fn with_error_after(mut self, records: usize) -> Self {
+		self.error_after = Some(records);
+		self
+	}
+	
+	//		with_flush_every													
+	/// Flushes the body after every given number of records.
+	/// 
+	/// This replaces any previous flush boundary. The header row of a CSV
+	/// stream is delivered along with the first records.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `records` - The number of records in each chunk. The last chunk may
+	///   have fewer. A number of `0` is treated as `1`.
+	/// 
+	pub fn with_flush_every(mut self, records: usize) -> Self {
+		self.flush = Flush::Records(records.max(1));
+		self
+	}
+	
+	//		without_header														
+	/// Removes the header row of a CSV stream.
+	pub fn without_header(mut self) -> Self {
+		self.header = None;
+		self
+	}
+	
+	//		chunks																
+	/// Returns the chunks in which the body is delivered, in order, up to any
+	/// failure.
+	#[must_use]
+	pub fn chunks(&self) -> Vec<Bytes> {
+		let header    = self.header.clone().unwrap_or_default();
+		let delivered = self.delivered();
+		match self.flush {
+			Flush::Bytes(size)      => {
+				let body = Bytes::from([&[header], delivered].concat().concat());
+				(0..body.len()).step_by(size).map(|start| body.slice(start..body.len().min(start.saturating_add(size)))).collect()
+			},
+			Flush::Records(records) => {
+				let mut chunks = delivered.chunks(records).map(|group| Bytes::from(group.concat())).collect::<Vec<_>>();
+				match chunks.first_mut() {
+					Some(first)                => *first = Bytes::from([header, first.clone()].concat()),
+					None if !header.is_empty() => chunks.push(header),
+					None                       => {},
+				}
+				chunks
+			},
+		}
+	}
+	
+	//		format																
+	/// Returns the format of the records.
+	#[must_use]
+	pub const fn format(&self) -> MockRecordFormat {
+		self.format
+	}
+	
+	//		respond																
+	/// Produces the streamed response for the given request.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `request` - The request to respond to.
+	/// 
+	/// # Errors
+	/// 
+	/// This function does not currently return errors, but returns a
+	/// [`Result`] in order to serve as a responder.
+	/// 
+	pub fn respond(&self, request: &MockRequest) -> Result<MockResponse, MockError> {
+		Ok(self.response(request.url.clone()))
+	}
+	
+	//		response															
+	/// Creates the streamed response.
+	/// 
+	/// The response has no `Content-Length`, and is sent with chunked transfer
+	/// encoding.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `url` - The URL of the response.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the URL is not valid.
+	/// 
+	#[must_use]
+	pub fn response<U: IntoUrl>(&self, url: U) -> MockResponse {
+		let mut response = create_mock_response(
+			url,
+			StatusCode::OK,
+			Some(self.format.content_type()),
+			None,
+			HashMap::<String, String>::new(),
+			Ok(b""),
+		);
+		drop(response.headers.insert(TRANSFER_ENCODING, HeaderValue::from_static("chunked")));
+		let chunks       = self.chunks();
+		response.body    = match self.error_after.filter(|&after| after < self.records.len()) {
+			Some(after) => Err(MockError::builder()
+				.body()
+				.message(format!("The connection was closed after {after} records"))
+				.url(response.url.clone())
+				.build()
+			),
+			None        => Ok(Arc::new(Bytes::from(chunks.concat()))),
+		};
+		response.chunks  = Some(chunks);
+		response
+	}
+	
+	//		delivered															
+	/// Returns the records that are delivered before any failure.
+	fn delivered(&self) -> &[Bytes] {
+		let count = self.error_after.map_or(self.records.len(), |after| after.min(self.records.len()));
+		self.records.get(..count).unwrap_or_default()
+	}
+}
+
+
+
+//		Functions
+
+//		encode_csv																
+/// Encodes a single record as CSV, with or without the header row.
+fn encode_csv<T: Serialize>(record: &T, header: bool) -> Bytes {
+	let mut writer = WriterBuilder::new().has_headers(header).from_writer(Vec::new());
+	writer.serialize(record).expect("Record cannot be encoded as CSV");
+	Bytes::from(writer.into_inner().expect("Record cannot be encoded as CSV"))
+}
@@ -12,6 +12,54 @@
 //! then configure the mocks to expect certain requests and to return certain
 //! responses, and then run the tests.
 //! 
+//! The [`try_create_mock_client()`] and [`try_create_mock_response()`] helpers
+//! return a [`MockUrlError`] for a fixture with an invalid URL, rather than
+//! panicking, and resolve relative URLs against the base URL set on the
+//! entered [`Context`] with [`Context::set_base_url()`].
+//! 
+//! Alternatively, a client can be created from a set of [`MockRoute`]s using
+//! [`create_routed_mock_client()`], in which case each request is passed to the
+//! responder of the route that matches it, along with the method, URL, headers,
+//! and body that were sent, so that the response can depend upon the request.
+//! Streaming bodies are consumed chunk by chunk, recording the size and timing
+//! of each chunk, and routes can be made to slow down or break off uploads, so
+//! that upload progress reporting and retry logic can be tested. Routes can also
+//! be given a [`FaultPlan`], which injects latency and errors into the requests
+//! that they serve, or a set of [`MockWeightedResponses`], which chooses between
+//! several responses at random, in proportion to their weights, so that a
+//! backend that fails some of the time can be simulated reproducibly. A set of
+//! [`MockRepresentations`] instead chooses between several bodies according to
+//! the `Accept` header of each request, so that clients that negotiate formats
+//! can be tested for each of them. A client
+//! created with [`create_pooled_mock_client()`] also sends its requests over
+//! the simulated connections of a [`MockConnectionPool`], so that the effects
+//! of connection pooling settings can be observed, and one created with
+//! [`create_limited_mock_client()`] enforces [`MockLimits`] on the sizes of
+//! the responses that it receives. A client created with
+//! [`create_layered_mock_client()`] serves a few routes of its own on top of
+//! another client, and passes every other request on to it, so that a shared
+//! fixture can be specialised for a test without being rebuilt.
+//! 
+//! Waits are driven by the virtual clock of the [`Context`] entered on the
+//! current thread, when the `clock` feature is enabled. Injected latency, route
+//! delays, upload delays, and request timeouts then wait on the clock rather
+//! than for real time to pass. Waits that run concurrently overlap, just as
+//! they would in real time, so the clock only moves as far as the longest of
+//! them. A request given a [`timeout()`](MockRequestBuilder::timeout()) fails
+//! with a timeout error once that much virtual time has been spent, and
+//! [`sleep()`] can be used in place of the sleep function of an async runtime,
+//! such as when waiting for the time given by
+//! [`retry_after()`](MockResponse::retry_after()). This means that retry and
+//! backoff logic behaves in the same way as it does in production, but without
+//! the test waiting, and without the need to pause the time of the runtime.
+//! Without an entered context, real time is used.
+//! 
+//! The mocks also compile for the `wasm32-unknown-unknown` target, so that
+//! front-end applications that use the WebAssembly backend of Reqwest can share
+//! their fixtures with server code, and run them under `wasm-bindgen-test`. Any
+//! delays then use browser timers rather than threads, and as request bodies
+//! cannot be streamed in WebAssembly, each body is received as a single chunk.
+//! 
 //! # Examples
 //! 
 //! ```rust
@@ -31,31 +79,127 @@
 
 //		Packages
 
+use crate::{
+	context::{Context, mismatch_message},
+	fault::{FaultKind, FaultPlan},
+	seed::{seed_for, split_mix64},
+};
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
 use bytes::Bytes;
 use core::{
 	error::Error,
 	fmt::{Debug, Display, Formatter, self},
+	future::{Future, self},
 	hash::BuildHasher,
 	pin::Pin,
+	sync::atomic::{AtomicUsize, Ordering},
+	time::Duration,
+};
+use futures_util::{
+	future::join_all,
+	stream::{Stream, StreamExt, self},
 };
-use futures_util::stream::{Stream, self};
 #[allow(clippy::useless_attribute, reason = "Not useless! Here for the false positive")]
 #[allow(clippy::allow_attributes,  reason = "False positive lint")]
 #[allow(unused_imports,            reason = "False positive due to mocks")]
-use mockall::{Sequence, concretize, mock};
+use mockall::{concretize, mock};
 use reqwest::{
 	Body,
 	IntoUrl,
+	Method,
 	StatusCode,
 	Url,
-	header::{HeaderMap, HeaderName, CONTENT_LENGTH, CONTENT_TYPE},
+	Version,
+	header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE, USER_AGENT, VARY},
 };
 use serde::{Serialize, de::DeserializeOwned};
 use serde_json::from_slice as from_json_slice;
 use std::{
-	collections::HashMap,
-	sync::Arc,
+	collections::{HashMap, VecDeque},
+	sync::{Arc, Mutex, PoisonError},
+	thread,
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+#[cfg(feature = "scenario")]
+use crate::scenario::MockScenario;
+#[cfg(not(target_arch = "wasm32"))]
+use core::{
+	future::poll_fn,
+	task::{Poll, Waker},
 };
+#[cfg(not(target_arch = "wasm32"))]
+use http_body::Body as HttpBody;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+
+
+//		Modules
+
+//		prelude																	
+/// The mocks, named after the genuine types.
+/// 
+/// This allows the mocks for the [`reqwest`] crate to be imported with a single
+/// glob import when testing, without renaming them. The names are the same as
+/// those used by the `swap_uses` attribute, and by the combined
+/// [`prelude`](crate::prelude).
+/// 
+pub mod prelude {
+	pub use super::{
+		MockClient         as Client,
+		MockError          as Error,
+		MockRequestBuilder as RequestBuilder,
+		MockResponse       as Response,
+	};
+}
+
+
+
+//		Types
+
+/// A request expected by a client created by [`create_mock_client()`], the
+/// headers that it must send, and the response to return for it.
+type ExpectedRequest = (Url, HeaderMap, Result<MockResponse, MockError>);
+
+/// A function that produces the response for a request matched by a route,
+/// given the details of the call.
+type Responder = Arc<dyn Fn(&MockRequest, &MockInvocation) -> Result<MockResponse, MockError> + Send + Sync>;
+
+/// A function that produces the response for a request.
+type SimpleResponder = Arc<dyn Fn(&MockRequest) -> Result<MockResponse, MockError> + Send + Sync>;
+
+
+
+//		Constants
+
+/// The pairs of [`MockError`] flags that cannot both be set, as the real error
+/// is never both at once.
+const CONFLICTS: [(&str, &str); 20] = [
+	("builder",  "body"),
+	("builder",  "connect"),
+	("builder",  "decode"),
+	("builder",  "redirect"),
+	("builder",  "request"),
+	("builder",  "status"),
+	("builder",  "timeout"),
+	("connect",  "body"),
+	("connect",  "decode"),
+	("connect",  "redirect"),
+	("decode",   "redirect"),
+	("decode",   "request"),
+	("decode",   "timeout"),
+	("redirect", "body"),
+	("status",   "body"),
+	("status",   "connect"),
+	("status",   "decode"),
+	("status",   "redirect"),
+	("status",   "request"),
+	("status",   "timeout"),
+];
+
+/// The abbreviated names of the months, as used in HTTP dates.
+const MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
 
 
 
@@ -138,6 +282,22 @@ mock! {
 		/// 
 		#[concretize]
 		pub fn put<U: IntoUrl>(&self, url: U) -> MockRequestBuilder;
+		
+		//		request															
+		/// Creates a request builder for a request with the given method to the
+		/// given URL.
+		/// 
+		/// # Parameters
+		/// 
+		/// * `method` - The HTTP method of the request.
+		/// * `url`    - The URL to create a request builder for.
+		/// 
+		/// # See also
+		/// 
+		/// * [`reqwest::Client::request()`]
+		/// 
+		#[concretize]
+		pub fn request<U: IntoUrl>(&self, method: Method, url: U) -> MockRequestBuilder;
 	}
 	
 	//󰭅		Clone																
@@ -158,14 +318,15 @@ mock! {
 	}
 }
 
-//		RequestBuilder															
+//		RequestSender															
 mock! {
-	/// A mocked Reqwest request builder.
+	/// A mocked sender for a Reqwest request builder.
 	/// 
-	/// This is mocked by [`mockall`], and allows configuration of expected
-	/// requests and responses for testing.
+	/// This is mocked by [`mockall`], and allows configuration of the response
+	/// to return when a [`MockRequestBuilder`] that did not come from a routed
+	/// client is sent.
 	/// 
-	pub RequestBuilder {
+	pub RequestSender {
 		//		send															
 		/// Sends the request and returns the response.
 		/// 
@@ -175,159 +336,1003 @@ mock! {
 		/// 
 		pub async fn send(&self) -> Result<MockResponse, MockError>;
 	}
+	
+	//󰭅		Debug																
+	impl Debug for RequestSender {
+		//		fmt																
+		#[concretize]
+		fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+			write!(f, "Mocked Reqwest request sender")
+		}
+	}
 }
 
-//󰭅		RequestBuilder															
-impl MockRequestBuilder {
-	//		body																
-	/// Set the request body.
+
+
+//		Enums
+
+//		MockCredentials															
+/// The credentials carried by the `Authorization` header of a request.
+/// 
+/// This is not mocked by [`mockall`], and is a simple supporting type.
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockCredentials {
+	/// A username and password, as set by
+	/// [`basic_auth()`](MockRequestBuilder::basic_auth()). An empty password is
+	/// given as [`None`], as the two cannot be told apart once encoded.
+	Basic(String, Option<String>),
+	
+	/// A bearer token, as set by
+	/// [`bearer_auth()`](MockRequestBuilder::bearer_auth()).
+	Bearer(String),
+}
+
+//		MockDeadlineFormat														
+/// The ways in which a request can carry its deadline in a header.
+/// 
+/// This is not mocked by [`mockall`], and is a simple supporting type.
+/// 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockDeadlineFormat {
+	/// The time remaining, in the form used by the `grpc-timeout` header of
+	/// gRPC, which is a number of up to 8 digits followed by a unit of `H`,
+	/// `M`, `S`, `m`, `u`, or `n`, such as `250m` for 250 milliseconds.
+	GrpcTimeout,
+	
+	/// The time remaining, as a whole number of milliseconds.
+	RelativeMillis,
+	
+	/// The time at which the deadline passes, as a whole number of milliseconds
+	/// since the Unix epoch.
+	UnixMillis,
+}
+
+//󰭅		MockDeadlineFormat														
+impl MockDeadlineFormat {
+	//		parse																
+	/// Parses the value of a deadline header into the time remaining.
 	/// 
-	/// Note, this is a supporting function in order to provide compatible
-	/// functionality, and the mocked version actually does nothing.
+	/// A deadline that has already passed leaves no time remaining, rather than
+	/// failing to parse.
 	/// 
-	/// # See also
+	/// # Parameters
 	/// 
-	/// * [`reqwest::RequestBuilder::body()`]
+	/// * `value` - The value of the header.
+	/// * `now`   - The current time, which an absolute deadline is measured
+	///   from.
 	/// 
-	#[expect(unused_mut,                     reason = "Needed for compatibility with the real Reqwest")]
-	#[expect(clippy::needless_pass_by_value, reason = "Needed for compatibility with the real Reqwest")]
 	#[must_use]
-	pub fn body<T: Into<Body>>(mut self, _body: T) -> Self {
-		self
+	pub fn parse(self, value: &str, now: SystemTime) -> Option<Duration> {
+		match self {
+			Self::GrpcTimeout    => {
+				let (amount, unit) = value.split_at_checked(value.len().checked_sub(1)?)?;
+				if amount.is_empty() || amount.len() > 8 || !amount.bytes().all(|byte| byte.is_ascii_digit()) {
+					return None;
+				}
+				let number         = amount.parse::<u64>().ok()?;
+				match unit {
+					"H" => Some(Duration::from_secs(number.checked_mul(3_600)?)),
+					"M" => Some(Duration::from_secs(number.checked_mul(60)?)),
+					"S" => Some(Duration::from_secs(number)),
+					"m" => Some(Duration::from_millis(number)),
+					"u" => Some(Duration::from_micros(number)),
+					"n" => Some(Duration::from_nanos(number)),
+					_   => None,
+				}
+			},
+			Self::RelativeMillis => value.parse::<u64>().ok().map(Duration::from_millis),
+			Self::UnixMillis     => {
+				let deadline = UNIX_EPOCH.checked_add(Duration::from_millis(value.parse::<u64>().ok()?))?;
+				Some(deadline.duration_since(now).unwrap_or(Duration::ZERO))
+			},
+		}
 	}
+}
+
+//		MockErrorBuilderError													
+/// An error when building a [`MockError`] that could never be produced by the
+/// real error type.
+/// 
+/// This is not mocked by [`mockall`], and is a simple supporting type.
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[expect(variant_size_differences, reason = "Acceptable here")]
+#[non_exhaustive]
+pub enum MockErrorBuilderError {
+	/// Two flags were set that contradict each other, such as a builder error
+	/// that is also a timeout.
+	Conflict(&'static str, &'static str),
 	
-	//		form																
-	/// Specify to send a form body.
-	///
-	/// Note, this is a supporting function in order to provide compatible
-	/// functionality, and the mocked version actually does nothing.
-	///
-	/// # See also
+	/// A status was given that is not a client or server error, which the real
+	/// error never carries.
+	InvalidStatus(StatusCode),
+	
+	/// Both a message and a source were given, when the message is the source.
+	MessageAndSource,
+}
+
+//󰭅		Display																	
+impl Display for MockErrorBuilderError {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match *self {
+			Self::Conflict(first, second) => write!(f, "A {first} error cannot also be a {second} error"),
+			Self::InvalidStatus(status)   => write!(f, "A status error must have a 4xx or 5xx status, not {status}"),
+			Self::MessageAndSource        => write!(f, "An error can have a message or a source, but not both"),
+		}
+	}
+}
+
+//󰭅		Error																	
+impl Error for MockErrorBuilderError {}
+
+//		MockUrlError															
+/// An error when resolving the URL of a fixture, such as one given to
+/// [`try_create_mock_client()`].
+/// 
+/// This is not mocked by [`mockall`], and is a simple supporting type.
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockUrlError {
+	/// The URL of one entry in a list of fixtures could not be resolved. The
+	/// position of the entry is counted from 1.
+	Entry(usize, Box<Self>),
+	
+	/// The URL could not be parsed, for the given reason.
+	Invalid(String, String),
+	
+	/// The URL is relative, but no base URL has been set on the context with
+	/// [`Context::set_base_url()`].
+	Relative(String),
+}
+
+//󰭅		Display																	
+impl Display for MockUrlError {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match *self {
+			Self::Entry(position, ref error) => write!(f, "Entry {position} of the fixtures has an invalid URL: {error}"),
+			Self::Invalid(ref url, ref reason) => write!(f, "Invalid URL `{url}`: {reason}"),
+			Self::Relative(ref url)            => write!(f, "Relative URL `{url}` cannot be resolved, as no base URL has been set"),
+		}
+	}
+}
+
+//󰭅		Error																	
+impl Error for MockUrlError {
+	//		source																
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match *self {
+			Self::Entry(_, ref error)            => Some(&**error),
+			Self::Invalid(..) | Self::Relative(_) => None,
+		}
+	}
+}
+
+
+
+//		Structs
+
+//		Deadline																
+/// The time allowed for a request, and the time that it has spent waiting so
+/// far.
+struct Deadline {
+	//		Private properties													
+	/// The total time allowed, if limited.
+	limit: Option<Duration>,
+	
+	/// The time spent waiting so far.
+	spent: Duration,
+}
+
+//󰭅		Deadline																
+impl Deadline {
+	//		expire																
+	/// Waits for the rest of the time allowed, if it is limited.
+	async fn expire(&mut self) {
+		if let Some(limit) = self.limit {
+			sleep(limit.saturating_sub(self.spent)).await;
+			self.spent = limit;
+		}
+	}
+	
+	//		wait																
+	/// Waits for the given time, unless that would take longer than the time
+	/// allowed, in which case the rest of the time allowed is waited for and a
+	/// timeout error is returned.
+	async fn wait(&mut self, duration: Duration, url: &Url) -> Result<(), MockError> {
+		if self.limit.is_some_and(|limit| self.spent.saturating_add(duration) > limit) {
+			self.expire().await;
+			return fault_result(FaultKind::Timeout, url).map(drop);
+		}
+		sleep(duration).await;
+		self.spent = self.spent.saturating_add(duration);
+		Ok(())
+	}
+}
+
+//		InFlight																
+/// The number of requests that a route is serving at once, and the most that
+/// it has served at once, shared by the clones of the route.
+#[derive(Debug, Default)]
+struct InFlight {
+	//		Private properties													
+	/// The number of requests being served now.
+	current: AtomicUsize,
+	
+	/// The most requests that have been served at once.
+	peak:    AtomicUsize,
+}
+
+//󰭅		InFlight																
+impl InFlight {
+	//		enter																
+	/// Counts a request as being served, until the returned guard is dropped.
 	/// 
-	/// * [`reqwest::RequestBuilder::form()`]
+	/// The guard is dropped whether the request completes or is cancelled, so
+	/// that a request abandoned by a timeout in the code under test does not
+	/// count as in flight forever.
 	/// 
-	#[expect(unused_mut, reason = "Needed for compatibility with the real Reqwest")]
-	#[must_use]
-	pub const fn form<T: Serialize + ?Sized>(mut self, _form: &T) -> Self {
-		self
+	fn enter(&self) -> InFlightGuard<'_> {
+		let current = self.current.fetch_add(1, Ordering::SeqCst) + 1;
+		_ = self.peak.fetch_max(current, Ordering::SeqCst);
+		InFlightGuard(self)
+	}
+}
+
+//		InFlightGuard															
+/// A guard that counts a request as in flight until it is dropped.
+struct InFlightGuard<'f>(&'f InFlight);
+
+//󰭅		Drop																	
+impl Drop for InFlightGuard<'_> {
+	//		drop																
+	fn drop(&mut self) {
+		_ = self.0.current.fetch_sub(1, Ordering::SeqCst);
 	}
+}
+
+//		ExpectedRequests														
+/// The requests that a client created by [`create_mock_client()`] expects,
+/// in order, along with their responses.
+/// 
+/// This checks each request as it is made, and panics with a description of
+/// the difference if it is not the one expected, or if it is made after all of
+/// the expected requests. If a [`Context`] is entered on the current thread
+/// when the list is created, a check is registered with it that fails with a
+/// list of any expected requests that were never made. Otherwise, the list
+/// panics with that message when the client is dropped, unless the thread is
+/// already panicking.
+/// 
+struct ExpectedRequests {
+	//		Private properties													
+	/// Whether the check for missing requests has been registered with a
+	/// context, rather than being made when the list is dropped.
+	registered: bool,
 	
-	//		headers																
-	/// Adds headers to the request.
-	///
-	/// Note, this is a supporting function in order to provide compatible
-	/// functionality, and the mocked version actually does nothing.
-	///
-	/// # See also
+	/// The requests still expected, with their responses, shared with the
+	/// registered check.
+	remaining:  Arc<Mutex<VecDeque<ExpectedRequest>>>,
+	
+	/// The total number of requests expected.
+	total:      usize,
+}
+
+//󰭅		ExpectedRequests														
+impl ExpectedRequests {
+	//		new																	
+	/// Creates a new list of expected requests, registering the check for
+	/// missing requests with the context entered on the current thread, if
+	/// any.
+	fn new(requests: VecDeque<ExpectedRequest>) -> Self {
+		let total      = requests.len();
+		let remaining  = Arc::new(Mutex::new(requests));
+		let registered = Context::current().map(|context| {
+			let pending = Arc::clone(&remaining);
+			context.register("create_mock_client()", move || {
+				Self::check(total, &pending.lock().unwrap_or_else(PoisonError::into_inner))
+			});
+		}).is_some();
+		Self { registered, remaining, total }
+	}
+	
+	//		check																
+	/// Checks that all of the expected requests have been made.
 	/// 
-	/// * [`reqwest::RequestBuilder::headers()`]
+	/// # Parameters
 	/// 
-	#[expect(unused_mut,                     reason = "Needed for compatibility with the real Reqwest")]
-	#[expect(clippy::needless_pass_by_value, reason = "Needed for compatibility with the real Reqwest")]
-	#[must_use]
-	pub fn headers(mut self, _headers: HeaderMap) -> Self {
-		self
+	/// * `total`     - The total number of requests expected.
+	/// * `remaining` - The requests still expected.
+	/// 
+	/// # Errors
+	/// 
+	/// An error message listing the requests that were never made will be
+	/// returned if there are any.
+	/// 
+	fn check(total: usize, remaining: &VecDeque<ExpectedRequest>) -> Result<(), String> {
+		if remaining.is_empty() {
+			return Ok(());
+		}
+		let missing = remaining.iter().map(|entry| &entry.0).fold(String::new(), |mut list, url| {
+			list.push_str("\n  - GET ");
+			list.push_str(url.as_str());
+			list
+		});
+		Err(format!(
+			"create_mock_client() expected {total} GET request(s), but {} of them were never made:{missing}",
+			remaining.len(),
+		))
 	}
 	
-	//		json																
-	/// Specify to send a JSON body.
-	///
-	/// Note, this is a supporting function in order to provide compatible
-	/// functionality, and the mocked version actually does nothing.
-	///
-	/// # See also
+	//		next																
+	/// Checks that the given URL is the next one expected, and returns the
+	/// response for it.
 	/// 
-	/// * [`reqwest::RequestBuilder::json()`]
+	/// # Panics
 	/// 
-	#[expect(unused_mut, reason = "Needed for compatibility with the real Reqwest")]
-	#[must_use]
-	pub const fn json<T: Serialize + ?Sized>(mut self, _json: &T) -> Self {
-		self
+	/// This function will panic if the URL is not the next one expected, or if
+	/// there are no more requests expected.
+	/// 
+	fn next(&self, url: &str) -> ExpectedRequest {
+		let mut remaining = self.remaining.lock().unwrap_or_else(PoisonError::into_inner);
+		let number        = self.total.saturating_sub(remaining.len()).saturating_add(1);
+		let Some((expected, headers, response)) = remaining.pop_front() else {
+			drop(remaining);
+			panic!(
+				"create_mock_client() expected {} GET request(s), all of which have been made, but received another: GET {url}",
+				self.total,
+			);
+		};
+		drop(remaining);
+		assert!(expected.as_str() == url, "{}", mismatch_message(
+			&format!("create_mock_client() expected request {number} of {} to be GET {expected}", self.total),
+			&format!("GET {expected}"),
+			&format!("GET {url}"),
+		));
+		(expected, headers, response)
 	}
 }
 
+//󰭅		Drop																	
+impl Drop for ExpectedRequests {
+	//		drop																
+	fn drop(&mut self) {
+		if self.registered || thread::panicking() {
+			return;
+		}
+		let result = Self::check(self.total, &self.remaining.lock().unwrap_or_else(PoisonError::into_inner));
+		if let Err(message) = result {
+			panic!("{message}");
+		}
+	}
+}
 
-
-//		Structs
-
-//		MockError																
-/// A mocked error type for Reqwest.
-/// 
-/// Notably, the real [`reqwest::Error`] type cannot be created externally, and
-/// has logic that depends on private types. Instead of trying to reproduce that
-/// logic, this mock simply provides the means to specify what should be
-/// returned when using the functions provided by the error type.
+//		MockBodyChunk															
+/// A chunk of a streaming request body, as received by a routed client.
 /// 
 /// This is not mocked by [`mockall`], and is a simple supporting type.
 /// 
-#[expect(clippy::struct_excessive_bools, reason = "Acceptable here")]
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[non_exhaustive]
-pub struct MockError {
+pub struct MockBodyChunk {
 	//		Public properties													
-	/// Whether the error is related to the request or response body.
-	pub is_body:     bool,
-	
-	/// Whether the error is from a type builder.
-	pub is_builder:  bool,
-	
-	/// Whether the error is related to making a connection.
-	pub is_connect:  bool,
-	
-	/// Whether the error is related to decoding the response body.
-	pub is_decode:   bool,
+	/// The time between sending the request and receiving the chunk.
+	pub elapsed: Duration,
 	
-	/// Whether the error is from a redirect policy.
-	pub is_redirect: bool,
-	
-	/// Whether the error is related to the request.
-	pub is_request:  bool,
+	/// The size of the chunk, in bytes.
+	pub size:    usize,
+}
+
+//		MockConnection															
+/// A simulated connection opened by a client created by
+/// [`create_pooled_mock_client()`].
+/// 
+/// This is not mocked by [`mockall`], and is a simple supporting type.
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockConnection {
+	//		Public properties													
+	/// The identity of the connection, which is its position in the order in
+	/// which the connections were opened, starting from 0.
+	pub id:       usize,
 	
-	/// Whether the error is from [`Response::error_for_status()`](reqwest::Response::error_for_status).
-	pub is_status:   bool,
+	/// The origin that the connection was opened to, such as
+	/// `https://api.example.com`, including the port if it is not the default.
+	pub origin:   String,
 	
-	/// Whether the error is related to a timeout.
-	pub is_timeout:  bool,
+	/// The number of requests sent over the connection, including the one that
+	/// opened it.
+	pub requests: usize,
+}
+
+//		MockConnectionPool														
+/// A simulated pool of connections for a mocked Reqwest client.
+/// 
+/// Reqwest keeps the connections that it opens, and reuses an idle one to the
+/// same origin for the next request, rather than opening another. Its builder
+/// can limit how long a connection may stay idle, and how many idle connections
+/// are kept for each host. Neither has any observable effect on a mocked
+/// client, so this simulates the pool instead, so that the configuration can
+/// be asserted on by counting the connections that were opened and reused.
+/// 
+/// Each request that is sent takes an idle connection to its origin, if there
+/// is one, or opens a new one, and returns it to the pool once the response
+/// has been produced. Requests that are in flight at the same time therefore
+/// use separate connections. A connection is closed rather than returned if
+/// the request fails, if it has been idle for longer than the
+/// [idle timeout](MockConnectionPool::with_idle_timeout()), or if the pool
+/// already holds the [maximum](MockConnectionPool::with_max_idle_per_host())
+/// number of idle connections to that host. Idle time is measured using the
+/// virtual clock of the entered context when the `clock` feature is enabled.
+/// 
+/// Clones share the same connections, just as the clones of a Reqwest client
+/// share its pool, so a pool can be cloned before it is given to a client in
+/// order to check it afterwards. Separately built clients should each be given
+/// their own pool.
+/// 
+/// This is not mocked by [`mockall`], and is a simple supporting type.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use futures_executor::block_on;
+/// use reqwest::{Method, StatusCode};
+/// use sham::reqwest::{MockConnectionPool, MockRoute, create_mock_response, create_pooled_mock_client};
+/// use std::{collections::HashMap, time::Duration};
+/// 
+/// let pool   = MockConnectionPool::new().with_idle_timeout(Duration::from_secs(30));
+/// let client = create_pooled_mock_client(vec![
+///     MockRoute::prefixed(Method::GET, "https://api.example.com", |request| {
+///         Ok(create_mock_response(request.url.as_str(), StatusCode::OK, None::<&str>, None, HashMap::<String, String>::new(), Ok(b"")))
+///     }),
+///     MockRoute::prefixed(Method::GET, "https://auth.example.com", |request| {
+///         Ok(create_mock_response(request.url.as_str(), StatusCode::OK, None::<&str>, None, HashMap::<String, String>::new(), Ok(b"")))
+///     }),
+/// ], &pool);
+/// 
+/// for url in ["https://auth.example.com/token", "https://api.example.com/a", "https://api.example.com/b"] {
+///     block_on(client.get(url).send()).unwrap();
+/// }
+/// assert_eq!(pool.opened(), 2);
+/// assert_eq!(pool.reused(), 1);
+/// assert_eq!(pool.connections()[1].requests, 2);
+/// ```
+/// 
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct MockConnectionPool {
+	//		Private properties													
+	/// The time after which an idle connection is closed, if limited.
+	idle_timeout:      Option<Duration>,
 	
-	/// The status code, if the error was generated from a response.
-	pub status:      Option<StatusCode>,
+	/// The most idle connections to keep for each host.
+	max_idle_per_host: usize,
 	
-	/// A possible URL related to this error.
-	pub url:         Option<Url>,
+	/// The connections opened so far, and those that are idle, shared by the
+	/// clones of the pool.
+	state:             Arc<Mutex<PoolState>>,
 }
 
-//󰭅		MockError																
-impl MockError {
-	//		is_body																
-	/// Whether the error is related to the request or response body.
-	/// 
-	/// # See also
-	/// 
-	/// * [`reqwest::Error::is_body()`]
-	/// 
-	#[must_use]
-	pub const fn is_body(&self) -> bool {
-		self.is_body
+//󰭅		MockConnectionPool														
+impl MockConnectionPool {
+	//		new																	
+	/// Creates a new, empty pool, with the same defaults as Reqwest: idle
+	/// connections are closed after 90 seconds, and there is no limit on how
+	/// many are kept for each host.
+	pub fn new() -> Self {
+		Self {
+			idle_timeout:      Some(Duration::from_secs(90)),
+			max_idle_per_host: usize::MAX,
+			state:             Arc::new(Mutex::new(PoolState::default())),
+		}
 	}
 	
-	//		is_builder															
-	/// Whether the error is from a type builder.
+	//		with_idle_timeout													
+	/// Sets the time after which an idle connection is closed.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `timeout` - The time that a connection may stay idle, or [`None`] for
+	///   no limit.
 	/// 
 	/// # See also
 	/// 
-	/// * [`reqwest::Error::is_builder()`]
+	/// * [`reqwest::ClientBuilder::pool_idle_timeout()`]
 	/// 
-	#[must_use]
-	pub const fn is_builder(&self) -> bool {
-		self.is_builder
+	pub fn with_idle_timeout<D: Into<Option<Duration>>>(mut self, timeout: D) -> Self {
+		self.idle_timeout = timeout.into();
+		self
 	}
 	
-	//		is_connect															
-	/// Whether the error is related to making a connection.
+	//		with_max_idle_per_host												
+	/// Sets the most idle connections to keep for each host.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `max` - The number of idle connections to keep. Zero means that no
+	///   connection is ever reused.
 	/// 
 	/// # See also
 	/// 
-	/// * [`reqwest::Error::status()`]
+	/// * [`reqwest::ClientBuilder::pool_max_idle_per_host()`]
+	/// 
+	pub const fn with_max_idle_per_host(mut self, max: usize) -> Self {
+		self.max_idle_per_host = max;
+		self
+	}
+	
+	//		connections															
+	/// Returns the connections opened so far, in the order in which they were
+	/// opened.
+	#[must_use]
+	pub fn connections(&self) -> Vec<MockConnection> {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner).connections.clone()
+	}
+	
+	//		idle																
+	/// Returns the number of connections that are idle in the pool, without
+	/// closing any that have passed the idle timeout.
+	#[must_use]
+	pub fn idle(&self) -> usize {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner).idle.len()
+	}
+	
+	//		opened																
+	/// Returns the number of connections opened so far.
+	#[must_use]
+	pub fn opened(&self) -> usize {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner).connections.len()
+	}
+	
+	//		reused																
+	/// Returns the number of requests sent over a connection that had already
+	/// been used.
+	#[must_use]
+	pub fn reused(&self) -> usize {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner).connections.iter()
+			.map(|connection| connection.requests.saturating_sub(1))
+			.sum()
+	}
+	
+	//		checkout															
+	/// Takes an idle connection to the origin of the given URL from the pool,
+	/// or opens a new one, and returns its identity.
+	fn checkout(&self, url: &Url) -> usize {
+		let origin    = url.origin().ascii_serialization();
+		let current   = now();
+		let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+		let timeout   = self.idle_timeout;
+		state.idle.retain(|&(_, since)| timeout.map_or(true, |limit| {
+			current.duration_since(since).unwrap_or(Duration::ZERO) <= limit
+		}));
+		let reusable  = state.idle_to(&origin).next();
+		let id        = if let Some(index) = reusable {
+			state.idle.remove(index).0
+		} else {
+			let opened = state.connections.len();
+			state.connections.push(MockConnection { id: opened, origin, requests: 0 });
+			opened
+		};
+		state.connections[id].requests += 1;
+		drop(state);
+		id
+	}
+	
+	//		release																
+	/// Returns a connection to the pool once its request has completed, unless
+	/// the pool already holds as many idle connections to its origin as it may.
+	fn release(&self, id: usize) {
+		let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+		if state.idle_to(&state.connections[id].origin).count() < self.max_idle_per_host {
+			state.idle.push((id, now()));
+		}
+		drop(state);
+	}
+}
+
+//󰭅		Default																	
+impl Default for MockConnectionPool {
+	//		default																
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+//		MockDeadlineObservation													
+/// The deadline carried by a request, as seen by [`MockDeadlines`].
+/// 
+/// This is not mocked by [`mockall`], and is a simple supporting type.
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockDeadlineObservation {
+	//		Public properties													
+	/// The URL of the request.
+	pub url:       Url,
+	
+	/// The time at which the request arrived, which comes from the virtual
+	/// clock of the entered context when the `clock` feature is enabled.
+	pub at:        SystemTime,
+	
+	/// The time remaining before the deadline, or [`None`] if the header was
+	/// missing or could not be parsed.
+	pub remaining: Option<Duration>,
+}
+
+//		MockDeadlines															
+/// A record of the deadlines carried by requests, in order to check that
+/// they are propagated correctly.
+/// 
+/// Code that calls other services often passes its own deadline on to them in
+/// a header, such as `grpc-timeout`, reduced by the time that has already been
+/// spent, so that work is abandoned downstream once nobody is waiting for it.
+/// This records the deadline carried by each request, along with when it
+/// arrived, so that the deadlines of a chain of calls can be checked against
+/// each other. When the `clock` feature is enabled and a [`Context`] is
+/// entered, the arrival times come from its virtual clock, so the time spent in
+/// route delays and injected latency is accounted for exactly, and the check
+/// does not depend upon the speed of the machine.
+/// 
+/// Requests are recorded by the routes given the record with
+/// [`MockRoute::with_deadlines()`], or by calling
+/// [`observe()`](MockDeadlines::observe()) from a responder. Clones share the
+/// same record.
+/// 
+/// This is not mocked by [`mockall`], and is a simple supporting type.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use futures_executor::block_on;
+/// use reqwest::{Method, StatusCode, header::{HeaderMap, HeaderValue}};
+/// use sham::reqwest::{MockDeadlines, MockDurationMatcher, MockRoute, create_mock_response, create_routed_mock_client};
+/// use std::{collections::HashMap, time::{Duration, Instant}};
+/// 
+/// let deadlines = MockDeadlines::grpc();
+/// let client    = create_routed_mock_client(vec![
+///     MockRoute::prefixed(Method::GET, "https://api.example.com", |request| {
+///         Ok(create_mock_response(request.url.as_str(), StatusCode::OK, None::<&str>, None, HashMap::<String, String>::new(), Ok(b"")))
+///     }).with_deadlines(&deadlines).with_delay(Duration::from_millis(100)),
+/// ]);
+/// 
+/// let started   = Instant::now();
+/// for path in ["users", "orders"] {
+///     let remaining   = Duration::from_secs(1).saturating_sub(started.elapsed());
+///     let mut headers = HeaderMap::new();
+///     drop(headers.insert("grpc-timeout", HeaderValue::from_str(&format!("{}m", remaining.as_millis())).unwrap()));
+///     block_on(client.get(format!("https://api.example.com/{path}")).headers(headers).send()).unwrap();
+/// }
+/// 
+/// deadlines.assert_propagated(&MockDurationMatcher::new(Duration::from_millis(50)));
+/// ```
+/// 
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct MockDeadlines {
+	//		Private properties													
+	/// The format of the header.
+	format:   MockDeadlineFormat,
+	
+	/// The name of the header that carries the deadline.
+	header:   HeaderName,
+	
+	/// The deadlines seen so far, in order, shared by the clones of the record.
+	observed: Arc<Mutex<Vec<MockDeadlineObservation>>>,
+}
+
+//󰭅		MockDeadlines															
+impl MockDeadlines {
+	//		new																	
+	/// Creates a new, empty record of the deadlines carried in the given
+	/// header.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `header` - The name of the header, such as `x-request-deadline`.
+	/// * `format` - The format of the header.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the header name is not valid.
+	/// 
+	pub fn new(header: &str, format: MockDeadlineFormat) -> Self {
+		Self {
+			format,
+			header:   HeaderName::from_bytes(header.as_bytes()).expect("Invalid header name"),
+			observed: Arc::new(Mutex::new(Vec::new())),
+		}
+	}
+	
+	//		grpc																
+	/// Creates a new, empty record of the deadlines carried in the
+	/// `grpc-timeout` header of gRPC.
+	pub fn grpc() -> Self {
+		Self::new("grpc-timeout", MockDeadlineFormat::GrpcTimeout)
+	}
+	
+	//		assert_propagated													
+	/// Asserts that every request carried a deadline, and that they all point
+	/// at the same moment.
+	/// 
+	/// The time remaining of each request is compared with that of the first
+	/// request, less the time that passed between the two arriving. This
+	/// catches deadlines that are dropped, that are passed on without being
+	/// reduced, or that are reduced by the wrong amount, such as when a unit is
+	/// confused.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `matcher` - The matcher used to compare each time remaining with the
+	///   one expected.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if no requests were recorded, if a request did
+	/// not carry a valid deadline, or if a deadline differs from the one
+	/// expected by more than the tolerance of the matcher.
+	/// 
+	pub fn assert_propagated(&self, matcher: &MockDurationMatcher) {
+		let observed    = self.observations();
+		let Some(first) = observed.first() else {
+			panic!("Expected requests carrying a {} header, but none were recorded", self.header);
+		};
+		let mut initial = Duration::ZERO;
+		for (index, observation) in observed.iter().enumerate() {
+			let Some(remaining) = observation.remaining else {
+				panic!(
+					"Expected request {} to {} to carry a valid {} header, but it did not",
+					index.saturating_add(1),
+					observation.url,
+					self.header,
+				);
+			};
+			if index == 0 {
+				initial = remaining;
+			}
+			let elapsed  = observation.at.duration_since(first.at).unwrap_or(Duration::ZERO);
+			let expected = initial.saturating_sub(elapsed);
+			assert!(
+				matcher.matches(remaining, expected),
+				"Expected the {} header of request {} to {} to leave {expected:?} ± {:?}, as {elapsed:?} had passed since the first request, but it left {remaining:?}",
+				self.header,
+				index.saturating_add(1),
+				observation.url,
+				matcher.tolerance(),
+			);
+		}
+	}
+	
+	//		observations														
+	/// Returns the deadlines seen so far, in the order in which the requests
+	/// arrived.
+	#[must_use]
+	pub fn observations(&self) -> Vec<MockDeadlineObservation> {
+		self.observed.lock().unwrap_or_else(PoisonError::into_inner).clone()
+	}
+	
+	//		observe																
+	/// Records the deadline carried by the given request, as of now, and
+	/// returns the time remaining.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `request` - The request to record.
+	/// 
+	pub fn observe(&self, request: &MockRequest) -> Option<Duration> {
+		let at        = now();
+		let remaining = request.headers.get(&self.header)
+			.and_then(|value| value.to_str().ok())
+			.and_then(|value| self.format.parse(value.trim(), at))
+		;
+		self.observed.lock().unwrap_or_else(PoisonError::into_inner).push(MockDeadlineObservation {
+			url: request.url.clone(),
+			at,
+			remaining,
+		});
+		remaining
+	}
+}
+
+//		MockDurationMatcher														
+/// A matcher that decides whether two durations are close enough to be
+/// considered the same.
+/// 
+/// Durations that are measured, rather than set, rarely match exactly, such
+/// as a deadline that has been reduced by the time spent so far, and then
+/// rounded to the unit of a header. This compares them within a tolerance.
+/// 
+/// This is not mocked by [`mockall`], and is a simple supporting type.
+/// 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MockDurationMatcher {
+	//		Private properties													
+	/// The most that the durations may differ by, in either direction.
+	tolerance: Duration,
+}
+
+//󰭅		MockDurationMatcher														
+impl MockDurationMatcher {
+	//		new																	
+	/// Creates a new matcher with the given tolerance.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `tolerance` - The most that the durations may differ by, in either
+	///   direction.
+	/// 
+	#[must_use]
+	pub const fn new(tolerance: Duration) -> Self {
+		Self { tolerance }
+	}
+	
+	//		exact																
+	/// Creates a new matcher that only matches identical durations.
+	#[must_use]
+	pub const fn exact() -> Self {
+		Self::new(Duration::ZERO)
+	}
+	
+	//		assert_matches														
+	/// Asserts that the given duration matches the one expected.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `actual`   - The duration to check.
+	/// * `expected` - The duration expected.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the durations differ by more than the
+	/// tolerance.
+	/// 
+	pub fn assert_matches(&self, actual: Duration, expected: Duration) {
+		assert!(
+			self.matches(actual, expected),
+			"Expected a duration of {expected:?} ± {:?}, but got {actual:?}",
+			self.tolerance,
+		);
+	}
+	
+	//		matches																
+	/// Whether the given duration matches the one expected.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `actual`   - The duration to check.
+	/// * `expected` - The duration expected.
+	/// 
+	#[must_use]
+	pub fn matches(&self, actual: Duration, expected: Duration) -> bool {
+		actual.abs_diff(expected) <= self.tolerance
+	}
+	
+	//		tolerance															
+	/// Returns the most that the durations may differ by.
+	#[must_use]
+	pub const fn tolerance(&self) -> Duration {
+		self.tolerance
+	}
+}
+
+//		MockError																
+/// A mocked error type for Reqwest.
+/// 
+/// Notably, the real [`reqwest::Error`] type cannot be created externally, and
+/// has logic that depends on private types. Instead of trying to reproduce that
+/// logic, this mock simply provides the means to specify what should be
+/// returned when using the functions provided by the error type.
+/// 
+/// The underlying cause can be given as a source, in the same way that the real
+/// error wraps the errors from its connector, so that code which inspects the
+/// chain of sources, such as to report a TLS failure, can be tested.
+/// 
+/// Errors are best created with [`MockError::builder()`], which sets the flags
+/// that go together, and refuses those that contradict each other.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use reqwest::{StatusCode, Url};
+/// use sham::reqwest::MockError;
+/// 
+/// let url   = Url::parse("https://api.example.com/users").unwrap();
+/// let error = MockError::builder()
+///     .status(StatusCode::BAD_GATEWAY)
+///     .url(url)
+///     .message("upstream died")
+///     .build()
+/// ;
+/// assert!(error.is_status());
+/// assert_eq!(error.status(), Some(StatusCode::BAD_GATEWAY));
+/// assert_eq!(error.to_string(), "Mocked Reqwest error: upstream died");
+/// 
+/// assert!(MockError::builder().timeout().status(StatusCode::BAD_GATEWAY).try_build().is_err());
+/// ```
+/// 
+/// This is not mocked by [`mockall`], and is a simple supporting type.
+/// 
+#[expect(clippy::struct_excessive_bools, reason = "Acceptable here")]
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct MockError {
+	//		Public properties													
+	/// Whether the error is related to the request or response body.
+	pub is_body:     bool,
+	
+	/// Whether the error is from a type builder.
+	pub is_builder:  bool,
+	
+	/// Whether the error is related to making a connection.
+	pub is_connect:  bool,
+	
+	/// Whether the error is related to decoding the response body.
+	pub is_decode:   bool,
+	
+	/// Whether the error is from a redirect policy.
+	pub is_redirect: bool,
+	
+	/// Whether the error is related to the request.
+	pub is_request:  bool,
+	
+	/// Whether the error is from [`Response::error_for_status()`](reqwest::Response::error_for_status).
+	pub is_status:   bool,
+	
+	/// Whether the error is related to a timeout.
+	pub is_timeout:  bool,
+	
+	/// The underlying cause of the error, if any.
+	pub source:      Option<Arc<dyn Error + Send + Sync>>,
+	
+	/// The status code, if the error was generated from a response.
+	pub status:      Option<StatusCode>,
+	
+	/// A possible URL related to this error.
+	pub url:         Option<Url>,
+}
+
+//󰭅		MockError																
+impl MockError {
+	//		builder																
+	/// Creates a builder for an error, with no flags set.
+	pub fn builder() -> MockErrorBuilder {
+		MockErrorBuilder::default()
+	}
+	
+	//		is_body																
+	/// Whether the error is related to the request or response body.
+	/// 
+	/// # See also
+	/// 
+	/// * [`reqwest::Error::is_body()`]
+	/// 
+	#[must_use]
+	pub const fn is_body(&self) -> bool {
+		self.is_body
+	}
+	
+	//		is_builder															
+	/// Whether the error is from a type builder.
+	/// 
+	/// # See also
+	/// 
+	/// * [`reqwest::Error::is_builder()`]
+	/// 
+	#[must_use]
+	pub const fn is_builder(&self) -> bool {
+		self.is_builder
+	}
+	
+	//		is_connect															
+	/// Whether the error is related to making a connection.
+	/// 
+	/// # See also
+	/// 
+	/// * [`reqwest::Error::status()`]
 	/// 
 	#[must_use]
 	pub const fn is_connect(&self) -> bool {
@@ -460,187 +1465,2505 @@ impl MockError {
 impl Display for MockError {
 	//		fmt																	
 	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-		write!(f, "Mocked Reqwest error")
+		match self.source.as_deref().and_then(|source| source.downcast_ref::<MockErrorMessage>()) {
+			Some(message) => write!(f, "Mocked Reqwest error: {}", message.0),
+			None          => write!(f, "Mocked Reqwest error"),
+		}
 	}
 }
 
+//󰭅		Eq																		
+impl Eq for MockError {}
+
 //󰭅		Error																	
-impl Error for MockError {}
+impl Error for MockError {
+	//		source																
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		self.source.as_deref().map(|source| -> &(dyn Error + 'static) { source })
+	}
+}
 
-//		MockResponse															
-/// A mocked response type for Reqwest.
+//󰭅		PartialEq																
+impl PartialEq for MockError {
+	//		eq																	
+	/// Compares two errors. Sources are compared by their messages, as errors
+	/// cannot generally be compared.
+	fn eq(&self, other: &Self) -> bool {
+		self.is_body        == other.is_body
+			&& self.is_builder  == other.is_builder
+			&& self.is_connect  == other.is_connect
+			&& self.is_decode   == other.is_decode
+			&& self.is_redirect == other.is_redirect
+			&& self.is_request  == other.is_request
+			&& self.is_status   == other.is_status
+			&& self.is_timeout  == other.is_timeout
+			&& self.status      == other.status
+			&& self.url         == other.url
+			&& self.source.as_ref().map(ToString::to_string) == other.source.as_ref().map(ToString::to_string)
+	}
+}
+
+//		MockErrorBuilder														
+/// A builder for a [`MockError`].
+/// 
+/// Each flag is set by the method of the same name. As with the real errors,
+/// connection errors are request errors, and so are timeouts, unless they
+/// happened while reading the body. Contradictory flags, such as a builder
+/// error that is also a timeout, are refused when the error is built.
 /// 
 /// This is not mocked by [`mockall`], and is a simple supporting type.
 /// 
-#[derive(Clone, Debug, Eq, PartialEq)]
-#[non_exhaustive]
-pub struct MockResponse {
-	//		Public properties													
-	/// The URL of the response.
-	pub url:     Url,
-	
-	/// The status code of the response.
-	pub status:  StatusCode,
-	
-	/// The headers of the response.
-	pub headers: HeaderMap,
+#[derive(Clone, Debug, Default)]
+#[must_use]
+pub struct MockErrorBuilder {
+	//		Private properties													
+	/// The error being built.
+	error:   MockError,
 	
-	/// The body of the response.
-	pub body:    Result<Arc<Bytes>, MockError>,
+	/// The description of the error, if one has been given.
+	message: Option<String>,
 }
 
-//󰭅		MockResponse															
-#[expect(clippy::unused_async, reason = "Needed for compatibility with the real Reqwest")]
-impl MockResponse {
-	//		bytes																
-	/// Returns the body of the response as a byte array.
-	/// 
-	/// # Errors
-	/// 
-	/// An error will be returned if there was a problem obtaining the body.
+//󰭅		MockErrorBuilder														
+impl MockErrorBuilder {
+	//		body																
+	/// Marks the error as related to the request or response body.
+	pub const fn body(mut self) -> Self {
+		self.error.is_body = true;
+		self
+	}
+	
+	//		build																
+	/// Builds the error.
 	/// 
-	/// # See also
+	/// # Panics
 	/// 
-	/// * [`reqwest::Response::bytes()`]
+	/// This function will panic if the flags contradict each other, or if the
+	/// status is not an error. Use [`try_build()`](MockErrorBuilder::try_build())
+	/// to handle these as errors instead.
 	/// 
-	pub async fn bytes(&self) -> Result<Bytes, MockError> {
-		self.body.clone().map(|bytes| (*bytes).clone())
+	#[must_use]
+	pub fn build(self) -> MockError {
+		self.try_build().unwrap_or_else(|err| panic!("Invalid mock error: {err}"))
 	}
 	
-	//		bytes_stream														
-	/// Returns the body of the response as a stream of byte arrays.
-	/// 
-	/// # Errors
+	//		builder																
+	/// Marks the error as coming from a type builder.
+	pub const fn builder(mut self) -> Self {
+		self.error.is_builder = true;
+		self
+	}
+	
+	//		connect																
+	/// Marks the error as related to making a connection, which also makes it
+	/// a request error.
+	pub const fn connect(mut self) -> Self {
+		self.error.is_connect = true;
+		self
+	}
+	
+	//		decode																
+	/// Marks the error as related to decoding the response body.
+	pub const fn decode(mut self) -> Self {
+		self.error.is_decode = true;
+		self
+	}
+	
+	//		message																
+	/// Sets a description of the error, which is included in its message.
 	/// 
-	/// An error will be returned if there was a problem obtaining the body.
+	/// The description becomes the source of the error, so it cannot be given
+	/// along with a [`source()`](MockErrorBuilder::source()).
 	/// 
-	/// # See also
+	/// # Parameters
 	/// 
-	/// * [`reqwest::Response::bytes_stream()`]
+	/// * `message` - The description.
 	/// 
-	#[must_use]
-	pub fn bytes_stream(&self) -> Pin<Box<dyn Stream<Item = Result<Bytes, MockError>> + Send>> {
-		let body = self.body.clone();
-		Box::pin(stream::once(async move { body.map(|bytes| (*bytes).clone()) }))
+	pub fn message<S: Into<String>>(mut self, message: S) -> Self {
+		self.message = Some(message.into());
+		self
 	}
 	
-	//		error_for_status													
-	/// Turn a response into an error if the server returned an error.
-	///
-	/// # Errors
+	//		redirect															
+	/// Marks the error as coming from a redirect policy.
+	pub const fn redirect(mut self) -> Self {
+		self.error.is_redirect = true;
+		self
+	}
+	
+	//		request																
+	/// Marks the error as related to the request.
+	pub const fn request(mut self) -> Self {
+		self.error.is_request = true;
+		self
+	}
+	
+	//		source																
+	/// Sets the underlying cause of the error.
 	/// 
-	/// An error will be returned if the server returned an error.
+	/// # Parameters
 	/// 
-	/// # See also
+	/// * `source` - The underlying cause.
 	/// 
-	/// * [`reqwest::Response::error_for_status()`]
+	pub fn source<E: Error + Send + Sync + 'static>(mut self, source: E) -> Self {
+		self.error.source = Some(Arc::new(source));
+		self
+	}
+	
+	//		status																
+	/// Marks the error as coming from [`MockResponse::error_for_status()`],
+	/// with the given status.
 	/// 
-	pub fn error_for_status(self) -> Result<Self, MockError> {
-		let status = self.status();
-		if status.is_client_error() || status.is_server_error() {
-			Err(MockError {
-				is_status: true,
-				status:    Some(status),
-				url:       Some(self.url),
-				..Default::default()
-			})
-		} else {
-			Ok(self)
+	/// # Parameters
+	/// 
+	/// * `status` - The status of the response, which must be a client or
+	///   server error.
+	/// 
+	pub const fn status(mut self, status: StatusCode) -> Self {
+		self.error.is_status = true;
+		self.error.status    = Some(status);
+		self
+	}
+	
+	//		timeout																
+	/// Marks the error as related to a timeout, which also makes it a request
+	/// error, unless it is marked as related to the body.
+	pub const fn timeout(mut self) -> Self {
+		self.error.is_timeout = true;
+		self
+	}
+	
+	//		try_build															
+	/// Builds the error.
+	/// 
+	/// # Errors
+	/// 
+	/// A [`MockErrorBuilderError`] will be returned if the flags contradict each
+	/// other, if the status is not an error, or if both a message and a source
+	/// were given.
+	/// 
+	pub fn try_build(self) -> Result<MockError, MockErrorBuilderError> {
+		let mut error = self.error;
+		let flags     = [
+			("body",     error.is_body),
+			("builder",  error.is_builder),
+			("connect",  error.is_connect),
+			("decode",   error.is_decode),
+			("redirect", error.is_redirect),
+			("request",  error.is_request),
+			("status",   error.is_status),
+			("timeout",  error.is_timeout),
+		];
+		let is_set = |name: &str| flags.iter().any(|&(flag, set)| set && flag == name);
+		if let Some(&(first, second)) = CONFLICTS.iter().find(|&&(first, second)| is_set(first) && is_set(second)) {
+			return Err(MockErrorBuilderError::Conflict(first, second));
+		}
+		if let Some(status) = error.status.filter(|status| !status.is_client_error() && !status.is_server_error()) {
+			return Err(MockErrorBuilderError::InvalidStatus(status));
 		}
+		if let Some(message) = self.message {
+			if error.source.is_some() {
+				return Err(MockErrorBuilderError::MessageAndSource);
+			}
+			error.source = Some(Arc::new(MockErrorMessage(message)));
+		}
+		error.is_request |= error.is_connect || (error.is_timeout && !error.is_body);
+		Ok(error)
 	}
 	
-	//		error_for_status_ref												
-	/// Turn a reference to a response into an error if the server returned an
-	/// error.
+	//		url																	
+	/// Sets the URL related to the error.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `url` - The URL.
+	/// 
+	pub fn url(mut self, url: Url) -> Self {
+		self.error.url = Some(url);
+		self
+	}
+}
+
+//		MockErrorMessage														
+/// The description of a [`MockError`], given as its source.
+#[derive(Debug)]
+struct MockErrorMessage(String);
+
+//󰭅		Display																	
+impl Display for MockErrorMessage {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.0)
+	}
+}
+
+//󰭅		Error																	
+impl Error for MockErrorMessage {}
+
+//		MockInvocation															
+/// The details of a call to a [`MockRoute`], which are given to responders
+/// created with [`MockRoute::contextual()`].
+/// 
+/// This is not mocked by [`mockall`], and is a simple supporting type.
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockInvocation {
+	//		Public properties													
+	/// The number of this call to the route, starting from 1. Calls that fail
+	/// due to injected faults are counted.
+	pub call:        usize,
+	
+	/// The identity of the [`MockConnection`] that the request was sent over,
+	/// if the client was created with [`create_pooled_mock_client()`].
+	pub connection:  Option<usize>,
+	
+	/// The time of the call, which comes from the virtual clock of the entered
+	/// context when the `clock` feature is enabled.
+	pub now:         SystemTime,
+	
+	/// The current state of the scenario given to
+	/// [`MockRoute::with_scenario()`], if any.
+	#[cfg(feature = "scenario")]
+	pub scenario:    Option<String>,
+	
+	/// The number of calls to all of the routes of the client so far, including
+	/// this one.
+	pub total_calls: usize,
+}
+
+//		MockLimits																
+/// The limits that a client created with [`create_limited_mock_client()`]
+/// enforces on the responses that it receives.
+/// 
+/// The genuine client can be given limits such as these through the settings
+/// of its HTTP connections, or by wrapping the body of each response, and
+/// these produce the same errors that it would.
+/// 
+/// This is not mocked by [`mockall`], and is a simple supporting type.
+/// 
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockLimits {
+	//		Public properties													
+	/// The largest body allowed, in bytes, if limited. Reading a larger body
+	/// fails with a body error once the limit is passed, after the chunks that
+	/// fit within it have been delivered.
+	pub max_body:    Option<usize>,
+	
+	/// The largest header block allowed, in bytes, if limited. This counts the
+	/// status line and each header line as they would be sent over HTTP/1.1.
+	/// A response with a larger header block fails with a request error, as
+	/// the genuine client fails when the message head is too large.
+	pub max_headers: Option<usize>,
+}
+
+//󰭅		MockLimits																
+impl MockLimits {
+	//		new																	
+	/// Creates a new set of limits, which limits nothing.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self { max_body: None, max_headers: None }
+	}
+	
+	//		with_max_body														
+	/// Sets the largest body allowed.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `bytes` - The largest body allowed, in bytes.
+	/// 
+	#[must_use]
+	pub const fn with_max_body(mut self, bytes: usize) -> Self {
+		self.max_body = Some(bytes);
+		self
+	}
+	
+	//		with_max_headers													
+	/// Sets the largest header block allowed.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `bytes` - The largest header block allowed, in bytes.
+	/// 
+	#[must_use]
+	pub const fn with_max_headers(mut self, bytes: usize) -> Self {
+		self.max_headers = Some(bytes);
+		self
+	}
+	
+	//		enforce																
+	/// Applies the limits to the given response.
 	/// 
 	/// # Errors
 	/// 
-	/// An error will be returned if the server returned an error.
+	/// A request error will be returned if the header block is too large.
+	/// 
+	fn enforce(self, mut response: MockResponse) -> Result<MockResponse, MockError> {
+		if let Some(limit) = self.max_headers {
+			let status = format!("{:?} {}\r\n", response.version, response.status).len();
+			let size   = response.headers.iter()
+				.map(|(name, value)| name.as_str().len() + value.len() + 4)
+				.sum::<usize>() + status + 2
+			;
+			if size > limit {
+				return Err(MockError::builder().request().message("message head is too large").url(response.url).build());
+			}
+		}
+		let Some(limit) = self.max_body else {
+			return Ok(response);
+		};
+		let exceeded = MockError::builder().body().message("length limit exceeded").url(response.url.clone()).build();
+		if let Some(ref mut chunks) = response.chunks {
+			let mut total = 0;
+			if let Some(position) = chunks.iter().position(|chunk| { total += chunk.len(); total > limit }) {
+				chunks.truncate(position);
+				response.body = Err(exceeded);
+			}
+		} else if response.body.as_ref().is_ok_and(|body| body.len() > limit) {
+			response.body = Err(exceeded);
+		}
+		Ok(response)
+	}
+}
+
+//		MockRequest																
+/// A request sent using a [`MockRequestBuilder`].
+/// 
+/// This is passed to the responder of the matching [`MockRoute`], so that the
+/// response can depend upon what was sent.
+/// 
+/// This is not mocked by [`mockall`], and is a simple supporting type.
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockRequest {
+	//		Public properties													
+	/// The HTTP method of the request.
+	pub method:  Method,
+	
+	/// The URL of the request.
+	pub url:     Url,
+	
+	/// The headers of the request.
+	pub headers: HeaderMap,
+	
+	/// The body of the request, if one was set.
+	pub body:    Option<Bytes>,
+	
+	/// The chunks in which a streaming body was received, in order. This is
+	/// empty when the body was held in memory.
+	pub chunks:  Vec<MockBodyChunk>,
+}
+
+//		MockRequestBuilder														
+/// A mocked Reqwest request builder.
+/// 
+/// This records the request as it is built. When it has come from a client
+/// created by [`create_routed_mock_client()`], sending it passes the request to
+/// the responder of the matching [`MockRoute`]. Otherwise, sending it defers to
+/// the mocked [`sender`](MockRequestBuilder::sender), which is configured using
+/// its `expect_send()` method.
+/// 
+/// This wraps a sender that is mocked by [`mockall`], and allows configuration
+/// of expected requests and responses for testing.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use reqwest::StatusCode;
+/// use sham::reqwest::{MockRequestBuilder, create_mock_response};
+/// use std::collections::HashMap;
+/// 
+/// let mut builder = MockRequestBuilder::new();
+/// _ = builder.sender.expect_send()
+///     .times(1)
+///     .returning(|| Ok(create_mock_response("https://example.com/", StatusCode::OK, None::<&str>, None, HashMap::<String, String>::new(), Ok(b""))))
+/// ;
+/// 
+/// let response = futures_executor::block_on(builder.send()).unwrap();
+/// assert_eq!(response.status(), StatusCode::OK);
+/// ```
+/// 
+#[derive(Debug, Default)]
+pub struct MockRequestBuilder {
+	//		Public properties													
+	/// The mocked sender, used when the builder did not come from a routed
+	/// client.
+	pub sender: MockRequestSender,
+	
+	//		Private properties													
+	/// The client to send the request with when none of the routes match it,
+	/// if the builder came from a layered client.
+	base:     Option<Arc<MockClient>>,
+	
+	/// The body of the request, if one has been set.
+	body:     Option<Bytes>,
+	
+	/// An error encountered whilst building the request, which will be returned
+	/// when it is sent.
+	error:    Option<MockError>,
+	
+	/// The headers of the request.
+	headers:  HeaderMap,
+	
+	/// The limits that the client enforces on responses, if any.
+	limits:   Option<MockLimits>,
+	
+	/// The HTTP method of the request.
+	method:   Method,
+	
+	/// The pool of connections of the client, if it has one.
+	pool:     Option<MockConnectionPool>,
+	
+	/// The headers that the request must send, if the builder came from a
+	/// client created by [`create_mock_client_with_headers()`].
+	required: HeaderMap,
+	
+	/// The routes to respond from, if the builder came from a routed client.
+	routes:   Option<Arc<[MockRoute]>>,
+	
+	/// The streaming body of the request, if one was set and not yet sent.
+	stream:   Mutex<Option<Body>>,
+	
+	/// The total time allowed for the request, if limited.
+	timeout:  Option<Duration>,
+	
+	/// The URL of the request, if known.
+	url:      Option<Url>,
+}
+
+//󰭅		MockRequestBuilder														
+impl MockRequestBuilder {
+	//		new																	
+	/// Creates a new request builder with no expectations.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+	
+	//		for_request															
+	/// Creates a new request builder for the given method and URL.
+	fn for_request(method: Method, url: Url, routes: Option<Arc<[MockRoute]>>, pool: Option<MockConnectionPool>) -> Self {
+		Self {
+			method,
+			url: Some(url),
+			pool,
+			routes,
+			..Default::default()
+		}
+	}
+	
+	//		basic_auth															
+	/// Enable HTTP basic authentication.
+	/// 
+	/// The credentials are encoded into an `Authorization` header, which is
+	/// marked as sensitive, and can be checked with
+	/// [`sent_credentials()`](MockRequestBuilder::sent_credentials()).
 	/// 
 	/// # See also
 	/// 
-	/// * [`reqwest::Response::error_for_status_ref()`]
+	/// * [`reqwest::RequestBuilder::basic_auth()`]
 	/// 
-	pub fn error_for_status_ref(&self) -> Result<&Self, MockError> {
-		let status = self.status();
-		if status.is_client_error() || status.is_server_error() {
-			Err(MockError {
-				is_status: true,
-				status:    Some(status),
-				url:       Some(self.url.clone()),
-				..Default::default()
-			})
-		} else {
-			Ok(self)
+	#[must_use]
+	pub fn basic_auth<U: Display, P: Display>(self, username: U, password: Option<P>) -> Self {
+		let credentials = password.map_or_else(|| format!("{username}:"), |secret| format!("{username}:{secret}"));
+		self.authorization(&format!("Basic {}", BASE64.encode(credentials)))
+	}
+	
+	//		bearer_auth															
+	/// Enable HTTP bearer authentication.
+	/// 
+	/// The token is sent in an `Authorization` header, which is marked as
+	/// sensitive, and can be checked with
+	/// [`sent_credentials()`](MockRequestBuilder::sent_credentials()).
+	/// 
+	/// # See also
+	/// 
+	/// * [`reqwest::RequestBuilder::bearer_auth()`]
+	/// 
+	#[must_use]
+	pub fn bearer_auth<T: Display>(self, token: T) -> Self {
+		self.authorization(&format!("Bearer {token}"))
+	}
+	
+	//		body																
+	/// Set the request body.
+	/// 
+	/// A streaming body, such as one created by [`Body::wrap_stream()`], is
+	/// consumed chunk by chunk when the request is sent, and is then recorded
+	/// along with the size and timing of each chunk. A streaming body can only
+	/// be sent once. A body held in memory is recorded as it is, and can be
+	/// checked with [`sent_body()`](MockRequestBuilder::sent_body()).
+	/// 
+	/// # See also
+	/// 
+	/// * [`reqwest::RequestBuilder::body()`]
+	/// 
+	#[must_use]
+	pub fn body<T: Into<Body>>(mut self, body: T) -> Self {
+		let converted = body.into();
+		self.body     = converted.as_bytes().map(Bytes::copy_from_slice);
+		self.stream   = Mutex::new(self.body.is_none().then_some(converted));
+		self
+	}
+	
+	//		checkpoint															
+	/// Validates all expectations of the mocked sender, and discards them.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if any of the expectations were not satisfied.
+	/// 
+	pub fn checkpoint(&mut self) {
+		self.sender.checkpoint();
+	}
+	
+	//		form																
+	/// Specify to send a form body.
+	/// 
+	/// The form is URL-encoded and recorded as the body, and the content type
+	/// is set if it has not been already. The recorded body can be checked
+	/// with [`sent_form()`](MockRequestBuilder::sent_form()).
+	/// 
+	/// # See also
+	/// 
+	/// * [`reqwest::RequestBuilder::form()`]
+	/// 
+	#[must_use]
+	pub fn form<T: Serialize + ?Sized>(mut self, form: &T) -> Self {
+		match serde_urlencoded::to_string(form) {
+			Ok(encoded) => self.set_body(encoded.into(), "application/x-www-form-urlencoded"),
+			Err(_)      => self.error = Some(MockError { is_builder: true, ..Default::default() }),
+		}
+		self
+	}
+	
+	//		header																
+	/// Adds a header to the request.
+	/// 
+	/// The value is added alongside any existing values for the same header
+	/// name. An invalid name or value makes sending the request fail with a
+	/// builder error, as it does with the genuine builder.
+	/// 
+	/// # See also
+	/// 
+	/// * [`reqwest::RequestBuilder::header()`]
+	/// 
+	#[must_use]
+	pub fn header<K, V>(mut self, key: K, value: V) -> Self
+	where
+		K: TryInto<HeaderName>,
+		V: TryInto<HeaderValue>,
+	{
+		match (key.try_into(), value.try_into()) {
+			(Ok(name), Ok(converted)) => _ = self.headers.append(name, converted),
+			_                         => self.error = Some(MockError { is_builder: true, ..Default::default() }),
 		}
+		self
 	}
 	
 	//		headers																
-	/// Returns the headers of the response.
+	/// Adds headers to the request.
+	/// 
+	/// Any existing values for the given header names are replaced.
 	/// 
 	/// # See also
 	/// 
-	/// * [`reqwest::Response::headers()`]
+	/// * [`reqwest::RequestBuilder::headers()`]
 	/// 
 	#[must_use]
-	pub const fn headers(&self) -> &HeaderMap {
-		&self.headers
+	pub fn headers(mut self, headers: HeaderMap) -> Self {
+		let mut previous: Option<HeaderName> = None;
+		for (key, value) in headers {
+			if let Some(name) = key {
+				drop(self.headers.insert(&name, value));
+				previous = Some(name);
+			} else if let Some(ref last) = previous {
+				_ = self.headers.append(last, value);
+			}
+		}
+		self
 	}
 	
 	//		json																
-	/// Returns the body of the response deserialized from JSON to type `T`.
+	/// Specify to send a JSON body.
+	/// 
+	/// The value is serialised and recorded as the body, and the content type
+	/// is set if it has not been already. The recorded body can be checked
+	/// with [`sent_json()`](MockRequestBuilder::sent_json()).
+	/// 
+	/// # See also
+	/// 
+	/// * [`reqwest::RequestBuilder::json()`]
+	/// 
+	#[must_use]
+	pub fn json<T: Serialize + ?Sized>(mut self, json: &T) -> Self {
+		match serde_json::to_vec(json) {
+			Ok(encoded) => self.set_body(encoded.into(), "application/json"),
+			Err(_)      => self.error = Some(MockError { is_builder: true, ..Default::default() }),
+		}
+		self
+	}
+	
+	//		query																
+	/// Modify the query string of the URL.
+	/// 
+	/// The value is URL-encoded and its pairs are appended to any that the URL
+	/// already has, so they can be checked with
+	/// [`sent_query()`](MockRequestBuilder::sent_query()), and matched by
+	/// routes with [`MockRoute::with_query()`]. A value that cannot be encoded
+	/// makes sending the request fail with a builder error, as it does with the
+	/// genuine builder, and so does a builder that has no URL to add the query
+	/// to, rather than the query being lost.
+	/// 
+	/// # Examples
+	/// 
+	/// ```rust
+	/// use futures_executor::block_on;
+	/// use sham::reqwest::MockRequestBuilder;
+	/// 
+	/// let builder = MockRequestBuilder::new().query(&[("page", "2")]);
+	/// assert!(block_on(builder.send()).unwrap_err().is_builder());
+	/// ```
+	/// 
+	/// # See also
+	/// 
+	/// * [`reqwest::RequestBuilder::query()`]
+	/// 
+	#[must_use]
+	pub fn query<T: Serialize + ?Sized>(mut self, query: &T) -> Self {
+		let Some(ref mut url) = self.url else {
+			self.error = Some(MockError { is_builder: true, ..Default::default() });
+			return self;
+		};
+		let mut pairs = url.query_pairs_mut();
+		let encoded   = query.serialize(serde_urlencoded::Serializer::new(&mut pairs)).is_ok();
+		drop(pairs);
+		if url.query() == Some("") {
+			url.set_query(None);
+		}
+		if !encoded {
+			self.error = Some(MockError { is_builder: true, ..Default::default() });
+		}
+		self
+	}
+	
+	//		send																
+	/// Sends the request and returns the response.
+	/// 
+	/// The request and its outcome are recorded with the [`Context`] entered on
+	/// the current thread, if there is one. When the `throttle` feature is
+	/// enabled, the time at which the request was sent is also reported to the
+	/// [throttle observer](Context::throttle()) of the context.
 	/// 
 	/// # Errors
 	/// 
-	/// An error will be returned if there was a problem obtaining the body, or
-	/// if body is not in JSON format, or if it cannot be properly deserialized
-	/// to target type `T`.
+	/// An error will be returned if there was a problem building the request,
+	/// or if the responder or mocked sender returns one.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the builder came from a routed client and
+	/// no route matches the request.
+	/// 
+	/// # See also
+	/// 
+	/// * [`reqwest::RequestBuilder::send()`]
+	/// 
+	pub async fn send(&self) -> Result<MockResponse, MockError> {
+		#[cfg(feature = "throttle")]
+		if let (Some(context), Some(host)) = (Context::current(), self.url.as_ref().and_then(Url::host_str)) {
+			context.throttle().observe(host, now());
+		}
+		let mut deadline = Deadline { limit: self.timeout, spent: Duration::ZERO };
+		let result       = self.dispatch(&mut deadline).await;
+		if result.as_ref().is_err_and(MockError::is_timeout) {
+			deadline.expire().await;
+		}
+		if let Some(context) = Context::current() {
+			let url        = self.url.as_ref().map_or("", Url::as_str);
+			let action     = format!("{} {url}", self.method);
+			let outcome    = result.as_ref().map_or_else(ToString::to_string, |response| response.status.to_string());
+			let user_agent = self.user_agent().map(|agent| String::from_utf8_lossy(agent.as_bytes()).into_owned());
+			context.record("reqwest", &action, &outcome);
+			context.record_user_agent(&action, user_agent);
+		}
+		result
+	}
+	
+	//		sent_body															
+	/// The body that the request is sent with, if one has been set and is held
+	/// in memory.
+	/// 
+	/// This is the body given to [`body()`](MockRequestBuilder::body()), or the
+	/// one encoded by [`form()`](MockRequestBuilder::form()) or
+	/// [`json()`](MockRequestBuilder::json()), exactly as it would go over the
+	/// wire, so that tests can check the payload that the code under test
+	/// built. A streaming body is not held in memory, and is instead available
+	/// to the responder of the route as [`MockRequest::body`] once it has been
+	/// received.
+	/// 
+	/// # Examples
+	/// 
+	/// ```rust
+	/// use serde_json::json;
+	/// use sham::reqwest::MockRequestBuilder;
+	/// 
+	/// let builder = MockRequestBuilder::new().json(&json!({ "name": "app" }));
+	/// assert_eq!(builder.sent_body().unwrap().as_ref(), br#"{"name":"app"}"#);
+	/// ```
+	/// 
+	#[must_use]
+	pub const fn sent_body(&self) -> Option<&Bytes> {
+		self.body.as_ref()
+	}
+	
+	//		sent_credentials													
+	/// The credentials in the `Authorization` header of the request, if it has
+	/// one that uses the basic or bearer scheme, whether it was set by
+	/// [`basic_auth()`](MockRequestBuilder::basic_auth()),
+	/// [`bearer_auth()`](MockRequestBuilder::bearer_auth()), or directly as a
+	/// header.
+	/// 
+	/// # Examples
+	/// 
+	/// ```rust
+	/// use sham::reqwest::{MockCredentials, MockRequestBuilder};
+	/// 
+	/// let builder = MockRequestBuilder::new().basic_auth("alice", Some("secret"));
+	/// assert_eq!(builder.sent_credentials(), Some(MockCredentials::Basic("alice".to_owned(), Some("secret".to_owned()))));
+	/// 
+	/// let builder = MockRequestBuilder::new().bearer_auth("token-123");
+	/// assert_eq!(builder.sent_credentials(), Some(MockCredentials::Bearer("token-123".to_owned())));
+	/// assert!(builder.sent_headers()["authorization"].is_sensitive());
+	/// ```
+	/// 
+	#[must_use]
+	pub fn sent_credentials(&self) -> Option<MockCredentials> {
+		let value = self.headers.get(AUTHORIZATION)?.to_str().ok()?;
+		if let Some(token) = value.strip_prefix("Bearer ") {
+			return Some(MockCredentials::Bearer(token.to_owned()));
+		}
+		let decoded            = String::from_utf8(BASE64.decode(value.strip_prefix("Basic ")?).ok()?).ok()?;
+		let (username, secret) = decoded.split_once(':').unwrap_or((&decoded, ""));
+		Some(MockCredentials::Basic(username.to_owned(), (!secret.is_empty()).then(|| secret.to_owned())))
+	}
+	
+	//		sent_form															
+	/// Returns the body that the request is sent with, decoded as a
+	/// URL-encoded form.
+	/// 
+	/// This allows the form built with [`form()`](MockRequestBuilder::form())
+	/// to be checked field by field, regardless of the order in which the
+	/// fields were encoded. If a field appears more than once, the last value
+	/// is returned.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if no body held in memory has been set, or if
+	/// the body is not a valid URL-encoded form. These are decode errors, with
+	/// the form error as their source.
+	/// 
+	/// # Examples
+	/// 
+	/// ```rust
+	/// use sham::reqwest::MockRequestBuilder;
+	/// 
+	/// let builder = MockRequestBuilder::new().form(&[("grant_type", "client_credentials"), ("scope", "read write")]);
+	/// let form    = builder.sent_form().unwrap();
+	/// assert_eq!(form["scope"], "read write");
+	/// ```
+	/// 
+	pub fn sent_form(&self) -> Result<HashMap<String, String>, MockError> {
+		let Some(ref body) = self.body else {
+			return Err(self.undecodable(MockError::builder().decode().message("no body has been set")));
+		};
+		serde_urlencoded::from_bytes(body).map_err(|error| self.undecodable(MockError::builder().decode().source(error)))
+	}
+	
+	//		sent_headers														
+	/// The headers that have been set on the request, through
+	/// [`header()`](MockRequestBuilder::header()),
+	/// [`headers()`](MockRequestBuilder::headers()), and the methods that set
+	/// a body, so that tests can check what the code under test sent.
+	/// 
+	/// The default `User-Agent` of the entered [`Context`] is not included, as
+	/// it is only added when the request is sent.
+	/// 
+	/// # Examples
+	/// 
+	/// ```rust
+	/// use reqwest::header::{ACCEPT, AUTHORIZATION};
+	/// use sham::reqwest::MockRequestBuilder;
+	/// 
+	/// let builder = MockRequestBuilder::new()
+	///     .header(ACCEPT, "application/json")
+	///     .header(AUTHORIZATION, "Bearer secret")
+	/// ;
+	/// assert_eq!(builder.sent_headers()[AUTHORIZATION], "Bearer secret");
+	/// assert_eq!(builder.sent_headers().len(), 2);
+	/// ```
+	/// 
+	#[must_use]
+	pub const fn sent_headers(&self) -> &HeaderMap {
+		&self.headers
+	}
+	
+	//		sent_json															
+	/// Returns the body that the request is sent with, deserialised from JSON
+	/// to type `T`.
+	/// 
+	/// This allows the payload built with [`json()`](MockRequestBuilder::json())
+	/// to be compared structurally with what was expected, rather than as
+	/// bytes, which depend upon the order of fields and the formatting.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if no body held in memory has been set, or if
+	/// the body is not in JSON format, or if it cannot be properly deserialised
+	/// to target type `T`. These are all decode errors, and those from parsing
+	/// the body have the JSON error as their source.
+	/// 
+	/// # Examples
+	/// 
+	/// ```rust
+	/// use serde::Deserialize;
+	/// use serde_json::json;
+	/// use sham::reqwest::MockRequestBuilder;
+	/// 
+	/// #[derive(Debug, Deserialize, PartialEq)]
+	/// struct Order {
+	///     item:     String,
+	///     quantity: u32,
+	/// }
+	/// 
+	/// let builder = MockRequestBuilder::new().json(&json!({ "quantity": 2, "item": "widget" }));
+	/// assert_eq!(builder.sent_json::<Order>().unwrap(), Order { item: "widget".to_owned(), quantity: 2 });
+	/// ```
+	/// 
+	pub fn sent_json<T: DeserializeOwned>(&self) -> Result<T, MockError> {
+		let Some(ref body) = self.body else {
+			return Err(self.undecodable(MockError::builder().decode().message("no body has been set")));
+		};
+		from_json_slice(body).map_err(|error| self.undecodable(MockError::builder().decode().source(error)))
+	}
+	
+	//		sent_query															
+	/// The pairs in the query string of the URL of the request, in order,
+	/// including those added with [`query()`](MockRequestBuilder::query()).
+	/// 
+	/// # Examples
+	/// 
+	/// ```rust
+	/// use futures_executor::block_on;
+	/// use sham::reqwest::{MockError, create_mock_client};
+	/// 
+	/// let client  = create_mock_client(vec![("https://api.example.com/search?page=2", Err(MockError::builder().build()))]);
+	/// let builder = client.get("https://api.example.com/search?page=2").query(&[("q", "rust mocks")]);
+	/// assert_eq!(builder.sent_query(), vec![("page".to_owned(), "2".to_owned()), ("q".to_owned(), "rust mocks".to_owned())]);
+	/// assert!(block_on(builder.send()).is_err());
+	/// ```
+	/// 
+	#[must_use]
+	pub fn sent_query(&self) -> Vec<(String, String)> {
+		self.url.as_ref().map(|url| url.query_pairs().into_owned().collect()).unwrap_or_default()
+	}
+	
+	//		timeout																
+	/// Enables a request timeout.
+	/// 
+	/// The timeout applies from when the request starts connecting until the
+	/// response has been produced. If the latency injected by the
+	/// [`FaultPlan`] of the route and the [delay](MockRoute::with_delay()) of
+	/// the route add up to more than the timeout, the request fails with a
+	/// timeout error once the timeout has passed. A timeout error that
+	/// is produced in any other way, such as by a [`FaultPlan`], also takes the
+	/// whole of the timeout to arrive, as it would in production.
+	/// 
+	/// # See also
+	/// 
+	/// * [`reqwest::RequestBuilder::timeout()`]
+	/// 
+	#[must_use]
+	pub const fn timeout(mut self, timeout: Duration) -> Self {
+		self.timeout = Some(timeout);
+		self
+	}
+	
+	//		authorization														
+	/// Adds an `Authorization` header with the given value, marked as
+	/// sensitive, or records a builder error if the value is not valid.
+	fn authorization(mut self, value: &str) -> Self {
+		match HeaderValue::from_str(value) {
+			Ok(mut header) => {
+				header.set_sensitive(true);
+				_ = self.headers.append(AUTHORIZATION, header);
+			},
+			Err(_)         => self.error = Some(MockError { is_builder: true, ..Default::default() }),
+		}
+		self
+	}
+	
+	//		check_required_headers												
+	/// Checks that the request sends the headers required by the client that
+	/// created it.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if any of the required headers is missing, or
+	/// has a different value, showing the expected and received headers.
+	/// 
+	fn check_required_headers(&self) {
+		let url = self.url.as_ref().map_or("", Url::as_str);
+		for (name, value) in &self.required {
+			let sent = self.headers.get_all(name).iter().collect::<Vec<_>>();
+			assert!(sent.contains(&value), "{}", mismatch_message(
+				&format!("create_mock_client_with_headers() expected {} {url} to send the {name} header", self.method),
+				&format!("{name}: {}", String::from_utf8_lossy(value.as_bytes())),
+				&if sent.is_empty() {
+					format!("no {name} header")
+				} else {
+					sent.iter().map(|header| format!("{name}: {}", String::from_utf8_lossy(header.as_bytes()))).collect::<Vec<_>>().join(", ")
+				},
+			));
+		}
+	}
+	
+	//		delegate															
+	/// Sends the request using the base client of a layered client, as though
+	/// it had been built using that client.
+	/// 
+	/// The request builder is obtained from the method of the base client that
+	/// matches the method of the request, if there is one, and otherwise from
+	/// [`request()`](MockClient::request()). The limits and connection pool of
+	/// the base client apply if it has them, and otherwise those of this
+	/// builder do.
+	async fn delegate(&self, base: &MockClient, stream: Option<Body>, deadline: &mut Deadline) -> Result<MockResponse, MockError> {
+		let url         = self.url.clone().ok_or_else(missing_url)?;
+		let mut builder = match self.method {
+			Method::DELETE => base.delete(url),
+			Method::GET    => base.get(url),
+			Method::PATCH  => base.patch(url),
+			Method::POST   => base.post(url),
+			Method::PUT    => base.put(url),
+			_              => base.request(self.method.clone(), url),
+		};
+		builder.body    = self.body.clone();
+		builder.error   = self.error.clone();
+		builder.headers = self.headers.clone();
+		builder.limits  = builder.limits.or(self.limits);
+		builder.pool    = builder.pool.or_else(|| self.pool.clone());
+		builder.stream  = Mutex::new(stream);
+		builder.timeout = self.timeout;
+		Box::pin(builder.dispatch(deadline)).await
+	}
+	
+	//		dispatch															
+	/// Sends the request to the matching route or the mocked sender.
+	async fn dispatch(&self, deadline: &mut Deadline) -> Result<MockResponse, MockError> {
+		if let Some(ref error) = self.error {
+			return Err(error.clone());
+		}
+		let stream = self.stream.lock().unwrap_or_else(PoisonError::into_inner).take();
+		if let Some(ref routes) = self.routes {
+			let mut request = self.request()?;
+			if let Some(ref base) = self.base {
+				if !routes.iter().any(|route| route.matches(&request)) {
+					return self.delegate(base, stream, deadline).await;
+				}
+			}
+			let route       = find_route(routes, &request);
+			let flight      = route.in_flight.enter();
+			if let Some(ref deadlines) = route.deadlines {
+				_ = deadlines.observe(&request);
+			}
+			let connection  = self.pool.as_ref().map(|pool| pool.checkout(&request.url));
+			let call        = route.hits.fetch_add(1, Ordering::SeqCst) + 1;
+			let total       = routes.iter().map(MockRoute::hits).sum();
+			if let Some(result) = route.inject_fault(&request.url, deadline).await {
+				return result;
+			}
+			route.receive(&mut request, stream).await.map_err(|error| error.with_url(request.url.clone()))?;
+			deadline.wait(route.delay, &request.url).await?;
+			let mut result  = (route.responder)(&request, &route.invocation(call, total, connection));
+			if let Some(limits) = self.limits {
+				result = result.and_then(|response| limits.enforce(response));
+			}
+			drop(flight);
+			if let (Some(pool), Some(id), true) = (self.pool.as_ref(), connection, result.is_ok()) {
+				pool.release(id);
+			}
+			return result;
+		}
+		self.check_required_headers();
+		if let Some(body) = stream {
+			drop(receive_stream(body, None, None).await?);
+		}
+		self.sender.send().await
+	}
+	
+	//		request																
+	/// Returns the request that has been built so far.
+	/// 
+	/// The default `User-Agent` of the entered [`Context`] is added if the
+	/// request does not set its own, as the genuine client does with the one
+	/// given to its builder.
+	/// 
+	/// # Errors
+	/// 
+	/// A builder error will be returned if the request has no URL.
+	/// 
+	fn request(&self) -> Result<MockRequest, MockError> {
+		let mut headers = self.headers.clone();
+		if let Some(agent) = self.user_agent() {
+			_ = headers.entry(USER_AGENT).or_insert(agent);
+		}
+		Ok(MockRequest {
+			method:  self.method.clone(),
+			url:     self.url.clone().ok_or_else(missing_url)?,
+			headers,
+			body:    self.body.clone(),
+			chunks:  Vec::new(),
+		})
+	}
+	
+	//		set_body															
+	/// Sets the body, along with the content type if it has not been set.
+	fn set_body(&mut self, body: Bytes, content_type: &'static str) {
+		_ = self.headers.entry(CONTENT_TYPE).or_insert(HeaderValue::from_static(content_type));
+		self.body = Some(body);
+	}
+	
+	//		undecodable															
+	/// Builds an error for a recorded body that could not be decoded, with the
+	/// URL of the request, if known.
+	fn undecodable(&self, error: MockErrorBuilder) -> MockError {
+		match self.url {
+			Some(ref url) => error.url(url.clone()).build(),
+			None          => error.build(),
+		}
+	}
+	
+	//		user_agent															
+	/// Returns the `User-Agent` that the request will be sent with, which is
+	/// the one set on the request, or otherwise the default of the entered
+	/// [`Context`], if any.
+	fn user_agent(&self) -> Option<HeaderValue> {
+		self.headers.get(USER_AGENT).cloned()
+			.or_else(|| Context::current().and_then(|context| context.default_user_agent()))
+	}
+}
+
+//		MockRepresentations														
+/// A set of representations of a resource, one of which is chosen for each
+/// request according to its `Accept` header.
+/// 
+/// The representations are held in order of preference. For each request, the
+/// representation with the highest quality in the `Accept` header is chosen,
+/// with the quality of a media type taken from the most specific range that
+/// matches it, so that `text/*;q=0.5` applies to `text/html` unless
+/// `text/html` is listed itself. Ties are broken by order of preference, and a
+/// request without an `Accept` header gets the first representation. The
+/// response has the chosen `Content-Type`, and `Vary: Accept`, and if nothing
+/// is acceptable, it is a `406 Not Acceptable` that lists the media types that
+/// are available.
+/// 
+/// This is not mocked by [`mockall`], and is a simple supporting type.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use futures_executor::block_on;
+/// use reqwest::{Method, StatusCode, header::{ACCEPT, CONTENT_TYPE, HeaderMap}};
+/// use sham::reqwest::{MockRepresentations, MockRoute, create_routed_mock_client};
+/// 
+/// let url             = "https://api.example.com/items/1";
+/// let representations = MockRepresentations::new()
+///     .with_representation("application/json", r#"{"id":1}"#)
+///     .with_representation("application/xml", "<item><id>1</id></item>")
+/// ;
+/// let client          = create_routed_mock_client(vec![MockRoute::negotiated(Method::GET, url, &representations)]);
+/// 
+/// block_on(async {
+///     let mut headers = HeaderMap::new();
+///     headers.insert(ACCEPT, "application/xml, application/json;q=0.9".parse().unwrap());
+///     let response    = client.get(url).headers(headers).send().await.unwrap();
+///     assert_eq!(response.headers()[CONTENT_TYPE], "application/xml");
+///     assert_eq!(response.text().await.unwrap(), "<item><id>1</id></item>");
+/// 
+///     let mut headers = HeaderMap::new();
+///     headers.insert(ACCEPT, "text/html".parse().unwrap());
+///     let response    = client.get(url).headers(headers).send().await.unwrap();
+///     assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+/// });
+/// ```
+/// 
+#[derive(Clone, Debug, Default)]
+#[must_use]
+pub struct MockRepresentations {
+	//		Private properties													
+	/// The representations, in order of preference, as content types and
+	/// bodies.
+	representations: Vec<(String, Bytes)>,
+}
+
+//󰭅		MockRepresentations														
+impl MockRepresentations {
+	//		new																	
+	/// Creates a new set of representations, which is empty until configured.
+	pub fn new() -> Self {
+		Self::default()
+	}
+	
+	//		with_representation													
+	/// Adds a representation, which is less preferred than those already
+	/// added.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `content_type` - The content type of the representation, such as
+	///   `application/json`, which may include parameters such as a charset.
+	/// * `body`         - The body of the representation.
+	/// 
+	pub fn with_representation<B: AsRef<[u8]>>(mut self, content_type: &str, body: B) -> Self {
+		self.representations.push((content_type.to_owned(), Bytes::copy_from_slice(body.as_ref())));
+		self
+	}
+	
+	//		choose																
+	/// Returns the content type of the representation that would be chosen for
+	/// the given `Accept` header, if any is acceptable.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `accept` - The value of the `Accept` header, if there is one.
+	/// 
+	#[must_use]
+	pub fn choose(&self, accept: Option<&str>) -> Option<&str> {
+		self.position(accept).map(|index| self.representations[index].0.as_str())
+	}
+	
+	//		respond																
+	/// Chooses a representation, and produces the response for the given
+	/// request.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `request` - The request to respond to.
+	/// 
+	/// # Errors
+	/// 
+	/// This function does not currently return errors, but returns a
+	/// [`Result`] in order to serve as a responder.
+	/// 
+	pub fn respond(&self, request: &MockRequest) -> Result<MockResponse, MockError> {
+		let accept         = request.headers.get(ACCEPT).and_then(|value| value.to_str().ok());
+		let (status, content_type, body) = self.position(accept).map_or_else(
+			|| {
+				let available = self.representations.iter().map(|representation| representation.0.as_str()).collect::<Vec<_>>();
+				(StatusCode::NOT_ACCEPTABLE, "text/plain".to_owned(), Bytes::from(format!("Available representations: {}", available.join(", "))))
+			},
+			|index| (StatusCode::OK, self.representations[index].0.clone(), self.representations[index].1.clone()),
+		);
+		let mut response   = create_mock_response(request.url.clone(), status, Some(content_type), Some(body.len()), HashMap::<String, String>::new(), Ok(&body));
+		drop(response.headers.insert(VARY, HeaderValue::from_static("accept")));
+		Ok(response)
+	}
+	
+	//		position															
+	/// Returns the position of the representation that would be chosen for the
+	/// given `Accept` header, if any is acceptable.
+	fn position(&self, accept: Option<&str>) -> Option<usize> {
+		let Some(header) = accept.filter(|value| !value.trim().is_empty()) else {
+			return (!self.representations.is_empty()).then_some(0);
+		};
+		let ranges       = header.split(',').filter_map(|element| {
+			let mut parts = element.split(';');
+			let range     = parts.next()?.trim().to_ascii_lowercase();
+			let quality   = parts
+				.filter_map(|parameter| parameter.trim().strip_prefix("q=").or_else(|| parameter.trim().strip_prefix("Q=")))
+				.find_map(|value| value.trim().parse::<f32>().ok())
+				.unwrap_or(1.0)
+			;
+			range.split_once('/').map(|(kind, subtype)| (kind.to_owned(), subtype.to_owned(), quality))
+		}).collect::<Vec<_>>();
+		let mut best     = None::<(usize, f32)>;
+		for (index, representation) in self.representations.iter().enumerate() {
+			let essence             = representation.0.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+			let (kind, subtype)     = essence.split_once('/').unwrap_or((&essence, ""));
+			let quality             = ranges.iter()
+				.filter_map(|range| match (range.0.as_str(), range.1.as_str()) {
+					("*", "*")                                      => Some((0_u8, range.2)),
+					(other, "*") if other == kind                   => Some((1, range.2)),
+					(other, sub) if other == kind && sub == subtype => Some((2, range.2)),
+					_                                               => None,
+				})
+				.max_by_key(|&(specificity, _)| specificity)
+				.map_or(0.0, |(_, quality)| quality)
+			;
+			if quality > 0.0 && !best.is_some_and(|(_, top)| quality <= top) {
+				best = Some((index, quality));
+			}
+		}
+		best.map(|(index, _)| index)
+	}
+}
+
+//		MockResponse															
+/// A mocked response type for Reqwest.
+/// 
+/// This is not mocked by [`mockall`], and is a simple supporting type.
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockResponse {
+	//		Public properties													
+	/// The URL of the response.
+	pub url:     Url,
+	
+	/// The status code of the response.
+	pub status:  StatusCode,
+	
+	/// The headers of the response.
+	pub headers: HeaderMap,
+	
+	/// The body of the response.
+	pub body:    Result<Arc<Bytes>, MockError>,
+	
+	/// The chunks in which the body is delivered by
+	/// [`bytes_stream()`](MockResponse::bytes_stream()), if it is streamed. If
+	/// the body is an error, the chunks are what was delivered before it
+	/// failed, and are followed by the error. When this is `None`, the body is
+	/// delivered as a single chunk.
+	pub chunks:   Option<Vec<Bytes>>,
+	
+	/// Whether the body stalls once it has been sent, as happens with a chunked
+	/// body whose final chunk never arrives. Reading the whole body then never
+	/// completes, and the stream of the body yields the body and then waits
+	/// forever, so tests need to apply a timeout.
+	pub stalled:  bool,
+	
+	/// The trailers sent after the body, if any, such as the `grpc-status` of a
+	/// gRPC response. These are only available once the body has been read in
+	/// full. See [`trailers()`](MockResponse::trailers()).
+	pub trailers: Option<HeaderMap>,
+	
+	/// The HTTP version of the response, which is HTTP/1.1 by default.
+	pub version:  Version,
+	
+	//		Private properties													
+	/// The number of times that the body has been read, shared by the clones
+	/// and forks of the response.
+	pub(crate) replays: ReplayCount,
+}
+
+//󰭅		MockResponse															
+impl MockResponse {
+	//		bytes																
+	/// Returns the body of the response as a byte array.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if there was a problem obtaining the body.
+	/// 
+	/// If the body has [`stalled`](MockResponse::stalled), this never completes.
+	/// 
+	/// # See also
+	/// 
+	/// * [`reqwest::Response::bytes()`]
+	/// 
+	pub async fn bytes(&self) -> Result<Bytes, MockError> {
+		self.replays.increment();
+		if self.stalled {
+			future::pending::<()>().await;
+		}
+		self.body.clone().map(|bytes| (*bytes).clone())
+	}
+	
+	//		bytes_stream														
+	/// Returns the body of the response as a stream of byte arrays.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if there was a problem obtaining the body.
+	/// 
+	/// # See also
+	/// 
+	/// * [`reqwest::Response::bytes_stream()`]
+	/// 
+	#[must_use]
+	pub fn bytes_stream(&self) -> Pin<Box<dyn Stream<Item = Result<Bytes, MockError>> + Send>> {
+		self.replays.increment();
+		let body = self.body.clone();
+		let head = if let Some(chunks) = self.chunks.clone() {
+			let failure = body.err().map(Err);
+			stream::iter(chunks.into_iter().map(Ok).chain(failure)).boxed()
+		} else {
+			stream::once(async move { body.map(|bytes| (*bytes).clone()) }).boxed()
+		};
+		if self.stalled {
+			Box::pin(head.chain(stream::pending()))
+		} else {
+			Box::pin(head)
+		}
+	}
+	
+	//		error_for_status													
+	/// Turn a response into an error if the server returned an error.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the server returned an error.
+	/// 
+	/// # See also
+	/// 
+	/// * [`reqwest::Response::error_for_status()`]
+	/// 
+	pub fn error_for_status(self) -> Result<Self, MockError> {
+		let status = self.status();
+		if status.is_client_error() || status.is_server_error() {
+			Err(MockError::builder().status(status).url(self.url).build())
+		} else {
+			Ok(self)
+		}
+	}
+	
+	//		error_for_status_ref												
+	/// Turn a reference to a response into an error if the server returned an
+	/// error.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the server returned an error.
+	/// 
+	/// # See also
+	/// 
+	/// * [`reqwest::Response::error_for_status_ref()`]
+	/// 
+	pub fn error_for_status_ref(&self) -> Result<&Self, MockError> {
+		let status = self.status();
+		if status.is_client_error() || status.is_server_error() {
+			Err(MockError::builder().status(status).url(self.url.clone()).build())
+		} else {
+			Ok(self)
+		}
+	}
+	
+	//		fork																
+	/// Returns a copy of the response to serve another call.
+	/// 
+	/// The copy has its own stream state, so reading its body, whether in full
+	/// or as a stream, always starts from the beginning, and one consumer
+	/// cannot exhaust the body for another. The copy shares the count of
+	/// [`replays()`](MockResponse::replays()) with this response, so that a
+	/// response configured once can serve several expected calls, and the
+	/// number of times that it was read can be checked afterwards. This is the
+	/// same as cloning the response, but makes the intent explicit.
+	/// 
+	/// # Examples
+	/// 
+	/// ```rust
+	/// use futures_util::StreamExt;
+	/// use reqwest::StatusCode;
+	/// use sham::reqwest::create_mock_response;
+	/// use std::collections::HashMap;
+	/// 
+	/// # futures_executor::block_on(async {
+	/// let response = create_mock_response(
+	///     "https://example.com/feed",
+	///     StatusCode::OK,
+	///     None::<&str>,
+	///     None,
+	///     HashMap::<String, String>::new(),
+	///     Ok(b"data"),
+	/// );
+	/// let first    = response.fork();
+	/// let second   = response.fork();
+	/// 
+	/// assert_eq!(first.bytes_stream().count().await, 1);
+	/// assert_eq!(second.text().await.unwrap(), "data");
+	/// assert_eq!(response.replays(), 2);
+	/// # });
+	/// ```
+	/// 
+	#[must_use]
+	pub fn fork(&self) -> Self {
+		self.clone()
+	}
+	
+	//		headers																
+	/// Returns the headers of the response.
+	/// 
+	/// # See also
+	/// 
+	/// * [`reqwest::Response::headers()`]
+	/// 
+	#[must_use]
+	pub const fn headers(&self) -> &HeaderMap {
+		&self.headers
+	}
+	
+	//		json																
+	/// Returns the body of the response deserialized from JSON to type `T`.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if there was a problem obtaining the body, or
+	/// if body is not in JSON format, or if it cannot be properly deserialized
+	/// to target type `T`. The latter errors are decode errors, with the JSON
+	/// error as their source.
+	/// 
+	/// # See also
+	/// 
+	/// * [`reqwest::Response::json()`]
+	/// 
+	pub async fn json<T: DeserializeOwned>(&self) -> Result<T, MockError> {
+		let bytes = self.bytes().await?;
+		from_json_slice(&bytes).map_err(|error| MockError::builder().decode().source(error).url(self.url.clone()).build())
+	}
+	
+	//		replays																
+	/// The number of times that the body of the response, or of any of its
+	/// clones or [forks](MockResponse::fork()), has been read, whether in full
+	/// or as a stream.
+	#[must_use]
+	pub fn replays(&self) -> usize {
+		self.replays.0.load(Ordering::SeqCst)
+	}
+	
+	//		retry_after															
+	/// Returns the time to wait before retrying, from the `Retry-After` header.
+	/// 
+	/// The header can give either a number of seconds, or an HTTP date, in
+	/// which case the time until that date is returned, according to the
+	/// virtual clock of the [`Context`] entered on the current thread, if there
+	/// is one. A date in the past gives a zero wait. The result can be passed to
+	/// [`sleep()`] to wait in the same virtual time. A header that cannot be
+	/// parsed, including a date too far in the future to represent, gives
+	/// `None`.
+	/// 
+	/// # Examples
+	/// 
+	/// ```rust
+	/// use reqwest::StatusCode;
+	/// use sham::reqwest::create_mock_response;
+	/// use std::{collections::HashMap, time::Duration};
+	/// 
+	/// let respond = |value: &str| create_mock_response(
+	///     "https://api.example.com/",
+	///     StatusCode::TOO_MANY_REQUESTS,
+	///     None::<&str>,
+	///     None,
+	///     HashMap::from([("retry-after", value)]),
+	///     Ok(b""),
+	/// );
+	/// 
+	/// assert_eq!(respond("120").retry_after(), Some(Duration::from_secs(120)));
+	/// assert_eq!(respond("Thu, 01 Jan 1970 00:00:00 GMT").retry_after(), Some(Duration::ZERO));
+	/// assert_eq!(respond("Fri, 31 Dec 999999999999999 23:59:59 GMT").retry_after(), None);
+	/// ```
+	/// 
+	#[must_use]
+	pub fn retry_after(&self) -> Option<Duration> {
+		let value = self.headers.get("retry-after")?.to_str().ok()?.trim();
+		if let Ok(seconds) = value.parse::<u64>() {
+			return Some(Duration::from_secs(seconds));
+		}
+		Some(parse_http_date(value)?.duration_since(now()).unwrap_or_default())
+	}
+	
+	//		status																
+	/// Returns the status code of the response.
+	/// 
+	/// # See also
+	/// 
+	/// * [`reqwest::Response::status()`]
+	/// 
+	#[must_use]
+	pub const fn status(&self) -> StatusCode {
+		self.status
+	}
+	
+	//		trailers															
+	/// Returns the trailers sent after the body, if any.
+	/// 
+	/// As with the real response, the trailers arrive after the last frame of
+	/// the body, so this waits for the body in the same way as
+	/// [`bytes()`](MockResponse::bytes()), and never completes if the body has
+	/// [`stalled`](MockResponse::stalled). The real response exposes the
+	/// trailers as the final frame of its body, when it is converted into an
+	/// [`http::Response`](https://docs.rs/http/latest/http/response/struct.Response.html).
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if there was a problem obtaining the body, as
+	/// the trailers are then never received.
+	/// 
+	/// # Examples
+	/// 
+	/// ```rust
+	/// use futures_executor::block_on;
+	/// use reqwest::{StatusCode, Version, header::HeaderMap};
+	/// use sham::reqwest::create_mock_response;
+	/// use std::collections::HashMap;
+	/// 
+	/// let mut response = create_mock_response("https://api.example.com/Greeter/SayHello", StatusCode::OK, Some("application/grpc"), None, HashMap::<String, String>::new(), Ok(b""));
+	/// let mut trailers = HeaderMap::new();
+	/// trailers.insert("grpc-status", "14".parse().unwrap());
+	/// response.trailers = Some(trailers);
+	/// response.version  = Version::HTTP_2;
+	/// 
+	/// assert_eq!(response.version(), Version::HTTP_2);
+	/// let trailers = block_on(response.trailers()).unwrap().unwrap();
+	/// assert_eq!(trailers["grpc-status"], "14");
+	/// ```
+	/// 
+	pub async fn trailers(&self) -> Result<Option<HeaderMap>, MockError> {
+		drop(self.bytes().await?);
+		Ok(self.trailers.clone())
+	}
+	
+	//		text																
+	/// Returns the body of the response as a string.
+	/// 
+	/// Any invalid UTF-8 sequences are replaced with the replacement character,
+	/// in the same way as the real Reqwest does.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if there was a problem obtaining the body.
+	/// 
+	/// # See also
+	/// 
+	/// * [`reqwest::Response::text()`]
+	/// 
+	pub async fn text(&self) -> Result<String, MockError> {
+		self.bytes().await.map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+	}
+	
+	//		url																	
+	/// Returns the final URL of the response.
+	/// 
+	/// # See also
+	/// 
+	/// * [`reqwest::Response::url()`]
+	/// 
+	#[must_use]
+	pub const fn url(&self) -> &Url {
+		&self.url
+	}
+	
+	//		version																
+	/// Returns the HTTP version of the response.
+	/// 
+	/// # See also
+	/// 
+	/// * [`reqwest::Response::version()`]
+	/// 
+	#[must_use]
+	pub const fn version(&self) -> Version {
+		self.version
+	}
+}
+
+//		MockRoute																
+/// A route for a mocked Reqwest client.
+/// 
+/// A route matches requests by method and URL, ignoring any query string, and
+/// passes matching requests to its responder to produce the response. A route
+/// can also be created as a prefix, in which case it also matches the paths
+/// beneath its URL, which is useful when the path contains identifiers. Routes
+/// are given to [`create_routed_mock_client()`] to build a client that serves
+/// them.
+/// 
+/// This is not mocked by [`mockall`], and is a simple supporting type.
+/// 
+#[derive(Clone)]
+pub struct MockRoute {
+	//		Private properties													
+	/// The number of bytes after which to break uploads, and the number of
+	/// uploads that remain to be broken, if any.
+	broken_uploads: Option<(usize, Arc<AtomicUsize>)>,
+	
+	/// The record of the deadlines carried by requests, if any.
+	deadlines:      Option<MockDeadlines>,
+	
+	/// The time to wait before responding, once the body has been received.
+	delay:          Duration,
+	
+	/// The plan of faults to inject into requests, if any.
+	faults:         Option<FaultPlan>,
+	
+	/// The headers that a request must carry in order to match.
+	headers:        HeaderMap,
+	
+	/// The number of requests that the route has served, shared by its clones.
+	hits:           Arc<AtomicUsize>,
+	
+	/// The requests that the route is serving at once, shared by its clones.
+	in_flight:      Arc<InFlight>,
+	
+	/// The HTTP method to match.
+	method:         Method,
+	
+	/// Whether the route also matches the paths beneath its URL.
+	prefix:         bool,
+	
+	/// The query parameters that a request must carry in order to match.
+	query:          Vec<(String, String)>,
+	
+	/// The responder, which produces the response for a matching request.
+	responder:      Responder,
+	
+	/// The scenario whose state is given to the responder, if any.
+	#[cfg(feature = "scenario")]
+	scenario:       Option<MockScenario>,
+	
+	/// The time to wait after receiving each chunk of a streaming body, if
+	/// any.
+	upload_delay:   Option<Duration>,
+	
+	/// The URL to match, without any query string.
+	url:            Url,
+}
+
+//󰭅		MockRoute																
+impl MockRoute {
+	//		new																	
+	/// Creates a new route.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `method`    - The HTTP method to match.
+	/// * `url`       - The URL to match. Any query string is ignored.
+	/// * `responder` - The function that produces the response for a matching
+	///   request.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the URL is not valid.
+	/// 
+	pub fn new<U, F>(method: Method, url: U, responder: F) -> Self
+	where
+		U: IntoUrl,
+		F: Fn(&MockRequest) -> Result<MockResponse, MockError> + Send + Sync + 'static,
+	{
+		Self::contextual(method, url, move |request, _| responder(request))
+	}
+	
+	//		contextual															
+	/// Creates a new route whose responder is given the details of each call
+	/// along with the request.
+	/// 
+	/// This allows the response to depend upon how many times the route has
+	/// been called, and when, without the responder needing any state of its
+	/// own. For example, the first call can be made slow, or a token can be
+	/// made to expire after three calls.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `method`    - The HTTP method to match.
+	/// * `url`       - The URL to match. Any query string is ignored.
+	/// * `responder` - The function that produces the response for a matching
+	///   request, given the details of the call.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the URL is not valid.
+	/// 
+	/// # Examples
+	/// 
+	/// ```rust
+	/// use futures_executor::block_on;
+	/// use reqwest::{Method, StatusCode};
+	/// use sham::reqwest::{MockRoute, create_mock_response, create_routed_mock_client};
+	/// use std::collections::HashMap;
+	/// 
+	/// let client = create_routed_mock_client(vec![
+	///     MockRoute::contextual(Method::GET, "https://api.example.com/me", |request, invocation| {
+	///         let status = if invocation.call > 3 { StatusCode::UNAUTHORIZED } else { StatusCode::OK };
+	///         Ok(create_mock_response(request.url.as_str(), status, None::<&str>, None, HashMap::<String, String>::new(), Ok(b"")))
+	///     }),
+	/// ]);
+	/// 
+	/// let statuses = (0..4).map(|_| block_on(client.get("https://api.example.com/me").send()).unwrap().status()).collect::<Vec<_>>();
+	/// assert_eq!(statuses, vec![StatusCode::OK, StatusCode::OK, StatusCode::OK, StatusCode::UNAUTHORIZED]);
+	/// ```
+	/// 
+	pub fn contextual<U, F>(method: Method, url: U, responder: F) -> Self
+	where
+		U: IntoUrl,
+		F: Fn(&MockRequest, &MockInvocation) -> Result<MockResponse, MockError> + Send + Sync + 'static,
+	{
+		let mut route_url = url.into_url().unwrap();
+		route_url.set_query(None);
+		route_url.set_fragment(None);
+		Self {
+			broken_uploads: None,
+			deadlines:      None,
+			delay:          Duration::ZERO,
+			faults:         None,
+			headers:        HeaderMap::new(),
+			hits:           Arc::new(AtomicUsize::new(0)),
+			in_flight:      Arc::new(InFlight::default()),
+			method,
+			prefix:         false,
+			query:          Vec::new(),
+			responder:      Arc::new(responder),
+			#[cfg(feature = "scenario")]
+			scenario:       None,
+			upload_delay:   None,
+			url:            route_url,
+		}
+	}
+	
+	//		negotiated															
+	/// Creates a new route that chooses between several representations of a
+	/// resource according to the `Accept` header of each request.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `method`          - The HTTP method to match.
+	/// * `url`             - The URL to match. Any query string is ignored.
+	/// * `representations` - The representations to choose between.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the URL is not valid.
+	/// 
+	pub fn negotiated<U: IntoUrl>(method: Method, url: U, representations: &MockRepresentations) -> Self {
+		let shared = representations.clone();
+		Self::new(method, url, move |request| shared.respond(request))
+	}
+	
+	//		prefixed															
+	/// Creates a new route that matches the given URL and the paths beneath it.
+	/// 
+	/// For example, a prefixed route for `https://example.com/items` matches
+	/// `https://example.com/items` and `https://example.com/items/42`, but not
+	/// `https://example.com/itemsets`.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `method`    - The HTTP method to match.
+	/// * `url`       - The URL to match, along with the paths beneath it. Any
+	///   query string is ignored.
+	/// * `responder` - The function that produces the response for a matching
+	///   request.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the URL is not valid.
+	/// 
+	pub fn prefixed<U, F>(method: Method, url: U, responder: F) -> Self
+	where
+		U: IntoUrl,
+		F: Fn(&MockRequest) -> Result<MockResponse, MockError> + Send + Sync + 'static,
+	{
+		Self { prefix: true, ..Self::new(method, url, responder) }
+	}
+	
+	//		weighted															
+	/// Creates a new route that chooses between several responses at random, in
+	/// proportion to their weights.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `method`    - The HTTP method to match.
+	/// * `url`       - The URL to match. Any query string is ignored.
+	/// * `responses` - The responses to choose between. These are shared with
+	///   any clones, so that the choices made can be inspected afterwards.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the URL is not valid.
+	/// 
+	pub fn weighted<U: IntoUrl>(method: Method, url: U, responses: &MockWeightedResponses) -> Self {
+		let shared = responses.clone();
+		Self::new(method, url, move |request| shared.respond(request))
+	}
+	
+	//		with_broken_uploads													
+	/// Makes the given number of requests to the route fail once the given
+	/// number of bytes of their bodies have been received, as though the
+	/// connection had dropped during the upload. The errors are body errors.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `times` - The number of requests to fail, starting with the next one.
+	/// * `after` - The number of bytes to receive before failing.
+	/// 
+	#[must_use]
+	pub fn with_broken_uploads(mut self, times: usize, after: usize) -> Self {
+		self.broken_uploads = Some((after, Arc::new(AtomicUsize::new(times))));
+		self
+	}
+	
+	//		with_deadlines														
+	/// Records the deadline carried by each request that the route serves, as
+	/// it arrives, before any faults or delays.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `deadlines` - The record to add to. The record is shared with any
+	///   other routes that it is given to, so that a chain of calls to several
+	///   routes can be checked together.
+	/// 
+	#[must_use]
+	pub fn with_deadlines(mut self, deadlines: &MockDeadlines) -> Self {
+		self.deadlines = Some(deadlines.clone());
+		self
+	}
+	
+	//		with_delay															
+	/// Makes the route wait for the given time before responding, once the
+	/// body of the request has been received, as a slow server would.
+	/// 
+	/// The delay counts towards the [`timeout()`](MockRequestBuilder::timeout())
+	/// of the request, and uses virtual time when a [`Context`] is entered.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `delay` - The time to wait before responding.
+	/// 
+	#[must_use]
+	pub const fn with_delay(mut self, delay: Duration) -> Self {
+		self.delay = delay;
+		self
+	}
+	
+	//		with_faults															
+	/// Injects latency and errors into the requests served by the route,
+	/// according to the given plan.
+	/// 
+	/// Each request draws a fault from the plan before its body is received.
+	/// The latency is waited for, and then any error is produced as a Reqwest
+	/// error would be, with the underlying I/O error as its source, apart from
+	/// [`ServiceUnavailable`](FaultKind::ServiceUnavailable), which produces a
+	/// `503 Service Unavailable` response.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `plan` - The plan of faults to inject. The plan is shared with any
+	///   other mocks that it is attached to.
+	/// 
+	#[must_use]
+	pub fn with_faults(mut self, plan: &FaultPlan) -> Self {
+		self.faults = Some(plan.clone());
+		self
+	}
+	
+	//		with_header															
+	/// Makes the route match only requests that carry the given header with the
+	/// given value, such as an `Authorization` header.
+	/// 
+	/// This can be called more than once, in which case every header given must
+	/// be present. A request that sends several values for the same header
+	/// matches if any of them is the one required. Requests that do not match
+	/// are passed on to the next route, so a route that requires a header can
+	/// be followed by one for the same URL that rejects the request.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name`  - The name of the header.
+	/// * `value` - The value that the header must have.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the name or the value is not valid in a
+	/// header.
+	/// 
+	/// # Examples
+	/// 
+	/// ```rust
+	/// use futures_executor::block_on;
+	/// use reqwest::{Method, StatusCode, header::AUTHORIZATION};
+	/// use sham::reqwest::{MockRoute, create_mock_response, create_routed_mock_client};
+	/// use std::collections::HashMap;
+	/// 
+	/// let respond = |status| move |request: &sham::reqwest::MockRequest| {
+	///     Ok(create_mock_response(request.url.as_str(), status, None::<&str>, None, HashMap::<String, String>::new(), Ok(b"")))
+	/// };
+	/// let client  = create_routed_mock_client(vec![
+	///     MockRoute::new(Method::GET, "https://api.example.com/me", respond(StatusCode::OK))
+	///         .with_header(AUTHORIZATION, "Bearer secret"),
+	///     MockRoute::new(Method::GET, "https://api.example.com/me", respond(StatusCode::UNAUTHORIZED)),
+	/// ]);
+	/// 
+	/// let anonymous = block_on(client.get("https://api.example.com/me").send()).unwrap();
+	/// let signed_in = block_on(client.get("https://api.example.com/me").header(AUTHORIZATION, "Bearer secret").send()).unwrap();
+	/// assert_eq!(anonymous.status(), StatusCode::UNAUTHORIZED);
+	/// assert_eq!(signed_in.status(), StatusCode::OK);
+	/// ```
+	/// 
+	#[must_use]
+	pub fn with_header<K, V>(mut self, name: K, value: V) -> Self
+	where
+		K: TryInto<HeaderName>,
+		V: TryInto<HeaderValue>,
+	{
+		let (Ok(header_name), Ok(header_value)) = (name.try_into(), value.try_into()) else {
+			panic!("Invalid header required by mock route {} {}", self.method, self.url);
+		};
+		_ = self.headers.append(header_name, header_value);
+		self
+	}
+	
+	//		with_query															
+	/// Makes the route match only requests whose query string contains the
+	/// given key with the given value.
+	/// 
+	/// The pairs of the query string are compared once decoded, and regardless
+	/// of their order or of any other pairs, so `?b=2&a=1` matches a route that
+	/// requires `a` to be `1`. This can be called more than once, in which case
+	/// every pair given must be present.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `key`   - The key of the query parameter.
+	/// * `value` - The value that the parameter must have.
+	/// 
+	/// # Examples
+	/// 
+	/// ```rust
+	/// use futures_executor::block_on;
+	/// use reqwest::{Method, StatusCode};
+	/// use sham::reqwest::{MockRoute, create_mock_response, create_routed_mock_client};
+	/// use std::collections::HashMap;
+	/// 
+	/// let client = create_routed_mock_client(vec![
+	///     MockRoute::new(Method::GET, "https://api.example.com/search", |request| {
+	///         Ok(create_mock_response(request.url.as_str(), StatusCode::OK, None::<&str>, None, HashMap::<String, String>::new(), Ok(b"[]")))
+	///     }).with_query("q", "rust mocks"),
+	/// ]);
+	/// 
+	/// let response = block_on(client.get("https://api.example.com/search?page=2").query(&[("q", "rust mocks")]).send()).unwrap();
+	/// assert_eq!(response.status(), StatusCode::OK);
+	/// ```
+	/// 
+	#[must_use]
+	pub fn with_query(mut self, key: &str, value: &str) -> Self {
+		self.query.push((key.to_owned(), value.to_owned()));
+		self
+	}
+	
+	//		with_scenario														
+	/// Gives the current state of the given scenario to the responder with each
+	/// call, in [`MockInvocation::scenario`].
+	/// 
+	/// # Parameters
+	/// 
+	/// * `scenario` - The scenario. The state is shared with any other mocks
+	///   that it is given to.
+	/// 
+	#[cfg(feature = "scenario")]
+	#[must_use]
+	pub fn with_scenario(mut self, scenario: &MockScenario) -> Self {
+		self.scenario = Some(scenario.clone());
+		self
+	}
+	
+	//		with_upload_delay													
+	/// Makes the route wait for the given time after receiving each chunk of a
+	/// streaming body, before asking for the next one, so as to apply
+	/// backpressure to the sender.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `delay` - The time to wait after each chunk.
+	/// 
+	#[must_use]
+	pub const fn with_upload_delay(mut self, delay: Duration) -> Self {
+		self.upload_delay = Some(delay);
+		self
+	}
+	
+	//		assert_single_flight												
+	/// Runs the given number of tasks concurrently, and asserts that between
+	/// them they hit the route at most once.
+	/// 
+	/// This is intended for testing code that coalesces concurrent requests
+	/// for the same resource, such as a single-flight cache, where every task
+	/// should share the result of one request rather than making its own. The
+	/// tasks are all polled together on the current task, so they overlap as
+	/// they would on a runtime, and their outputs are returned in order. When
+	/// a [`Context`] is entered, the waits of the requests are driven by its
+	/// virtual clock, and still overlap.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `tasks` - The number of tasks to run.
+	/// * `task`  - The function that creates each task, given its index.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the tasks hit the route more than once,
+	/// stating how many times they did, and the most requests that were in
+	/// flight at once.
+	/// 
+	/// # Examples
+	/// 
+	/// ```rust
+	/// use futures_executor::block_on;
+	/// use futures_util::lock::Mutex;
+	/// use reqwest::{Method, StatusCode};
+	/// use sham::{Context, reqwest::{MockRoute, create_mock_response, create_routed_mock_client}};
+	/// use std::{collections::HashMap, time::Duration};
+	/// 
+	/// let context = Context::new();
+	/// let _guard  = context.enter();
+	/// let route   = MockRoute::new(Method::GET, "https://api.example.com/config", |request| {
+	///     Ok(create_mock_response(request.url.as_str(), StatusCode::OK, None::<&str>, None, HashMap::<String, String>::new(), Ok(b"{}")))
+	/// }).with_delay(Duration::from_millis(10));
+	/// let client  = create_routed_mock_client(vec![route.clone()]);
+	/// let cached  = Mutex::new(None);
+	/// 
+	/// let statuses = block_on(route.assert_single_flight(5, |_| async {
+	///     let mut status = cached.lock().await;
+	///     if status.is_none() {
+	///         *status = Some(client.get("https://api.example.com/config").send().await.unwrap().status());
+	///     }
+	///     status.unwrap()
+	/// }));
+	/// assert_eq!(statuses, vec![StatusCode::OK; 5]);
+	/// assert_eq!(route.hits(), 1);
+	/// ```
+	/// 
+	/// Requests that are not coalesced overlap at the route, and fail the
+	/// assertion:
+	/// 
+	/// ```rust,should_panic
+	/// use futures_executor::block_on;
+	/// use reqwest::{Method, StatusCode};
+	/// use sham::{Context, reqwest::{MockRoute, create_mock_response, create_routed_mock_client}};
+	/// use std::{collections::HashMap, time::Duration};
+	/// 
+	/// let context = Context::new();
+	/// let _guard  = context.enter();
+	/// let route   = MockRoute::new(Method::GET, "https://api.example.com/config", |request| {
+	///     Ok(create_mock_response(request.url.as_str(), StatusCode::OK, None::<&str>, None, HashMap::<String, String>::new(), Ok(b"{}")))
+	/// }).with_delay(Duration::from_millis(10));
+	/// let client  = create_routed_mock_client(vec![route.clone()]);
+	/// 
+	/// drop(block_on(route.assert_single_flight(5, |_| async { client.get("https://api.example.com/config").send().await })));
+	/// ```
+	/// 
+	pub async fn assert_single_flight<F, Fut>(&self, tasks: usize, task: F) -> Vec<Fut::Output>
+	where
+		F:   FnMut(usize) -> Fut,
+		Fut: Future,
+	{
+		let before  = self.hits();
+		let outputs = join_all((0..tasks).map(task)).await;
+		let hits    = self.hits().saturating_sub(before);
+		assert!(
+			hits <= 1,
+			"Expected {tasks} concurrent tasks to hit {self:?} at most once, but they hit it {hits} times, with up to {} requests in flight at once",
+			self.max_in_flight(),
+		);
+		outputs
+	}
+	
+	//		hits																
+	/// Returns the number of requests that the route has served.
+	/// 
+	/// Clones of a route share the same count, so a route that has been given
+	/// to a client can be cloned beforehand in order to check it afterwards.
+	/// 
+	#[must_use]
+	pub fn hits(&self) -> usize {
+		self.hits.load(Ordering::SeqCst)
+	}
+	
+	//		in_flight															
+	/// Returns the number of requests that the route is serving now.
+	/// 
+	/// A request is in flight from when the route is chosen until its response
+	/// has been produced, including any faults, delays, and the time taken to
+	/// receive its body. Clones of a route share the same count.
+	/// 
+	#[must_use]
+	pub fn in_flight(&self) -> usize {
+		self.in_flight.current.load(Ordering::SeqCst)
+	}
+	
+	//		matches																
+	/// Whether the route matches the given request, by its method and URL,
+	/// and any headers and query parameters required with
+	/// [`with_header()`](MockRoute::with_header()) and
+	/// [`with_query()`](MockRoute::with_query()).
+	/// 
+	/// # Parameters
+	/// 
+	/// * `request` - The request to check.
+	/// 
+	#[must_use]
+	pub fn matches(&self, request: &MockRequest) -> bool {
+		request.method == self.method
+			&& request.url.scheme()   == self.url.scheme()
+			&& request.url.host_str() == self.url.host_str()
+			&& request.url.port_or_known_default() == self.url.port_or_known_default()
+			&& self.matches_path(request.url.path())
+			&& self.headers.iter().all(|(name, value)| request.headers.get_all(name).iter().any(|sent| sent == value))
+			&& self.query.iter().all(|required| request.url.query_pairs().any(|(key, value)| key == required.0 && value == required.1))
+	}
+	
+	//		max_in_flight														
+	/// Returns the most requests that the route has served at once, over its
+	/// lifetime.
+	/// 
+	/// This shows whether concurrent callers overlapped at the route, such as
+	/// when checking that requests are coalesced or limited in their
+	/// concurrency. See [`in_flight()`](MockRoute::in_flight()).
+	/// 
+	/// # Examples
+	/// 
+	/// ```rust
+	/// use futures_executor::block_on;
+	/// use futures_util::future::join_all;
+	/// use reqwest::{Method, StatusCode};
+	/// use sham::{Context, reqwest::{MockRoute, create_mock_response, create_routed_mock_client}};
+	/// use std::{collections::HashMap, time::Duration};
+	/// 
+	/// let context = Context::new();
+	/// let _guard  = context.enter();
+	/// let route   = MockRoute::new(Method::GET, "https://api.example.com/config", |request| {
+	///     Ok(create_mock_response(request.url.as_str(), StatusCode::OK, None::<&str>, None, HashMap::<String, String>::new(), Ok(b"{}")))
+	/// }).with_delay(Duration::from_millis(50));
+	/// let client  = create_routed_mock_client(vec![route.clone()]);
+	/// 
+	/// let responses = block_on(join_all((0..5).map(|_| async { client.get("https://api.example.com/config").send().await })));
+	/// assert!(responses.into_iter().all(|response| response.unwrap().status() == StatusCode::OK));
+	/// assert_eq!(route.max_in_flight(), 5);
+	/// assert_eq!(route.in_flight(), 0);
+	/// ```
+	/// 
+	#[must_use]
+	pub fn max_in_flight(&self) -> usize {
+		self.in_flight.peak.load(Ordering::SeqCst)
+	}
+	
+	//		method																
+	/// Returns the HTTP method that the route matches.
+	#[must_use]
+	pub const fn method(&self) -> &Method {
+		&self.method
+	}
+	
+	//		respond																
+	/// Produces the response for the given request.
+	/// 
+	/// The responder is told that this is the most recent call to the route,
+	/// and, as a route does not know which client it belongs to, that the
+	/// total number of calls is that of the route alone.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `request` - The request to respond to.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the responder returns one.
+	/// 
+	pub fn respond(&self, request: &MockRequest) -> Result<MockResponse, MockError> {
+		let hits = self.hits();
+		(self.responder)(request, &self.invocation(hits, hits, None))
+	}
+	
+	//		url																	
+	/// Returns the URL that the route matches.
+	#[must_use]
+	pub const fn url(&self) -> &Url {
+		&self.url
+	}
+	
+	//		invocation															
+	/// Describes a call to the route, as it is about to be responded to.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `call`       - The number of the call to the route, starting from 1.
+	/// * `total`      - The number of calls to all of the routes of the client.
+	/// * `connection` - The identity of the connection used, if any.
+	/// 
+	#[cfg_attr(not(feature = "scenario"), expect(clippy::unused_self, reason = "Used by the scenario feature"))]
+	fn invocation(&self, call: usize, total: usize, connection: Option<usize>) -> MockInvocation {
+		MockInvocation {
+			call,
+			connection,
+			now:         now(),
+			#[cfg(feature = "scenario")]
+			scenario:    self.scenario.as_ref().map(MockScenario::state),
+			total_calls: total,
+		}
+	}
+	
+	//		inject_fault														
+	/// Draws a fault from the plan of the route, if it has one, waiting for the
+	/// latency and returning the result that any error produces.
+	async fn inject_fault(&self, url: &Url, deadline: &mut Deadline) -> Option<Result<MockResponse, MockError>> {
+		let fault = self.faults.as_ref()?.draw();
+		if let Err(error) = deadline.wait(fault.latency, url).await {
+			return Some(Err(error));
+		}
+		fault.kind.map(|kind| fault_result(kind, url))
+	}
+	
+	//		receive																
+	/// Receives the body of a request, consuming the streaming body if there
+	/// is one, and breaking the upload if required.
+	async fn receive(&self, request: &mut MockRequest, stream: Option<Body>) -> Result<(), MockError> {
+		let limit = self.broken_uploads.as_ref().and_then(|&(after, ref remaining)| {
+			remaining.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| count.checked_sub(1)).ok().map(|_| after)
+		});
+		if let Some(body) = stream {
+			let (data, chunks) = receive_stream(body, self.upload_delay, limit).await?;
+			request.body       = Some(data);
+			request.chunks     = chunks;
+		} else if limit.is_some_and(|after| request.body.as_ref().is_some_and(|body| body.len() >= after)) {
+			return Err(broken_upload());
+		}
+		Ok(())
+	}
+	
+	//		matches_path														
+	/// Whether the route matches the given path.
+	fn matches_path(&self, path: &str) -> bool {
+		let route_path = self.url.path();
+		if !self.prefix {
+			return path == route_path;
+		}
+		path.strip_prefix(route_path.trim_end_matches('/'))
+			.is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+	}
+}
+
+//󰭅		Debug																	
+impl Debug for MockRoute {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		write!(f, "MockRoute({} {}{}", self.method, self.url, if self.prefix { "/*" } else { "" })?;
+		for name in self.headers.keys() {
+			write!(f, " +{name}")?;
+		}
+		for pair in &self.query {
+			write!(f, " ?{}={}", pair.0, pair.1)?;
+		}
+		write!(f, ")")
+	}
+}
+
+//		MockWeightedResponses													
+/// A set of responses that are chosen between at random, in proportion to
+/// their weights.
+/// 
+/// The choices come from a pseudo-random generator seeded by the set, in the
+/// same way as a [`FaultPlan`], so that the sequence of responses is the same
+/// every time a test is run with the same seed. Clones share the generator, and
+/// the record of the choices made.
+/// 
+/// This is not mocked by [`mockall`], and is a simple supporting type.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use futures_executor::block_on;
+/// use reqwest::{Method, StatusCode};
+/// use sham::reqwest::{MockRoute, MockWeightedResponses, create_mock_response, create_routed_mock_client};
+/// use std::collections::HashMap;
+/// 
+/// let url       = "https://api.example.com/health";
+/// let responses = MockWeightedResponses::new(42)
+///     .with_response(90, Ok(create_mock_response(url, StatusCode::OK, None::<&str>, None, HashMap::<String, String>::new(), Ok(b""))))
+///     .with_response(10, Ok(create_mock_response(url, StatusCode::SERVICE_UNAVAILABLE, None::<&str>, None, HashMap::<String, String>::new(), Ok(b""))))
+/// ;
+/// let client    = create_routed_mock_client(vec![MockRoute::weighted(Method::GET, url, &responses)]);
+/// 
+/// let failures  = (0..1_000).filter(|_| {
+///     block_on(client.get(url).send()).unwrap().status() == StatusCode::SERVICE_UNAVAILABLE
+/// }).count();
+/// assert!((50..150).contains(&failures));
+/// assert_eq!(responses.choices().iter().filter(|&&choice| choice == 1).count(), failures);
+/// ```
+/// 
+#[derive(Clone)]
+#[must_use]
+pub struct MockWeightedResponses {
+	//		Private properties													
+	/// The responders to choose between, with their weights.
+	candidates: Vec<(u32, SimpleResponder)>,
+	
+	/// The state of the generator, and the choices made so far, shared by the
+	/// clones of the set.
+	state:      Arc<Mutex<(u64, Vec<usize>)>>,
+}
+
+//󰭅		MockWeightedResponses													
+impl MockWeightedResponses {
+	//		new																	
+	/// Creates a new set of responses, which is empty until configured.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `seed` - The seed for the generator that decides which response to
+	///   give.
+	/// 
+	pub fn new(seed: u64) -> Self {
+		Self {
+			candidates: Vec::new(),
+			state:      Arc::new(Mutex::new((seed, Vec::new()))),
+		}
+	}
+	
+	//		seeded																
+	/// Creates a new set of responses, which is empty until configured, seeded
+	/// from the seed of the current test.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the `SHAM_SEED` environment variable is
+	/// set, but is not an unsigned 64-bit integer.
+	/// 
+	/// # See also
+	/// 
+	/// * [`seed_for()`](crate::seed::seed_for())
+	/// 
+	pub fn seeded() -> Self {
+		Self::new(seed_for("weighted"))
+	}
+	
+	//		with_responder														
+	/// Adds a responder, which produces the response when it is chosen.
 	/// 
-	/// # See also
+	/// # Parameters
 	/// 
-	/// * [`reqwest::Response::json()`]
+	/// * `weight`    - The weight of the responder, relative to the others. A
+	///   weight of zero means that it is never chosen.
+	/// * `responder` - The function that produces the response for a request.
 	/// 
-	pub async fn json<T: DeserializeOwned>(&self) -> Result<T, MockError> {
-		self.bytes().await.map(|bytes| from_json_slice(&bytes).unwrap())
+	pub fn with_responder<F>(mut self, weight: u32, responder: F) -> Self
+	where
+		F: Fn(&MockRequest) -> Result<MockResponse, MockError> + Send + Sync + 'static,
+	{
+		self.candidates.push((weight, Arc::new(responder)));
+		self
 	}
 	
-	//		status																
-	/// Returns the status code of the response.
+	//		with_response														
+	/// Adds a response, which is given as-is when it is chosen.
 	/// 
-	/// # See also
+	/// # Parameters
 	/// 
-	/// * [`reqwest::Response::status()`]
+	/// * `weight`   - The weight of the response, relative to the others. A
+	///   weight of zero means that it is never chosen.
+	/// * `response` - The response, or error, to give.
 	/// 
+	pub fn with_response(self, weight: u32, response: Result<MockResponse, MockError>) -> Self {
+		self.with_responder(weight, move |_| response.clone())
+	}
+	
+	//		choices																
+	/// Returns the indexes of the responses chosen so far, in order, where
+	/// each index is the position in which the response was added.
 	#[must_use]
-	pub const fn status(&self) -> StatusCode {
-		self.status
+	pub fn choices(&self) -> Vec<usize> {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner).1.clone()
 	}
 	
-	//		text																
-	/// Returns the body of the response as a string.
+	//		respond																
+	/// Chooses a response, and produces it for the given request.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `request` - The request to respond to.
 	/// 
 	/// # Errors
 	/// 
-	/// An error will be returned if there was a problem obtaining the body.
+	/// An error will be returned if the chosen response is an error.
 	/// 
-	/// # See also
+	/// # Panics
 	/// 
-	/// * [`reqwest::Response::text()`]
+	/// This function will panic if there are no responses with a weight above
+	/// zero to choose from.
 	/// 
-	pub async fn text(&self) -> Result<String, MockError> {
-		self.bytes().await.map(|bytes| String::from_utf8(bytes.to_vec()).unwrap())
+	pub fn respond(&self, request: &MockRequest) -> Result<MockResponse, MockError> {
+		let total     = self.candidates.iter().map(|&(weight, _)| u64::from(weight)).sum::<u64>();
+		let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+		let mut point = split_mix64(&mut state.0).checked_rem(total).expect("No weighted responses to choose from");
+		let index     = self.candidates.iter().position(|&(weight, _)| {
+			let chosen = point < u64::from(weight);
+			point      = point.saturating_sub(u64::from(weight));
+			chosen
+		}).expect("Point is within the total weight");
+		state.1.push(index);
+		drop(state);
+		(self.candidates[index].1)(request)
+	}
+}
+
+//󰭅		Debug																	
+impl Debug for MockWeightedResponses {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let weights = self.candidates.iter().map(|&(weight, _)| weight).collect::<Vec<_>>();
+		write!(f, "MockWeightedResponses({weights:?})")
 	}
+}
+
+//		PoolState																
+/// The state of a [`MockConnectionPool`], which is shared by its clones.
+#[derive(Debug, Default)]
+struct PoolState {
+	//		Private properties													
+	/// The connections opened so far, in order, indexed by their identities.
+	connections: Vec<MockConnection>,
 	
-	//		url																	
-	/// Returns the final URL of the response.
-	/// 
-	/// # See also
-	/// 
-	/// * [`reqwest::Response::url()`]
-	/// 
-	#[must_use]
-	pub const fn url(&self) -> &Url {
-		&self.url
+	/// The identities of the connections that are idle, along with when they
+	/// became idle, from the least to the most recently used.
+	idle:        Vec<(usize, SystemTime)>,
+}
+
+//󰭅		PoolState																
+impl PoolState {
+	//		idle_to																
+	/// Returns the positions in the idle list of the connections that are
+	/// idle to the given origin.
+	fn idle_to<'s>(&'s self, origin: &'s str) -> impl Iterator<Item = usize> + 's {
+		self.idle.iter().enumerate()
+			.filter(move |&(_, &(id, _))| self.connections[id].origin == origin)
+			.map(|(index, _)| index)
+	}
+}
+
+//		ReplayCount																
+/// The number of times that the body of a [`MockResponse`] has been read,
+/// which is shared by the clones and forks of the response.
+/// 
+/// This does not take part in comparisons, so that responses compare equal
+/// however often they have been read.
+/// 
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ReplayCount(Arc<AtomicUsize>);
+
+//󰭅		ReplayCount																
+impl ReplayCount {
+	//		increment															
+	/// Counts another read of the body.
+	fn increment(&self) {
+		_ = self.0.fetch_add(1, Ordering::SeqCst);
+	}
+}
+
+//󰭅		Eq																		
+impl Eq for ReplayCount {}
+
+//󰭅		PartialEq																
+impl PartialEq for ReplayCount {
+	//		eq																	
+	fn eq(&self, _other: &Self) -> bool {
+		true
 	}
 }
 
@@ -648,36 +3971,292 @@ impl MockResponse {
 
 //		Functions
 
+//		broken_upload															
+/// Creates the error for an upload that was broken off.
+fn broken_upload() -> MockError {
+	MockError { is_body: true, is_request: true, ..Default::default() }
+}
+
+//		create_layered_mock_client												
+/// Creates a mock Reqwest client that serves the given routes on top of
+/// another client.
+/// 
+/// Each request is passed to the first of the given routes that matches it
+/// when sent, in the same way as for [`create_routed_mock_client()`]. A request
+/// that none of them match is built again using the base client, with the
+/// same method, URL, headers, body, and timeout, and sent using that instead,
+/// so that it is served by the routes or expectations of the base client. The base
+/// client is not asked for a request builder unless it is needed. Layers can
+/// be stacked, by using a layered client as the base of another.
+/// 
+/// # Parameters
+/// 
+/// * `base`      - The client to pass on the requests that are not overridden
+///   to. This can be shared between several layered clients.
+/// * `overrides` - The routes that take precedence over the base client.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use futures_executor::block_on;
+/// use reqwest::{Method, StatusCode};
+/// use sham::reqwest::{MockRoute, create_layered_mock_client, create_mock_response, create_routed_mock_client};
+/// use std::{collections::HashMap, sync::Arc};
+/// 
+/// let respond  = |status: StatusCode| move |request: &sham::reqwest::MockRequest| {
+///     Ok(create_mock_response(request.url.as_str(), status, None::<&str>, None, HashMap::<String, String>::new(), Ok(b"")))
+/// };
+/// let base     = Arc::new(create_routed_mock_client(vec![
+///     MockRoute::new(Method::GET, "https://api.example.com/users",  respond(StatusCode::OK)),
+///     MockRoute::new(Method::GET, "https://api.example.com/health", respond(StatusCode::OK)),
+///     MockRoute::new(Method::OPTIONS, "https://api.example.com/users", respond(StatusCode::NO_CONTENT)),
+/// ]));
+/// let client   = create_layered_mock_client(Arc::clone(&base), vec![
+///     MockRoute::new(Method::GET, "https://api.example.com/users",  respond(StatusCode::SERVICE_UNAVAILABLE)),
+/// ]);
+/// 
+/// block_on(async {
+///     assert_eq!(client.get("https://api.example.com/users").send().await.unwrap().status(), StatusCode::SERVICE_UNAVAILABLE);
+///     assert_eq!(client.get("https://api.example.com/health").send().await.unwrap().status(), StatusCode::OK);
+///     assert_eq!(client.request(Method::OPTIONS, "https://api.example.com/users").send().await.unwrap().status(), StatusCode::NO_CONTENT);
+///     assert_eq!(base.get("https://api.example.com/users").send().await.unwrap().status(), StatusCode::OK);
+/// });
+/// ```
+/// 
+#[must_use]
+pub fn create_layered_mock_client<B: Into<Arc<MockClient>>>(base: B, overrides: Vec<MockRoute>) -> MockClient {
+	let fallback: Arc<MockClient>    = base.into();
+	let shared: Arc<[MockRoute]>     = Arc::from(overrides);
+	let mut mock_client              = MockClient::new();
+	let (delete_routes, delete_base) = (Arc::clone(&shared), Arc::clone(&fallback));
+	_ = mock_client.expect_delete()
+		.returning(move |url| layered_request(Method::DELETE, url.as_str(), &delete_routes, &delete_base))
+	;
+	let (get_routes, get_base)       = (Arc::clone(&shared), Arc::clone(&fallback));
+	_ = mock_client.expect_get()
+		.returning(move |url| layered_request(Method::GET, url.as_str(), &get_routes, &get_base))
+	;
+	let (patch_routes, patch_base)   = (Arc::clone(&shared), Arc::clone(&fallback));
+	_ = mock_client.expect_patch()
+		.returning(move |url| layered_request(Method::PATCH, url.as_str(), &patch_routes, &patch_base))
+	;
+	let (post_routes, post_base)     = (Arc::clone(&shared), Arc::clone(&fallback));
+	_ = mock_client.expect_post()
+		.returning(move |url| layered_request(Method::POST, url.as_str(), &post_routes, &post_base))
+	;
+	let (put_routes, put_base)       = (Arc::clone(&shared), Arc::clone(&fallback));
+	_ = mock_client.expect_put()
+		.returning(move |url| layered_request(Method::PUT, url.as_str(), &put_routes, &put_base))
+	;
+	_ = mock_client.expect_request()
+		.returning(move |method, url| layered_request(method, url.as_str(), &shared, &fallback))
+	;
+	mock_client
+}
+
+//		create_limited_mock_client												
+/// Creates a mock Reqwest client that serves the given routes, and enforces
+/// the given limits on their responses.
+/// 
+/// This behaves in the same way as [`create_routed_mock_client()`], except
+/// that responses with a header block or body larger than allowed fail in the
+/// same way as they would with the genuine client, so that code that claims to
+/// protect against oversized responses can be tested.
+/// 
+/// # Parameters
+/// 
+/// * `routes` - The routes to serve.
+/// * `limits` - The limits to enforce.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use futures_executor::block_on;
+/// use reqwest::{Method, StatusCode};
+/// use sham::reqwest::{MockLimits, MockRoute, create_limited_mock_client, create_mock_response};
+/// use std::collections::HashMap;
+/// 
+/// let client = create_limited_mock_client(
+///     vec![MockRoute::new(Method::GET, "https://api.example.com/export", |request| {
+///         Ok(create_mock_response(request.url.clone(), StatusCode::OK, None::<&str>, None, HashMap::<String, String>::new(), Ok(&[0; 2_048])))
+///     })],
+///     MockLimits::new().with_max_body(1_024),
+/// );
+/// 
+/// block_on(async {
+///     let response = client.get("https://api.example.com/export").send().await.unwrap();
+///     assert!(response.bytes().await.unwrap_err().is_body());
+/// });
+/// ```
+/// 
+#[must_use]
+pub fn create_limited_mock_client(routes: Vec<MockRoute>, limits: MockLimits) -> MockClient {
+	let shared: Arc<[MockRoute]> = Arc::from(routes);
+	let mut mock_client          = MockClient::new();
+	let delete_routes            = Arc::clone(&shared);
+	_ = mock_client.expect_delete()
+		.returning(move |url| limited_request(Method::DELETE, url.as_str(), &delete_routes, limits))
+	;
+	let get_routes               = Arc::clone(&shared);
+	_ = mock_client.expect_get()
+		.returning(move |url| limited_request(Method::GET, url.as_str(), &get_routes, limits))
+	;
+	let patch_routes             = Arc::clone(&shared);
+	_ = mock_client.expect_patch()
+		.returning(move |url| limited_request(Method::PATCH, url.as_str(), &patch_routes, limits))
+	;
+	let post_routes              = Arc::clone(&shared);
+	_ = mock_client.expect_post()
+		.returning(move |url| limited_request(Method::POST, url.as_str(), &post_routes, limits))
+	;
+	let put_routes               = Arc::clone(&shared);
+	_ = mock_client.expect_put()
+		.returning(move |url| limited_request(Method::PUT, url.as_str(), &put_routes, limits))
+	;
+	_ = mock_client.expect_request()
+		.returning(move |method, url| limited_request(method, url.as_str(), &shared, limits))
+	;
+	mock_client
+}
+
 //		create_mock_client														
 /// Creates a mock Reqwest client.
 /// 
 /// # Parameters
 /// 
-/// * `responses` - The responses to return for specific URLs. This is a list of
-///                 tuples, where the first element is the expected URL, and the
-///                 second element is the response to return.
+/// * `responses` - The responses to return for specific URLs. This is a list
+///   of tuples, where the first element is the expected URL, and the second
+///   element is the response to return.
+/// 
+/// # Panics
+/// 
+/// This function will panic if any of the URLs is invalid, naming the entry
+/// that it belongs to. Use [`try_create_mock_client()`] to handle this as an
+/// error instead.
+/// 
+/// The client will panic if a `GET` request is made to a URL other than the
+/// next one expected, showing the expected and received requests, or if more
+/// requests are made than were expected. When the client is dropped, it will
+/// panic with a list of any expected requests that were never made, unless a
+/// [`Context`] was entered on the current thread when the client was created,
+/// in which case they are reported as a failure when the context is verified.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use futures_executor::block_on;
+/// use reqwest::StatusCode;
+/// use sham::{Context, reqwest::{create_mock_client, create_mock_response}, verify_all};
+/// use std::collections::HashMap;
+/// 
+/// let context = Context::new();
+/// let _guard  = context.enter();
+/// let client  = create_mock_client(vec![
+///     ("https://api.example.com/users", Ok(create_mock_response("https://api.example.com/users", StatusCode::OK, None::<&str>, None, HashMap::<String, String>::new(), Ok(b"[]")))),
+///     ("https://api.example.com/teams", Ok(create_mock_response("https://api.example.com/teams", StatusCode::OK, None::<&str>, None, HashMap::<String, String>::new(), Ok(b"[]")))),
+/// ]);
+/// 
+/// let response = block_on(client.get("https://api.example.com/users").send()).unwrap();
+/// assert_eq!(response.status(), StatusCode::OK);
+/// drop(client);
+/// 
+/// let error = verify_all().unwrap_err();
+/// assert_eq!(error.failures[0].name, "create_mock_client()");
+/// assert!(error.failures[0].message.ends_with("never made:\n  - GET https://api.example.com/teams"));
+/// ```
 /// 
 #[must_use]
 pub fn create_mock_client<U: IntoUrl>(responses: Vec<(U, Result<MockResponse, MockError>)>) -> MockClient {
+	create_mock_client_with_headers(responses.into_iter().map(|(url, response)| (url, HeaderMap::new(), response)).collect())
+}
+
+//		create_mock_client_with_headers											
+/// Creates a mock Reqwest client that also requires headers to be sent.
+/// 
+/// This behaves in the same way as [`create_mock_client()`], except that each
+/// expected request is also given the headers that it must send, such as an
+/// `Authorization` header. The headers are checked when the request is sent,
+/// as they are set on the request after the URL, and any other headers sent
+/// are ignored. An empty map requires no headers.
+/// 
+/// # Parameters
+/// 
+/// * `responses` - The responses to return for specific URLs. This is a list
+///   of tuples, where the first element is the expected URL, the second is
+///   the headers that the request must send, and the third is the response to
+///   return.
+/// 
+/// # Panics
+/// 
+/// This function will panic if any of the URLs is invalid, naming the entry
+/// that it belongs to.
+/// 
+/// The client will panic in the same cases as the one created by
+/// [`create_mock_client()`], and also if a request is sent without one of the
+/// headers required for it, or with a different value, showing the expected
+/// and received headers.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use futures_executor::block_on;
+/// use reqwest::{StatusCode, header::{AUTHORIZATION, HeaderMap, HeaderValue}};
+/// use sham::reqwest::{create_mock_client_with_headers, create_mock_response};
+/// use std::collections::HashMap;
+/// 
+/// let headers = HeaderMap::from_iter([(AUTHORIZATION, HeaderValue::from_static("Bearer secret"))]);
+/// let client  = create_mock_client_with_headers(vec![
+///     ("https://api.example.com/me", headers, Ok(create_mock_response("https://api.example.com/me", StatusCode::OK, None::<&str>, None, HashMap::<String, String>::new(), Ok(b"{}")))),
+/// ]);
+/// 
+/// let response = block_on(client.get("https://api.example.com/me").bearer_auth("secret").send()).unwrap();
+/// assert_eq!(response.status(), StatusCode::OK);
+/// ```
+/// 
+/// A request without a required header fails:
+/// 
+/// ```rust,should_panic
+/// use futures_executor::block_on;
+/// use reqwest::{StatusCode, header::{AUTHORIZATION, HeaderMap, HeaderValue}};
+/// use sham::reqwest::{create_mock_client_with_headers, create_mock_response};
+/// use std::collections::HashMap;
+/// 
+/// let headers = HeaderMap::from_iter([(AUTHORIZATION, HeaderValue::from_static("Bearer secret"))]);
+/// let client  = create_mock_client_with_headers(vec![
+///     ("https://api.example.com/me", headers, Ok(create_mock_response("https://api.example.com/me", StatusCode::OK, None::<&str>, None, HashMap::<String, String>::new(), Ok(b"{}")))),
+/// ]);
+/// 
+/// drop(block_on(client.get("https://api.example.com/me").send()));
+/// ```
+/// 
+#[must_use]
+pub fn create_mock_client_with_headers<U: IntoUrl>(responses: Vec<(U, HeaderMap, Result<MockResponse, MockError>)>) -> MockClient {
+	let count           = responses.len();
 	let mut mock_client = MockClient::new();
-	let mut sequence    = Sequence::new();
-	for (mock_url, mock_response) in responses {
-		let expected_url: Url = mock_url.into_url().unwrap();
-		_ = mock_client.expect_get()
-			.withf(move |url| url.as_str() == expected_url.as_str())
-			.times(1)
-			.in_sequence(&mut sequence)
-			.returning(move |_| {
-				let mut mock_request    = MockRequestBuilder::new();
-				let mock_response_clone = mock_response.clone();
-				_ = mock_request.expect_send()
-					.times(1)
-					.returning(move || mock_response_clone.clone())
-				;
-				mock_request
-			})
-		;
-	}
+	let expected        = ExpectedRequests::new(responses.into_iter()
+		.enumerate()
+		.map(|(index, (mock_url, headers, mock_response))| {
+			let url = mock_url.into_url().unwrap_or_else(|error| panic!(
+				"create_mock_client() entry {} of {count} has an invalid URL: {}",
+				index + 1,
+				error.source().map_or_else(|| error.to_string(), ToString::to_string),
+			));
+			(url, headers, mock_response)
+		})
+		.collect()
+	);
+	_ = mock_client.expect_get()
+		.returning(move |url| {
+			let (request_url, headers, mock_response) = expected.next(url.as_str());
+			let mut mock_request                      = MockRequestBuilder::for_request(Method::GET, request_url, None, None);
+			mock_request.required                     = headers;
+			_ = mock_request.sender.expect_send()
+				.times(1)
+				.returning(move || mock_response.clone())
+			;
+			mock_request
+		})
+	;
 	mock_client
 }
 
@@ -695,6 +4274,13 @@ pub fn create_mock_client<U: IntoUrl>(responses: Vec<(U, Result<MockResponse, Mo
 /// * `extra_headers` - Any additional headers to include in the response.
 /// * `body`          - The body of the response.
 /// 
+/// # Panics
+/// 
+/// This function will panic if the URL is invalid, or if the content type or
+/// any of the extra headers is not a valid header. Use
+/// [`try_create_mock_response()`] to handle an invalid URL as an error
+/// instead.
+/// 
 pub fn create_mock_response<U, S1, S2, S3, H: BuildHasher>(
 	url:           U,
 	status:        StatusCode,
@@ -710,7 +4296,10 @@ where
 	S3: Into<String>,
 {
 	MockResponse {
-		url:     url.into_url().unwrap(),
+		url:     url.into_url().unwrap_or_else(|error| panic!(
+			"create_mock_response() was given an invalid URL: {}",
+			error.source().map_or_else(|| error.to_string(), ToString::to_string),
+		)),
 		status,
 		headers: {
 			let mut headers = HeaderMap::new();
@@ -725,8 +4314,533 @@ where
 			));
 			headers
 		},
-		body:    body.map(|bytes| Arc::new(Bytes::copy_from_slice(bytes))),
+		body:     body.map(|bytes| Arc::new(Bytes::copy_from_slice(bytes))),
+		chunks:   None,
+		stalled:  false,
+		trailers: None,
+		version:  Version::HTTP_11,
+		replays:  ReplayCount::default(),
+	}
+}
+
+//		create_pooled_mock_client												
+/// Creates a mock Reqwest client that serves the given routes over the
+/// simulated connections of the given pool.
+/// 
+/// This behaves in the same way as [`create_routed_mock_client()`], except
+/// that each request is also sent over a [`MockConnection`] from the pool, so
+/// that the connections opened and reused can be checked afterwards.
+/// 
+/// # Parameters
+/// 
+/// * `routes` - The routes to serve.
+/// * `pool`   - The pool of connections to use. This is shared with the given
+///   pool, rather than copied from it.
+/// 
+#[must_use]
+pub fn create_pooled_mock_client(routes: Vec<MockRoute>, pool: &MockConnectionPool) -> MockClient {
+	routed_mock_client(routes, Some(pool.clone()))
+}
+
+//		create_routed_mock_client												
+/// Creates a mock Reqwest client that serves the given routes.
+/// 
+/// Requests of any method are accepted any number of times, in any order, and
+/// each one is passed to the first route that matches it when sent. A request
+/// for a URL that cannot be parsed fails with a builder error when it is sent,
+/// as it does with the genuine client.
+/// 
+/// # Parameters
+/// 
+/// * `routes` - The routes to serve.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use futures_executor::block_on;
+/// use reqwest::{Method, StatusCode};
+/// use sham::reqwest::{MockRoute, create_mock_response, create_routed_mock_client};
+/// use std::collections::HashMap;
+/// 
+/// let client = create_routed_mock_client(vec![
+///     MockRoute::new(Method::GET, "https://api.example.com/health", |request| {
+///         Ok(create_mock_response(request.url.as_str(), StatusCode::OK, None::<&str>, None, HashMap::<String, String>::new(), Ok(b"")))
+///     }),
+/// ]);
+/// 
+/// block_on(async {
+///     assert_eq!(client.get("https://api.example.com/health").send().await.unwrap().status(), StatusCode::OK);
+///     assert!(client.get("/health").send().await.unwrap_err().is_builder());
+/// });
+/// ```
+/// 
+#[must_use]
+pub fn create_routed_mock_client(routes: Vec<MockRoute>) -> MockClient {
+	routed_mock_client(routes, None)
+}
+
+//		fault_result															
+/// Produces the result of a request that an injected fault applies to.
+/// 
+/// # Parameters
+/// 
+/// * `kind` - The kind of fault.
+/// * `url`  - The URL of the request.
+/// 
+fn fault_result(kind: FaultKind, url: &Url) -> Result<MockResponse, MockError> {
+	if kind == FaultKind::ServiceUnavailable {
+		return Ok(create_mock_response(
+			url.as_str(),
+			StatusCode::SERVICE_UNAVAILABLE,
+			None::<&str>,
+			None,
+			HashMap::<String, String>::new(),
+			Ok(b""),
+		));
+	}
+	Err(MockError {
+		is_connect: kind == FaultKind::ConnectionRefused,
+		is_request: true,
+		is_timeout: kind == FaultKind::Timeout,
+		source:     Some(Arc::new(kind.io_error())),
+		url:        Some(url.clone()),
+		..MockError::default()
+	})
+}
+
+//		days_from_civil															
+/// Converts a year, month, and day of the month into a number of days since
+/// the Unix epoch, using the proleptic Gregorian calendar, or `None` if the
+/// date is before the epoch.
+const fn days_from_civil(year: u64, month: u64, day: u64) -> Option<u64> {
+	let shifted_year  = if month <= 2 { year.saturating_sub(1) } else { year };
+	let era           = shifted_year.div_euclid(400);
+	let of_era        = shifted_year.rem_euclid(400);
+	let shifted_month = if month > 2 { month.saturating_sub(3) } else { month.saturating_add(9) };
+	let of_year       = shifted_month.saturating_mul(153).saturating_add(2).div_euclid(5).saturating_add(day).saturating_sub(1);
+	let days          = of_era.saturating_mul(365)
+		.saturating_add(of_era.div_euclid(4))
+		.saturating_sub(of_era.div_euclid(100))
+		.saturating_add(of_year)
+	;
+	era.saturating_mul(146_097).saturating_add(days).checked_sub(719_468)
+}
+
+//		find_route																
+/// Finds the route that matches the given request.
+/// 
+/// # Panics
+/// 
+/// This function will panic if no route matches the request, listing the
+/// routes that are available.
+/// 
+fn find_route<'r>(routes: &'r [MockRoute], request: &MockRequest) -> &'r MockRoute {
+	routes.iter().find(|route| route.matches(request)).unwrap_or_else(|| panic!(
+		"No mock route matches {} {}, available routes: {routes:?}",
+		request.method,
+		request.url,
+	))
+}
+
+//		layered_request															
+/// Creates a request builder that responds from the given routes, or else
+/// from the given base client.
+fn layered_request(method: Method, url: &str, routes: &Arc<[MockRoute]>, base: &Arc<MockClient>) -> MockRequestBuilder {
+	let mut builder = routed_request(method, url, routes, None);
+	builder.base    = Some(Arc::clone(base));
+	builder
+}
+
+//		limited_request															
+/// Creates a request builder that responds from the given routes, within the
+/// given limits.
+fn limited_request(method: Method, url: &str, routes: &Arc<[MockRoute]>, limits: MockLimits) -> MockRequestBuilder {
+	let mut builder = routed_request(method, url, routes, None);
+	builder.limits  = Some(limits);
+	builder
+}
+
+//		missing_url																
+/// Creates the error for a request that is sent without a URL.
+fn missing_url() -> MockError {
+	MockError::builder().builder().message("no URL has been set").build()
+}
+
+//		now																		
+/// Returns the current time, according to the virtual clock of the
+/// [`Context`] entered on the current thread, if there is one.
+fn now() -> SystemTime {
+	#[cfg(feature = "clock")]
+	if let Some(context) = Context::current() {
+		return context.clock().now();
+	}
+	SystemTime::now()
+}
+
+//		parse_http_date															
+/// Parses an HTTP date, such as `Sun, 06 Nov 1994 08:49:37 GMT`.
+fn parse_http_date(value: &str) -> Option<SystemTime> {
+	let parts = value.split_whitespace().collect::<Vec<_>>();
+	let [_, day, month, year, time, "GMT"] = *parts.as_slice() else {
+		return None;
+	};
+	let mut fields             = time.split(':').map(|field| field.parse::<u64>().ok());
+	let (hours, minutes, secs) = (fields.next()??, fields.next()??, fields.next()??);
+	let day_number             = day.parse::<u64>().ok().filter(|number| (1..=31).contains(number))?;
+	let month_number           = MONTHS.iter().position(|&name| name == month)?.saturating_add(1) as u64;
+	if fields.next().is_some() || hours > 23 || minutes > 59 || secs > 60 {
+		return None;
+	}
+	let days                   = days_from_civil(year.parse().ok()?, month_number, day_number)?;
+	let seconds                = days.checked_mul(86_400)?.checked_add(hours * 3_600 + minutes * 60 + secs)?;
+	UNIX_EPOCH.checked_add(Duration::from_secs(seconds))
+}
+
+//		receive_stream															
+/// Consumes a streaming body chunk by chunk, recording the size and timing of
+/// each chunk.
+/// 
+/// # Parameters
+/// 
+/// * `body`  - The body to consume.
+/// * `delay` - The time to wait after each chunk, if any.
+/// * `limit` - The number of bytes after which to break off, if any.
+/// 
+/// # Errors
+/// 
+/// A body error will be returned if the body produces an error, or if it is
+/// broken off.
+/// 
+#[cfg(not(target_arch = "wasm32"))]
+async fn receive_stream(
+	mut body: Body,
+	delay:    Option<Duration>,
+	limit:    Option<usize>,
+) -> Result<(Bytes, Vec<MockBodyChunk>), MockError> {
+	let started    = Instant::now();
+	let offset     = virtual_elapsed();
+	let mut data   = Vec::new();
+	let mut chunks = Vec::new();
+	while let Some(frame) = poll_fn(|cx| Pin::new(&mut body).poll_frame(cx)).await {
+		let Ok(chunk) = frame.map_err(|error| MockError { source: Some(Arc::new(error)), ..broken_upload() })?.into_data() else {
+			continue;
+		};
+		data.extend_from_slice(&chunk);
+		chunks.push(MockBodyChunk {
+			elapsed: started.elapsed().saturating_add(virtual_elapsed().saturating_sub(offset)),
+			size:    chunk.len(),
+		});
+		if limit.is_some_and(|after| data.len() >= after) {
+			return Err(broken_upload());
+		}
+		if let Some(pause) = delay {
+			sleep(pause).await;
+		}
+	}
+	Ok((data.into(), chunks))
+}
+
+//		resolve_url																
+/// Resolves the URL of a fixture, against the base URL of the entered context
+/// if there is one.
+/// 
+/// # Parameters
+/// 
+/// * `url` - The URL to resolve.
+/// 
+/// # Errors
+/// 
+/// Returns [`MockUrlError::Relative`] if the URL is relative and there is no
+/// base URL, or [`MockUrlError::Invalid`] if it cannot be parsed.
+/// 
+fn resolve_url(url: &str) -> Result<Url, MockUrlError> {
+	let base     = Context::current().and_then(|context| context.base_url());
+	let resolved = base.as_ref().map_or_else(|| Url::parse(url), |base_url| base_url.join(url));
+	resolved.map_err(|error| {
+		let relative = base.is_none() && Url::parse("http://localhost/").is_ok_and(|dummy| dummy.join(url).is_ok());
+		if relative {
+			MockUrlError::Relative(url.to_owned())
+		} else {
+			MockUrlError::Invalid(url.to_owned(), error.to_string())
+		}
+	})
+}
+
+//		routed_mock_client														
+/// Creates a mock Reqwest client that serves the given routes, over the
+/// connections of the given pool, if any.
+fn routed_mock_client(routes: Vec<MockRoute>, pool: Option<MockConnectionPool>) -> MockClient {
+	let shared: Arc<[MockRoute]>     = Arc::from(routes);
+	let mut mock_client              = MockClient::new();
+	let (delete_routes, delete_pool) = (Arc::clone(&shared), pool.clone());
+	_ = mock_client.expect_delete()
+		.returning(move |url| routed_request(Method::DELETE, url.as_str(), &delete_routes, delete_pool.as_ref()))
+	;
+	let (get_routes, get_pool)       = (Arc::clone(&shared), pool.clone());
+	_ = mock_client.expect_get()
+		.returning(move |url| routed_request(Method::GET, url.as_str(), &get_routes, get_pool.as_ref()))
+	;
+	let (patch_routes, patch_pool)   = (Arc::clone(&shared), pool.clone());
+	_ = mock_client.expect_patch()
+		.returning(move |url| routed_request(Method::PATCH, url.as_str(), &patch_routes, patch_pool.as_ref()))
+	;
+	let (post_routes, post_pool)     = (Arc::clone(&shared), pool.clone());
+	_ = mock_client.expect_post()
+		.returning(move |url| routed_request(Method::POST, url.as_str(), &post_routes, post_pool.as_ref()))
+	;
+	let (put_routes, put_pool)       = (Arc::clone(&shared), pool.clone());
+	_ = mock_client.expect_put()
+		.returning(move |url| routed_request(Method::PUT, url.as_str(), &put_routes, put_pool.as_ref()))
+	;
+	_ = mock_client.expect_request()
+		.returning(move |method, url| routed_request(method, url.as_str(), &shared, pool.as_ref()))
+	;
+	mock_client
+}
+
+//		routed_request															
+/// Creates a request builder that responds from the given routes, over the
+/// connections of the given pool, if any.
+fn routed_request(method: Method, url: &str, routes: &Arc<[MockRoute]>, pool: Option<&MockConnectionPool>) -> MockRequestBuilder {
+	match Url::parse(url) {
+		Ok(parsed) => MockRequestBuilder::for_request(method, parsed, Some(Arc::clone(routes)), pool.cloned()),
+		Err(error) => MockRequestBuilder {
+			error:  Some(MockError::builder().builder().source(error).build()),
+			method,
+			routes: Some(Arc::clone(routes)),
+			..Default::default()
+		},
+	}
+}
+
+//		receive_stream															
+/// Consumes a body, which in WebAssembly is always held in memory, and so is
+/// received as a single chunk.
+/// 
+/// # Parameters
+/// 
+/// * `body`  - The body to consume.
+/// * `delay` - The time to wait after the chunk, if any.
+/// * `limit` - The number of bytes after which to break off, if any.
+/// 
+/// # Errors
+/// 
+/// A body error will be returned if the body is broken off.
+/// 
+#[cfg(target_arch = "wasm32")]
+async fn receive_stream(
+	body:  Body,
+	delay: Option<Duration>,
+	limit: Option<usize>,
+) -> Result<(Bytes, Vec<MockBodyChunk>), MockError> {
+	let data = body.as_bytes().map(Bytes::copy_from_slice).unwrap_or_default();
+	if limit.is_some_and(|after| data.len() >= after) {
+		return Err(broken_upload());
+	}
+	if let Some(pause) = delay {
+		sleep(pause).await;
+	}
+	let chunks = vec![MockBodyChunk { elapsed: Duration::ZERO, size: data.len() }];
+	Ok((data, chunks))
+}
+
+//		sleep																	
+/// Waits for the given time without blocking the executor.
+/// 
+/// When the `clock` feature is enabled and a [`Context`] is entered on the
+/// current thread, this waits on its virtual clock, using
+/// [`MockClock::sleep()`](crate::clock::MockClock::sleep()), just as the waits
+/// of the mocked requests do. The clock is moved on to the end of the wait as
+/// soon as nothing else is happening, so no real time passes, and waits that
+/// run concurrently overlap rather than adding up. It can therefore be used in
+/// place of the sleep function of an async runtime, such as when backing off
+/// between retries, so that the retries and the requests share the same time.
+/// Otherwise, real time passes.
+/// 
+/// # Parameters
+/// 
+/// * `duration` - The time to wait.
+/// 
+pub async fn sleep(duration: Duration) {
+	if duration.is_zero() {
+		return;
 	}
+	#[cfg(feature = "clock")]
+	if let Some(waiting) = Context::current().map(|context| context.clock().sleep(duration)) {
+		waiting.await;
+		return;
+	}
+	wait(duration).await;
+}
+
+//		try_create_mock_client													
+/// Creates a mock Reqwest client, returning an error if any of the URLs is
+/// invalid.
+/// 
+/// This behaves in the same way as [`create_mock_client()`], except that an
+/// invalid URL is returned as an error that names the entry it belongs to,
+/// rather than causing a panic. Relative URLs are resolved against the base
+/// URL of the entered [`Context`], if one has been set with
+/// [`Context::set_base_url()`].
+/// 
+/// # Parameters
+/// 
+/// * `responses` - The responses to return for specific URLs. This is a list
+///   of tuples, where the first element is the expected URL, and the second
+///   element is the response to return.
+/// 
+/// # Errors
+/// 
+/// Returns [`MockUrlError::Entry`] for the first entry with a URL that cannot
+/// be resolved.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use sham::reqwest::{MockError, MockUrlError, try_create_mock_client};
+/// 
+/// let error = try_create_mock_client(vec![
+///     ("https://example.com/ok", Err(MockError::builder().build())),
+///     ("https://exa mple.com/",  Err(MockError::builder().build())),
+/// ]).unwrap_err();
+/// assert!(matches!(error, MockUrlError::Entry(2, _)));
+/// 
+/// let error = try_create_mock_client(vec![("users", Err(MockError::builder().build()))]).unwrap_err();
+/// assert_eq!(error, MockUrlError::Entry(1, Box::new(MockUrlError::Relative("users".to_owned()))));
+/// ```
+/// 
+pub fn try_create_mock_client<U: AsRef<str>>(
+	responses: Vec<(U, Result<MockResponse, MockError>)>,
+) -> Result<MockClient, MockUrlError> {
+	let resolved = responses.into_iter()
+		.enumerate()
+		.map(|(index, (mock_url, mock_response))| {
+			resolve_url(mock_url.as_ref())
+				.map(|url| (url, mock_response))
+				.map_err(|error| MockUrlError::Entry(index + 1, Box::new(error)))
+		})
+		.collect::<Result<Vec<_>, _>>()?
+	;
+	Ok(create_mock_client(resolved))
+}
+
+//		try_create_mock_response												
+/// Creates a mock Reqwest response, returning an error if the URL is invalid.
+/// 
+/// This behaves in the same way as [`create_mock_response()`], except that an
+/// invalid URL is returned as an error rather than causing a panic. A relative
+/// URL is resolved against the base URL of the entered [`Context`], if one has
+/// been set with [`Context::set_base_url()`].
+/// 
+/// # Parameters
+/// 
+/// * `url`           - The URL of the response.
+/// * `status`        - The status code of the response.
+/// * `content_type`  - The content type of the response.
+/// * `content_len`   - The content length of the response.
+/// * `extra_headers` - Any additional headers to include in the response.
+/// * `body`          - The body of the response.
+/// 
+/// # Errors
+/// 
+/// Returns [`MockUrlError::Relative`] if the URL is relative and no base URL
+/// has been set, or [`MockUrlError::Invalid`] if it cannot be parsed.
+/// 
+/// # Panics
+/// 
+/// This function will panic if the content type or any of the extra headers
+/// is not a valid header.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use reqwest::{StatusCode, Url};
+/// use sham::{Context, reqwest::try_create_mock_response};
+/// use std::collections::HashMap;
+/// 
+/// let context = Context::new();
+/// context.set_base_url(Url::parse("https://api.example.com/v1/").unwrap());
+/// let _guard  = context.enter();
+/// 
+/// let response = try_create_mock_response(
+///     "users/1",
+///     StatusCode::OK,
+///     Some("application/json"),
+///     None,
+///     HashMap::<String, String>::new(),
+///     Ok(br#"{"id":1}"#),
+/// ).unwrap();
+/// assert_eq!(response.url().as_str(), "https://api.example.com/v1/users/1");
+/// ```
+/// 
+pub fn try_create_mock_response<U, S1, S2, S3, H: BuildHasher>(
+	url:           U,
+	status:        StatusCode,
+	content_type:  Option<S1>,
+	content_len:   Option<usize>,
+	extra_headers: HashMap<S2, S3, H>,
+	body:          Result<&[u8], MockError>,
+) -> Result<MockResponse, MockUrlError>
+where
+	U:  AsRef<str>,
+	S1: Into<String>,
+	S2: Into<String>,
+	S3: Into<String>,
+{
+	let resolved = resolve_url(url.as_ref())?;
+	Ok(create_mock_response(resolved, status, content_type, content_len, extra_headers, body))
 }
 
+//		virtual_elapsed															
+/// Returns the time that has passed on the virtual clock of the [`Context`]
+/// entered on the current thread, or zero if there is none.
+#[cfg(all(feature = "clock", not(target_arch = "wasm32")))]
+fn virtual_elapsed() -> Duration {
+	Context::current().map_or(Duration::ZERO, |context| context.clock().elapsed())
+}
+
+//		virtual_elapsed															
+/// Returns zero, as there is no virtual clock without the `clock` feature.
+#[cfg(all(not(feature = "clock"), not(target_arch = "wasm32")))]
+const fn virtual_elapsed() -> Duration {
+	Duration::ZERO
+}
+
+//		wait																	
+/// Waits for the given time without blocking the executor, by waking the task
+/// from another thread.
+#[cfg(not(target_arch = "wasm32"))]
+fn wait(duration: Duration) -> impl Future<Output = ()> {
+	let state     = Arc::new(Mutex::new((false, None::<Waker>)));
+	let mut timer = None;
+	poll_fn(move |cx| {
+		let mut guard = state.lock().unwrap_or_else(PoisonError::into_inner);
+		if guard.0 {
+			return Poll::Ready(());
+		}
+		guard.1 = Some(cx.waker().clone());
+		drop(guard);
+		if timer.is_none() {
+			let shared = Arc::clone(&state);
+			timer      = Some(thread::spawn(move || {
+				thread::sleep(duration);
+				let mut elapsed = shared.lock().unwrap_or_else(PoisonError::into_inner);
+				elapsed.0       = true;
+				let waker       = elapsed.1.take();
+				drop(elapsed);
+				if let Some(task) = waker {
+					task.wake();
+				}
+			}));
+		}
+		Poll::Pending
+	})
+}
 
+//		wait																	
+/// Waits for the given time without blocking the executor, using a browser
+/// timer, as WebAssembly has no threads to wake the task from.
+#[cfg(target_arch = "wasm32")]
+fn wait(duration: Duration) -> impl Future<Output = ()> {
+	gloo_timers::future::sleep(duration)
+}
@@ -0,0 +1,461 @@
+//! This module mocks HTTP resources in order to test caching and downloading
+//! clients.
+//! 
+//! This module provides a resource served through the mocked
+//! [`reqwest`](crate::reqwest) client with the semantics of conditional and
+//! range requests. Each response carries an `ETag`, and a `GET` request with a
+//! matching `If-None-Match` header is answered with `304 Not Modified` and no
+//! body. A request with a `Range` header is answered with `206 Partial Content`
+//! and the matching slice of the content, or with `416 Range Not Satisfiable`
+//! if the range lies outside of it. The resource can be changed, or its tag
+//! replaced, at any point, in order to script cache-busting, and responses can
+//! be cut short, or ranges rejected, in order to script failed downloads. This
+//! is important because an HTTP cache layer otherwise needs a real server in
+//! order to test that it revalidates its entries, and download logic needs one
+//! in order to test that it resumes correctly after an interruption.
+//! 
+//! The approach taken is that the test code creates a [`MockResource`] for each
+//! URL that the client fetches, and gives the [`MockRoute`] for each one to
+//! [`create_routed_mock_client()`]. The status codes of the responses are
+//! recorded, so that tests can assert which requests were served from the
+//! cache, or resumed.
+//! 
+//! Notably, the mock is more restrictive than the "real" code. Only `ETag`
+//! validators are supported, and not `Last-Modified`, and tags are always
+//! compared weakly for `If-None-Match`, and strongly for `If-Range`. Only
+//! single byte ranges are supported, and a `Range` header asking for several
+//! is ignored, so that the whole content is sent.
+//! 
+//! # Examples
+//! 
+//! Revalidating a cached copy:
+//! 
+//! ```rust
+//! use sham::resource::MockResource;
+//! use reqwest::{StatusCode, header::{ETAG, IF_NONE_MATCH, HeaderMap}};
+//! 
+//! let resource = MockResource::new("https://cdn.example.com/app.js", b"console.log(1);");
+//! let client   = resource.client();
+//! 
+//! futures_executor::block_on(async {
+//!     let first = client.get("https://cdn.example.com/app.js").send().await.unwrap();
+//!     assert_eq!(first.status(), StatusCode::OK);
+//!     let etag  = first.headers().get(ETAG).unwrap().clone();
+//! 
+//!     let mut headers = HeaderMap::new();
+//!     headers.insert(IF_NONE_MATCH, etag.clone());
+//!     let second = client.get("https://cdn.example.com/app.js").headers(headers.clone()).send().await.unwrap();
+//!     assert_eq!(second.status(), StatusCode::NOT_MODIFIED);
+//!     assert!(second.bytes().await.unwrap().is_empty());
+//! 
+//!     resource.update(b"console.log(2);");
+//!     let third = client.get("https://cdn.example.com/app.js").headers(headers).send().await.unwrap();
+//!     assert_eq!(third.status(), StatusCode::OK);
+//!     assert_ne!(third.headers().get(ETAG).unwrap(), &etag);
+//! });
+//! 
+//! assert_eq!(resource.statuses(), vec![StatusCode::OK, StatusCode::NOT_MODIFIED, StatusCode::OK]);
+//! ```
+//! 
+//! Resuming an interrupted download:
+//! 
+//! ```rust
+//! use futures_util::StreamExt;
+//! use sham::resource::MockResource;
+//! use reqwest::{StatusCode, header::{CONTENT_LENGTH, CONTENT_RANGE, RANGE, HeaderMap}};
+//! 
+//! let resource = MockResource::new("https://dl.example.com/image.iso", b"0123456789");
+//! let client   = resource.client();
+//! resource.interrupt_after(4);
+//! 
+//! futures_executor::block_on(async {
+//!     let first = client.get("https://dl.example.com/image.iso").send().await.unwrap();
+//!     assert_eq!(first.headers().get(CONTENT_LENGTH).unwrap(), "10");
+//!     let mut stream   = first.bytes_stream();
+//!     let mut received = stream.next().await.unwrap().unwrap().to_vec();
+//!     assert_eq!(received, b"0123");
+//!     assert!(stream.next().await.unwrap().unwrap_err().is_body());
+//! 
+//!     let mut headers = HeaderMap::new();
+//!     headers.insert(RANGE, format!("bytes={}-", received.len()).parse().unwrap());
+//!     let second = client.get("https://dl.example.com/image.iso").headers(headers.clone()).send().await.unwrap();
+//!     assert_eq!(second.status(), StatusCode::PARTIAL_CONTENT);
+//!     assert_eq!(second.headers().get(CONTENT_RANGE).unwrap(), "bytes 4-9/10");
+//!     received.extend(second.bytes().await.unwrap());
+//!     assert_eq!(received, b"0123456789");
+//! 
+//!     resource.reject_ranges(true);
+//!     let third = client.get("https://dl.example.com/image.iso").headers(headers).send().await.unwrap();
+//!     assert_eq!(third.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+//! });
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::reqwest::{
+	MockClient,
+	MockError,
+	MockRequest,
+	MockResponse,
+	MockRoute,
+	create_mock_response,
+	create_routed_mock_client,
+};
+use bytes::Bytes;
+use core::ops::Range;
+use reqwest::{
+	IntoUrl,
+	Method,
+	StatusCode,
+	Url,
+	header::{HeaderName, ACCEPT_RANGES, CACHE_CONTROL, CONTENT_RANGE, ETAG, IF_NONE_MATCH, IF_RANGE, RANGE},
+};
+use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+
+
+//		Enums
+
+//		RangeRequest															
+/// The outcome of parsing a `Range` header.
+#[derive(Debug, Eq, PartialEq)]
+enum RangeRequest {
+	/// The header should be ignored, and the whole content sent.
+	Ignored,
+	
+	/// The header asks for the given span of the content.
+	Satisfiable(Range<usize>),
+	
+	/// The header asks for a span that lies outside of the content.
+	Unsatisfiable,
+}
+
+
+
+//		Structs
+
+//		MockResource															
+/// A mocked HTTP resource, which answers conditional and range requests.
+/// 
+/// Clones share the same content, tag, and record of responses.
+/// 
+#[derive(Clone, Debug)]
+pub struct MockResource {
+	//		Private properties													
+	/// The value of the `Cache-Control` header, if any.
+	cache_control: Option<String>,
+	
+	/// The content type of the resource.
+	content_type:  String,
+	
+	/// The current state of the resource, shared between clones.
+	state:         Arc<Mutex<ResourceState>>,
+	
+	/// The URL of the resource.
+	url:           Url,
+}
+
+//󰭅		MockResource															
+impl MockResource {
+	//		new																	
+	/// Creates a new resource with a content type of
+	/// `application/octet-stream`.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `url`  - The URL of the resource.
+	/// * `body` - The content of the resource.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the URL is not valid.
+	/// 
+	pub fn new<U: IntoUrl>(url: U, body: &[u8]) -> Self {
+		Self {
+			cache_control: None,
+			content_type:  "application/octet-stream".to_owned(),
+			state:         Arc::new(Mutex::new(ResourceState {
+				body:            body.to_vec(),
+				interrupt_after: None,
+				reject_ranges:   false,
+				statuses:        Vec::new(),
+				version:         1,
+			})),
+			url:           url.into_url().unwrap(),
+		}
+	}
+	
+	//		with_cache_control													
+	/// Sets the `Cache-Control` header sent with each response.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `cache_control` - The value of the header, e.g. `max-age=60`.
+	/// 
+	#[must_use]
+	pub fn with_cache_control(mut self, cache_control: &str) -> Self {
+		self.cache_control = Some(cache_control.to_owned());
+		self
+	}
+	
+	//		with_content_type													
+	/// Sets the content type of the resource.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `content_type` - The content type, e.g. `application/json`.
+	/// 
+	#[must_use]
+	pub fn with_content_type(mut self, content_type: &str) -> Self {
+		content_type.clone_into(&mut self.content_type);
+		self
+	}
+	
+	//		bust																
+	/// Replaces the tag of the resource without changing its content, so that
+	/// cached copies are no longer considered fresh.
+	pub fn bust(&self) {
+		self.lock().version += 1;
+	}
+	
+	//		client																
+	/// Creates a mock client that serves only this resource.
+	#[must_use]
+	pub fn client(&self) -> MockClient {
+		create_routed_mock_client(vec![self.route()])
+	}
+	
+	//		etag																
+	/// Returns the current `ETag` of the resource, including its quotes.
+	#[must_use]
+	pub fn etag(&self) -> String {
+		self.lock().etag()
+	}
+	
+	//		interrupt_after														
+	/// Cuts short the body of the next response that carries content, after the
+	/// given number of bytes, as though the connection had dropped. The bytes
+	/// sent are delivered as a chunk of the body stream, which then fails with
+	/// a body error, and reading the whole body fails with the same error. The
+	/// `Content-Length` states the full length, as the server would have sent
+	/// it, so that the client can resume from where it stopped.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `bytes` - The number of bytes to send before stopping.
+	/// 
+	pub fn interrupt_after(&self, bytes: usize) {
+		self.lock().interrupt_after = Some(bytes);
+	}
+	
+	//		reject_ranges														
+	/// Sets whether range requests are rejected with `416 Range Not
+	/// Satisfiable`, as though the server could not serve partial content.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `reject` - Whether to reject range requests.
+	/// 
+	pub fn reject_ranges(&self, reject: bool) {
+		self.lock().reject_ranges = reject;
+	}
+	
+	//		route																
+	/// Returns the route that serves the resource, for use with
+	/// [`create_routed_mock_client()`].
+	#[must_use]
+	pub fn route(&self) -> MockRoute {
+		let resource = self.clone();
+		MockRoute::new(Method::GET, self.url.clone(), move |request| resource.serve(request))
+	}
+	
+	//		statuses															
+	/// Returns the status codes of the responses served, in order.
+	#[must_use]
+	pub fn statuses(&self) -> Vec<StatusCode> {
+		self.lock().statuses.clone()
+	}
+	
+	//		update																
+	/// Changes the content of the resource, which also replaces its tag.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `body` - The new content.
+	/// 
+	pub fn update(&self, body: &[u8]) {
+		let mut state = self.lock();
+		body.clone_into(&mut state.body);
+		state.version += 1;
+		drop(state);
+	}
+	
+	//		url																	
+	/// Returns the URL of the resource.
+	#[must_use]
+	pub fn url(&self) -> Url {
+		self.url.clone()
+	}
+	
+	//		lock																
+	/// Locks the state of the resource.
+	fn lock(&self) -> MutexGuard<'_, ResourceState> {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+	
+	//		serve																
+	/// Serves a request.
+	#[expect(clippy::unnecessary_wraps, reason = "Needed for the route signature")]
+	fn serve(&self, request: &MockRequest) -> Result<MockResponse, MockError> {
+		let mut state   = self.lock();
+		let etag        = state.etag();
+		let total       = state.body.len();
+		let mut headers = HashMap::from([
+			(ACCEPT_RANGES.to_string(), "bytes".to_owned()),
+			(ETAG.to_string(),          etag.clone()),
+		]);
+		if let Some(ref cache_control) = self.cache_control {
+			drop(headers.insert(CACHE_CONTROL.to_string(), cache_control.clone()));
+		}
+		let matched     = request.headers.get_all(IF_NONE_MATCH).iter()
+			.filter_map(|value| value.to_str().ok())
+			.flat_map(|value| value.split(','))
+			.any(|tag| matches_weakly(tag.trim(), &etag))
+		;
+		let unchanged   = !matches!(header_str(request, &IF_RANGE), Some(tag) if tag != etag);
+		let range       = match header_str(request, &RANGE).filter(|_| unchanged) {
+			_ if matched                   => None,
+			None                           => Some(RangeRequest::Ignored),
+			Some(_) if state.reject_ranges => Some(RangeRequest::Unsatisfiable),
+			Some(value)                    => Some(parse_range(value, total)),
+		};
+		let response    = match range {
+			None                                  => {
+				create_mock_response(request.url.clone(), StatusCode::NOT_MODIFIED, None::<String>, None, headers, Ok(b""))
+			},
+			Some(RangeRequest::Unsatisfiable)     => {
+				drop(headers.insert(CONTENT_RANGE.to_string(), format!("bytes */{total}")));
+				create_mock_response(request.url.clone(), StatusCode::RANGE_NOT_SATISFIABLE, None::<String>, None, headers, Ok(b""))
+			},
+			Some(RangeRequest::Ignored)           => self.respond(request, StatusCode::OK, &mut state, 0..total, headers),
+			Some(RangeRequest::Satisfiable(span)) => {
+				drop(headers.insert(CONTENT_RANGE.to_string(), format!("bytes {}-{}/{total}", span.start, span.end - 1)));
+				self.respond(request, StatusCode::PARTIAL_CONTENT, &mut state, span, headers)
+			},
+		};
+		state.statuses.push(response.status);
+		drop(state);
+		Ok(response)
+	}
+	
+	//		respond																
+	/// Creates a response carrying part or all of the content, cut short if an
+	/// interruption has been scheduled.
+	fn respond(
+		&self,
+		request: &MockRequest,
+		status:  StatusCode,
+		state:   &mut ResourceState,
+		span:    Range<usize>,
+		headers: HashMap<String, String>,
+	) -> MockResponse {
+		let length = span.len();
+		let body   = &state.body[span];
+		let Some(limit) = state.interrupt_after.take().filter(|&bytes| bytes < length) else {
+			return create_mock_response(request.url.clone(), status, Some(self.content_type.clone()), Some(length), headers, Ok(body));
+		};
+		let error        = MockError::builder().body().message("connection closed before message completed").url(request.url.clone()).build();
+		let mut response = create_mock_response(request.url.clone(), status, Some(self.content_type.clone()), Some(length), headers, Err(error));
+		response.chunks  = Some(vec![Bytes::copy_from_slice(&body[..limit])]);
+		response
+	}
+}
+
+//		ResourceState															
+/// The state of a [`MockResource`].
+#[derive(Debug)]
+struct ResourceState {
+	//		Private properties													
+	/// The content of the resource.
+	body:            Vec<u8>,
+	
+	/// The number of bytes after which to cut short the next response, if
+	/// any.
+	interrupt_after: Option<usize>,
+	
+	/// Whether to reject all range requests.
+	reject_ranges:   bool,
+	
+	/// The status codes of the responses served.
+	statuses:        Vec<StatusCode>,
+	
+	/// The version of the content, from which the tag is derived.
+	version:         u64,
+}
+
+//󰭅		ResourceState															
+impl ResourceState {
+	//		etag																
+	/// Returns the current `ETag` of the resource, including its quotes.
+	fn etag(&self) -> String {
+		format!("\"v{}\"", self.version)
+	}
+}
+
+
+
+//		Functions
+
+//		header_str																
+/// Returns the value of a request header, if it is present and valid.
+fn header_str<'r>(request: &'r MockRequest, name: &HeaderName) -> Option<&'r str> {
+	request.headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+//		matches_weakly															
+/// Whether an entity tag from an `If-None-Match` header matches the current
+/// tag, using weak comparison.
+fn matches_weakly(candidate: &str, etag: &str) -> bool {
+	candidate == "*" || candidate.strip_prefix("W/").unwrap_or(candidate) == etag
+}
+
+//		parse_range																
+/// Parses the value of a `Range` header against content of the given length.
+/// Anything other than a single, valid byte range is ignored.
+fn parse_range(value: &str, total: usize) -> RangeRequest {
+	let Some((first, last)) = value.trim().strip_prefix("bytes=").and_then(|spec| spec.split_once('-')) else {
+		return RangeRequest::Ignored;
+	};
+	if last.contains(',') {
+		return RangeRequest::Ignored;
+	}
+	if first.is_empty() {
+		return match last.parse::<usize>() {
+			Ok(0)               => RangeRequest::Unsatisfiable,
+			Ok(_) if total == 0 => RangeRequest::Unsatisfiable,
+			Ok(suffix)          => RangeRequest::Satisfiable(total.saturating_sub(suffix)..total),
+			Err(_)              => RangeRequest::Ignored,
+		};
+	}
+	let Ok(start) = first.parse::<usize>() else {
+		return RangeRequest::Ignored;
+	};
+	let end = if last.is_empty() {
+		usize::MAX
+	} else {
+		match last.parse::<usize>() {
+			Ok(end) if end >= start => end,
+			_                       => return RangeRequest::Ignored,
+		}
+	};
+	if start >= total {
+		return RangeRequest::Unsatisfiable;
+	}
+	RangeRequest::Satisfiable(start..end.min(total - 1) + 1)
+}
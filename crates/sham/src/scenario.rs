@@ -0,0 +1,215 @@
+//! This module provides shared scenario states in order to coordinate mocks.
+//! 
+//! This module provides a [`MockScenario`], which holds a single named state
+//! that several mocks can both read and change, so that what one mock returns
+//! can depend upon what has happened to another. For example, once the mocked
+//! `systemctl start` command has "run", the mocked health-check endpoint can
+//! start returning `200 OK` instead of `503 Service Unavailable`. This is
+//! important because tests of code that orchestrates several external systems
+//! need those systems to react to each other consistently, and configuring
+//! each mock with a fixed sequence of responses is brittle, as it depends upon
+//! the exact order and number of the calls made.
+//! 
+//! The approach taken is that the scenario starts in a given state, and moves
+//! between states when told to, either unconditionally with
+//! [`set()`](MockScenario::set()), or only from a given state with
+//! [`transition()`](MockScenario::transition()). Clones share the same state,
+//! so a clone can be moved into the closures of any mock, such as the
+//! responders of [`MockRoute`]s, which [`route()`](MockScenario::route())
+//! creates with the current state passed in, or the `returning()` closures of
+//! the [`mockall`](https://crates.io/crates/mockall) mocks for commands, which
+//! can use [`select()`](MockScenario::select()) to choose their output by
+//! state. Every change of state is recorded, so that the test can check the
+//! path that the scenario took.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use futures_executor::block_on;
+//! use reqwest::{Method, StatusCode};
+//! use sham::{
+//!     reqwest::{create_mock_response, create_routed_mock_client},
+//!     scenario::MockScenario,
+//! };
+//! use std::collections::HashMap;
+//! 
+//! let scenario = MockScenario::new("stopped");
+//! let client   = create_routed_mock_client(vec![
+//!     scenario.route(Method::GET, "https://app.local/health", |state, request| {
+//!         let status = if state == "started" { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+//!         Ok(create_mock_response(request.url.as_str(), status, None::<&str>, None, HashMap::<String, String>::new(), Ok(b"")))
+//!     }),
+//! ]);
+//! let systemctl = {
+//!     let scenario = scenario.clone();
+//!     move |action: &str| {
+//!         if action == "start" {
+//!             scenario.transition("stopped", "started");
+//!         }
+//!         scenario.select(&[("started", 0), ("stopped", 3)]).unwrap_or(1)
+//!     }
+//! };
+//! 
+//! let health = || block_on(client.get("https://app.local/health").send()).unwrap().status();
+//! assert_eq!(systemctl("status"), 3);
+//! assert_eq!(health(), StatusCode::SERVICE_UNAVAILABLE);
+//! assert_eq!(systemctl("start"), 0);
+//! assert_eq!(health(), StatusCode::OK);
+//! assert_eq!(scenario.history(), vec!["stopped", "started"]);
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::reqwest::{MockError, MockRequest, MockResponse, MockRoute};
+use reqwest::{IntoUrl, Method};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+
+
+//		Structs
+
+//		MockScenario															
+/// A named state shared by the mocks that take part in a scenario.
+/// 
+/// Clones share the same state.
+/// 
+#[derive(Clone, Debug)]
+pub struct MockScenario {
+	//		Private properties													
+	/// The states that the scenario has been in, the last being the current
+	/// one.
+	history: Arc<Mutex<Vec<String>>>,
+}
+
+//󰭅		MockScenario															
+impl MockScenario {
+	//		new																	
+	/// Creates a new scenario in the given state.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `initial` - The state to start in.
+	/// 
+	#[must_use]
+	pub fn new(initial: &str) -> Self {
+		Self { history: Arc::new(Mutex::new(vec![initial.to_owned()])) }
+	}
+	
+	//		history																
+	/// Returns the states that the scenario has been in, in order, starting
+	/// with the initial state and ending with the current one.
+	#[must_use]
+	pub fn history(&self) -> Vec<String> {
+		self.lock().clone()
+	}
+	
+	//		is																	
+	/// Returns whether the scenario is in the given state.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `state` - The state to check for.
+	/// 
+	#[must_use]
+	pub fn is(&self, state: &str) -> bool {
+		self.lock().last().is_some_and(|current| current == state)
+	}
+	
+	//		route																
+	/// Creates a route whose responder is given the current state of the
+	/// scenario along with each request.
+	/// 
+	/// The responder can also change the state, using a clone of the scenario,
+	/// so that the route itself can move the scenario on.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `method`    - The HTTP method to match.
+	/// * `url`       - The URL to match. Any query string is ignored.
+	/// * `responder` - The function that produces the response for a matching
+	///   request, given the current state.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the URL is not valid.
+	/// 
+	/// # See also
+	/// 
+	/// * [`MockRoute::new()`]
+	/// 
+	pub fn route<U, F>(&self, method: Method, url: U, responder: F) -> MockRoute
+	where
+		U: IntoUrl,
+		F: Fn(&str, &MockRequest) -> Result<MockResponse, MockError> + Send + Sync + 'static,
+	{
+		let scenario = self.clone();
+		MockRoute::new(method, url, move |request| responder(&scenario.state(), request))
+	}
+	
+	//		select																
+	/// Returns the value given for the current state, if there is one.
+	/// 
+	/// This allows a mock to choose its output, such as the exit code or
+	/// output of a command, according to the state of the scenario.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `outputs` - The values for each state. If a state is listed more than
+	///   once, the first value is used.
+	/// 
+	#[must_use]
+	pub fn select<T: Clone>(&self, outputs: &[(&str, T)]) -> Option<T> {
+		let current = self.state();
+		outputs.iter().find(|&&(state, _)| state == current).map(|output| output.1.clone())
+	}
+	
+	//		set																	
+	/// Moves the scenario to the given state, whatever state it is in.
+	/// 
+	/// The change is recorded even if the scenario is already in the state.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `state` - The state to move to.
+	/// 
+	pub fn set(&self, state: &str) {
+		self.lock().push(state.to_owned());
+	}
+	
+	//		state																
+	/// Returns the current state.
+	#[must_use]
+	pub fn state(&self) -> String {
+		self.lock().last().cloned().unwrap_or_default()
+	}
+	
+	//		transition															
+	/// Moves the scenario to the given state, but only if it is in the
+	/// expected state, returning whether it moved.
+	/// 
+	/// The check and the change happen together, so that only one of several
+	/// mocks racing to make the same transition succeeds.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `from` - The state that the scenario must be in.
+	/// * `to`   - The state to move to.
+	/// 
+	pub fn transition(&self, from: &str, to: &str) -> bool {
+		let mut history = self.lock();
+		if !history.last().is_some_and(|current| current == from) {
+			return false;
+		}
+		history.push(to.to_owned());
+		true
+	}
+	
+	//		lock																
+	/// Locks the history of the scenario, recovering from poisoning.
+	fn lock(&self) -> MutexGuard<'_, Vec<String>> {
+		self.history.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+}
@@ -0,0 +1,199 @@
+//! This module provides scoped thread-local state in order to keep tests
+//! isolated from one another.
+//! 
+//! This module provides the [`Scope`] type, which every part of this crate
+//! that keeps state outside of the objects created by a test must use, along
+//! with the registry of all such state. This is important because `cargo test`
+//! runs tests in parallel, so any state that is shared across the process,
+//! such as a global recorder or the environment, can bleed from one test into
+//! another, and fail tests in ways that depend upon their timing.
+//! 
+//! The approach taken is that there is no global mutable state. Each kind of
+//! state has a thread-local stack of values, declared in the registry below,
+//! and a value is entered with [`Scope::enter()`], which returns a
+//! [`ScopeGuard`] that removes it again when dropped. Scopes therefore nest,
+//! and can be exited in any order without disturbing one another, and nothing
+//! outlives the test that set it up. A guard that is dropped on a different
+//! thread to the one that it was entered on has nothing to remove there, as
+//! the value was only ever visible on the thread that entered it. Futures can
+//! carry a value with them with [`Scope::scope()`], which enters it around
+//! every poll, making it task-local rather than thread-local.
+//! 
+//! A [`Scope`] can only be created in the registry, so that the registry lists
+//! all of the thread-local state of this crate, and new state is always scoped
+//! in the same way. Modules should not declare thread-local state of their own
+//! with `thread_local!`. State that belongs to other crates is scoped by their
+//! own guards, such as the default subscriber of the `tracing` mock and the
+//! local recorder of the `metrics` mock, and the mocked configuration and
+//! environment are held in memory rather than in the environment of the
+//! process.
+//! 
+
+
+
+//		Packages
+
+use crate::context::Context;
+use core::{
+	cell::RefCell,
+	fmt::{Debug, Formatter, self},
+	future::Future,
+	pin::Pin,
+	sync::atomic::{AtomicU64, Ordering},
+	task::{Context as TaskContext, Poll},
+};
+use std::thread::{self, LocalKey, ThreadId};
+
+#[cfg(feature = "log")]
+use crate::log::MockLogRecord;
+#[cfg(feature = "log")]
+use std::sync::{Arc, Mutex};
+
+
+
+//		Types
+
+/// The values entered for a scope on the current thread, in order, each with
+/// the identifier of the guard that entered it.
+type Stack<T> = RefCell<Vec<(u64, T)>>;
+
+
+
+//		Statics
+
+/// The identifier to give to the next guard.
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// The contexts entered on the current thread.
+pub static CONTEXT: Scope<Context> = Scope::new("context", &CONTEXTS);
+
+/// The log captures active on the current thread.
+#[cfg(feature = "log")]
+pub static LOG_CAPTURE: Scope<Arc<Mutex<Vec<MockLogRecord>>>> = Scope::new("log capture", &LOG_CAPTURES);
+
+thread_local! {
+	/// The stack for [`CONTEXT`].
+	static CONTEXTS: Stack<Context> = const { RefCell::new(Vec::new()) };
+	
+	/// The stack for [`LOG_CAPTURE`].
+	#[cfg(feature = "log")]
+	static LOG_CAPTURES: Stack<Arc<Mutex<Vec<MockLogRecord>>>> = const { RefCell::new(Vec::new()) };
+}
+
+
+
+//		Structs
+
+//		Scope																	
+/// A kind of state that is scoped to the current thread.
+pub struct Scope<T: 'static> {
+	//		Private properties													
+	/// The name of the state, for debugging.
+	name:  &'static str,
+	
+	/// The thread-local stack of entered values.
+	stack: &'static LocalKey<Stack<T>>,
+}
+
+//󰭅		Scope																	
+impl<T: Clone + 'static> Scope<T> {
+	//		new																	
+	/// Creates a new scope, backed by the given thread-local stack.
+	const fn new(name: &'static str, stack: &'static LocalKey<Stack<T>>) -> Self {
+		Self { name, stack }
+	}
+	
+	//		current																
+	/// Returns the value most recently entered on the current thread that is
+	/// still entered, if any.
+	pub fn current(&'static self) -> Option<T> {
+		self.stack.try_with(|stack| stack.borrow().last().map(|entry| entry.1.clone())).ok().flatten()
+	}
+	
+	//		enter																
+	/// Enters a value on the current thread, until the returned guard is
+	/// dropped.
+	pub fn enter(&'static self, value: T) -> ScopeGuard<T> {
+		let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+		self.stack.with(|stack| stack.borrow_mut().push((id, value)));
+		ScopeGuard { id, scope: self, thread: thread::current().id() }
+	}
+	
+	//		scope																
+	/// Wraps a future so that the given value is entered whenever it is
+	/// polled, on whichever thread that happens.
+	pub fn scope<F: Future>(&'static self, value: T, future: F) -> Scoped<F, T> {
+		Scoped { future: Box::pin(future), scope: self, value }
+	}
+}
+
+//		ScopeGuard																
+/// A guard that keeps a value entered in a [`Scope`] on the current thread.
+/// 
+/// When dropped, the value is removed, whether or not it is the one most
+/// recently entered.
+pub struct ScopeGuard<T: 'static> {
+	//		Private properties													
+	/// The identifier of the entry.
+	id:     u64,
+	
+	/// The scope that the value was entered in.
+	scope:  &'static Scope<T>,
+	
+	/// The thread that the value was entered on.
+	thread: ThreadId,
+}
+
+//󰭅		Debug																	
+impl<T> Debug for ScopeGuard<T> {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ScopeGuard")
+			.field("scope",  &self.scope.name)
+			.field("id",     &self.id)
+			.field("thread", &self.thread)
+			.finish()
+	}
+}
+
+//󰭅		Drop																	
+impl<T> Drop for ScopeGuard<T> {
+	//		drop																
+	fn drop(&mut self) {
+		if thread::current().id() != self.thread {
+			return;
+		}
+		let removed = self.scope.stack.try_with(|stack| {
+			let mut entries = stack.borrow_mut();
+			entries.iter().position(|entry| entry.0 == self.id).map(|index| entries.remove(index))
+		});
+		drop(removed);
+	}
+}
+
+//		Scoped																	
+/// A future that enters a value in a [`Scope`] whenever it is polled.
+pub struct Scoped<F, T: 'static> {
+	//		Private properties													
+	/// The wrapped future.
+	future: Pin<Box<F>>,
+	
+	/// The scope to enter the value in.
+	scope:  &'static Scope<T>,
+	
+	/// The value to enter.
+	value:  T,
+}
+
+//󰭅		Future																	
+impl<F: Future, T: Clone + Unpin + 'static> Future for Scoped<F, T> {
+	type Output = F::Output;
+	
+	//		poll																
+	fn poll(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<F::Output> {
+		let guard  = self.scope.enter(self.value.clone());
+		let result = self.future.as_mut().poll(cx);
+		drop(guard);
+		result
+	}
+}
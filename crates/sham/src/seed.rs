@@ -0,0 +1,166 @@
+//! This module provides the seed of each test in order to make randomised
+//! behaviour reproducible.
+//! 
+//! This module provides [`seed()`], which returns the seed of the current test,
+//! and [`seed_for()`], which derives a separate seed from it for each part of a
+//! test that makes random choices, such as a `FaultPlan` injecting chaos, or a
+//! set of `MockWeightedResponses` from the `reqwest` module. This is important
+//! because tests that use randomness to explore failures are only useful if a
+//! failure can be replayed, and hard-coding a seed in every test means that
+//! the same small set of cases is tried on every run, whereas choosing seeds
+//! at random means that a failure looks flaky and cannot be reproduced.
+//! 
+//! The approach taken is that each [`Context`] has one seed, which is taken
+//! from the `SHAM_SEED` environment variable if it is set, or is otherwise
+//! chosen at random when it is first needed, and which can also be set with
+//! [`Context::set_seed()`]. Each part of a test derives its own seed from this
+//! by name, so that adding randomness to one part does not change the choices
+//! made by the others. If the context was entered, and its seed was used, the
+//! seed is printed to standard error when the test fails, so that running the
+//! test again with `SHAM_SEED` set to that value replays exactly the same
+//! choices. The `test` attribute, when the `macros` feature is enabled, does
+//! this for every test. Outside of an entered context, there is
+//! no seed shared by the test, and each call takes the seed from `SHAM_SEED`,
+//! or chooses a new one.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use sham::{Context, seed::{derive_seed, seed_for}};
+//! 
+//! let context = Context::new();
+//! context.set_seed(42);
+//! let _guard  = context.enter();
+//! 
+//! assert_eq!(sham::seed(), 42);
+//! assert_eq!(seed_for("fault"), derive_seed(42, "fault"));
+//! assert_ne!(seed_for("fault"), seed_for("weighted"));
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::context::Context;
+use core::hash::{BuildHasher, Hasher};
+use std::{
+	collections::hash_map::RandomState,
+	env,
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+
+
+//		Constants
+
+/// The environment variable from which the seed of each test is taken, if it
+/// is set.
+pub const SEED_VARIABLE: &str = "SHAM_SEED";
+
+
+
+//		Functions
+
+//		derive_seed																
+/// Derives the seed for one part of a test from the seed of the test.
+/// 
+/// The derivation is stable, so the same seed and name always give the same
+/// result, on every platform and with every version of this crate.
+/// 
+/// # Parameters
+/// 
+/// * `seed`      - The seed of the test.
+/// * `subsystem` - The name of the part of the test, such as `fault`.
+/// 
+#[must_use]
+pub fn derive_seed(seed: u64, subsystem: &str) -> u64 {
+	let mut state = subsystem.bytes().fold(seed ^ 0xCBF2_9CE4_8422_2325, |hash, byte| {
+		(hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01B3)
+	});
+	split_mix64(&mut state)
+}
+
+//		seed																	
+/// Returns the seed of the current test.
+/// 
+/// This is the seed of the context that has been entered on the current
+/// thread. If no context has been entered, it is taken from the `SHAM_SEED`
+/// environment variable, or chosen at random if that is not set.
+/// 
+/// # Panics
+/// 
+/// This function will panic if `SHAM_SEED` is set, but is not an unsigned
+/// 64-bit integer, in decimal or in hex with a `0x` prefix.
+/// 
+/// # See also
+/// 
+/// * [`Context::seed()`]
+/// 
+#[must_use]
+pub fn seed() -> u64 {
+	Context::current().map_or_else(initial_seed, |context| context.seed())
+}
+
+//		seed_for																
+/// Returns the seed for one part of the current test, derived from the seed of
+/// the test.
+/// 
+/// # Parameters
+/// 
+/// * `subsystem` - The name of the part of the test, such as `fault`.
+/// 
+/// # Panics
+/// 
+/// This function will panic if `SHAM_SEED` is set, but is not an unsigned
+/// 64-bit integer.
+/// 
+/// # See also
+/// 
+/// * [`derive_seed()`]
+/// * [`seed()`]
+/// 
+#[must_use]
+pub fn seed_for(subsystem: &str) -> u64 {
+	derive_seed(seed(), subsystem)
+}
+
+//		initial_seed															
+/// Returns the seed with which a test starts, which is taken from the
+/// `SHAM_SEED` environment variable if it is set, or otherwise chosen at
+/// random.
+/// 
+/// # Panics
+/// 
+/// This function will panic if `SHAM_SEED` is set, but is not an unsigned
+/// 64-bit integer.
+/// 
+pub(crate) fn initial_seed() -> u64 {
+	let Some(value) = env::var_os(SEED_VARIABLE) else {
+		let mut hasher = RandomState::new().build_hasher();
+		hasher.write_u128(SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_nanos()));
+		return hasher.finish();
+	};
+	let text    = value.to_string_lossy();
+	let trimmed = text.trim();
+	let parsed  = trimmed.strip_prefix("0x").or_else(|| trimmed.strip_prefix("0X"))
+		.map_or_else(|| trimmed.parse(), |hex| u64::from_str_radix(hex, 16))
+	;
+	parsed.unwrap_or_else(|_| panic!("{SEED_VARIABLE} must be an unsigned 64-bit integer, but is `{trimmed}`"))
+}
+
+//		split_mix64																
+/// Advances the given generator state, and returns the next pseudo-random
+/// number, using the `SplitMix64` algorithm.
+/// 
+/// # Parameters
+/// 
+/// * `state` - The state of the generator, which starts as the seed.
+/// 
+pub(crate) const fn split_mix64(state: &mut u64) -> u64 {
+	*state        = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+	let mut value = *state;
+	value         = (value ^ (value >> 30_u8)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+	value         = (value ^ (value >> 27_u8)).wrapping_mul(0x94D0_49BB_1331_11EB);
+	value ^ (value >> 31_u8)
+}
@@ -0,0 +1,582 @@
+//! This module mocks an error-reporting client in order to test reporting.
+//! 
+//! This module provides a [`MockSentry`] client, in the style of the
+//! [`sentry`](https://crates.io/crates/sentry) crate, which captures messages
+//! and errors as [`MockSentryEvent`]s, complete with the tags, fingerprint,
+//! extra data, and breadcrumbs of the scope that they were captured in. This
+//! is important because code that is meant to report specific failures, and
+//! just as importantly is meant not to report expected ones, should be checked
+//! without sending anything over the network.
+//! 
+//! The approach taken is that the "real" code should report through a thin
+//! client type that wraps the error-reporting library, and uses the
+//! [`MockSentry`] in its place when running in test mode. The test code then
+//! runs the code and inspects the captured events. Clones share the same
+//! events and scope, so the test can keep one and hand another to the code
+//! under test.
+//! 
+//! Notably, the mock is more restrictive than the "real" code. Tag and extra
+//! values are strings, and there is a single hub, so the scope is shared
+//! between threads.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use sham::sentry::{MockBreadcrumb, MockLevel, MockSentry};
+//! use std::io::{Error as IoError, ErrorKind as IoErrorKind};
+//! 
+//! let sentry = MockSentry::new();
+//! sentry.configure_scope(|scope| scope.set_tag("component", "billing"));
+//! sentry.add_breadcrumb(MockBreadcrumb { message: Some("charging card".to_owned()), ..Default::default() });
+//! 
+//! sentry.with_scope(
+//!     |scope| scope.set_fingerprint(Some(&["payment-declined"])),
+//!     || sentry.capture_error(&IoError::new(IoErrorKind::Other, "declined")),
+//! );
+//! sentry.capture_message("retrying", MockLevel::Info);
+//! 
+//! let events = sentry.events();
+//! assert_eq!(events.len(), 2);
+//! assert_eq!(events[0].level, MockLevel::Error);
+//! assert_eq!(events[0].exception[0].value, "declined");
+//! assert_eq!(events[0].fingerprint, vec!["payment-declined"]);
+//! assert_eq!(events[0].tags["component"], "billing");
+//! assert_eq!(events[0].breadcrumbs.len(), 1);
+//! assert_eq!(events[1].fingerprint, vec!["{{ default }}"]);
+//! ```
+//! 
+
+
+
+//		Packages
+
+use core::{
+	error::Error,
+	iter::successors,
+};
+use std::{
+	collections::{BTreeMap, VecDeque},
+	sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+
+
+//		Modules
+
+//		prelude																	
+/// The mocks, named after the genuine types.
+/// 
+/// This allows the mocks for the [Sentry](https://crates.io/crates/sentry)
+/// crate to be imported with a single glob import when testing, without
+/// renaming them. The names are the same as those used by the `swap_uses`
+/// attribute, and by the combined [`prelude`](crate::prelude).
+/// 
+pub mod prelude {
+	pub use super::{
+		MockBreadcrumb      as Breadcrumb,
+		MockLevel           as Level,
+		MockScope           as Scope,
+		MockSentryEvent     as Event,
+		MockSentryException as Exception,
+	};
+}
+
+
+
+//		Constants
+
+/// The maximum number of breadcrumbs kept, as per the Sentry default.
+const MAX_BREADCRUMBS: usize = 100;
+
+
+
+//		Enums
+
+//		MockLevel																
+/// The severity level of an event or breadcrumb.
+/// 
+/// # See also
+/// 
+/// * `sentry::Level`
+/// 
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub enum MockLevel {
+	/// Debugging information.
+	Debug,
+	
+	/// Informational messages.
+	#[default]
+	Info,
+	
+	/// Warnings.
+	Warning,
+	
+	/// Errors.
+	Error,
+	
+	/// Fatal errors.
+	Fatal,
+}
+
+
+
+//		Structs
+
+//		MockBreadcrumb															
+/// A breadcrumb, recording something that happened before an event.
+/// 
+/// # See also
+/// 
+/// * `sentry::Breadcrumb`
+/// 
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MockBreadcrumb {
+	//		Public properties													
+	/// The category of the breadcrumb, e.g. `http`.
+	pub category: Option<String>,
+	
+	/// Additional data about the breadcrumb.
+	pub data:     BTreeMap<String, String>,
+	
+	/// The severity level of the breadcrumb.
+	pub level:    MockLevel,
+	
+	/// A human-readable message.
+	pub message:  Option<String>,
+	
+	/// The type of the breadcrumb. Sentry uses `default` if this is empty.
+	pub ty:       String,
+}
+
+//		MockScope																
+/// The contextual data attached to captured events.
+/// 
+/// # See also
+/// 
+/// * `sentry::Scope`
+/// 
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MockScope {
+	//		Private properties													
+	/// The breadcrumbs, oldest first.
+	breadcrumbs: VecDeque<MockBreadcrumb>,
+	
+	/// The extra data.
+	extra:       BTreeMap<String, String>,
+	
+	/// The fingerprint, if overridden.
+	fingerprint: Option<Vec<String>>,
+	
+	/// The level, if overridden.
+	level:       Option<MockLevel>,
+	
+	/// The tags.
+	tags:        BTreeMap<String, String>,
+	
+	/// The identifier of the user, if set.
+	user:        Option<String>,
+}
+
+//󰭅		MockScope																
+impl MockScope {
+	//		clear_breadcrumbs													
+	/// Removes all breadcrumbs.
+	/// 
+	/// # See also
+	/// 
+	/// * `sentry::Scope::clear_breadcrumbs()`
+	/// 
+	pub fn clear_breadcrumbs(&mut self) {
+		self.breadcrumbs.clear();
+	}
+	
+	//		remove_extra														
+	/// Removes an item of extra data.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `key` - The key of the extra data.
+	/// 
+	/// # See also
+	/// 
+	/// * `sentry::Scope::remove_extra()`
+	/// 
+	pub fn remove_extra(&mut self, key: &str) {
+		drop(self.extra.remove(key));
+	}
+	
+	//		remove_tag															
+	/// Removes a tag.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `key` - The key of the tag.
+	/// 
+	/// # See also
+	/// 
+	/// * `sentry::Scope::remove_tag()`
+	/// 
+	pub fn remove_tag(&mut self, key: &str) {
+		drop(self.tags.remove(key));
+	}
+	
+	//		set_extra															
+	/// Sets an item of extra data.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `key`   - The key of the extra data.
+	/// * `value` - The value of the extra data.
+	/// 
+	/// # See also
+	/// 
+	/// * `sentry::Scope::set_extra()`
+	/// 
+	pub fn set_extra(&mut self, key: &str, value: &str) {
+		drop(self.extra.insert(key.to_owned(), value.to_owned()));
+	}
+	
+	//		set_fingerprint														
+	/// Sets the fingerprint used to group events, or restores the default
+	/// grouping if [`None`].
+	/// 
+	/// # Parameters
+	/// 
+	/// * `fingerprint` - The parts of the fingerprint.
+	/// 
+	/// # See also
+	/// 
+	/// * `sentry::Scope::set_fingerprint()`
+	/// 
+	pub fn set_fingerprint(&mut self, fingerprint: Option<&[&str]>) {
+		self.fingerprint = fingerprint.map(|parts| parts.iter().map(|&part| part.to_owned()).collect());
+	}
+	
+	//		set_level															
+	/// Overrides the level of captured events, or stops overriding it if
+	/// [`None`].
+	/// 
+	/// # Parameters
+	/// 
+	/// * `level` - The level to use.
+	/// 
+	/// # See also
+	/// 
+	/// * `sentry::Scope::set_level()`
+	/// 
+	pub const fn set_level(&mut self, level: Option<MockLevel>) {
+		self.level = level;
+	}
+	
+	//		set_tag																
+	/// Sets a tag.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `key`   - The key of the tag.
+	/// * `value` - The value of the tag.
+	/// 
+	/// # See also
+	/// 
+	/// * `sentry::Scope::set_tag()`
+	/// 
+	pub fn set_tag(&mut self, key: &str, value: &str) {
+		drop(self.tags.insert(key.to_owned(), value.to_owned()));
+	}
+	
+	//		set_user															
+	/// Sets the identifier of the user, or clears it if [`None`].
+	/// 
+	/// # Parameters
+	/// 
+	/// * `user` - The identifier of the user.
+	/// 
+	/// # See also
+	/// 
+	/// * `sentry::Scope::set_user()`
+	/// 
+	pub fn set_user(&mut self, user: Option<&str>) {
+		self.user = user.map(ToOwned::to_owned);
+	}
+	
+	//		apply																
+	/// Creates an event with the data of this scope.
+	fn apply(&self, event_id: String, level: MockLevel, message: Option<String>, exception: Vec<MockSentryException>) -> MockSentryEvent {
+		MockSentryEvent {
+			breadcrumbs: self.breadcrumbs.iter().cloned().collect(),
+			event_id,
+			exception,
+			extra:       self.extra.clone(),
+			fingerprint: self.fingerprint.clone().unwrap_or_else(|| vec!["{{ default }}".to_owned()]),
+			level:       self.level.unwrap_or(level),
+			message,
+			tags:        self.tags.clone(),
+			user:        self.user.clone(),
+		}
+	}
+}
+
+//		MockSentry																
+/// A mocked error-reporting client.
+#[derive(Clone, Debug, Default)]
+pub struct MockSentry {
+	//		Private properties													
+	/// The state of the client, shared between clones.
+	state: Arc<Mutex<SentryState>>,
+}
+
+//󰭅		MockSentry																
+impl MockSentry {
+	//		new																	
+	/// Creates a new client with an empty scope and no events.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+	
+	//		add_breadcrumb														
+	/// Adds a breadcrumb to the current scope. Only the most recent 100
+	/// breadcrumbs are kept.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `breadcrumb` - The breadcrumb to add.
+	/// 
+	/// # See also
+	/// 
+	/// * `sentry::add_breadcrumb()`
+	/// 
+	pub fn add_breadcrumb(&self, breadcrumb: MockBreadcrumb) {
+		let mut state = self.lock();
+		let scope     = state.scope_mut();
+		if scope.breadcrumbs.len() >= MAX_BREADCRUMBS {
+			drop(scope.breadcrumbs.pop_front());
+		}
+		scope.breadcrumbs.push_back(breadcrumb);
+		drop(state);
+	}
+	
+	//		capture_error														
+	/// Captures an error, along with its chain of sources, at the
+	/// [`Error`](MockLevel::Error) level.
+	/// 
+	/// As with Sentry, the exceptions are recorded with the innermost source
+	/// first, and the type of each is taken from its [`Debug`](core::fmt::Debug)
+	/// output.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `error` - The error to capture.
+	/// 
+	/// # See also
+	/// 
+	/// * `sentry::capture_error()`
+	/// 
+	pub fn capture_error<E: Error>(&self, error: &E) -> String {
+		let root: &dyn Error = error;
+		let mut exception    = successors(Some(root), |&current| current.source())
+			.map(|current| MockSentryException {
+				ty:    type_from_debug(&format!("{current:?}")),
+				value: current.to_string(),
+			})
+			.collect::<Vec<_>>()
+		;
+		exception.reverse();
+		self.capture(MockLevel::Error, None, exception)
+	}
+	
+	//		capture_message														
+	/// Captures a message at the given level.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `message` - The message to capture.
+	/// * `level`   - The level of the message.
+	/// 
+	/// # See also
+	/// 
+	/// * `sentry::capture_message()`
+	/// 
+	pub fn capture_message(&self, message: &str, level: MockLevel) -> String {
+		self.capture(level, Some(message.to_owned()), Vec::new())
+	}
+	
+	//		clear																
+	/// Removes all captured events, leaving the scope unchanged.
+	pub fn clear(&self) {
+		self.lock().events.clear();
+	}
+	
+	//		configure_scope														
+	/// Modifies the current scope.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `configure` - The function to modify the scope with.
+	/// 
+	/// # See also
+	/// 
+	/// * `sentry::configure_scope()`
+	/// 
+	pub fn configure_scope<F, R>(&self, configure: F) -> R
+	where
+		F: FnOnce(&mut MockScope) -> R,
+	{
+		let mut state = self.lock();
+		let result    = configure(state.scope_mut());
+		drop(state);
+		result
+	}
+	
+	//		events																
+	/// Returns the captured events, in order.
+	#[must_use]
+	pub fn events(&self) -> Vec<MockSentryEvent> {
+		self.lock().events.clone()
+	}
+	
+	//		with_scope															
+	/// Runs a callback in a temporary scope, which starts as a copy of the
+	/// current scope and is discarded afterwards.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `configure` - The function to modify the temporary scope with.
+	/// * `callback`  - The function to run in the temporary scope.
+	/// 
+	/// # See also
+	/// 
+	/// * `sentry::with_scope()`
+	/// 
+	pub fn with_scope<C, F, R>(&self, configure: C, callback: F) -> R
+	where
+		C: FnOnce(&mut MockScope),
+		F: FnOnce() -> R,
+	{
+		let mut state = self.lock();
+		let mut scope = state.scope_mut().clone();
+		configure(&mut scope);
+		state.scopes.push(scope);
+		drop(state);
+		let result    = callback();
+		drop(self.lock().scopes.pop());
+		result
+	}
+	
+	//		capture																
+	/// Captures an event in the current scope.
+	fn capture(&self, level: MockLevel, message: Option<String>, exception: Vec<MockSentryException>) -> String {
+		let mut state = self.lock();
+		state.last_id = state.last_id.saturating_add(1);
+		let event_id  = format!("{:032x}", state.last_id);
+		let event     = state.scope_mut().apply(event_id.clone(), level, message, exception);
+		state.events.push(event);
+		drop(state);
+		event_id
+	}
+	
+	//		lock																
+	/// Locks the shared state.
+	fn lock(&self) -> MutexGuard<'_, SentryState> {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+}
+
+//		MockSentryEvent															
+/// A captured event.
+/// 
+/// # See also
+/// 
+/// * `sentry::protocol::Event`
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockSentryEvent {
+	//		Public properties													
+	/// The breadcrumbs of the scope, oldest first.
+	pub breadcrumbs: Vec<MockBreadcrumb>,
+	
+	/// The ID of the event.
+	pub event_id:    String,
+	
+	/// The exceptions, innermost first. This is empty for messages.
+	pub exception:   Vec<MockSentryException>,
+	
+	/// The extra data of the scope.
+	pub extra:       BTreeMap<String, String>,
+	
+	/// The fingerprint used to group the event.
+	pub fingerprint: Vec<String>,
+	
+	/// The severity level of the event.
+	pub level:       MockLevel,
+	
+	/// The message, for captured messages.
+	pub message:     Option<String>,
+	
+	/// The tags of the scope.
+	pub tags:        BTreeMap<String, String>,
+	
+	/// The identifier of the user, if set.
+	pub user:        Option<String>,
+}
+
+//		MockSentryException														
+/// An exception within a captured event.
+/// 
+/// # See also
+/// 
+/// * `sentry::protocol::Exception`
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockSentryException {
+	//		Public properties													
+	/// The type of the error.
+	pub ty:    String,
+	
+	/// The message of the error.
+	pub value: String,
+}
+
+//		SentryState																
+/// The state of a [`MockSentry`].
+#[derive(Debug, Default)]
+struct SentryState {
+	//		Private properties													
+	/// The captured events.
+	events:  Vec<MockSentryEvent>,
+	
+	/// The number used for the last event ID.
+	last_id: u128,
+	
+	/// The stack of scopes, innermost last. The root scope is created when
+	/// first needed.
+	scopes:  Vec<MockScope>,
+}
+
+//󰭅		SentryState																
+impl SentryState {
+	//		scope_mut															
+	/// Returns the current scope.
+	fn scope_mut(&mut self) -> &mut MockScope {
+		if self.scopes.is_empty() {
+			self.scopes.push(MockScope::default());
+		}
+		self.scopes.last_mut().expect("There is always a scope")
+	}
+}
+
+
+
+//		Functions
+
+//		type_from_debug															
+/// Derives the type of an error from its [`Debug`](core::fmt::Debug) output,
+/// in the same way as Sentry, falling back to `Error`.
+fn type_from_debug(debug: &str) -> String {
+	let name = debug.split(|c: char| !c.is_alphanumeric() && c != '_' && c != ':').next().unwrap_or_default();
+	let last = name.rsplit("::").next().unwrap_or_default();
+	if last.is_empty() { "Error".to_owned() } else { last.to_owned() }
+}
@@ -0,0 +1,361 @@
+//! This module provides shell command parsers in order to test quoting.
+//! 
+//! This module provides [`MockShell`], which splits a command string that is
+//! passed to `sh -c` or `cmd /C` into the words and operators that the shell
+//! would see, and checks that given values reach the shell as single words,
+//! without being split, expanded, or turned into further commands. This is
+//! important because code that builds command strings from user-provided
+//! values, such as file names and branch names, is a classic source of
+//! injection vulnerabilities, and a test that only compares the command string
+//! against an expected one cannot tell whether the quoting would actually hold
+//! up against a hostile value.
+//! 
+//! The approach taken is that the command string that the code under test
+//! passes to the shell is captured, such as from the arguments given to a
+//! mocked command, and is then re-parsed here using the quoting rules of the
+//! shell, and [`MockShell::assert_quoted()`] checks that each value appears,
+//! intact, as a word of its own. The parsers are deliberately conservative:
+//! anything that the shell could expand, such as a `$` outside single quotes,
+//! counts as an expansion, even where the shell would treat it literally, as
+//! code that relies on such subtleties is fragile anyway. They do not run
+//! anything, or perform the expansions.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use sham::shell::MockShell;
+//! 
+//! let name = "backup; rm -rf ~";
+//! let safe = format!("tar -czf '{}.tgz' data", name.replace('\'', r"'\''"));
+//! MockShell::Posix.assert_quoted(&safe, &[&format!("{name}.tgz")]);
+//! 
+//! assert!(!MockShell::Posix.is_quoted(&format!("tar -czf {name} data"), name));
+//! assert!(!MockShell::Posix.is_quoted(r#"echo "$(id)""#, "$(id)"));
+//! assert!(MockShell::Cmd.is_quoted(r#"type "C:\My Files\a & b.txt""#, r"C:\My Files\a & b.txt"));
+//! assert!(!MockShell::Cmd.is_quoted(r#"type "%USERPROFILE%""#, "%USERPROFILE%"));
+//! ```
+//! 
+
+
+
+//		Packages
+
+use core::{
+	error::Error,
+	fmt::{Display, Formatter, self},
+	iter::Peekable,
+	str::Chars,
+};
+
+
+
+//		Enums
+
+//		MockShell																
+/// A shell whose quoting rules a command string can be parsed with.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum MockShell {
+	/// The Windows command interpreter, as run by `cmd /C`.
+	/// 
+	/// Double quotes protect spaces and operators, and `^` escapes the next
+	/// character outside them. Variables in the form `%NAME%`, and `!NAME!`
+	/// when delayed expansion is enabled, are expanded even inside double
+	/// quotes, so any value that contains `%` or `!` counts as expanded.
+	Cmd,
+	
+	/// A POSIX shell, as run by `sh -c`.
+	/// 
+	/// Single quotes protect everything up to the next single quote, double
+	/// quotes protect everything apart from `$`, `` ` ``, and `\`, and `\`
+	/// escapes the next character outside quotes. Parameter and command
+	/// substitutions, globs, and a leading `~` count as expansions.
+	Posix,
+}
+
+//󰭅		MockShell																
+impl MockShell {
+	//		assert_quoted														
+	/// Asserts that each of the given values reaches the shell as a single
+	/// word, intact, and without any expansion.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `command` - The command string passed to the shell.
+	/// * `values`  - The values that should have been quoted.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the command cannot be parsed, or if any of
+	/// the values is not a word of its own, listing the tokens that the
+	/// command was split into.
+	/// 
+	pub fn assert_quoted(self, command: &str, values: &[&str]) {
+		let tokens = self.split(command).unwrap_or_else(|error| {
+			panic!("Expected a command that {self} can parse, but {command:?} is invalid: {error}")
+		});
+		for value in values {
+			assert!(
+				tokens.iter().any(|token| token.is_literal(value)),
+				"Expected {value:?} to reach {self} as a single word without expansion, but {command:?} splits into:\n{}",
+				tokens.iter().fold(String::new(), |mut list, token| {
+					list.push_str("  - ");
+					list.push_str(&token.to_string());
+					list.push('\n');
+					list
+				}),
+			);
+		}
+	}
+	
+	//		is_quoted															
+	/// Whether the given value reaches the shell as a single word, intact, and
+	/// without any expansion.
+	/// 
+	/// A command that cannot be parsed does not quote anything.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `command` - The command string passed to the shell.
+	/// * `value`   - The value that should have been quoted.
+	/// 
+	#[must_use]
+	pub fn is_quoted(self, command: &str, value: &str) -> bool {
+		self.split(command).is_ok_and(|tokens| tokens.iter().any(|token| token.is_literal(value)))
+	}
+	
+	//		split																
+	/// Splits a command string into the words and operators that the shell
+	/// would see, with the quotes and escapes removed from the words.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `command` - The command string passed to the shell.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if a quote is not closed, or if the command
+	/// ends with an escape character.
+	/// 
+	pub fn split(self, command: &str) -> Result<Vec<MockShellToken>, MockShellError> {
+		let mut splitter = Splitter { chars: command.chars().peekable(), tokens: Vec::new(), word: None };
+		match self {
+			Self::Cmd   => splitter.split_cmd()?,
+			Self::Posix => splitter.split_posix()?,
+		}
+		splitter.finish_word();
+		Ok(splitter.tokens)
+	}
+}
+
+//󰭅		Display																	
+impl Display for MockShell {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let description = match *self {
+			Self::Cmd   => "cmd /C",
+			Self::Posix => "sh -c",
+		};
+		write!(f, "{description}")
+	}
+}
+
+//		MockShellError															
+/// An error when parsing a command string.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockShellError {
+	/// The command ends with an escape character, which has nothing to escape.
+	TrailingEscape,
+	
+	/// A quote of the given kind is never closed.
+	UnterminatedQuote(char),
+}
+
+//󰭅		Display																	
+impl Display for MockShellError {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match *self {
+			Self::TrailingEscape           => write!(f, "Command ends with an escape character"),
+			Self::UnterminatedQuote(quote) => write!(f, "Unterminated {quote} quote"),
+		}
+	}
+}
+
+//󰭅		Error																	
+impl Error for MockShellError {}
+
+//		MockShellToken															
+/// A token of a command string, as the shell would see it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockShellToken {
+	/// An operator that separates or redirects commands, such as `;`, `&&`,
+	/// `|`, or `>`. A line break is given as `\n`.
+	Operator(String),
+	
+	/// A word, which is an argument of a command.
+	Word {
+		/// The text of the word, with its quotes and escapes removed.
+		text:    String,
+		
+		/// Whether the shell could expand the word, such as by substituting a
+		/// variable, in which case the text is given as written.
+		expands: bool,
+	},
+}
+
+//󰭅		MockShellToken															
+impl MockShellToken {
+	//		is_literal															
+	/// Whether the token is a word with exactly the given text, which the
+	/// shell does not expand.
+	fn is_literal(&self, value: &str) -> bool {
+		matches!(*self, Self::Word { ref text, expands: false } if text == value)
+	}
+}
+
+//󰭅		Display																	
+impl Display for MockShellToken {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match *self {
+			Self::Operator(ref operator)            => write!(f, "operator \"{}\"", operator.escape_debug()),
+			Self::Word { ref text, expands: false } => write!(f, "word \"{}\"", text.escape_debug()),
+			Self::Word { ref text, expands: true }  => write!(f, "expanded word \"{}\"", text.escape_debug()),
+		}
+	}
+}
+
+
+
+//		Structs
+
+//		Splitter																
+/// The state of splitting a command string into tokens.
+struct Splitter<'c> {
+	//		Private properties													
+	/// The characters that remain to be read.
+	chars:  Peekable<Chars<'c>>,
+	
+	/// The tokens found so far.
+	tokens: Vec<MockShellToken>,
+	
+	/// The text of the word being read, and whether it expands, if a word has
+	/// been started.
+	word:   Option<(String, bool)>,
+}
+
+//󰭅		Splitter																
+impl Splitter<'_> {
+	//		finish_word															
+	/// Adds the word being read, if any, to the tokens.
+	fn finish_word(&mut self) {
+		if let Some((text, expands)) = self.word.take() {
+			self.tokens.push(MockShellToken::Word { text, expands });
+		}
+	}
+	
+	//		operator															
+	/// Adds an operator, taking the next character as well if the two form a
+	/// single operator, such as `&&`.
+	fn operator(&mut self, first: char, pairs: &[&str]) {
+		self.finish_word();
+		let mut operator = first.to_string();
+		if let Some(&next) = self.chars.peek() {
+			operator.push(next);
+			if pairs.contains(&operator.as_str()) {
+				_ = self.chars.next();
+			} else {
+				_ = operator.pop();
+			}
+		}
+		self.tokens.push(MockShellToken::Operator(operator));
+	}
+	
+	//		push																
+	/// Adds a character to the word being read, starting one if needed.
+	fn push(&mut self, c: char, expands: bool) {
+		let word = self.word.get_or_insert_with(|| (String::new(), false));
+		word.0.push(c);
+		word.1  |= expands;
+	}
+	
+	//		split_cmd															
+	/// Splits the command using the rules of `cmd /C`.
+	fn split_cmd(&mut self) -> Result<(), MockShellError> {
+		let mut quoted = false;
+		while let Some(c) = self.chars.next() {
+			match c {
+				'"'                                  => {
+					quoted = !quoted;
+					_      = self.word.get_or_insert_with(|| (String::new(), false));
+				},
+				'%' | '!'                            => self.push(c, true),
+				_ if quoted                          => self.push(c, false),
+				' ' | '\t'                           => self.finish_word(),
+				'\r' | '\n'                          => self.operator('\n', &[]),
+				'^'                                  => {
+					let escaped = self.chars.next().ok_or(MockShellError::TrailingEscape)?;
+					self.push(escaped, false);
+				},
+				'&' | '|' | '<' | '>' | '(' | ')'    => self.operator(c, &["&&", "||", ">>"]),
+				_                                    => self.push(c, false),
+			}
+		}
+		if quoted {
+			return Err(MockShellError::UnterminatedQuote('"'));
+		}
+		Ok(())
+	}
+	
+	//		split_posix															
+	/// Splits the command using the rules of `sh -c`.
+	fn split_posix(&mut self) -> Result<(), MockShellError> {
+		while let Some(c) = self.chars.next() {
+			match c {
+				' ' | '\t'                                   => self.finish_word(),
+				'\n'                                         => self.operator('\n', &[]),
+				';' | '&' | '|' | '<' | '>' | '(' | ')'      => self.operator(c, &["&&", "||", ";;", ">>", "<<", ">&", "<&", "|&", ">|"]),
+				'#' if self.word.is_none()                   => {
+					while self.chars.next_if(|&next| next != '\n').is_some() {}
+				},
+				'\\'                                         => match self.chars.next() {
+					Some('\n')    => {},
+					Some(escaped) => self.push(escaped, false),
+					None          => return Err(MockShellError::TrailingEscape),
+				},
+				'\''                                         => {
+					_ = self.word.get_or_insert_with(|| (String::new(), false));
+					loop {
+						match self.chars.next() {
+							Some('\'')   => break,
+							Some(quoted) => self.push(quoted, false),
+							None         => return Err(MockShellError::UnterminatedQuote('\'')),
+						}
+					}
+				},
+				'"'                                          => {
+					_ = self.word.get_or_insert_with(|| (String::new(), false));
+					loop {
+						match self.chars.next() {
+							Some('"')          => break,
+							Some('\\')         => match self.chars.next_if(|&next| matches!(next, '$' | '`' | '"' | '\\' | '\n')) {
+								Some('\n')    => {},
+								Some(escaped) => self.push(escaped, false),
+								None          => self.push('\\', false),
+							},
+							Some(quoted)       => self.push(quoted, matches!(quoted, '$' | '`')),
+							None               => return Err(MockShellError::UnterminatedQuote('"')),
+						}
+					}
+				},
+				'~' if self.word.is_none()                   => self.push(c, true),
+				'$' | '`' | '*' | '?' | '['                  => self.push(c, true),
+				_                                            => self.push(c, false),
+			}
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,207 @@
+//! This module mocks [`std::thread::sleep()`] in order to test blocking waits.
+//! 
+//! This module provides a [`Sleeper`] trait, with a [`RealSleeper`] that
+//! blocks the current thread, and a [`MockSleeper`] that instead advances a
+//! [`MockClock`] and records each sleep that was requested. This is important
+//! because synchronous retry loops, polling loops, and rate limiters should be
+//! testable without waiting for real time to pass, and the amount of time that
+//! they would have waited is often the very thing that needs to be checked.
+//! 
+//! The approach taken is that the "real" code should sleep, and read the time,
+//! through a [`Sleeper`] that it is given, using the [`RealSleeper`] in normal
+//! operation. The test code then supplies a [`MockSleeper`] instead, and checks
+//! the recorded sleeps afterwards. Because sleeping advances the shared clock,
+//! any other mocks in this crate that are driven by the same [`MockClock`],
+//! such as caches and token expiry, see the time pass as well.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use core::time::Duration;
+//! use sham::{
+//!     clock::MockClock,
+//!     sleep::{MockSleeper, Sleeper},
+//! };
+//! 
+//! fn retry<S: Sleeper>(sleeper: &S, attempts: u32) {
+//!     for attempt in 0..attempts {
+//!         sleeper.sleep(Duration::from_millis(100) * 2_u32.pow(attempt));
+//!     }
+//! }
+//! 
+//! let clock   = MockClock::new();
+//! let sleeper = MockSleeper::new(&clock);
+//! retry(&sleeper, 3);
+//! 
+//! assert_eq!(sleeper.count(), 3);
+//! assert_eq!(sleeper.total(), Duration::from_millis(700));
+//! assert_eq!(clock.elapsed(), Duration::from_millis(700));
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::clock::MockClock;
+use core::time::Duration;
+use std::{
+	sync::{Arc, Mutex, MutexGuard, PoisonError},
+	thread,
+	time::SystemTime,
+};
+
+
+
+//		Traits
+
+//§		Sleeper																	
+/// A provider of blocking sleeps and of the current time.
+/// 
+/// # See also
+/// 
+/// * [`std::thread::sleep()`]
+/// * [`std::time::SystemTime::now()`]
+/// 
+pub trait Sleeper {
+	//		now																	
+	/// Returns the current time.
+	/// 
+	/// # See also
+	/// 
+	/// * [`std::time::SystemTime::now()`]
+	/// 
+	fn now(&self) -> SystemTime;
+	
+	//		sleep																
+	/// Blocks the current thread for the given duration.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `duration` - The amount of time to sleep for.
+	/// 
+	/// # See also
+	/// 
+	/// * [`std::thread::sleep()`]
+	/// 
+	fn sleep(&self, duration: Duration);
+	
+	//		sleep_until															
+	/// Blocks the current thread until the given time. If the time has already
+	/// passed, this returns immediately without sleeping.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `deadline` - The time to sleep until.
+	/// 
+	fn sleep_until(&self, deadline: SystemTime) {
+		if let Ok(remaining) = deadline.duration_since(self.now()) {
+			if !remaining.is_zero() {
+				self.sleep(remaining);
+			}
+		}
+	}
+}
+
+
+
+//		Structs
+
+//		MockSleeper																
+/// A mocked sleeper, which advances a [`MockClock`] instead of blocking.
+/// 
+/// Each sleep requested is recorded, and clones share the same records.
+/// 
+#[derive(Clone, Debug)]
+pub struct MockSleeper {
+	//		Private properties													
+	/// The clock to advance.
+	clock:  MockClock,
+	
+	/// The durations of the sleeps requested, in order.
+	sleeps: Arc<Mutex<Vec<Duration>>>,
+}
+
+//󰭅		MockSleeper																
+impl MockSleeper {
+	//		new																	
+	/// Creates a new sleeper that advances the given clock.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `clock` - The clock to advance when sleeping.
+	/// 
+	#[must_use]
+	pub fn new(clock: &MockClock) -> Self {
+		Self {
+			clock:  clock.clone(),
+			sleeps: Arc::new(Mutex::new(Vec::new())),
+		}
+	}
+	
+	//		count																
+	/// Returns the number of sleeps requested.
+	#[must_use]
+	pub fn count(&self) -> usize {
+		self.lock().len()
+	}
+	
+	//		reset																
+	/// Clears the recorded sleeps, without moving the clock.
+	pub fn reset(&self) {
+		self.lock().clear();
+	}
+	
+	//		sleeps																
+	/// Returns the durations of the sleeps requested, in order.
+	#[must_use]
+	pub fn sleeps(&self) -> Vec<Duration> {
+		self.lock().clone()
+	}
+	
+	//		total																
+	/// Returns the total amount of time that sleeping was requested for.
+	#[must_use]
+	pub fn total(&self) -> Duration {
+		self.lock().iter().sum()
+	}
+	
+	//		lock																
+	/// Locks the recorded sleeps.
+	fn lock(&self) -> MutexGuard<'_, Vec<Duration>> {
+		self.sleeps.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+}
+
+//󰭅		Sleeper																	
+impl Sleeper for MockSleeper {
+	//		now																	
+	fn now(&self) -> SystemTime {
+		self.clock.now()
+	}
+	
+	//		sleep																
+	fn sleep(&self, duration: Duration) {
+		self.lock().push(duration);
+		self.clock.advance(duration);
+	}
+}
+
+//		RealSleeper																
+/// A sleeper that really blocks the current thread, for use in normal
+/// operation.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RealSleeper;
+
+//󰭅		Sleeper																	
+impl Sleeper for RealSleeper {
+	//		now																	
+	fn now(&self) -> SystemTime {
+		SystemTime::now()
+	}
+	
+	//		sleep																
+	fn sleep(&self, duration: Duration) {
+		thread::sleep(duration);
+	}
+}
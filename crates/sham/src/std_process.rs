@@ -21,18 +21,43 @@
 //! [`FakeCommand`] when running in test mode. This is achieved by using
 //! conditional compilation. The test code then configures the mocks to expect
 //! certain requests and to return certain responses, and then runs the tests.
+//! Each command that is executed is recorded, along with its outcome, with the
+//! [`Context`] entered on the current thread, if any, so that it appears in the
+//! report of the interactions.
 //! 
 
 
 
 //		Packages
 
+use crate::context::{Context, mismatch_message};
 use std::{
 	env::args,
 	ffi::OsStr,
 	io::Error as IoError,
+	sync::{Arc, Mutex, PoisonError},
+	thread,
 };
-use mockall::{Sequence, automock};
+use mockall::automock;
+
+
+
+//		Modules
+
+//		prelude																	
+/// The mocks, named after the genuine types.
+/// 
+/// This allows the mocks for [`std::process`] to be imported with a single glob
+/// import when testing, without renaming them. The names are the same as those
+/// used by the `swap_uses` attribute, and by the combined
+/// [`prelude`](crate::prelude).
+/// 
+pub mod prelude {
+	pub use super::{
+		FakeCommand as Command,
+		MockStdio   as Stdio,
+	};
+}
 
 
 
@@ -104,15 +129,34 @@ pub trait Command {
 /// then interacts with the mocked functions, whilst returning itself for
 /// chaining.
 /// 
+/// If a [`Context`] is entered on the current thread when the command is
+/// created, a check is registered with it that fails with a list of any
+/// expected calls that were never made. Otherwise, the command panics with that
+/// message when it is dropped, unless the thread is already panicking.
+/// 
 /// # See also
 /// 
 /// * [`std::process::Command`]
 /// 
 #[derive(Debug, Default)]
 pub struct FakeCommand {
+	/// The arguments given to the command, for recording its execution.
+	arguments:  Vec<String>,
+	
 	/// The mock command. This is a stand-in for the real command instance, and
 	/// will behave in the same way, but do nothing.
-	command: MockCommand,
+	command:    MockCommand,
+	
+	/// The names of the expected calls that have not been made yet, in order,
+	/// shared with the registered check.
+	pending:    Arc<Mutex<Vec<&'static str>>>,
+	
+	/// The program to run, for recording its execution.
+	program:    String,
+	
+	/// Whether the check for missing calls has been registered with a context,
+	/// rather than being made when the command is dropped.
+	registered: bool,
 }
 
 //󰭅		FakeCommand																
@@ -120,42 +164,76 @@ impl FakeCommand {
 	//		new																	
 	/// Creates a new instance of the [`FakeCommand`] struct.
 	/// 
+	/// The command expects to be given the arguments of the current process,
+	/// and then to have its standard input, output, and error configured, and
+	/// to be executed, in that order. Any call made out of order panics.
+	/// 
+	/// # Examples
+	/// 
+	/// ```rust
+	/// use sham::{Context, std_process::{FakeCommand, MockStdio}, verify_all};
+	/// use std::env::args;
+	/// 
+	/// let context = Context::new();
+	/// let _guard  = context.enter();
+	/// let mut git = FakeCommand::new("git");
+	/// _ = git.args(args().skip(1).collect()).stdin(MockStdio::inherit());
+	/// drop(git);
+	/// 
+	/// let error = verify_all().unwrap_err();
+	/// assert_eq!(error.failures[0].name, "FakeCommand git");
+	/// assert!(error.failures[0].message.ends_with("never made:\n  - stdout()\n  - stderr()\n  - exec()"));
+	/// ```
+	/// 
 	/// # See also
 	/// 
 	/// * [`std::process::Command::new()`]
 	/// 
 	#[must_use]
-	pub fn new<S: AsRef<OsStr>>(_program: S) -> Self {
-		let mut sequence     = Sequence::new();
+	pub fn new<S: AsRef<OsStr>>(program: S) -> Self {
 		let mut mock_command = MockCommand::new();
 		_ = mock_command.expect_args()
-			.withf(|list| list == &args().skip(1).collect::<Vec<_>>())
-			.times(1)
-			.in_sequence(&mut sequence)
-			.returning(|_| ())
+			.times(0..=1)
+			.returning(|list| {
+				let expected = args().skip(1).collect::<Vec<_>>();
+				assert!(list == expected, "{}", mismatch_message(
+					"FakeCommand expected to be given the arguments of the current process",
+					&format!("{expected:?}"),
+					&format!("{list:?}"),
+				));
+			})
 		;
 		_ = mock_command.expect_stdin()
-			.times(1)
-			.in_sequence(&mut sequence)
+			.times(0..=1)
 			.returning(|_| ())
 		;
 		_ = mock_command.expect_stdout()
-			.times(1)
-			.in_sequence(&mut sequence)
+			.times(0..=1)
 			.returning(|_| ())
 		;
 		_ = mock_command.expect_stderr()
-			.times(1)
-			.in_sequence(&mut sequence)
+			.times(0..=1)
 			.returning(|_| ())
 		;
 		_ = mock_command.expect_exec()
-			.times(1)
-			.in_sequence(&mut sequence)
+			.times(0..=1)
 			.returning(|| IoError::from_raw_os_error(0))
 		;
+		let name       = program.as_ref().to_string_lossy().into_owned();
+		let pending    = Arc::new(Mutex::new(vec!["args", "stdin", "stdout", "stderr", "exec"]));
+		let registered = Context::current().map(|context| {
+			let remaining = Arc::clone(&pending);
+			let run       = name.clone();
+			context.register(format!("FakeCommand {name}"), move || {
+				Self::check(&run, &remaining.lock().unwrap_or_else(PoisonError::into_inner))
+			});
+		}).is_some();
 		Self {
-			command: mock_command,
+			arguments: Vec::new(),
+			command:   mock_command,
+			pending,
+			program:   name,
+			registered,
 		}
 	}
 	
@@ -167,19 +245,107 @@ impl FakeCommand {
 	/// * [`std::process::Command::args()`]
 	/// 
 	pub fn args(&mut self, args: Vec<String>) -> &mut Self {
+		self.arguments.extend(args.iter().cloned());
+		self.called("args");
 		self.command.args(args);
 		self
 	}
 	
+	//		called																
+	/// Marks an expected call as having been made.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the call.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the call is not the next one expected.
+	/// 
+	fn called(&self, name: &'static str) {
+		let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+		let next        = pending.first().copied();
+		if next == Some(name) {
+			_ = pending.remove(0);
+			return;
+		}
+		drop(pending);
+		let expected = next.map_or_else(|| "no call".to_owned(), |call| format!("{call}()"));
+		panic!("{}", mismatch_message(
+			&format!("FakeCommand for {} expected {expected} next", self.program),
+			&expected,
+			&format!("{name}()"),
+		));
+	}
+	
+	//		check																
+	/// Checks that all of the expected calls have been made.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `program` - The program that the command runs.
+	/// * `pending` - The names of the expected calls that have not been made.
+	/// 
+	/// # Errors
+	/// 
+	/// An error message listing the calls that were never made will be
+	/// returned if there are any.
+	/// 
+	fn check(program: &str, pending: &[&str]) -> Result<(), String> {
+		if pending.is_empty() {
+			return Ok(());
+		}
+		let missing = pending.iter().fold(String::new(), |mut list, name| {
+			list.push_str("\n  - ");
+			list.push_str(name);
+			list.push_str("()");
+			list
+		});
+		Err(format!("FakeCommand expected {program} to be run, but {} of its calls were never made:{missing}", pending.len()))
+	}
+	
 	//		exec																
 	/// Unix-specific extensions to the [`Command`] builder.
 	/// 
+	/// The command is recorded, along with the error returned, with the
+	/// [`Context`] entered on the current thread, if any.
+	/// 
+	/// # Examples
+	/// 
+	/// ```rust
+	/// use sham::{Context, std_process::{FakeCommand, MockStdio}};
+	/// use std::env::args;
+	/// 
+	/// let context   = Context::new();
+	/// let guard     = context.enter();
+	/// let arguments = args().skip(1).collect::<Vec<_>>();
+	/// let error     = FakeCommand::new("git")
+	///     .args(arguments.clone())
+	///     .stdin(MockStdio::inherit())
+	///     .stdout(MockStdio::inherit())
+	///     .stderr(MockStdio::inherit())
+	///     .exec()
+	/// ;
+	/// drop(guard);
+	/// 
+	/// let interactions = context.interactions();
+	/// assert_eq!(interactions[0].module,  "std_process");
+	/// assert_eq!(interactions[0].action,  ["git".to_owned()].into_iter().chain(arguments).collect::<Vec<_>>().join(" "));
+	/// assert_eq!(interactions[0].outcome, error.to_string());
+	/// ```
+	/// 
 	/// # See also
 	/// 
 	/// * [`std::os::unix::process::CommandExt::exec()`]
 	/// 
 	pub fn exec(&mut self) -> IoError {
-		self.command.exec()
+		self.called("exec");
+		let error = self.command.exec();
+		if let Some(context) = Context::current() {
+			let action = self.arguments.iter().fold(self.program.clone(), |action, argument| format!("{action} {argument}"));
+			context.record("std_process", &action, &error.to_string());
+		}
+		error
 	}
 	
 	//		stdin																
@@ -190,6 +356,7 @@ impl FakeCommand {
 	/// * [`std::process::Command::stdin()`]
 	/// 
 	pub fn stdin(&mut self, cfg: MockStdio) -> &mut Self {
+		self.called("stdin");
 		self.command.stdin(cfg);
 		self
 	}
@@ -202,6 +369,7 @@ impl FakeCommand {
 	/// * [`std::process::Command::stdout()`]
 	/// 
 	pub fn stdout(&mut self, cfg: MockStdio) -> &mut Self {
+		self.called("stdout");
 		self.command.stdout(cfg);
 		self
 	}
@@ -214,11 +382,26 @@ impl FakeCommand {
 	/// * [`std::process::Command::stderr()`]
 	/// 
 	pub fn stderr(&mut self, cfg: MockStdio) -> &mut Self {
+		self.called("stderr");
 		self.command.stderr(cfg);
 		self
 	}
 }
 
+//󰭅		Drop																	
+impl Drop for FakeCommand {
+	//		drop																
+	fn drop(&mut self) {
+		if self.registered || thread::panicking() {
+			return;
+		}
+		let result = Self::check(&self.program, &self.pending.lock().unwrap_or_else(PoisonError::into_inner));
+		if let Err(message) = result {
+			panic!("{message}");
+		}
+	}
+}
+
 //		MockStdio																
 /// Mockable version of the standard input/output (stdio) configuration.
 /// 
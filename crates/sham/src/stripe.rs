@@ -0,0 +1,559 @@
+//! This module mocks a Stripe-style payment API in order to test payments.
+//! 
+//! This module provides a set of routes for the [`reqwest`](crate::reqwest)
+//! mock that implement the common payment intent flows of the [Stripe API](https://docs.stripe.com/api/payment_intents),
+//! i.e. create, retrieve, confirm, and cancel, along with signed webhook events
+//! and deliberately mis-signed ones. This is important because payment code is
+//! full of failure paths, such as declined cards and invalid webhooks, and
+//! hand-writing realistic fixtures for each of them is tedious and error-prone.
+//! 
+//! The approach taken is that a [`MockStripe`] holds the payment intents that
+//! have been created, and its [`routes()`](MockStripe::routes()) serve requests
+//! against them. The outcome of confirming a payment intent is determined by
+//! its payment method, using the same test payment method IDs as Stripe's own
+//! test mode, so that fixtures carry over:
+//! 
+//!   - `pm_card_authenticationRequired`          - Requires further action.
+//!   - `pm_card_chargeDeclined`                  - Declined, `generic_decline`.
+//!   - `pm_card_chargeDeclinedExpiredCard`       - Declined, `expired_card`.
+//!   - `pm_card_chargeDeclinedInsufficientFunds` - Declined, `insufficient_funds`.
+//!   - Anything else                             - Succeeds.
+//! 
+//! Requests must carry an API key, as a bearer token or as the basic auth
+//! username, otherwise they are rejected in the same way as by Stripe.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use futures_executor::block_on;
+//! use reqwest::header::{AUTHORIZATION, HeaderMap};
+//! use serde_json::Value as Json;
+//! use sham::{clock::MockClock, stripe::{MockStripe, MockWebhookFault}};
+//! 
+//! let stripe   = MockStripe::new(&MockClock::new());
+//! let client   = stripe.client();
+//! let mut auth = HeaderMap::new();
+//! auth.insert(AUTHORIZATION, "Bearer sk_test_123".parse().unwrap());
+//! 
+//! block_on(async {
+//!     let intent: Json = client.post(stripe.payment_intents_url())
+//!         .headers(auth.clone())
+//!         .form(&[("amount", "2000"), ("currency", "gbp"), ("payment_method", "pm_card_chargeDeclined")])
+//!         .send().await.unwrap()
+//!         .json().await.unwrap()
+//!     ;
+//!     let id = intent["id"].as_str().unwrap();
+//! 
+//!     let response = client.post(format!("{}/{id}/confirm", stripe.payment_intents_url()))
+//!         .headers(auth)
+//!         .send().await.unwrap()
+//!     ;
+//!     assert_eq!(response.status(), 402);
+//!     let error: Json = response.json().await.unwrap();
+//!     assert_eq!(error["error"]["decline_code"], "generic_decline");
+//!     assert_eq!(stripe.payment_intent(id).unwrap()["status"], "requires_payment_method");
+//! });
+//! 
+//! let object  = stripe.payment_intent("pi_000000000000000000000001").unwrap();
+//! let webhook = stripe.webhook_with_fault("payment_intent.payment_failed", &object, MockWebhookFault::Missing);
+//! assert!(webhook.payload.contains("payment_intent.payment_failed"));
+//! assert_eq!(webhook.signature, None);
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::{
+	clock::MockClock,
+	reqwest::{
+		MockClient,
+		MockError,
+		MockRequest,
+		MockResponse,
+		MockRoute,
+		create_mock_response,
+		create_routed_mock_client,
+	},
+};
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use core::time::Duration;
+use hmac::{Hmac, Mac};
+use reqwest::{
+	IntoUrl,
+	Method,
+	StatusCode,
+	Url,
+	header::AUTHORIZATION,
+};
+use serde_json::{Map, Value as Json, json};
+use sha2::Sha256;
+use std::{
+	collections::{BTreeMap, HashMap},
+	sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+
+
+//		Enums
+
+//		MockWebhookFault														
+/// A deliberate fault in a webhook signature.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockWebhookFault {
+	/// The signature is valid, but the timestamp is ten minutes old, which is
+	/// outside of Stripe's default five minute tolerance.
+	Expired,
+	
+	/// The signature was made with a different secret.
+	InvalidSignature,
+	
+	/// The signature header cannot be parsed.
+	Malformed,
+	
+	/// There is no signature header.
+	Missing,
+}
+
+
+
+//		Structs
+
+//		StripeState																
+/// The state of a [`MockStripe`] instance.
+#[derive(Debug)]
+struct StripeState {
+	/// The payment intents created so far, by ID.
+	intents:        BTreeMap<String, Json>,
+	
+	/// The number used to make the next ID unique.
+	next_id:        u64,
+	
+	/// The secret used to sign webhook events.
+	webhook_secret: String,
+}
+
+//		MockStripe																
+/// A mocked Stripe-style payment API.
+/// 
+/// Clones share the same state, so a clone can be used to serve routes while
+/// the original is used to make assertions and generate webhook events.
+/// 
+#[derive(Clone, Debug)]
+pub struct MockStripe {
+	//		Private properties													
+	/// The base URL of the API.
+	base:  Url,
+	
+	/// The clock used for creation times and webhook timestamps.
+	clock: MockClock,
+	
+	/// The state of the API, shared between clones.
+	state: Arc<Mutex<StripeState>>,
+}
+
+//󰭅		MockStripe																
+impl MockStripe {
+	//		new																	
+	/// Creates a new API, served at `https://api.stripe.com`, with a webhook
+	/// secret of `whsec_test`.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `clock` - The clock used for creation times and webhook timestamps.
+	/// 
+	#[must_use]
+	pub fn new(clock: &MockClock) -> Self {
+		Self {
+			base:  Url::parse("https://api.stripe.com/").unwrap(),
+			clock: clock.clone(),
+			state: Arc::new(Mutex::new(StripeState {
+				intents:        BTreeMap::new(),
+				next_id:        0,
+				webhook_secret: "whsec_test".to_owned(),
+			})),
+		}
+	}
+	
+	//		with_base_url														
+	/// Sets the base URL that the API is served at.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `url` - The base URL.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the URL is not valid.
+	/// 
+	#[must_use]
+	pub fn with_base_url<U: IntoUrl>(mut self, url: U) -> Self {
+		self.base = url.into_url().unwrap();
+		self
+	}
+	
+	//		with_webhook_secret													
+	/// Sets the secret used to sign webhook events.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `secret` - The webhook signing secret.
+	/// 
+	#[must_use]
+	pub fn with_webhook_secret(self, secret: &str) -> Self {
+		secret.clone_into(&mut self.lock().webhook_secret);
+		self
+	}
+	
+	//		client																
+	/// Creates a mock client that serves the API's routes.
+	#[must_use]
+	pub fn client(&self) -> MockClient {
+		create_routed_mock_client(self.routes())
+	}
+	
+	//		payment_intent														
+	/// Returns the payment intent with the given ID, if it exists.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `id` - The ID of the payment intent.
+	/// 
+	#[must_use]
+	pub fn payment_intent(&self, id: &str) -> Option<Json> {
+		self.lock().intents.get(id).cloned()
+	}
+	
+	//		payment_intents_url													
+	/// Returns the URL of the payment intents resource.
+	#[must_use]
+	pub fn payment_intents_url(&self) -> Url {
+		let mut url = self.base.clone();
+		url.set_path(&format!("{}/v1/payment_intents", self.base.path().trim_end_matches('/')));
+		url
+	}
+	
+	//		routes																
+	/// Returns the routes that serve the API.
+	/// 
+	/// The routes are:
+	/// 
+	///   - `POST v1/payment_intents`              - Create a payment intent.
+	///   - `GET  v1/payment_intents/{id}`         - Retrieve a payment intent.
+	///   - `POST v1/payment_intents/{id}/confirm` - Confirm a payment intent.
+	///   - `POST v1/payment_intents/{id}/cancel`  - Cancel a payment intent.
+	/// 
+	#[must_use]
+	pub fn routes(&self) -> Vec<MockRoute> {
+		let get  = self.clone();
+		let post = self.clone();
+		vec![
+			MockRoute::prefixed(Method::GET,  self.payment_intents_url(), move |request| get.serve(request)),
+			MockRoute::prefixed(Method::POST, self.payment_intents_url(), move |request| post.serve(request)),
+		]
+	}
+	
+	//		webhook																
+	/// Creates a correctly signed webhook event.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `event_type` - The type of the event, such as `payment_intent.succeeded`.
+	/// * `object`     - The object that the event is about.
+	/// 
+	#[must_use]
+	pub fn webhook(&self, event_type: &str, object: &Json) -> MockStripeWebhook {
+		self.create_webhook(event_type, object, None)
+	}
+	
+	//		webhook_with_fault													
+	/// Creates a webhook event with a deliberate fault in its signature.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `event_type` - The type of the event, such as `payment_intent.succeeded`.
+	/// * `object`     - The object that the event is about.
+	/// * `fault`      - The fault to introduce.
+	/// 
+	#[must_use]
+	pub fn webhook_with_fault(&self, event_type: &str, object: &Json, fault: MockWebhookFault) -> MockStripeWebhook {
+		self.create_webhook(event_type, object, Some(fault))
+	}
+	
+	//		cancel																
+	/// Cancels a payment intent.
+	fn cancel(&self, request: &MockRequest, id: &str) -> MockResponse {
+		let mut state = self.lock();
+		let Some(intent) = state.intents.get_mut(id) else {
+			return missing(request, id);
+		};
+		let (status, body) = if intent["status"] == "succeeded" || intent["status"] == "canceled" {
+			let message = format!("You cannot cancel this PaymentIntent because it has a status of {}.", intent["status"]);
+			(StatusCode::BAD_REQUEST, error_body("invalid_request_error", "payment_intent_unexpected_state", &message))
+		} else {
+			intent["status"] = json!("canceled");
+			(StatusCode::OK, intent.clone())
+		};
+		drop(state);
+		json_response(request, status, &body)
+	}
+	
+	//		confirm																
+	/// Confirms a payment intent.
+	fn confirm(&self, request: &MockRequest, id: &str, params: &HashMap<String, String>) -> MockResponse {
+		let mut state = self.lock();
+		let Some(intent) = state.intents.get_mut(id) else {
+			return missing(request, id);
+		};
+		let (status, body) = confirm_intent(intent, params);
+		drop(state);
+		json_response(request, status, &body)
+	}
+	
+	//		create																
+	/// Creates a payment intent.
+	fn create(&self, request: &MockRequest, params: &HashMap<String, String>) -> MockResponse {
+		let Some(amount) = params.get("amount").and_then(|amount| amount.parse::<u64>().ok()) else {
+			return stripe_error(request, StatusCode::BAD_REQUEST, "invalid_request_error", "parameter_missing", "Missing required param: amount.");
+		};
+		let Some(currency) = params.get("currency") else {
+			return stripe_error(request, StatusCode::BAD_REQUEST, "invalid_request_error", "parameter_missing", "Missing required param: currency.");
+		};
+		let mut state  = self.lock();
+		state.next_id += 1;
+		let id         = format!("pi_{:024}", state.next_id);
+		let metadata   = params.iter()
+			.filter_map(|param| {
+				param.0.strip_prefix("metadata[")
+					.and_then(|key| key.strip_suffix(']'))
+					.map(|key| (key.to_owned(), json!(param.1)))
+			})
+			.collect::<Map<_, _>>()
+		;
+		let intent = json!({
+			"id":                 id,
+			"object":             "payment_intent",
+			"amount":             amount,
+			"currency":           currency,
+			"status":             if params.contains_key("payment_method") { "requires_confirmation" } else { "requires_payment_method" },
+			"client_secret":      format!("{id}_secret_{}", state.next_id),
+			"created":            self.clock.unix_timestamp(),
+			"last_payment_error": null,
+			"livemode":           false,
+			"metadata":           metadata,
+			"payment_method":     params.get("payment_method"),
+		});
+		drop(state.intents.insert(id.clone(), intent));
+		drop(state);
+		if params.get("confirm").is_some_and(|confirm| confirm == "true") {
+			return self.confirm(request, &id, params);
+		}
+		json_response(request, StatusCode::OK, &self.payment_intent(&id).unwrap_or_default())
+	}
+	
+	//		create_webhook														
+	/// Creates a webhook event, signed with the given fault, if any.
+	fn create_webhook(&self, event_type: &str, object: &Json, fault: Option<MockWebhookFault>) -> MockStripeWebhook {
+		let mut state  = self.lock();
+		state.next_id += 1;
+		let timestamp  = self.clock.unix_timestamp();
+		let payload    = json!({
+			"id":          format!("evt_{:024}", state.next_id),
+			"object":      "event",
+			"api_version": "2024-06-20",
+			"created":     timestamp,
+			"data":        { "object": object },
+			"livemode":    false,
+			"type":        event_type,
+		}).to_string();
+		let signature  = match fault {
+			None                                     => Some(sign_webhook(&state.webhook_secret, timestamp, &payload)),
+			Some(MockWebhookFault::Expired)          => {
+				let expired = timestamp.saturating_sub(Duration::from_secs(600).as_secs());
+				Some(sign_webhook(&state.webhook_secret, expired, &payload))
+			},
+			Some(MockWebhookFault::InvalidSignature) => Some(sign_webhook("whsec_wrong", timestamp, &payload)),
+			Some(MockWebhookFault::Malformed)        => Some("t=not-a-timestamp,v1=".to_owned()),
+			Some(MockWebhookFault::Missing)          => None,
+		};
+		drop(state);
+		MockStripeWebhook { payload, signature }
+	}
+	
+	//		lock																
+	/// Locks the state of the API.
+	fn lock(&self) -> MutexGuard<'_, StripeState> {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+	
+	//		serve																
+	/// Serves a request to the payment intents resource.
+	#[expect(clippy::unnecessary_wraps, reason = "Needed for the route signature")]
+	fn serve(&self, request: &MockRequest) -> Result<MockResponse, MockError> {
+		if !has_api_key(request) {
+			return Ok(stripe_error(
+				request,
+				StatusCode::UNAUTHORIZED,
+				"invalid_request_error",
+				"api_key_missing",
+				"You did not provide an API key.",
+			));
+		}
+		let base     = self.payment_intents_url();
+		let rest     = request.url.path().strip_prefix(base.path()).unwrap_or_default().trim_matches('/');
+		let segments = rest.split('/').filter(|segment| !segment.is_empty()).collect::<Vec<_>>();
+		let params   = request.body.as_ref()
+			.and_then(|body| serde_urlencoded::from_bytes::<HashMap<String, String>>(body).ok())
+			.unwrap_or_default()
+		;
+		Ok(match (request.method.as_str(), segments.as_slice()) {
+			("GET",  &[id])            => self.payment_intent(id).map_or_else(
+				|| missing(request, id),
+				|intent| json_response(request, StatusCode::OK, &intent),
+			),
+			("POST", &[])              => self.create(request, &params),
+			("POST", &[id, "cancel"])  => self.cancel(request, id),
+			("POST", &[id, "confirm"]) => self.confirm(request, id, &params),
+			_                          => stripe_error(
+				request,
+				StatusCode::NOT_FOUND,
+				"invalid_request_error",
+				"resource_missing",
+				&format!("Unrecognized request URL ({} {}).", request.method, request.url.path()),
+			),
+		})
+	}
+}
+
+//		MockStripeWebhook														
+/// A webhook event, as it would be delivered to a webhook receiver.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockStripeWebhook {
+	//		Public properties													
+	/// The JSON payload of the event, i.e. the request body.
+	pub payload:   String,
+	
+	/// The value of the `Stripe-Signature` header, if there is one.
+	pub signature: Option<String>,
+}
+
+
+
+//		Functions
+
+//		confirm_intent															
+/// Confirms a payment intent, determining the outcome from its payment method,
+/// and returns the status and body of the response.
+fn confirm_intent(intent: &mut Json, params: &HashMap<String, String>) -> (StatusCode, Json) {
+	if intent["status"] != "requires_payment_method" && intent["status"] != "requires_confirmation" {
+		let message = format!("You cannot confirm this PaymentIntent because it has a status of {}.", intent["status"]);
+		return (StatusCode::BAD_REQUEST, error_body("invalid_request_error", "payment_intent_unexpected_state", &message));
+	}
+	if let Some(payment_method) = params.get("payment_method") {
+		intent["payment_method"] = json!(payment_method);
+	}
+	let payment_method = intent["payment_method"].as_str().map(ToOwned::to_owned);
+	let decline        = match payment_method.as_deref() {
+		None                                            => {
+			let message = "You cannot confirm this PaymentIntent because it's missing a payment method.";
+			return (StatusCode::BAD_REQUEST, error_body("invalid_request_error", "payment_intent_unexpected_state", message));
+		},
+		Some("pm_card_authenticationRequired")          => {
+			intent["status"] = json!("requires_action");
+			None
+		},
+		Some("pm_card_chargeDeclined")                  => Some(("card_declined", "generic_decline", "Your card was declined.")),
+		Some("pm_card_chargeDeclinedExpiredCard")       => Some(("expired_card", "expired_card", "Your card has expired.")),
+		Some("pm_card_chargeDeclinedInsufficientFunds") => Some(("card_declined", "insufficient_funds", "Your card has insufficient funds.")),
+		Some(_)                                         => {
+			intent["status"] = json!("succeeded");
+			None
+		},
+	};
+	let Some((code, decline_code, message)) = decline else {
+		intent["last_payment_error"] = Json::Null;
+		return (StatusCode::OK, intent.clone());
+	};
+	let error = json!({
+		"type":         "card_error",
+		"code":         code,
+		"decline_code": decline_code,
+		"message":      message,
+	});
+	intent["status"]             = json!("requires_payment_method");
+	intent["last_payment_error"] = error.clone();
+	let mut body                 = json!({ "error": error });
+	body["error"]["payment_intent"] = intent.clone();
+	(StatusCode::PAYMENT_REQUIRED, body)
+}
+
+//		error_body																
+/// Creates the body of a Stripe error response.
+fn error_body(kind: &str, code: &str, message: &str) -> Json {
+	json!({
+		"error": {
+			"type":    kind,
+			"code":    code,
+			"message": message,
+		},
+	})
+}
+
+//		has_api_key																
+/// Whether the request carries an API key, as a bearer token or as the basic
+/// auth username.
+fn has_api_key(request: &MockRequest) -> bool {
+	let Some(value) = request.headers.get(AUTHORIZATION).and_then(|value| value.to_str().ok()) else {
+		return false;
+	};
+	if let Some(token) = value.strip_prefix("Bearer ") {
+		return !token.is_empty();
+	}
+	value.strip_prefix("Basic ")
+		.and_then(|encoded| BASE64.decode(encoded).ok())
+		.and_then(|decoded| String::from_utf8(decoded).ok())
+		.is_some_and(|decoded| decoded.split(':').next().is_some_and(|user| !user.is_empty()))
+}
+
+//		json_response															
+/// Creates a JSON response to the given request.
+fn json_response(request: &MockRequest, status: StatusCode, body: &Json) -> MockResponse {
+	let bytes = body.to_string().into_bytes();
+	create_mock_response(
+		request.url.clone(),
+		status,
+		Some("application/json"),
+		Some(bytes.len()),
+		HashMap::<String, String>::new(),
+		Ok(&bytes),
+	)
+}
+
+//		missing																	
+/// Creates the error response for a payment intent that does not exist.
+fn missing(request: &MockRequest, id: &str) -> MockResponse {
+	stripe_error(
+		request,
+		StatusCode::NOT_FOUND,
+		"invalid_request_error",
+		"resource_missing",
+		&format!("No such payment_intent: '{id}'"),
+	)
+}
+
+//		sign_webhook															
+/// Creates a `Stripe-Signature` header value for the given payload.
+fn sign_webhook(secret: &str, timestamp: u64, payload: &str) -> String {
+	let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+	mac.update(format!("{timestamp}.{payload}").as_bytes());
+	format!("t={timestamp},v1={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+//		stripe_error															
+/// Creates a Stripe error response to the given request.
+fn stripe_error(request: &MockRequest, status: StatusCode, kind: &str, code: &str, message: &str) -> MockResponse {
+	json_response(request, status, &error_body(kind, code, message))
+}
@@ -0,0 +1,474 @@
+//! This module mocks systemd in order to test service management and readiness.
+//! 
+//! This module provides a [`MockSystemd`], which stands in for both sides of a
+//! daemon's dealings with [systemd](https://systemd.io/): the `systemctl`
+//! commands that start, stop, and query units, and the `sd_notify()` messages
+//! that a service sends to report that it is ready, what its status is, and
+//! that it is still alive for the watchdog. Time is driven by a [`MockClock`].
+//! This is important because these interactions can otherwise only be tested
+//! on a machine that runs systemd, with the privileges needed to manage units,
+//! and because the failures that matter, such as a unit that fails to start or
+//! a watchdog that is not kept alive in time, need to be produced on demand.
+//! 
+//! The approach taken is that the "real" code should manage units and send
+//! notifications through a client that it is given, and the test code then
+//! supplies a [`MockSystemd`]. Clones share the same state, so the test can
+//! keep one in order to check what the code under test did with another. Unit
+//! names without a suffix are given the `.service` suffix, as `systemctl` does.
+//! Notifications use the same `KEY=VALUE` lines as `sd_notify()`, and the
+//! watchdog is considered to have expired if the gap between the start of the
+//! service and the first `WATCHDOG=1`, between any two of them, or between the
+//! last of them and the current time, is longer than the watchdog timeout.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use core::time::Duration;
+//! use sham::{
+//!     clock::MockClock,
+//!     systemd::{MockSystemd, MockSystemdError, MockUnitState},
+//! };
+//! 
+//! let clock   = MockClock::new();
+//! let systemd = MockSystemd::new(&clock)
+//!     .with_unit("nginx", MockUnitState::Inactive)
+//!     .with_failing_unit("broken.service")
+//!     .with_watchdog(Duration::from_secs(10))
+//! ;
+//! 
+//! systemd.start("nginx").unwrap();
+//! assert!(systemd.is_active("nginx.service"));
+//! assert!(matches!(systemd.start("broken"), Err(MockSystemdError::JobFailed(_))));
+//! assert_eq!(systemd.status("broken"), Ok(MockUnitState::Failed));
+//! assert_eq!(systemd.calls(), vec!["start nginx.service", "start broken.service"]);
+//! 
+//! systemd.notify("READY=1\nSTATUS=Serving requests");
+//! assert!(systemd.is_ready());
+//! assert_eq!(systemd.status_text().unwrap(), "Serving requests");
+//! 
+//! clock.advance(Duration::from_secs(8));
+//! systemd.notify("WATCHDOG=1");
+//! clock.advance(Duration::from_secs(8));
+//! assert!(!systemd.watchdog_expired());
+//! clock.advance(Duration::from_secs(3));
+//! assert!(systemd.watchdog_expired());
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::clock::MockClock;
+use core::{
+	error::Error,
+	fmt::{Display, Formatter, self},
+	iter,
+	time::Duration,
+};
+use std::{
+	collections::BTreeMap,
+	sync::{Arc, Mutex, MutexGuard, PoisonError},
+	time::SystemTime,
+};
+
+
+
+//		Enums
+
+//		MockSystemdError														
+/// A mocked systemd error type.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockSystemdError {
+	/// The job to start the unit failed.
+	JobFailed(String),
+	
+	/// The unit does not exist.
+	UnitNotFound(String),
+}
+
+//󰭅		Display																	
+impl Display for MockSystemdError {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match *self {
+			Self::JobFailed(ref unit)    => write!(f, "Job for {unit} failed because the control process exited with error code."),
+			Self::UnitNotFound(ref unit) => write!(f, "Unit {unit} not found."),
+		}
+	}
+}
+
+//󰭅		Error																	
+impl Error for MockSystemdError {}
+
+//		MockUnitState															
+/// The active state of a unit.
+/// 
+/// This is displayed as it is printed by `systemctl is-active`, such as
+/// `active`.
+/// 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockUnitState {
+	/// The unit is running.
+	Active,
+	
+	/// The unit is starting.
+	Activating,
+	
+	/// The unit is stopping.
+	Deactivating,
+	
+	/// The unit failed, such as by its process exiting with an error.
+	Failed,
+	
+	/// The unit is not running.
+	Inactive,
+}
+
+//󰭅		Display																	
+impl Display for MockUnitState {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let state = match *self {
+			Self::Active       => "active",
+			Self::Activating   => "activating",
+			Self::Deactivating => "deactivating",
+			Self::Failed       => "failed",
+			Self::Inactive     => "inactive",
+		};
+		write!(f, "{state}")
+	}
+}
+
+
+
+//		Structs
+
+//		MockNotification														
+/// A notification sent to systemd, as with `sd_notify()`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockNotification {
+	//		Public properties													
+	/// The time at which the notification was sent.
+	pub at:     SystemTime,
+	
+	/// The `KEY=VALUE` assignments of the notification, in order.
+	pub fields: Vec<(String, String)>,
+}
+
+//󰭅		MockNotification														
+impl MockNotification {
+	//		get																	
+	/// Returns the value of the last assignment of the given key, if any.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `key` - The key to look up, such as `STATUS`.
+	/// 
+	#[must_use]
+	pub fn get(&self, key: &str) -> Option<&str> {
+		self.fields.iter().rev().find(|field| field.0 == key).map(|field| field.1.as_str())
+	}
+}
+
+//		MockSystemd																
+/// A mocked systemd, which manages units and receives notifications.
+#[derive(Clone, Debug)]
+pub struct MockSystemd {
+	//		Private properties													
+	/// The clock that timestamps notifications and drives the watchdog.
+	clock:    MockClock,
+	
+	/// The time at which the service started, which the watchdog counts from.
+	started:  SystemTime,
+	
+	/// The state of the manager, shared between clones.
+	state:    Arc<Mutex<SystemdState>>,
+	
+	/// The watchdog timeout, if the watchdog is enabled.
+	watchdog: Option<Duration>,
+}
+
+//󰭅		MockSystemd																
+impl MockSystemd {
+	//		new																	
+	/// Creates a new manager with no units, and the watchdog disabled.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `clock` - The clock that timestamps notifications and drives the
+	///   watchdog.
+	/// 
+	#[must_use]
+	pub fn new(clock: &MockClock) -> Self {
+		Self {
+			clock:    clock.clone(),
+			started:  clock.now(),
+			state:    Arc::new(Mutex::new(SystemdState::default())),
+			watchdog: None,
+		}
+	}
+	
+	//		with_failing_unit													
+	/// Adds a unit that fails whenever it is started, and is initially
+	/// inactive.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the unit.
+	/// 
+	#[must_use]
+	pub fn with_failing_unit(self, name: &str) -> Self {
+		_ = self.lock().units.insert(unit_name(name), Unit { fails: true, state: MockUnitState::Inactive });
+		self
+	}
+	
+	//		with_unit															
+	/// Adds a unit, which can be started successfully.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name`  - The name of the unit.
+	/// * `state` - The initial state of the unit.
+	/// 
+	#[must_use]
+	pub fn with_unit(self, name: &str, state: MockUnitState) -> Self {
+		_ = self.lock().units.insert(unit_name(name), Unit { fails: false, state });
+		self
+	}
+	
+	//		with_watchdog														
+	/// Enables the watchdog, as with `WatchdogSec=` in the unit file.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `timeout` - How long the service may go without sending `WATCHDOG=1`.
+	/// 
+	#[must_use]
+	pub const fn with_watchdog(mut self, timeout: Duration) -> Self {
+		self.watchdog = Some(timeout);
+		self
+	}
+	
+	//		calls																
+	/// Returns the `systemctl` commands run so far, in order, such as
+	/// `start nginx.service`.
+	#[must_use]
+	pub fn calls(&self) -> Vec<String> {
+		self.lock().calls.clone()
+	}
+	
+	//		is_active															
+	/// Whether a unit is active, as with `systemctl is-active`. Units that do
+	/// not exist are not active.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the unit.
+	/// 
+	#[must_use]
+	pub fn is_active(&self, name: &str) -> bool {
+		self.status(name) == Ok(MockUnitState::Active)
+	}
+	
+	//		is_ready															
+	/// Whether the service has sent `READY=1`, and has not since sent
+	/// `STOPPING=1`.
+	#[must_use]
+	pub fn is_ready(&self) -> bool {
+		self.lock().notifications.iter().rev()
+			.flat_map(|notification| notification.fields.iter().rev())
+			.find(|field| field.1 == "1" && (field.0 == "READY" || field.0 == "STOPPING"))
+			.is_some_and(|field| field.0 == "READY")
+	}
+	
+	//		notifications														
+	/// Returns the notifications sent so far, in order.
+	#[must_use]
+	pub fn notifications(&self) -> Vec<MockNotification> {
+		self.lock().notifications.clone()
+	}
+	
+	//		notify																
+	/// Sends a notification, as with `sd_notify()`.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `state` - The `KEY=VALUE` assignments to send, separated by newlines,
+	///   such as `READY=1\nSTATUS=Running`. Lines without an `=` are ignored.
+	/// 
+	pub fn notify(&self, state: &str) {
+		let notification = MockNotification {
+			at:     self.clock.now(),
+			fields: state.lines()
+				.filter_map(|line| line.split_once('='))
+				.map(|(key, value)| (key.to_owned(), value.to_owned()))
+				.collect(),
+		};
+		self.lock().notifications.push(notification);
+	}
+	
+	//		restart																
+	/// Restarts a unit, as with `systemctl restart`.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the unit.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the unit does not exist, or fails to
+	/// start.
+	/// 
+	pub fn restart(&self, name: &str) -> Result<(), MockSystemdError> {
+		self.run("restart", name, |unit| {
+			if unit.fails { MockUnitState::Failed } else { MockUnitState::Active }
+		})
+	}
+	
+	//		start																
+	/// Starts a unit, as with `systemctl start`.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the unit.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the unit does not exist, or fails to
+	/// start.
+	/// 
+	pub fn start(&self, name: &str) -> Result<(), MockSystemdError> {
+		self.run("start", name, |unit| {
+			if unit.fails { MockUnitState::Failed } else { MockUnitState::Active }
+		})
+	}
+	
+	//		status																
+	/// Returns the state of a unit, as with `systemctl is-active`.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the unit.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the unit does not exist.
+	/// 
+	pub fn status(&self, name: &str) -> Result<MockUnitState, MockSystemdError> {
+		let unit = unit_name(name);
+		self.lock().units.get(&unit).map(|entry| entry.state).ok_or(MockSystemdError::UnitNotFound(unit))
+	}
+	
+	//		status_text															
+	/// Returns the status most recently sent with `STATUS=`, if any.
+	#[must_use]
+	pub fn status_text(&self) -> Option<String> {
+		self.lock().notifications.iter().rev().find_map(|notification| notification.get("STATUS").map(ToOwned::to_owned))
+	}
+	
+	//		stop																
+	/// Stops a unit, as with `systemctl stop`.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the unit.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the unit does not exist.
+	/// 
+	pub fn stop(&self, name: &str) -> Result<(), MockSystemdError> {
+		self.run("stop", name, |_| MockUnitState::Inactive)
+	}
+	
+	//		watchdog_expired													
+	/// Whether the watchdog has expired, which is when the service has at any
+	/// point gone for longer than the watchdog timeout without sending
+	/// `WATCHDOG=1`. This is always `false` if the watchdog is disabled.
+	#[must_use]
+	pub fn watchdog_expired(&self) -> bool {
+		let Some(timeout) = self.watchdog else {
+			return false;
+		};
+		let state = self.lock();
+		let pings = state.notifications.iter()
+			.filter(|notification| notification.get("WATCHDOG") == Some("1"))
+			.map(|notification| notification.at)
+			.collect::<Vec<_>>()
+		;
+		drop(state);
+		iter::once(self.started)
+			.chain(pings)
+			.chain(iter::once(self.clock.now()))
+			.collect::<Vec<_>>()
+			.windows(2)
+			.any(|pair| pair[1].duration_since(pair[0]).unwrap_or_default() > timeout)
+	}
+	
+	//		watchdog_timeout													
+	/// Returns the watchdog timeout, if the watchdog is enabled, as the service
+	/// would find it with `sd_watchdog_enabled()`.
+	#[must_use]
+	pub const fn watchdog_timeout(&self) -> Option<Duration> {
+		self.watchdog
+	}
+	
+	//		lock																
+	/// Locks the state of the manager.
+	fn lock(&self) -> MutexGuard<'_, SystemdState> {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+	
+	//		run																	
+	/// Records a `systemctl` command, and applies it to a unit.
+	fn run<F: FnOnce(&Unit) -> MockUnitState>(&self, command: &str, name: &str, apply: F) -> Result<(), MockSystemdError> {
+		let unit      = unit_name(name);
+		let mut state = self.lock();
+		state.calls.push(format!("{command} {unit}"));
+		let entry     = state.units.get_mut(&unit).ok_or_else(|| MockSystemdError::UnitNotFound(unit.clone()))?;
+		entry.state   = apply(entry);
+		let failed    = entry.state == MockUnitState::Failed;
+		drop(state);
+		if failed { Err(MockSystemdError::JobFailed(unit)) } else { Ok(()) }
+	}
+}
+
+//		SystemdState															
+/// The state of a [`MockSystemd`] instance.
+#[derive(Debug, Default)]
+struct SystemdState {
+	/// The `systemctl` commands run so far, in order.
+	calls:         Vec<String>,
+	
+	/// The notifications sent so far, in order.
+	notifications: Vec<MockNotification>,
+	
+	/// The units, by name.
+	units:         BTreeMap<String, Unit>,
+}
+
+//		Unit																	
+/// A unit managed by a [`MockSystemd`].
+#[derive(Clone, Copy, Debug)]
+struct Unit {
+	/// Whether the unit fails whenever it is started.
+	fails: bool,
+	
+	/// The current state of the unit.
+	state: MockUnitState,
+}
+
+
+
+//		Functions
+
+//		unit_name																
+/// Returns the full name of a unit, adding the `.service` suffix if the name
+/// has none, as `systemctl` does.
+fn unit_name(name: &str) -> String {
+	if name.contains('.') { name.to_owned() } else { format!("{name}.service") }
+}
@@ -0,0 +1,896 @@
+//! This module mocks a terminal backend in order to test TUI applications.
+//! 
+//! This module provides a [`MockTerminal`] that stands in for the real terminal
+//! when testing applications built on [`crossterm`] and libraries that use it,
+//! such as [Ratatui](https://crates.io/crates/ratatui). It supplies scripted
+//! key, mouse, and resize events, and captures everything written to it into a
+//! virtual screen buffer, by interpreting the ANSI escape sequences that
+//! [`crossterm`] emits for cursor movement, clearing, and styling. The terminal
+//! size and raw-mode state are also controllable. This is important because
+//! unit tests should not read from or draw to a real terminal.
+//! 
+//! The approach taken is that the "real" code writes its output to a generic
+//! [`Write`] implementation, and obtains events, the terminal size, and the
+//! raw-mode state through a handle, rather than calling the [`crossterm`]
+//! functions directly. The test code then passes in a [`MockTerminal`], scripts
+//! the input events, runs the code, and asserts on the rendered [`MockScreen`].
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use crossterm::{
+//!     cursor::MoveTo,
+//!     event::{Event, KeyCode},
+//!     execute,
+//!     style::{Color, Print, SetForegroundColor},
+//! };
+//! use sham::terminal::MockTerminal;
+//! 
+//! let mut terminal = MockTerminal::new(20, 2);
+//! terminal.push_key(KeyCode::Enter);
+//! execute!(terminal, MoveTo(2, 1), SetForegroundColor(Color::Red), Print("Hello")).unwrap();
+//! 
+//! assert_eq!(terminal.screen().line(1), "  Hello");
+//! assert_eq!(terminal.screen().cell(2, 1).unwrap().style.foreground_color, Some(Color::Red));
+//! assert!(matches!(terminal.read().unwrap(), Event::Key(key) if key.code == KeyCode::Enter));
+//! ```
+//! 
+
+
+
+//		Packages
+
+use core::{
+	str::from_utf8,
+	time::Duration,
+};
+use crossterm::{
+	event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind},
+	style::{Attribute, Color, ContentStyle},
+};
+use std::{
+	collections::VecDeque,
+	io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult, Write},
+};
+
+
+
+//		Enums
+
+//		Token																	
+/// A unit of terminal output decoded from the written bytes.
+#[derive(Debug)]
+enum Token {
+	/// A printable character.
+	Char(char),
+	
+	/// A single-byte control character.
+	Control(u8),
+	
+	/// A control sequence introducer (CSI) sequence, i.e. `ESC [`, along with
+	/// its parameter string and final command byte.
+	Csi(String, u8),
+	
+	/// An escape sequence consisting of a single command byte.
+	Escape(u8),
+	
+	/// An operating system command (OSC) sequence, i.e. `ESC ]`, along with its
+	/// content.
+	Osc(String),
+}
+
+
+
+//		Structs
+
+//		MockCell																
+/// A single cell in the virtual screen buffer.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockCell {
+	//		Public properties													
+	/// The character displayed in the cell.
+	pub symbol: char,
+	
+	/// The style applied to the cell.
+	pub style:  ContentStyle,
+}
+
+//󰭅		Default																	
+impl Default for MockCell {
+	//		default																
+	fn default() -> Self {
+		Self {
+			symbol: ' ',
+			style:  ContentStyle::default(),
+		}
+	}
+}
+
+//		MockScreen																
+/// A virtual screen buffer capturing the output written to a [`MockTerminal`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MockScreen {
+	//		Private properties													
+	/// The cells of the screen, stored row by row.
+	cells:  Vec<MockCell>,
+	
+	/// The width of the screen, in columns.
+	width:  u16,
+	
+	/// The height of the screen, in rows.
+	height: u16,
+}
+
+//󰭅		MockScreen																
+impl MockScreen {
+	//		new																	
+	/// Creates a new, blank screen of the given size.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `width`  - The width of the screen, in columns.
+	/// * `height` - The height of the screen, in rows.
+	/// 
+	#[must_use]
+	pub fn new(width: u16, height: u16) -> Self {
+		Self {
+			cells: vec![MockCell::default(); usize::from(width) * usize::from(height)],
+			width,
+			height,
+		}
+	}
+	
+	//		cell																
+	/// Returns the cell at the given position, if it is within the screen.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `column` - The column of the cell, starting from zero.
+	/// * `row`    - The row of the cell, starting from zero.
+	/// 
+	#[must_use]
+	pub fn cell(&self, column: u16, row: u16) -> Option<&MockCell> {
+		self.index(column, row).map(|index| &self.cells[index])
+	}
+	
+	//		contents															
+	/// Returns the text of the whole screen, with rows separated by newlines.
+	/// 
+	/// Trailing whitespace is removed from each row, but empty rows are kept,
+	/// so that the result always contains one line per row.
+	/// 
+	#[must_use]
+	pub fn contents(&self) -> String {
+		self.lines().join("\n")
+	}
+	
+	//		line																
+	/// Returns the text of the given row, with trailing whitespace removed.
+	/// 
+	/// If the row is outside the screen, an empty string is returned.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `row` - The row to return, starting from zero.
+	/// 
+	#[must_use]
+	pub fn line(&self, row: u16) -> String {
+		if row >= self.height {
+			return String::new();
+		}
+		let start = usize::from(row) * usize::from(self.width);
+		let end   = start + usize::from(self.width);
+		self.cells[start..end].iter().map(|cell| cell.symbol).collect::<String>().trim_end().to_owned()
+	}
+	
+	//		lines																
+	/// Returns the text of each row, with trailing whitespace removed.
+	#[must_use]
+	pub fn lines(&self) -> Vec<String> {
+		(0..self.height).map(|row| self.line(row)).collect()
+	}
+	
+	//		size																
+	/// Returns the size of the screen, as `(columns, rows)`.
+	#[must_use]
+	pub const fn size(&self) -> (u16, u16) {
+		(self.width, self.height)
+	}
+	
+	//		clear_range															
+	/// Resets the cells within the given range of indexes to blank cells.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `start` - The index of the first cell to clear.
+	/// * `end`   - The index after the last cell to clear.
+	/// 
+	fn clear_range(&mut self, start: usize, end: usize) {
+		let limit = end.min(self.cells.len());
+		if start < limit {
+			self.cells[start..limit].fill(MockCell::default());
+		}
+	}
+	
+	//		index																
+	/// Returns the index of the cell at the given position, if it is within
+	/// the screen.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `column` - The column of the cell, starting from zero.
+	/// * `row`    - The row of the cell, starting from zero.
+	/// 
+	fn index(&self, column: u16, row: u16) -> Option<usize> {
+		(column < self.width && row < self.height)
+			.then(|| usize::from(row) * usize::from(self.width) + usize::from(column))
+	}
+	
+	//		resize																
+	/// Changes the size of the screen, keeping the content of the cells that
+	/// remain within it.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `width`  - The new width of the screen, in columns.
+	/// * `height` - The new height of the screen, in rows.
+	/// 
+	fn resize(&mut self, width: u16, height: u16) {
+		let mut resized = Self::new(width, height);
+		for row in 0..height.min(self.height) {
+			for column in 0..width.min(self.width) {
+				if let (Some(from), Some(to)) = (self.index(column, row), resized.index(column, row)) {
+					resized.cells[to] = self.cells[from];
+				}
+			}
+		}
+		*self = resized;
+	}
+	
+	//		scroll_down															
+	/// Scrolls the content of the screen down, inserting blank rows at the top.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `rows` - The number of rows to scroll by.
+	/// 
+	fn scroll_down(&mut self, rows: u16) {
+		let offset = usize::from(rows.min(self.height)) * usize::from(self.width);
+		self.cells.rotate_right(offset);
+		self.clear_range(0, offset);
+	}
+	
+	//		scroll_up															
+	/// Scrolls the content of the screen up, inserting blank rows at the
+	/// bottom.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `rows` - The number of rows to scroll by.
+	/// 
+	fn scroll_up(&mut self, rows: u16) {
+		let offset = usize::from(rows.min(self.height)) * usize::from(self.width);
+		self.cells.rotate_left(offset);
+		self.clear_range(self.cells.len() - offset, self.cells.len());
+	}
+}
+
+//		MockTerminal															
+/// A mocked terminal, compatible with the output of [`crossterm`].
+/// 
+/// This provides scripted input events, a controllable size and raw-mode
+/// state, and captures everything written to it into a [`MockScreen`]. Output
+/// is written using the [`Write`] implementation, which means that the
+/// [`crossterm::execute!()`] and [`crossterm::queue!()`] macros can be used
+/// with it directly.
+/// 
+/// The supported escape sequences cover cursor movement, cursor visibility,
+/// saving and restoring the cursor position, clearing, scrolling, the
+/// alternate screen, the window title, and all of the colours and attributes
+/// that [`crossterm`] can set. Any other escape sequences are ignored.
+/// 
+#[derive(Clone, Debug)]
+pub struct MockTerminal {
+	//		Private properties													
+	/// Whether the alternate screen is active.
+	alternate_screen: bool,
+	
+	/// The position of the cursor, as `(column, row)`. The column may be equal
+	/// to the width of the screen, in which case the next character printed
+	/// will wrap onto the next row.
+	cursor:           (u16, u16),
+	
+	/// Whether the cursor is visible.
+	cursor_visible:   bool,
+	
+	/// The scripted events that have not yet been read.
+	events:           VecDeque<Event>,
+	
+	/// Bytes that have been written but not yet processed, such as an
+	/// incomplete escape sequence.
+	pending:          Vec<u8>,
+	
+	/// Whether raw mode is enabled.
+	raw_mode:         bool,
+	
+	/// The saved position of the cursor.
+	saved_cursor:     (u16, u16),
+	
+	/// The virtual screen buffer.
+	screen:           MockScreen,
+	
+	/// The style applied to newly-printed characters.
+	style:            ContentStyle,
+	
+	/// The title of the terminal window, if one has been set.
+	title:            Option<String>,
+}
+
+//󰭅		MockTerminal															
+impl MockTerminal {
+	//		new																	
+	/// Creates a new terminal of the given size, with a blank screen and no
+	/// scripted events.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `columns` - The width of the terminal, in columns.
+	/// * `rows`    - The height of the terminal, in rows.
+	/// 
+	#[must_use]
+	pub fn new(columns: u16, rows: u16) -> Self {
+		Self {
+			alternate_screen: false,
+			cursor:           (0, 0),
+			cursor_visible:   true,
+			events:           VecDeque::new(),
+			pending:          Vec::new(),
+			raw_mode:         false,
+			saved_cursor:     (0, 0),
+			screen:           MockScreen::new(columns, rows),
+			style:            ContentStyle::default(),
+			title:            None,
+		}
+	}
+	
+	//		cursor_position														
+	/// Returns the position of the cursor, as `(column, row)`.
+	/// 
+	/// # Errors
+	/// 
+	/// This function does not return an error, but returns a [`Result`] for
+	/// compatibility with the real function.
+	/// 
+	/// # See also
+	/// 
+	/// * [`crossterm::cursor::position()`]
+	/// 
+	pub fn cursor_position(&self) -> IoResult<(u16, u16)> {
+		Ok((self.cursor.0.min(self.screen.width.saturating_sub(1)), self.cursor.1))
+	}
+	
+	//		disable_raw_mode													
+	/// Disables raw mode.
+	/// 
+	/// # Errors
+	/// 
+	/// This function does not return an error, but returns a [`Result`] for
+	/// compatibility with the real function.
+	/// 
+	/// # See also
+	/// 
+	/// * [`crossterm::terminal::disable_raw_mode()`]
+	/// 
+	pub fn disable_raw_mode(&mut self) -> IoResult<()> {
+		self.raw_mode = false;
+		Ok(())
+	}
+	
+	//		enable_raw_mode														
+	/// Enables raw mode.
+	/// 
+	/// # Errors
+	/// 
+	/// This function does not return an error, but returns a [`Result`] for
+	/// compatibility with the real function.
+	/// 
+	/// # See also
+	/// 
+	/// * [`crossterm::terminal::enable_raw_mode()`]
+	/// 
+	pub fn enable_raw_mode(&mut self) -> IoResult<()> {
+		self.raw_mode = true;
+		Ok(())
+	}
+	
+	//		is_alternate_screen													
+	/// Whether the alternate screen is active.
+	#[must_use]
+	pub const fn is_alternate_screen(&self) -> bool {
+		self.alternate_screen
+	}
+	
+	//		is_cursor_visible													
+	/// Whether the cursor is visible.
+	#[must_use]
+	pub const fn is_cursor_visible(&self) -> bool {
+		self.cursor_visible
+	}
+	
+	//		is_raw_mode_enabled													
+	/// Whether raw mode is enabled.
+	/// 
+	/// # Errors
+	/// 
+	/// This function does not return an error, but returns a [`Result`] for
+	/// compatibility with the real function.
+	/// 
+	/// # See also
+	/// 
+	/// * [`crossterm::terminal::is_raw_mode_enabled()`]
+	/// 
+	pub const fn is_raw_mode_enabled(&self) -> IoResult<bool> {
+		Ok(self.raw_mode)
+	}
+	
+	//		poll																
+	/// Checks whether there is an event available to read.
+	/// 
+	/// As the events are scripted, this returns immediately, without waiting
+	/// for the timeout.
+	/// 
+	/// # Errors
+	/// 
+	/// This function does not return an error, but returns a [`Result`] for
+	/// compatibility with the real function.
+	/// 
+	/// # See also
+	/// 
+	/// * [`crossterm::event::poll()`]
+	/// 
+	pub fn poll(&self, _timeout: Duration) -> IoResult<bool> {
+		Ok(!self.events.is_empty())
+	}
+	
+	//		push_event															
+	/// Adds an event to the end of the scripted event queue.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `event` - The event to add.
+	/// 
+	pub fn push_event(&mut self, event: Event) {
+		self.events.push_back(event);
+	}
+	
+	//		push_key															
+	/// Adds a key press without modifiers to the end of the scripted event
+	/// queue.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `code` - The key that was pressed.
+	/// 
+	pub fn push_key(&mut self, code: KeyCode) {
+		self.push_key_with_modifiers(code, KeyModifiers::NONE);
+	}
+	
+	//		push_key_with_modifiers												
+	/// Adds a key press with modifiers to the end of the scripted event queue.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `code`      - The key that was pressed.
+	/// * `modifiers` - The modifier keys that were held down.
+	/// 
+	pub fn push_key_with_modifiers(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+		self.push_event(Event::Key(KeyEvent::new(code, modifiers)));
+	}
+	
+	//		push_mouse															
+	/// Adds a mouse event without modifiers to the end of the scripted event
+	/// queue.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `kind`   - The kind of mouse event.
+	/// * `column` - The column that the event occurred at.
+	/// * `row`    - The row that the event occurred at.
+	/// 
+	pub fn push_mouse(&mut self, kind: MouseEventKind, column: u16, row: u16) {
+		self.push_event(Event::Mouse(MouseEvent { kind, column, row, modifiers: KeyModifiers::NONE }));
+	}
+	
+	//		push_text															
+	/// Adds a key press to the end of the scripted event queue for each
+	/// character of the given text.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `text` - The text that was typed.
+	/// 
+	pub fn push_text(&mut self, text: &str) {
+		for ch in text.chars() {
+			self.push_key(KeyCode::Char(ch));
+		}
+	}
+	
+	//		read																
+	/// Reads the next scripted event.
+	/// 
+	/// # Errors
+	/// 
+	/// Unlike the real function, which blocks until an event is available, an
+	/// error of kind [`UnexpectedEof`](IoErrorKind::UnexpectedEof) will be
+	/// returned if there are no more scripted events.
+	/// 
+	/// # See also
+	/// 
+	/// * [`crossterm::event::read()`]
+	/// 
+	pub fn read(&mut self) -> IoResult<Event> {
+		self.events.pop_front().ok_or_else(|| IoError::new(IoErrorKind::UnexpectedEof, "No more scripted terminal events"))
+	}
+	
+	//		remaining_events													
+	/// Returns the number of scripted events that have not yet been read.
+	#[must_use]
+	pub fn remaining_events(&self) -> usize {
+		self.events.len()
+	}
+	
+	//		resize																
+	/// Changes the size of the terminal, and adds the corresponding resize
+	/// event to the end of the scripted event queue.
+	/// 
+	/// The content of the screen is kept where it remains within the new size,
+	/// and the cursor is moved inside the new bounds if necessary.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `columns` - The new width of the terminal, in columns.
+	/// * `rows`    - The new height of the terminal, in rows.
+	/// 
+	pub fn resize(&mut self, columns: u16, rows: u16) {
+		self.screen.resize(columns, rows);
+		self.cursor = (self.cursor.0.min(columns), self.cursor.1.min(rows.saturating_sub(1)));
+		self.push_event(Event::Resize(columns, rows));
+	}
+	
+	//		screen																
+	/// Returns the virtual screen buffer.
+	#[must_use]
+	pub const fn screen(&self) -> &MockScreen {
+		&self.screen
+	}
+	
+	//		size																
+	/// Returns the size of the terminal, as `(columns, rows)`.
+	/// 
+	/// # Errors
+	/// 
+	/// This function does not return an error, but returns a [`Result`] for
+	/// compatibility with the real function.
+	/// 
+	/// # See also
+	/// 
+	/// * [`crossterm::terminal::size()`]
+	/// 
+	pub const fn size(&self) -> IoResult<(u16, u16)> {
+		Ok(self.screen.size())
+	}
+	
+	//		title																
+	/// Returns the title of the terminal window, if one has been set.
+	#[must_use]
+	pub fn title(&self) -> Option<&str> {
+		self.title.as_deref()
+	}
+	
+	//		apply_csi															
+	/// Applies a control sequence introducer (CSI) sequence.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `params`  - The parameter string of the sequence.
+	/// * `command` - The final command byte of the sequence.
+	/// 
+	fn apply_csi(&mut self, params: &str, command: u8) {
+		if let Some(private) = params.strip_prefix('?') {
+			let enabled = command == b'h';
+			if command == b'h' || command == b'l' {
+				for mode in private.split(';') {
+					match mode {
+						"25"   => self.cursor_visible   = enabled,
+						"1049" => self.alternate_screen = enabled,
+						_      => {},
+					}
+				}
+			}
+			return;
+		}
+		let numbers: Vec<u16> = params.split(';').map(|param| param.parse().unwrap_or(0)).collect();
+		let first             = numbers.first().copied().unwrap_or(0);
+		let count             = first.max(1);
+		let (width, height)   = self.screen.size();
+		let (column, row)     = self.cursor;
+		match command {
+			b'A' => self.cursor.1 = row.saturating_sub(count),
+			b'B' => self.cursor.1 = row.saturating_add(count).min(height.saturating_sub(1)),
+			b'C' => self.cursor.0 = column.saturating_add(count).min(width.saturating_sub(1)),
+			b'D' => self.cursor.0 = column.min(width.saturating_sub(1)).saturating_sub(count),
+			b'E' => self.cursor   = (0, row.saturating_add(count).min(height.saturating_sub(1))),
+			b'F' => self.cursor   = (0, row.saturating_sub(count)),
+			b'G' => self.cursor.0 = count.min(width).saturating_sub(1),
+			b'd' => self.cursor.1 = count.min(height).saturating_sub(1),
+			b'H' | b'f' => {
+				let target_column = numbers.get(1).copied().unwrap_or(0).max(1);
+				self.cursor       = (target_column.min(width).saturating_sub(1), count.min(height).saturating_sub(1));
+			},
+			b'J' => {
+				let cursor_index = self.cursor_index();
+				match first {
+					0 => self.screen.clear_range(cursor_index, usize::MAX),
+					1 => self.screen.clear_range(0, cursor_index.saturating_add(1)),
+					_ => self.screen.clear_range(0, usize::MAX),
+				}
+			},
+			b'K' => {
+				let row_start    = usize::from(row) * usize::from(width);
+				let row_end      = row_start + usize::from(width);
+				let cursor_index = self.cursor_index();
+				match first {
+					0 => self.screen.clear_range(cursor_index, row_end),
+					1 => self.screen.clear_range(row_start, cursor_index.saturating_add(1)),
+					_ => self.screen.clear_range(row_start, row_end),
+				}
+			},
+			b'S' => self.screen.scroll_up(count),
+			b'T' => self.screen.scroll_down(count),
+			b's' => self.saved_cursor = self.cursor,
+			b'u' => self.cursor       = self.saved_cursor,
+			b'm' => self.apply_sgr(params),
+			_    => {},
+		}
+	}
+	
+	//		apply_sgr															
+	/// Applies a select graphic rendition (SGR) sequence, which sets colours
+	/// and attributes.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `params` - The parameter string of the sequence.
+	/// 
+	fn apply_sgr(&mut self, params: &str) {
+		let mut values = params.split(';');
+		while let Some(value) = values.next() {
+			let attributes = &mut self.style.attributes;
+			match value {
+				"" | "0"        => self.style = ContentStyle::default(),
+				"1"             => attributes.set(Attribute::Bold),
+				"2"             => attributes.set(Attribute::Dim),
+				"3"             => attributes.set(Attribute::Italic),
+				"4" | "4:1"     => attributes.set(Attribute::Underlined),
+				"4:2"           => attributes.set(Attribute::DoubleUnderlined),
+				"4:3"           => attributes.set(Attribute::Undercurled),
+				"4:4"           => attributes.set(Attribute::Underdotted),
+				"4:5"           => attributes.set(Attribute::Underdashed),
+				"5"             => attributes.set(Attribute::SlowBlink),
+				"6"             => attributes.set(Attribute::RapidBlink),
+				"7"             => attributes.set(Attribute::Reverse),
+				"8"             => attributes.set(Attribute::Hidden),
+				"9"             => attributes.set(Attribute::CrossedOut),
+				"20"            => attributes.set(Attribute::Fraktur),
+				"21"            => attributes.unset(Attribute::Bold),
+				"22"            => {
+					attributes.unset(Attribute::Bold);
+					attributes.unset(Attribute::Dim);
+				},
+				"23"            => {
+					attributes.unset(Attribute::Italic);
+					attributes.unset(Attribute::Fraktur);
+				},
+				"24" | "4:0"    => {
+					for attribute in [
+						Attribute::Underlined,
+						Attribute::DoubleUnderlined,
+						Attribute::Undercurled,
+						Attribute::Underdotted,
+						Attribute::Underdashed,
+					] {
+						attributes.unset(attribute);
+					}
+				},
+				"25"            => {
+					attributes.unset(Attribute::SlowBlink);
+					attributes.unset(Attribute::RapidBlink);
+				},
+				"27"            => attributes.unset(Attribute::Reverse),
+				"28"            => attributes.unset(Attribute::Hidden),
+				"29"            => attributes.unset(Attribute::CrossedOut),
+				"51"            => attributes.set(Attribute::Framed),
+				"52"            => attributes.set(Attribute::Encircled),
+				"53"            => attributes.set(Attribute::OverLined),
+				"54"            => {
+					attributes.unset(Attribute::Framed);
+					attributes.unset(Attribute::Encircled);
+				},
+				"55"            => attributes.unset(Attribute::OverLined),
+				"39"            => self.style.foreground_color = None,
+				"49"            => self.style.background_color = None,
+				"59"            => self.style.underline_color  = None,
+				"38" | "48" | "58" => {
+					let color = match values.next() {
+						Some("5") => values.next().and_then(|n| Color::parse_ansi(&format!("5;{n}"))),
+						Some("2") => {
+							let rgb: Vec<&str> = values.by_ref().take(3).collect();
+							Color::parse_ansi(&format!("2;{}", rgb.join(";")))
+						},
+						_         => None,
+					};
+					match value {
+						"38" => self.style.foreground_color = color,
+						"48" => self.style.background_color = color,
+						_    => self.style.underline_color  = color,
+					}
+				},
+				_               => {
+					if let Ok(code) = value.parse::<u8>() {
+						let color = match code {
+							30..=37   => Color::parse_ansi(&format!("5;{}", code - 30)),
+							40..=47   => Color::parse_ansi(&format!("5;{}", code - 40)),
+							90..=97   => Color::parse_ansi(&format!("5;{}", code - 90 + 8)),
+							100..=107 => Color::parse_ansi(&format!("5;{}", code - 100 + 8)),
+							_         => continue,
+						};
+						if (30..=37).contains(&code) || (90..=97).contains(&code) {
+							self.style.foreground_color = color;
+						} else {
+							self.style.background_color = color;
+						}
+					}
+				},
+			}
+		}
+	}
+	
+	//		apply_token															
+	/// Applies a decoded unit of output to the terminal state.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `token` - The token to apply.
+	/// 
+	fn apply_token(&mut self, token: Token) {
+		match token {
+			Token::Char(ch)               => self.print(ch),
+			Token::Control(b'\n')         => self.line_feed(),
+			Token::Control(b'\r')         => self.cursor.0 = 0,
+			Token::Control(0x08)          => self.cursor.0 = self.cursor.0.saturating_sub(1),
+			Token::Control(b'\t')         => {
+				let width     = self.screen.width;
+				self.cursor.0 = (self.cursor.0 | 7).saturating_add(1).min(width.saturating_sub(1));
+			},
+			Token::Csi(params, command)   => self.apply_csi(&params, command),
+			Token::Escape(b'7')           => self.saved_cursor = self.cursor,
+			Token::Escape(b'8')           => self.cursor       = self.saved_cursor,
+			Token::Control(_) |
+			Token::Escape(_)              => {},
+			Token::Osc(content)           => {
+				if let Some((kind, title)) = content.split_once(';') {
+					if kind == "0" || kind == "2" {
+						self.title = Some(title.to_owned());
+					}
+				}
+			},
+		}
+	}
+	
+	//		cursor_index														
+	/// Returns the index of the screen cell under the cursor.
+	fn cursor_index(&self) -> usize {
+		let (column, row) = self.cursor;
+		usize::from(row) * usize::from(self.screen.width) + usize::from(column)
+	}
+	
+	//		line_feed															
+	/// Moves the cursor down one row, scrolling the screen if the cursor is on
+	/// the last row.
+	fn line_feed(&mut self) {
+		if self.cursor.1.saturating_add(1) >= self.screen.height {
+			self.screen.scroll_up(1);
+		} else {
+			self.cursor.1 += 1;
+		}
+	}
+	
+	//		print																
+	/// Prints a character at the cursor position using the current style, and
+	/// advances the cursor, wrapping onto the next row if necessary.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `ch` - The character to print.
+	/// 
+	fn print(&mut self, ch: char) {
+		if self.cursor.0 >= self.screen.width {
+			self.cursor.0 = 0;
+			self.line_feed();
+		}
+		if let Some(index) = self.screen.index(self.cursor.0, self.cursor.1) {
+			self.screen.cells[index] = MockCell { symbol: ch, style: self.style };
+			self.cursor.0 += 1;
+		}
+	}
+	
+	//		next_token															
+	/// Decodes the next unit of output from the given bytes.
+	/// 
+	/// Returns the token along with the number of bytes it occupies, or [`None`]
+	/// if the bytes end part-way through a character or escape sequence.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `bytes` - The bytes to decode.
+	/// 
+	fn next_token(bytes: &[u8]) -> Option<(Token, usize)> {
+		let first = *bytes.first()?;
+		match first {
+			0x1B => match *bytes.get(1)? {
+				b'[' => {
+					let end     = bytes.iter().skip(2).position(|byte| (0x40..=0x7E).contains(byte))? + 2;
+					let params  = String::from_utf8_lossy(&bytes[2..end]).into_owned();
+					Some((Token::Csi(params, bytes[end]), end + 1))
+				},
+				b']' => {
+					let content = &bytes[2..];
+					let (end, terminator) = content.iter().enumerate().find_map(|(index, byte)| match *byte {
+						0x07                                         => Some((index, 1)),
+						0x1B if content.get(index + 1) == Some(&b'\\') => Some((index, 2)),
+						_                                            => None,
+					})?;
+					Some((Token::Osc(String::from_utf8_lossy(&content[..end]).into_owned()), end + terminator + 2))
+				},
+				command => Some((Token::Escape(command), 2)),
+			},
+			0x00..=0x1F | 0x7F => Some((Token::Control(first), 1)),
+			_ => {
+				let length = match first {
+					0xC0..=0xDF => 2,
+					0xE0..=0xEF => 3,
+					0xF0..=0xF7 => 4,
+					_           => 1,
+				};
+				if bytes.len() < length {
+					return None;
+				}
+				Some(from_utf8(&bytes[..length]).ok().and_then(|s| s.chars().next()).map_or(
+					(Token::Char(char::REPLACEMENT_CHARACTER), 1),
+					|ch| (Token::Char(ch), length),
+				))
+			},
+		}
+	}
+}
+
+//󰭅		Write																	
+impl Write for MockTerminal {
+	//		write																
+	fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+		self.pending.extend_from_slice(buf);
+		let mut position = 0;
+		while let Some((token, length)) = Self::next_token(&self.pending[position..]) {
+			self.apply_token(token);
+			position += length;
+		}
+		drop(self.pending.drain(..position));
+		Ok(buf.len())
+	}
+	
+	//		flush																
+	fn flush(&mut self) -> IoResult<()> {
+		Ok(())
+	}
+}
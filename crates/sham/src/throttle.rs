@@ -0,0 +1,304 @@
+//! This module observes the rate of outgoing requests in order to test
+//! client-side rate limiting.
+//! 
+//! This module provides [`MockThrottleObserver`], which records when each
+//! request was sent to each host, and checks that no more than a given
+//! [`MockRate`] of requests was ever sent to a host. This is important because
+//! a client-side rate limiter that lets through bursts, or that counts its
+//! window from the wrong point, only shows up as errors from the upstream API
+//! in production, and otherwise tends to be checked by eye from logs.
+//! 
+//! The approach taken is that each [`Context`](crate::Context) has an observer,
+//! available from [`Context::throttle()`](crate::Context::throttle()), to which
+//! the mocked Reqwest clients report each request as it is sent while the
+//! context is entered. Times come from the virtual clock of the context when
+//! the `clock` feature is enabled, so waits made by a rate limiter through
+//! [`sleep()`](crate::reqwest::sleep()) take no real time, and otherwise from
+//! the system clock. A rate is checked over every window of its length that
+//! starts at a request, rather than over fixed windows, so that a burst that
+//! straddles the boundary between two fixed windows is still caught.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use core::time::Duration;
+//! use sham::throttle::{MockRateExt, MockThrottleObserver};
+//! use std::time::{SystemTime, UNIX_EPOCH};
+//! 
+//! let observer = MockThrottleObserver::new();
+//! for offset in [0, 200, 400, 600, 800, 1_000, 1_200] {
+//!     observer.observe("api.example.com", UNIX_EPOCH + Duration::from_millis(offset));
+//! }
+//! 
+//! observer.assert_max_rate("api.example.com", 5.per_second());
+//! assert_eq!(observer.peak("api.example.com", Duration::from_secs(1)), 5);
+//! assert_eq!(observer.intervals("api.example.com")[0], Duration::from_millis(200));
+//! ```
+//! 
+
+
+
+//		Packages
+
+use core::{
+	fmt::{Display, Formatter, Write as _, self},
+	time::Duration,
+};
+use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex, MutexGuard, PoisonError},
+	time::SystemTime,
+};
+
+
+
+//		Traits
+
+//§		MockRateExt																
+/// Creates a [`MockRate`] from a number of requests, such as with
+/// `5.per_second()`.
+pub trait MockRateExt {
+	//		per																	
+	/// The number of requests allowed in the given time.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `period` - The time in which the requests are allowed.
+	/// 
+	fn per(self, period: Duration) -> MockRate;
+	
+	//		per_minute															
+	/// The number of requests allowed in a minute.
+	fn per_minute(self) -> MockRate;
+	
+	//		per_second															
+	/// The number of requests allowed in a second.
+	fn per_second(self) -> MockRate;
+}
+
+//󰭅		MockRateExt																
+impl MockRateExt for u32 {
+	//		per																	
+	fn per(self, period: Duration) -> MockRate {
+		MockRate::new(self, period)
+	}
+	
+	//		per_minute															
+	fn per_minute(self) -> MockRate {
+		MockRate::new(self, Duration::from_secs(60))
+	}
+	
+	//		per_second															
+	fn per_second(self) -> MockRate {
+		MockRate::new(self, Duration::from_secs(1))
+	}
+}
+
+
+
+//		Structs
+
+//		MockRate																
+/// A number of requests allowed in a period of time.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockRate {
+	//		Public properties													
+	/// The time in which the requests are allowed.
+	pub period:   Duration,
+	
+	/// The number of requests allowed.
+	pub requests: u32,
+}
+
+//󰭅		MockRate																
+impl MockRate {
+	//		new																	
+	/// Creates a new rate.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `requests` - The number of requests allowed.
+	/// * `period`   - The time in which the requests are allowed.
+	/// 
+	#[must_use]
+	pub const fn new(requests: u32, period: Duration) -> Self {
+		Self { period, requests }
+	}
+}
+
+//󰭅		Display																	
+impl Display for MockRate {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		write!(f, "{} request(s) per {}ms", self.requests, self.period.as_millis())
+	}
+}
+
+//		MockThrottleObserver													
+/// An observer of the times at which requests were sent to each host.
+/// 
+/// Clones share the same state, so a clone can be given to the code that
+/// reports the requests while the original is used to make assertions.
+/// 
+#[derive(Clone, Debug, Default)]
+pub struct MockThrottleObserver {
+	//		Private properties													
+	/// The times at which requests were sent, by host.
+	state: Arc<Mutex<HashMap<String, Vec<SystemTime>>>>,
+}
+
+//󰭅		MockThrottleObserver													
+impl MockThrottleObserver {
+	//		new																	
+	/// Creates a new observer, which has seen no requests.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+	
+	//		assert_max_rate														
+	/// Checks that the requests sent to the given host never exceeded the
+	/// given rate, over any window of its period.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `host` - The host to check, such as `api.example.com`.
+	/// * `rate` - The highest rate allowed.
+	/// 
+	/// # Panics
+	/// 
+	/// Panics with the busiest window if more requests than allowed were sent
+	/// within the period of the rate.
+	/// 
+	pub fn assert_max_rate(&self, host: &str, rate: MockRate) {
+		let times          = self.times(host);
+		let (start, count) = busiest(&times, rate.period);
+		if count <= rate.requests as usize {
+			return;
+		}
+		let first   = times[0];
+		let mut msg = format!("Expected at most {rate} to {host}, but {count} were sent within {}ms:", rate.period.as_millis());
+		for time in times.iter().skip(start).take(count) {
+			_ = write!(msg, "\n  +{}ms", time.duration_since(first).unwrap_or_default().as_millis());
+		}
+		panic!("{msg}");
+	}
+	
+	//		assert_min_interval													
+	/// Checks that consecutive requests to the given host were always at least
+	/// the given time apart.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `host`     - The host to check, such as `api.example.com`.
+	/// * `interval` - The shortest time allowed between requests.
+	/// 
+	/// # Panics
+	/// 
+	/// Panics with the shortest interval if any two consecutive requests were
+	/// closer together than allowed.
+	/// 
+	pub fn assert_min_interval(&self, host: &str, interval: Duration) {
+		let intervals = self.intervals(host);
+		if let Some((index, shortest)) = intervals.iter().enumerate().min_by_key(|entry| *entry.1) {
+			assert!(
+				*shortest >= interval,
+				"Expected requests to {host} to be at least {}ms apart, but requests {} and {} were {}ms apart",
+				interval.as_millis(),
+				index + 1,
+				index + 2,
+				shortest.as_millis(),
+			);
+		}
+	}
+	
+	//		hosts																
+	/// The hosts to which requests have been sent, in alphabetical order.
+	#[must_use]
+	pub fn hosts(&self) -> Vec<String> {
+		let mut hosts: Vec<String> = self.lock().keys().cloned().collect();
+		hosts.sort();
+		hosts
+	}
+	
+	//		intervals															
+	/// The times between consecutive requests to the given host, in order.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `host` - The host, such as `api.example.com`.
+	/// 
+	#[must_use]
+	pub fn intervals(&self, host: &str) -> Vec<Duration> {
+		self.times(host).windows(2)
+			.map(|pair| pair[1].duration_since(pair[0]).unwrap_or_default())
+			.collect()
+	}
+	
+	//		lock																
+	/// Locks the state, recovering from poisoning.
+	fn lock(&self) -> MutexGuard<'_, HashMap<String, Vec<SystemTime>>> {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+	
+	//		observe																
+	/// Records a request sent to the given host.
+	/// 
+	/// The mocked Reqwest clients call this for the observer of the entered
+	/// [`Context`](crate::Context), and other mocks can do the same.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `host` - The host that the request was sent to.
+	/// * `at`   - The time at which the request was sent.
+	/// 
+	pub fn observe(&self, host: &str, at: SystemTime) {
+		self.lock().entry(host.to_owned()).or_default().push(at);
+	}
+	
+	//		peak																
+	/// The most requests sent to the given host within any window of the given
+	/// length.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `host`   - The host, such as `api.example.com`.
+	/// * `window` - The length of the window.
+	/// 
+	#[must_use]
+	pub fn peak(&self, host: &str, window: Duration) -> usize {
+		busiest(&self.times(host), window).1
+	}
+	
+	//		times																
+	/// The times at which requests were sent to the given host, in order.
+	fn times(&self, host: &str) -> Vec<SystemTime> {
+		let mut times = self.lock().get(host).cloned().unwrap_or_default();
+		times.sort();
+		times
+	}
+}
+
+
+
+//		Functions
+
+//		busiest																	
+/// Finds the window of the given length, starting at one of the given sorted
+/// times, that contains the most of them, and returns the position of its
+/// first time and the number that it contains.
+fn busiest(times: &[SystemTime], window: Duration) -> (usize, usize) {
+	let mut best = (0, 0);
+	let mut end  = 0;
+	for (start, &from) in times.iter().enumerate() {
+		end = end.max(start);
+		while end < times.len() && times[end].duration_since(from).unwrap_or_default() < window {
+			end += 1;
+		}
+		if end - start > best.1 {
+			best = (start, end - start);
+		}
+	}
+	best
+}
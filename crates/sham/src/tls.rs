@@ -0,0 +1,490 @@
+//! This module mocks TLS handshakes in order to test their failure handling.
+//! 
+//! This module provides a layer that wraps any mocked stream, such as a
+//! [`MockUnixStream`](crate::unix::MockUnixStream), along with routes for the
+//! mocked [`reqwest`](crate::reqwest) client, and that fails the handshake for
+//! chosen hosts in the ways that a real TLS implementation does: an expired
+//! certificate, a certificate that is not valid for the hostname, a certificate
+//! from an unknown issuer, or a handshake that times out. This is important
+//! because the code paths that handle these failures, such as certificate
+//! pinning and the messages shown to users, are otherwise very hard to reach
+//! in tests, as they need real servers with deliberately broken certificates.
+//! 
+//! The approach taken is that the "real" code should perform the handshake
+//! through a connector that it is given, and the test code then supplies a
+//! [`MockTls`] configured with the failures to simulate. Each failure surfaces
+//! as a [`MockTlsError`], which can be found in the chain of sources of the
+//! error that is returned, so that the different failures can be told apart in
+//! the same way as the errors of the real TLS implementation.
+//! 
+//! Notably, the mock is more restrictive than the "real" code. No encryption
+//! takes place, and a successful handshake simply passes data through to the
+//! wrapped stream. Hosts are matched exactly, and routes only match the default
+//! HTTPS port unless a port is included in the host.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use sham::{
+//!     reqwest::{MockRoute, create_mock_response},
+//!     tls::{MockTls, MockTlsError, MockTlsFailure},
+//!     unix::MockUnixStream,
+//! };
+//! use reqwest::{Method, StatusCode};
+//! use std::{collections::HashMap, io::ErrorKind};
+//! 
+//! let tls = MockTls::new()
+//!     .with_failure("expired.example.com", MockTlsFailure::CertificateExpired)
+//!     .with_failure("slow.example.com",    MockTlsFailure::HandshakeTimeout)
+//! ;
+//! 
+//! let (stream, _server) = MockUnixStream::pair();
+//! let error = tls.connect("expired.example.com", stream).unwrap_err();
+//! assert_eq!(error.kind(), ErrorKind::InvalidData);
+//! assert_eq!(MockTlsError::find(&error).unwrap().failure, MockTlsFailure::CertificateExpired);
+//! 
+//! let (stream, _server) = MockUnixStream::pair();
+//! assert_eq!(tls.connect("api.example.com", stream).unwrap().server_name(), "api.example.com");
+//! 
+//! let client = tls.client(vec![
+//!     MockRoute::prefixed(Method::GET, "https://slow.example.com/", |request| Ok(create_mock_response(
+//!         request.url.clone(), StatusCode::OK, None::<String>, None, HashMap::<String, String>::new(), Ok(b"")
+//!     ))),
+//! ]);
+//! let error = futures_executor::block_on(client.get("https://slow.example.com/status").send()).unwrap_err();
+//! assert!(error.is_connect() && error.is_timeout());
+//! assert_eq!(MockTlsError::find(&error).unwrap().failure, MockTlsFailure::HandshakeTimeout);
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::reqwest::{MockClient, MockError, MockRoute, create_routed_mock_client};
+use core::{
+	error::Error,
+	fmt::{Display, Formatter, self},
+	iter::successors,
+};
+use reqwest::Method;
+use std::{
+	collections::HashMap,
+	io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write},
+	sync::Arc,
+};
+
+#[cfg(any(feature = "futures-io", feature = "tokio"))]
+use core::{
+	pin::Pin,
+	task::{Context, Poll},
+};
+#[cfg(feature = "futures-io")]
+use futures_io::{AsyncRead as FuturesAsyncRead, AsyncWrite as FuturesAsyncWrite};
+#[cfg(feature = "tokio")]
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+
+
+//		Modules
+
+//		prelude																	
+/// The mocks, named after the genuine types.
+/// 
+/// This allows the mocks for the TLS connectors of
+/// [`native-tls`](https://crates.io/crates/native-tls) and
+/// [`tokio-rustls`](https://crates.io/crates/tokio-rustls) to be imported with
+/// a single glob import when testing, without renaming them. The names are the
+/// same as those used by the `swap_uses` attribute, and by the combined
+/// [`prelude`](crate::prelude).
+/// 
+pub mod prelude {
+	pub use super::{
+		MockTls       as TlsConnector,
+		MockTlsStream as TlsStream,
+	};
+}
+
+
+
+//		Enums
+
+//		MockTlsFailure															
+/// A TLS handshake failure to simulate.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum MockTlsFailure {
+	/// The certificate of the server has expired.
+	CertificateExpired,
+	
+	/// The server did not complete the handshake in time.
+	HandshakeTimeout,
+	
+	/// The certificate of the server is not valid for the hostname.
+	HostnameMismatch,
+	
+	/// The certificate of the server was issued by an unknown authority, such
+	/// as when it is self-signed.
+	UnknownIssuer,
+}
+
+//󰭅		MockTlsFailure															
+impl MockTlsFailure {
+	//		kind																
+	/// Returns the kind of I/O error that the failure surfaces as.
+	#[must_use]
+	pub const fn kind(self) -> ErrorKind {
+		match self {
+			Self::HandshakeTimeout => ErrorKind::TimedOut,
+			Self::CertificateExpired
+			| Self::HostnameMismatch
+			| Self::UnknownIssuer  => ErrorKind::InvalidData,
+		}
+	}
+}
+
+
+
+//		Structs
+
+//		MockTls																	
+/// A mocked TLS connector, which fails the handshake for chosen hosts.
+/// 
+/// # See also
+/// 
+/// * `tokio_rustls::TlsConnector`
+/// * `native_tls::TlsConnector`
+/// 
+#[derive(Clone, Debug, Default)]
+pub struct MockTls {
+	//		Private properties													
+	/// The certificates presented by hosts, in DER form.
+	certificates: HashMap<String, Vec<u8>>,
+	
+	/// The failures to simulate, by host.
+	failures:     HashMap<String, MockTlsFailure>,
+}
+
+//󰭅		MockTls																	
+impl MockTls {
+	//		new																	
+	/// Creates a new connector, with which every handshake succeeds.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+	
+	//		with_certificate													
+	/// Sets the certificate that a host presents, so that code which pins
+	/// certificates can check it.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `host`        - The host.
+	/// * `certificate` - The certificate, in DER form.
+	/// 
+	#[must_use]
+	pub fn with_certificate(mut self, host: &str, certificate: &[u8]) -> Self {
+		drop(self.certificates.insert(host.to_owned(), certificate.to_vec()));
+		self
+	}
+	
+	//		with_failure														
+	/// Sets the failure to simulate when performing a handshake with a host.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `host`    - The host, including the port if it is not the default.
+	/// * `failure` - The failure to simulate.
+	/// 
+	#[must_use]
+	pub fn with_failure(mut self, host: &str, failure: MockTlsFailure) -> Self {
+		_ = self.failures.insert(host.to_owned(), failure);
+		self
+	}
+	
+	//		client																
+	/// Creates a mock Reqwest client that serves the given routes, except that
+	/// HTTPS requests to failing hosts fail during the handshake.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `routes` - The routes to serve.
+	/// 
+	#[must_use]
+	pub fn client(&self, routes: Vec<MockRoute>) -> MockClient {
+		let mut all_routes = self.routes();
+		all_routes.extend(routes);
+		create_routed_mock_client(all_routes)
+	}
+	
+	//		connect																
+	/// Performs a handshake with a host over the given stream.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `host`   - The host to connect to, which is checked against the
+	///   certificate.
+	/// * `stream` - The stream to wrap.
+	/// 
+	/// # Errors
+	/// 
+	/// If a failure has been set for the host, an error of the kind given by
+	/// [`MockTlsFailure::kind()`] will be returned, wrapping a
+	/// [`MockTlsError`].
+	/// 
+	/// # See also
+	/// 
+	/// * `tokio_rustls::TlsConnector::connect()`
+	/// * `native_tls::TlsConnector::connect()`
+	/// 
+	pub fn connect<S>(&self, host: &str, stream: S) -> IoResult<MockTlsStream<S>> {
+		if let Some(error) = self.error_for(host) {
+			return Err(IoError::new(error.failure.kind(), error));
+		}
+		Ok(MockTlsStream {
+			certificate: self.certificates.get(host).cloned(),
+			host:        host.to_owned(),
+			inner:       stream,
+		})
+	}
+	
+	//		routes																
+	/// Returns routes that fail every HTTPS request to a failing host, for use
+	/// ahead of other routes with
+	/// [`create_routed_mock_client()`](crate::reqwest::create_routed_mock_client()).
+	/// 
+	/// The errors returned are connection errors, which are also timeouts for
+	/// [`HandshakeTimeout`](MockTlsFailure::HandshakeTimeout), and have the
+	/// [`MockTlsError`] as their source.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if a failing host does not form a valid URL.
+	/// 
+	#[must_use]
+	pub fn routes(&self) -> Vec<MockRoute> {
+		let mut hosts: Vec<&String> = self.failures.keys().collect();
+		hosts.sort();
+		hosts.into_iter().flat_map(|host| {
+			[Method::DELETE, Method::GET, Method::PATCH, Method::POST, Method::PUT].map(|method| {
+				let tls = self.clone();
+				MockRoute::prefixed(method, format!("https://{host}/"), move |request| {
+					let host_and_port = match (request.url.host_str(), request.url.port()) {
+						(Some(name), Some(port)) => format!("{name}:{port}"),
+						(Some(name), None)       => name.to_owned(),
+						(None,       _)          => String::new(),
+					};
+					let error = tls.error_for(&host_and_port).expect("Route only matches failing hosts");
+					Err(MockError {
+						is_connect: true,
+						is_request: true,
+						is_timeout: error.failure == MockTlsFailure::HandshakeTimeout,
+						source:     Some(Arc::new(error)),
+						url:        Some(request.url.clone()),
+						..Default::default()
+					})
+				})
+			})
+		}).collect()
+	}
+	
+	//		error_for															
+	/// Returns the error for a handshake with a host, if it should fail.
+	fn error_for(&self, host: &str) -> Option<MockTlsError> {
+		self.failures.get(host).map(|&failure| MockTlsError { failure, host: host.to_owned() })
+	}
+}
+
+//		MockTlsError															
+/// A mocked TLS handshake error.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockTlsError {
+	//		Public properties													
+	/// The failure that occurred.
+	pub failure: MockTlsFailure,
+	
+	/// The host that the handshake was with.
+	pub host:    String,
+}
+
+//󰭅		MockTlsError															
+impl MockTlsError {
+	//		find																
+	/// Finds the TLS error in the chain of sources of an error, including the
+	/// error itself and the errors wrapped by I/O errors.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `error` - The error to search.
+	/// 
+	#[must_use]
+	pub fn find<E: Error + 'static>(error: &E) -> Option<&Self> {
+		let root: &(dyn Error + 'static) = error;
+		successors(Some(root), |&current| current.source()).find_map(|current| {
+			current.downcast_ref::<Self>().or_else(|| {
+				current.downcast_ref::<IoError>()
+					.and_then(IoError::get_ref)
+					.and_then(|inner| inner.downcast_ref::<Self>())
+			})
+		})
+	}
+}
+
+//󰭅		Display																	
+impl Display for MockTlsError {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self.failure {
+			MockTlsFailure::CertificateExpired => write!(f, "invalid peer certificate for {}: certificate expired", self.host),
+			MockTlsFailure::HandshakeTimeout   => write!(f, "TLS handshake with {} timed out", self.host),
+			MockTlsFailure::HostnameMismatch   => write!(f, "invalid peer certificate: certificate not valid for name \"{}\"", self.host),
+			MockTlsFailure::UnknownIssuer      => write!(f, "invalid peer certificate for {}: unknown issuer", self.host),
+		}
+	}
+}
+
+//󰭅		Error																	
+impl Error for MockTlsError {}
+
+//		MockTlsStream															
+/// A mocked TLS stream, which passes data through to the stream that it wraps.
+/// 
+/// # See also
+/// 
+/// * `tokio_rustls::client::TlsStream`
+/// * `native_tls::TlsStream`
+/// 
+#[derive(Debug)]
+pub struct MockTlsStream<S> {
+	//		Private properties													
+	/// The certificate presented by the host, in DER form, if one was set.
+	certificate: Option<Vec<u8>>,
+	
+	/// The host that the handshake was with.
+	host:        String,
+	
+	/// The wrapped stream.
+	inner:       S,
+}
+
+//󰭅		MockTlsStream															
+impl<S> MockTlsStream<S> {
+	//		get_mut																
+	/// Returns a mutable reference to the wrapped stream.
+	pub const fn get_mut(&mut self) -> &mut S {
+		&mut self.inner
+	}
+	
+	//		get_ref																
+	/// Returns a reference to the wrapped stream.
+	#[must_use]
+	pub const fn get_ref(&self) -> &S {
+		&self.inner
+	}
+	
+	//		into_inner															
+	/// Returns the wrapped stream.
+	#[must_use]
+	pub fn into_inner(self) -> S {
+		self.inner
+	}
+	
+	//		peer_certificate													
+	/// Returns the certificate presented by the host, in DER form, if one was
+	/// set using [`MockTls::with_certificate()`].
+	/// 
+	/// # See also
+	/// 
+	/// * `native_tls::TlsStream::peer_certificate()`
+	/// 
+	#[must_use]
+	pub fn peer_certificate(&self) -> Option<&[u8]> {
+		self.certificate.as_deref()
+	}
+	
+	//		server_name															
+	/// Returns the host that the handshake was with.
+	#[must_use]
+	pub fn server_name(&self) -> &str {
+		&self.host
+	}
+}
+
+//󰭅		AsyncRead																
+#[cfg(feature = "tokio")]
+impl<S: AsyncRead + Unpin> AsyncRead for MockTlsStream<S> {
+	//		poll_read															
+	fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<IoResult<()>> {
+		Pin::new(&mut self.get_mut().inner).poll_read(cx, buf)
+	}
+}
+
+//󰭅		AsyncWrite																
+#[cfg(feature = "tokio")]
+impl<S: AsyncWrite + Unpin> AsyncWrite for MockTlsStream<S> {
+	//		poll_write															
+	fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<IoResult<usize>> {
+		Pin::new(&mut self.get_mut().inner).poll_write(cx, buf)
+	}
+	
+	//		poll_flush															
+	fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+		Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+	}
+	
+	//		poll_shutdown														
+	fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+		Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+	}
+}
+
+//󰭅		FuturesAsyncRead														
+#[cfg(feature = "futures-io")]
+impl<S: FuturesAsyncRead + Unpin> FuturesAsyncRead for MockTlsStream<S> {
+	//		poll_read															
+	fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<IoResult<usize>> {
+		Pin::new(&mut self.get_mut().inner).poll_read(cx, buf)
+	}
+}
+
+//󰭅		FuturesAsyncWrite														
+#[cfg(feature = "futures-io")]
+impl<S: FuturesAsyncWrite + Unpin> FuturesAsyncWrite for MockTlsStream<S> {
+	//		poll_write															
+	fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<IoResult<usize>> {
+		Pin::new(&mut self.get_mut().inner).poll_write(cx, buf)
+	}
+	
+	//		poll_flush															
+	fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+		Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+	}
+	
+	//		poll_close															
+	fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+		Pin::new(&mut self.get_mut().inner).poll_close(cx)
+	}
+}
+
+//󰭅		Read																	
+impl<S: Read> Read for MockTlsStream<S> {
+	//		read																
+	fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+		self.inner.read(buf)
+	}
+}
+
+//󰭅		Write																	
+impl<S: Write> Write for MockTlsStream<S> {
+	//		write																
+	fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+		self.inner.write(buf)
+	}
+	
+	//		flush																
+	fn flush(&mut self) -> IoResult<()> {
+		self.inner.flush()
+	}
+}
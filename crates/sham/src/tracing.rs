@@ -0,0 +1,472 @@
+//! This module mocks a [`tracing`] subscriber in order to test instrumentation.
+//! 
+//! This module provides a test subscriber that captures the spans and events
+//! emitted through the [`tracing`] crate, along with their fields and their
+//! parentage, so that tests can query them afterwards and assert that the
+//! instrumentation contracts of the code under test are upheld, alongside its
+//! behaviour. This is important because spans and events often feed dashboards
+//! and alerts, and changes to them can otherwise go unnoticed.
+//! 
+//! The approach taken is that [`MockTracing::capture()`] sets the test
+//! subscriber as the default for the current thread, until the capture is
+//! dropped. As `cargo test` runs each test on its own thread, this keeps the
+//! spans and events of tests that run in parallel isolated from each other.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use sham::tracing::MockTracing;
+//! use tracing::{info, info_span};
+//! 
+//! let tracing = MockTracing::capture();
+//! info_span!("db.query", table = "users").in_scope(|| {
+//!     info!(rows = 3, "Query complete");
+//! });
+//! 
+//! let spans  = tracing.spans();
+//! let events = tracing.events();
+//! assert_eq!(spans.named("db.query").count(), 1);
+//! assert_eq!(spans.named("db.query").next().unwrap().field("table"), Some("users"));
+//! assert_eq!(events.with_message("Query complete").count(), 1);
+//! assert_eq!(events.in_span(&spans, "db.query").next().unwrap().field("rows"), Some("3"));
+//! ```
+//! 
+
+
+
+//		Packages
+
+use core::fmt::Debug;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use tracing::{
+	Event,
+	Level,
+	Metadata,
+	Subscriber,
+	field::{Field, Visit},
+	span::{Attributes, Id, Record},
+	subscriber::{DefaultGuard, set_default},
+};
+
+
+
+//		Structs
+
+//		CaptureState															
+/// The spans and events captured by a [`CaptureSubscriber`].
+#[derive(Debug, Default)]
+struct CaptureState {
+	/// The captured events, in the order they were emitted.
+	events: Vec<MockEvent>,
+	
+	/// The captured spans, in the order they were created. The ID of each span
+	/// is its index in this list plus one.
+	spans:  Vec<MockSpan>,
+	
+	/// The IDs of the spans that are currently entered, innermost last.
+	stack:  Vec<u64>,
+}
+
+//		CaptureSubscriber														
+/// The test subscriber, which records spans and events into shared state.
+#[derive(Debug)]
+struct CaptureSubscriber {
+	/// The captured spans and events.
+	state: Arc<Mutex<CaptureState>>,
+}
+
+//󰭅		CaptureSubscriber														
+impl CaptureSubscriber {
+	//		state																
+	/// Locks and returns the captured state.
+	fn state(&self) -> MutexGuard<'_, CaptureState> {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+}
+
+//󰭅		Subscriber																
+impl Subscriber for CaptureSubscriber {
+	//		enabled																
+	fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+		true
+	}
+	
+	//		new_span															
+	fn new_span(&self, span: &Attributes<'_>) -> Id {
+		let mut visitor = FieldCollector::default();
+		span.record(&mut visitor);
+		let mut state   = self.state();
+		let parent      = if span.is_contextual() {
+			state.stack.last().copied()
+		} else {
+			span.parent().map(Id::into_u64)
+		};
+		let id          = state.spans.len() as u64 + 1;
+		let captured    = MockSpan {
+			id,
+			name:         span.metadata().name().to_owned(),
+			target:       span.metadata().target().to_owned(),
+			level:        *span.metadata().level(),
+			fields:       visitor.fields,
+			parent,
+			follows_from: Vec::new(),
+			entered:      0,
+		};
+		state.spans.push(captured);
+		drop(state);
+		Id::from_u64(id)
+	}
+	
+	//		record																
+	fn record(&self, span: &Id, values: &Record<'_>) {
+		let mut visitor = FieldCollector::default();
+		values.record(&mut visitor);
+		if let Some(captured) = self.state().spans.iter_mut().find(|captured| captured.id == span.into_u64()) {
+			for (name, value) in visitor.fields {
+				if let Some(existing) = captured.fields.iter_mut().find(|existing| existing.0 == name) {
+					existing.1 = value;
+				} else {
+					captured.fields.push((name, value));
+				}
+			}
+		}
+	}
+	
+	//		record_follows_from													
+	fn record_follows_from(&self, span: &Id, follows: &Id) {
+		if let Some(captured) = self.state().spans.iter_mut().find(|captured| captured.id == span.into_u64()) {
+			captured.follows_from.push(follows.into_u64());
+		}
+	}
+	
+	//		event																
+	fn event(&self, event: &Event<'_>) {
+		let mut visitor = FieldCollector::default();
+		event.record(&mut visitor);
+		let mut state   = self.state();
+		let parent      = if event.is_contextual() {
+			state.stack.last().copied()
+		} else {
+			event.parent().map(Id::into_u64)
+		};
+		let message     = visitor.fields
+			.iter()
+			.position(|field| field.0 == "message")
+			.map(|index| visitor.fields.remove(index).1)
+		;
+		state.events.push(MockEvent {
+			level:   *event.metadata().level(),
+			target:  event.metadata().target().to_owned(),
+			message,
+			fields:  visitor.fields,
+			parent,
+		});
+	}
+	
+	//		enter																
+	fn enter(&self, span: &Id) {
+		let mut state = self.state();
+		state.stack.push(span.into_u64());
+		if let Some(captured) = state.spans.iter_mut().find(|captured| captured.id == span.into_u64()) {
+			captured.entered += 1;
+		}
+	}
+	
+	//		exit																
+	fn exit(&self, span: &Id) {
+		let mut state = self.state();
+		if let Some(position) = state.stack.iter().rposition(|&entered| entered == span.into_u64()) {
+			_ = state.stack.remove(position);
+		}
+	}
+}
+
+//		FieldCollector															
+/// Collects the fields of a span or event as strings.
+#[derive(Debug, Default)]
+struct FieldCollector {
+	/// The collected fields, in the order they were visited.
+	fields: Vec<(String, String)>,
+}
+
+//󰭅		Visit																	
+impl Visit for FieldCollector {
+	//		record_str															
+	fn record_str(&mut self, field: &Field, value: &str) {
+		self.fields.push((field.name().to_owned(), value.to_owned()));
+	}
+	
+	//		record_debug														
+	fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+		self.fields.push((field.name().to_owned(), format!("{value:?}")));
+	}
+}
+
+//		MockEvent																
+/// A captured event.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockEvent {
+	//		Public properties													
+	/// The level of the event.
+	pub level:   Level,
+	
+	/// The target of the event, which defaults to the module path.
+	pub target:  String,
+	
+	/// The message of the event, if it has one.
+	pub message: Option<String>,
+	
+	/// The fields of the event, other than the message, with the values
+	/// formatted as strings, in the order they were given.
+	pub fields:  Vec<(String, String)>,
+	
+	/// The ID of the span that the event occurred within, if any.
+	pub parent:  Option<u64>,
+}
+
+//󰭅		MockEvent																
+impl MockEvent {
+	//		field																
+	/// Returns the value of the named field, if the event has it.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the field.
+	/// 
+	#[must_use]
+	pub fn field(&self, name: &str) -> Option<&str> {
+		self.fields.iter().find(|field| field.0 == name).map(|field| field.1.as_str())
+	}
+}
+
+//		MockEvents																
+/// A queryable collection of captured events.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MockEvents(Vec<MockEvent>);
+
+//󰭅		MockEvents																
+impl MockEvents {
+	//		at_level															
+	/// Returns the events at the given level.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `level` - The level of the events to return.
+	/// 
+	pub fn at_level(&self, level: Level) -> impl Iterator<Item = &MockEvent> {
+		self.0.iter().filter(move |event| event.level == level)
+	}
+	
+	//		in_span																
+	/// Returns the events that occurred directly within a span of the given
+	/// name.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `spans` - The spans captured alongside the events.
+	/// * `name`  - The name of the span that the events occurred within.
+	/// 
+	pub fn in_span<'a>(&'a self, spans: &'a MockSpans, name: &'a str) -> impl Iterator<Item = &'a MockEvent> {
+		self.0.iter().filter(move |event| {
+			event.parent.and_then(|parent| spans.get(parent)).is_some_and(|span| span.name == name)
+		})
+	}
+	
+	//		is_empty															
+	/// Whether no events were captured.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+	
+	//		iter																
+	/// Returns all of the events, in the order they were emitted.
+	pub fn iter(&self) -> impl Iterator<Item = &MockEvent> {
+		self.0.iter()
+	}
+	
+	//		len																	
+	/// Returns the number of events captured.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+	
+	//		with_message														
+	/// Returns the events with a message containing the given text.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `text` - The text that the messages should contain.
+	/// 
+	pub fn with_message<'a>(&'a self, text: &'a str) -> impl Iterator<Item = &'a MockEvent> {
+		self.0.iter().filter(move |event| event.message.as_ref().is_some_and(|message| message.contains(text)))
+	}
+}
+
+//		MockSpan																
+/// A captured span.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockSpan {
+	//		Public properties													
+	/// The ID of the span, which is unique within the capture.
+	pub id:           u64,
+	
+	/// The name of the span.
+	pub name:         String,
+	
+	/// The target of the span, which defaults to the module path.
+	pub target:       String,
+	
+	/// The level of the span.
+	pub level:        Level,
+	
+	/// The fields of the span, including any recorded after it was created,
+	/// with the values formatted as strings.
+	pub fields:       Vec<(String, String)>,
+	
+	/// The ID of the parent span, if any.
+	pub parent:       Option<u64>,
+	
+	/// The IDs of the spans that this span follows from.
+	pub follows_from: Vec<u64>,
+	
+	/// The number of times the span was entered.
+	pub entered:      usize,
+}
+
+//󰭅		MockSpan																
+impl MockSpan {
+	//		field																
+	/// Returns the value of the named field, if the span has it.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the field.
+	/// 
+	#[must_use]
+	pub fn field(&self, name: &str) -> Option<&str> {
+		self.fields.iter().find(|field| field.0 == name).map(|field| field.1.as_str())
+	}
+}
+
+//		MockSpans																
+/// A queryable collection of captured spans.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MockSpans(Vec<MockSpan>);
+
+//󰭅		MockSpans																
+impl MockSpans {
+	//		children_of															
+	/// Returns the spans whose parent is the given span.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `parent` - The parent span.
+	/// 
+	pub fn children_of<'a>(&'a self, parent: &'a MockSpan) -> impl Iterator<Item = &'a MockSpan> {
+		self.0.iter().filter(move |span| span.parent == Some(parent.id))
+	}
+	
+	//		get																	
+	/// Returns the span with the given ID, if it exists.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `id` - The ID of the span.
+	/// 
+	#[must_use]
+	pub fn get(&self, id: u64) -> Option<&MockSpan> {
+		self.0.iter().find(|span| span.id == id)
+	}
+	
+	//		is_empty															
+	/// Whether no spans were captured.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+	
+	//		iter																
+	/// Returns all of the spans, in the order they were created.
+	pub fn iter(&self) -> impl Iterator<Item = &MockSpan> {
+		self.0.iter()
+	}
+	
+	//		len																	
+	/// Returns the number of spans captured.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+	
+	//		named																
+	/// Returns the spans with the given name.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the spans to return.
+	/// 
+	pub fn named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a MockSpan> {
+		self.0.iter().filter(move |span| span.name == name)
+	}
+	
+	//		parent_of															
+	/// Returns the parent of the given span, if it has one.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `span` - The child span.
+	/// 
+	#[must_use]
+	pub fn parent_of(&self, span: &MockSpan) -> Option<&MockSpan> {
+		span.parent.and_then(|parent| self.get(parent))
+	}
+}
+
+//		MockTracing																
+/// A capture of the spans and events emitted on the current thread.
+/// 
+/// Spans and events are captured from when [`capture()`](MockTracing::capture())
+/// is called until the capture is dropped, at which point the previous default
+/// subscriber is restored.
+/// 
+#[derive(Debug)]
+pub struct MockTracing {
+	//		Private properties													
+	/// The guard that keeps the test subscriber as the thread's default.
+	_guard: DefaultGuard,
+	
+	/// The captured spans and events.
+	state:  Arc<Mutex<CaptureState>>,
+}
+
+//󰭅		MockTracing																
+impl MockTracing {
+	//		capture																
+	/// Starts capturing the spans and events emitted on the current thread.
+	#[must_use]
+	pub fn capture() -> Self {
+		let state = Arc::new(Mutex::new(CaptureState::default()));
+		Self {
+			_guard: set_default(CaptureSubscriber { state: Arc::clone(&state) }),
+			state,
+		}
+	}
+	
+	//		events																
+	/// Returns a copy of the events captured so far.
+	#[must_use]
+	pub fn events(&self) -> MockEvents {
+		MockEvents(self.state.lock().unwrap_or_else(PoisonError::into_inner).events.clone())
+	}
+	
+	//		spans																
+	/// Returns a copy of the spans captured so far.
+	#[must_use]
+	pub fn spans(&self) -> MockSpans {
+		MockSpans(self.state.lock().unwrap_or_else(PoisonError::into_inner).spans.clone())
+	}
+}
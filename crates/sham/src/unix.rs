@@ -0,0 +1,1118 @@
+//! This module mocks Unix domain sockets in order to test their users.
+//! 
+//! This module provides in-memory stand-ins for Unix domain sockets, in the
+//! style of [`std::os::unix::net`] and of the Unix socket types of
+//! [Tokio](https://crates.io/crates/tokio), with streams that implement both
+//! [`Read`]/[`Write`] and, with the `tokio` and `futures-io` features, the
+//! asynchronous I/O traits of Tokio and of [`futures-io`](https://crates.io/crates/futures-io),
+//! as used by `async-std` and `smol`. Sockets live in a
+//! [`MockUnixSockets`] namespace rather than on the filesystem, and each
+//! connection carries the credentials of its peer, as reported by `SO_PEERCRED`.
+//! This is important because daemons that expose control sockets, and clients
+//! of sockets such as `docker.sock`, otherwise need real filesystem sockets,
+//! real peers, and real users in order to be tested.
+//! 
+//! The approach taken is that the "real" code should bind and connect through
+//! a provider that it is given, and the test code then supplies a
+//! [`MockUnixSockets`] instead. The other end of a connection can be the test
+//! code itself, using a [`MockUnixListener`], or a [`MockUnixPeer`] that
+//! follows a script of expected requests and replies.
+//! 
+//! Notably, the mock is more restrictive than the "real" code. Only paths are
+//! supported as addresses, and accepting a connection returns only the stream.
+//! Because nothing else can send data on behalf of a scripted peer, reading
+//! from it while it is waiting for a request fails with
+//! [`TimedOut`](ErrorKind::TimedOut) rather than blocking forever. When the
+//! script ends, the peer closes the connection.
+//! 
+//! A [`FaultPlan`] can be attached to a namespace, in which case each
+//! connection attempt draws a fault from it, waiting for the latency and then
+//! failing with the corresponding I/O error, if any.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use sham::unix::{MockPeerCred, MockUnixPeer, MockUnixSockets};
+//! use std::io::{ErrorKind, Read, Write};
+//! 
+//! let sockets = MockUnixSockets::new();
+//! let docker  = MockUnixPeer::new()
+//!     .expect(b"GET /_ping HTTP/1.1\r\n\r\n")
+//!     .reply(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nOK")
+//! ;
+//! sockets.serve("/var/run/docker.sock", docker.clone()).unwrap();
+//! 
+//! let mut stream = sockets.connect("/var/run/docker.sock").unwrap();
+//! stream.write_all(b"GET /_ping HTTP/1.1\r\n\r\n").unwrap();
+//! let mut response = String::new();
+//! stream.read_to_string(&mut response).unwrap();
+//! assert!(response.ends_with("OK"));
+//! 
+//! let listener = sockets.bind("/run/app.sock").unwrap();
+//! let _client  = sockets.connect_as("/run/app.sock", MockPeerCred::new(0, 0, Some(1))).unwrap();
+//! let server   = listener.accept().unwrap();
+//! assert_eq!(server.peer_cred().unwrap().uid, 0);
+//! 
+//! drop(listener);
+//! assert_eq!(sockets.connect("/run/app.sock").unwrap_err().kind(), ErrorKind::ConnectionRefused);
+//! assert_eq!(sockets.connect("/run/other.sock").unwrap_err().kind(), ErrorKind::NotFound);
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::{
+	context::Context as MockContext,
+	fault::FaultPlan,
+	path::MockPathMatcher,
+};
+use core::{
+	future::poll_fn,
+	sync::atomic::{AtomicBool, Ordering},
+	task::{Poll, Waker},
+	time::Duration,
+};
+use std::{
+	collections::{HashMap, VecDeque},
+	io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write},
+	net::Shutdown,
+	path::{Path, PathBuf},
+	sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError},
+	thread,
+};
+
+#[cfg(any(feature = "futures-io", feature = "tokio"))]
+use core::{pin::Pin, task::Context};
+#[cfg(feature = "futures-io")]
+use futures_io::{AsyncRead as FuturesAsyncRead, AsyncWrite as FuturesAsyncWrite};
+#[cfg(feature = "tokio")]
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+
+
+//		Modules
+
+//		prelude																	
+/// The mocks, named after the genuine types.
+/// 
+/// This allows the mocks for the Unix domain sockets of [`std::os::unix::net`]
+/// and [`tokio::net`] to be imported with a single glob import when testing,
+/// without renaming them. The names are the same as those used by the
+/// `swap_uses` attribute, and by the combined [`prelude`](crate::prelude).
+/// 
+pub mod prelude {
+	pub use super::{
+		MockPeerCred     as UCred,
+		MockUnixListener as UnixListener,
+		MockUnixStream   as UnixStream,
+	};
+}
+
+
+
+//		Enums
+
+//		Endpoint																
+/// Something bound to a path in a [`MockUnixSockets`] namespace.
+#[derive(Clone, Debug)]
+enum Endpoint {
+	/// A listener, which may have been closed.
+	Listener(Arc<Shared<ListenerState>>),
+	
+	/// A scripted peer.
+	Peer(MockUnixPeer),
+}
+
+//		Step																	
+/// A step in the script of a [`MockUnixPeer`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Step {
+	/// Close the connection.
+	Close,
+	
+	/// Expect the given bytes to be sent by the client.
+	Expect(Vec<u8>),
+	
+	/// Send the given bytes to the client.
+	Reply(Vec<u8>),
+}
+
+
+
+//		Structs
+
+//		ListenerState															
+/// The state of a [`MockUnixListener`].
+#[derive(Debug)]
+struct ListenerState {
+	//		Private properties													
+	/// Whether the listener has been closed.
+	closed:  bool,
+	
+	/// The credentials of the process that owns the listener.
+	cred:    MockPeerCred,
+	
+	/// The connections waiting to be accepted.
+	pending: VecDeque<MockUnixStream>,
+	
+	/// The task waiting to accept a connection, if any.
+	waker:   Option<Waker>,
+}
+
+//		MockPeerCred															
+/// The credentials of the process at the other end of a connection.
+/// 
+/// # See also
+/// 
+/// * `std::os::unix::net::UCred`
+/// * `tokio::net::unix::UCred`
+/// 
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct MockPeerCred {
+	//		Public properties													
+	/// The effective group ID of the process.
+	pub gid: u32,
+	
+	/// The ID of the process, if it is known.
+	pub pid: Option<i32>,
+	
+	/// The effective user ID of the process.
+	pub uid: u32,
+}
+
+//󰭅		MockPeerCred															
+impl MockPeerCred {
+	//		new																	
+	/// Creates a new set of credentials.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `uid` - The effective user ID.
+	/// * `gid` - The effective group ID.
+	/// * `pid` - The process ID, if it is known.
+	/// 
+	#[must_use]
+	pub const fn new(uid: u32, gid: u32, pid: Option<i32>) -> Self {
+		Self { gid, pid, uid }
+	}
+}
+
+//󰭅		Default																	
+impl Default for MockPeerCred {
+	//		default																
+	/// Returns the credentials of an ordinary user, with user and group IDs of
+	/// 1000 and a process ID of 4242.
+	fn default() -> Self {
+		Self::new(1_000, 1_000, Some(4_242))
+	}
+}
+
+//		MockUnixListener														
+/// A mocked Unix domain socket listener.
+/// 
+/// Dropping the listener closes it, after which connecting to its path is
+/// refused until the path is removed and bound again, as with a stale socket
+/// file.
+/// 
+/// # See also
+/// 
+/// * [`std::os::unix::net::UnixListener`]
+/// * `tokio::net::UnixListener`
+/// 
+#[derive(Debug)]
+pub struct MockUnixListener {
+	//		Private properties													
+	/// The path that the listener is bound to.
+	path:  PathBuf,
+	
+	/// The state of the listener, shared with the namespace.
+	state: Arc<Shared<ListenerState>>,
+}
+
+//󰭅		MockUnixListener														
+impl MockUnixListener {
+	//		accept																
+	/// Accepts a connection, blocking until one is made.
+	/// 
+	/// # Errors
+	/// 
+	/// This function does not currently return an error, but the signature
+	/// matches that of the "real" code.
+	/// 
+	/// # See also
+	/// 
+	/// * [`std::os::unix::net::UnixListener::accept()`]
+	/// 
+	pub fn accept(&self) -> IoResult<MockUnixStream> {
+		let mut state = self.state.lock();
+		loop {
+			if let Some(stream) = state.pending.pop_front() {
+				return Ok(stream);
+			}
+			state = self.state.condvar.wait(state).unwrap_or_else(PoisonError::into_inner);
+		}
+	}
+	
+	//		accept_async														
+	/// Accepts a connection, waiting asynchronously until one is made.
+	/// 
+	/// # Errors
+	/// 
+	/// This function does not currently return an error, but the signature
+	/// matches that of the "real" code.
+	/// 
+	/// # See also
+	/// 
+	/// * `tokio::net::UnixListener::accept()`
+	/// 
+	pub async fn accept_async(&self) -> IoResult<MockUnixStream> {
+		poll_fn(|cx| {
+			let mut state = self.state.lock();
+			state.pending.pop_front().map_or_else(|| {
+				state.waker = Some(cx.waker().clone());
+				Poll::Pending
+			}, |stream| Poll::Ready(Ok(stream)))
+		}).await
+	}
+	
+	//		local_path															
+	/// Returns the path that the listener is bound to.
+	#[must_use]
+	pub fn local_path(&self) -> &Path {
+		&self.path
+	}
+}
+
+//󰭅		Drop																	
+impl Drop for MockUnixListener {
+	//		drop																
+	fn drop(&mut self) {
+		let mut state = self.state.lock();
+		state.closed  = true;
+		state.pending.clear();
+		drop(state);
+	}
+}
+
+//		MockUnixPeer															
+/// A scripted peer, which serves each connection made to its path by
+/// following the same script of expected requests and replies.
+/// 
+/// Clones share the same record of received data, so a clone can be served
+/// while the original is used to make assertions.
+/// 
+#[derive(Clone, Debug, Default)]
+pub struct MockUnixPeer {
+	//		Private properties													
+	/// The credentials of the peer.
+	cred:     MockPeerCred,
+	
+	/// Everything received from clients, in order.
+	received: Arc<Mutex<Vec<u8>>>,
+	
+	/// The script to follow.
+	steps:    Vec<Step>,
+}
+
+//󰭅		MockUnixPeer															
+impl MockUnixPeer {
+	//		new																	
+	/// Creates a new peer with an empty script and the default credentials.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+	
+	//		close																
+	/// Adds a step that closes the connection.
+	#[must_use]
+	pub fn close(mut self) -> Self {
+		self.steps.push(Step::Close);
+		self
+	}
+	
+	//		expect																
+	/// Adds a step that waits for the client to send the given bytes.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `request` - The bytes expected.
+	/// 
+	#[must_use]
+	pub fn expect(mut self, request: &[u8]) -> Self {
+		self.steps.push(Step::Expect(request.to_vec()));
+		self
+	}
+	
+	//		reply																
+	/// Adds a step that sends the given bytes to the client.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `response` - The bytes to send.
+	/// 
+	#[must_use]
+	pub fn reply(mut self, response: &[u8]) -> Self {
+		self.steps.push(Step::Reply(response.to_vec()));
+		self
+	}
+	
+	//		with_cred															
+	/// Sets the credentials of the peer, as seen by clients.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `cred` - The credentials.
+	/// 
+	#[must_use]
+	pub const fn with_cred(mut self, cred: MockPeerCred) -> Self {
+		self.cred = cred;
+		self
+	}
+	
+	//		received															
+	/// Returns everything received from clients, in order.
+	#[must_use]
+	pub fn received(&self) -> Vec<u8> {
+		self.received.lock().unwrap_or_else(PoisonError::into_inner).clone()
+	}
+}
+
+//		MockUnixSockets															
+/// A namespace of mocked Unix domain sockets, which stands in for the
+/// filesystem paths that sockets are bound to.
+/// 
+/// Clones share the same namespace.
+/// 
+#[derive(Clone, Debug, Default)]
+pub struct MockUnixSockets {
+	//		Private properties													
+	/// The endpoints, by path.
+	endpoints: Arc<Mutex<HashMap<PathBuf, Endpoint>>>,
+	
+	/// The plan of faults to inject into connection attempts, if any.
+	faults:    Option<FaultPlan>,
+	
+	/// The matcher used to find endpoints by path.
+	paths:     MockPathMatcher,
+}
+
+//󰭅		MockUnixSockets															
+impl MockUnixSockets {
+	//		new																	
+	/// Creates a new, empty namespace.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+	
+	//		with_faults															
+	/// Injects latency and errors into connection attempts, according to the
+	/// given plan.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `plan` - The plan of faults to inject. The plan is shared with any
+	///   other mocks that it is attached to.
+	/// 
+	#[must_use]
+	pub fn with_faults(mut self, plan: &FaultPlan) -> Self {
+		self.faults = Some(plan.clone());
+		self
+	}
+	
+	//		with_path_matcher													
+	/// Sets the matcher used to find endpoints by path. By default, paths are
+	/// normalised as described by [`MockPathMatcher::new()`], so that `./app.sock`
+	/// and `app.sock` are the same socket.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `matcher` - The matcher to use, such as [`MockPathMatcher::exact()`]
+	///   to turn normalisation off.
+	/// 
+	#[must_use]
+	pub const fn with_path_matcher(mut self, matcher: MockPathMatcher) -> Self {
+		self.paths = matcher;
+		self
+	}
+	
+	//		bind																
+	/// Binds a listener to the given path, owned by a process with the default
+	/// credentials.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `path` - The path to bind to.
+	/// 
+	/// # Errors
+	/// 
+	/// An error of kind [`AddrInUse`](ErrorKind::AddrInUse) will be returned if
+	/// the path is already bound, even if its listener has since been closed.
+	/// 
+	/// # See also
+	/// 
+	/// * [`std::os::unix::net::UnixListener::bind()`]
+	/// 
+	pub fn bind<P: AsRef<Path>>(&self, path: P) -> IoResult<MockUnixListener> {
+		self.bind_as(path, MockPeerCred::default())
+	}
+	
+	//		bind_as																
+	/// Binds a listener to the given path, owned by a process with the given
+	/// credentials.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `path` - The path to bind to.
+	/// * `cred` - The credentials of the owning process, as seen by clients.
+	/// 
+	/// # Errors
+	/// 
+	/// An error of kind [`AddrInUse`](ErrorKind::AddrInUse) will be returned if
+	/// the path is already bound, even if its listener has since been closed.
+	/// 
+	pub fn bind_as<P: AsRef<Path>>(&self, path: P, cred: MockPeerCred) -> IoResult<MockUnixListener> {
+		let state = Arc::new(Shared::new(ListenerState { closed: false, cred, pending: VecDeque::new(), waker: None }));
+		self.insert(path.as_ref(), Endpoint::Listener(Arc::clone(&state)))?;
+		Ok(MockUnixListener { path: path.as_ref().to_path_buf(), state })
+	}
+	
+	//		connect																
+	/// Connects to the given path, as a process with the default credentials.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `path` - The path to connect to.
+	/// 
+	/// # Errors
+	/// 
+	/// An error of kind [`NotFound`](ErrorKind::NotFound) will be returned if
+	/// nothing is bound to the path, or of kind
+	/// [`ConnectionRefused`](ErrorKind::ConnectionRefused) if its listener has
+	/// been closed.
+	/// 
+	/// # See also
+	/// 
+	/// * [`std::os::unix::net::UnixStream::connect()`]
+	/// * `tokio::net::UnixStream::connect()`
+	/// 
+	pub fn connect<P: AsRef<Path>>(&self, path: P) -> IoResult<MockUnixStream> {
+		self.connect_as(path, MockPeerCred::default())
+	}
+	
+	//		connect_as															
+	/// Connects to the given path, as a process with the given credentials.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `path` - The path to connect to.
+	/// * `cred` - The credentials of the connecting process, as seen by the
+	///   listener.
+	/// 
+	/// # Errors
+	/// 
+	/// An error of kind [`NotFound`](ErrorKind::NotFound) will be returned if
+	/// nothing is bound to the path, or of kind
+	/// [`ConnectionRefused`](ErrorKind::ConnectionRefused) if its listener has
+	/// been closed. If a [`FaultPlan`] is attached, an injected fault will be
+	/// returned as the corresponding I/O error.
+	/// 
+	/// The connection and its outcome are recorded with the
+	/// [`Context`](MockContext) entered on the current thread, if there is one.
+	/// 
+	pub fn connect_as<P: AsRef<Path>>(&self, path: P, cred: MockPeerCred) -> IoResult<MockUnixStream> {
+		let result = self.establish(path.as_ref(), cred);
+		if let Some(context) = MockContext::current() {
+			let outcome = result.as_ref().map_or_else(ToString::to_string, |_| "connected".to_owned());
+			context.record("unix", &format!("connect {}", path.as_ref().display()), &outcome);
+		}
+		result
+	}
+	
+	//		exists																
+	/// Returns whether anything is bound to the given path, including a closed
+	/// listener.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `path` - The path to check.
+	/// 
+	#[must_use]
+	pub fn exists<P: AsRef<Path>>(&self, path: P) -> bool {
+		let endpoints = self.lock();
+		self.key(&endpoints, path.as_ref()).is_some()
+	}
+	
+	//		remove																
+	/// Removes whatever is bound to the given path, as unlinking the socket
+	/// file would, returning whether there was anything. Existing connections
+	/// are not affected.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `path` - The path to remove.
+	/// 
+	pub fn remove<P: AsRef<Path>>(&self, path: P) -> bool {
+		let mut endpoints = self.lock();
+		self.key(&endpoints, path.as_ref()).and_then(|key| endpoints.remove(&key)).is_some()
+	}
+	
+	//		serve																
+	/// Binds a scripted peer to the given path. Each connection made to the
+	/// path follows the script of the peer from the start.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `path` - The path to bind to.
+	/// * `peer` - The scripted peer.
+	/// 
+	/// # Errors
+	/// 
+	/// An error of kind [`AddrInUse`](ErrorKind::AddrInUse) will be returned if
+	/// the path is already bound.
+	/// 
+	pub fn serve<P: AsRef<Path>>(&self, path: P, peer: MockUnixPeer) -> IoResult<()> {
+		self.insert(path.as_ref(), Endpoint::Peer(peer))
+	}
+	
+	//		establish															
+	/// Connects to the given path, applying any attached [`FaultPlan`].
+	fn establish(&self, path: &Path, cred: MockPeerCred) -> IoResult<MockUnixStream> {
+		if let Some(ref plan) = self.faults {
+			let fault = plan.draw();
+			if !fault.latency.is_zero() {
+				thread::sleep(fault.latency);
+			}
+			if let Some(kind) = fault.kind {
+				return Err(kind.io_error());
+			}
+		}
+		let endpoints = self.lock();
+		let endpoint  = self.key(&endpoints, path).and_then(|key| endpoints.get(&key).cloned()).ok_or_else(|| IoError::from(ErrorKind::NotFound))?;
+		drop(endpoints);
+		match endpoint {
+			Endpoint::Listener(listener) => {
+				let mut state = listener.lock();
+				if state.closed {
+					return Err(ErrorKind::ConnectionRefused.into());
+				}
+				let (client, server) = MockUnixStream::pair_as(cred, state.cred);
+				state.pending.push_back(server);
+				if let Some(waker) = state.waker.take() {
+					waker.wake();
+				}
+				drop(state);
+				listener.condvar.notify_all();
+				Ok(client)
+			},
+			Endpoint::Peer(peer)         => Ok(MockUnixStream::scripted(&peer)),
+		}
+	}
+	
+	//		insert																
+	/// Binds an endpoint to a path that is not already bound.
+	fn insert(&self, path: &Path, endpoint: Endpoint) -> IoResult<()> {
+		let mut endpoints = self.lock();
+		if self.key(&endpoints, path).is_some() {
+			return Err(ErrorKind::AddrInUse.into());
+		}
+		drop(endpoints.insert(path.to_path_buf(), endpoint));
+		drop(endpoints);
+		Ok(())
+	}
+	
+	//		key																	
+	/// Returns the path under which the endpoint matching the given path is
+	/// bound, if any.
+	fn key(&self, endpoints: &HashMap<PathBuf, Endpoint>, path: &Path) -> Option<PathBuf> {
+		endpoints.keys().find(|&key| self.paths.matches(key, path)).cloned()
+	}
+	
+	//		lock																
+	/// Locks the endpoints.
+	fn lock(&self) -> MutexGuard<'_, HashMap<PathBuf, Endpoint>> {
+		self.endpoints.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+}
+
+//		MockUnixStream															
+/// A mocked Unix domain socket stream.
+/// 
+/// Dropping the stream closes the connection, so that the other end reads the
+/// end of the stream, and fails to write with
+/// [`BrokenPipe`](ErrorKind::BrokenPipe).
+/// 
+/// # See also
+/// 
+/// * [`std::os::unix::net::UnixStream`]
+/// * `tokio::net::UnixStream`
+/// 
+#[derive(Debug)]
+pub struct MockUnixStream {
+	//		Private properties													
+	/// The data sent by the other end.
+	incoming:     Arc<Shared<Pipe>>,
+	
+	/// Whether reads return [`WouldBlock`](ErrorKind::WouldBlock) instead of
+	/// blocking.
+	nonblocking:  AtomicBool,
+	
+	/// The data sent to the other end.
+	outgoing:     Arc<Shared<Pipe>>,
+	
+	/// The credentials of the other end.
+	peer_cred:    MockPeerCred,
+	
+	/// How long a blocking read waits before failing, if limited.
+	read_timeout: Mutex<Option<Duration>>,
+	
+	/// The script followed by the other end, if it is a scripted peer.
+	script:       Option<Mutex<Script>>,
+}
+
+//󰭅		MockUnixStream															
+impl MockUnixStream {
+	//		pair																
+	/// Creates a connected pair of streams, both with the default credentials.
+	/// 
+	/// # See also
+	/// 
+	/// * [`std::os::unix::net::UnixStream::pair()`]
+	/// * `tokio::net::UnixStream::pair()`
+	/// 
+	#[must_use]
+	pub fn pair() -> (Self, Self) {
+		Self::pair_as(MockPeerCred::default(), MockPeerCred::default())
+	}
+	
+	//		pair_as																
+	/// Creates a connected pair of streams with the given credentials.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `first`  - The credentials of the process holding the first stream.
+	/// * `second` - The credentials of the process holding the second stream.
+	/// 
+	#[must_use]
+	pub fn pair_as(first: MockPeerCred, second: MockPeerCred) -> (Self, Self) {
+		let forward  = Arc::new(Shared::new(Pipe::default()));
+		let backward = Arc::new(Shared::new(Pipe::default()));
+		(
+			Self::new(Arc::clone(&backward), Arc::clone(&forward), second, None),
+			Self::new(forward, backward, first, None),
+		)
+	}
+	
+	//		peer_cred															
+	/// Returns the credentials of the process at the other end.
+	/// 
+	/// # Errors
+	/// 
+	/// This function does not currently return an error, but the signature
+	/// matches that of the "real" code.
+	/// 
+	/// # See also
+	/// 
+	/// * `std::os::unix::net::UnixStream::peer_cred()`
+	/// * `tokio::net::UnixStream::peer_cred()`
+	/// 
+	pub const fn peer_cred(&self) -> IoResult<MockPeerCred> {
+		Ok(self.peer_cred)
+	}
+	
+	//		set_nonblocking														
+	/// Sets whether reads return [`WouldBlock`](ErrorKind::WouldBlock) when no
+	/// data is available, instead of blocking.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `nonblocking` - Whether to enable non-blocking mode.
+	/// 
+	/// # Errors
+	/// 
+	/// This function does not currently return an error, but the signature
+	/// matches that of the "real" code.
+	/// 
+	/// # See also
+	/// 
+	/// * [`std::os::unix::net::UnixStream::set_nonblocking()`]
+	/// 
+	pub fn set_nonblocking(&self, nonblocking: bool) -> IoResult<()> {
+		self.nonblocking.store(nonblocking, Ordering::SeqCst);
+		Ok(())
+	}
+	
+	//		set_read_timeout													
+	/// Sets how long a blocking read waits for data before failing with
+	/// [`WouldBlock`](ErrorKind::WouldBlock), or removes the limit if [`None`].
+	/// 
+	/// # Parameters
+	/// 
+	/// * `timeout` - The timeout.
+	/// 
+	/// # Errors
+	/// 
+	/// This function does not currently return an error, but the signature
+	/// matches that of the "real" code.
+	/// 
+	/// # See also
+	/// 
+	/// * [`std::os::unix::net::UnixStream::set_read_timeout()`]
+	/// 
+	pub fn set_read_timeout(&self, timeout: Option<Duration>) -> IoResult<()> {
+		*self.read_timeout.lock().unwrap_or_else(PoisonError::into_inner) = timeout;
+		Ok(())
+	}
+	
+	//		shutdown															
+	/// Shuts down the reading half, the writing half, or both halves of the
+	/// connection.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `how` - Which halves to shut down.
+	/// 
+	/// # Errors
+	/// 
+	/// This function does not currently return an error, but the signature
+	/// matches that of the "real" code.
+	/// 
+	/// # See also
+	/// 
+	/// * [`std::os::unix::net::UnixStream::shutdown()`]
+	/// 
+	pub fn shutdown(&self, how: Shutdown) -> IoResult<()> {
+		if matches!(how, Shutdown::Read | Shutdown::Both) {
+			self.incoming.update(|pipe| pipe.reader_closed = true);
+		}
+		if matches!(how, Shutdown::Write | Shutdown::Both) {
+			self.outgoing.update(|pipe| pipe.writer_closed = true);
+		}
+		Ok(())
+	}
+	
+	//		new																	
+	/// Creates a stream from its parts.
+	fn new(incoming: Arc<Shared<Pipe>>, outgoing: Arc<Shared<Pipe>>, peer_cred: MockPeerCred, script: Option<Script>) -> Self {
+		Self {
+			incoming,
+			nonblocking:  AtomicBool::new(false),
+			outgoing,
+			peer_cred,
+			read_timeout: Mutex::new(None),
+			script:       script.map(Mutex::new),
+		}
+	}
+	
+	//		scripted															
+	/// Creates a stream connected to a scripted peer.
+	fn scripted(peer: &MockUnixPeer) -> Self {
+		let incoming = Arc::new(Shared::new(Pipe { scripted: true, ..Default::default() }));
+		let script   = Script { received: Arc::clone(&peer.received), steps: peer.steps.iter().cloned().collect() };
+		let stream   = Self::new(incoming, Arc::new(Shared::new(Pipe::default())), peer.cred, Some(script));
+		stream.advance(&[]);
+		stream
+	}
+	
+	//		advance																
+	/// Passes data written by the client to the scripted peer, if any, and
+	/// carries out the steps of the script that are then ready.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the data does not match what the script
+	/// expects.
+	/// 
+	fn advance(&self, data: &[u8]) {
+		let Some(ref script_lock) = self.script else {
+			return;
+		};
+		let mut script = script_lock.lock().unwrap_or_else(PoisonError::into_inner);
+		script.received.lock().unwrap_or_else(PoisonError::into_inner).extend_from_slice(data);
+		let mut replies = Vec::new();
+		let mut close   = false;
+		let mut pending = self.outgoing.lock();
+		pending.buffer.extend(data);
+		while let Some(step) = script.steps.front() {
+			match *step {
+				Step::Close                => close = true,
+				Step::Expect(ref expected) => {
+					let available = pending.buffer.len().min(expected.len());
+					let matches   = pending.buffer.iter().zip(expected).all(|(actual, wanted)| actual == wanted);
+					assert!(matches, "Unexpected data sent to scripted peer: expected {:?}", String::from_utf8_lossy(expected));
+					if available < expected.len() {
+						break;
+					}
+					drop(pending.buffer.drain(..available));
+				},
+				Step::Reply(ref reply)     => replies.push(reply.clone()),
+			}
+			drop(script.steps.pop_front());
+			if close {
+				break;
+			}
+		}
+		drop(pending);
+		let finished = close || script.steps.is_empty();
+		drop(script);
+		self.incoming.update(|pipe| {
+			for reply in replies {
+				pipe.buffer.extend(reply);
+			}
+			pipe.writer_closed |= finished;
+		});
+	}
+	
+	//		poll_incoming														
+	/// Takes up to the given number of bytes of incoming data, if any is
+	/// available, returning [`None`] if the read would block.
+	fn poll_incoming(&self, pipe: &mut Pipe, max: usize) -> IoResult<Option<Vec<u8>>> {
+		if pipe.reader_closed {
+			return Ok(Some(Vec::new()));
+		}
+		if !pipe.buffer.is_empty() {
+			let count = pipe.buffer.len().min(max);
+			return Ok(Some(pipe.buffer.drain(..count).collect()));
+		}
+		if pipe.writer_closed {
+			return Ok(Some(Vec::new()));
+		}
+		if pipe.scripted {
+			return Err(IoError::new(ErrorKind::TimedOut, "Scripted peer is waiting for a request"));
+		}
+		if self.nonblocking.load(Ordering::SeqCst) {
+			return Err(ErrorKind::WouldBlock.into());
+		}
+		Ok(None)
+	}
+	
+	//		write_outgoing														
+	/// Writes data to the other end.
+	fn write_outgoing(&self, data: &[u8]) -> IoResult<usize> {
+		if self.script.is_some() {
+			if self.outgoing.lock().writer_closed {
+				return Err(ErrorKind::BrokenPipe.into());
+			}
+			self.advance(data);
+			return Ok(data.len());
+		}
+		let mut pipe = self.outgoing.lock();
+		if pipe.writer_closed || pipe.reader_closed {
+			return Err(ErrorKind::BrokenPipe.into());
+		}
+		pipe.buffer.extend(data);
+		let waker    = pipe.waker.take();
+		drop(pipe);
+		self.outgoing.condvar.notify_all();
+		if let Some(task) = waker {
+			task.wake();
+		}
+		Ok(data.len())
+	}
+}
+
+//󰭅		AsyncRead																
+#[cfg(feature = "tokio")]
+impl AsyncRead for MockUnixStream {
+	//		poll_read															
+	fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<IoResult<()>> {
+		let mut pipe = self.incoming.lock();
+		match self.poll_incoming(&mut pipe, buf.remaining()) {
+			Ok(Some(data)) => {
+				buf.put_slice(&data);
+				Poll::Ready(Ok(()))
+			},
+			Ok(None)       => {
+				pipe.waker = Some(cx.waker().clone());
+				Poll::Pending
+			},
+			Err(err)       => Poll::Ready(Err(err)),
+		}
+	}
+}
+
+//󰭅		AsyncWrite																
+#[cfg(feature = "tokio")]
+impl AsyncWrite for MockUnixStream {
+	//		poll_write															
+	fn poll_write(self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &[u8]) -> Poll<IoResult<usize>> {
+		Poll::Ready(self.write_outgoing(buf))
+	}
+	
+	//		poll_flush															
+	fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+		Poll::Ready(Ok(()))
+	}
+	
+	//		poll_shutdown														
+	fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+		Poll::Ready(self.shutdown(Shutdown::Write))
+	}
+}
+
+//󰭅		FuturesAsyncRead														
+#[cfg(feature = "futures-io")]
+impl FuturesAsyncRead for MockUnixStream {
+	//		poll_read															
+	fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<IoResult<usize>> {
+		let mut pipe = self.incoming.lock();
+		match self.poll_incoming(&mut pipe, buf.len()) {
+			Ok(Some(data)) => {
+				let count = data.len();
+				buf.iter_mut().zip(data).for_each(|(slot, byte)| *slot = byte);
+				Poll::Ready(Ok(count))
+			},
+			Ok(None)       => {
+				pipe.waker = Some(cx.waker().clone());
+				Poll::Pending
+			},
+			Err(err)       => Poll::Ready(Err(err)),
+		}
+	}
+}
+
+//󰭅		FuturesAsyncWrite														
+#[cfg(feature = "futures-io")]
+impl FuturesAsyncWrite for MockUnixStream {
+	//		poll_write															
+	fn poll_write(self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &[u8]) -> Poll<IoResult<usize>> {
+		Poll::Ready(self.write_outgoing(buf))
+	}
+	
+	//		poll_flush															
+	fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+		Poll::Ready(Ok(()))
+	}
+	
+	//		poll_close															
+	fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+		Poll::Ready(self.shutdown(Shutdown::Write))
+	}
+}
+
+//󰭅		Drop																	
+impl Drop for MockUnixStream {
+	//		drop																
+	fn drop(&mut self) {
+		self.outgoing.update(|pipe| pipe.writer_closed = true);
+		self.incoming.update(|pipe| pipe.reader_closed = true);
+	}
+}
+
+//󰭅		Read																	
+impl Read for MockUnixStream {
+	//		read																
+	fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+		let timeout  = *self.read_timeout.lock().unwrap_or_else(PoisonError::into_inner);
+		let mut pipe = self.incoming.lock();
+		loop {
+			if let Some(data) = self.poll_incoming(&mut pipe, buf.len())? {
+				let count = data.len();
+				buf.iter_mut().zip(data).for_each(|(slot, byte)| *slot = byte);
+				return Ok(count);
+			}
+			if let Some(limit) = timeout {
+				let (guard, result) = self.incoming.condvar.wait_timeout(pipe, limit).unwrap_or_else(PoisonError::into_inner);
+				if result.timed_out() {
+					return Err(ErrorKind::WouldBlock.into());
+				}
+				pipe = guard;
+			} else {
+				pipe = self.incoming.condvar.wait(pipe).unwrap_or_else(PoisonError::into_inner);
+			}
+		}
+	}
+}
+
+//󰭅		Write																	
+impl Write for MockUnixStream {
+	//		write																
+	fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+		self.write_outgoing(buf)
+	}
+	
+	//		flush																
+	fn flush(&mut self) -> IoResult<()> {
+		Ok(())
+	}
+}
+
+//		Pipe																	
+/// One direction of a connection.
+#[derive(Debug, Default)]
+struct Pipe {
+	//		Private properties													
+	/// The data written and not yet read.
+	buffer:        VecDeque<u8>,
+	
+	/// Whether the reading end has been closed.
+	reader_closed: bool,
+	
+	/// Whether the data is written by a scripted peer.
+	scripted:      bool,
+	
+	/// The task waiting to read, if any.
+	waker:         Option<Waker>,
+	
+	/// Whether the writing end has been closed.
+	writer_closed: bool,
+}
+
+//		Script																	
+/// The progress of a connection through the script of a [`MockUnixPeer`].
+#[derive(Debug)]
+struct Script {
+	//		Private properties													
+	/// Everything received from clients, shared with the peer.
+	received: Arc<Mutex<Vec<u8>>>,
+	
+	/// The steps that remain.
+	steps:    VecDeque<Step>,
+}
+
+//		Shared																	
+/// State shared between threads, with a condition variable for waiting on
+/// changes.
+#[derive(Debug)]
+struct Shared<T> {
+	//		Private properties													
+	/// The condition variable, notified on every update.
+	condvar: Condvar,
+	
+	/// The state.
+	state:   Mutex<T>,
+}
+
+//󰭅		Shared																	
+impl<T> Shared<T> {
+	//		new																	
+	/// Creates a new shared state.
+	const fn new(state: T) -> Self {
+		Self { condvar: Condvar::new(), state: Mutex::new(state) }
+	}
+	
+	//		lock																
+	/// Locks the state.
+	fn lock(&self) -> MutexGuard<'_, T> {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+}
+
+//󰭅		Shared																	
+impl Shared<Pipe> {
+	//		update																
+	/// Modifies a pipe, and then wakes anything waiting to read from it.
+	fn update<F: FnOnce(&mut Pipe)>(&self, modify: F) {
+		let mut pipe = self.lock();
+		modify(&mut pipe);
+		let waker    = pipe.waker.take();
+		drop(pipe);
+		self.condvar.notify_all();
+		if let Some(task) = waker {
+			task.wake();
+		}
+	}
+}
@@ -0,0 +1,409 @@
+//! This module provides URL normalisation in order to match requests without
+//! false negatives.
+//! 
+//! This module provides [`MockUrlMatcher`], which decides whether two URLs
+//! refer to the same resource, and describes how they differ when they do not.
+//! This is important because comparing URLs as strings fails for URLs that are
+//! written differently but mean the same thing, such as `%7E` and `~`, an
+//! explicit default port, a trailing slash, or query parameters in a different
+//! order, which leads to tests that fail for reasons that have nothing to do
+//! with the code under test, or to assertions being loosened until they check
+//! very little.
+//! 
+//! The approach taken is that URLs are parsed, and then normalised before they
+//! are compared: the scheme and host are lower-cased, default ports are
+//! removed, `.` and `..` segments are resolved, each segment of the path is
+//! percent-decoded and then re-encoded in a canonical way, so that an encoded
+//! `/` is still distinguished from a separator, and the query parameters are
+//! decoded in the same way. By default, a trailing slash, the order of the
+//! query parameters, and the fragment are ignored, each of which can be made
+//! significant. When URLs do not match, the matcher describes the first
+//! difference, naming the part of the URL that differs. For tests that need to
+//! check the exact URLs used, [`MockUrlMatcher::exact()`] turns normalisation
+//! off.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use sham::url::MockUrlMatcher;
+//! 
+//! let matcher = MockUrlMatcher::new();
+//! assert!(matcher.matches(
+//!     "https://API.example.com:443/users/%7Ejo/?page=2&sort=name%20asc",
+//!     "https://api.example.com/users/~jo?sort=name+asc&page=2#top",
+//! ));
+//! assert!(!matcher.matches("https://api.example.com/a%2Fb", "https://api.example.com/a/b"));
+//! assert_eq!(
+//!     matcher.diff("https://api.example.com/users?page=2", "https://api.example.com/users?page=3").unwrap(),
+//!     "Expected query parameter `page=2`, which is missing, and found unexpected query parameter `page=3`",
+//! );
+//! assert_eq!(
+//!     matcher.normalise("HTTP://Example.com:80/a/./b/../c/?z=1&a=%41").unwrap(),
+//!     "http://example.com/a/c?a=A&z=1",
+//! );
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::reqwest::MockRequest;
+use core::{
+	fmt::Write,
+	str::from_utf8,
+};
+use reqwest::Url;
+
+
+
+//		Structs
+
+//		MockUrlMatcher															
+/// A matcher that decides whether two URLs refer to the same resource.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[expect(clippy::struct_excessive_bools, reason = "Each is an independent setting")]
+#[non_exhaustive]
+pub struct MockUrlMatcher {
+	//		Public properties													
+	/// Whether URLs are compared exactly as given, without normalisation.
+	pub exact:          bool,
+	
+	/// Whether the fragment is significant. By default, it is not.
+	pub fragment:       bool,
+	
+	/// Whether the order of the query parameters is significant. By default,
+	/// it is not.
+	pub query_order:    bool,
+	
+	/// Whether a trailing slash on the path is significant. By default, it is
+	/// not.
+	pub trailing_slash: bool,
+}
+
+//󰭅		MockUrlMatcher															
+impl MockUrlMatcher {
+	//		new																	
+	/// Creates a new matcher that normalises URLs, and ignores trailing
+	/// slashes, the order of query parameters, and fragments.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			exact:          false,
+			fragment:       false,
+			query_order:    false,
+			trailing_slash: false,
+		}
+	}
+	
+	//		exact																
+	/// Creates a new matcher that compares URLs exactly as given.
+	#[must_use]
+	pub const fn exact() -> Self {
+		Self {
+			exact:          true,
+			fragment:       true,
+			query_order:    true,
+			trailing_slash: true,
+		}
+	}
+	
+	//		with_significant_fragment											
+	/// Sets whether the fragment is significant.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `significant` - Whether to compare fragments.
+	/// 
+	#[must_use]
+	pub const fn with_significant_fragment(mut self, significant: bool) -> Self {
+		self.fragment = significant;
+		self
+	}
+	
+	//		with_significant_query_order										
+	/// Sets whether the order of the query parameters is significant.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `significant` - Whether to compare the order of query parameters.
+	/// 
+	#[must_use]
+	pub const fn with_significant_query_order(mut self, significant: bool) -> Self {
+		self.query_order = significant;
+		self
+	}
+	
+	//		with_significant_trailing_slash										
+	/// Sets whether a trailing slash on the path is significant.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `significant` - Whether to compare trailing slashes.
+	/// 
+	#[must_use]
+	pub const fn with_significant_trailing_slash(mut self, significant: bool) -> Self {
+		self.trailing_slash = significant;
+		self
+	}
+	
+	//		assert_matches														
+	/// Asserts that two URLs match.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `expected` - The expected URL.
+	/// * `actual`   - The actual URL.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the URLs do not match, describing the first
+	/// difference.
+	/// 
+	pub fn assert_matches(&self, expected: &str, actual: &str) {
+		if let Some(difference) = self.diff(expected, actual) {
+			panic!("URLs do not match. {difference}\nExpected: {expected}\nFound:    {actual}");
+		}
+	}
+	
+	//		assert_request_url													
+	/// Asserts that the URL of a request matches the expected URL.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `request`  - The request whose URL to check.
+	/// * `expected` - The expected URL.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the URLs do not match, describing the first
+	/// difference.
+	/// 
+	pub fn assert_request_url(&self, request: &MockRequest, expected: &str) {
+		self.assert_matches(expected, request.url.as_str());
+	}
+	
+	//		diff																
+	/// Describes the first difference between two URLs, if any.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `expected` - The expected URL.
+	/// * `actual`   - The actual URL.
+	/// 
+	#[must_use]
+	pub fn diff(&self, expected: &str, actual: &str) -> Option<String> {
+		if self.exact {
+			return (expected != actual).then(|| format!("Expected `{expected}`, found `{actual}`"));
+		}
+		let Some(wanted) = self.parts(expected) else {
+			return Some(format!("The expected URL `{expected}` is invalid"));
+		};
+		let Some(found)  = self.parts(actual) else {
+			return Some(format!("The actual URL `{actual}` is invalid"));
+		};
+		let components   = [
+			("scheme",    wanted.scheme.as_str(),    found.scheme.as_str()),
+			("authority", wanted.authority.as_str(), found.authority.as_str()),
+			("path",      wanted.path.as_str(),      found.path.as_str()),
+		];
+		if let Some(&(name, left, right)) = components.iter().find(|&&(_, left, right)| left != right) {
+			return Some(format!("Expected {name} `{left}`, found `{right}`"));
+		}
+		if wanted.query != found.query {
+			return Some(describe_query(&wanted.query, &found.query, self.query_order));
+		}
+		(wanted.fragment != found.fragment).then(|| format!(
+			"Expected fragment `{}`, found `{}`",
+			wanted.fragment.unwrap_or_default(),
+			found.fragment.unwrap_or_default(),
+		))
+	}
+	
+	//		matches																
+	/// Whether the given URLs refer to the same resource.
+	/// 
+	/// URLs that cannot be parsed only match if they are identical.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `left`  - The first URL.
+	/// * `right` - The second URL.
+	/// 
+	#[must_use]
+	pub fn matches(&self, left: &str, right: &str) -> bool {
+		left == right || self.diff(left, right).is_none()
+	}
+	
+	//		matches_request														
+	/// Whether the URL of a request matches the given URL.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `request`  - The request whose URL to check.
+	/// * `expected` - The expected URL.
+	/// 
+	#[must_use]
+	pub fn matches_request(&self, request: &MockRequest, expected: &str) -> bool {
+		self.matches(expected, request.url.as_str())
+	}
+	
+	//		normalise															
+	/// Normalises a URL for comparison, if it can be parsed.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `url` - The URL to normalise.
+	/// 
+	#[must_use]
+	pub fn normalise(&self, url: &str) -> Option<String> {
+		if self.exact {
+			return Some(url.to_owned());
+		}
+		let parts = self.parts(url)?;
+		let query = parts.query.iter().map(|parameter| format!("{}={}", parameter.0, parameter.1)).collect::<Vec<_>>().join("&");
+		Some(format!(
+			"{}://{}{}{}{}",
+			parts.scheme,
+			parts.authority,
+			parts.path,
+			if query.is_empty() { String::new() } else { format!("?{query}") },
+			parts.fragment.map(|fragment| format!("#{fragment}")).unwrap_or_default(),
+		))
+	}
+	
+	//		parts																
+	/// Parses a URL, and normalises its parts.
+	fn parts(self, url: &str) -> Option<UrlParts> {
+		let parsed        = Url::parse(url.trim()).ok()?;
+		let mut authority = String::new();
+		if !parsed.username().is_empty() {
+			authority.push_str(&canonical(parsed.username().as_bytes(), false));
+			if let Some(password) = parsed.password() {
+				authority.push(':');
+				authority.push_str(&canonical(password.as_bytes(), false));
+			}
+			authority.push('@');
+		}
+		authority.push_str(parsed.host_str().unwrap_or_default());
+		if let Some(port) = parsed.port() {
+			_ = write!(authority, ":{port}");
+		}
+		let path          = parsed.path_segments().map_or_else(
+			|| canonical(&decode(parsed.path()), false),
+			|segments| {
+				let mut canonical_segments = segments.map(|segment| canonical(&decode(segment), false)).collect::<Vec<_>>();
+				if !self.trailing_slash && canonical_segments.last().is_some_and(String::is_empty) {
+					drop(canonical_segments.pop());
+				}
+				format!("/{}", canonical_segments.join("/"))
+			},
+		);
+		let mut query     = parsed.query_pairs()
+			.map(|(key, value)| (canonical(key.as_bytes(), true), canonical(value.as_bytes(), true)))
+			.collect::<Vec<_>>()
+		;
+		if !self.query_order {
+			query.sort();
+		}
+		Some(UrlParts {
+			authority,
+			fragment: parsed.fragment().filter(|_| self.fragment).map(|fragment| canonical(&decode(fragment), false)),
+			path,
+			query,
+			scheme:   parsed.scheme().to_owned(),
+		})
+	}
+}
+
+//󰭅		Default																	
+impl Default for MockUrlMatcher {
+	//		default																
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+//		UrlParts																
+/// The normalised parts of a URL.
+#[derive(Debug, Eq, PartialEq)]
+struct UrlParts {
+	/// The user information, host, and port, if not the default.
+	authority: String,
+	
+	/// The fragment, if it is significant and present.
+	fragment:  Option<String>,
+	
+	/// The path.
+	path:      String,
+	
+	/// The query parameters, as keys and values.
+	query:     Vec<(String, String)>,
+	
+	/// The scheme.
+	scheme:    String,
+}
+
+
+
+//		Functions
+
+//		canonical																
+/// Percent-encodes decoded bytes in a canonical way, leaving only the
+/// characters that never need encoding, with upper-case hex digits.
+/// 
+/// Within a query component, the characters that separate parameters are also
+/// encoded.
+fn canonical(bytes: &[u8], query: bool) -> String {
+	let mut encoded = String::with_capacity(bytes.len());
+	for &byte in bytes {
+		let plain = byte.is_ascii_alphanumeric()
+			|| matches!(byte, b'-' | b'.' | b'_' | b'~' | b'!' | b'$' | b'\'' | b'(' | b')' | b'*' | b',' | b';' | b':' | b'@')
+			|| (!query && matches!(byte, b'&' | b'+' | b'='))
+		;
+		if plain {
+			encoded.push(char::from(byte));
+		} else {
+			_ = write!(encoded, "%{byte:02X}");
+		}
+	}
+	encoded
+}
+
+//		decode																	
+/// Percent-decodes a component of a URL. Sequences that are not valid are
+/// left as they are.
+fn decode(text: &str) -> Vec<u8> {
+	let bytes       = text.as_bytes();
+	let mut decoded = Vec::with_capacity(bytes.len());
+	let mut index   = 0;
+	while let Some(&byte) = bytes.get(index) {
+		let hex = bytes.get(index.saturating_add(1)..index.saturating_add(3))
+			.and_then(|digits| from_utf8(digits).ok())
+			.filter(|_| byte == b'%')
+			.and_then(|digits| u8::from_str_radix(digits, 16).ok())
+		;
+		if let Some(value) = hex {
+			decoded.push(value);
+			index = index.saturating_add(3);
+		} else {
+			decoded.push(byte);
+			index = index.saturating_add(1);
+		}
+	}
+	decoded
+}
+
+//		describe_query															
+/// Describes how two sets of query parameters differ.
+fn describe_query(expected: &[(String, String)], actual: &[(String, String)], ordered: bool) -> String {
+	let missing    = expected.iter().find(|&parameter| !actual.contains(parameter)).map(|parameter| format!("{}={}", parameter.0, parameter.1));
+	let unexpected = actual.iter().find(|&parameter| !expected.contains(parameter)).map(|parameter| format!("{}={}", parameter.0, parameter.1));
+	match (missing, unexpected) {
+		(Some(wanted), Some(found)) => format!("Expected query parameter `{wanted}`, which is missing, and found unexpected query parameter `{found}`"),
+		(Some(wanted), None)        => format!("Expected query parameter `{wanted}`, which is missing"),
+		(None, Some(found))         => format!("Found unexpected query parameter `{found}`"),
+		(None, None) if ordered     => "Expected the query parameters in a different order".to_owned(),
+		(None, None)                => "Expected the query parameters a different number of times".to_owned(),
+	}
+}
@@ -0,0 +1,490 @@
+//! This module provides signed webhook fixtures in order to test webhooks.
+//! 
+//! This module provides a [`MockWebhookSigner`] that produces correctly signed,
+//! and deliberately mis-signed, webhook payloads for the common HMAC-SHA256
+//! signature schemes, i.e. those used by [GitHub](https://docs.github.com/en/webhooks/using-webhooks/validating-webhook-deliveries),
+//! [Slack](https://api.slack.com/authentication/verifying-requests-from-slack),
+//! [Stripe](https://docs.stripe.com/webhooks#verify-manually), and the
+//! [Standard Webhooks](https://www.standardwebhooks.com/) specification. It can
+//! also verify deliveries, and serve a route for the [`reqwest`](crate::reqwest)
+//! mock that does so. This is important because signature handling is easy to
+//! get subtly wrong, such as by verifying a re-serialised body or ignoring the
+//! timestamp, and hand-computing signatures in every test is tedious.
+//! 
+//! The approach taken depends upon which side is being tested. To test a
+//! webhook receiver, the test code uses [`sign()`](MockWebhookSigner::sign())
+//! or [`sign_with_fault()`](MockWebhookSigner::sign_with_fault()) to create a
+//! delivery, and passes its headers and payload to the receiver. To test a
+//! webhook sender, the test code serves the [`route()`](MockWebhookSigner::route())
+//! for the destination URL, lets the sender deliver to it, and then checks the
+//! [`deliveries()`](MockWebhookSigner::deliveries()). Timestamps come from a
+//! [`MockClock`], so that expiry can be tested as well.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use futures_executor::block_on;
+//! use sham::{
+//!     clock::MockClock,
+//!     reqwest::create_routed_mock_client,
+//!     webhook::{MockSignatureError, MockSignatureFault, MockWebhookScheme, MockWebhookSigner},
+//! };
+//! 
+//! let signer = MockWebhookSigner::new(MockWebhookScheme::GitHub, "It's a Secret to Everybody", &MockClock::new());
+//! let good   = signer.sign(r#"{"action":"opened"}"#);
+//! let bad    = signer.sign_with_fault(r#"{"action":"opened"}"#, MockSignatureFault::Tampered);
+//! 
+//! assert_eq!(signer.verify(good.payload.as_bytes(), &good.headers), Ok(()));
+//! assert_eq!(signer.verify(bad.payload.as_bytes(),  &bad.headers),  Err(MockSignatureError::Mismatch));
+//! 
+//! let client = create_routed_mock_client(vec![signer.route("https://example.com/hooks")]);
+//! block_on(async {
+//!     let response = client.post("https://example.com/hooks")
+//!         .headers(bad.headers.clone())
+//!         .body(bad.payload.clone())
+//!         .send().await.unwrap()
+//!     ;
+//!     assert_eq!(response.status(), 401);
+//! });
+//! assert_eq!(signer.deliveries()[0].verified, Err(MockSignatureError::Mismatch));
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::{
+	clock::MockClock,
+	reqwest::{
+		MockError,
+		MockRequest,
+		MockResponse,
+		MockRoute,
+		create_mock_response,
+	},
+};
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use core::{
+	error::Error,
+	fmt::{Display, Formatter, self},
+	time::Duration,
+};
+use hmac::{Hmac, Mac};
+use reqwest::{
+	IntoUrl,
+	Method,
+	StatusCode,
+	header::{HeaderMap, HeaderName, HeaderValue},
+};
+use sha2::Sha256;
+use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+
+
+//		Enums
+
+//		MockSignatureError														
+/// A webhook signature verification error type.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockSignatureError {
+	/// The signature is valid, but the timestamp is outside of the tolerance.
+	Expired,
+	
+	/// The signature or timestamp headers cannot be parsed.
+	Malformed,
+	
+	/// The signature does not match the payload.
+	Mismatch,
+	
+	/// The signature or timestamp headers are missing.
+	Missing,
+}
+
+//󰭅		Display																	
+impl Display for MockSignatureError {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match *self {
+			Self::Expired   => write!(f, "Webhook timestamp is outside of the tolerance"),
+			Self::Malformed => write!(f, "Webhook signature is malformed"),
+			Self::Mismatch  => write!(f, "Webhook signature does not match the payload"),
+			Self::Missing   => write!(f, "Webhook signature is missing"),
+		}
+	}
+}
+
+//󰭅		Error																	
+impl Error for MockSignatureError {}
+
+//		MockSignatureFault														
+/// A deliberate fault in a webhook signature.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockSignatureFault {
+	/// The signature is valid, but the timestamp is ten minutes old. For the
+	/// GitHub scheme, which has no timestamp, the signature is simply valid.
+	Expired,
+	
+	/// The signature was made with a different secret.
+	InvalidSignature,
+	
+	/// The signature header cannot be parsed.
+	Malformed,
+	
+	/// There is no signature header.
+	Missing,
+	
+	/// The payload was altered after signing, as happens when a body is parsed
+	/// and re-serialised before verification.
+	Tampered,
+}
+
+//		MockWebhookScheme														
+/// A webhook signature scheme. All of these use HMAC-SHA256.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockWebhookScheme {
+	/// The GitHub scheme, which signs the payload alone and sends a hex
+	/// signature in `X-Hub-Signature-256: sha256=…`.
+	GitHub,
+	
+	/// The Slack scheme, which signs `v0:{timestamp}:{payload}` and sends the
+	/// timestamp in `X-Slack-Request-Timestamp` and a hex signature in
+	/// `X-Slack-Signature: v0=…`.
+	Slack,
+	
+	/// The Standard Webhooks scheme, which signs `{id}.{timestamp}.{payload}`
+	/// with the base64-decoded secret, after any `whsec_` prefix, and sends
+	/// `webhook-id`, `webhook-timestamp`, and a base64 signature in
+	/// `webhook-signature: v1,…`.
+	StandardWebhooks,
+	
+	/// The Stripe scheme, which signs `{timestamp}.{payload}` and sends both
+	/// in `Stripe-Signature: t=…,v1=…`.
+	Stripe,
+}
+
+
+
+//		Structs
+
+//		MockSignedWebhook														
+/// A webhook delivery, as it would be sent to a webhook receiver.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockSignedWebhook {
+	//		Public properties													
+	/// The headers of the delivery, including the signature.
+	pub headers: HeaderMap,
+	
+	/// The payload, i.e. the request body.
+	pub payload: String,
+}
+
+//		MockWebhookDelivery														
+/// A record of a delivery received by the [`route()`](MockWebhookSigner::route()).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockWebhookDelivery {
+	//		Public properties													
+	/// The headers of the delivery.
+	pub headers:  HeaderMap,
+	
+	/// The payload, i.e. the request body.
+	pub payload:  Vec<u8>,
+	
+	/// The outcome of verifying the signature.
+	pub verified: Result<(), MockSignatureError>,
+}
+
+//		MockWebhookSigner														
+/// A signer and verifier of webhook deliveries.
+/// 
+/// Clones share the same record of deliveries, so a clone can be used to serve
+/// the route while the original is used to make assertions.
+/// 
+#[derive(Clone, Debug)]
+pub struct MockWebhookSigner {
+	//		Private properties													
+	/// The clock used for timestamps.
+	clock:     MockClock,
+	
+	/// The scheme used to sign and verify.
+	scheme:    MockWebhookScheme,
+	
+	/// The shared secret.
+	secret:    String,
+	
+	/// The state of the signer, shared between clones.
+	state:     Arc<Mutex<SignerState>>,
+	
+	/// How far a timestamp may be from the current time and still be accepted.
+	tolerance: Duration,
+}
+
+//󰭅		MockWebhookSigner														
+impl MockWebhookSigner {
+	//		new																	
+	/// Creates a new signer, with a tolerance of five minutes.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `scheme` - The signature scheme.
+	/// * `secret` - The shared secret.
+	/// * `clock`  - The clock used for timestamps.
+	/// 
+	#[must_use]
+	pub fn new(scheme: MockWebhookScheme, secret: &str, clock: &MockClock) -> Self {
+		Self {
+			clock:     clock.clone(),
+			scheme,
+			secret:    secret.to_owned(),
+			state:     Arc::new(Mutex::new(SignerState { deliveries: Vec::new(), next_id: 0 })),
+			tolerance: Duration::from_secs(300),
+		}
+	}
+	
+	//		with_tolerance														
+	/// Sets how far a timestamp may be from the current time and still be
+	/// accepted when verifying.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `tolerance` - The tolerance.
+	/// 
+	#[must_use]
+	pub const fn with_tolerance(mut self, tolerance: Duration) -> Self {
+		self.tolerance = tolerance;
+		self
+	}
+	
+	//		deliveries															
+	/// Returns the deliveries received by the [`route()`](MockWebhookSigner::route()),
+	/// in order.
+	#[must_use]
+	pub fn deliveries(&self) -> Vec<MockWebhookDelivery> {
+		self.lock().deliveries.clone()
+	}
+	
+	//		route																
+	/// Returns a route that receives deliveries by `POST` to the given URL,
+	/// verifies them, and records them. Verified deliveries receive a
+	/// `204 No Content` response, and others receive `401 Unauthorized`.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `url` - The URL to receive deliveries at.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the URL is not valid.
+	/// 
+	pub fn route<U: IntoUrl>(&self, url: U) -> MockRoute {
+		let signer = self.clone();
+		MockRoute::new(Method::POST, url, move |request| signer.receive(request))
+	}
+	
+	//		sign																
+	/// Creates a correctly signed delivery of the given payload.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `payload` - The payload to deliver.
+	/// 
+	#[must_use]
+	pub fn sign(&self, payload: &str) -> MockSignedWebhook {
+		self.create(payload, None)
+	}
+	
+	//		sign_with_fault														
+	/// Creates a delivery of the given payload with a deliberate fault in its
+	/// signature.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `payload` - The payload to deliver.
+	/// * `fault`   - The fault to introduce.
+	/// 
+	#[must_use]
+	pub fn sign_with_fault(&self, payload: &str, fault: MockSignatureFault) -> MockSignedWebhook {
+		self.create(payload, Some(fault))
+	}
+	
+	//		verify																
+	/// Verifies the signature of a delivery.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `payload` - The payload, exactly as received.
+	/// * `headers` - The headers of the delivery.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if the signature is missing, malformed, or
+	/// does not match the payload, or if the timestamp is outside of the
+	/// tolerance.
+	/// 
+	pub fn verify(&self, payload: &[u8], headers: &HeaderMap) -> Result<(), MockSignatureError> {
+		let header     = |name: &str| headers.get(name).map(|value| value.to_str().map_err(|_err| MockSignatureError::Malformed)).transpose();
+		let parse_time = |value: Option<&str>| value.ok_or(MockSignatureError::Missing)?.parse::<u64>().map_err(|_err| MockSignatureError::Malformed);
+		let (timestamp, id, signatures) = match self.scheme {
+			MockWebhookScheme::GitHub           => {
+				let value = header("x-hub-signature-256")?.ok_or(MockSignatureError::Missing)?;
+				(None, String::new(), vec![decode_hex(value.strip_prefix("sha256="))?])
+			},
+			MockWebhookScheme::Slack            => {
+				let value = header("x-slack-signature")?.ok_or(MockSignatureError::Missing)?;
+				(Some(parse_time(header("x-slack-request-timestamp")?)?), String::new(), vec![decode_hex(value.strip_prefix("v0="))?])
+			},
+			MockWebhookScheme::StandardWebhooks => {
+				let value      = header("webhook-signature")?.ok_or(MockSignatureError::Missing)?;
+				let id         = header("webhook-id")?.ok_or(MockSignatureError::Missing)?.to_owned();
+				let signatures = value.split(' ')
+					.filter_map(|part| part.strip_prefix("v1,"))
+					.map(|encoded| BASE64.decode(encoded).map_err(|_err| MockSignatureError::Malformed))
+					.collect::<Result<Vec<_>, _>>()?
+				;
+				(Some(parse_time(header("webhook-timestamp")?)?), id, signatures)
+			},
+			MockWebhookScheme::Stripe           => {
+				let value  = header("stripe-signature")?.ok_or(MockSignatureError::Missing)?;
+				let fields = value.split(',').filter_map(|part| part.split_once('=')).collect::<Vec<_>>();
+				let time   = fields.iter().find(|&&(key, _)| key == "t").map(|&(_, time)| time);
+				let signatures = fields.iter()
+					.filter(|&&(key, _)| key == "v1")
+					.map(|&(_, signature)| decode_hex(Some(signature)))
+					.collect::<Result<Vec<_>, _>>()?
+				;
+				(Some(parse_time(time).map_err(|_err| MockSignatureError::Malformed)?), String::new(), signatures)
+			},
+		};
+		if signatures.is_empty() {
+			return Err(MockSignatureError::Malformed);
+		}
+		let expected = self.mac(&self.secret, timestamp.unwrap_or_default(), &id, payload);
+		if !signatures.contains(&expected) {
+			return Err(MockSignatureError::Mismatch);
+		}
+		let now = self.clock.unix_timestamp();
+		if timestamp.is_some_and(|time| time.abs_diff(now) > self.tolerance.as_secs()) {
+			return Err(MockSignatureError::Expired);
+		}
+		Ok(())
+	}
+	
+	//		create																
+	/// Creates a delivery, with an optional fault.
+	fn create(&self, payload: &str, fault: Option<MockSignatureFault>) -> MockSignedWebhook {
+		let mut state  = self.lock();
+		state.next_id  = state.next_id.saturating_add(1);
+		let id         = format!("msg_{:024}", state.next_id);
+		drop(state);
+		let now        = self.clock.unix_timestamp();
+		let timestamp  = if fault == Some(MockSignatureFault::Expired) { now.saturating_sub(600) } else { now };
+		let secret     = if fault == Some(MockSignatureFault::InvalidSignature) { "not-the-secret" } else { &self.secret };
+		let mac        = self.mac(secret, timestamp, &id, payload.as_bytes());
+		let malformed  = fault == Some(MockSignatureFault::Malformed);
+		let (name, signature) = match self.scheme {
+			MockWebhookScheme::GitHub           => ("x-hub-signature-256", if malformed { "sha256=not-hex".to_owned() } else { format!("sha256={}", hex::encode(&mac)) }),
+			MockWebhookScheme::Slack            => ("x-slack-signature",   if malformed { "v0=not-hex".to_owned() }     else { format!("v0={}", hex::encode(&mac)) }),
+			MockWebhookScheme::StandardWebhooks => ("webhook-signature",   if malformed { "v1,not!base64".to_owned() }  else { format!("v1,{}", BASE64.encode(&mac)) }),
+			MockWebhookScheme::Stripe           => ("stripe-signature",    if malformed { "t=not-a-timestamp,v1=".to_owned() } else { format!("t={timestamp},v1={}", hex::encode(&mac)) }),
+		};
+		let mut headers = HeaderMap::new();
+		match self.scheme {
+			MockWebhookScheme::GitHub | MockWebhookScheme::Stripe => {},
+			MockWebhookScheme::Slack            => insert_header(&mut headers, "x-slack-request-timestamp", &timestamp.to_string()),
+			MockWebhookScheme::StandardWebhooks => {
+				insert_header(&mut headers, "webhook-id",        &id);
+				insert_header(&mut headers, "webhook-timestamp", &timestamp.to_string());
+			},
+		}
+		if fault != Some(MockSignatureFault::Missing) {
+			insert_header(&mut headers, name, &signature);
+		}
+		let delivered = if fault == Some(MockSignatureFault::Tampered) { format!("{payload} ") } else { payload.to_owned() };
+		MockSignedWebhook { headers, payload: delivered }
+	}
+	
+	//		lock																
+	/// Locks the shared state.
+	fn lock(&self) -> MutexGuard<'_, SignerState> {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+	
+	//		mac																	
+	/// Computes the raw signature of a payload according to the scheme.
+	fn mac(&self, secret: &str, timestamp: u64, id: &str, payload: &[u8]) -> Vec<u8> {
+		let key     = match self.scheme {
+			MockWebhookScheme::StandardWebhooks => BASE64.decode(secret.strip_prefix("whsec_").unwrap_or(secret)).unwrap_or_else(|_err| secret.as_bytes().to_vec()),
+			MockWebhookScheme::GitHub
+			| MockWebhookScheme::Slack
+			| MockWebhookScheme::Stripe         => secret.as_bytes().to_vec(),
+		};
+		let prefix  = match self.scheme {
+			MockWebhookScheme::GitHub           => String::new(),
+			MockWebhookScheme::Slack            => format!("v0:{timestamp}:"),
+			MockWebhookScheme::StandardWebhooks => format!("{id}.{timestamp}."),
+			MockWebhookScheme::Stripe           => format!("{timestamp}."),
+		};
+		let mut mac = Hmac::<Sha256>::new_from_slice(&key).expect("HMAC accepts keys of any length");
+		mac.update(prefix.as_bytes());
+		mac.update(payload);
+		mac.finalize().into_bytes().to_vec()
+	}
+	
+	//		receive																
+	/// Receives, verifies, and records a delivery.
+	#[expect(clippy::unnecessary_wraps, reason = "Needed for the route signature")]
+	fn receive(&self, request: &MockRequest) -> Result<MockResponse, MockError> {
+		let payload  = request.body.as_ref().map(|body| body.to_vec()).unwrap_or_default();
+		let verified = self.verify(&payload, &request.headers);
+		let status   = if verified.is_ok() { StatusCode::NO_CONTENT } else { StatusCode::UNAUTHORIZED };
+		let body     = verified.err().map(|error| error.to_string()).unwrap_or_default();
+		self.lock().deliveries.push(MockWebhookDelivery { headers: request.headers.clone(), payload, verified });
+		Ok(create_mock_response(
+			request.url.clone(),
+			status,
+			Some("text/plain"),
+			Some(body.len()),
+			HashMap::<String, String>::new(),
+			Ok(body.as_bytes()),
+		))
+	}
+}
+
+//		SignerState																
+/// The state of a [`MockWebhookSigner`].
+#[derive(Debug)]
+struct SignerState {
+	//		Private properties													
+	/// The deliveries received by the route.
+	deliveries: Vec<MockWebhookDelivery>,
+	
+	/// The number used to make the next message ID unique.
+	next_id:    u64,
+}
+
+
+
+//		Functions
+
+//		decode_hex																
+/// Decodes a hex signature, which must be present.
+fn decode_hex(value: Option<&str>) -> Result<Vec<u8>, MockSignatureError> {
+	hex::decode(value.ok_or(MockSignatureError::Malformed)?).map_err(|_err| MockSignatureError::Malformed)
+}
+
+//		insert_header															
+/// Inserts a header with a known-good name and value.
+fn insert_header(headers: &mut HeaderMap, name: &'static str, value: &str) {
+	drop(headers.insert(
+		HeaderName::from_static(name),
+		HeaderValue::from_str(value).expect("Webhook header values are always valid"),
+	));
+}
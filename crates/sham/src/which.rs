@@ -0,0 +1,300 @@
+//! This module mocks binary discovery in order to test feature detection.
+//! 
+//! This module provides a stand-in for the lookups of the
+//! [`which`](https://crates.io/crates/which) crate, which search the
+//! directories of the `PATH` variable for an executable with a given name.
+//! [`MockWhich`] holds a virtual `PATH` and a set of virtual executables, and
+//! finds binaries among them, or reports that they cannot be found. This is
+//! important because code that detects optional tools, such as checking
+//! whether `ffmpeg` is installed before offering video conversion, otherwise
+//! behaves differently depending upon what happens to be installed on the
+//! machine running the tests, and the branch for a missing tool is rarely
+//! tested at all.
+//! 
+//! The approach taken is that the "real" code should look binaries up through
+//! a finder that it is given, rather than calling the `which` crate directly,
+//! and the test code then supplies a [`MockWhich`]. The search follows the
+//! rules of the platform that is being simulated, rather than of the one that
+//! is running the tests: [`MockWhich::new()`] searches as Linux and macOS do,
+//! and [`MockWhich::windows()`] searches as Windows does, trying the extensions
+//! of `PATHEXT` and ignoring case. Paths are compared using a
+//! [`MockPathMatcher`]. Each lookup is recorded, along with its outcome, with
+//! the [`Context`] entered on the current thread, if any.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use sham::which::MockWhich;
+//! use std::path::PathBuf;
+//! 
+//! let which = MockWhich::new()
+//!     .with_path("/usr/local/bin:/usr/bin")
+//!     .with_binary("/usr/bin/ffmpeg")
+//! ;
+//! assert_eq!(which.which("ffmpeg").unwrap(), PathBuf::from("/usr/bin/ffmpeg"));
+//! assert!(which.which("convert").is_err());
+//! assert_eq!(which.lookups(), vec!["ffmpeg", "convert"]);
+//! 
+//! let which = MockWhich::windows()
+//!     .with_path(r"C:\Windows\System32;C:\Tools")
+//!     .with_executable(r"C:\Tools\FFmpeg.EXE")
+//! ;
+//! assert_eq!(which.which("ffmpeg").unwrap(), PathBuf::from(r"C:\Tools\FFmpeg.EXE"));
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::{
+	context::Context,
+	path::MockPathMatcher,
+};
+use core::{
+	error::Error,
+	fmt::{Display, Formatter, self},
+};
+use std::{
+	path::{Path, PathBuf},
+	sync::{Arc, Mutex, PoisonError},
+};
+
+
+
+//		Constants
+
+/// The extensions that Windows tries, in order, when looking for a binary
+/// whose name has none, as listed in the default `PATHEXT` variable.
+const WINDOWS_EXTENSIONS: [&str; 4] = [".COM", ".EXE", ".BAT", ".CMD"];
+
+
+
+//		Enums
+
+//		MockWhichError															
+/// An error when looking up a binary.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MockWhichError {
+	/// No executable with the given name was found.
+	CannotFindBinaryPath(String),
+}
+
+//󰭅		Display																	
+impl Display for MockWhichError {
+	//		fmt																	
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match *self {
+			Self::CannotFindBinaryPath(ref name) => write!(f, "Cannot find binary path for {name}"),
+		}
+	}
+}
+
+//󰭅		Error																	
+impl Error for MockWhichError {}
+
+
+
+//		Structs
+
+//		MockWhich																
+/// A virtual `PATH` and set of executables, which binaries are looked up in.
+/// 
+/// Clones share the record of the lookups made, so a finder can be cloned
+/// before it is given to the code under test in order to check it afterwards.
+/// 
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct MockWhich {
+	//		Private properties													
+	/// The executables that exist.
+	executables: Vec<PathBuf>,
+	
+	/// The extensions to try when a name has none, which is empty unless
+	/// Windows is being simulated.
+	extensions:  Vec<String>,
+	
+	/// The names looked up so far, in order, shared by the clones.
+	lookups:     Arc<Mutex<Vec<String>>>,
+	
+	/// The matcher used to compare paths.
+	matcher:     MockPathMatcher,
+	
+	/// The directories to search, in order.
+	path:        Vec<String>,
+	
+	/// The character that separates the directories of the `PATH` variable.
+	separator:   char,
+}
+
+//󰭅		MockWhich																
+impl MockWhich {
+	//		new																	
+	/// Creates a new finder that searches as Linux and macOS do, with an empty
+	/// `PATH`, and no executables.
+	pub fn new() -> Self {
+		Self {
+			executables: Vec::new(),
+			extensions:  Vec::new(),
+			lookups:     Arc::new(Mutex::new(Vec::new())),
+			matcher:     MockPathMatcher::new().with_case_sensitivity(true),
+			path:        Vec::new(),
+			separator:   ':',
+		}
+	}
+	
+	//		windows																
+	/// Creates a new finder that searches as Windows does, with an empty
+	/// `PATH`, and no executables.
+	/// 
+	/// Names are compared without regard to case, the directories of the
+	/// `PATH` are separated by `;`, and a name without an extension is tried
+	/// with each of the extensions of the default `PATHEXT` in turn, which are
+	/// `.COM`, `.EXE`, `.BAT`, and `.CMD`.
+	/// 
+	pub fn windows() -> Self {
+		Self {
+			extensions: WINDOWS_EXTENSIONS.iter().map(|&extension| extension.to_owned()).collect(),
+			matcher:    MockPathMatcher::new().with_case_sensitivity(false),
+			separator:  ';',
+			..Self::new()
+		}
+	}
+	
+	//		with_binary															
+	/// Adds an executable, and adds its directory to the end of the `PATH` if
+	/// it is not already there, so that it is found by name.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `location` - Where the executable is.
+	/// 
+	pub fn with_binary<P: AsRef<Path>>(mut self, location: P) -> Self {
+		let path = location.as_ref();
+		if let Some(parent) = path.parent().map(|parent| parent.to_string_lossy().into_owned()) {
+			if !parent.is_empty() && !self.path.iter().any(|entry| self.matcher.matches(entry, &parent)) {
+				self.path.push(parent);
+			}
+		}
+		self.with_executable(path)
+	}
+	
+	//		with_executable														
+	/// Adds an executable, without changing the `PATH`.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `path` - Where the executable is.
+	/// 
+	pub fn with_executable<P: AsRef<Path>>(mut self, path: P) -> Self {
+		self.executables.push(path.as_ref().to_path_buf());
+		self
+	}
+	
+	//		with_path															
+	/// Sets the `PATH`, replacing the directories already in it.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `value` - The value of the `PATH` variable, with the directories
+	///   separated as they are on the simulated platform. Empty entries are
+	///   ignored.
+	/// 
+	pub fn with_path(mut self, value: &str) -> Self {
+		self.path = value.split(self.separator).filter(|entry| !entry.is_empty()).map(ToOwned::to_owned).collect();
+		self
+	}
+	
+	//		lookups																
+	/// Returns the names looked up so far, in order.
+	#[must_use]
+	pub fn lookups(&self) -> Vec<String> {
+		self.lookups.lock().unwrap_or_else(PoisonError::into_inner).clone()
+	}
+	
+	//		which																
+	/// Finds the first executable with the given name.
+	/// 
+	/// A name that contains a path separator is checked as a path in its own
+	/// right, rather than being searched for in the `PATH`.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the binary.
+	/// 
+	/// # Errors
+	/// 
+	/// An error will be returned if there is no such executable.
+	/// 
+	/// # See also
+	/// 
+	/// * [`which::which()`](https://docs.rs/which/latest/which/fn.which.html)
+	/// 
+	pub fn which(&self, name: &str) -> Result<PathBuf, MockWhichError> {
+		let result = self.find(name).into_iter().next().ok_or_else(|| MockWhichError::CannotFindBinaryPath(name.to_owned()));
+		if let Some(context) = Context::current() {
+			let outcome = result.as_ref().map_or_else(ToString::to_string, |path| path.display().to_string());
+			context.record("which", &format!("which {name}"), &outcome);
+		}
+		result
+	}
+	
+	//		which_all															
+	/// Finds every executable with the given name, in the order in which they
+	/// would be found.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the binary.
+	/// 
+	/// # See also
+	/// 
+	/// * [`which::which_all()`](https://docs.rs/which/latest/which/fn.which_all.html)
+	/// 
+	#[must_use]
+	pub fn which_all(&self, name: &str) -> Vec<PathBuf> {
+		let found = self.find(name);
+		if let Some(context) = Context::current() {
+			context.record("which", &format!("which_all {name}"), &format!("{} found", found.len()));
+		}
+		found
+	}
+	
+	//		candidates															
+	/// Returns the paths that a binary with the given name could be at, in the
+	/// order in which they are tried.
+	fn candidates(&self, name: &str) -> Vec<String> {
+		let names = if self.extensions.is_empty() || self.extensions.iter().any(|extension| {
+			self.matcher.normalise(name).ends_with(&self.matcher.normalise(extension))
+		}) {
+			vec![name.to_owned()]
+		} else {
+			self.extensions.iter().map(|extension| format!("{name}{extension}")).collect()
+		};
+		if name.contains('/') || (self.separator == ';' && name.contains('\\')) {
+			return names;
+		}
+		self.path.iter()
+			.flat_map(|directory| names.iter().map(move |file| format!("{directory}/{file}")))
+			.collect()
+	}
+	
+	//		find																
+	/// Records a lookup, and returns the executables that match it, in order.
+	fn find(&self, name: &str) -> Vec<PathBuf> {
+		self.lookups.lock().unwrap_or_else(PoisonError::into_inner).push(name.to_owned());
+		self.candidates(name).iter()
+			.filter_map(|candidate| self.executables.iter().find(|executable| self.matcher.matches(executable, candidate)))
+			.cloned()
+			.collect()
+	}
+}
+
+//󰭅		Default																	
+impl Default for MockWhich {
+	//		default																
+	fn default() -> Self {
+		Self::new()
+	}
+}
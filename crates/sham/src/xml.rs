@@ -0,0 +1,318 @@
+//! This module provides XML helpers for the `reqwest` mock in order to test
+//! XML-based integrations.
+//! 
+//! This module provides [`create_mock_xml_response()`], which builds a response
+//! with an XML body and the matching headers, and a [`MockXmlMatcher`], which
+//! compares XML documents by their structure rather than by their text. This is
+//! important because many enterprise and SOAP-style services speak XML rather
+//! than JSON, and the XML that a client sends is hard to assert on as a string:
+//! attribute order, indentation, the choice of namespace prefixes, and whether
+//! an empty element is self-closing all vary without changing the meaning.
+//! 
+//! The approach taken is that documents are parsed, and then compared element
+//! by element. Elements match if they have the same namespace and local name,
+//! the same attributes in any order, and the same children in the same order.
+//! Comments and processing instructions are ignored, as is whitespace around
+//! text, and text that is only whitespace. When documents do not match, the
+//! matcher describes the first difference along with the path to it, so that
+//! test failures are easy to read. The body of a captured [`MockRequest`] can
+//! be checked directly.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use reqwest::StatusCode;
+//! use sham::xml::{MockXmlMatcher, create_mock_xml_response};
+//! 
+//! let matcher = MockXmlMatcher::new();
+//! assert!(matcher.matches(
+//!     r#"<order xmlns="urn:shop" id="1" currency="GBP"><item sku="A1">2</item></order>"#,
+//!     r#"
+//!         <s:order xmlns:s="urn:shop" currency="GBP" id="1">
+//!             <!-- the only item -->
+//!             <s:item sku="A1"> 2 </s:item>
+//!         </s:order>
+//!     "#,
+//! ));
+//! assert_eq!(
+//!     matcher.diff(r#"<order><item sku="A1"/></order>"#, r#"<order><item sku="B2"/></order>"#).unwrap(),
+//!     r#"At /order/item[1]: expected attribute `sku` to be "A1", found "B2""#,
+//! );
+//! 
+//! let response = create_mock_xml_response("https://api.example.com/orders/1", StatusCode::OK, "<order/>");
+//! assert_eq!(response.headers()["content-type"], "application/xml; charset=utf-8");
+//! ```
+//! 
+
+
+
+//		Packages
+
+use crate::reqwest::{MockRequest, MockResponse, create_mock_response};
+use reqwest::{IntoUrl, StatusCode};
+use roxmltree::{Document, Node};
+use std::collections::HashMap;
+
+
+
+//		Structs
+
+//		MockXmlMatcher															
+/// A matcher that compares XML documents by their structure.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MockXmlMatcher {
+	//		Public properties													
+	/// Whether whitespace around text, and text that is only whitespace, is
+	/// significant. By default, it is not.
+	pub whitespace: bool,
+}
+
+//󰭅		MockXmlMatcher															
+impl MockXmlMatcher {
+	//		new																	
+	/// Creates a new matcher that ignores insignificant whitespace.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			whitespace: false,
+		}
+	}
+	
+	//		with_significant_whitespace											
+	/// Sets whether whitespace around text, and text that is only whitespace,
+	/// is significant.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `significant` - Whether to compare whitespace.
+	/// 
+	#[must_use]
+	pub const fn with_significant_whitespace(mut self, significant: bool) -> Self {
+		self.whitespace = significant;
+		self
+	}
+	
+	//		assert_matches														
+	/// Asserts that two documents match.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `expected` - The expected document.
+	/// * `actual`   - The actual document.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the documents do not match, or cannot be
+	/// parsed, describing the first difference.
+	/// 
+	pub fn assert_matches(&self, expected: &str, actual: &str) {
+		if let Some(difference) = self.diff(expected, actual) {
+			panic!("XML does not match. {difference}");
+		}
+	}
+	
+	//		assert_request_body													
+	/// Asserts that the body of a request matches the expected document.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `request`  - The request whose body to check.
+	/// * `expected` - The expected document.
+	/// 
+	/// # Panics
+	/// 
+	/// This function will panic if the body does not match, or is missing or
+	/// cannot be parsed, describing the first difference.
+	/// 
+	pub fn assert_request_body(&self, request: &MockRequest, expected: &str) {
+		let body = request.body.as_deref().map(String::from_utf8_lossy).unwrap_or_default();
+		self.assert_matches(expected, &body);
+	}
+	
+	//		diff																
+	/// Describes the first difference between two documents, if any.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `expected` - The expected document.
+	/// * `actual`   - The actual document.
+	/// 
+	#[must_use]
+	pub fn diff(&self, expected: &str, actual: &str) -> Option<String> {
+		match Document::parse(actual) {
+			Ok(document) => self.diff_node(expected, document.root_element()),
+			Err(err)     => Some(format!("The actual XML is invalid: {err}")),
+		}
+	}
+	
+	//		matches																
+	/// Whether two documents match.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `left`  - The first document.
+	/// * `right` - The second document.
+	/// 
+	#[must_use]
+	pub fn matches(&self, left: &str, right: &str) -> bool {
+		self.diff(left, right).is_none()
+	}
+	
+	//		matches_request														
+	/// Whether the body of a request matches the given document.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `request`  - The request whose body to check.
+	/// * `expected` - The expected document.
+	/// 
+	#[must_use]
+	pub fn matches_request(&self, request: &MockRequest, expected: &str) -> bool {
+		request.body.as_deref().is_some_and(|body| self.matches(expected, &String::from_utf8_lossy(body)))
+	}
+	
+	//		children															
+	/// Returns the children of an element that take part in comparisons, which
+	/// are elements and text, with the text trimmed unless whitespace is
+	/// significant.
+	fn children<'a, 'input>(self, element: Node<'a, 'input>) -> Vec<Child<'a, 'input>> {
+		element.children().filter_map(|node| {
+			if node.is_element() {
+				return Some(Child::Element(node));
+			}
+			let text = node.text().filter(|_| node.is_text())?;
+			let kept = if self.whitespace { text } else { text.trim() };
+			(!kept.is_empty()).then_some(Child::Text(kept))
+		}).collect()
+	}
+	
+	//		diff_node															
+	/// Describes the first difference between a document and an element that
+	/// has already been parsed, such as part of a larger document, if any.
+	pub(crate) fn diff_node(self, expected: &str, actual: Node<'_, '_>) -> Option<String> {
+		let document = match Document::parse(expected) {
+			Ok(document) => document,
+			Err(err)     => return Some(format!("The expected XML is invalid: {err}")),
+		};
+		let root     = document.root_element();
+		self.diff_elements(&format!("/{}", root.tag_name().name()), root, actual)
+	}
+	
+	//		diff_elements														
+	/// Describes the first difference between two elements, if any.
+	fn diff_elements(self, path: &str, expected: Node<'_, '_>, actual: Node<'_, '_>) -> Option<String> {
+		if expected.tag_name() != actual.tag_name() {
+			return Some(format!("At {path}: expected element `{}`, found `{}`", describe(expected), describe(actual)));
+		}
+		let mut expected_attributes = expected.attributes().map(|attribute| (attribute.namespace(), attribute.name(), attribute.value())).collect::<Vec<_>>();
+		let mut actual_attributes   = actual.attributes().map(|attribute| (attribute.namespace(), attribute.name(), attribute.value())).collect::<Vec<_>>();
+		expected_attributes.sort_unstable();
+		actual_attributes.sort_unstable();
+		for &(namespace, name, value) in &expected_attributes {
+			match actual_attributes.iter().find(|attribute| attribute.0 == namespace && attribute.1 == name) {
+				None                   => return Some(format!("At {path}: expected attribute `{name}`, which is missing")),
+				Some(&(_, _, found)) if found != value => {
+					return Some(format!("At {path}: expected attribute `{name}` to be {value:?}, found {found:?}"));
+				},
+				Some(_)                => {},
+			}
+		}
+		if let Some(&(_, name, _)) = actual_attributes.iter().find(|attribute| !expected_attributes.iter().any(|other| other.0 == attribute.0 && other.1 == attribute.1)) {
+			return Some(format!("At {path}: unexpected attribute `{name}`"));
+		}
+		let expected_children = self.children(expected);
+		let actual_children   = self.children(actual);
+		for (index, (expected_child, actual_child)) in expected_children.iter().zip(&actual_children).enumerate() {
+			match (expected_child, actual_child) {
+				(&Child::Element(left), &Child::Element(right)) => {
+					let position = expected_children.iter().take(index + 1)
+						.filter(|child| matches!(**child, Child::Element(other) if other.tag_name() == left.tag_name()))
+						.count()
+					;
+					let child    = format!("{path}/{}[{position}]", left.tag_name().name());
+					if let Some(difference) = self.diff_elements(&child, left, right) {
+						return Some(difference);
+					}
+				},
+				(&Child::Text(left), &Child::Text(right)) => {
+					if left != right {
+						return Some(format!("At {path}: expected text {left:?}, found {right:?}"));
+					}
+				},
+				(left, right) => {
+					return Some(format!("At {path}: expected {}, found {}", left.describe(), right.describe()));
+				},
+			}
+		}
+		match (expected_children.get(actual_children.len()), actual_children.get(expected_children.len())) {
+			(Some(missing), _) => Some(format!("At {path}: expected {}, which is missing", missing.describe())),
+			(_, Some(extra))   => Some(format!("At {path}: unexpected {}", extra.describe())),
+			(None, None)       => None,
+		}
+	}
+}
+
+//		Child																	
+/// A child of an element that takes part in comparisons.
+#[derive(Debug)]
+enum Child<'a, 'input> {
+	/// An element.
+	Element(Node<'a, 'input>),
+	
+	/// Some text, trimmed unless whitespace is significant.
+	Text(&'a str),
+}
+
+//󰭅		Child																	
+impl Child<'_, '_> {
+	//		describe															
+	/// Describes the child for a difference message.
+	fn describe(&self) -> String {
+		match *self {
+			Self::Element(node)  => format!("element `{}`", describe(node)),
+			Self::Text(text)     => format!("text {text:?}"),
+		}
+	}
+}
+
+
+
+//		Functions
+
+//		create_mock_xml_response												
+/// Creates a mock Reqwest response with an XML body.
+/// 
+/// The content type is `application/xml; charset=utf-8`, and the content length
+/// is set to match the body.
+/// 
+/// # Parameters
+/// 
+/// * `url`    - The URL of the response.
+/// * `status` - The status code of the response.
+/// * `xml`    - The body of the response.
+/// 
+/// # Panics
+/// 
+/// This function will panic if the URL is not valid.
+/// 
+pub fn create_mock_xml_response<U: IntoUrl>(url: U, status: StatusCode, xml: &str) -> MockResponse {
+	create_mock_response(
+		url,
+		status,
+		Some("application/xml; charset=utf-8"),
+		Some(xml.len()),
+		HashMap::<String, String>::new(),
+		Ok(xml.as_bytes()),
+	)
+}
+
+//		describe																
+/// Describes the name of an element, including its namespace if it has one.
+fn describe(node: Node<'_, '_>) -> String {
+	let name = node.tag_name();
+	match name.namespace() {
+		Some(namespace) if !namespace.is_empty() => format!("{{{namespace}}}{}", name.name()),
+		_                                        => name.name().to_owned(),
+	}
+}